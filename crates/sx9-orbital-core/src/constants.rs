@@ -0,0 +1,19 @@
+//! Constants used by the propagation core
+//!
+//! `sx9-orbital-simulator` takes its copies of these from here.
+
+use core::f64::consts::PI;
+
+pub const TWO_PI: f64 = 2.0 * PI;
+
+/// Earth physical constants (WGS84)
+pub const EARTH_RADIUS_KM: f64 = 6378.137; // Equatorial radius
+pub const EARTH_MU: f64 = 398600.4418; // Gravitational parameter km³/s²
+pub const EARTH_J2: f64 = 1.08262668e-3; // Second zonal harmonic
+
+/// Smallest |1 - 5cos²i| for Brouwer theory
+pub const BROUWER_CRITICAL_MARGIN: f64 = 0.05;
+
+/// Kepler solver
+pub const KEPLER_ITERATION_LIMIT: usize = 50; // Maximum iterations for Kepler's equation
+pub const KEPLER_TOLERANCE: f64 = 1e-12; // Convergence tolerance
@@ -0,0 +1,560 @@
+//! Allocation-free propagation core for embedded flight software
+//!
+//! Element conversion, the Kepler solver and secular J2 propagation, written
+//! against `core` and `libm` only: no heap, no chrono, no `std` math. Time is
+//! seconds since the element epoch. The crate is `#![no_std]`, so the same
+//! code builds for FSO terminal processors; `sx9-orbital-simulator`
+//! re-exports it as `propagation_core` and its propagators wrap it with
+//! timestamps, tracing and crate errors.
+
+#![cfg_attr(not(test), no_std)]
+
+pub mod constants;
+
+use crate::constants::{
+    BROUWER_CRITICAL_MARGIN, EARTH_J2, EARTH_MU, EARTH_RADIUS_KM, KEPLER_ITERATION_LIMIT,
+    KEPLER_TOLERANCE, TWO_PI,
+};
+use core::f64::consts::PI;
+use core::fmt;
+use libm::{acos, asin, atan, atan2, cos, fabs, fmod, sin, sqrt, tan};
+
+/// Classical orbital elements in radians
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Elements {
+    pub semi_major_axis_km: f64,
+    pub eccentricity: f64,
+    pub inclination_rad: f64,
+    pub raan_rad: f64,
+    pub argument_of_perigee_rad: f64,
+    pub mean_anomaly_rad: f64,
+}
+
+/// Inertial position and velocity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateVector {
+    pub position_km: [f64; 3],
+    pub velocity_km_s: [f64; 3],
+}
+
+/// Secular element rates due to J2 in rad/s
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct J2Rates {
+    pub raan_rad_s: f64,
+    pub argument_of_perigee_rad_s: f64,
+    /// Mean motion including the J2 correction
+    pub mean_anomaly_rad_s: f64,
+}
+
+/// Failure in the propagation core
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreError {
+    /// Kepler's equation did not converge within the iteration limit
+    KeplerNoConvergence,
+    /// Only closed orbits (0 <= e < 1, a > 0) are supported
+    UnboundOrbit,
+    /// Brouwer's long-period terms diverge near the critical inclination
+    CriticalInclination,
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeplerNoConvergence => write!(f, "Kepler's equation failed to converge"),
+            Self::UnboundOrbit => write!(f, "orbit is not closed"),
+            Self::CriticalInclination => {
+                write!(
+                    f,
+                    "mean elements are singular near the critical inclination"
+                )
+            }
+        }
+    }
+}
+
+/// Mean motion in rad/s
+pub fn mean_motion(semi_major_axis_km: f64) -> f64 {
+    sqrt(EARTH_MU / (semi_major_axis_km * semi_major_axis_km * semi_major_axis_km))
+}
+
+/// Solve Kepler's equation M = E - e sin(E) for the eccentric anomaly
+pub fn solve_kepler(mean_anomaly_rad: f64, eccentricity: f64) -> Result<f64, CoreError> {
+    let mut eccentric_anomaly = mean_anomaly_rad;
+    for _ in 0..KEPLER_ITERATION_LIMIT {
+        let delta = (mean_anomaly_rad - eccentric_anomaly + eccentricity * sin(eccentric_anomaly))
+            / (1.0 - eccentricity * cos(eccentric_anomaly));
+        eccentric_anomaly += delta;
+        if fabs(delta) < KEPLER_TOLERANCE {
+            return Ok(eccentric_anomaly);
+        }
+    }
+    Err(CoreError::KeplerNoConvergence)
+}
+
+pub fn eccentric_to_true_anomaly(eccentric_anomaly_rad: f64, eccentricity: f64) -> f64 {
+    let factor = sqrt((1.0 + eccentricity) / (1.0 - eccentricity));
+    2.0 * atan(factor * tan(eccentric_anomaly_rad / 2.0))
+}
+
+pub fn true_to_mean_anomaly(true_anomaly_rad: f64, eccentricity: f64) -> f64 {
+    let eccentric_anomaly = 2.0
+        * atan2(
+            sqrt(1.0 - eccentricity) * sin(true_anomaly_rad / 2.0),
+            sqrt(1.0 + eccentricity) * cos(true_anomaly_rad / 2.0),
+        );
+    eccentric_anomaly - eccentricity * sin(eccentric_anomaly)
+}
+
+/// Position and velocity from elements at their own epoch
+pub fn elements_to_state(elements: &Elements) -> Result<StateVector, CoreError> {
+    check_closed(elements)?;
+    let e = elements.eccentricity;
+    let eccentric_anomaly = solve_kepler(fmod(elements.mean_anomaly_rad, TWO_PI), e)?;
+    let true_anomaly = eccentric_to_true_anomaly(eccentric_anomaly, e);
+
+    let p = elements.semi_major_axis_km * (1.0 - e * e);
+    let radius = p / (1.0 + e * cos(true_anomaly));
+    let speed_factor = sqrt(EARTH_MU / p);
+    let position = [radius * cos(true_anomaly), radius * sin(true_anomaly), 0.0];
+    let velocity = [
+        -speed_factor * sin(true_anomaly),
+        speed_factor * (e + cos(true_anomaly)),
+        0.0,
+    ];
+
+    let rotation = perifocal_to_eci(elements);
+    Ok(StateVector {
+        position_km: rotate(&rotation, position),
+        velocity_km_s: rotate(&rotation, velocity),
+    })
+}
+
+/// Osculating elements from an inertial state
+///
+/// Equatorial and circular orbits place the undefined angles at zero and fold
+/// them into the remaining angles.
+pub fn state_to_elements(state: &StateVector) -> Result<Elements, CoreError> {
+    let r = state.position_km;
+    let v = state.velocity_km_s;
+    let radius = norm(r);
+    let speed = norm(v);
+
+    let energy = speed * speed / 2.0 - EARTH_MU / radius;
+    if energy >= 0.0 {
+        return Err(CoreError::UnboundOrbit);
+    }
+    let semi_major_axis_km = -EARTH_MU / (2.0 * energy);
+
+    let h = cross(r, v);
+    let h_norm = norm(h);
+    let node = [-h[1], h[0], 0.0];
+    let node_norm = norm(node);
+    let r_dot_v = dot(r, v);
+    let eccentricity_vector =
+        [0, 1, 2].map(|i| ((speed * speed - EARTH_MU / radius) * r[i] - r_dot_v * v[i]) / EARTH_MU);
+    let eccentricity = norm(eccentricity_vector);
+    let inclination_rad = acos(clamp_unit(h[2] / h_norm));
+
+    const SMALL: f64 = 1e-11;
+    let equatorial = node_norm < SMALL * h_norm;
+    let circular = eccentricity < SMALL;
+
+    let raan_rad = if equatorial {
+        0.0
+    } else {
+        normalize(atan2(node[1], node[0]))
+    };
+    // Reference direction for perigee and anomaly: node line, or x-axis
+    let reference = if equatorial {
+        [1.0, 0.0, 0.0]
+    } else {
+        [node[0] / node_norm, node[1] / node_norm, 0.0]
+    };
+    let in_plane = cross(scale(h, 1.0 / h_norm), reference);
+    let angle_from_reference =
+        |vector: [f64; 3]| normalize(atan2(dot(vector, in_plane), dot(vector, reference)));
+
+    let (argument_of_perigee_rad, true_anomaly) = if circular {
+        (0.0, angle_from_reference(r))
+    } else {
+        let argument_of_perigee = angle_from_reference(eccentricity_vector);
+        let true_anomaly = normalize(angle_from_reference(r) - argument_of_perigee);
+        (argument_of_perigee, true_anomaly)
+    };
+
+    Ok(Elements {
+        semi_major_axis_km,
+        eccentricity,
+        inclination_rad,
+        raan_rad,
+        argument_of_perigee_rad,
+        mean_anomaly_rad: normalize(true_to_mean_anomaly(true_anomaly, eccentricity)),
+    })
+}
+
+/// Secular J2 drift of the node, perigee and mean anomaly
+pub fn j2_secular_rates(elements: &Elements) -> J2Rates {
+    let e2 = elements.eccentricity * elements.eccentricity;
+    let p = elements.semi_major_axis_km * (1.0 - e2);
+    let n = mean_motion(elements.semi_major_axis_km);
+    let factor = 1.5 * n * EARTH_J2 * (EARTH_RADIUS_KM / p) * (EARTH_RADIUS_KM / p);
+    let cos_i = cos(elements.inclination_rad);
+    let sin_i2 = 1.0 - cos_i * cos_i;
+
+    J2Rates {
+        raan_rad_s: -factor * cos_i,
+        argument_of_perigee_rad_s: factor * (2.0 - 2.5 * sin_i2),
+        mean_anomaly_rad_s: n + factor * sqrt(1.0 - e2) * (1.0 - 1.5 * sin_i2),
+    }
+}
+
+/// Two-body propagation by `dt_seconds` from the element epoch
+pub fn propagate_kepler(elements: &Elements, dt_seconds: f64) -> Result<StateVector, CoreError> {
+    check_closed(elements)?;
+    elements_to_state(&Elements {
+        mean_anomaly_rad: elements.mean_anomaly_rad
+            + mean_motion(elements.semi_major_axis_km) * dt_seconds,
+        ..*elements
+    })
+}
+
+/// Two-body propagation with secular J2 node, perigee and mean-motion drift
+pub fn propagate_j2(elements: &Elements, dt_seconds: f64) -> Result<StateVector, CoreError> {
+    check_closed(elements)?;
+    let rates = j2_secular_rates(elements);
+    elements_to_state(&Elements {
+        raan_rad: elements.raan_rad + rates.raan_rad_s * dt_seconds,
+        argument_of_perigee_rad: elements.argument_of_perigee_rad
+            + rates.argument_of_perigee_rad_s * dt_seconds,
+        mean_anomaly_rad: elements.mean_anomaly_rad + rates.mean_anomaly_rad_s * dt_seconds,
+        ..*elements
+    })
+}
+
+/// Osculating elements for Brouwer mean elements under J2
+///
+/// First-order short- and long-period terms in Lyddane's form: eccentricity
+/// and mean anomaly are corrected together through (e cos M, e sin M), and
+/// inclination and node through (sin(i/2) cos Ω, sin(i/2) sin Ω), so circular
+/// and equatorial orbits stay finite. Within `BROUWER_CRITICAL_MARGIN` of the
+/// critical inclination the long-period terms diverge and the orbit is
+/// refused.
+pub fn mean_to_osculating(elements: &Elements) -> Result<Elements, CoreError> {
+    check_closed(elements)?;
+    let a = elements.semi_major_axis_km;
+    let e = elements.eccentricity;
+    let inclination = elements.inclination_rad;
+    let raan = elements.raan_rad;
+    let g = elements.argument_of_perigee_rad;
+    let m = elements.mean_anomaly_rad;
+
+    let c2 = cos(inclination) * cos(inclination);
+    let s2 = 1.0 - c2;
+    let critical = 1.0 - 5.0 * c2;
+    if fabs(critical) < BROUWER_CRITICAL_MARGIN {
+        return Err(CoreError::CriticalInclination);
+    }
+    let cos_i = cos(inclination);
+    let sin_i = sin(inclination);
+
+    let e2 = e * e;
+    let eta = sqrt(1.0 - e2);
+    let eta2 = eta * eta;
+    let eta3 = eta2 * eta;
+    let eta6 = eta3 * eta3;
+    let gamma = 0.5 * EARTH_J2 * (EARTH_RADIUS_KM / a) * (EARTH_RADIUS_KM / a);
+    let gamma_p = gamma / (eta2 * eta2);
+
+    let f = eccentric_to_true_anomaly(solve_kepler(m, e)?, e);
+    let (sin_f, cos_f) = (sin(f), cos(f));
+    let a_r = (1.0 + e * cos_f) / eta2;
+    let a_r3 = a_r * a_r * a_r;
+    // Equation of the centre plus e sin f
+    let centre = wrap_signed(f - m) + e * sin_f;
+    let (sin_2g, cos_2g) = (sin(2.0 * g), cos(2.0 * g));
+    let short_cos =
+        3.0 * cos(2.0 * g + 2.0 * f) + 3.0 * e * cos(2.0 * g + f) + e * cos(2.0 * g + 3.0 * f);
+    let short_sin =
+        3.0 * sin(2.0 * g + 2.0 * f) + 3.0 * e * sin(2.0 * g + f) + e * sin(2.0 * g + 3.0 * f);
+
+    let a_osc = a + a
+        * gamma
+        * ((3.0 * c2 - 1.0) * (a_r3 - 1.0 / eta3) + 3.0 * s2 * a_r3 * cos(2.0 * g + 2.0 * f));
+
+    let long_period = 1.0 - 11.0 * c2 - 40.0 * c2 * c2 / critical;
+    let de_long = gamma_p / 8.0 * e * eta2 * long_period * cos_2g;
+    let cos_series = 3.0 * cos_f + 3.0 * e * cos_f * cos_f + e2 * cos_f * cos_f * cos_f;
+    let de = de_long
+        + eta2 / 2.0
+            * (gamma
+                * ((3.0 * c2 - 1.0) / eta6 * (e * eta + e / (1.0 + eta) + cos_series)
+                    + 3.0 * s2 / eta6 * (e + cos_series) * cos(2.0 * g + 2.0 * f))
+                - gamma_p * s2 * (3.0 * cos(2.0 * g + f) + cos(2.0 * g + 3.0 * f)));
+
+    // The long-period part vanishes with sin²i, so equatorial orbits take its limit of zero
+    let di_long = if fabs(sin_i) < 1e-12 {
+        0.0
+    } else {
+        -e * de_long * cos_i / (eta2 * sin_i)
+    };
+    let di = di_long + gamma_p / 2.0 * cos_i * sqrt(s2) * short_cos;
+
+    let draan = -gamma_p / 8.0
+        * e2
+        * cos_i
+        * (11.0 + 80.0 * c2 / critical + 200.0 * c2 * c2 / (critical * critical))
+        * sin_2g
+        - gamma_p / 2.0 * cos_i * (6.0 * centre - short_sin);
+    let longitude = m + g + raan + gamma_p / 8.0 * eta3 * long_period * sin_2g
+        - gamma_p / 16.0
+            * (2.0 + e2
+                - 11.0 * (2.0 + 3.0 * e2) * c2
+                - 40.0 * (2.0 + 5.0 * e2) * c2 * c2 / critical
+                - 400.0 * e2 * c2 * c2 * c2 / (critical * critical))
+            * sin_2g
+        + gamma_p / 4.0 * (-6.0 * critical * centre + (3.0 - 5.0 * c2) * short_sin)
+        + draan;
+
+    let a_r_eta2 = a_r * eta * a_r * eta;
+    let e_dm = gamma_p / 8.0 * e * eta3 * long_period * sin_2g
+        - gamma_p / 4.0
+            * eta3
+            * (2.0 * (3.0 * c2 - 1.0) * (a_r_eta2 + a_r + 1.0) * sin_f
+                + 3.0
+                    * s2
+                    * ((-a_r_eta2 - a_r + 1.0) * sin(2.0 * g + f)
+                        + (a_r_eta2 + a_r + 1.0 / 3.0) * sin(2.0 * g + 3.0 * f)));
+
+    let d1 = (e + de) * sin(m) + e_dm * cos(m);
+    let d2 = (e + de) * cos(m) - e_dm * sin(m);
+    let half_sin = sin(inclination / 2.0) + cos(inclination / 2.0) * di / 2.0;
+    let node_shift = sin(inclination / 2.0) * draan;
+    let d3 = half_sin * sin(raan) + node_shift * cos(raan);
+    let d4 = half_sin * cos(raan) - node_shift * sin(raan);
+
+    let mean_anomaly_rad = atan2(d1, d2);
+    let raan_rad = atan2(d3, d4);
+    Ok(Elements {
+        semi_major_axis_km: a_osc,
+        eccentricity: sqrt(d1 * d1 + d2 * d2),
+        inclination_rad: 2.0 * asin(clamp_unit(sqrt(d3 * d3 + d4 * d4))),
+        raan_rad: normalize(raan_rad),
+        argument_of_perigee_rad: normalize(longitude - mean_anomaly_rad - raan_rad),
+        mean_anomaly_rad: normalize(mean_anomaly_rad),
+    })
+}
+
+fn check_closed(elements: &Elements) -> Result<(), CoreError> {
+    if elements.semi_major_axis_km > 0.0 && (0.0..1.0).contains(&elements.eccentricity) {
+        Ok(())
+    } else {
+        Err(CoreError::UnboundOrbit)
+    }
+}
+
+/// Rows of the perifocal-to-ECI rotation
+fn perifocal_to_eci(elements: &Elements) -> [[f64; 3]; 3] {
+    let (sin_raan, cos_raan) = (sin(elements.raan_rad), cos(elements.raan_rad));
+    let (sin_inc, cos_inc) = (sin(elements.inclination_rad), cos(elements.inclination_rad));
+    let (sin_arg, cos_arg) = (
+        sin(elements.argument_of_perigee_rad),
+        cos(elements.argument_of_perigee_rad),
+    );
+
+    [
+        [
+            cos_raan * cos_arg - sin_raan * sin_arg * cos_inc,
+            -cos_raan * sin_arg - sin_raan * cos_arg * cos_inc,
+            sin_raan * sin_inc,
+        ],
+        [
+            sin_raan * cos_arg + cos_raan * sin_arg * cos_inc,
+            -sin_raan * sin_arg + cos_raan * cos_arg * cos_inc,
+            -cos_raan * sin_inc,
+        ],
+        [sin_arg * sin_inc, cos_arg * sin_inc, cos_inc],
+    ]
+}
+
+fn rotate(rows: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    rows.map(|row| dot(row, vector))
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    sqrt(dot(a, a))
+}
+
+fn scale(a: [f64; 3], factor: f64) -> [f64; 3] {
+    [a[0] * factor, a[1] * factor, a[2] * factor]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn clamp_unit(value: f64) -> f64 {
+    value.clamp(-1.0, 1.0)
+}
+
+/// Wrap an angle into [0, 2π)
+fn normalize(angle_rad: f64) -> f64 {
+    let wrapped = fmod(angle_rad, TWO_PI);
+    if wrapped < 0.0 {
+        wrapped + TWO_PI
+    } else {
+        wrapped
+    }
+}
+
+/// Wrap an angle into [-π, π)
+fn wrap_signed(angle_rad: f64) -> f64 {
+    normalize(angle_rad + PI) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const DEG_TO_RAD: f64 = PI / 180.0;
+
+    fn elements() -> Elements {
+        Elements {
+            semi_major_axis_km: 7000.0,
+            eccentricity: 0.05,
+            inclination_rad: 55.0 * DEG_TO_RAD,
+            raan_rad: 40.0 * DEG_TO_RAD,
+            argument_of_perigee_rad: 30.0 * DEG_TO_RAD,
+            mean_anomaly_rad: 100.0 * DEG_TO_RAD,
+        }
+    }
+
+    #[test]
+    fn test_element_state_round_trip() {
+        let original = elements();
+        let recovered = state_to_elements(&elements_to_state(&original).unwrap()).unwrap();
+
+        assert!((recovered.semi_major_axis_km - original.semi_major_axis_km).abs() < 1e-6);
+        assert!((recovered.eccentricity - original.eccentricity).abs() < 1e-10);
+        for (a, b) in [
+            (recovered.inclination_rad, original.inclination_rad),
+            (recovered.raan_rad, original.raan_rad),
+            (
+                recovered.argument_of_perigee_rad,
+                original.argument_of_perigee_rad,
+            ),
+            (recovered.mean_anomaly_rad, original.mean_anomaly_rad),
+        ] {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+
+        let hyperbolic = StateVector {
+            position_km: [7000.0, 0.0, 0.0],
+            velocity_km_s: [0.0, 12.0, 0.0],
+        };
+        assert_eq!(state_to_elements(&hyperbolic), Err(CoreError::UnboundOrbit));
+    }
+
+    #[test]
+    fn test_j2_propagation() {
+        let elements = elements();
+        let day = 86400.0;
+
+        // LEO at 55° regresses about 4° per day
+        let rates = j2_secular_rates(&elements);
+        let raan_deg_per_day = rates.raan_rad_s * day / DEG_TO_RAD;
+        assert!(raan_deg_per_day < -3.5 && raan_deg_per_day > -5.0);
+
+        let kepler = state_to_elements(&propagate_kepler(&elements, day).unwrap()).unwrap();
+        assert!((kepler.raan_rad - elements.raan_rad).abs() < 1e-9);
+
+        let drifted = state_to_elements(&propagate_j2(&elements, day).unwrap()).unwrap();
+        assert!((drifted.raan_rad - (elements.raan_rad + rates.raan_rad_s * day)).abs() < 1e-9);
+        // Secular drift leaves the orbit's size and shape unchanged
+        assert!((drifted.semi_major_axis_km - elements.semi_major_axis_km).abs() < 1e-6);
+        assert!((drifted.eccentricity - elements.eccentricity).abs() < 1e-10);
+    }
+
+    fn angle_difference(a: f64, b: f64) -> f64 {
+        fabs((a - b + core::f64::consts::PI).rem_euclid(TWO_PI) - core::f64::consts::PI)
+    }
+
+    proptest! {
+        // Away from the singular equatorial and circular cases every element
+        // comes back: the semi-major axis to 1e-9 relative, eccentricity to
+        // 1e-9 and the angles to 1e-7 rad
+        #[test]
+        fn test_keplerian_cartesian_round_trip(
+            semi_major_axis_km in 6_600.0f64..50_000.0,
+            eccentricity in 1e-3f64..0.9,
+            inclination_deg in 0.5f64..179.5,
+            raan_rad in 0.0f64..TWO_PI,
+            argument_of_perigee_rad in 0.0f64..TWO_PI,
+            mean_anomaly_rad in 0.0f64..TWO_PI,
+        ) {
+            let original = Elements {
+                semi_major_axis_km,
+                eccentricity,
+                inclination_rad: inclination_deg * DEG_TO_RAD,
+                raan_rad,
+                argument_of_perigee_rad,
+                mean_anomaly_rad,
+            };
+            let recovered = state_to_elements(&elements_to_state(&original).unwrap()).unwrap();
+
+            let relative_size_error =
+                fabs(recovered.semi_major_axis_km - semi_major_axis_km) / semi_major_axis_km;
+            prop_assert!(relative_size_error < 1e-9);
+            prop_assert!(fabs(recovered.eccentricity - eccentricity) < 1e-9);
+            for (recovered, original) in [
+                (recovered.inclination_rad, original.inclination_rad),
+                (recovered.raan_rad, raan_rad),
+                (recovered.argument_of_perigee_rad, argument_of_perigee_rad),
+                (recovered.mean_anomaly_rad, mean_anomaly_rad),
+            ] {
+                prop_assert!(
+                    angle_difference(recovered, original) < 1e-7,
+                    "{} != {}",
+                    recovered,
+                    original
+                );
+            }
+        }
+
+        // Including circular and equatorial orbits, the state survives a
+        // trip through elements to 1e-6 km and 1e-9 km/s
+        #[test]
+        fn test_cartesian_keplerian_round_trip(
+            semi_major_axis_km in 6_600.0f64..50_000.0,
+            eccentricity in 0.0f64..0.9,
+            inclination_rad in 0.0f64..=core::f64::consts::PI,
+            raan_rad in 0.0f64..TWO_PI,
+            argument_of_perigee_rad in 0.0f64..TWO_PI,
+            mean_anomaly_rad in 0.0f64..TWO_PI,
+        ) {
+            let state = elements_to_state(&Elements {
+                semi_major_axis_km,
+                eccentricity,
+                inclination_rad,
+                raan_rad,
+                argument_of_perigee_rad,
+                mean_anomaly_rad,
+            })
+            .unwrap();
+            let recovered = elements_to_state(&state_to_elements(&state).unwrap()).unwrap();
+
+            for i in 0..3 {
+                prop_assert!(fabs(recovered.position_km[i] - state.position_km[i]) < 1e-6);
+                prop_assert!(fabs(recovered.velocity_km_s[i] - state.velocity_km_s[i]) < 1e-9);
+            }
+        }
+    }
+}
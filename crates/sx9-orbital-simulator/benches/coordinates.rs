@@ -0,0 +1,46 @@
+//! ECI -> topocentric transform throughput
+//!
+//! Compare the per-position loop with the batched path:
+//! `cargo bench --bench coordinates` against
+//! `cargo bench --bench coordinates --features simd`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ctas7_orbital_mechanics::{Position3D, TopocentricFrame};
+
+fn positions(count: usize) -> Vec<Position3D> {
+    (0..count)
+        .map(|i| {
+            let angle = i as f64 * 0.001;
+            Position3D::new(
+                14378.0 * angle.cos(),
+                14378.0 * angle.sin(),
+                8000.0 * (angle * 3.0).sin(),
+            )
+        })
+        .collect()
+}
+
+fn topocentric_transforms(c: &mut Criterion) {
+    let frame = TopocentricFrame::new(40.0, -105.0, 1600.0);
+    let mut group = c.benchmark_group("eci_to_topocentric");
+
+    for count in [1_024, 65_536] {
+        let positions = positions(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::new("scalar", count), &positions, |b, p| {
+            b.iter(|| {
+                p.iter()
+                    .map(|position| frame.transform(black_box(position)).elevation_deg())
+                    .collect::<Vec<f64>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("batch", count), &positions, |b, p| {
+            b.iter(|| frame.elevations_deg(black_box(p)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, topocentric_transforms);
+criterion_main!(benches);
@@ -63,6 +63,10 @@ fn main() -> Result<()> {
             longitude_deg: -105.0,
             elevation_m: 1600.0,
         },
+        availability: Default::default(),
+        mount: None,
+        min_elevation_deg: None,
+        timezone: None,
     };
     engine_with_station.add_ground_station(station);
 
@@ -0,0 +1,36 @@
+//! Constellation config loader fuzz target
+//!
+//! The first byte picks JSON, YAML or TOML and the rest is the document.
+//! Parsing, migration and building the engine from whatever parses must all
+//! return errors rather than panic.
+//!
+//! ```text
+//! cargo +nightly fuzz run constellation_config
+//! ```
+
+#![no_main]
+
+use ctas7_orbital_mechanics::config::{migrate_constellation_config, parse_constellation_config};
+use ctas7_orbital_mechanics::{ConfigFormat, ConfigMigrator, OrbitalMechanicsEngine};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, document)) = data.split_first() else {
+        return;
+    };
+    let Ok(content) = std::str::from_utf8(document) else {
+        return;
+    };
+    let format = match selector % 3 {
+        0 => ConfigFormat::Json,
+        1 => ConfigFormat::Yaml,
+        _ => ConfigFormat::Toml,
+    };
+
+    if let Ok(config) = parse_constellation_config(content, format) {
+        let _ = OrbitalMechanicsEngine::with_config(config);
+    }
+    if let Ok((config, _)) = migrate_constellation_config(content, format, &ConfigMigrator::new()) {
+        let _ = OrbitalMechanicsEngine::with_config(config);
+    }
+});
@@ -0,0 +1,29 @@
+//! TLE parser fuzz target
+//!
+//! Any text must parse to a result without panicking, and every set that
+//! parses and formats must parse again.
+//!
+//! ```text
+//! cargo +nightly fuzz run tle_parser
+//! ```
+
+#![no_main]
+
+use ctas7_orbital_mechanics::{parse_tles, Tle};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(tles) = parse_tles(text) else {
+        return;
+    };
+
+    for tle in tles {
+        let _ = tle.to_orbit("FUZZ-01".to_string());
+        if let Ok(formatted) = tle.format() {
+            Tle::parse(&formatted).expect("formatted TLE parses");
+        }
+    }
+});
@@ -0,0 +1,138 @@
+//! Optical terminal spatial acquisition
+//!
+//! Before a laser link closes, the terminal must find its partner inside an
+//! uncertainty cone set by ephemeris error at the link range and by its own
+//! open-loop pointing error. The cone is covered by a spiral scan outward
+//! from the predicted direction, one beam-width cell per dwell, and repeated
+//! if the partner is missed. The partner's true direction is taken as a
+//! circular Gaussian about the prediction, so the chance of having swept over
+//! it grows with the area scanned so far.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Uncertainty cone and scan pattern of an optical terminal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcquisitionModel {
+    /// 1-sigma cross-line-of-sight ephemeris error of the partner
+    pub ephemeris_error_m: f64,
+    /// 1-sigma open-loop pointing error of the terminal
+    pub pointing_error_urad: f64,
+    /// Full beam divergence used while scanning
+    pub beam_divergence_urad: f64,
+    /// Time spent on each scan cell
+    pub dwell_seconds: f64,
+    /// Cone radius scanned, in sigma
+    pub coverage_sigma: f64,
+    /// Fraction of a beam width shared by neighbouring cells
+    pub cell_overlap: f64,
+}
+
+impl AcquisitionModel {
+    /// Scan a 3-sigma cone with cells overlapping by 15%
+    pub fn new(
+        ephemeris_error_m: f64,
+        pointing_error_urad: f64,
+        beam_divergence_urad: f64,
+        dwell_seconds: f64,
+    ) -> Result<Self> {
+        let model = Self {
+            ephemeris_error_m,
+            pointing_error_urad,
+            beam_divergence_urad,
+            dwell_seconds,
+            coverage_sigma: 3.0,
+            cell_overlap: 0.15,
+        };
+        let valid = ephemeris_error_m >= 0.0
+            && pointing_error_urad >= 0.0
+            && beam_divergence_urad > 0.0
+            && dwell_seconds > 0.0;
+        if !valid {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Invalid acquisition model: ephemeris error {} m, pointing error {} µrad, \
+                 beam {} µrad, dwell {} s",
+                ephemeris_error_m, pointing_error_urad, beam_divergence_urad, dwell_seconds
+            )));
+        }
+        Ok(model)
+    }
+
+    /// 1-sigma angular uncertainty of the partner direction at `range_km`
+    pub fn sigma_urad(&self, range_km: f64) -> f64 {
+        let ephemeris_urad = self.ephemeris_error_m / (range_km * 1000.0) * 1e6;
+        ephemeris_urad.hypot(self.pointing_error_urad)
+    }
+
+    /// Half-angle of the scanned uncertainty cone at `range_km`
+    pub fn uncertainty_cone_urad(&self, range_km: f64) -> f64 {
+        self.coverage_sigma * self.sigma_urad(range_km)
+    }
+
+    /// Cells in one scan of the cone
+    pub fn scan_cells(&self, range_km: f64) -> usize {
+        let cone = self.uncertainty_cone_urad(range_km);
+        let cells = std::f64::consts::PI * cone * cone / self.cell_step_urad().powi(2);
+        (cells.ceil() as usize).max(1)
+    }
+
+    /// Time for one full scan of the cone
+    pub fn scan_time_seconds(&self, range_km: f64) -> f64 {
+        self.scan_cells(range_km) as f64 * self.dwell_seconds
+    }
+
+    /// Probability the partner has been found within `seconds` of starting the scan
+    pub fn probability_within(&self, range_km: f64, seconds: f64) -> f64 {
+        if seconds <= 0.0 {
+            return 0.0;
+        }
+        let sigma = self.sigma_urad(range_km);
+        if sigma == 0.0 {
+            return 1.0;
+        }
+
+        let scan_time = self.scan_time_seconds(range_km);
+        let full_scans = (seconds / scan_time).floor();
+        let partial_seconds = seconds - full_scans * scan_time;
+        let miss_per_scan = (-0.5 * self.coverage_sigma * self.coverage_sigma).exp();
+
+        // Radius swept so far in the current spiral, from the cell area covered
+        let cone = self.uncertainty_cone_urad(range_km);
+        let swept_area = partial_seconds / self.dwell_seconds * self.cell_step_urad().powi(2);
+        let swept_radius_sq = (swept_area / std::f64::consts::PI).min(cone * cone);
+        let miss_partial = (-swept_radius_sq / (2.0 * sigma * sigma)).exp();
+
+        1.0 - miss_per_scan.powf(full_scans) * miss_partial
+    }
+
+    fn cell_step_urad(&self) -> f64 {
+        self.beam_divergence_urad * (1.0 - self.cell_overlap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquisition_probability() {
+        let model = AcquisitionModel::new(100.0, 20.0, 10.0, 0.05).unwrap();
+
+        // 100 m at 10,000 km is 10 µrad, added in quadrature to the pointing error
+        assert!((model.sigma_urad(10_000.0) - 500f64.sqrt()).abs() < 1e-9);
+        assert!(model.uncertainty_cone_urad(2_000.0) > model.uncertainty_cone_urad(10_000.0));
+        let scan_time = model.scan_time_seconds(10_000.0);
+        assert_eq!(model.scan_cells(10_000.0), 196);
+
+        assert_eq!(model.probability_within(10_000.0, 0.0), 0.0);
+        let half = model.probability_within(10_000.0, 0.5 * scan_time);
+        let one = model.probability_within(10_000.0, scan_time);
+        let two = model.probability_within(10_000.0, 2.0 * scan_time);
+        assert!(half > 0.5 && half < one);
+        assert!((one - (1.0 - (-4.5f64).exp())).abs() < 1e-9);
+        assert!(two > one && two < 1.0);
+
+        assert!(AcquisitionModel::new(100.0, 20.0, 0.0, 0.05).is_err());
+        assert!(AcquisitionModel::new(-1.0, 20.0, 10.0, 0.05).is_err());
+    }
+}
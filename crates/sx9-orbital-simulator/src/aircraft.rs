@@ -0,0 +1,457 @@
+//! Aircraft deconfliction for optical uplinks
+//!
+//! Tracks aircraft from ADS-B position reports, read from a recorded track
+//! file or, with the `online` feature, polled from a live receiver feed.
+//! Each tick the simulator dead-reckons every tracked aircraft over a short
+//! look-ahead and predicts when it will enter a protection cone around an
+//! uplink beam, so the uplink can be shuttered before the aircraft arrives.
+//! The beam is held at its current pointing over the look-ahead; MEO uplinks
+//! slew far more slowly than an aircraft crosses a cone of a few degrees.
+
+use crate::constants::EARTH_RADIUS_KM;
+use crate::error::{Result, ResultExt};
+use crate::ground_station::GroundStation;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const FEET_TO_M: f64 = 0.3048;
+const KNOTS_TO_M_S: f64 = 1852.0 / 3600.0;
+
+/// One ADS-B position report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AircraftReport {
+    /// ICAO 24-bit address in hex
+    pub icao24: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callsign: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    /// Altitude above mean sea level
+    pub altitude_m: f64,
+    #[serde(default)]
+    pub ground_speed_m_s: f64,
+    /// Course over ground, clockwise from north
+    #[serde(default)]
+    pub track_deg: f64,
+    #[serde(default)]
+    pub vertical_rate_m_s: f64,
+}
+
+/// Latest reports per aircraft
+#[derive(Debug, Clone)]
+pub struct AircraftTracker {
+    reports: HashMap<String, Vec<AircraftReport>>,
+    /// Reports older than this are not extrapolated
+    pub max_report_age_seconds: f64,
+}
+
+/// Protection cone and look-ahead for uplink deconfliction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeconflictionConfig {
+    /// Half-angle of the cone around the beam that aircraft must not enter
+    pub protection_cone_deg: f64,
+    /// How far ahead conjunctions are predicted
+    pub look_ahead_seconds: f64,
+    pub step_seconds: f64,
+    /// Aircraft farther from the station are beyond the beam's hazard range
+    pub max_range_km: f64,
+}
+
+/// Predicted entry of an aircraft into an uplink protection cone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AircraftConjunction {
+    pub icao24: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callsign: Option<String>,
+    pub station_id: String,
+    pub conjunction_time: DateTime<Utc>,
+    /// Time from the prediction to cone entry; zero if already inside
+    pub lead_time_seconds: f64,
+    /// Angle between the beam and the aircraft at cone entry
+    pub separation_deg: f64,
+    pub range_km: f64,
+}
+
+impl AircraftReport {
+    /// Dead-reckoned position at `time` along the reported track
+    pub fn extrapolate(&self, time: DateTime<Utc>) -> Self {
+        let dt = (time - self.timestamp).num_milliseconds() as f64 / 1000.0;
+        let distance_km = self.ground_speed_m_s * dt / 1000.0;
+        let track = self.track_deg.to_radians();
+        let north_km = distance_km * track.cos();
+        let east_km = distance_km * track.sin();
+        Self {
+            timestamp: time,
+            latitude_deg: self.latitude_deg + (north_km / EARTH_RADIUS_KM).to_degrees(),
+            longitude_deg: self.longitude_deg
+                + (east_km / (EARTH_RADIUS_KM * self.latitude_deg.to_radians().cos())).to_degrees(),
+            altitude_m: self.altitude_m + self.vertical_rate_m_s * dt,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for AircraftTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AircraftTracker {
+    /// Tracker that extrapolates reports up to 30 s old
+    pub fn new() -> Self {
+        Self {
+            reports: HashMap::new(),
+            max_report_age_seconds: 30.0,
+        }
+    }
+
+    /// Add a report; a report at an existing timestamp replaces it
+    pub fn ingest(&mut self, report: AircraftReport) {
+        let history = self.reports.entry(report.icao24.clone()).or_default();
+        match history.binary_search_by_key(&report.timestamp, |r| r.timestamp) {
+            Ok(index) => history[index] = report,
+            Err(index) => history.insert(index, report),
+        }
+    }
+
+    pub fn extend(&mut self, reports: impl IntoIterator<Item = AircraftReport>) {
+        for report in reports {
+            self.ingest(report);
+        }
+    }
+
+    /// Read a recorded track file: one JSON `AircraftReport` per line
+    pub fn load_tracks<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).for_path(path)?;
+        let mut tracker = Self::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            tracker.ingest(serde_json::from_str(line).for_path(path)?);
+        }
+        Ok(tracker)
+    }
+
+    /// Tracked aircraft
+    pub fn aircraft_count(&self) -> usize {
+        self.reports.len()
+    }
+
+    /// Position of every aircraft with a recent report, sorted by ICAO address
+    pub fn positions_at(&self, time: DateTime<Utc>) -> Vec<AircraftReport> {
+        let max_age = Duration::milliseconds((self.max_report_age_seconds * 1000.0) as i64);
+        let mut positions: Vec<AircraftReport> = self
+            .reports
+            .values()
+            .filter_map(|history| {
+                let latest = history.partition_point(|r| r.timestamp <= time);
+                let report = history[..latest].last()?;
+                (time - report.timestamp <= max_age).then(|| report.extrapolate(time))
+            })
+            .collect();
+        positions.sort_by(|a, b| a.icao24.cmp(&b.icao24));
+        positions
+    }
+
+    /// Drop reports older than `before`, keeping each aircraft's latest
+    pub fn prune(&mut self, before: DateTime<Utc>) {
+        for history in self.reports.values_mut() {
+            let stale = history.partition_point(|r| r.timestamp < before);
+            history.drain(..stale.min(history.len().saturating_sub(1)));
+        }
+    }
+}
+
+impl Default for DeconflictionConfig {
+    fn default() -> Self {
+        Self {
+            protection_cone_deg: 5.0,
+            look_ahead_seconds: 60.0,
+            step_seconds: 1.0,
+            max_range_km: 150.0,
+        }
+    }
+}
+
+impl DeconflictionConfig {
+    /// First predicted entry of `aircraft` into the cone around a beam
+    pub fn predict_conjunction(
+        &self,
+        station: &GroundStation,
+        beam_azimuth_deg: f64,
+        beam_elevation_deg: f64,
+        aircraft: &AircraftReport,
+    ) -> Option<AircraftConjunction> {
+        let steps = (self.look_ahead_seconds / self.step_seconds.max(1e-3)).floor() as i64;
+        (0..=steps).find_map(|step| {
+            let lead_time_seconds = step as f64 * self.step_seconds;
+            let time =
+                aircraft.timestamp + Duration::milliseconds((lead_time_seconds * 1000.0) as i64);
+            let position = aircraft.extrapolate(time);
+            let (azimuth_deg, elevation_deg, range_km) = look_angles(station, &position);
+            let separation_deg = angular_separation_deg(
+                (beam_azimuth_deg, beam_elevation_deg),
+                (azimuth_deg, elevation_deg),
+            );
+            (separation_deg <= self.protection_cone_deg && range_km <= self.max_range_km).then(
+                || AircraftConjunction {
+                    icao24: aircraft.icao24.clone(),
+                    callsign: aircraft.callsign.clone(),
+                    station_id: station.station_id.clone(),
+                    conjunction_time: time,
+                    lead_time_seconds,
+                    separation_deg,
+                    range_km,
+                },
+            )
+        })
+    }
+}
+
+/// Parse a dump1090/readsb `aircraft.json` snapshot
+///
+/// Aircraft without a position or on the ground are skipped. Geometric
+/// altitude is preferred over barometric.
+pub fn parse_aircraft_json(content: &str) -> Result<Vec<AircraftReport>> {
+    let snapshot: ReceiverSnapshot = serde_json::from_str(content)?;
+    let now = DateTime::from_timestamp_millis((snapshot.now * 1000.0) as i64).unwrap_or_default();
+    Ok(snapshot
+        .aircraft
+        .into_iter()
+        .filter_map(|aircraft| {
+            let altitude_ft = aircraft.alt_geom.or_else(|| {
+                aircraft
+                    .alt_baro
+                    .as_ref()
+                    .and_then(serde_json::Value::as_f64)
+            })?;
+            let age_ms = (aircraft.seen_pos.unwrap_or(0.0) * 1000.0) as i64;
+            Some(AircraftReport {
+                icao24: aircraft.hex,
+                callsign: aircraft
+                    .flight
+                    .map(|flight| flight.trim().to_string())
+                    .filter(|flight| !flight.is_empty()),
+                timestamp: now - Duration::milliseconds(age_ms),
+                latitude_deg: aircraft.lat?,
+                longitude_deg: aircraft.lon?,
+                altitude_m: altitude_ft * FEET_TO_M,
+                ground_speed_m_s: aircraft.gs.unwrap_or(0.0) * KNOTS_TO_M_S,
+                track_deg: aircraft.track.unwrap_or(0.0),
+                vertical_rate_m_s: aircraft.geom_rate.or(aircraft.baro_rate).unwrap_or(0.0)
+                    * FEET_TO_M
+                    / 60.0,
+            })
+        })
+        .collect())
+}
+
+/// Live ADS-B receiver polled over HTTP
+#[cfg(feature = "online")]
+#[derive(Debug, Clone)]
+pub struct AdsbFeed {
+    /// URL of a dump1090/readsb `aircraft.json`
+    pub url: String,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "online")]
+impl AdsbFeed {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetch the current snapshot
+    pub async fn fetch(&self) -> Result<Vec<AircraftReport>> {
+        let body = self
+            .client
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        parse_aircraft_json(&body)
+    }
+}
+
+/// Poll `feed` and feed its reports to the simulator
+///
+/// Runs forever; spawn it on the runtime next to the simulation loop. Failed
+/// polls are logged and retried on the next interval.
+#[cfg(feature = "online")]
+pub async fn run_adsb_feed(
+    feed: AdsbFeed,
+    simulator: std::sync::Arc<crate::satellite_simulator::SatelliteSimulator>,
+    poll_interval: std::time::Duration,
+) {
+    let mut interval = tokio::time::interval(poll_interval);
+    loop {
+        interval.tick().await;
+        match feed.fetch().await {
+            Ok(reports) => simulator.ingest_aircraft(reports),
+            Err(e) => tracing::warn!(
+                target: crate::trace_targets::SIMULATOR,
+                url = %feed.url,
+                error = %e,
+                "ADS-B poll failed"
+            ),
+        }
+    }
+}
+
+/// Azimuth, elevation and range of an aircraft from a station
+///
+/// Uses the station's local tangent plane with the Earth's curvature drop,
+/// which is accurate within the deconfliction range.
+fn look_angles(station: &GroundStation, aircraft: &AircraftReport) -> (f64, f64, f64) {
+    let position = &station.position;
+    let delta_longitude =
+        (aircraft.longitude_deg - position.longitude_deg + 180.0).rem_euclid(360.0) - 180.0;
+    let east_km =
+        EARTH_RADIUS_KM * position.latitude_deg.to_radians().cos() * delta_longitude.to_radians();
+    let north_km = EARTH_RADIUS_KM * (aircraft.latitude_deg - position.latitude_deg).to_radians();
+    let horizontal_km = east_km.hypot(north_km);
+    let up_km = (aircraft.altitude_m - position.elevation_m) / 1000.0
+        - horizontal_km * horizontal_km / (2.0 * EARTH_RADIUS_KM);
+
+    let azimuth_deg = east_km.atan2(north_km).to_degrees().rem_euclid(360.0);
+    let elevation_deg = up_km.atan2(horizontal_km).to_degrees();
+    (azimuth_deg, elevation_deg, horizontal_km.hypot(up_km))
+}
+
+/// Angle between two azimuth/elevation directions
+fn angular_separation_deg((az1, el1): (f64, f64), (az2, el2): (f64, f64)) -> f64 {
+    let (el1, el2) = (el1.to_radians(), el2.to_radians());
+    let cosine = el1.sin() * el2.sin() + el1.cos() * el2.cos() * (az1 - az2).to_radians().cos();
+    cosine.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// Subset of a dump1090/readsb `aircraft.json` snapshot
+#[derive(Deserialize)]
+struct ReceiverSnapshot {
+    /// Unix time of the snapshot in seconds
+    now: f64,
+    aircraft: Vec<ReceiverAircraft>,
+}
+
+#[derive(Deserialize)]
+struct ReceiverAircraft {
+    hex: String,
+    flight: Option<String>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// Feet, or the string "ground"
+    alt_baro: Option<serde_json::Value>,
+    alt_geom: Option<f64>,
+    /// Knots
+    gs: Option<f64>,
+    track: Option<f64>,
+    /// Feet per minute
+    baro_rate: Option<f64>,
+    geom_rate: Option<f64>,
+    /// Seconds since the position was received
+    seen_pos: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::StationPosition;
+    use chrono::TimeZone;
+
+    fn station() -> GroundStation {
+        GroundStation {
+            station_id: "GS-OPT".to_string(),
+            name: "Optical Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 35.0,
+                longitude_deg: -106.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        }
+    }
+
+    #[test]
+    fn test_predicts_cone_entry_with_lead_time() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 20, 12, 0, 0).unwrap();
+        // Beam east at 10° passes 5 km altitude about 28 km out
+        let beam = (90.0, 10.0);
+        let crossing = AircraftReport {
+            icao24: "a1b2c3".to_string(),
+            callsign: Some("TEST12".to_string()),
+            timestamp: now,
+            latitude_deg: 35.0 + (10.0 / EARTH_RADIUS_KM).to_degrees(),
+            longitude_deg: -106.0
+                + (28.3 / (EARTH_RADIUS_KM * 35.0_f64.to_radians().cos())).to_degrees(),
+            altitude_m: 5000.0,
+            ground_speed_m_s: 200.0,
+            track_deg: 180.0,
+            vertical_rate_m_s: 0.0,
+        };
+        let config = DeconflictionConfig::default();
+
+        // Southbound at 200 m/s reaches the ~2.5 km wide cone from 10 km north
+        let conjunction = config
+            .predict_conjunction(&station(), beam.0, beam.1, &crossing)
+            .unwrap();
+        assert!(
+            (30.0..45.0).contains(&conjunction.lead_time_seconds),
+            "lead time {} s",
+            conjunction.lead_time_seconds
+        );
+        assert!(conjunction.separation_deg <= config.protection_cone_deg);
+
+        let departing = AircraftReport {
+            track_deg: 0.0,
+            ..crossing.clone()
+        };
+        assert!(config
+            .predict_conjunction(&station(), beam.0, beam.1, &departing)
+            .is_none());
+
+        let mut tracker = AircraftTracker::new();
+        tracker.extend([crossing.clone(), departing]);
+        assert_eq!(tracker.aircraft_count(), 1);
+        assert!(tracker.positions_at(now - Duration::seconds(1)).is_empty());
+        let later = tracker.positions_at(now + Duration::seconds(10));
+        assert!(later[0].latitude_deg > crossing.latitude_deg);
+        assert!(tracker.positions_at(now + Duration::seconds(31)).is_empty());
+    }
+
+    #[test]
+    fn test_parse_receiver_snapshot() {
+        let json = r#"{
+            "now": 1710936000.0,
+            "aircraft": [
+                {"hex": "a1b2c3", "flight": "TEST12  ", "lat": 35.1, "lon": -106.2,
+                 "alt_baro": 10000, "alt_geom": 10250, "gs": 400.0, "track": 90.0,
+                 "geom_rate": -600, "seen_pos": 0.5},
+                {"hex": "d4e5f6", "alt_baro": "ground", "lat": 35.0, "lon": -106.0},
+                {"hex": "0a0b0c", "alt_baro": 20000}
+            ]
+        }"#;
+        let reports = parse_aircraft_json(json).unwrap();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.callsign.as_deref(), Some("TEST12"));
+        assert!((report.altitude_m - 3124.2).abs() < 1e-6);
+        assert!((report.ground_speed_m_s - 205.778).abs() < 1e-3);
+        assert!((report.vertical_rate_m_s + 3.048).abs() < 1e-9);
+        assert_eq!(
+            report.timestamp,
+            Utc.timestamp_millis_opt(1_710_935_999_500).unwrap()
+        );
+    }
+}
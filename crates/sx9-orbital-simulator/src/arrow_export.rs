@@ -0,0 +1,377 @@
+//! Arrow/Parquet export of propagation and visibility datasets
+//!
+//! Converts `SatelliteState` time series and `VisibilityWindow` tables into
+//! Arrow record batches and writes them as Snappy-compressed Parquet, so large
+//! studies load directly into pandas or polars. Timestamps are stored as
+//! POSIX UTC microseconds, which have no 23:59:60: epochs inside a leap
+//! second are written as 23:59:59.999999 (see `time::posix_micros`). State
+//! tables can be read back for playback.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::SatelliteState;
+use crate::time;
+use crate::visibility::{PassType, VisibilityWindow};
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, StringArray, TimestampMicrosecondArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use chrono::{DateTime, Utc};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+impl From<arrow::error::ArrowError> for OrbitalMechanicsError {
+    fn from(err: arrow::error::ArrowError) -> Self {
+        Self::ExportError(err.to_string())
+    }
+}
+
+impl From<parquet::errors::ParquetError> for OrbitalMechanicsError {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        Self::ExportError(err.to_string())
+    }
+}
+
+/// Streams satellite states into one Parquet file
+///
+/// Each `write` call becomes at least one row group, so long runs can be
+/// exported without holding every state in memory.
+pub struct StateParquetWriter {
+    writer: ArrowWriter<File>,
+}
+
+/// Schema of the satellite state table
+pub fn state_schema() -> SchemaRef {
+    let float = |name: &str| Field::new(name, DataType::Float64, false);
+    Arc::new(Schema::new(vec![
+        Field::new("satellite_id", DataType::Utf8, false),
+        timestamp_field("timestamp"),
+        float("x_km"),
+        float("y_km"),
+        float("z_km"),
+        float("vx_km_s"),
+        float("vy_km_s"),
+        float("vz_km_s"),
+        float("latitude_deg"),
+        float("longitude_deg"),
+        float("altitude_km"),
+        Field::new("in_eclipse", DataType::Boolean, false),
+        float("orbital_radius_km"),
+    ]))
+}
+
+/// Schema of the visibility window table
+pub fn visibility_schema() -> SchemaRef {
+    let float = |name: &str| Field::new(name, DataType::Float64, false);
+    Arc::new(Schema::new(vec![
+        Field::new("satellite_id", DataType::Utf8, false),
+        Field::new("station_id", DataType::Utf8, false),
+        timestamp_field("start_time"),
+        timestamp_field("end_time"),
+        float("duration_seconds"),
+        float("usable_seconds"),
+        timestamp_field("max_elevation_time"),
+        float("max_elevation_deg"),
+        float("min_range_km"),
+        float("mean_range_km"),
+        float("azimuth_span_deg"),
+        Field::new("pass_type", DataType::Utf8, false),
+        Field::new("aos_uncertainty_seconds", DataType::Float64, true),
+        Field::new("los_uncertainty_seconds", DataType::Float64, true),
+    ]))
+}
+
+/// One row per state
+pub fn states_to_record_batch(states: &[SatelliteState]) -> Result<RecordBatch> {
+    let column = |value: fn(&SatelliteState) -> f64| -> ArrayRef {
+        Arc::new(states.iter().map(value).collect::<Float64Array>())
+    };
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            states.iter().map(|s| s.satellite_id.as_str()),
+        )),
+        timestamps(states.iter().map(|s| s.timestamp)),
+        column(|s| s.position_eci[0]),
+        column(|s| s.position_eci[1]),
+        column(|s| s.position_eci[2]),
+        column(|s| s.velocity_eci[0]),
+        column(|s| s.velocity_eci[1]),
+        column(|s| s.velocity_eci[2]),
+        column(|s| s.geodetic.latitude_deg),
+        column(|s| s.geodetic.longitude_deg),
+        column(|s| s.geodetic.altitude_km),
+        Arc::new(
+            states
+                .iter()
+                .map(|s| Some(s.in_eclipse))
+                .collect::<BooleanArray>(),
+        ),
+        column(|s| s.orbital_radius),
+    ];
+    Ok(RecordBatch::try_new(state_schema(), columns)?)
+}
+
+/// States from a batch with the state schema
+///
+/// Only the ID, timestamp and ECI columns are read; derived columns are
+/// recomputed from the state vector.
+pub fn record_batch_to_states(batch: &RecordBatch) -> Result<Vec<SatelliteState>> {
+    let satellite_ids = typed_column::<StringArray>(batch, "satellite_id")?;
+    let timestamps = typed_column::<TimestampMicrosecondArray>(batch, "timestamp")?;
+    let vector = |names: [&str; 3]| -> Result<[&Float64Array; 3]> {
+        Ok([
+            typed_column(batch, names[0])?,
+            typed_column(batch, names[1])?,
+            typed_column(batch, names[2])?,
+        ])
+    };
+    let position = vector(["x_km", "y_km", "z_km"])?;
+    let velocity = vector(["vx_km_s", "vy_km_s", "vz_km_s"])?;
+
+    (0..batch.num_rows())
+        .map(|row| {
+            let timestamp =
+                DateTime::from_timestamp_micros(timestamps.value(row)).ok_or_else(|| {
+                    OrbitalMechanicsError::ExportError(format!(
+                        "timestamp out of range in row {row}"
+                    ))
+                })?;
+            Ok(SatelliteState::new(
+                satellite_ids.value(row).to_string(),
+                timestamp,
+                position.map(|column| column.value(row)),
+                velocity.map(|column| column.value(row)),
+            ))
+        })
+        .collect()
+}
+
+/// One row per window
+pub fn visibility_windows_to_record_batch(windows: &[VisibilityWindow]) -> Result<RecordBatch> {
+    let column = |value: fn(&VisibilityWindow) -> f64| -> ArrayRef {
+        Arc::new(windows.iter().map(value).collect::<Float64Array>())
+    };
+    let optional = |value: fn(&VisibilityWindow) -> Option<f64>| -> ArrayRef {
+        Arc::new(windows.iter().map(value).collect::<Float64Array>())
+    };
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            windows.iter().map(|w| w.satellite_id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            windows.iter().map(|w| w.station_id.as_str()),
+        )),
+        timestamps(windows.iter().map(|w| w.start_time)),
+        timestamps(windows.iter().map(|w| w.end_time)),
+        column(|w| w.duration_seconds),
+        column(|w| w.usable_seconds()),
+        timestamps(windows.iter().map(|w| w.max_elevation_time)),
+        column(|w| w.max_elevation_deg),
+        column(|w| w.min_range_km),
+        column(|w| w.mean_range_km),
+        column(|w| w.azimuth_span_deg),
+        Arc::new(StringArray::from_iter_values(windows.iter().map(
+            |w| match w.pass_type {
+                PassType::Normal => "Normal",
+                PassType::Continuous => "Continuous",
+                PassType::Partial => "Partial",
+            },
+        ))),
+        optional(|w| w.aos_uncertainty_seconds),
+        optional(|w| w.los_uncertainty_seconds),
+    ];
+    Ok(RecordBatch::try_new(visibility_schema(), columns)?)
+}
+
+/// Write a state time series to a Parquet file
+pub fn write_states_parquet<P: AsRef<Path>>(states: &[SatelliteState], path: P) -> Result<()> {
+    let path = path.as_ref();
+    write_batch(&states_to_record_batch(states)?, path).for_path(path)
+}
+
+/// Read a state table written by `write_states_parquet` or `StateParquetWriter`
+pub fn read_states_parquet<P: AsRef<Path>>(path: P) -> Result<Vec<SatelliteState>> {
+    let path = path.as_ref();
+    read_states(path).for_path(path)
+}
+
+/// Write visibility windows to a Parquet file
+pub fn write_visibility_parquet<P: AsRef<Path>>(
+    windows: &[VisibilityWindow],
+    path: P,
+) -> Result<()> {
+    let path = path.as_ref();
+    write_batch(&visibility_windows_to_record_batch(windows)?, path).for_path(path)
+}
+
+impl StateParquetWriter {
+    /// Create the file, truncating any existing one
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let writer = open_writer(path, state_schema()).for_path(path)?;
+        Ok(Self { writer })
+    }
+
+    /// Append states as a new row group
+    pub fn write(&mut self, states: &[SatelliteState]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        let batch = states_to_record_batch(states)?;
+        self.writer.write(&batch)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the file footer; the file is unreadable until this is called
+    pub fn finish(self) -> Result<()> {
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+fn timestamp_field(name: &str) -> Field {
+    Field::new(
+        name,
+        DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        false,
+    )
+}
+
+fn timestamps(values: impl Iterator<Item = DateTime<Utc>>) -> ArrayRef {
+    Arc::new(
+        TimestampMicrosecondArray::from_iter_values(values.map(time::posix_micros))
+            .with_timezone("UTC"),
+    )
+}
+
+fn open_writer(path: &Path, schema: SchemaRef) -> Result<ArrowWriter<File>> {
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    Ok(ArrowWriter::try_new(
+        File::create(path)?,
+        schema,
+        Some(properties),
+    )?)
+}
+
+fn read_states(path: &Path) -> Result<Vec<SatelliteState>> {
+    let mut states = Vec::new();
+    for batch in ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()? {
+        states.extend(record_batch_to_states(&batch?)?);
+    }
+    Ok(states)
+}
+
+fn typed_column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T> {
+    batch
+        .column_by_name(name)
+        .and_then(|column| column.as_any().downcast_ref::<T>())
+        .ok_or_else(|| {
+            OrbitalMechanicsError::ExportError(format!("missing or mistyped column `{name}`"))
+        })
+}
+
+fn write_batch(batch: &RecordBatch, path: &Path) -> Result<()> {
+    let mut writer = open_writer(path, batch.schema())?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::{OrbitalElements, SatelliteOrbit};
+    use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+    use arrow::array::Array;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn read_rows(path: &Path) -> Vec<RecordBatch> {
+        ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let epoch = Utc::now();
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "PQ-01".to_string(),
+            "Parquet Test".to_string(),
+            elements,
+            epoch,
+        );
+        let propagator = KeplerianPropagator::new();
+        let states: Vec<SatelliteState> = (0..10)
+            .map(|i| {
+                propagator
+                    .propagate(&satellite, epoch + Duration::minutes(i))
+                    .unwrap()
+            })
+            .collect();
+
+        let dir = tempdir().unwrap();
+        let streamed = dir.path().join("states.parquet");
+        let mut writer = StateParquetWriter::create(&streamed).unwrap();
+        writer.write(&states[..4]).unwrap();
+        writer.write(&states[4..]).unwrap();
+        writer.finish().unwrap();
+
+        let batches = read_rows(&streamed);
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 10);
+        assert_eq!(batches[0].schema(), state_schema());
+        let x = batches[0]
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(x.value(0), states[0].position_eci[0]);
+        let restored = read_states_parquet(&streamed).unwrap();
+        assert_eq!(restored.len(), 10);
+        assert_eq!(
+            restored[9].timestamp.timestamp_micros(),
+            states[9].timestamp.timestamp_micros()
+        );
+        assert_eq!(restored[9].velocity_eci, states[9].velocity_eci);
+
+        let window = VisibilityWindow {
+            satellite_id: "PQ-01".to_string(),
+            station_id: "GS-001".to_string(),
+            start_time: epoch,
+            end_time: epoch + Duration::minutes(10),
+            duration_seconds: 600.0,
+            max_elevation_time: epoch + Duration::minutes(5),
+            max_elevation_deg: 45.0,
+            min_range_km: 9000.0,
+            mean_range_km: 9000.0,
+            azimuth_span_deg: 0.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: Some(1.5),
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        };
+        let path = dir.path().join("windows.parquet");
+        write_visibility_parquet(&[window], &path).unwrap();
+
+        let batch = &read_rows(&path)[0];
+        assert_eq!(batch.num_rows(), 1);
+        let los = batch
+            .column_by_name("los_uncertainty_seconds")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert!(los.is_null(0));
+    }
+}
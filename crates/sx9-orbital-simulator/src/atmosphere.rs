@@ -0,0 +1,213 @@
+//! Atmospheric density and drag for LEO objects
+//!
+//! Piecewise exponential density (Vallado, Table 8-4) with an optional
+//! solar-activity correction to the thermospheric scale height, and the
+//! drag acceleration of a co-rotating atmosphere.
+
+use crate::constants::*;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+/// Altitude below which an object is treated as re-entered
+pub const REENTRY_ALTITUDE_KM: f64 = 100.0;
+
+/// Altitude above which drag is neglected
+pub const MAX_DRAG_ALTITUDE_KM: f64 = 2500.0;
+
+/// Base altitude (km), base density (kg/m³) and scale height (km)
+const EXPONENTIAL_TABLE: [(f64, f64, f64); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// Altitude from which the solar-activity correction applies
+const THERMOSPHERE_BASE_KM: f64 = 180.0;
+
+/// Solar and geomagnetic activity driving thermospheric density
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SpaceWeather {
+    /// 10.7 cm solar radio flux in solar flux units
+    pub f107_sfu: f64,
+    /// Daily planetary geomagnetic index
+    pub ap: f64,
+}
+
+/// Density model
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum AtmosphereModel {
+    /// Static exponential table for moderate solar activity
+    #[default]
+    Exponential,
+    /// Exponential table with thermospheric scale heights stretched by
+    /// exospheric temperature
+    SolarScaled(SpaceWeather),
+}
+
+/// Spacecraft properties that set the drag acceleration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DragParameters {
+    pub mass_kg: f64,
+    /// Cross-section normal to the flow
+    pub drag_area_m2: f64,
+    pub drag_coefficient: f64,
+}
+
+impl SpaceWeather {
+    /// Exospheric temperature in kelvin (Jacchia-style approximation)
+    pub fn exospheric_temperature_k(&self) -> f64 {
+        379.0 + 3.24 * self.f107_sfu + 1.3 * self.ap
+    }
+}
+
+impl Default for SpaceWeather {
+    /// Moderate activity, matching the exponential table
+    fn default() -> Self {
+        Self {
+            f107_sfu: 150.0,
+            ap: 15.0,
+        }
+    }
+}
+
+impl AtmosphereModel {
+    /// Mass density in kg/m³ at a geodetic altitude
+    pub fn density_kg_per_m3(&self, altitude_km: f64) -> f64 {
+        if altitude_km > MAX_DRAG_ALTITUDE_KM {
+            return 0.0;
+        }
+        let altitude_km = altitude_km.max(0.0);
+
+        match self {
+            Self::SolarScaled(weather) if altitude_km > THERMOSPHERE_BASE_KM => {
+                // Integrate up from the thermosphere base with stretched scale heights
+                let stretch = weather.exospheric_temperature_k()
+                    / SpaceWeather::default().exospheric_temperature_k();
+                let mut density = Self::Exponential.density_kg_per_m3(THERMOSPHERE_BASE_KM);
+                for (i, (base_km, _, scale_height_km)) in EXPONENTIAL_TABLE.iter().enumerate() {
+                    if *base_km < THERMOSPHERE_BASE_KM || *base_km >= altitude_km {
+                        continue;
+                    }
+                    let top_km = EXPONENTIAL_TABLE
+                        .get(i + 1)
+                        .map_or(altitude_km, |(next, _, _)| next.min(altitude_km));
+                    density *= (-(top_km - base_km) / (scale_height_km * stretch)).exp();
+                }
+                density
+            }
+            _ => {
+                let (base_km, base_density, scale_height_km) = EXPONENTIAL_TABLE
+                    .iter()
+                    .rev()
+                    .find(|(base, _, _)| altitude_km >= *base)
+                    .copied()
+                    .unwrap_or(EXPONENTIAL_TABLE[0]);
+                base_density * (-(altitude_km - base_km) / scale_height_km).exp()
+            }
+        }
+    }
+}
+
+impl DragParameters {
+    /// Ballistic coefficient m / (Cd A) in kg/m²
+    pub fn ballistic_coefficient_kg_per_m2(&self) -> f64 {
+        self.mass_kg / (self.drag_coefficient * self.drag_area_m2)
+    }
+}
+
+impl Default for DragParameters {
+    fn default() -> Self {
+        Self {
+            mass_kg: 500.0,
+            drag_area_m2: 2.0,
+            drag_coefficient: 2.2,
+        }
+    }
+}
+
+/// Drag acceleration in km/s² for an ECI state
+///
+/// The atmosphere co-rotates with the Earth; altitude is measured above the
+/// equatorial radius.
+pub fn drag_acceleration(
+    position_km: [f64; 3],
+    velocity_km_s: [f64; 3],
+    drag: &DragParameters,
+    model: &AtmosphereModel,
+) -> [f64; 3] {
+    let position = Vector3::from(position_km);
+    let density = model.density_kg_per_m3(position.norm() - EARTH_RADIUS_KM);
+    if density <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let earth_rotation = Vector3::new(0.0, 0.0, EARTH_ROTATION_RATE);
+    let relative = Vector3::from(velocity_km_s) - earth_rotation.cross(&position);
+    let speed_m_s = relative.norm() * KM_TO_M;
+
+    // a = -1/2 ρ v² / B along the relative velocity, converted from m/s² to km/s²
+    let factor = -0.5 * density * speed_m_s / drag.ballistic_coefficient_kg_per_m2();
+    (factor * relative).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density_profile() {
+        let model = AtmosphereModel::Exponential;
+        assert!((model.density_kg_per_m3(0.0) - 1.225).abs() < 1e-9);
+        assert!((model.density_kg_per_m3(400.0) - 3.725e-12).abs() < 1e-18);
+
+        // Decreasing with altitude across layer boundaries
+        let mut previous = f64::INFINITY;
+        for altitude in (0..1200).step_by(5) {
+            let density = model.density_kg_per_m3(altitude as f64);
+            assert!(density < previous);
+            previous = density;
+        }
+        assert_eq!(model.density_kg_per_m3(3000.0), 0.0);
+
+        // Solar maximum inflates the upper thermosphere
+        let active = AtmosphereModel::SolarScaled(SpaceWeather {
+            f107_sfu: 250.0,
+            ap: 40.0,
+        });
+        let quiet = AtmosphereModel::SolarScaled(SpaceWeather {
+            f107_sfu: 70.0,
+            ap: 4.0,
+        });
+        assert!(active.density_kg_per_m3(500.0) > 2.0 * model.density_kg_per_m3(500.0));
+        assert!(quiet.density_kg_per_m3(500.0) < 0.5 * model.density_kg_per_m3(500.0));
+        assert_eq!(
+            active.density_kg_per_m3(120.0),
+            model.density_kg_per_m3(120.0)
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! Ground illumination by a laser downlink
+//!
+//! For laser safety review, traces where a downlink beam lands during a pass:
+//! the beam center, which sits on the station unless the terminal carries a
+//! pointing bias, and the footprint ellipse the diverging beam lights on the
+//! ground. The beam cone is cut by the station's local horizontal plane, so
+//! the footprint stretches by 1/sin(elevation) along the azimuth toward the
+//! satellite. Footprints that overlap a no-lase `LaserSafetyZone` are
+//! flagged, whatever the zone's ceiling, since a downlink reaches the ground.
+//!
+//! The trace exports to GeoJSON: the beam center as a `LineString` and each
+//! sampled footprint as a `Polygon`.
+
+use crate::constants::EARTH_RADIUS_KM;
+use crate::coordinates::wrap_angle_deg;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::laser_safety::{contains, local_east_north_km, LaserSafetyZone};
+use crate::orbit::SatelliteOrbit;
+use crate::pass_profile;
+use crate::propagator::OrbitalPropagator;
+use crate::units::Seconds;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+/// Downlink beam shape and aim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownlinkBeam {
+    /// Full divergence angle
+    pub divergence_urad: f64,
+    /// Aim offset in the elevation plane; positive lands beyond the station
+    #[serde(default)]
+    pub along_track_bias_urad: f64,
+    /// Aim offset across it; positive lands to the right seen from the satellite
+    #[serde(default)]
+    pub cross_track_bias_urad: f64,
+}
+
+/// Beam landing at one instant of the pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeamFootprint {
+    pub timestamp: DateTime<Utc>,
+    pub elevation_deg: f64,
+    pub azimuth_deg: f64,
+    pub range_km: f64,
+    pub center_latitude_deg: f64,
+    pub center_longitude_deg: f64,
+    pub semi_major_m: f64,
+    pub semi_minor_m: f64,
+    /// The major axis lies along the azimuth to the satellite
+    pub major_axis_azimuth_deg: f64,
+    /// Zones the footprint overlaps
+    pub zone_ids: Vec<String>,
+}
+
+/// Interval when the footprint overlaps a no-lase zone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneIllumination {
+    pub zone_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+/// Ground trace of a downlink beam over one pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeamGroundTrace {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub beam: DownlinkBeam,
+    pub footprints: Vec<BeamFootprint>,
+    /// By start time
+    pub zone_illuminations: Vec<ZoneIllumination>,
+}
+
+impl DownlinkBeam {
+    /// Beam aimed straight at the station
+    pub fn new(divergence_urad: f64) -> Self {
+        Self {
+            divergence_urad,
+            along_track_bias_urad: 0.0,
+            cross_track_bias_urad: 0.0,
+        }
+    }
+
+    pub fn with_pointing_bias(mut self, along_track_urad: f64, cross_track_urad: f64) -> Self {
+        self.along_track_bias_urad = along_track_urad;
+        self.cross_track_bias_urad = cross_track_urad;
+        self
+    }
+}
+
+impl BeamFootprint {
+    /// Footprint outline as `[latitude_deg, longitude_deg]`, closed implicitly
+    pub fn outline(&self, vertices: usize) -> Vec<[f64; 2]> {
+        let center = [self.center_latitude_deg, self.center_longitude_deg];
+        ellipse_east_north_km(
+            [0.0, 0.0],
+            self.major_axis_azimuth_deg,
+            self.semi_major_m / 1000.0,
+            self.semi_minor_m / 1000.0,
+            vertices,
+        )
+        .into_iter()
+        .map(|point| offset_lat_lon(center, point))
+        .collect()
+    }
+}
+
+impl BeamGroundTrace {
+    /// Trace the beam through `window`, sampled every `step_seconds`
+    pub fn for_pass(
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+        beam: &DownlinkBeam,
+        zones: &[LaserSafetyZone],
+        step_seconds: f64,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Self> {
+        if !beam.divergence_urad.is_finite() || beam.divergence_urad <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Downlink beam divergence must be positive, got {} µrad",
+                beam.divergence_urad
+            )));
+        }
+        let position = &station.position;
+        let zone_polygons: Vec<Vec<[f64; 2]>> = zones
+            .iter()
+            .map(|zone| {
+                zone.vertices
+                    .iter()
+                    .map(|&vertex| local_east_north_km(position, vertex))
+                    .collect()
+            })
+            .collect();
+
+        let mut footprints = Vec::new();
+        for time in
+            pass_profile::sample_times(window.start_time, window.end_time, Seconds(step_seconds))?
+        {
+            let state = propagator
+                .propagate(satellite, time)
+                .for_satellite(&satellite.satellite_id)
+                .for_station(&station.station_id)
+                .at_epoch(time)?;
+            let look = state.look_angles_from_station(
+                position.latitude_deg,
+                position.longitude_deg,
+                position.elevation_m,
+            );
+            // Below the horizon the beam cone never closes on the ground plane
+            let sin_elevation = look.elevation_deg.to_radians().sin().max(MIN_SIN_ELEVATION);
+
+            let range_m = look.range_km * 1000.0;
+            let semi_minor_m = range_m * beam.divergence_urad * 1e-6 / 2.0;
+            let semi_major_m = semi_minor_m / sin_elevation;
+            let along_km = range_m * beam.along_track_bias_urad * 1e-6 / sin_elevation / 1000.0;
+            let cross_km = range_m * beam.cross_track_bias_urad * 1e-6 / 1000.0;
+            let azimuth = look.azimuth_deg.to_radians();
+            let toward = [azimuth.sin(), azimuth.cos()];
+            let right = [-azimuth.cos(), azimuth.sin()];
+            let center = [
+                -toward[0] * along_km + right[0] * cross_km,
+                -toward[1] * along_km + right[1] * cross_km,
+            ];
+
+            let outline = ellipse_east_north_km(
+                center,
+                look.azimuth_deg,
+                semi_major_m / 1000.0,
+                semi_minor_m / 1000.0,
+                ELLIPSE_VERTICES,
+            );
+            let zone_ids = zones
+                .iter()
+                .zip(&zone_polygons)
+                .filter(|(_, polygon)| polygons_overlap(&outline, polygon))
+                .map(|(zone, _)| zone.zone_id.clone())
+                .collect();
+
+            let [center_latitude_deg, center_longitude_deg] =
+                offset_lat_lon([position.latitude_deg, position.longitude_deg], center);
+            footprints.push(BeamFootprint {
+                timestamp: time,
+                elevation_deg: look.elevation_deg,
+                azimuth_deg: look.azimuth_deg,
+                range_km: look.range_km,
+                center_latitude_deg,
+                center_longitude_deg,
+                semi_major_m,
+                semi_minor_m,
+                major_axis_azimuth_deg: look.azimuth_deg,
+                zone_ids,
+            });
+        }
+
+        let zone_illuminations = zone_illuminations(&footprints);
+        if !zone_illuminations.is_empty() {
+            tracing::warn!(
+                target: crate::trace_targets::FSO,
+                satellite_id = %satellite.satellite_id,
+                station_id = %station.station_id,
+                illuminations = zone_illuminations.len(),
+                "Downlink footprint crosses no-lase zones"
+            );
+        }
+
+        Ok(Self {
+            satellite_id: satellite.satellite_id.clone(),
+            station_id: station.station_id.clone(),
+            beam: beam.clone(),
+            footprints,
+            zone_illuminations,
+        })
+    }
+
+    /// GeoJSON FeatureCollection of the beam center trace and each footprint
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let trace: Vec<[f64; 2]> = self
+            .footprints
+            .iter()
+            .map(|f| [f.center_longitude_deg, f.center_latitude_deg])
+            .collect();
+        let mut features = vec![json!({
+            "type": "Feature",
+            "geometry": { "type": "LineString", "coordinates": trace },
+            "properties": { "kind": "beam_center" }
+        })];
+        features.extend(self.footprints.iter().map(|footprint| {
+            let mut ring: Vec<[f64; 2]> = footprint
+                .outline(ELLIPSE_VERTICES)
+                .into_iter()
+                .map(|[latitude, longitude]| [longitude, latitude])
+                .collect();
+            ring.push(ring[0]);
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "Polygon", "coordinates": [ring] },
+                "properties": {
+                    "kind": "footprint",
+                    "timestamp": footprint.timestamp.to_rfc3339(),
+                    "elevation_deg": footprint.elevation_deg,
+                    "semi_major_m": footprint.semi_major_m,
+                    "semi_minor_m": footprint.semi_minor_m,
+                    "zone_ids": footprint.zone_ids,
+                    "excluded": !footprint.zone_ids.is_empty(),
+                }
+            })
+        }));
+
+        json!({
+            "type": "FeatureCollection",
+            "properties": {
+                "satellite_id": self.satellite_id,
+                "station_id": self.station_id,
+                "divergence_urad": self.beam.divergence_urad,
+                "zone_illuminations": self.zone_illuminations,
+            },
+            "features": features,
+        })
+    }
+
+    /// Write the trace as GeoJSON
+    pub fn write_geojson<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, serde_json::to_string_pretty(&self.to_geojson())?).for_path(path)
+    }
+}
+
+/// Runs of consecutive footprints overlapping each zone
+fn zone_illuminations(footprints: &[BeamFootprint]) -> Vec<ZoneIllumination> {
+    let mut open: Vec<ZoneIllumination> = Vec::new();
+    let mut closed = Vec::new();
+    for footprint in footprints {
+        let (still, ended): (Vec<_>, Vec<_>) = open
+            .into_iter()
+            .partition(|run| footprint.zone_ids.contains(&run.zone_id));
+        closed.extend(ended);
+        open = still;
+        for zone_id in &footprint.zone_ids {
+            match open.iter_mut().find(|run| &run.zone_id == zone_id) {
+                Some(run) => run.end_time = footprint.timestamp,
+                None => open.push(ZoneIllumination {
+                    zone_id: zone_id.clone(),
+                    start_time: footprint.timestamp,
+                    end_time: footprint.timestamp,
+                }),
+            }
+        }
+    }
+    closed.extend(open);
+    closed.sort_by(|a, b| (a.start_time, &a.zone_id).cmp(&(b.start_time, &b.zone_id)));
+    closed
+}
+
+/// Ellipse outline in local east/north km, major axis along `azimuth_deg`
+fn ellipse_east_north_km(
+    center: [f64; 2],
+    azimuth_deg: f64,
+    semi_major_km: f64,
+    semi_minor_km: f64,
+    vertices: usize,
+) -> Vec<[f64; 2]> {
+    let azimuth = azimuth_deg.to_radians();
+    let major = [azimuth.sin(), azimuth.cos()];
+    let minor = [-azimuth.cos(), azimuth.sin()];
+    (0..vertices)
+        .map(|i| {
+            let t = 2.0 * std::f64::consts::PI * i as f64 / vertices as f64;
+            let (a, b) = (semi_major_km * t.cos(), semi_minor_km * t.sin());
+            [
+                center[0] + major[0] * a + minor[0] * b,
+                center[1] + major[1] * a + minor[1] * b,
+            ]
+        })
+        .collect()
+}
+
+/// Inverse of the equirectangular projection about `origin`
+fn offset_lat_lon([latitude, longitude]: [f64; 2], [east_km, north_km]: [f64; 2]) -> [f64; 2] {
+    let longitude =
+        longitude + (east_km / (EARTH_RADIUS_KM * latitude.to_radians().cos())).to_degrees();
+    [
+        latitude + (north_km / EARTH_RADIUS_KM).to_degrees(),
+        wrap_angle_deg(longitude),
+    ]
+}
+
+/// Whether two simple polygons share any area or boundary
+fn polygons_overlap(a: &[[f64; 2]], b: &[[f64; 2]]) -> bool {
+    let edges = |polygon: &[[f64; 2]]| -> Vec<([f64; 2], [f64; 2])> {
+        (0..polygon.len())
+            .map(|i| (polygon[i], polygon[(i + 1) % polygon.len()]))
+            .collect()
+    };
+    a.iter().any(|&point| contains(point, b))
+        || b.iter().any(|&point| contains(point, a))
+        || edges(a)
+            .iter()
+            .any(|&p| edges(b).iter().any(|&q| segments_cross(p, q)))
+}
+
+fn segments_cross((p1, p2): ([f64; 2], [f64; 2]), (q1, q2): ([f64; 2], [f64; 2])) -> bool {
+    let orient = |a: [f64; 2], b: [f64; 2], c: [f64; 2]| {
+        (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+    };
+    let (d1, d2) = (orient(q1, q2, p1), orient(q1, q2, p2));
+    let (d3, d4) = (orient(p1, p2, q1), orient(p1, p2, q2));
+    d1 * d2 <= 0.0 && d3 * d4 <= 0.0
+}
+
+/// Outline vertices of each footprint ellipse
+const ELLIPSE_VERTICES: usize = 32;
+
+/// Grazing limit for the footprint stretch, about 0.6° elevation
+const MIN_SIN_ELEVATION: f64 = 0.01;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::laser_safety::LaserSafetyZoneKind;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use crate::visibility::VisibilityCalculator;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_beam_ground_trace() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(8000.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite =
+            SatelliteOrbit::new("DL-1".to_string(), "Downlink".to_string(), elements, epoch);
+        let station = GroundStation::builder()
+            .station_id("GS-DL")
+            .latitude_deg(0.0)
+            .longitude_deg(0.0)
+            .build()
+            .unwrap();
+        let propagator = KeplerianPropagator::new();
+        let window = VisibilityCalculator::new()
+            .calculate_windows(&satellite, &station, epoch, 12.0, &propagator)
+            .unwrap()
+            .remove(0);
+
+        // A 100 m square with a corner at the station
+        let zone = LaserSafetyZone::new(
+            "ZONE-1",
+            LaserSafetyZoneKind::Other,
+            vec![
+                [-0.0009, -0.0009],
+                [-0.0009, 0.0],
+                [0.0, 0.0],
+                [0.0, -0.0009],
+            ],
+        )
+        .unwrap();
+        let far = LaserSafetyZone::new(
+            "ZONE-FAR",
+            LaserSafetyZoneKind::Airport,
+            vec![[1.0, 1.0], [1.0, 1.1], [1.1, 1.1], [1.1, 1.0]],
+        )
+        .unwrap();
+
+        let beam = DownlinkBeam::new(10.0);
+        let trace = BeamGroundTrace::for_pass(
+            &satellite,
+            &station,
+            &window,
+            &beam,
+            &[zone, far],
+            60.0,
+            &propagator,
+        )
+        .unwrap();
+
+        // Aimed at the station: the center stays put and touches the corner zone
+        let first = &trace.footprints[0];
+        assert!(first.center_latitude_deg.abs() < 1e-9);
+        assert!((first.semi_minor_m - first.range_km * 5.0e-3).abs() < 1e-9);
+        let low = trace
+            .footprints
+            .iter()
+            .min_by(|a, b| a.elevation_deg.total_cmp(&b.elevation_deg))
+            .unwrap();
+        assert!(low.semi_major_m > 1.5 * low.semi_minor_m);
+        assert_eq!(trace.zone_illuminations.len(), 1);
+        assert_eq!(trace.zone_illuminations[0].zone_id, "ZONE-1");
+        assert_eq!(trace.zone_illuminations[0].start_time, window.start_time);
+
+        // A large bias moves the center off the station
+        let biased = DownlinkBeam::new(10.0).with_pointing_bias(0.0, 100.0);
+        let trace = BeamGroundTrace::for_pass(
+            &satellite,
+            &station,
+            &window,
+            &biased,
+            &[],
+            60.0,
+            &propagator,
+        )
+        .unwrap();
+        let center = &trace.footprints[0];
+        let offset_km = EARTH_RADIUS_KM
+            * center
+                .center_latitude_deg
+                .to_radians()
+                .hypot(center.center_longitude_deg.to_radians());
+        assert!((offset_km - center.range_km * 1e-4).abs() < 1e-6);
+
+        let geojson = trace.to_geojson();
+        assert_eq!(geojson["features"][0]["geometry"]["type"], "LineString");
+        assert_eq!(
+            geojson["features"].as_array().unwrap().len(),
+            trace.footprints.len() + 1
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Beta angle history and eclipse seasons
+//!
+//! Samples the Sun beta angle of an orbit over a span (typically a year) and
+//! the eclipse duration it implies, then groups consecutive eclipsing
+//! samples into seasons with their worst-case eclipse. Thermal and power
+//! sizing of FSO terminals is driven by these extremes rather than by any
+//! single propagated orbit.
+//!
+//! Eclipse durations use the same cylindrical Earth shadow as
+//! `ephemeris::is_sunlit`, evaluated analytically for a circular orbit at
+//! the semi-major axis.
+
+use crate::constants::*;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::SatelliteOrbit;
+use crate::units::Degrees;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest span `analyze_beta_angle` accepts, a century
+const MAX_DURATION_DAYS: f64 = 36_525.0;
+
+/// Beta angle and eclipse length at one time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BetaAngleSample {
+    pub timestamp: DateTime<Utc>,
+    pub beta_deg: f64,
+    /// Shadow time per orbit; zero when the orbit never enters eclipse
+    pub eclipse_seconds: f64,
+}
+
+/// Run of consecutive samples with eclipses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EclipseSeason {
+    /// First sample with an eclipse
+    pub start_time: DateTime<Utc>,
+    /// Last sample with an eclipse
+    pub end_time: DateTime<Utc>,
+    pub max_eclipse_seconds: f64,
+    pub max_eclipse_time: DateTime<Utc>,
+    /// Beta angle closest to zero during the season
+    pub min_abs_beta_deg: f64,
+}
+
+/// Beta angle history of one satellite with its eclipse seasons
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BetaAngleReport {
+    pub satellite_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub step_hours: f64,
+    pub samples: Vec<BetaAngleSample>,
+    /// A season still open at either end of the span is clipped there
+    pub seasons: Vec<EclipseSeason>,
+    pub min_beta_deg: f64,
+    pub max_beta_deg: f64,
+    pub max_eclipse_seconds: f64,
+}
+
+impl BetaAngleReport {
+    /// Fraction of samples without an eclipse
+    pub fn eclipse_free_fraction(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let free = self
+            .samples
+            .iter()
+            .filter(|s| s.eclipse_seconds == 0.0)
+            .count();
+        free as f64 / self.samples.len() as f64
+    }
+
+    /// Season containing `time`, if any
+    pub fn season_at(&self, time: DateTime<Utc>) -> Option<&EclipseSeason> {
+        self.seasons
+            .iter()
+            .find(|s| s.start_time <= time && time <= s.end_time)
+    }
+}
+
+/// Fraction of a circular orbit spent in Earth's shadow at beta angle `beta`
+pub fn eclipse_fraction(semi_major_axis_km: f64, beta: Degrees) -> f64 {
+    let a = semi_major_axis_km;
+    let cos_beta = beta.cos();
+    let shadow_edge = (a * a - EARTH_RADIUS_KM * EARTH_RADIUS_KM).max(0.0).sqrt();
+    if a * cos_beta <= shadow_edge {
+        return 0.0;
+    }
+    (shadow_edge / (a * cos_beta)).acos() / std::f64::consts::PI
+}
+
+/// Sample the beta angle and eclipse length every `step_hours` over `duration_days`
+pub fn analyze_beta_angle(
+    satellite: &SatelliteOrbit,
+    start_time: DateTime<Utc>,
+    duration_days: f64,
+    step_hours: f64,
+) -> Result<BetaAngleReport> {
+    let positive_step = step_hours.is_finite() && step_hours > 0.0;
+    if !positive_step || !(0.0..=MAX_DURATION_DAYS).contains(&duration_days) {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Beta angle analysis needs a positive step and a duration of 0 to {} days, got {} h over {} days",
+            MAX_DURATION_DAYS, step_hours, duration_days
+        )));
+    }
+
+    let span = Duration::milliseconds((duration_days * DAYS_TO_SECONDS * 1000.0) as i64);
+    let end_time = start_time.checked_add_signed(span).ok_or_else(|| {
+        OrbitalMechanicsError::config_error(format!(
+            "Beta angle analysis of {} days from {} runs past the supported date range",
+            duration_days, start_time
+        ))
+    })?;
+    let step = Duration::milliseconds((step_hours * 3.6e6).round().max(1.0) as i64);
+
+    let mut samples = Vec::new();
+    let mut time = start_time;
+    while time <= end_time {
+        let beta = satellite.beta_angle(time);
+        let fraction = eclipse_fraction(satellite.elements.semi_major_axis_km, beta);
+        samples.push(BetaAngleSample {
+            timestamp: time,
+            beta_deg: beta.value(),
+            eclipse_seconds: fraction * satellite.period_seconds,
+        });
+        time += step;
+    }
+
+    let seasons = eclipse_seasons(&samples);
+    let beta = samples.iter().map(|s| s.beta_deg);
+    Ok(BetaAngleReport {
+        satellite_id: satellite.satellite_id.clone(),
+        start_time,
+        end_time,
+        step_hours,
+        min_beta_deg: beta.clone().fold(f64::INFINITY, f64::min),
+        max_beta_deg: beta.fold(f64::NEG_INFINITY, f64::max),
+        max_eclipse_seconds: seasons
+            .iter()
+            .map(|s| s.max_eclipse_seconds)
+            .fold(0.0, f64::max),
+        samples,
+        seasons,
+    })
+}
+
+fn eclipse_seasons(samples: &[BetaAngleSample]) -> Vec<EclipseSeason> {
+    let mut seasons: Vec<EclipseSeason> = Vec::new();
+    let mut in_season = false;
+
+    for sample in samples {
+        if sample.eclipse_seconds <= 0.0 {
+            in_season = false;
+            continue;
+        }
+        match seasons.last_mut() {
+            Some(season) if in_season => {
+                season.end_time = sample.timestamp;
+                if sample.eclipse_seconds > season.max_eclipse_seconds {
+                    season.max_eclipse_seconds = sample.eclipse_seconds;
+                    season.max_eclipse_time = sample.timestamp;
+                }
+                season.min_abs_beta_deg = season.min_abs_beta_deg.min(sample.beta_deg.abs());
+            }
+            _ => seasons.push(EclipseSeason {
+                start_time: sample.timestamp,
+                end_time: sample.timestamp,
+                max_eclipse_seconds: sample.eclipse_seconds,
+                max_eclipse_time: sample.timestamp,
+                min_abs_beta_deg: sample.beta_deg.abs(),
+            }),
+        }
+        in_season = true;
+    }
+
+    seasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_eclipse_fraction() {
+        // 400 km circular orbit: about 39% of the orbit in shadow at beta = 0
+        let a = EARTH_RADIUS_KM + 400.0;
+        assert!((eclipse_fraction(a, Degrees(0.0)) - 0.3904).abs() < 1e-3);
+        assert!(eclipse_fraction(a, Degrees(40.0)) < eclipse_fraction(a, Degrees(20.0)));
+        let beta_star = (EARTH_RADIUS_KM / a).asin() * RAD_TO_DEG;
+        assert_eq!(eclipse_fraction(a, Degrees(beta_star + 0.1)), 0.0);
+        assert_eq!(eclipse_fraction(a, Degrees(-beta_star - 0.1)), 0.0);
+    }
+
+    #[test]
+    fn test_dawn_dusk_eclipse_season() {
+        // Sun-synchronous dawn-dusk orbit: the node sits 90° east of the
+        // equinox Sun, so eclipses only occur around one solstice
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let satellite = SatelliteOrbit::builder()
+            .semi_major_axis_km(7077.7)
+            .inclination_deg(98.2)
+            .raan_deg(90.0)
+            .epoch(start)
+            .build()
+            .unwrap();
+
+        let report = analyze_beta_angle(&satellite, start, 365.25, 6.0).unwrap();
+        assert_eq!(report.seasons.len(), 1);
+        let season = &report.seasons[0];
+        assert!(season.max_eclipse_seconds > 0.0 && season.max_eclipse_seconds < 25.0 * 60.0);
+        assert_eq!(report.max_eclipse_seconds, season.max_eclipse_seconds);
+        assert!(report.season_at(start).is_none());
+        assert!(report.eclipse_free_fraction() > 0.5);
+        assert!(report.min_beta_deg > 50.0);
+
+        assert!(analyze_beta_angle(&satellite, start, 365.25, 0.0).is_err());
+        assert!(analyze_beta_angle(&satellite, start, 1e12, 6.0).is_err());
+        assert!(analyze_beta_angle(&satellite, start, f64::NAN, 6.0).is_err());
+
+        // Steps under a millisecond advance by one millisecond
+        let report = analyze_beta_angle(&satellite, start, 1e-6, 1e-9).unwrap();
+        assert_eq!(report.samples.len(), 87);
+    }
+}
@@ -0,0 +1,103 @@
+//! `sx9-orbital` command-line tool
+//!
+//! ```text
+//! sx9-orbital update-data [--cache-dir DIR]
+//! sx9-orbital passes CONFIG [--start TIME] [--hours N] [--format table|csv|json]
+//! ```
+//!
+//! `update-data` fetches the latest IERS leap second and Earth orientation
+//! tables into the per-user data cache, which the other commands then
+//! prefer over the library's embedded copy. It needs the `online` feature.
+//!
+//! `passes` lists the passes of the constellation in CONFIG over the `--hours`
+//! (default 24) after `--start` (RFC 3339, default now), with AOS and LOS in
+//! UTC and in the local time of stations that set a `timezone`.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use ctas7_orbital_mechanics::config::load_constellation_config;
+use ctas7_orbital_mechanics::earth_data::use_cached_earth_data;
+#[cfg(feature = "online")]
+use ctas7_orbital_mechanics::earth_data::{cache_dir, update_data};
+use ctas7_orbital_mechanics::OrbitalMechanicsEngine;
+#[cfg(feature = "online")]
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: sx9-orbital update-data [--cache-dir DIR]
+       sx9-orbital passes CONFIG [--start TIME] [--hours N] [--format table|csv|json]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Logs go to stderr, keeping stdout for CSV and JSON output
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        #[cfg(feature = "online")]
+        Some("update-data") => {
+            let mut directory = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--cache-dir" => {
+                        let value = args.next().ok_or_else(|| anyhow!("{}", USAGE))?;
+                        directory = Some(PathBuf::from(value));
+                    }
+                    _ => bail!("unknown argument `{}`\n{}", arg, USAGE),
+                }
+            }
+            let directory = directory
+                .or_else(cache_dir)
+                .ok_or_else(|| anyhow!("no cache directory; pass --cache-dir"))?;
+
+            let update = update_data(&directory).await?;
+            println!(
+                "Updated {}: {} leap seconds, {} EOP records{}",
+                update.directory.display(),
+                update.leap_second_count,
+                update.eop_record_count,
+                update
+                    .eop_last_mjd
+                    .map(|mjd| format!(" through MJD {:.0}", mjd))
+                    .unwrap_or_default()
+            );
+            Ok(())
+        }
+        #[cfg(not(feature = "online"))]
+        Some("update-data") => {
+            bail!("update-data needs sx9-orbital built with the `online` feature")
+        }
+        Some("passes") => {
+            let config = args.next().ok_or_else(|| anyhow!("{}", USAGE))?;
+            let mut start = Utc::now();
+            let mut hours = 24.0;
+            let mut format = "table".to_string();
+            while let Some(arg) = args.next() {
+                let value = args.next().ok_or_else(|| anyhow!("{}", USAGE))?;
+                match arg.as_str() {
+                    "--start" => start = DateTime::parse_from_rfc3339(&value)?.with_timezone(&Utc),
+                    "--hours" => hours = value.parse()?,
+                    "--format" => format = value,
+                    _ => bail!("unknown argument `{}`\n{}", arg, USAGE),
+                }
+            }
+
+            use_cached_earth_data();
+            let config = load_constellation_config(&config)?;
+            let mut engine = OrbitalMechanicsEngine::with_config(config.clone())?;
+            for station in &config.ground_station_config.custom_stations {
+                engine.add_ground_station(station.to_ground_station());
+            }
+            let report = engine.pass_report(start, hours)?;
+            match format.as_str() {
+                "table" => print!("{}", report.to_table()),
+                "csv" => print!("{}", report.to_csv()),
+                "json" => println!("{}", report.to_json()?),
+                _ => bail!("unknown format `{}`\n{}", format, USAGE),
+            }
+            Ok(())
+        }
+        _ => bail!("{}", USAGE),
+    }
+}
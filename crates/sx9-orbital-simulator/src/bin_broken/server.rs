@@ -1,7 +1,9 @@
 use ctas7_orbital_mechanics::config::Config;
 use ctas7_orbital_mechanics::satellite_simulator::SatelliteSimulator;
+use ctas7_orbital_mechanics::self_check::self_check;
 use anyhow::Result;
-use tokio::net::TcpListener;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
 use tracing::{info, error};
 use std::sync::Arc;
 
@@ -29,14 +31,16 @@ async fn main() -> Result<()> {
     let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).await?;
     info!("Server listening on port {}", port);
 
-    // Basic health check endpoint
-    let listener_clone = listener.try_clone()?;
+    // Health endpoint: GET /healthz runs the self-check, anything else is 404
     tokio::spawn(async move {
         loop {
-            if let Ok((socket, addr)) = listener_clone.accept().await {
+            if let Ok((socket, addr)) = listener.accept().await {
                 info!("Accepted connection from {}", addr);
-                // Connection handling logic would go here
-                drop(socket);
+                tokio::spawn(async move {
+                    if let Err(err) = handle_connection(socket).await {
+                        error!("Connection from {} failed: {}", addr, err);
+                    }
+                });
             }
         }
     });
@@ -47,3 +51,32 @@ async fn main() -> Result<()> {
         info!("Orbital Mechanics Server is running");
     }
 }
+
+async fn handle_connection(socket: TcpStream) -> Result<()> {
+    let mut reader = BufReader::new(socket);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+
+    let (status, body) = match request_line.split_whitespace().nth(1) {
+        Some("/healthz") => {
+            let report = self_check();
+            (report.http_status(), serde_json::to_string(&report)?)
+        }
+        _ => (404, "{\"error\":\"not found\"}".to_string()),
+    };
+    let reason = match status {
+        200 => "OK",
+        503 => "Service Unavailable",
+        _ => "Not Found",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    reader.get_mut().write_all(response.as_bytes()).await?;
+    Ok(())
+}
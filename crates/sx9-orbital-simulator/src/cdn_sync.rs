@@ -0,0 +1,221 @@
+//! Bridge to the geospatial CDN's orbital cache
+//!
+//! The geospatial CDN keeps an `OrbitalObject` per NORAD ID for its map
+//! tiles, holding the object's latest TLE. `SatelliteOrbit` converts from
+//! one through `Tle`, and `CdnOrbitalSync` writes the engine's propagated
+//! states back into the cache at a fixed cadence, as TLEs osculating at the
+//! sync time, so the tiles and the orbital engine work from the same orbits.
+//!
+//! Pushed TLEs carry two-body elements and zero drag terms; see `tle` for
+//! what that costs against SGP4.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use crate::playback;
+use crate::propagator::OrbitalPropagator;
+use crate::tle::Tle;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use sx9_cdn_geospatial::{GeospatialCdnNode, OrbitalObject};
+
+impl TryFrom<&OrbitalObject> for SatelliteOrbit {
+    type Error = OrbitalMechanicsError;
+
+    /// Orbit from the object's TLE, identified by its NORAD ID
+    fn try_from(object: &OrbitalObject) -> Result<Self> {
+        let satellite_id = object.norad_id.to_string();
+        let mut orbit = Tle::parse_lines(&object.tle_line1, &object.tle_line2)
+            .and_then(|tle| tle.to_orbit(satellite_id.clone()))
+            .for_satellite(&satellite_id)?;
+        orbit.name = object.name.clone();
+        Ok(orbit)
+    }
+}
+
+impl TryFrom<OrbitalObject> for SatelliteOrbit {
+    type Error = OrbitalMechanicsError;
+
+    fn try_from(object: OrbitalObject) -> Result<Self> {
+        Self::try_from(&object)
+    }
+}
+
+/// Cache entry for `object` with a TLE osculating with `state`
+///
+/// The TLE keeps the object's catalog number, classification and
+/// international designator.
+pub fn refreshed_orbital_object(
+    object: &OrbitalObject,
+    state: &SatelliteState,
+) -> Result<OrbitalObject> {
+    let satellite_id = object.norad_id.to_string();
+    let orbit = playback::osculating_orbit(&satellite_id, &object.name, state)?;
+    let previous = Tle::parse_lines(&object.tle_line1, &object.tle_line2).ok();
+    let mut tle = Tle::from_orbit(&orbit, object.norad_id);
+    if let Some(previous) = previous {
+        tle.classification = previous.classification;
+        tle.international_designator = previous.international_designator;
+        tle.element_set_number = previous.element_set_number + 1;
+    }
+    let [tle_line1, tle_line2] = tle.to_lines().for_satellite(&satellite_id)?;
+
+    Ok(OrbitalObject {
+        tle_line1,
+        tle_line2,
+        epoch: state.timestamp,
+        updated_at: Utc::now(),
+        ..object.clone()
+    })
+}
+
+/// Keeps a CDN node's orbital cache in step with the engine's propagation
+pub struct CdnOrbitalSync {
+    node: Arc<GeospatialCdnNode>,
+    /// Cache entries being refreshed, with the orbits propagated for them
+    tracked: Vec<(OrbitalObject, SatelliteOrbit)>,
+    /// Interval between pushes when running
+    pub cadence_seconds: f64,
+}
+
+impl CdnOrbitalSync {
+    /// Sync tracking nothing yet, pushing every `cadence_seconds`
+    pub fn new(node: Arc<GeospatialCdnNode>, cadence_seconds: f64) -> Self {
+        Self {
+            node,
+            tracked: Vec::new(),
+            cadence_seconds,
+        }
+    }
+
+    /// Track `object`, replacing any tracked object with its NORAD ID
+    pub fn track(&mut self, object: OrbitalObject) -> Result<()> {
+        let orbit = SatelliteOrbit::try_from(&object)?;
+        self.tracked.retain(|(o, _)| o.norad_id != object.norad_id);
+        self.tracked.push((object, orbit));
+        Ok(())
+    }
+
+    /// Track every object now in the node's cache
+    ///
+    /// Objects whose TLE does not parse are skipped with a warning. Returns
+    /// how many objects are tracked.
+    pub fn track_cache(&mut self) -> usize {
+        for object in self.node.get_all_orbital() {
+            let norad_id = object.norad_id;
+            if let Err(error) = self.track(object) {
+                tracing::warn!(
+                    target: crate::trace_targets::ENGINE,
+                    norad_id,
+                    %error,
+                    "Skipping CDN orbital object"
+                );
+            }
+        }
+        self.tracked.len()
+    }
+
+    /// Orbits propagated for the tracked objects
+    pub fn satellites(&self) -> impl Iterator<Item = &SatelliteOrbit> {
+        self.tracked.iter().map(|(_, orbit)| orbit)
+    }
+
+    /// Propagate every tracked object to `time` and store it in the cache
+    ///
+    /// Returns the number of objects pushed.
+    pub async fn push(
+        &self,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<usize> {
+        for (object, orbit) in &self.tracked {
+            let state = propagator
+                .propagate(orbit, time)
+                .for_satellite(&orbit.satellite_id)
+                .at_epoch(time)?;
+            self.node
+                .store_orbital(refreshed_orbital_object(object, &state)?)
+                .await;
+        }
+        tracing::debug!(
+            target: crate::trace_targets::ENGINE,
+            objects = self.tracked.len(),
+            time = %time,
+            "Pushed orbital states to the CDN cache"
+        );
+        Ok(self.tracked.len())
+    }
+
+    /// Push at the wall-clock time every `cadence_seconds` until a push fails
+    pub async fn run(&self, propagator: &dyn OrbitalPropagator) -> Result<()> {
+        if !self.cadence_seconds.is_finite() || self.cadence_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "CDN sync cadence must be positive, got {} s",
+                self.cadence_seconds
+            )));
+        }
+        let mut ticker =
+            tokio::time::interval(std::time::Duration::from_secs_f64(self.cadence_seconds));
+        loop {
+            ticker.tick().await;
+            self.push(Utc::now(), propagator).await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::{Duration, TimeZone};
+
+    #[tokio::test]
+    async fn test_cdn_orbital_sync() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(14378.0, 0.001, 55.0, 30.0, 10.0, 20.0).unwrap();
+        let orbit = SatelliteOrbit::new("SAT".to_string(), "MEO".to_string(), elements, epoch);
+        let mut tle = Tle::from_orbit(&orbit, 43_001);
+        tle.international_designator = "18001A".to_string();
+        let [tle_line1, tle_line2] = tle.to_lines().unwrap();
+        let object = OrbitalObject {
+            norad_id: 43_001,
+            name: "SX9-MEO-1".to_string(),
+            tle_line1,
+            tle_line2,
+            epoch,
+            updated_at: epoch,
+            object_type: "satellite".to_string(),
+            country: Some("US".to_string()),
+        };
+
+        let converted = SatelliteOrbit::try_from(&object).unwrap();
+        assert_eq!(converted.satellite_id, "43001");
+        assert_eq!(converted.name, "SX9-MEO-1");
+        assert!((converted.elements.semi_major_axis_km - 14378.0).abs() < 0.01);
+
+        let node = Arc::new(GeospatialCdnNode::new("test-node".to_string()));
+        node.store_orbital(object).await;
+        let mut sync = CdnOrbitalSync::new(node.clone(), 60.0);
+        assert_eq!(sync.track_cache(), 1);
+
+        let propagator = KeplerianPropagator::new();
+        let later = epoch + Duration::hours(3);
+        assert_eq!(sync.push(later, &propagator).await.unwrap(), 1);
+
+        // The cache now describes the same orbit from the later epoch
+        let pushed = node.get_orbital(43_001).unwrap();
+        assert_eq!(pushed.epoch, later);
+        assert_eq!(pushed.country.as_deref(), Some("US"));
+        let refreshed = Tle::parse_lines(&pushed.tle_line1, &pushed.tle_line2).unwrap();
+        assert_eq!(refreshed.international_designator, "18001A");
+        let expected = propagator.propagate(&converted, later).unwrap();
+        let actual = propagator
+            .propagate(&SatelliteOrbit::try_from(&pushed).unwrap(), later)
+            .unwrap();
+        let miss_km = (0..3)
+            .map(|i| (expected.position_eci[i] - actual.position_eci[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(miss_km < 5.0, "{} km", miss_km);
+    }
+}
@@ -3,9 +3,21 @@
 //! Provides configurable constellation parameters, ground station networks,
 //! and orbital mechanics settings.
 
-use crate::error::{OrbitalMechanicsError, Result};
+pub mod migration;
+
+use crate::constants::defaults;
+pub use crate::coordinates::EarthModel;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::force_model::ForceModelSet;
+use crate::fso_analysis::{AdaptiveOptics, GeoArcAvoidance, SkyBackground, ThermalKeepOut};
+use crate::ground_station::{GroundStation, StationAvailability, StationPosition};
+use crate::mount::MountType;
 use crate::propagator::PropagatorType;
+use chrono_tz::Tz;
+use migration::{ConfigMigrator, MigrationReport};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -17,6 +29,10 @@ pub struct ConstellationConfig {
     pub description: String,
     pub version: String,
 
+    /// Config file schema version (see `migration`)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+
     /// Constellation type and parameters
     pub constellation_type: ConstellationType,
     pub orbital_parameters: OrbitalParameters,
@@ -72,6 +88,12 @@ pub struct CustomSatellitePosition {
     pub longitude_of_ascending_node_deg: f64,
     pub argument_of_perigee_deg: f64,
     pub mean_anomaly_deg: f64,
+    /// Orbital plane assignment; requires `slot_id`
+    #[serde(default)]
+    pub plane_id: Option<u32>,
+    /// Slot within the plane
+    #[serde(default)]
+    pub slot_id: Option<u32>,
 }
 
 /// Orbital parameters for the constellation
@@ -167,6 +189,37 @@ pub struct CustomGroundStation {
     pub longitude_deg: f64,
     pub elevation_m: f64,
     pub capabilities: Option<GroundStationCapabilities>,
+    /// Outage and maintenance calendar
+    #[serde(default)]
+    pub availability: StationAvailability,
+    /// Antenna mount type and limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mount: Option<MountType>,
+    /// IANA time zone for station-local reports, e.g. `Pacific/Honolulu`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+}
+
+impl CustomGroundStation {
+    /// Station definition used by visibility and simulation
+    pub fn to_ground_station(&self) -> GroundStation {
+        GroundStation {
+            station_id: self.station_id.clone(),
+            name: self.name.clone(),
+            position: StationPosition {
+                latitude_deg: self.latitude_deg,
+                longitude_deg: self.longitude_deg,
+                elevation_m: self.elevation_m,
+            },
+            availability: self.availability.clone(),
+            mount: self.mount.clone(),
+            min_elevation_deg: self
+                .capabilities
+                .as_ref()
+                .map(|capabilities| capabilities.minimum_elevation_deg),
+            timezone: self.timezone,
+        }
+    }
 }
 
 /// Ground station capabilities
@@ -195,8 +248,67 @@ pub struct AnalysisConfig {
     /// Atmospheric model
     pub atmospheric_model: AtmosphericModel,
 
-    /// Earth model
+    /// Earth model for station positions, geodetic conversion and visibility
     pub earth_model: EarthModel,
+
+    /// Tolerances for special orbit detection
+    #[serde(default)]
+    pub classification_tolerances: ClassificationTolerances,
+
+    /// Accuracy of visibility window rise/set times (bisection refinement)
+    #[serde(default = "default_visibility_edge_accuracy_seconds")]
+    pub visibility_edge_accuracy_seconds: f64,
+
+    /// Perturbations for the numerical propagator
+    #[serde(default)]
+    pub force_models: ForceModelSet,
+}
+
+fn default_schema_version() -> u32 {
+    migration::LEGACY_SCHEMA_VERSION
+}
+
+fn default_visibility_edge_accuracy_seconds() -> f64 {
+    defaults::VISIBILITY_EDGE_ACCURACY_SECONDS
+}
+
+/// Tolerances used when detecting special orbit families
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassificationTolerances {
+    /// Allowed deviation of the J2 nodal rate from the mean solar rate (deg/day)
+    pub sun_synchronous_rate_deg_per_day: f64,
+
+    /// Allowed deviation from the critical inclination (deg)
+    pub critical_inclination_deg: f64,
+
+    /// Allowed relative deviation from half/one sidereal day for Molniya/Tundra
+    pub period_fraction: f64,
+
+    /// Minimum eccentricity for a Molniya orbit
+    pub molniya_min_eccentricity: f64,
+
+    /// Minimum eccentricity for a Tundra orbit
+    pub tundra_min_eccentricity: f64,
+
+    /// Allowed deviation from the J2/J3 frozen eccentricity
+    pub frozen_eccentricity: f64,
+
+    /// Allowed deviation of the argument of perigee from 90° for frozen orbits (deg)
+    pub frozen_argument_of_perigee_deg: f64,
+}
+
+impl Default for ClassificationTolerances {
+    fn default() -> Self {
+        Self {
+            sun_synchronous_rate_deg_per_day: 0.05,
+            critical_inclination_deg: 0.5,
+            period_fraction: 0.02,
+            molniya_min_eccentricity: 0.5,
+            tundra_min_eccentricity: 0.15,
+            frozen_eccentricity: 5e-4,
+            frozen_argument_of_perigee_deg: 5.0,
+        }
+    }
 }
 
 /// Atmospheric models for FSO analysis
@@ -221,19 +333,6 @@ pub struct AtmosphericParameters {
     pub cloud_cover_percent: f64,
 }
 
-/// Earth models for orbital calculations
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum EarthModel {
-    Wgs84,
-    Grs80,
-    Sphere,
-    Custom {
-        equatorial_radius_km: f64,
-        flattening: f64,
-        gravitational_parameter_km3_per_s2: f64,
-    },
-}
-
 /// FSO link analysis configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FsoConfig {
@@ -252,6 +351,22 @@ pub struct FsoConfig {
     /// Atmospheric effects
     pub atmospheric_effects: bool,
     pub turbulence_model: TurbulenceModel,
+
+    /// Sun keep-out constraints on terminal boresights
+    #[serde(default)]
+    pub thermal_keep_outs: Vec<ThermalKeepOut>,
+
+    /// GEO belt and protected-object avoidance for optical uplinks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_arc_avoidance: Option<GeoArcAvoidance>,
+
+    /// Adaptive-optics correction by station ID; unlisted stations are passive
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub adaptive_optics: BTreeMap<String, AdaptiveOptics>,
+
+    /// Daytime sky background at ground receivers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sky_background: Option<SkyBackground>,
 }
 
 /// FSO transmitter configuration
@@ -298,6 +413,7 @@ impl ConstellationConfig {
             name: "LaserLight FSO MEO".to_string(),
             description: "12-satellite MEO constellation for optical communications".to_string(),
             version: "1.0.0".to_string(),
+            schema_version: migration::CURRENT_SCHEMA_VERSION,
 
             constellation_type: ConstellationType::WalkerDelta {
                 total_satellites: 12,
@@ -353,6 +469,9 @@ impl ConstellationConfig {
                 max_propagation_hours: 168.0, // 1 week
                 atmospheric_model: AtmosphericModel::Standard,
                 earth_model: EarthModel::Wgs84,
+                classification_tolerances: ClassificationTolerances::default(),
+                visibility_edge_accuracy_seconds: defaults::VISIBILITY_EDGE_ACCURACY_SECONDS,
+                force_models: ForceModelSet::default(),
             },
 
             fso_config: FsoConfig {
@@ -377,6 +496,10 @@ impl ConstellationConfig {
                 },
                 atmospheric_effects: true,
                 turbulence_model: TurbulenceModel::HufnagelValley,
+                thermal_keep_outs: Vec::new(),
+                geo_arc_avoidance: None,
+                adaptive_optics: BTreeMap::new(),
+                sky_background: None,
             },
         }
     }
@@ -416,6 +539,17 @@ impl ConstellationConfig {
 
         config
     }
+
+    /// Stable hex digest of the serialized configuration
+    ///
+    /// Identical configurations hash identically across builds and machines,
+    /// so stored analysis results can be traced back to the inputs that
+    /// produced them.
+    pub fn content_hash(&self) -> Result<String> {
+        let json = serde_json::to_string(self)?;
+        let hash = murmur3::murmur3_x64_128(&mut json.as_bytes(), 0)?;
+        Ok(format!("{:032x}", hash))
+    }
 }
 
 impl Default for ConstellationConfig {
@@ -424,28 +558,134 @@ impl Default for ConstellationConfig {
     }
 }
 
-/// Load constellation configuration from JSON file
+/// Serialization format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Detect format from the file extension (JSON when unknown or missing)
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// Load constellation configuration, detecting JSON/YAML/TOML by extension
 pub fn load_constellation_config<P: AsRef<Path>>(path: P) -> Result<ConstellationConfig> {
-    let content = fs::read_to_string(path).map_err(|e| {
-        OrbitalMechanicsError::ConfigError(format!("Failed to read config file: {}", e))
-    })?;
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).for_path(path)?;
+
+    parse_constellation_config(&content, ConfigFormat::from_path(path)).for_path(path)
+}
+
+/// Parse constellation configuration, reporting the path of any offending field
+pub fn parse_constellation_config(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<ConstellationConfig> {
+    parse_document(content, format)
+}
+
+/// Deserialize any config document, reporting the path of any offending field
+pub(crate) fn parse_document<T: DeserializeOwned>(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<T> {
+    match format {
+        ConfigFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(content);
+            serde_path_to_error::deserialize(&mut deserializer).map_err(field_error)
+        }
+        ConfigFormat::Yaml => {
+            serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content))
+                .map_err(field_error)
+        }
+        ConfigFormat::Toml => {
+            serde_path_to_error::deserialize(toml::Deserializer::new(content)).map_err(field_error)
+        }
+    }
+}
+
+/// Load constellation configuration, upgrading older schema versions
+///
+/// Returns the config together with a report of what the migration changed.
+pub fn load_constellation_config_with_migration<P: AsRef<Path>>(
+    path: P,
+) -> Result<(ConstellationConfig, MigrationReport)> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).for_path(path)?;
+
+    migrate_constellation_config(
+        &content,
+        ConfigFormat::from_path(path),
+        &ConfigMigrator::new(),
+    )
+    .for_path(path)
+}
 
-    serde_json::from_str(&content)
-        .map_err(|e| OrbitalMechanicsError::ConfigError(format!("Failed to parse config: {}", e)))
+/// Parse constellation configuration, upgrading it with `migrator` first
+pub fn migrate_constellation_config(
+    content: &str,
+    format: ConfigFormat,
+    migrator: &ConfigMigrator,
+) -> Result<(ConstellationConfig, MigrationReport)> {
+    let mut document: serde_json::Value = match format {
+        ConfigFormat::Json => serde_json::from_str(content)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(content)
+            .map_err(|e| OrbitalMechanicsError::config_error(e.to_string()))?,
+        ConfigFormat::Toml => toml::from_str(content)
+            .map_err(|e| OrbitalMechanicsError::config_error(e.to_string()))?,
+    };
+
+    let report = migrator.migrate(&mut document)?;
+    let config = serde_path_to_error::deserialize(document).map_err(field_error)?;
+
+    Ok((config, report))
 }
 
-/// Save constellation configuration to JSON file
+/// Save constellation configuration, choosing JSON/YAML/TOML by extension
 pub fn save_constellation_config<P: AsRef<Path>>(
     config: &ConstellationConfig,
     path: P,
 ) -> Result<()> {
-    let content = serde_json::to_string_pretty(config).map_err(|e| {
-        OrbitalMechanicsError::ConfigError(format!("Failed to serialize config: {}", e))
-    })?;
+    let path = path.as_ref();
+    let content = serialize_constellation_config(config, ConfigFormat::from_path(path))?;
+
+    fs::write(path, content).for_path(path)
+}
 
-    fs::write(path, content).map_err(|e| {
-        OrbitalMechanicsError::ConfigError(format!("Failed to write config file: {}", e))
-    })
+/// Serialize constellation configuration in the given format
+pub fn serialize_constellation_config(
+    config: &ConstellationConfig,
+    format: ConfigFormat,
+) -> Result<String> {
+    match format {
+        ConfigFormat::Json => Ok(serde_json::to_string_pretty(config)?),
+        ConfigFormat::Yaml => serde_yaml::to_string(config)
+            .map_err(|e| OrbitalMechanicsError::config_error(e.to_string())),
+        ConfigFormat::Toml => toml::to_string_pretty(config)
+            .map_err(|e| OrbitalMechanicsError::config_error(e.to_string())),
+    }
+}
+
+/// Convert a path-tracking deserialization error into a field error
+fn field_error<E: std::fmt::Display>(err: serde_path_to_error::Error<E>) -> OrbitalMechanicsError {
+    OrbitalMechanicsError::ConfigFieldError {
+        path: err.path().to_string(),
+        message: err.inner().to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -498,6 +738,24 @@ mod tests {
         assert_eq!(deserialized.name, config.name);
     }
 
+    #[test]
+    fn test_content_hash_ignores_insertion_order() {
+        let stations = ["GS-A", "GS-B", "GS-C", "GS-D"];
+        let with_ao = |order: &[&str]| {
+            let mut config = ConstellationConfig::default();
+            for station_id in order {
+                config
+                    .fso_config
+                    .adaptive_optics
+                    .insert(station_id.to_string(), AdaptiveOptics::new(1.0, 0.1));
+            }
+            config.content_hash().unwrap()
+        };
+        let reversed: Vec<&str> = stations.iter().rev().copied().collect();
+        assert_eq!(with_ao(&stations), with_ao(&reversed));
+        assert_ne!(with_ao(&stations), with_ao(&[]));
+    }
+
     #[test]
     fn test_config_file_operations() {
         let dir = tempdir().unwrap();
@@ -509,4 +767,64 @@ mod tests {
         let loaded_config = load_constellation_config(&file_path).unwrap();
         assert_eq!(loaded_config.name, config.name);
     }
+
+    #[test]
+    fn test_yaml_and_toml_round_trip() {
+        let dir = tempdir().unwrap();
+        let config = ConstellationConfig::default();
+
+        for name in ["config.yaml", "config.yml", "config.toml"] {
+            let file_path = dir.path().join(name);
+            save_constellation_config(&config, &file_path).unwrap();
+
+            let loaded = load_constellation_config(&file_path).unwrap();
+            assert_eq!(loaded.name, config.name);
+            assert_eq!(
+                loaded.orbital_parameters.altitude_km,
+                config.orbital_parameters.altitude_km
+            );
+        }
+
+        let yaml = std::fs::read_to_string(dir.path().join("config.yaml")).unwrap();
+        assert!(yaml.contains("name: LaserLight FSO MEO"));
+    }
+
+    #[test]
+    fn test_field_error_reports_path() {
+        let mut value = serde_json::to_value(ConstellationConfig::default()).unwrap();
+        value["orbital_parameters"]["altitude_km"] = serde_json::json!("high");
+
+        let err = parse_constellation_config(&value.to_string(), ConfigFormat::Json).unwrap_err();
+        match err {
+            OrbitalMechanicsError::ConfigFieldError { path, .. } => {
+                assert_eq!(path, "orbital_parameters.altitude_km");
+            }
+            other => panic!("Expected field error, got {}", other),
+        }
+    }
+
+    #[test]
+    fn test_load_with_migration_upgrades_legacy_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("legacy.json");
+
+        let mut legacy = serde_json::to_value(ConstellationConfig::default()).unwrap();
+        legacy.as_object_mut().unwrap().remove("schema_version");
+        let analysis = legacy["analysis_config"].as_object_mut().unwrap();
+        analysis.remove("classification_tolerances");
+        analysis.remove("visibility_edge_accuracy_seconds");
+        fs::write(&file_path, legacy.to_string()).unwrap();
+
+        let (config, report) = load_constellation_config_with_migration(&file_path).unwrap();
+        assert_eq!(config.schema_version, migration::CURRENT_SCHEMA_VERSION);
+        assert_eq!(report.from_version, migration::LEGACY_SCHEMA_VERSION);
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.contains("classification_tolerances")));
+
+        // Plain loading still accepts the legacy file
+        let plain = load_constellation_config(&file_path).unwrap();
+        assert_eq!(plain.schema_version, migration::LEGACY_SCHEMA_VERSION);
+    }
 }
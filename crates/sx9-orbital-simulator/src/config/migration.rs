@@ -0,0 +1,506 @@
+//! Versioned constellation config schema migration
+//!
+//! Configs carry a `schema_version`. Older files are upgraded one version at a
+//! time by registered steps operating on the untyped document, before it is
+//! deserialized into `ConstellationConfig`.
+//!
+//! Every release that adds a config field bumps `CURRENT_SCHEMA_VERSION` and
+//! registers a step for it, so an upgraded file records each field that took
+//! a default: collections are written out explicitly, and optional fields left
+//! unset are listed in the report.
+
+use super::ClassificationTolerances;
+use crate::constants::defaults;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::ground_station::StationAvailability;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Schema version written by this release
+pub const CURRENT_SCHEMA_VERSION: u32 = 10;
+
+/// Schema version assumed for files without a `schema_version` field
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Edits a document in place, returning a description of each change
+pub type MigrationFn = fn(&mut Value) -> Result<Vec<String>>;
+
+/// Upgrade from `from_version` to `from_version + 1`
+///
+#[derive(Debug, Clone)]
+pub struct MigrationStep {
+    pub from_version: u32,
+    pub description: &'static str,
+    pub apply: MigrationFn,
+}
+
+/// What a migration run changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// One entry per change, prefixed with the step that made it
+    pub changes: Vec<String>,
+}
+
+/// Registry of migration steps
+#[derive(Debug, Clone)]
+pub struct ConfigMigrator {
+    steps: Vec<MigrationStep>,
+    target_version: u32,
+}
+
+impl MigrationReport {
+    /// Whether the document was already at the target version
+    pub fn is_noop(&self) -> bool {
+        self.from_version == self.to_version
+    }
+}
+
+impl ConfigMigrator {
+    /// Create migrator with the built-in steps up to `CURRENT_SCHEMA_VERSION`
+    pub fn new() -> Self {
+        let mut migrator = Self::empty(CURRENT_SCHEMA_VERSION);
+        migrator.register(MigrationStep {
+            from_version: 1,
+            description: "add analysis tolerances",
+            apply: v1_to_v2,
+        });
+        let steps: [(&'static str, MigrationFn); 8] = [
+            ("add Sun keep-outs", v2_to_v3),
+            ("add plane/slot assignments", v3_to_v4),
+            ("add station calendars", v4_to_v5),
+            ("add GEO arc avoidance", v5_to_v6),
+            ("add antenna mounts", v6_to_v7),
+            ("add adaptive optics", v7_to_v8),
+            ("add sky background", v8_to_v9),
+            ("add station time zones", v9_to_v10),
+        ];
+        for (from_version, (description, apply)) in (2..).zip(steps) {
+            migrator.register(MigrationStep {
+                from_version,
+                description,
+                apply,
+            });
+        }
+        migrator
+    }
+
+    /// Create migrator with no steps, upgrading to `target_version`
+    pub fn empty(target_version: u32) -> Self {
+        Self {
+            steps: Vec::new(),
+            target_version,
+        }
+    }
+
+    /// Register a step, replacing any existing step from the same version
+    pub fn register(&mut self, step: MigrationStep) -> &mut Self {
+        self.steps.retain(|s| s.from_version != step.from_version);
+        self.steps.push(step);
+        self.steps.sort_by_key(|s| s.from_version);
+        self
+    }
+
+    /// Schema version migrations upgrade to
+    pub fn target_version(&self) -> u32 {
+        self.target_version
+    }
+
+    /// Upgrade a config document in place to the target version
+    pub fn migrate(&self, document: &mut Value) -> Result<MigrationReport> {
+        let from_version = schema_version(document)?;
+        if from_version > self.target_version {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Config schema version {} is newer than supported version {}",
+                from_version, self.target_version
+            )));
+        }
+
+        let mut report = MigrationReport {
+            from_version,
+            to_version: from_version,
+            changes: Vec::new(),
+        };
+
+        while report.to_version < self.target_version {
+            let version = report.to_version;
+            let step = self
+                .steps
+                .iter()
+                .find(|s| s.from_version == version)
+                .ok_or_else(|| {
+                    OrbitalMechanicsError::config_error(format!(
+                        "No migration registered from config schema version {}",
+                        version
+                    ))
+                })?;
+
+            for change in (step.apply)(document)? {
+                report.changes.push(format!(
+                    "v{}->v{} ({}): {}",
+                    version,
+                    version + 1,
+                    step.description,
+                    change
+                ));
+            }
+            report.to_version = version + 1;
+        }
+
+        root_object(document)?.insert("schema_version".to_string(), report.to_version.into());
+        Ok(report)
+    }
+}
+
+impl Default for ConfigMigrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Schema version recorded in a config document
+pub fn schema_version(document: &Value) -> Result<u32> {
+    match document.get("schema_version") {
+        None | Some(Value::Null) => Ok(LEGACY_SCHEMA_VERSION),
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| OrbitalMechanicsError::ConfigFieldError {
+                path: "schema_version".to_string(),
+                message: format!("expected a non-negative integer, got {}", value),
+            }),
+    }
+}
+
+fn root_object(document: &mut Value) -> Result<&mut Map<String, Value>> {
+    document
+        .as_object_mut()
+        .ok_or_else(|| OrbitalMechanicsError::config_error("Config document must be a mapping"))
+}
+
+/// v1 predates special orbit detection and visibility edge refinement
+fn v1_to_v2(document: &mut Value) -> Result<Vec<String>> {
+    let mut changes = Vec::new();
+    let Some(analysis) = root_object(document)?
+        .get_mut("analysis_config")
+        .and_then(Value::as_object_mut)
+    else {
+        return Ok(changes);
+    };
+
+    if !analysis.contains_key("classification_tolerances") {
+        analysis.insert(
+            "classification_tolerances".to_string(),
+            serde_json::to_value(ClassificationTolerances::default())?,
+        );
+        changes.push("analysis_config.classification_tolerances: added defaults".to_string());
+    }
+
+    if !analysis.contains_key("visibility_edge_accuracy_seconds") {
+        analysis.insert(
+            "visibility_edge_accuracy_seconds".to_string(),
+            defaults::VISIBILITY_EDGE_ACCURACY_SECONDS.into(),
+        );
+        changes.push(format!(
+            "analysis_config.visibility_edge_accuracy_seconds: set to {} s",
+            defaults::VISIBILITY_EDGE_ACCURACY_SECONDS
+        ));
+    }
+
+    Ok(changes)
+}
+
+/// `fso_config` mapping, when the document has one
+fn fso_config(document: &mut Value) -> Result<Option<&mut Map<String, Value>>> {
+    Ok(root_object(document)?
+        .get_mut("fso_config")
+        .and_then(Value::as_object_mut))
+}
+
+/// Custom ground station mappings with their index
+fn custom_stations(document: &mut Value) -> Result<Vec<(usize, &mut Map<String, Value>)>> {
+    Ok(root_object(document)?
+        .get_mut("ground_station_config")
+        .and_then(|stations| stations.get_mut("custom_stations"))
+        .and_then(Value::as_array_mut)
+        .map(|stations| {
+            stations
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, station)| Some((index, station.as_object_mut()?)))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Fill in `key` at `path` when missing, describing what the default means
+///
+/// `default` is written to the document; `None` leaves an optional field unset
+/// and only reports it.
+fn default_field(
+    object: &mut Map<String, Value>,
+    path: &str,
+    key: &str,
+    default: Option<Value>,
+    meaning: &str,
+) -> Option<String> {
+    if object.contains_key(key) {
+        return None;
+    }
+    if let Some(default) = default {
+        object.insert(key.to_string(), default);
+    }
+    Some(format!("{}.{}: {}", path, key, meaning))
+}
+
+/// v2 predates Sun keep-out constraints on optical terminals
+fn v2_to_v3(document: &mut Value) -> Result<Vec<String>> {
+    Ok(fso_config(document)?
+        .and_then(|fso| {
+            default_field(
+                fso,
+                "fso_config",
+                "thermal_keep_outs",
+                Some(Value::Array(Vec::new())),
+                "added empty list, no Sun keep-out",
+            )
+        })
+        .into_iter()
+        .collect())
+}
+
+/// v3 predates plane and slot assignments of custom satellites
+fn v3_to_v4(document: &mut Value) -> Result<Vec<String>> {
+    let mut changes = Vec::new();
+    let Some(satellites) = root_object(document)?
+        .get_mut("constellation_type")
+        .and_then(|constellation| constellation.get_mut("Custom"))
+        .and_then(|custom| custom.get_mut("satellites"))
+        .and_then(Value::as_array_mut)
+    else {
+        return Ok(changes);
+    };
+
+    for (index, satellite) in satellites.iter_mut().enumerate() {
+        let Some(satellite) = satellite.as_object_mut() else {
+            continue;
+        };
+        let path = format!("constellation_type.Custom.satellites[{}]", index);
+        for key in ["plane_id", "slot_id"] {
+            changes.extend(default_field(
+                satellite,
+                &path,
+                key,
+                None,
+                "left unassigned",
+            ));
+        }
+    }
+    Ok(changes)
+}
+
+/// v4 predates ground station outage and maintenance calendars
+fn v4_to_v5(document: &mut Value) -> Result<Vec<String>> {
+    let availability = serde_json::to_value(StationAvailability::default())?;
+    Ok(custom_stations(document)?
+        .into_iter()
+        .filter_map(|(index, station)| {
+            default_field(
+                station,
+                &format!("ground_station_config.custom_stations[{}]", index),
+                "availability",
+                Some(availability.clone()),
+                "added empty calendar, always available",
+            )
+        })
+        .collect())
+}
+
+/// v5 predates GEO arc avoidance for optical uplinks
+fn v5_to_v6(document: &mut Value) -> Result<Vec<String>> {
+    Ok(fso_config(document)?
+        .and_then(|fso| {
+            default_field(
+                fso,
+                "fso_config",
+                "geo_arc_avoidance",
+                None,
+                "left unset, no GEO arc constraint",
+            )
+        })
+        .into_iter()
+        .collect())
+}
+
+/// v6 predates antenna mount models
+fn v6_to_v7(document: &mut Value) -> Result<Vec<String>> {
+    Ok(custom_stations(document)?
+        .into_iter()
+        .filter_map(|(index, station)| {
+            default_field(
+                station,
+                &format!("ground_station_config.custom_stations[{}]", index),
+                "mount",
+                None,
+                "left unset, no mount limits",
+            )
+        })
+        .collect())
+}
+
+/// v7 predates adaptive-optics correction at ground stations
+fn v7_to_v8(document: &mut Value) -> Result<Vec<String>> {
+    Ok(fso_config(document)?
+        .and_then(|fso| {
+            default_field(
+                fso,
+                "fso_config",
+                "adaptive_optics",
+                Some(Value::Object(Map::new())),
+                "added empty map, all stations passive",
+            )
+        })
+        .into_iter()
+        .collect())
+}
+
+/// v8 predates the daytime sky background penalty
+fn v8_to_v9(document: &mut Value) -> Result<Vec<String>> {
+    Ok(fso_config(document)?
+        .and_then(|fso| {
+            default_field(
+                fso,
+                "fso_config",
+                "sky_background",
+                None,
+                "left unset, no daytime penalty",
+            )
+        })
+        .into_iter()
+        .collect())
+}
+
+/// v9 predates station-local time zones
+fn v9_to_v10(document: &mut Value) -> Result<Vec<String>> {
+    Ok(custom_stations(document)?
+        .into_iter()
+        .filter_map(|(index, station)| {
+            default_field(
+                station,
+                &format!("ground_station_config.custom_stations[{}]", index),
+                "timezone",
+                None,
+                "left unset, reports in UTC only",
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_legacy_document_upgraded() {
+        let mut document = json!({
+            "name": "Legacy",
+            "analysis_config": { "time_step_seconds": 60.0 }
+        });
+
+        let report = ConfigMigrator::new().migrate(&mut document).unwrap();
+        assert_eq!(report.from_version, LEGACY_SCHEMA_VERSION);
+        assert_eq!(report.to_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(report.changes.len(), 2);
+        assert_eq!(document["schema_version"], json!(CURRENT_SCHEMA_VERSION));
+        assert_eq!(
+            document["analysis_config"]["visibility_edge_accuracy_seconds"],
+            json!(defaults::VISIBILITY_EDGE_ACCURACY_SECONDS)
+        );
+
+        // Already current: nothing to do
+        let report = ConfigMigrator::new().migrate(&mut document).unwrap();
+        assert!(report.is_noop());
+    }
+
+    #[test]
+    fn test_v2_document_gets_later_fields() {
+        let mut document = serde_json::to_value(crate::ConstellationConfig::default()).unwrap();
+        document["schema_version"] = json!(2);
+        let fso = document["fso_config"].as_object_mut().unwrap();
+        fso.remove("thermal_keep_outs");
+        fso.remove("adaptive_optics");
+        document["constellation_type"] = json!({ "Custom": { "satellites": [{
+            "satellite_id": "SAT-1", "name": "SAT-1", "semi_major_axis_km": 7000.0,
+            "eccentricity": 0.0, "inclination_deg": 53.0,
+            "longitude_of_ascending_node_deg": 0.0, "argument_of_perigee_deg": 0.0,
+            "mean_anomaly_deg": 0.0
+        }]}});
+        document["ground_station_config"]["custom_stations"] = json!([{
+            "station_id": "GS-1", "name": "GS-1", "latitude_deg": 21.3,
+            "longitude_deg": -157.9, "elevation_m": 10.0, "capabilities": null
+        }]);
+
+        let report = ConfigMigrator::new().migrate(&mut document).unwrap();
+        assert_eq!(report.from_version, 2);
+        assert_eq!(report.to_version, CURRENT_SCHEMA_VERSION);
+        // Every step from v2 reports the field it defaulted
+        for version in 2..CURRENT_SCHEMA_VERSION {
+            let prefix = format!("v{}->v{} ", version, version + 1);
+            assert!(
+                report.changes.iter().any(|c| c.starts_with(&prefix)),
+                "no change from {}: {:?}",
+                prefix,
+                report.changes
+            );
+        }
+        assert_eq!(report.changes.len(), 9);
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.contains("custom_stations[0].timezone: left unset")));
+
+        // Collections are written out, optional fields stay absent
+        assert_eq!(document["fso_config"]["thermal_keep_outs"], json!([]));
+        assert_eq!(document["fso_config"]["adaptive_optics"], json!({}));
+        let station = &document["ground_station_config"]["custom_stations"][0];
+        assert_eq!(station["availability"]["outages"], json!([]));
+        assert!(station.get("mount").is_none());
+
+        let config: crate::ConstellationConfig = serde_json::from_value(document).unwrap();
+        assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_registered_steps_and_version_errors() {
+        fn rename_name(document: &mut Value) -> Result<Vec<String>> {
+            let root = root_object(document)?;
+            if let Some(name) = root.remove("title") {
+                root.insert("name".to_string(), name);
+                return Ok(vec!["title: renamed to name".to_string()]);
+            }
+            Ok(Vec::new())
+        }
+
+        let mut migrator = ConfigMigrator::empty(3);
+        migrator.register(MigrationStep {
+            from_version: 2,
+            description: "rename title",
+            apply: rename_name,
+        });
+
+        let mut document = json!({ "schema_version": 2, "title": "Renamed" });
+        let report = migrator.migrate(&mut document).unwrap();
+        assert_eq!(document["name"], json!("Renamed"));
+        assert_eq!(report.changes.len(), 1);
+
+        // No step registered from v1
+        let mut legacy = json!({ "title": "Old" });
+        assert!(migrator.migrate(&mut legacy).is_err());
+
+        // Newer than supported
+        let mut future = json!({ "schema_version": 4 });
+        assert!(migrator.migrate(&mut future).is_err());
+    }
+}
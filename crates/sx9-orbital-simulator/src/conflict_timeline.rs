@@ -0,0 +1,352 @@
+//! Scheduler conflict timelines for Gantt-style views
+//!
+//! One lane per station lists every requested contact, whether the
+//! scheduler booked it, and the spans where it clashes with something else:
+//! another requested contact on the same antenna, an outage or maintenance
+//! window from the station calendar, or a mount limit found by the
+//! visibility calculator. The timeline is exported as JSON for direct
+//! rendering, or as CSV with one row per conflict span; contacts without
+//! conflicts get a single row with the conflict columns empty.
+
+use crate::error::{Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::ground_station::{GroundStation, GroundStationNetwork};
+use crate::pass_scoring::ScoredPass;
+use crate::visibility::{MountConflictKind, VisibilityWindow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Why part of a requested contact cannot be served
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConflictReason {
+    /// Another requested contact wants the same antenna
+    AntennaBusy { satellite_id: String },
+    /// Station outage or maintenance window
+    Maintenance { reason: String },
+    /// Transit near zenith the mount cannot follow
+    Keyhole,
+    /// Mount unwinding its cable wrap
+    CableWrap,
+}
+
+/// Portion of a contact affected by one conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictSpan {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub reason: ConflictReason,
+}
+
+/// Requested contact on a station lane
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineContact {
+    pub satellite_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Whether the scheduler booked this contact
+    pub scheduled: bool,
+    /// Conflict spans in start-time order
+    pub conflicts: Vec<ConflictSpan>,
+}
+
+/// Requested contacts of one station, by start time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationLane {
+    pub station_id: String,
+    pub contacts: Vec<TimelineContact>,
+}
+
+/// Requested contacts and their conflicts, one lane per station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictTimeline {
+    /// Lanes ordered by station ID
+    pub lanes: Vec<StationLane>,
+}
+
+impl ConflictTimeline {
+    /// Timeline of `requested` contacts, marking those booked in `schedule`
+    ///
+    /// Station calendars come from `network`; stations missing from it are
+    /// taken as always available.
+    pub fn build(
+        requested: &[VisibilityWindow],
+        schedule: &[ScoredPass],
+        network: &GroundStationNetwork,
+    ) -> Self {
+        let mut by_station: BTreeMap<&str, Vec<&VisibilityWindow>> = BTreeMap::new();
+        for window in requested {
+            by_station
+                .entry(window.station_id.as_str())
+                .or_default()
+                .push(window);
+        }
+
+        let lanes = by_station
+            .into_iter()
+            .map(|(station_id, mut windows)| {
+                windows.sort_by_key(|window| window.start_time);
+                let station = network.get_station(station_id);
+                let contacts = windows
+                    .iter()
+                    .map(|window| TimelineContact {
+                        satellite_id: window.satellite_id.clone(),
+                        start_time: window.start_time,
+                        end_time: window.end_time,
+                        scheduled: schedule.iter().any(|contact| {
+                            contact.window.satellite_id == window.satellite_id
+                                && contact.window.station_id == window.station_id
+                                && contact.window.start_time == window.start_time
+                                && contact.window.end_time == window.end_time
+                        }),
+                        conflicts: contact_conflicts(window, &windows, station),
+                    })
+                    .collect();
+                StationLane {
+                    station_id: station_id.to_string(),
+                    contacts,
+                }
+            })
+            .collect();
+
+        Self { lanes }
+    }
+
+    /// Conflict spans across all lanes
+    pub fn conflict_count(&self) -> usize {
+        self.lanes
+            .iter()
+            .flat_map(|lane| &lane.contacts)
+            .map(|contact| contact.conflicts.len())
+            .sum()
+    }
+
+    /// Render the timeline as CSV, one row per conflict span
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// Render the timeline as CSV in the timestamp format and naming of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let mut csv = String::from(
+            "station_id,satellite_id,contact_start,contact_end,scheduled,\
+             conflict_start,conflict_end,reason,detail\n",
+        );
+        let time = |t: DateTime<Utc>| options.timestamp(t);
+        for lane in &self.lanes {
+            for contact in &lane.contacts {
+                let prefix = format!(
+                    "{},{},{},{},{}",
+                    lane.station_id,
+                    options.satellite_name(&contact.satellite_id),
+                    time(contact.start_time),
+                    time(contact.end_time),
+                    contact.scheduled
+                );
+                if contact.conflicts.is_empty() {
+                    let _ = writeln!(csv, "{},,,,", prefix);
+                }
+                for conflict in &contact.conflicts {
+                    let (reason, detail) = match &conflict.reason {
+                        ConflictReason::AntennaBusy { satellite_id } => {
+                            ("antenna_busy", options.satellite_name(satellite_id))
+                        }
+                        ConflictReason::Maintenance { reason } => ("maintenance", reason.as_str()),
+                        ConflictReason::Keyhole => ("keyhole", ""),
+                        ConflictReason::CableWrap => ("cable_wrap", ""),
+                    };
+                    let _ = writeln!(
+                        csv,
+                        "{},{},{},{},{}",
+                        prefix,
+                        time(conflict.start_time),
+                        time(conflict.end_time),
+                        reason,
+                        detail.replace(',', ";")
+                    );
+                }
+            }
+        }
+        csv
+    }
+
+    /// Render the timeline as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the timeline to a CSV file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write the timeline to a CSV file in the timestamp format and naming of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv_with(options)).for_path(path)
+    }
+
+    /// Write the timeline to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?).for_path(path)
+    }
+}
+
+/// Conflicts of `window` with its lane mates, the station calendar and the mount
+fn contact_conflicts(
+    window: &VisibilityWindow,
+    lane: &[&VisibilityWindow],
+    station: Option<&GroundStation>,
+) -> Vec<ConflictSpan> {
+    let mut conflicts: Vec<ConflictSpan> = lane
+        .iter()
+        .filter(|other| !std::ptr::eq(**other, window))
+        .filter_map(|other| {
+            let start = window.start_time.max(other.start_time);
+            let end = window.end_time.min(other.end_time);
+            (start < end).then(|| ConflictSpan {
+                start_time: start,
+                end_time: end,
+                reason: ConflictReason::AntennaBusy {
+                    satellite_id: other.satellite_id.clone(),
+                },
+            })
+        })
+        .collect();
+
+    if let Some(station) = station {
+        let calendar = station
+            .availability
+            .unavailable_intervals(window.start_time, window.end_time);
+        conflicts.extend(calendar.into_iter().map(|interval| ConflictSpan {
+            start_time: interval.start_time.max(window.start_time),
+            end_time: interval.end_time.min(window.end_time),
+            reason: ConflictReason::Maintenance {
+                reason: interval.reason,
+            },
+        }));
+    }
+
+    conflicts.extend(window.mount_conflicts.iter().map(|conflict| ConflictSpan {
+        start_time: conflict.start_time,
+        end_time: conflict.end_time,
+        reason: match conflict.kind {
+            MountConflictKind::Keyhole => ConflictReason::Keyhole,
+            MountConflictKind::CableWrap => ConflictReason::CableWrap,
+        },
+    }));
+    conflicts.sort_by_key(|conflict| conflict.start_time);
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::StationOutage;
+    use crate::pass_scoring::{schedule_contacts, DefaultPassScorer};
+    use crate::visibility::{MountConflict, PassType};
+    use chrono::{Duration, TimeZone};
+
+    fn window(satellite_id: &str, start: DateTime<Utc>, minutes: i64) -> VisibilityWindow {
+        VisibilityWindow {
+            satellite_id: satellite_id.to_string(),
+            station_id: "GS-1".to_string(),
+            start_time: start,
+            end_time: start + Duration::minutes(minutes),
+            duration_seconds: minutes as f64 * 60.0,
+            max_elevation_time: start + Duration::minutes(minutes / 2),
+            max_elevation_deg: 45.0,
+            min_range_km: 9000.0,
+            mean_range_km: 10000.0,
+            azimuth_span_deg: 120.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_conflict_timeline() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let mut keyhole = window("SAT-B", t0 + Duration::minutes(5), 10);
+        keyhole.max_elevation_deg = 88.0;
+        keyhole.mount_conflicts.push(MountConflict {
+            kind: MountConflictKind::Keyhole,
+            start_time: t0 + Duration::minutes(9),
+            end_time: t0 + Duration::minutes(11),
+        });
+        let requested = vec![
+            window("SAT-A", t0, 10),
+            keyhole,
+            window("SAT-C", t0 + Duration::minutes(60), 10),
+        ];
+        let schedule = schedule_contacts(requested.clone(), &DefaultPassScorer::new());
+
+        let mut network = GroundStationNetwork::new();
+        network.add_station(
+            GroundStation::builder()
+                .station_id("GS-1")
+                .name("Station 1")
+                .position(40.0, -105.0, 1600.0)
+                .outage(StationOutage {
+                    start_time: t0 + Duration::minutes(65),
+                    end_time: t0 + Duration::minutes(90),
+                    reason: "Mirror recoat, phase 2".to_string(),
+                })
+                .build()
+                .unwrap(),
+        );
+
+        let timeline = ConflictTimeline::build(&requested, &schedule, &network);
+        assert_eq!(timeline.lanes.len(), 1);
+        let contacts = &timeline.lanes[0].contacts;
+        assert_eq!(contacts.len(), 3);
+        // The high pass wins the antenna; both overlapping passes show the clash
+        assert!(!contacts[0].scheduled && contacts[1].scheduled);
+        assert_eq!(
+            contacts[0].conflicts[0].reason,
+            ConflictReason::AntennaBusy {
+                satellite_id: "SAT-B".to_string()
+            }
+        );
+        assert_eq!(
+            contacts[0].conflicts[0].start_time,
+            t0 + Duration::minutes(5)
+        );
+        assert_eq!(
+            contacts[0].conflicts[0].end_time,
+            t0 + Duration::minutes(10)
+        );
+        let reasons: Vec<&ConflictReason> =
+            contacts[1].conflicts.iter().map(|c| &c.reason).collect();
+        assert_eq!(reasons.len(), 2);
+        assert_eq!(reasons[1], &ConflictReason::Keyhole);
+        // Maintenance is clipped to the contact
+        assert_eq!(
+            contacts[2].conflicts[0].start_time,
+            t0 + Duration::minutes(65)
+        );
+        assert_eq!(
+            contacts[2].conflicts[0].end_time,
+            t0 + Duration::minutes(70)
+        );
+        assert_eq!(timeline.conflict_count(), 4);
+
+        let csv = timeline.to_csv();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains(",keyhole,"));
+        assert!(csv.contains("maintenance,Mirror recoat; phase 2"));
+        let json: serde_json::Value = serde_json::from_str(&timeline.to_json().unwrap()).unwrap();
+        assert_eq!(
+            json["lanes"][0]["contacts"][1]["conflicts"][1]["reason"]["type"],
+            "keyhole"
+        );
+    }
+}
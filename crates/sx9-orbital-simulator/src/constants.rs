@@ -1,21 +1,32 @@
 //! Physical and mathematical constants for orbital mechanics
 
 use std::f64::consts::PI;
+use sx9_orbital_core::constants as orbital_core;
 
 /// Mathematical constants
-pub const TWO_PI: f64 = 2.0 * PI;
+pub const TWO_PI: f64 = orbital_core::TWO_PI;
 pub const HALF_PI: f64 = PI / 2.0;
 pub const DEG_TO_RAD: f64 = PI / 180.0;
 pub const RAD_TO_DEG: f64 = 180.0 / PI;
 
 /// Earth physical constants (WGS84)
-pub const EARTH_RADIUS_KM: f64 = 6378.137; // Equatorial radius
+pub const EARTH_RADIUS_KM: f64 = orbital_core::EARTH_RADIUS_KM; // Equatorial radius
 pub const EARTH_POLAR_RADIUS_KM: f64 = 6356.7523142; // Polar radius
 pub const EARTH_FLATTENING: f64 = 1.0 / 298.257223563; // WGS84 flattening
-pub const EARTH_MU: f64 = 398600.4418; // Gravitational parameter km³/s²
-pub const EARTH_J2: f64 = 1.08262668e-3; // Second zonal harmonic
+pub const EARTH_MU: f64 = orbital_core::EARTH_MU; // Gravitational parameter km³/s²
+pub const EARTH_J2: f64 = orbital_core::EARTH_J2; // Second zonal harmonic
+pub const EARTH_J3: f64 = -2.53265648e-6; // Third zonal harmonic
+pub const EARTH_J4: f64 = -1.61962159e-6; // Fourth zonal harmonic
 pub const EARTH_ROTATION_RATE: f64 = 7.2921159e-5; // rad/s
 
+/// GRS80 ellipsoid (same equatorial radius as WGS84)
+pub const GRS80_FLATTENING: f64 = 1.0 / 298.257222101;
+pub const GRS80_MU: f64 = 398600.5; // km³/s²
+
+/// Third-body gravitational parameters
+pub const SUN_MU: f64 = 1.32712440018e11; // km³/s²
+pub const MOON_MU: f64 = 4902.800066; // km³/s²
+
 /// Time constants
 pub const SIDEREAL_DAY_SECONDS: f64 = 86164.0905; // seconds
 pub const SOLAR_DAY_SECONDS: f64 = 86400.0; // seconds
@@ -54,10 +65,15 @@ pub const STARLINK_ALTITUDE_KM: f64 = 550.0; // Starlink constellation
 pub const GPS_ALTITUDE_KM: f64 = 20200.0; // GPS satellites
 pub const LASERLIGHT_FSO_ALTITUDE_KM: f64 = 8000.0; // LaserLight FSO MEO
 
+/// Special orbit parameters
+pub const SUN_SYNCHRONOUS_NODAL_RATE_DEG_PER_DAY: f64 = 360.0 / 365.2421897; // Mean solar motion
+pub const CRITICAL_INCLINATION_DEG: f64 = 63.4349488; // Zero apsidal drift under J2
+pub const BROUWER_CRITICAL_MARGIN: f64 = orbital_core::BROUWER_CRITICAL_MARGIN; // Smallest |1 - 5cos²i| for Brouwer theory
+
 /// Propagation and numerical constants
 pub const SGP4_MAX_DAYS: f64 = 365.25; // Maximum SGP4 propagation period
-pub const KEPLER_ITERATION_LIMIT: usize = 50; // Maximum iterations for Kepler's equation
-pub const KEPLER_TOLERANCE: f64 = 1e-12; // Convergence tolerance
+pub const KEPLER_ITERATION_LIMIT: usize = orbital_core::KEPLER_ITERATION_LIMIT; // Maximum iterations for Kepler's equation
+pub const KEPLER_TOLERANCE: f64 = orbital_core::KEPLER_TOLERANCE; // Convergence tolerance
 pub const NEWTON_RAPHSON_TOLERANCE: f64 = 1e-15; // Newton-Raphson tolerance
 
 /// Free Space Optical (FSO) constants
@@ -90,6 +106,7 @@ pub const MAX_ECCENTRICITY: f64 = 0.99; // Maximum allowed eccentricity
 pub const MAX_INCLINATION_DEG: f64 = 180.0; // Maximum inclination
 pub const MAX_SEMI_MAJOR_AXIS_KM: f64 = 1e8; // Maximum semi-major axis
 pub const MIN_SEMI_MAJOR_AXIS_KM: f64 = EARTH_RADIUS_KM + 160.0; // Minimum altitude
+pub const MAX_CONSTELLATION_SATELLITES: usize = 100_000; // Largest generated pattern
 
 /// Default configuration values
 pub mod defaults {
@@ -102,6 +119,12 @@ pub mod defaults {
     /// Default ground station minimum elevation
     pub const MIN_ELEVATION_DEG: f64 = 10.0;
 
+    /// Default accuracy of refined visibility window edges
+    pub const VISIBILITY_EDGE_ACCURACY_SECONDS: f64 = 1.0;
+
+    /// Default sampling step for thermal keep-out checks within a pass
+    pub const KEEP_OUT_SAMPLE_SECONDS: f64 = 10.0;
+
     /// Default FSO transmit power
     pub const FSO_TRANSMIT_POWER_W: f64 = 1.0;
 
@@ -119,7 +142,7 @@ pub mod validation {
 
     /// Validate semi-major axis
     pub fn validate_semi_major_axis(a_km: f64) -> Result<()> {
-        if a_km < MIN_SEMI_MAJOR_AXIS_KM || a_km > MAX_SEMI_MAJOR_AXIS_KM {
+        if !(MIN_SEMI_MAJOR_AXIS_KM..=MAX_SEMI_MAJOR_AXIS_KM).contains(&a_km) {
             return Err(OrbitalMechanicsError::invalid_elements(
                 format!("Semi-major axis {:.1} km outside valid range [{:.1}, {:.1}] km",
                     a_km, MIN_SEMI_MAJOR_AXIS_KM, MAX_SEMI_MAJOR_AXIS_KM)
@@ -130,7 +153,7 @@ pub mod validation {
 
     /// Validate eccentricity
     pub fn validate_eccentricity(e: f64) -> Result<()> {
-        if e < 0.0 || e >= MAX_ECCENTRICITY {
+        if !(0.0..MAX_ECCENTRICITY).contains(&e) {
             return Err(OrbitalMechanicsError::invalid_elements(
                 format!("Eccentricity {:.6} outside valid range [0.0, {:.2})",
                     e, MAX_ECCENTRICITY)
@@ -141,7 +164,7 @@ pub mod validation {
 
     /// Validate inclination
     pub fn validate_inclination(i_deg: f64) -> Result<()> {
-        if i_deg < 0.0 || i_deg > MAX_INCLINATION_DEG {
+        if !(0.0..=MAX_INCLINATION_DEG).contains(&i_deg) {
             return Err(OrbitalMechanicsError::invalid_elements(
                 format!("Inclination {:.3}° outside valid range [0.0, {:.1}]°",
                     i_deg, MAX_INCLINATION_DEG)
@@ -152,7 +175,7 @@ pub mod validation {
 
     /// Validate angle in degrees (0-360)
     pub fn validate_angle_0_360(angle_deg: f64, name: &str) -> Result<()> {
-        if angle_deg < 0.0 || angle_deg >= 360.0 {
+        if !(0.0..360.0).contains(&angle_deg) {
             return Err(OrbitalMechanicsError::invalid_elements(
                 format!("{} {:.3}° outside valid range [0.0, 360.0)°", name, angle_deg)
             ));
@@ -161,6 +184,8 @@ pub mod validation {
     }
 
     /// Validate all basic orbital elements
+    ///
+    /// NaN fails every range check, so non-finite values are rejected too.
     pub fn validate_orbital_elements(
         a_km: f64,
         e: f64,
@@ -204,6 +229,9 @@ mod tests {
         assert!(validate_eccentricity(-0.1).is_err()); // Negative
         assert!(validate_inclination(200.0).is_err()); // Too high
         assert!(validate_angle_0_360(400.0, "test").is_err()); // Out of range
+        assert!(validate_semi_major_axis(f64::NAN).is_err());
+        assert!(validate_eccentricity(f64::NAN).is_err());
+        assert!(validate_angle_0_360(f64::INFINITY, "test").is_err());
     }
 
     #[test]
@@ -1,13 +1,59 @@
 //! Satellite constellation design and management
 
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc, Timelike, Datelike};
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use crate::config::{ConstellationConfig, ConstellationType, CustomSatellitePosition, PredefinedPattern};
-use crate::orbit::{SatelliteOrbit, OrbitalElements};
+use crate::orbit::{SatelliteOrbit, SatelliteState, OrbitalElements};
 use crate::constants::*;
-use crate::error::{OrbitalMechanicsError, Result};
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
 use crate::propagator::OrbitalPropagator;
+use crate::tle::Tle;
+
+/// Orbital plane index within a constellation pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PlaneId(pub u32);
+
+/// Slot index within an orbital plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlotId(pub u32);
+
+/// Plane/slot a satellite is assigned to and the orbit that defines the slot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlotAssignment {
+    pub plane: PlaneId,
+    pub slot: SlotId,
+    /// Elements of the slot center at `nominal_epoch`
+    pub nominal_elements: OrbitalElements,
+    pub nominal_epoch: DateTime<Utc>,
+}
+
+impl SlotAssignment {
+    /// Orbit of the slot center, for two-body propagation
+    pub fn nominal_orbit(&self, satellite_id: &str) -> SatelliteOrbit {
+        SatelliteOrbit::new(
+            satellite_id.to_string(),
+            format!("{} slot {}", self.plane, self.slot),
+            self.nominal_elements.clone(),
+            self.nominal_epoch,
+        )
+    }
+}
+
+impl std::fmt::Display for PlaneId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "P{}", self.0)
+    }
+}
+
+impl std::fmt::Display for SlotId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "S{}", self.0)
+    }
+}
 
 /// Satellite constellation management
 #[derive(Debug, Clone)]
@@ -20,6 +66,15 @@ pub struct Constellation {
     /// Satellites in the constellation
     satellites: HashMap<String, SatelliteOrbit>,
 
+    /// Satellite IDs by group tag, e.g. "spares" or "plane-3"
+    groups: BTreeMap<String, BTreeSet<String>>,
+
+    /// Alternative names by naming scheme (e.g. "norad"), then by satellite ID
+    aliases: BTreeMap<String, BTreeMap<String, String>>,
+
+    /// Satellite ID of every alias, across schemes
+    alias_ids: HashMap<String, String>,
+
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
 
@@ -38,6 +93,9 @@ impl Constellation {
             description,
             constellation_type,
             satellites: HashMap::new(),
+            groups: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            alias_ids: HashMap::new(),
             created_at: now,
             updated_at: now,
         }
@@ -85,10 +143,22 @@ impl Constellation {
         phasing_parameter: usize,
         orbital_params: &crate::config::OrbitalParameters,
     ) -> Result<()> {
+        if total_satellites == 0
+            || num_planes == 0
+            || total_satellites > MAX_CONSTELLATION_SATELLITES
+        {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Walker Delta pattern needs at least one plane and between 1 and {} satellites, \
+                 got {} satellites in {} planes",
+                MAX_CONSTELLATION_SATELLITES, total_satellites, num_planes
+            )));
+        }
         if total_satellites % num_planes != 0 {
-            return Err(OrbitalMechanicsError::config_error(
-                "Total satellites must be divisible by number of planes for Walker Delta pattern"
-            ));
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Walker Delta pattern needs the number of planes to divide the total, \
+                 got {} satellites in {} planes",
+                total_satellites, num_planes
+            )));
         }
 
         let satellites_per_plane = total_satellites / num_planes;
@@ -124,7 +194,8 @@ impl Constellation {
                     phased_ma,
                 )?;
 
-                let satellite = SatelliteOrbit::new(satellite_id, name, elements, epoch);
+                let satellite = SatelliteOrbit::new(satellite_id, name, elements, epoch)
+                    .with_slot(PlaneId(plane_idx as u32), SlotId(sat_idx as u32));
                 self.add_satellite(satellite)?;
             }
         }
@@ -146,13 +217,26 @@ impl Constellation {
                 sat_config.mean_anomaly_deg,
             )?;
 
-            let satellite = SatelliteOrbit::new(
+            let mut satellite = SatelliteOrbit::new(
                 sat_config.satellite_id.clone(),
                 sat_config.name.clone(),
                 elements,
                 epoch,
             );
 
+            match (sat_config.plane_id, sat_config.slot_id) {
+                (Some(plane), Some(slot)) => {
+                    satellite = satellite.with_slot(PlaneId(plane), SlotId(slot));
+                }
+                (None, None) => {}
+                _ => {
+                    return Err(OrbitalMechanicsError::config_error(format!(
+                        "Satellite {} must set both plane_id and slot_id, or neither",
+                        sat_config.satellite_id
+                    )));
+                }
+            }
+
             self.add_satellite(satellite)?;
         }
 
@@ -247,31 +331,143 @@ impl Constellation {
                 format!("Satellite {} already exists in constellation", satellite.satellite_id)
             ));
         }
+        if let Some(owner) = self.alias_ids.get(&satellite.satellite_id) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Satellite ID {} is already an alias of {}",
+                satellite.satellite_id, owner
+            )));
+        }
+        if let Some(assignment) = &satellite.slot {
+            if let Some(occupant) = self.satellite_in_slot(assignment.plane, assignment.slot) {
+                return Err(OrbitalMechanicsError::config_error(format!(
+                    "Slot {}/{} is already assigned to {}",
+                    assignment.plane, assignment.slot, occupant.satellite_id
+                )));
+            }
+        }
 
         self.satellites.insert(satellite.satellite_id.clone(), satellite);
         self.updated_at = Utc::now();
         Ok(())
     }
 
-    /// Remove satellite from constellation
+    /// Remove satellite, by ID or alias, with its group tags and aliases
     pub fn remove_satellite(&mut self, satellite_id: &str) -> Result<SatelliteOrbit> {
-        match self.satellites.remove(satellite_id) {
+        let satellite_id = self
+            .resolve_id(satellite_id)
+            .unwrap_or(satellite_id)
+            .to_string();
+        match self.satellites.remove(&satellite_id) {
             Some(satellite) => {
+                self.groups.retain(|_, members| {
+                    members.remove(&satellite_id);
+                    !members.is_empty()
+                });
+                self.aliases.retain(|_, names| {
+                    names.remove(&satellite_id);
+                    !names.is_empty()
+                });
+                self.alias_ids.retain(|_, id| *id != satellite_id);
                 self.updated_at = Utc::now();
                 Ok(satellite)
             }
-            None => Err(OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()))
+            None => Err(OrbitalMechanicsError::SatelliteNotFound(satellite_id))
         }
     }
 
-    /// Get satellite by ID
+    /// Get satellite by ID or alias
     pub fn get_satellite(&self, satellite_id: &str) -> Option<&SatelliteOrbit> {
-        self.satellites.get(satellite_id)
+        let id = self.alias_ids.get(satellite_id).map_or(satellite_id, String::as_str);
+        self.satellites.get(id)
     }
 
-    /// Get mutable satellite by ID
+    /// Get mutable satellite by ID or alias
     pub fn get_satellite_mut(&mut self, satellite_id: &str) -> Option<&mut SatelliteOrbit> {
-        self.satellites.get_mut(satellite_id)
+        let id = self.alias_ids.get(satellite_id).map_or(satellite_id, String::as_str);
+        self.satellites.get_mut(id)
+    }
+
+    /// Satellite ID a name refers to, whether an ID or an alias
+    pub fn resolve_id<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        self.get_satellite(name).map(|s| s.satellite_id.as_str())
+    }
+
+    /// Name a satellite, by ID or alias, under a naming scheme
+    ///
+    /// Replaces the satellite's earlier name in that scheme. An alias may not
+    /// be the ID or alias of another satellite, so every name resolves to
+    /// one satellite.
+    pub fn add_alias(&mut self, satellite_id: &str, scheme: &str, alias: &str) -> Result<()> {
+        let satellite_id = self
+            .resolve_id(satellite_id)
+            .ok_or_else(|| OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()))?
+            .to_string();
+        if let Some(owner) = self.resolve_id(alias).filter(|owner| *owner != satellite_id) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Alias {} already names satellite {}",
+                alias, owner
+            )));
+        }
+
+        let previous = self
+            .aliases
+            .entry(scheme.to_string())
+            .or_default()
+            .insert(satellite_id.clone(), alias.to_string());
+        // The previous name may still be in use under another scheme
+        if let Some(previous) = previous.filter(|previous| previous != alias) {
+            if !self.aliases.values().any(|names| names.get(&satellite_id) == Some(&previous)) {
+                self.alias_ids.remove(&previous);
+            }
+        }
+        if alias != satellite_id {
+            self.alias_ids.insert(alias.to_string(), satellite_id);
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// A satellite's name under a naming scheme
+    pub fn alias(&self, satellite_id: &str, scheme: &str) -> Option<&str> {
+        let satellite_id = self.resolve_id(satellite_id)?;
+        self.aliases.get(scheme)?.get(satellite_id).map(String::as_str)
+    }
+
+    /// A satellite's names as `(scheme, alias)` pairs, in scheme order
+    pub fn aliases_of(&self, satellite_id: &str) -> Vec<(&str, &str)> {
+        let Some(satellite_id) = self.resolve_id(satellite_id) else {
+            return Vec::new();
+        };
+        self.aliases
+            .iter()
+            .filter_map(|(scheme, names)| {
+                names.get(satellite_id).map(|alias| (scheme.as_str(), alias.as_str()))
+            })
+            .collect()
+    }
+
+    /// Naming schemes with at least one alias, in order
+    pub fn naming_schemes(&self) -> impl Iterator<Item = &str> {
+        self.aliases.keys().map(String::as_str)
+    }
+
+    /// Satellite IDs mapped to their names under a scheme
+    ///
+    /// Pass to `ExportOptions::with_satellite_names` to write exports in
+    /// that scheme.
+    pub fn names_in_scheme(&self, scheme: &str) -> BTreeMap<String, String> {
+        self.aliases.get(scheme).cloned().unwrap_or_default()
+    }
+
+    /// Reference every satellite's elements to `epoch`
+    ///
+    /// Configs carry no element epoch, so satellites built from one start at
+    /// the time of loading. Pinning the epoch makes analyses reproducible.
+    pub fn set_element_epoch(&mut self, epoch: DateTime<Utc>) {
+        for satellite in self.satellites.values_mut() {
+            satellite.epoch = epoch;
+        }
+        self.updated_at = Utc::now();
     }
 
     /// Get all satellites
@@ -279,6 +475,123 @@ impl Constellation {
         self.satellites.values()
     }
 
+    /// Satellite assigned to a plane/slot
+    pub fn satellite_in_slot(&self, plane: PlaneId, slot: SlotId) -> Option<&SatelliteOrbit> {
+        self.satellites.values().find(|s| {
+            s.slot
+                .as_ref()
+                .is_some_and(|a| a.plane == plane && a.slot == slot)
+        })
+    }
+
+    /// Satellites assigned to a plane, ordered by slot
+    pub fn satellites_in_plane(&self, plane: PlaneId) -> Vec<&SatelliteOrbit> {
+        let mut satellites: Vec<&SatelliteOrbit> = self
+            .satellites
+            .values()
+            .filter(|s| s.slot.as_ref().is_some_and(|a| a.plane == plane))
+            .collect();
+        satellites.sort_by_key(|s| s.slot.as_ref().map(|a| a.slot));
+        satellites
+    }
+
+    /// Planes with at least one assigned satellite, in order
+    pub fn planes(&self) -> Vec<PlaneId> {
+        let mut planes: Vec<PlaneId> = self
+            .satellites
+            .values()
+            .filter_map(|s| s.slot.as_ref().map(|a| a.plane))
+            .collect();
+        planes.sort();
+        planes.dedup();
+        planes
+    }
+
+    /// Add satellites to a group, creating it if needed
+    ///
+    /// A satellite can be in any number of groups. Fails without tagging
+    /// anything if an ID is not in the constellation.
+    pub fn tag<I, S>(&mut self, group: &str, satellite_ids: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let ids: Vec<String> = satellite_ids
+            .into_iter()
+            .map(|id| id.as_ref().to_string())
+            .collect();
+        if let Some(missing) = ids.iter().find(|id| !self.satellites.contains_key(*id)) {
+            return Err(OrbitalMechanicsError::SatelliteNotFound(missing.clone()));
+        }
+
+        self.groups.entry(group.to_string()).or_default().extend(ids);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Remove satellites from a group; the group is dropped once empty
+    pub fn untag<I, S>(&mut self, group: &str, satellite_ids: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if let Some(members) = self.groups.get_mut(group) {
+            for id in satellite_ids {
+                members.remove(id.as_ref());
+            }
+            if members.is_empty() {
+                self.groups.remove(group);
+            }
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// Group names, in order
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.groups.keys().map(String::as_str)
+    }
+
+    /// Groups a satellite belongs to, in order
+    pub fn groups_of(&self, satellite_id: &str) -> Vec<&str> {
+        self.groups
+            .iter()
+            .filter(|(_, members)| members.contains(satellite_id))
+            .map(|(group, _)| group.as_str())
+            .collect()
+    }
+
+    /// Satellites in a group, ordered by ID
+    ///
+    /// An unknown group is an error rather than empty, so a misspelt name
+    /// does not silently select nothing.
+    pub fn satellites_in_group(&self, group: &str) -> Result<Vec<&SatelliteOrbit>> {
+        let members = self.groups.get(group).ok_or_else(|| {
+            OrbitalMechanicsError::config_error(format!("Unknown satellite group {}", group))
+        })?;
+        Ok(members
+            .iter()
+            .filter_map(|id| self.satellites.get(id))
+            .collect())
+    }
+
+    /// States of a group's satellites at `time`, ordered by satellite ID
+    pub fn states_for_group(
+        &self,
+        group: &str,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Vec<SatelliteState>> {
+        self.satellites_in_group(group)?
+            .into_iter()
+            .map(|satellite| {
+                propagator
+                    .propagate(satellite, time)
+                    .for_satellite(&satellite.satellite_id)
+                    .at_epoch(time)
+            })
+            .collect()
+    }
+
     /// Get satellite count
     pub fn satellite_count(&self) -> usize {
         self.satellites.len()
@@ -391,49 +704,27 @@ impl Constellation {
     }
 
     /// Export constellation to TLE format
+    ///
+    /// Each satellite is written as a three-line set with its elements
+    /// advanced to `time`. Designed satellites have no catalog numbers, so
+    /// they are numbered from 1 in satellite ID order.
     pub fn to_tle_format(&self, time: DateTime<Utc>) -> Result<String> {
-        let mut tle_data = String::new();
-
-        for satellite in self.satellites.values() {
-            // Line 0: Satellite name
-            tle_data.push_str(&format!("{}\n", satellite.name));
-
-            // Line 1: Catalog number, classification, launch year/number, epoch, derivatives, drag, ephemeris type, element number
-            tle_data.push_str(&format!("1 {:5}U {:8} {:14.8} {:10} {:8} 0 {:4}9\n",
-                25544, // Placeholder catalog number
-                "98067A", // Placeholder international designator
-                self.julian_day_from_datetime(time),
-                " .00002182", // First derivative of mean motion
-                " 00000-0", // Second derivative of mean motion
-                9999 // Element number
-            ));
+        let mut satellites: Vec<&SatelliteOrbit> = self.satellites.values().collect();
+        satellites.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
 
-            // Line 2: Inclination, RAAN, eccentricity, argument of perigee, mean anomaly, mean motion, revolution number
-            tle_data.push_str(&format!("2 {:5} {:8.4} {:8.4} {:7} {:8.4} {:8.4} {:11.8}{:5}\n",
-                25544, // Catalog number (same as line 1)
-                satellite.elements.inclination_deg,
-                satellite.elements.raan_deg,
-                (satellite.elements.eccentricity * 10000000.0) as u32, // Remove decimal point
-                satellite.elements.argument_of_perigee_deg,
-                satellite.elements.mean_anomaly_deg,
-                satellite.mean_motion_rev_per_day,
-                99999 // Revolution number at epoch
-            ));
+        let mut tle_data = String::new();
+        for (index, satellite) in satellites.into_iter().enumerate() {
+            let catalog_number = u32::try_from(index + 1).unwrap_or(u32::MAX);
+            let mut tle = Tle::from_orbit(satellite, catalog_number);
+            tle.epoch = time;
+            tle.mean_anomaly_deg = satellite.update_mean_anomaly(time).rem_euclid(360.0);
+
+            let [line1, line2] = tle.to_lines().for_satellite(&satellite.satellite_id)?;
+            tle_data.push_str(&format!("{}\n{}\n{}\n", satellite.name, line1, line2));
         }
 
         Ok(tle_data)
     }
-
-    /// Convert DateTime to Julian Day (simplified)
-    fn julian_day_from_datetime(&self, dt: DateTime<Utc>) -> f64 {
-        // Simplified Julian Day calculation
-        let year = dt.year() as f64;
-        let day_of_year = dt.ordinal() as f64;
-        let hour_fraction = (dt.hour() as f64 + dt.minute() as f64 / 60.0 + dt.second() as f64 / 3600.0) / 24.0;
-
-        // Approximate Julian Day (not exact, but sufficient for TLE format)
-        2440000.0 + (year - 1970.0) * 365.25 + day_of_year + hour_fraction
-    }
 }
 
 /// Constellation coverage statistics
@@ -502,6 +793,32 @@ mod tests {
         let result = constellation.generate_walker_delta(12, 3, 1, &orbital_params);
         assert!(result.is_ok());
         assert_eq!(constellation.satellite_count(), 12);
+
+        assert_eq!(constellation.planes(), vec![PlaneId(0), PlaneId(1), PlaneId(2)]);
+        let plane = constellation.satellites_in_plane(PlaneId(1));
+        assert_eq!(plane.len(), 4);
+        assert_eq!(plane[0].satellite_id, "SAT-005");
+        assert_eq!(
+            constellation.satellite_in_slot(PlaneId(2), SlotId(3)).unwrap().satellite_id,
+            "SAT-012"
+        );
+
+        // Slots are unique
+        let elements = OrbitalElements::new(14371.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let duplicate = SatelliteOrbit::new("SAT-999".to_string(), "Dup".to_string(), elements, Utc::now())
+            .with_slot(PlaneId(0), SlotId(0));
+        assert!(constellation.add_satellite(duplicate).is_err());
+
+        // Degenerate patterns and NaN elements are errors, not panics
+        assert!(constellation.generate_walker_delta(12, 0, 1, &orbital_params).is_err());
+        assert!(constellation.generate_walker_delta(0, 3, 1, &orbital_params).is_err());
+        assert!(constellation.generate_walker_delta(12, 5, 1, &orbital_params).is_err());
+        assert!(constellation.generate_walker_delta(usize::MAX, 1, 1, &orbital_params).is_err());
+        let nan_params = crate::config::OrbitalParameters {
+            altitude_km: f64::NAN,
+            ..orbital_params
+        };
+        assert!(constellation.generate_walker_delta(4, 1, 1, &nan_params).is_err());
     }
 
     #[test]
@@ -554,4 +871,99 @@ mod tests {
         assert!(constellation.remove_satellite("TEST-01").is_ok());
         assert_eq!(constellation.satellite_count(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_satellite_groups() {
+        let mut constellation = Constellation::default();
+        let epoch = Utc::now();
+        constellation.tag("plane-1", ["LASERLIGHT-FSO-05", "LASERLIGHT-FSO-06"]).unwrap();
+        constellation.tag("spares", vec!["LASERLIGHT-FSO-06".to_string()]).unwrap();
+        constellation.tag("plane-1", ["LASERLIGHT-FSO-07"]).unwrap();
+
+        // Unknown satellites tag nothing; unknown groups are errors
+        assert!(constellation.tag("spares", ["LASERLIGHT-FSO-06", "SAT-999"]).is_err());
+        assert_eq!(constellation.satellites_in_group("spares").unwrap().len(), 1);
+        assert!(constellation.satellites_in_group("plane-3").is_err());
+
+        let ids: Vec<&str> = constellation
+            .satellites_in_group("plane-1")
+            .unwrap()
+            .iter()
+            .map(|s| s.satellite_id.as_str())
+            .collect();
+        assert_eq!(ids, ["LASERLIGHT-FSO-05", "LASERLIGHT-FSO-06", "LASERLIGHT-FSO-07"]);
+        assert_eq!(constellation.groups_of("LASERLIGHT-FSO-06"), ["plane-1", "spares"]);
+        let propagator = crate::propagator::KeplerianPropagator::new();
+        let states = constellation.states_for_group("plane-1", epoch, &propagator).unwrap();
+        assert_eq!(states.len(), 3);
+        assert_eq!(states[2].satellite_id, "LASERLIGHT-FSO-07");
+
+        // Removal and untagging keep groups consistent
+        constellation.remove_satellite("LASERLIGHT-FSO-06").unwrap();
+        assert_eq!(constellation.groups().collect::<Vec<_>>(), ["plane-1"]);
+        constellation.untag("plane-1", ["LASERLIGHT-FSO-05"]);
+        assert_eq!(constellation.satellites_in_group("plane-1").unwrap().len(), 1);
+        constellation.untag("plane-1", ["LASERLIGHT-FSO-07"]);
+        assert_eq!(constellation.groups().count(), 0);
+    }
+
+    #[test]
+    fn test_satellite_aliases() {
+        let mut constellation = Constellation::default();
+        constellation.add_alias("LASERLIGHT-FSO-01", "norad", "LL-1 (NORAD 90001)").unwrap();
+        constellation.add_alias("LASERLIGHT-FSO-01", "internal", "LL1").unwrap();
+        constellation.add_alias("LL1", "norad", "LASERLIGHT 1").unwrap();
+
+        // Any name resolves, and a renamed alias stops resolving
+        let satellite = constellation.get_satellite("LASERLIGHT 1").unwrap();
+        assert_eq!(satellite.satellite_id, "LASERLIGHT-FSO-01");
+        assert!(constellation.get_satellite_mut("LL1").is_some());
+        assert!(constellation.get_satellite("LL-1 (NORAD 90001)").is_none());
+        assert_eq!(constellation.alias("LL1", "norad"), Some("LASERLIGHT 1"));
+        assert_eq!(
+            constellation.aliases_of("LASERLIGHT-FSO-01"),
+            [("internal", "LL1"), ("norad", "LASERLIGHT 1")]
+        );
+
+        // Names stay unique across satellites
+        assert!(constellation.add_alias("LASERLIGHT-FSO-02", "norad", "LL1").is_err());
+        assert!(constellation.add_alias("LASERLIGHT-FSO-02", "norad", "LASERLIGHT-FSO-01").is_err());
+        assert!(constellation.add_alias("SAT-999", "norad", "LASERLIGHT 999").is_err());
+        let mut clash = constellation.get_satellite("LL1").unwrap().clone();
+        clash.satellite_id = "LL1".to_string();
+        assert!(constellation.add_satellite(clash).is_err());
+
+        let names = constellation.names_in_scheme("norad");
+        let options = crate::export::ExportOptions::default().with_satellite_names(names);
+        assert_eq!(options.satellite_name("LASERLIGHT-FSO-01"), "LASERLIGHT 1");
+        assert_eq!(options.satellite_name("LASERLIGHT-FSO-02"), "LASERLIGHT-FSO-02");
+
+        constellation.remove_satellite("LASERLIGHT 1").unwrap();
+        assert!(constellation.get_satellite("LL1").is_none());
+        assert_eq!(constellation.naming_schemes().count(), 0);
+    }
+
+    #[test]
+    fn test_tle_export_round_trips() {
+        let constellation = Constellation::default();
+        let mut satellites: Vec<&SatelliteOrbit> = constellation.satellites().collect();
+        satellites.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
+        let time = satellites[0].epoch + chrono::Duration::hours(3);
+
+        let tles = crate::tle::parse_tles(&constellation.to_tle_format(time).unwrap()).unwrap();
+        assert_eq!(tles.len(), satellites.len());
+
+        for (index, (tle, satellite)) in tles.iter().zip(&satellites).enumerate() {
+            assert_eq!(tle.catalog_number, index as u32 + 1);
+            assert_eq!(tle.name.as_deref(), Some(satellite.name.as_str()));
+            assert!((tle.epoch - time).num_milliseconds().abs() <= 1);
+            assert!((tle.inclination_deg - satellite.elements.inclination_deg).abs() < 1e-4);
+            assert!((tle.raan_deg - satellite.elements.raan_deg).abs() < 1e-4);
+            assert!((tle.mean_motion_rev_per_day - satellite.mean_motion_rev_per_day).abs() < 1e-7);
+
+            let expected = satellite.update_mean_anomaly(time).rem_euclid(360.0);
+            let diff = (tle.mean_anomaly_deg - expected).rem_euclid(360.0);
+            assert!(diff.min(360.0 - diff) < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,470 @@
+//! Contact plan export
+//!
+//! Turns scheduled contacts into files other tools can load:
+//!
+//! - STK interval lists (`.int`), one interval per contact, for STK
+//!   scheduling and access constraints.
+//! - DTN contact plans in ION `ionadmin` syntax (`a contact` / `a range`),
+//!   as read by ION and the ns-3 DTN bundle protocol models.
+//!
+//! DTN nodes are numbered from 1 in order of their IDs, and a comment block
+//! at the top of the plan maps numbers back to satellite and station IDs.
+//! Times in the DTN plan are whole seconds relative to the plan epoch.
+//!
+//! The plan can also be analysed as a delay-tolerant network. Contact graph
+//! routing finds the earliest time a bundle reaches each node: it may wait at
+//! a node for the next contact, needs the whole bundle to fit in the rest of
+//! the contact at that link's data rate, and then takes the light time to
+//! arrive. Every contact is assumed free for this bundle, so queueing behind
+//! other traffic is not modelled.
+
+use crate::constants::{KM_TO_M, SPEED_OF_LIGHT};
+use crate::error::{Result, ResultExt};
+use crate::pass_scoring::ScoredPass;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// One booked contact between a satellite and a ground station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedContact {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Link data rate in each direction
+    pub data_rate_bps: f64,
+    /// Mean slant range over the contact
+    pub mean_range_km: f64,
+}
+
+/// Contacts in start-time order, with the epoch relative times refer to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactPlan {
+    pub epoch: DateTime<Utc>,
+    pub contacts: Vec<PlannedContact>,
+}
+
+/// One contact used to forward a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleHop {
+    pub from: String,
+    pub to: String,
+    /// Index into `ContactPlan::contacts`
+    pub contact_index: usize,
+    /// Transmission start, after any wait for the contact to open
+    pub departure_time: DateTime<Utc>,
+    /// Last byte received at `to`
+    pub arrival_time: DateTime<Utc>,
+}
+
+/// Earliest delivery of a bundle between two nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleDelivery {
+    pub source: String,
+    pub destination: String,
+    pub send_time: DateTime<Utc>,
+    pub delivery_time: DateTime<Utc>,
+    pub latency_seconds: f64,
+    pub hops: Vec<BundleHop>,
+}
+
+/// Node waiting in the contact graph search, ordered by arrival
+struct QueueEntry {
+    arrival_seconds: f64,
+    node: usize,
+}
+
+impl ContactPlan {
+    /// Build a plan from `schedule_contacts` output at a fixed data rate
+    ///
+    /// The epoch is the earliest contact start, or now for an empty schedule.
+    pub fn from_scheduled(scheduled: &[ScoredPass], data_rate_bps: f64) -> Self {
+        let mut contacts: Vec<PlannedContact> = scheduled
+            .iter()
+            .map(|pass| PlannedContact {
+                satellite_id: pass.window.satellite_id.clone(),
+                station_id: pass.window.station_id.clone(),
+                start_time: pass.window.start_time,
+                end_time: pass.window.end_time,
+                data_rate_bps,
+                mean_range_km: pass.window.mean_range_km,
+            })
+            .collect();
+        contacts.sort_by_key(|contact| contact.start_time);
+        let epoch = contacts.first().map_or_else(Utc::now, |c| c.start_time);
+
+        Self { epoch, contacts }
+    }
+
+    /// Use a different epoch for relative times (e.g. the scenario start)
+    pub fn with_epoch(mut self, epoch: DateTime<Utc>) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Set the data rate of every contact between two nodes, in either direction
+    pub fn set_link_rate(&mut self, node_a: &str, node_b: &str, data_rate_bps: f64) {
+        for contact in &mut self.contacts {
+            let (satellite, station) = (contact.satellite_id.as_str(), contact.station_id.as_str());
+            if (satellite, station) == (node_a, node_b) || (satellite, station) == (node_b, node_a)
+            {
+                contact.data_rate_bps = data_rate_bps;
+            }
+        }
+    }
+
+    /// Earliest delivery of a bundle sent from `source` at `send_time`
+    ///
+    /// Returns `None` when no sequence of contacts reaches `destination`.
+    pub fn earliest_delivery(
+        &self,
+        source: &str,
+        destination: &str,
+        send_time: DateTime<Utc>,
+        bundle_size_bytes: f64,
+    ) -> Option<BundleDelivery> {
+        self.earliest_deliveries(source, send_time, bundle_size_bytes)
+            .into_iter()
+            .find(|delivery| delivery.destination == destination)
+    }
+
+    /// Earliest delivery from `source` to every node it can reach, by node ID
+    pub fn earliest_deliveries(
+        &self,
+        source: &str,
+        send_time: DateTime<Utc>,
+        bundle_size_bytes: f64,
+    ) -> Vec<BundleDelivery> {
+        let nodes: Vec<String> = self.node_numbers().into_keys().collect();
+        let index: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_str(), i))
+            .collect();
+        let Some(&source_index) = index.get(source) else {
+            return Vec::new();
+        };
+
+        // Links leaving each node: (contact, neighbour)
+        let mut links: Vec<Vec<(usize, usize)>> = vec![Vec::new(); nodes.len()];
+        for (i, contact) in self.contacts.iter().enumerate() {
+            let satellite = index[contact.satellite_id.as_str()];
+            let station = index[contact.station_id.as_str()];
+            links[satellite].push((i, station));
+            links[station].push((i, satellite));
+        }
+
+        // Seconds after `send_time`, with the hop that achieved them
+        let mut arrival = vec![f64::INFINITY; nodes.len()];
+        let mut previous: Vec<Option<(usize, usize, f64)>> = vec![None; nodes.len()];
+        let mut queue = BinaryHeap::new();
+        arrival[source_index] = 0.0;
+        queue.push(QueueEntry {
+            arrival_seconds: 0.0,
+            node: source_index,
+        });
+
+        while let Some(QueueEntry {
+            arrival_seconds,
+            node,
+        }) = queue.pop()
+        {
+            if arrival_seconds > arrival[node] {
+                continue;
+            }
+            for &(contact_index, neighbour) in &links[node] {
+                let contact = &self.contacts[contact_index];
+                if contact.data_rate_bps <= 0.0 {
+                    continue;
+                }
+                let opens = seconds_between(send_time, contact.start_time);
+                let closes = seconds_between(send_time, contact.end_time);
+                let departure = arrival_seconds.max(opens);
+                let transmitted = departure + bundle_size_bytes * 8.0 / contact.data_rate_bps;
+                if transmitted > closes {
+                    continue;
+                }
+                let received = transmitted + contact.mean_range_km * KM_TO_M / SPEED_OF_LIGHT;
+                if received < arrival[neighbour] {
+                    arrival[neighbour] = received;
+                    previous[neighbour] = Some((node, contact_index, departure));
+                    queue.push(QueueEntry {
+                        arrival_seconds: received,
+                        node: neighbour,
+                    });
+                }
+            }
+        }
+
+        let at = |seconds: f64| send_time + Duration::microseconds((seconds * 1e6).round() as i64);
+        (0..nodes.len())
+            .filter(|&node| node != source_index && arrival[node].is_finite())
+            .map(|destination| {
+                let mut hops = Vec::new();
+                let mut node = destination;
+                while let Some((from, contact_index, departure)) = previous[node] {
+                    hops.push(BundleHop {
+                        from: nodes[from].clone(),
+                        to: nodes[node].clone(),
+                        contact_index,
+                        departure_time: at(departure),
+                        arrival_time: at(arrival[node]),
+                    });
+                    node = from;
+                }
+                hops.reverse();
+
+                BundleDelivery {
+                    source: source.to_string(),
+                    destination: nodes[destination].clone(),
+                    send_time,
+                    delivery_time: at(arrival[destination]),
+                    latency_seconds: arrival[destination],
+                    hops,
+                }
+            })
+            .collect()
+    }
+
+    /// DTN node number for every satellite and station in the plan
+    pub fn node_numbers(&self) -> BTreeMap<String, u64> {
+        let mut ids: Vec<&str> = self
+            .contacts
+            .iter()
+            .flat_map(|c| [c.satellite_id.as_str(), c.station_id.as_str()])
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.into_iter()
+            .zip(1..)
+            .map(|(id, number)| (id.to_string(), number))
+            .collect()
+    }
+
+    /// Render the plan as an STK interval list
+    pub fn to_stk_intervals(&self) -> String {
+        let format = |time: DateTime<Utc>| time.format("%-d %b %Y %H:%M:%S%.3f").to_string();
+
+        let mut int = String::from("stk.v.11.0\n\nBEGIN IntervalList\n\n");
+        let _ = writeln!(int, "    ScenarioEpoch {}", format(self.epoch));
+        int.push_str("    DateUnitAbrv UTCG\n\nBEGIN Intervals\n\n");
+        for contact in &self.contacts {
+            let _ = writeln!(
+                int,
+                "    \"{}\" \"{}\" \"{} to {}\"",
+                format(contact.start_time),
+                format(contact.end_time),
+                contact.satellite_id,
+                contact.station_id
+            );
+        }
+        int.push_str("\nEND Intervals\n\nEND IntervalList\n");
+        int
+    }
+
+    /// Render the plan as ION `ionadmin` contact and range commands
+    ///
+    /// Each contact is written in both directions, with the rate in bytes per
+    /// second and the one-way light time rounded up to whole seconds.
+    pub fn to_dtn_contact_plan(&self) -> String {
+        let nodes = self.node_numbers();
+        let seconds = |time: DateTime<Utc>| (time - self.epoch).num_seconds();
+
+        let mut plan = format!("# Contact plan, epoch {}\n", self.epoch.to_rfc3339());
+        for (id, number) in &nodes {
+            let _ = writeln!(plan, "# node {} = {}", number, id);
+        }
+        for contact in &self.contacts {
+            let (start, end) = (seconds(contact.start_time), seconds(contact.end_time));
+            let rate_bytes = (contact.data_rate_bps / 8.0).round() as u64;
+            let light_time = (contact.mean_range_km * KM_TO_M / SPEED_OF_LIGHT)
+                .ceil()
+                .max(1.0);
+            let satellite = nodes[&contact.satellite_id];
+            let station = nodes[&contact.station_id];
+
+            for (from, to) in [(satellite, station), (station, satellite)] {
+                let _ = writeln!(
+                    plan,
+                    "a contact +{} +{} {} {} {}",
+                    start, end, from, to, rate_bytes
+                );
+            }
+            let _ = writeln!(
+                plan,
+                "a range +{} +{} {} {} {}",
+                start, end, satellite, station, light_time as u64
+            );
+        }
+        plan
+    }
+
+    /// Write the STK interval list to a `.int` file
+    pub fn write_stk_intervals<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_stk_intervals()).for_path(path)?;
+        Ok(())
+    }
+
+    /// Write the DTN contact plan to a file
+    pub fn write_dtn_contact_plan<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_dtn_contact_plan()).for_path(path)?;
+        Ok(())
+    }
+}
+
+impl PartialEq for QueueEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueueEntry {}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for a min-heap on arrival time
+        other.arrival_seconds.total_cmp(&self.arrival_seconds)
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn seconds_between(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    (end - start)
+        .num_microseconds()
+        .map_or(f64::INFINITY, |us| us as f64 / 1e6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pass_scoring::schedule_contacts;
+    use crate::visibility::{PassType, VisibilityWindow};
+    use chrono::{Duration, TimeZone};
+
+    fn window(satellite_id: &str, station_id: &str, start: DateTime<Utc>) -> VisibilityWindow {
+        VisibilityWindow {
+            satellite_id: satellite_id.to_string(),
+            station_id: station_id.to_string(),
+            start_time: start,
+            end_time: start + Duration::minutes(10),
+            duration_seconds: 600.0,
+            max_elevation_time: start + Duration::minutes(5),
+            max_elevation_deg: 60.0,
+            min_range_km: 8000.0,
+            mean_range_km: 9000.0,
+            azimuth_span_deg: 120.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_stk_and_dtn_export() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap();
+        let scheduled = schedule_contacts(
+            vec![
+                window("SAT-02", "GS-A", epoch + Duration::minutes(30)),
+                window("SAT-01", "GS-A", epoch),
+            ],
+            &|w: &VisibilityWindow| w.max_elevation_deg,
+        );
+        let plan = ContactPlan::from_scheduled(&scheduled, 1.0e6);
+        assert_eq!(plan.epoch, epoch);
+
+        let int = plan.to_stk_intervals();
+        assert!(int.contains("ScenarioEpoch 5 Mar 2024 12:00:00.000"));
+        assert!(int.contains(
+            "\"5 Mar 2024 12:30:00.000\" \"5 Mar 2024 12:40:00.000\" \"SAT-02 to GS-A\""
+        ));
+        assert_eq!(int.lines().filter(|l| l.starts_with("    \"")).count(), 2);
+
+        // GS-A = 1, SAT-01 = 2, SAT-02 = 3; 9000 km is 30 ms of light time
+        let dtn = plan.to_dtn_contact_plan();
+        let commands: Vec<&str> = dtn.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "a contact +0 +600 2 1 125000",
+                "a contact +0 +600 1 2 125000",
+                "a range +0 +600 2 1 1",
+                "a contact +1800 +2400 3 1 125000",
+                "a contact +1800 +2400 1 3 125000",
+                "a range +1800 +2400 3 1 1",
+            ]
+        );
+        assert!(dtn.contains("# node 3 = SAT-02"));
+    }
+
+    #[test]
+    fn test_earliest_bundle_delivery() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap();
+        // GS-A hands to SAT-01, which reaches GS-B later; SAT-02 carries from
+        // GS-B onwards to GS-C
+        let scheduled: Vec<ScoredPass> = [
+            window("SAT-01", "GS-A", epoch),
+            window("SAT-01", "GS-B", epoch + Duration::minutes(40)),
+            window("SAT-02", "GS-B", epoch + Duration::minutes(45)),
+            window("SAT-02", "GS-C", epoch + Duration::minutes(90)),
+        ]
+        .into_iter()
+        .map(|window| ScoredPass { window, score: 1.0 })
+        .collect();
+        let mut plan = ContactPlan::from_scheduled(&scheduled, 1.0e6);
+        let light_time = 9.0e6 / SPEED_OF_LIGHT;
+
+        // 10 MB takes 80 s per hop at 1 Mbit/s
+        let send_time = epoch + Duration::minutes(2);
+        let delivery = plan
+            .earliest_delivery("GS-A", "GS-C", send_time, 1.0e7)
+            .unwrap();
+        let hops: Vec<(&str, &str)> = delivery
+            .hops
+            .iter()
+            .map(|hop| (hop.from.as_str(), hop.to.as_str()))
+            .collect();
+        assert_eq!(
+            hops,
+            vec![
+                ("GS-A", "SAT-01"),
+                ("SAT-01", "GS-B"),
+                ("GS-B", "SAT-02"),
+                ("SAT-02", "GS-C")
+            ]
+        );
+        // Last hop departs when SAT-02 rises over GS-C
+        let expected = 88.0 * 60.0 + 80.0 + light_time;
+        assert!((delivery.latency_seconds - expected).abs() < 1e-5);
+        assert_eq!(
+            delivery.hops[3].departure_time,
+            epoch + Duration::minutes(90)
+        );
+
+        // A bundle too large for the first contact never leaves
+        assert!(plan
+            .earliest_delivery("GS-A", "GS-C", send_time, 1.0e9)
+            .is_none());
+
+        // A slower uplink still fits but delays the first hop
+        plan.set_link_rate("GS-A", "SAT-01", 2.0e5);
+        let slow = plan
+            .earliest_delivery("GS-A", "SAT-01", send_time, 1.0e7)
+            .unwrap();
+        assert!((slow.latency_seconds - (400.0 + light_time)).abs() < 1e-5);
+        assert_eq!(plan.earliest_deliveries("GS-A", send_time, 1.0e7).len(), 4);
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 use crate::constants::*;
+#[cfg(feature = "simd")]
+use wide::f64x4;
 
 /// 3D position vector
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +21,23 @@ pub struct GeodeticPosition {
     pub altitude_km: f64,
 }
 
+/// Reference shape of the Earth for geodetic and topocentric conversions
+///
+/// `Sphere` uses the WGS84 equatorial radius, matching the station model
+/// used before ellipsoids were supported.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum EarthModel {
+    #[default]
+    Wgs84,
+    Grs80,
+    Sphere,
+    Custom {
+        equatorial_radius_km: f64,
+        flattening: f64,
+        gravitational_parameter_km3_per_s2: f64,
+    },
+}
+
 /// Coordinate system types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CoordinateSystem {
@@ -32,6 +51,31 @@ pub enum CoordinateSystem {
     Topocentric,
 }
 
+/// Position relative to a ground station in the topocentric SEZ frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Topocentric {
+    pub south_km: f64,
+    pub east_km: f64,
+    pub zenith_km: f64,
+    pub range_km: f64,
+}
+
+/// Ground station frame for repeated ECI -> topocentric transforms
+///
+/// Uses the same non-rotating station model as
+/// `SatelliteState::look_angles_from_station`: the station sits on the
+/// chosen `EarthModel` and zenith is its ellipsoid normal. With the `simd`
+/// feature, batches are transformed four positions at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TopocentricFrame {
+    /// Station position in ECI (km)
+    pub origin: [f64; 3],
+    sin_lat: f64,
+    cos_lat: f64,
+    sin_lon: f64,
+    cos_lon: f64,
+}
+
 impl Position3D {
     pub fn new(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z }
@@ -50,4 +94,507 @@ impl From<[f64; 3]> for Position3D {
     fn from(arr: [f64; 3]) -> Self {
         Self::new(arr[0], arr[1], arr[2])
     }
-}
\ No newline at end of file
+}
+
+impl EarthModel {
+    pub fn equatorial_radius_km(&self) -> f64 {
+        match self {
+            Self::Wgs84 | Self::Grs80 | Self::Sphere => EARTH_RADIUS_KM,
+            Self::Custom {
+                equatorial_radius_km,
+                ..
+            } => *equatorial_radius_km,
+        }
+    }
+
+    pub fn flattening(&self) -> f64 {
+        match self {
+            Self::Wgs84 => EARTH_FLATTENING,
+            Self::Grs80 => GRS80_FLATTENING,
+            Self::Sphere => 0.0,
+            Self::Custom { flattening, .. } => *flattening,
+        }
+    }
+
+    pub fn gravitational_parameter_km3_per_s2(&self) -> f64 {
+        match self {
+            Self::Wgs84 | Self::Sphere => EARTH_MU,
+            Self::Grs80 => GRS80_MU,
+            Self::Custom {
+                gravitational_parameter_km3_per_s2,
+                ..
+            } => *gravitational_parameter_km3_per_s2,
+        }
+    }
+
+    pub fn polar_radius_km(&self) -> f64 {
+        self.equatorial_radius_km() * (1.0 - self.flattening())
+    }
+
+    /// First eccentricity squared, e² = f(2 - f)
+    pub fn eccentricity_squared(&self) -> f64 {
+        let f = self.flattening();
+        f * (2.0 - f)
+    }
+
+    /// Earth-fixed position of a point at geodetic latitude, longitude and height
+    pub fn geodetic_to_ecef(
+        &self,
+        latitude_deg: f64,
+        longitude_deg: f64,
+        altitude_km: f64,
+    ) -> [f64; 3] {
+        let (sin_lat, cos_lat) = (latitude_deg * DEG_TO_RAD).sin_cos();
+        let (sin_lon, cos_lon) = (longitude_deg * DEG_TO_RAD).sin_cos();
+        let e2 = self.eccentricity_squared();
+        // Prime vertical radius of curvature
+        let n = self.equatorial_radius_km() / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+
+        [
+            (n + altitude_km) * cos_lat * cos_lon,
+            (n + altitude_km) * cos_lat * sin_lon,
+            (n * (1.0 - e2) + altitude_km) * sin_lat,
+        ]
+    }
+
+    /// Geodetic latitude, longitude and height of an Earth-fixed position
+    ///
+    /// Fixed-point iteration on latitude; converges to well under a
+    /// millimetre in a few steps for any Earth-like flattening, including
+    /// over the poles.
+    pub fn ecef_to_geodetic(&self, position: [f64; 3]) -> GeodeticPosition {
+        let [x, y, z] = position;
+        let a = self.equatorial_radius_km();
+        let e2 = self.eccentricity_squared();
+        let p = (x * x + y * y).sqrt();
+
+        let mut latitude = z.atan2(p * (1.0 - e2));
+        for _ in 0..GEODETIC_MAX_ITERATIONS {
+            let sin_lat = latitude.sin();
+            let n = a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+            let next = (z + e2 * n * sin_lat).atan2(p);
+            let converged = (next - latitude).abs() < GEODETIC_TOLERANCE_RAD;
+            latitude = next;
+            if converged {
+                break;
+            }
+        }
+
+        let (sin_lat, cos_lat) = latitude.sin_cos();
+        // Height along the normal, well conditioned at every latitude
+        let altitude_km =
+            p * cos_lat + z * sin_lat - a * (1.0 - e2 * sin_lat * sin_lat).sqrt();
+
+        GeodeticPosition {
+            latitude_deg: latitude * RAD_TO_DEG,
+            longitude_deg: y.atan2(x) * RAD_TO_DEG,
+            altitude_km,
+        }
+    }
+}
+
+/// Wrap an angle or angle difference into [-180, 180)
+pub fn wrap_angle_deg(angle_deg: f64) -> f64 {
+    (angle_deg + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Remove 360° jumps from a series of angles, e.g. azimuth crossing north
+///
+/// Each step is taken the shorter way round, so the result is continuous
+/// and may leave [0, 360).
+pub fn unwrap_angles_deg(angles_deg: &[f64]) -> Vec<f64> {
+    let mut unwrapped: Vec<f64> = Vec::with_capacity(angles_deg.len());
+    for &angle_deg in angles_deg {
+        let next = match unwrapped.last() {
+            Some(&previous) => previous + wrap_angle_deg(angle_deg - previous),
+            None => angle_deg,
+        };
+        unwrapped.push(next);
+    }
+    unwrapped
+}
+
+/// Split a ground track into polylines that draw cleanly on a lat/lon map
+///
+/// A step across the antimeridian ends one polyline at ±180° and starts the
+/// next at ∓180°, at the interpolated latitude. A step whose longitude
+/// changes by more than 90° went over a pole (at sensible sampling nothing
+/// else moves that fast); the polyline is taken up to the pole at its last
+/// longitude and the next starts from the pole at the new one. Longitudes
+/// are wrapped into [-180, 180).
+pub fn split_ground_track(track: &[GeodeticPosition]) -> Vec<Vec<GeodeticPosition>> {
+    let mut polylines = Vec::new();
+    let mut current: Vec<GeodeticPosition> = Vec::new();
+
+    for point in track {
+        let point = GeodeticPosition {
+            longitude_deg: wrap_angle_deg(point.longitude_deg),
+            ..point.clone()
+        };
+        if let Some(previous) = current.last() {
+            let step_deg = wrap_angle_deg(point.longitude_deg - previous.longitude_deg);
+            let altitude_km = (previous.altitude_km + point.altitude_km) / 2.0;
+            let boundary = if step_deg.abs() > POLE_CROSSING_LONGITUDE_STEP_DEG {
+                let pole_deg = 90f64.copysign(previous.latitude_deg + point.latitude_deg);
+                Some((
+                    GeodeticPosition {
+                        latitude_deg: pole_deg,
+                        longitude_deg: previous.longitude_deg,
+                        altitude_km,
+                    },
+                    point.longitude_deg,
+                ))
+            } else if (previous.longitude_deg + step_deg).abs() > 180.0 {
+                let edge_deg = 180f64.copysign(step_deg);
+                let fraction = (edge_deg - previous.longitude_deg) / step_deg;
+                Some((
+                    GeodeticPosition {
+                        latitude_deg: previous.latitude_deg
+                            + fraction * (point.latitude_deg - previous.latitude_deg),
+                        longitude_deg: edge_deg,
+                        altitude_km,
+                    },
+                    -edge_deg,
+                ))
+            } else {
+                None
+            };
+
+            if let Some((end, next_longitude_deg)) = boundary {
+                let start = GeodeticPosition {
+                    longitude_deg: next_longitude_deg,
+                    ..end.clone()
+                };
+                current.push(end);
+                polylines.push(std::mem::take(&mut current));
+                current.push(start);
+            }
+        }
+        current.push(point);
+    }
+
+    if !current.is_empty() {
+        polylines.push(current);
+    }
+    polylines
+}
+
+/// Longitude step above which `split_ground_track` assumes a pole crossing
+const POLE_CROSSING_LONGITUDE_STEP_DEG: f64 = 90.0;
+
+/// Latitude iterations allowed in `EarthModel::ecef_to_geodetic`
+const GEODETIC_MAX_ITERATIONS: usize = 10;
+
+/// Latitude change (~0.06 mm on the surface) at which iteration stops
+const GEODETIC_TOLERANCE_RAD: f64 = 1e-11;
+
+impl Topocentric {
+    pub fn elevation_deg(&self) -> f64 {
+        (self.zenith_km / self.range_km).asin() * RAD_TO_DEG
+    }
+
+    /// Azimuth clockwise from north, in [0, 360)
+    pub fn azimuth_deg(&self) -> f64 {
+        let azimuth_deg = self.east_km.atan2(self.south_km) * RAD_TO_DEG;
+        if azimuth_deg < 0.0 {
+            azimuth_deg + 360.0
+        } else {
+            azimuth_deg
+        }
+    }
+}
+
+impl TopocentricFrame {
+    /// Station on the spherical Earth model
+    pub fn new(latitude_deg: f64, longitude_deg: f64, altitude_m: f64) -> Self {
+        Self::with_model(&EarthModel::Sphere, latitude_deg, longitude_deg, altitude_m)
+    }
+
+    /// Station at geodetic coordinates on `model`
+    pub fn with_model(
+        model: &EarthModel,
+        latitude_deg: f64,
+        longitude_deg: f64,
+        altitude_m: f64,
+    ) -> Self {
+        let (sin_lat, cos_lat) = (latitude_deg * DEG_TO_RAD).sin_cos();
+        let (sin_lon, cos_lon) = (longitude_deg * DEG_TO_RAD).sin_cos();
+
+        Self {
+            origin: model.geodetic_to_ecef(latitude_deg, longitude_deg, altitude_m / 1000.0),
+            sin_lat,
+            cos_lat,
+            sin_lon,
+            cos_lon,
+        }
+    }
+
+    /// Transform one ECI position
+    pub fn transform(&self, position: &Position3D) -> Topocentric {
+        let dx = position.x - self.origin[0];
+        let dy = position.y - self.origin[1];
+        let dz = position.z - self.origin[2];
+
+        Topocentric {
+            south_km: -dx * self.sin_lat * self.cos_lon - dy * self.sin_lat * self.sin_lon
+                + dz * self.cos_lat,
+            east_km: -dx * self.sin_lon + dy * self.cos_lon,
+            zenith_km: dx * self.cos_lat * self.cos_lon + dy * self.cos_lat * self.sin_lon
+                + dz * self.sin_lat,
+            range_km: (dx * dx + dy * dy + dz * dz).sqrt(),
+        }
+    }
+
+    /// Transform a batch of ECI positions
+    pub fn transform_batch(&self, positions: &[Position3D]) -> Vec<Topocentric> {
+        #[cfg(feature = "simd")]
+        {
+            let mut output = Vec::with_capacity(positions.len());
+            let chunks = positions.chunks_exact(4);
+            let remainder = chunks.remainder();
+            for chunk in chunks {
+                let [south, east, zenith, range] = self.transform_lanes(chunk);
+                let (south, east) = (south.to_array(), east.to_array());
+                let (zenith, range) = (zenith.to_array(), range.to_array());
+                output.extend((0..4).map(|i| Topocentric {
+                    south_km: south[i],
+                    east_km: east[i],
+                    zenith_km: zenith[i],
+                    range_km: range[i],
+                }));
+            }
+            output.extend(remainder.iter().map(|p| self.transform(p)));
+            output
+        }
+        #[cfg(not(feature = "simd"))]
+        {
+            positions.iter().map(|p| self.transform(p)).collect()
+        }
+    }
+
+    /// Elevation angles of a batch of ECI positions in degrees
+    pub fn elevations_deg(&self, positions: &[Position3D]) -> Vec<f64> {
+        #[cfg(feature = "simd")]
+        {
+            let mut output = Vec::with_capacity(positions.len());
+            let chunks = positions.chunks_exact(4);
+            let remainder = chunks.remainder();
+            for chunk in chunks {
+                let [_, _, zenith, range] = self.transform_lanes(chunk);
+                output.extend((zenith / range).asin().to_array().map(|e| e * RAD_TO_DEG));
+            }
+            output.extend(remainder.iter().map(|p| self.transform(p).elevation_deg()));
+            output
+        }
+        #[cfg(not(feature = "simd"))]
+        {
+            positions.iter().map(|p| self.transform(p).elevation_deg()).collect()
+        }
+    }
+
+    /// South, east, zenith and range of four positions
+    #[cfg(feature = "simd")]
+    fn transform_lanes(&self, chunk: &[Position3D]) -> [f64x4; 4] {
+        let lane = |value: fn(&Position3D) -> f64| {
+            f64x4::from([value(&chunk[0]), value(&chunk[1]), value(&chunk[2]), value(&chunk[3])])
+        };
+        let dx = lane(|p| p.x) - f64x4::splat(self.origin[0]);
+        let dy = lane(|p| p.y) - f64x4::splat(self.origin[1]);
+        let dz = lane(|p| p.z) - f64x4::splat(self.origin[2]);
+
+        let sin_lat = f64x4::splat(self.sin_lat);
+        let cos_lat = f64x4::splat(self.cos_lat);
+        let sin_lon = f64x4::splat(self.sin_lon);
+        let cos_lon = f64x4::splat(self.cos_lon);
+
+        [
+            -dx * sin_lat * cos_lon - dy * sin_lat * sin_lon + dz * cos_lat,
+            -dx * sin_lon + dy * cos_lon,
+            dx * cos_lat * cos_lon + dy * cos_lat * sin_lon + dz * sin_lat,
+            (dx * dx + dy * dy + dz * dz).sqrt(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::SatelliteState;
+    use chrono::Utc;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_batch_matches_scalar_transform() {
+        let frame = TopocentricFrame::new(40.0, -105.0, 1600.0);
+        // Odd count exercises the remainder after full SIMD lanes
+        let positions: Vec<Position3D> = (0..11)
+            .map(|i| {
+                let angle = i as f64 * 0.5;
+                Position3D::new(
+                    14378.0 * angle.cos(),
+                    14378.0 * angle.sin(),
+                    3000.0 - 500.0 * i as f64,
+                )
+            })
+            .collect();
+
+        let batch = frame.transform_batch(&positions);
+        let elevations = frame.elevations_deg(&positions);
+        assert_eq!(batch.len(), positions.len());
+        for ((position, topocentric), elevation_deg) in
+            positions.iter().zip(&batch).zip(&elevations)
+        {
+            let expected = SatelliteState::new(
+                "TOPO-01".to_string(),
+                Utc::now(),
+                position.to_array(),
+                [0.0, 0.0, 0.0],
+            )
+            .look_angles_from_station(40.0, -105.0, 1600.0);
+            assert!((topocentric.range_km - expected.range_km).abs() < 1e-9);
+            assert!((topocentric.elevation_deg() - expected.elevation_deg).abs() < 1e-9);
+            assert!((topocentric.azimuth_deg() - expected.azimuth_deg).abs() < 1e-9);
+            assert!((elevation_deg - expected.elevation_deg).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_earth_models_agree_across_modules() {
+        let (latitude_deg, longitude_deg, altitude_m) = (78.23, 15.39, 500.0);
+        for model in [EarthModel::Wgs84, EarthModel::Grs80, EarthModel::Sphere] {
+            // Round trip, including straight over the pole
+            for (lat, lon, alt_km) in [(latitude_deg, longitude_deg, 0.5), (90.0, 0.0, 550.0)] {
+                let geodetic = model.ecef_to_geodetic(model.geodetic_to_ecef(lat, lon, alt_km));
+                assert!((geodetic.latitude_deg - lat).abs() < 1e-9);
+                assert!((geodetic.altitude_km - alt_km).abs() < 1e-6);
+            }
+
+            // A satellite 550 km along the station's normal is at zenith on
+            // the same model, in both the frame and orbit look angles
+            let overhead = model.geodetic_to_ecef(latitude_deg, longitude_deg, 550.0);
+            let state =
+                SatelliteState::new("ZEN-01".to_string(), Utc::now(), overhead, [0.0, 0.0, 0.0]);
+            let frame =
+                TopocentricFrame::with_model(&model, latitude_deg, longitude_deg, altitude_m);
+            let look = state.look_angles_from_station_with_model(
+                &model,
+                latitude_deg,
+                longitude_deg,
+                altitude_m,
+            );
+            let topocentric = frame.transform(&Position3D::from(overhead));
+            assert!((topocentric.elevation_deg() - 90.0).abs() < 1e-6);
+            assert!((look.elevation_deg - topocentric.elevation_deg()).abs() < 1e-12);
+            assert!((look.range_km - 549.5).abs() < 1e-6);
+
+            let sub_point = state.geodetic_with_model(&model);
+            assert!((sub_point.latitude_deg - latitude_deg).abs() < 1e-9);
+            assert!((sub_point.altitude_km - 550.0).abs() < 1e-6);
+        }
+
+        // Mixing models tilts zenith by the geodetic/geocentric latitude gap
+        let overhead = EarthModel::Wgs84.geodetic_to_ecef(45.0, 0.0, 550.0);
+        let spherical =
+            TopocentricFrame::new(45.0, 0.0, 0.0).transform(&Position3D::from(overhead));
+        assert!(spherical.elevation_deg() < 89.9);
+        assert!((EarthModel::Wgs84.polar_radius_km() - EARTH_POLAR_RADIUS_KM).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_polar_ground_track_splits_at_poles_and_antimeridian() {
+        use crate::orbit::{OrbitalElements, SatelliteOrbit};
+        use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+
+        // One revolution of a 90° orbit in the plane over a Svalbard-like station
+        let epoch = Utc::now();
+        let elements = OrbitalElements::new(7000.0, 0.0, 90.0, 15.39, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new("POL-01".to_string(), "Polar".to_string(), elements, epoch);
+        let propagator = KeplerianPropagator::new();
+        let track: Vec<GeodeticPosition> = (0..=97)
+            .map(|minute| {
+                let state = propagator
+                    .propagate(&orbit, epoch + chrono::Duration::minutes(minute))
+                    .unwrap();
+                EarthModel::Sphere.ecef_to_geodetic(state.position_eci)
+            })
+            .collect();
+
+        // North pole, then south pole: three meridian polylines, none jumping
+        let polylines = split_ground_track(&track);
+        assert_eq!(polylines.len(), 3);
+        assert_eq!(polylines[0].last().unwrap().latitude_deg, 90.0);
+        assert_eq!(polylines[1][0].latitude_deg, 90.0);
+        assert_eq!(polylines[1].last().unwrap().latitude_deg, -90.0);
+        for polyline in &polylines {
+            for pair in polyline.windows(2) {
+                assert!((pair[1].longitude_deg - pair[0].longitude_deg).abs() < 1e-6);
+            }
+        }
+        assert!((polylines[1][1].longitude_deg + 164.61).abs() < 1e-6);
+
+        // Eastward across the antimeridian
+        let point = |latitude_deg, longitude_deg| GeodeticPosition {
+            latitude_deg,
+            longitude_deg,
+            altitude_km: 600.0,
+        };
+        let crossing = [point(10.0, 170.0), point(12.0, 178.0), point(14.0, 186.0)];
+        let polylines = split_ground_track(&crossing);
+        assert_eq!(polylines.len(), 2);
+        let (end, start) = (polylines[0].last().unwrap(), &polylines[1][0]);
+        assert_eq!((end.latitude_deg, end.longitude_deg), (12.5, 180.0));
+        assert_eq!((start.latitude_deg, start.longitude_deg), (12.5, -180.0));
+        assert_eq!(polylines[1][1].longitude_deg, -174.0);
+
+        assert_eq!(unwrap_angles_deg(&[350.0, 5.0, 20.0, 340.0]), [350.0, 365.0, 380.0, 340.0]);
+    }
+
+    fn earth_model() -> impl Strategy<Value = EarthModel> {
+        prop_oneof![
+            Just(EarthModel::Wgs84),
+            Just(EarthModel::Grs80),
+            Just(EarthModel::Sphere)
+        ]
+    }
+
+    proptest! {
+        // Latitude and height within 1e-9° and a millimetre, from below sea
+        // level to beyond GEO; longitude is undefined at the poles
+        #[test]
+        fn test_geodetic_round_trip(
+            model in earth_model(),
+            latitude_deg in -90.0f64..=90.0,
+            longitude_deg in -180.0f64..180.0,
+            altitude_km in -0.5f64..40_000.0,
+        ) {
+            let ecef = model.geodetic_to_ecef(latitude_deg, longitude_deg, altitude_km);
+            let geodetic = model.ecef_to_geodetic(ecef);
+
+            prop_assert!((geodetic.latitude_deg - latitude_deg).abs() < 1e-9);
+            prop_assert!((geodetic.altitude_km - altitude_km).abs() < 1e-6);
+            if latitude_deg.abs() < 89.999 {
+                prop_assert!(wrap_angle_deg(geodetic.longitude_deg - longitude_deg).abs() < 1e-9);
+            }
+        }
+
+        // Sub-satellite point and back to the same position within a millimetre
+        #[test]
+        fn test_eci_geodetic_round_trip(
+            model in earth_model(),
+            radius_km in 6_600.0f64..50_000.0,
+            right_ascension_deg in -180.0f64..180.0,
+            declination_deg in -90.0f64..=90.0,
+        ) {
+            let (sin_ra, cos_ra) = right_ascension_deg.to_radians().sin_cos();
+            let (sin_dec, cos_dec) = declination_deg.to_radians().sin_cos();
+            let position =
+                [radius_km * cos_dec * cos_ra, radius_km * cos_dec * sin_ra, radius_km * sin_dec];
+            let state = SatelliteState::new("PROP-01".to_string(), Utc::now(), position, [0.0; 3]);
+
+            let recovered = state.geodetic_with_model(&model).to_ecef_with_model(&model);
+            for (recovered, original) in recovered.iter().zip(position) {
+                prop_assert!((recovered - original).abs() < 1e-6, "{} != {}", recovered, original);
+            }
+        }
+    }
+}
@@ -0,0 +1,603 @@
+//! Frequency coordination conflict detection
+//!
+//! Flags intervals where two satellites transmit in overlapping RF bands to
+//! ground stations close enough to share an interference environment while
+//! the satellites sit within a small angular separation as seen from either
+//! station. Conflicts are collected into a report suitable for ITU-style
+//! coordination filings.
+
+use crate::constants::*;
+use crate::constellation::Constellation;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::ground_station::{GroundStation, GroundStationNetwork};
+use crate::orbit::{GeodeticPosition, LookAngles, SatelliteOrbit, SatelliteState};
+use crate::progress::{NoProgress, Phase, ProgressSink};
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Assigned RF band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RfBand {
+    pub name: String,
+    pub min_frequency_hz: f64,
+    pub max_frequency_hz: f64,
+}
+
+/// Satellite-to-station downlink in an assigned band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transmission {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub band: RfBand,
+}
+
+/// Thresholds for conflict detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinationConfig {
+    /// Stations further apart than this are treated as independent
+    pub max_station_distance_km: f64,
+    /// Angular separation below which two transmissions interfere
+    pub min_angular_separation_deg: f64,
+    /// Elevation above which a link is considered active
+    pub min_elevation_deg: f64,
+    /// Sampling interval for the analysis
+    pub step_seconds: f64,
+}
+
+/// Interval during which two transmissions interfere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterferenceConflict {
+    pub satellite_a: String,
+    pub station_a: String,
+    pub satellite_b: String,
+    pub station_b: String,
+    /// Lower edge of the shared spectrum
+    pub overlap_min_frequency_hz: f64,
+    /// Upper edge of the shared spectrum
+    pub overlap_max_frequency_hz: f64,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Closest approach of the two satellites as seen from either station
+    pub min_angular_separation_deg: f64,
+    pub station_distance_km: f64,
+}
+
+/// Coordination conflicts over an analysis period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinationReport {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub config: CoordinationConfig,
+    /// Conflicts ordered by start time
+    pub conflicts: Vec<InterferenceConflict>,
+}
+
+/// Frequency coordination analysis
+#[derive(Debug, Clone, Default)]
+pub struct CoordinationAnalyzer {
+    pub config: CoordinationConfig,
+}
+
+/// Transmission pair sharing spectrum at nearby stations
+struct CandidatePair<'a> {
+    a: ActiveLink<'a>,
+    b: ActiveLink<'a>,
+    overlap_hz: (f64, f64),
+    station_distance_km: f64,
+}
+
+#[derive(Clone, Copy)]
+struct ActiveLink<'a> {
+    satellite: &'a SatelliteOrbit,
+    station: &'a GroundStation,
+}
+
+/// Conflict interval being accumulated: start, last conflicting sample, closest separation
+type OpenConflict = (DateTime<Utc>, DateTime<Utc>, f64);
+
+impl RfBand {
+    pub fn new(
+        name: impl Into<String>,
+        min_frequency_hz: f64,
+        max_frequency_hz: f64,
+    ) -> Result<Self> {
+        if !(min_frequency_hz > 0.0 && max_frequency_hz > min_frequency_hz) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "RF band needs 0 < min < max frequency, got {} - {} Hz",
+                min_frequency_hz, max_frequency_hz
+            )));
+        }
+        Ok(Self {
+            name: name.into(),
+            min_frequency_hz,
+            max_frequency_hz,
+        })
+    }
+
+    /// Shared frequency range with another band, if any
+    pub fn overlap(&self, other: &RfBand) -> Option<(f64, f64)> {
+        let low = self.min_frequency_hz.max(other.min_frequency_hz);
+        let high = self.max_frequency_hz.min(other.max_frequency_hz);
+        (low < high).then_some((low, high))
+    }
+}
+
+impl Default for CoordinationConfig {
+    fn default() -> Self {
+        Self {
+            max_station_distance_km: 100.0,
+            min_angular_separation_deg: 2.0,
+            min_elevation_deg: 10.0,
+            step_seconds: 60.0,
+        }
+    }
+}
+
+impl InterferenceConflict {
+    pub fn duration_seconds(&self) -> f64 {
+        (self.end_time - self.start_time).num_milliseconds() as f64 / 1000.0
+    }
+}
+
+impl CoordinationAnalyzer {
+    /// Create analyzer with default thresholds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create analyzer with custom thresholds
+    pub fn with_config(config: CoordinationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Find interfering transmission pairs over a period
+    ///
+    /// Pairs from the same satellite are skipped. A link is active while its
+    /// station is available and sees its satellite above the minimum
+    /// elevation; a pair conflicts while both links are active and the two
+    /// satellites are closer than the separation threshold from either
+    /// station.
+    pub fn analyze(
+        &self,
+        constellation: &Constellation,
+        stations: &GroundStationNetwork,
+        transmissions: &[Transmission],
+        propagator: &dyn OrbitalPropagator,
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+    ) -> Result<CoordinationReport> {
+        self.analyze_with_progress(
+            constellation,
+            stations,
+            transmissions,
+            propagator,
+            start_time,
+            duration_hours,
+            &NoProgress,
+        )
+    }
+
+    /// Find interfering transmission pairs like `analyze`, reporting progress
+    /// to `progress` and checking it for cancellation after every time step of
+    /// the conjunction screening
+    #[allow(clippy::too_many_arguments)]
+    pub fn analyze_with_progress(
+        &self,
+        constellation: &Constellation,
+        stations: &GroundStationNetwork,
+        transmissions: &[Transmission],
+        propagator: &dyn OrbitalPropagator,
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        progress: &dyn ProgressSink,
+    ) -> Result<CoordinationReport> {
+        let config = &self.config;
+        // NaN and infinite values fail both checks rather than slipping past them
+        let step_valid = config.step_seconds.is_finite() && config.step_seconds > 0.0;
+        let duration_valid = duration_hours.is_finite() && duration_hours >= 0.0;
+        if !step_valid || !duration_valid {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Coordination analysis needs a finite positive step and non-negative duration, got {} s over {} h",
+                config.step_seconds, duration_hours
+            )));
+        }
+
+        let links = transmissions
+            .iter()
+            .map(|transmission| {
+                let satellite = constellation
+                    .get_satellite(&transmission.satellite_id)
+                    .ok_or_else(|| {
+                        OrbitalMechanicsError::SatelliteNotFound(transmission.satellite_id.clone())
+                    })?;
+                let station = stations
+                    .get_station(&transmission.station_id)
+                    .ok_or_else(|| {
+                        OrbitalMechanicsError::GroundStationNotFound(
+                            transmission.station_id.clone(),
+                        )
+                    })?;
+                Ok(ActiveLink { satellite, station })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut pairs = Vec::new();
+        for (i, first) in transmissions.iter().enumerate() {
+            for (j, second) in transmissions.iter().enumerate().skip(i + 1) {
+                if first.satellite_id == second.satellite_id {
+                    continue;
+                }
+                let Some(overlap_hz) = first.band.overlap(&second.band) else {
+                    continue;
+                };
+                let station_distance_km = station_distance_km(links[i].station, links[j].station)?;
+                if station_distance_km > config.max_station_distance_km {
+                    continue;
+                }
+                pairs.push(CandidatePair {
+                    a: links[i],
+                    b: links[j],
+                    overlap_hz,
+                    station_distance_km,
+                });
+            }
+        }
+
+        let end_time = start_time + Duration::milliseconds((duration_hours * 3.6e6) as i64);
+        let step = Duration::milliseconds((config.step_seconds * 1000.0).round().max(1.0) as i64);
+        let mut open: Vec<Option<OpenConflict>> = vec![None; pairs.len()];
+        let mut conflicts = Vec::new();
+        let mut time = start_time;
+        let steps = if pairs.is_empty() {
+            0
+        } else {
+            ((end_time - start_time).num_milliseconds() / step.num_milliseconds()) as usize + 1
+        };
+        let phase = Phase::start(progress, "conjunction screening", steps)?;
+
+        for done in 1..=steps {
+            let mut states: HashMap<&str, SatelliteState> = HashMap::new();
+            for pair in &pairs {
+                for link in [&pair.a, &pair.b] {
+                    let id = link.satellite.satellite_id.as_str();
+                    if !states.contains_key(id) {
+                        let state = propagator
+                            .propagate(link.satellite, time)
+                            .for_satellite(id)
+                            .at_epoch(time)?;
+                        states.insert(id, state);
+                    }
+                }
+            }
+
+            for (pair, interval) in pairs.iter().zip(open.iter_mut()) {
+                let separation = self.separation_deg(pair, &states, time);
+                match (separation, interval.as_mut()) {
+                    (Some(separation), Some((_, last, closest))) => {
+                        *last = time;
+                        *closest = closest.min(separation);
+                    }
+                    (Some(separation), None) => *interval = Some((time, time, separation)),
+                    (None, _) => {
+                        if let Some(finished) = interval.take() {
+                            conflicts.push(pair.conflict(finished));
+                        }
+                    }
+                }
+            }
+
+            time += step;
+            phase.step(done)?;
+        }
+        phase.finish();
+
+        for (pair, interval) in pairs.iter().zip(open) {
+            if let Some(finished) = interval {
+                conflicts.push(pair.conflict(finished));
+            }
+        }
+        conflicts.sort_by_key(|c| c.start_time);
+
+        Ok(CoordinationReport {
+            start_time,
+            end_time,
+            config: config.clone(),
+            conflicts,
+        })
+    }
+
+    /// Angular separation when both links are active and below the threshold
+    fn separation_deg(
+        &self,
+        pair: &CandidatePair,
+        states: &HashMap<&str, SatelliteState>,
+        time: DateTime<Utc>,
+    ) -> Option<f64> {
+        let look = |state: &SatelliteState, station: &GroundStation| {
+            state.look_angles_from_station(
+                station.position.latitude_deg,
+                station.position.longitude_deg,
+                station.position.elevation_m,
+            )
+        };
+        let state_a = &states[pair.a.satellite.satellite_id.as_str()];
+        let state_b = &states[pair.b.satellite.satellite_id.as_str()];
+
+        let wanted_a = look(state_a, pair.a.station);
+        let wanted_b = look(state_b, pair.b.station);
+        let active = |link: &ActiveLink, angles: &LookAngles| {
+            link.station.is_available(time) && angles.elevation_deg >= self.config.min_elevation_deg
+        };
+        if !active(&pair.a, &wanted_a) || !active(&pair.b, &wanted_b) {
+            return None;
+        }
+
+        // Interferer as seen by each victim station
+        let at_a = angle_between_deg(&wanted_a, &look(state_b, pair.a.station));
+        let at_b = angle_between_deg(&wanted_b, &look(state_a, pair.b.station));
+        let separation = at_a.min(at_b);
+        (separation < self.config.min_angular_separation_deg).then_some(separation)
+    }
+}
+
+impl CandidatePair<'_> {
+    fn conflict(&self, (start_time, end_time, closest): OpenConflict) -> InterferenceConflict {
+        InterferenceConflict {
+            satellite_a: self.a.satellite.satellite_id.clone(),
+            station_a: self.a.station.station_id.clone(),
+            satellite_b: self.b.satellite.satellite_id.clone(),
+            station_b: self.b.station.station_id.clone(),
+            overlap_min_frequency_hz: self.overlap_hz.0,
+            overlap_max_frequency_hz: self.overlap_hz.1,
+            start_time,
+            end_time,
+            min_angular_separation_deg: closest,
+            station_distance_km: self.station_distance_km,
+        }
+    }
+}
+
+impl CoordinationReport {
+    /// Total time in conflict across all pairs
+    pub fn total_conflict_seconds(&self) -> f64 {
+        self.conflicts.iter().map(|c| c.duration_seconds()).sum()
+    }
+
+    /// Render report as CSV (one row per conflict)
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// Render report as CSV in the units and format of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let mut csv = format!(
+            "satellite_a,station_a,satellite_b,station_b,overlap_min_hz,overlap_max_hz,\
+             start_utc,end_utc,duration_s,min_separation_{},station_distance_{}\n",
+            options.angle_unit.suffix(),
+            options.distance_unit.suffix()
+        );
+        for conflict in &self.conflicts {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                options.satellite_name(&conflict.satellite_a),
+                conflict.station_a,
+                options.satellite_name(&conflict.satellite_b),
+                conflict.station_b,
+                options.decimal(conflict.overlap_min_frequency_hz, Some(0)),
+                options.decimal(conflict.overlap_max_frequency_hz, Some(0)),
+                options.timestamp(conflict.start_time),
+                options.timestamp(conflict.end_time),
+                options.decimal(conflict.duration_seconds(), Some(0)),
+                options.angle(conflict.min_angular_separation_deg, Some(4)),
+                options.distance(conflict.station_distance_km, Some(3))
+            );
+        }
+        csv
+    }
+
+    /// Render report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write report to a CSV file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write report to a CSV file in the units and format of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv_with(options)).for_path(path)
+    }
+
+    /// Write report to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?).for_path(path)
+    }
+}
+
+/// Great-circle distance between two stations
+fn station_distance_km(a: &GroundStation, b: &GroundStation) -> Result<f64> {
+    let position = |station: &GroundStation| {
+        GeodeticPosition::new(
+            station.position.latitude_deg,
+            station.position.longitude_deg,
+            station.position.elevation_m * M_TO_KM,
+        )
+        .for_station(&station.station_id)
+    };
+    Ok(position(a)?.distance_to(&position(b)?))
+}
+
+/// Angle between two topocentric look directions
+fn angle_between_deg(a: &LookAngles, b: &LookAngles) -> f64 {
+    let direction = |angles: &LookAngles| {
+        let (el, az) = (
+            angles.elevation_deg * DEG_TO_RAD,
+            angles.azimuth_deg * DEG_TO_RAD,
+        );
+        [el.cos() * az.sin(), el.cos() * az.cos(), el.sin()]
+    };
+    let (u, v) = (direction(a), direction(b));
+    let cosine = u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+    cosine.clamp(-1.0, 1.0).acos() * RAD_TO_DEG
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConstellationType;
+    use crate::ground_station::StationPosition;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+
+    fn setup(anomaly_offset_deg: f64) -> (Constellation, GroundStationNetwork) {
+        let epoch = Utc::now();
+        let mut constellation = Constellation::new(
+            "COORD".to_string(),
+            "Coordination test".to_string(),
+            ConstellationType::Custom { satellites: vec![] },
+        );
+        for (id, anomaly) in [("SAT-A", 0.0), ("SAT-B", anomaly_offset_deg)] {
+            let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, anomaly).unwrap();
+            constellation
+                .add_satellite(SatelliteOrbit::new(
+                    id.to_string(),
+                    id.to_string(),
+                    elements,
+                    epoch,
+                ))
+                .unwrap();
+        }
+
+        let mut stations = GroundStationNetwork::new();
+        for (id, longitude_deg) in [("GS-A", 0.0), ("GS-B", 0.5)] {
+            stations.add_station(GroundStation {
+                station_id: id.to_string(),
+                name: id.to_string(),
+                position: StationPosition {
+                    latitude_deg: 0.0,
+                    longitude_deg,
+                    elevation_m: 0.0,
+                },
+                availability: Default::default(),
+                mount: None,
+                min_elevation_deg: None,
+                timezone: None,
+            });
+        }
+        (constellation, stations)
+    }
+
+    fn transmissions(band_b: RfBand) -> Vec<Transmission> {
+        vec![
+            Transmission {
+                satellite_id: "SAT-A".to_string(),
+                station_id: "GS-A".to_string(),
+                band: RfBand::new("Ka", 17.7e9, 18.6e9).unwrap(),
+            },
+            Transmission {
+                satellite_id: "SAT-B".to_string(),
+                station_id: "GS-B".to_string(),
+                band: band_b,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_band_overlap() {
+        let ka = RfBand::new("Ka", 17.7e9, 18.6e9).unwrap();
+        let shared = RfBand::new("Ka upper", 18.3e9, 20.2e9).unwrap();
+        assert_eq!(ka.overlap(&shared), Some((18.3e9, 18.6e9)));
+        assert_eq!(ka.overlap(&RfBand::new("X", 8.0e9, 8.4e9).unwrap()), None);
+        assert!(RfBand::new("Bad", 2.0e9, 1.0e9).is_err());
+    }
+
+    #[test]
+    fn test_coordination_conflicts() {
+        let propagator = KeplerianPropagator::new();
+        let analyzer = CoordinationAnalyzer::new();
+        let shared = RfBand::new("Ka", 18.0e9, 18.4e9).unwrap();
+
+        // Co-located satellites 1° apart in anomaly, ~55 km apart stations
+        let (constellation, stations) = setup(1.0);
+        let start = constellation.get_satellite("SAT-A").unwrap().epoch;
+        let report = analyzer
+            .analyze(
+                &constellation,
+                &stations,
+                &transmissions(shared.clone()),
+                &propagator,
+                start,
+                6.0,
+            )
+            .unwrap();
+        assert!(!report.conflicts.is_empty());
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.satellite_a, "SAT-A");
+        assert_eq!(conflict.overlap_min_frequency_hz, 18.0e9);
+        assert!(conflict.min_angular_separation_deg < 2.0);
+        assert!((conflict.station_distance_km - 55.6).abs() < 1.0);
+        assert!(report.total_conflict_seconds() > 600.0);
+        assert_eq!(report.to_csv().lines().count(), report.conflicts.len() + 1);
+
+        // Well separated satellites and disjoint bands do not conflict
+        let (constellation, stations) = setup(20.0);
+        let report = analyzer
+            .analyze(
+                &constellation,
+                &stations,
+                &transmissions(shared),
+                &propagator,
+                start,
+                6.0,
+            )
+            .unwrap();
+        assert!(report.conflicts.is_empty());
+
+        let (constellation, stations) = setup(1.0);
+        let x_band = RfBand::new("X", 8.0e9, 8.4e9).unwrap();
+        let report = analyzer
+            .analyze(
+                &constellation,
+                &stations,
+                &transmissions(x_band.clone()),
+                &propagator,
+                start,
+                6.0,
+            )
+            .unwrap();
+        assert!(report.conflicts.is_empty());
+
+        // NaN durations and steps are rejected, not treated as valid
+        let nan_duration = analyzer.analyze(
+            &constellation,
+            &stations,
+            &transmissions(x_band.clone()),
+            &propagator,
+            start,
+            f64::NAN,
+        );
+        assert!(nan_duration.is_err());
+        let mut analyzer = analyzer;
+        analyzer.config.step_seconds = f64::NAN;
+        let nan_step = analyzer.analyze(
+            &constellation,
+            &stations,
+            &transmissions(x_band),
+            &propagator,
+            start,
+            6.0,
+        );
+        assert!(nan_step.is_err());
+    }
+}
@@ -0,0 +1,403 @@
+//! State covariance and ephemeris export
+//!
+//! Carries 6x6 ECI position/velocity covariances through the numerical
+//! propagator's state transition matrix, reduces them to position
+//! uncertainty ellipsoids, and exports time-tagged states with their
+//! uncertainty for orbit determination products.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::orbit::SatelliteOrbit;
+use crate::propagator::{NumericalPropagator, OrbitalPropagator};
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::{Matrix3, Matrix6, SymmetricEigen};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// 6x6 matrix over the ECI state [x, y, z, vx, vy, vz]
+pub type StateMatrix = [[f64; 6]; 6];
+
+/// Covariance of the ECI state in km², km²/s and km²/s²
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateCovariance {
+    pub matrix: StateMatrix,
+}
+
+/// One-sigma position uncertainty ellipsoid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncertaintyEllipsoid {
+    /// Semi-axes in descending order
+    pub semi_axes_km: [f64; 3],
+    /// ECI unit vector along each semi-axis
+    pub axes_eci: [[f64; 3]; 3],
+}
+
+/// Time-tagged state with optional uncertainty
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisRecord {
+    pub timestamp: DateTime<Utc>,
+    pub position_eci: [f64; 3],
+    pub velocity_eci: [f64; 3],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub covariance: Option<StateCovariance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position_ellipsoid: Option<UncertaintyEllipsoid>,
+}
+
+/// Ephemeris of one satellite over a time span
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateEphemeris {
+    pub satellite_id: String,
+    pub propagator: String,
+    pub step_seconds: f64,
+    pub records: Vec<EphemerisRecord>,
+}
+
+impl StateCovariance {
+    /// Diagonal covariance from per-axis position and velocity sigmas
+    pub fn from_sigmas(position_sigma_km: f64, velocity_sigma_km_s: f64) -> Self {
+        let mut matrix = [[0.0; 6]; 6];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            let sigma = if i < 3 {
+                position_sigma_km
+            } else {
+                velocity_sigma_km_s
+            };
+            row[i] = sigma * sigma;
+        }
+        Self { matrix }
+    }
+
+    /// Map through a state transition matrix: Φ P Φᵀ
+    pub fn propagate(&self, stm: &StateMatrix) -> Self {
+        let phi = to_matrix6(stm);
+        let propagated = phi * to_matrix6(&self.matrix) * phi.transpose();
+        Self {
+            matrix: from_matrix6(&propagated),
+        }
+    }
+
+    /// Upper-left 3x3 position block
+    pub fn position_covariance(&self) -> [[f64; 3]; 3] {
+        let mut block = [[0.0; 3]; 3];
+        for (i, row) in block.iter_mut().enumerate() {
+            row.copy_from_slice(&self.matrix[i][..3]);
+        }
+        block
+    }
+
+    /// Root-sum-square position sigma
+    pub fn position_sigma_km(&self) -> f64 {
+        (0..3).map(|i| self.matrix[i][i]).sum::<f64>().sqrt()
+    }
+
+    /// One-sigma position ellipsoid from the eigen-decomposition of the
+    /// position block
+    pub fn position_ellipsoid(&self) -> UncertaintyEllipsoid {
+        let eigen = SymmetricEigen::new(Matrix3::from_fn(|i, j| self.matrix[i][j]));
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+        UncertaintyEllipsoid {
+            semi_axes_km: order.map(|i| eigen.eigenvalues[i].max(0.0).sqrt()),
+            axes_eci: order.map(|i| {
+                let axis = eigen.eigenvectors.column(i);
+                [axis[0], axis[1], axis[2]]
+            }),
+        }
+    }
+}
+
+impl EphemerisRecord {
+    fn new(
+        timestamp: DateTime<Utc>,
+        position_eci: [f64; 3],
+        velocity_eci: [f64; 3],
+        covariance: Option<StateCovariance>,
+    ) -> Self {
+        Self {
+            timestamp,
+            position_eci,
+            velocity_eci,
+            position_ellipsoid: covariance.as_ref().map(|c| c.position_ellipsoid()),
+            covariance,
+        }
+    }
+}
+
+impl StateEphemeris {
+    /// Sample a satellite's states at a fixed step
+    pub fn generate(
+        propagator: &dyn OrbitalPropagator,
+        satellite: &SatelliteOrbit,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        step_seconds: f64,
+    ) -> Result<Self> {
+        let mut records = Vec::new();
+        for time in sample_times(start_time, end_time, step_seconds)? {
+            let state = propagator
+                .propagate(satellite, time)
+                .for_satellite(&satellite.satellite_id)
+                .at_epoch(time)?;
+            records.push(EphemerisRecord::new(
+                time,
+                state.position_eci,
+                state.velocity_eci,
+                None,
+            ));
+        }
+
+        Ok(Self {
+            satellite_id: satellite.satellite_id.clone(),
+            propagator: propagator.name().to_string(),
+            step_seconds,
+            records,
+        })
+    }
+
+    /// Sample states with the covariance propagated from the element epoch
+    ///
+    /// The state and STM are integrated once through the samples, so the
+    /// cost grows linearly with the span.
+    pub fn generate_with_covariance(
+        propagator: &NumericalPropagator,
+        satellite: &SatelliteOrbit,
+        epoch_covariance: &StateCovariance,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        step_seconds: f64,
+    ) -> Result<Self> {
+        let times = sample_times(start_time, end_time, step_seconds)?;
+        let records = propagator
+            .propagate_with_stm_series(satellite, &times)
+            .for_satellite(&satellite.satellite_id)?
+            .into_iter()
+            .map(|(state, stm)| {
+                EphemerisRecord::new(
+                    state.timestamp,
+                    state.position_eci,
+                    state.velocity_eci,
+                    Some(epoch_covariance.propagate(&stm)),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            satellite_id: satellite.satellite_id.clone(),
+            propagator: propagator.name().to_string(),
+            step_seconds,
+            records,
+        })
+    }
+
+    /// Render ephemeris as CSV
+    ///
+    /// Uncertainty columns (per-axis position sigmas and ellipsoid semi-axes)
+    /// are included when any record carries a covariance. Epochs inside a
+    /// leap second are written as `23:59:60.xxx`.
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// Render ephemeris as CSV in the units and format of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let has_covariance = self.records.iter().any(|r| r.covariance.is_some());
+
+        let mut csv = format!(
+            "timestamp_utc,x_{0},y_{0},z_{0},vx_{0}_s,vy_{0}_s,vz_{0}_s",
+            options.distance_unit.suffix()
+        );
+        if has_covariance {
+            let _ = write!(
+                csv,
+                ",sigma_x_{0},sigma_y_{0},sigma_z_{0},\
+                 semi_major_{0},semi_intermediate_{0},semi_minor_{0}",
+                options.distance_unit.suffix()
+            );
+        }
+        csv.push('\n');
+
+        for record in &self.records {
+            let [x, y, z] = record.position_eci;
+            let [vx, vy, vz] = record.velocity_eci;
+            let _ = write!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                options.timestamp(record.timestamp),
+                options.distance(x, Some(6)),
+                options.distance(y, Some(6)),
+                options.distance(z, Some(6)),
+                options.distance(vx, Some(9)),
+                options.distance(vy, Some(9)),
+                options.distance(vz, Some(9))
+            );
+            if has_covariance {
+                let sigmas = record
+                    .covariance
+                    .as_ref()
+                    .map_or([f64::NAN; 3], |c| [0, 1, 2].map(|i| c.matrix[i][i].sqrt()));
+                let axes = record
+                    .position_ellipsoid
+                    .as_ref()
+                    .map_or([f64::NAN; 3], |e| e.semi_axes_km);
+                for value in sigmas.iter().chain(axes.iter()) {
+                    let _ = write!(csv, ",{}", options.distance(*value, Some(6)));
+                }
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Render ephemeris as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write ephemeris to a CSV file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write ephemeris to a CSV file in the units and format of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv_with(options)).for_path(path)?;
+        Ok(())
+    }
+
+    /// Write ephemeris to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?).for_path(path)?;
+        Ok(())
+    }
+}
+
+fn sample_times(
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    step_seconds: f64,
+) -> Result<Vec<DateTime<Utc>>> {
+    if !step_seconds.is_finite() || step_seconds < 0.001 || end_time < start_time {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Ephemeris needs a step of at least 1 ms and end after start, got {} s from {} to {}",
+            step_seconds, start_time, end_time
+        )));
+    }
+
+    let step = Duration::milliseconds((step_seconds * 1000.0).round().max(1.0) as i64);
+    let mut times = Vec::new();
+    let mut time = start_time;
+    while time <= end_time {
+        times.push(time);
+        time += step;
+    }
+    Ok(times)
+}
+
+fn to_matrix6(matrix: &StateMatrix) -> Matrix6<f64> {
+    Matrix6::from_fn(|i, j| matrix[i][j])
+}
+
+fn from_matrix6(matrix: &Matrix6<f64>) -> StateMatrix {
+    std::array::from_fn(|i| std::array::from_fn(|j| matrix[(i, j)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+
+    #[test]
+    fn test_position_ellipsoid() {
+        // Correlated x/y block: eigenvalues 4 ± 3 along (1, 1) and (1, -1)
+        let mut covariance = StateCovariance::from_sigmas(1.0, 1e-3);
+        covariance.matrix[0][0] = 4.0;
+        covariance.matrix[1][1] = 4.0;
+        covariance.matrix[0][1] = 3.0;
+        covariance.matrix[1][0] = 3.0;
+
+        let ellipsoid = covariance.position_ellipsoid();
+        assert!((ellipsoid.semi_axes_km[0] - 7.0_f64.sqrt()).abs() < 1e-9);
+        assert!((ellipsoid.semi_axes_km[1] - 1.0).abs() < 1e-9);
+        assert!((ellipsoid.semi_axes_km[2] - 1.0).abs() < 1e-9);
+        let major = ellipsoid.axes_eci[0];
+        assert!((major[0].abs() - 0.5_f64.sqrt()).abs() < 1e-9);
+        assert!((major[0] - major[1]).abs() < 1e-9);
+        assert!((covariance.position_sigma_km() - 9.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sample_times_rejects_degenerate_steps() {
+        let start = Utc::now();
+        let end = start + Duration::seconds(1);
+        assert!(sample_times(start, end, f64::NAN).is_err());
+        assert!(sample_times(start, end, 0.0004).is_err());
+        assert_eq!(sample_times(start, end, 0.001).unwrap().len(), 1001);
+    }
+
+    #[test]
+    fn test_ephemeris_with_covariance() {
+        let epoch = Utc::now();
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "OD-01".to_string(),
+            "Covariance".to_string(),
+            elements,
+            epoch,
+        );
+        let propagator = NumericalPropagator::new(60.0);
+        let initial = StateCovariance::from_sigmas(0.1, 1e-5);
+
+        let ephemeris = StateEphemeris::generate_with_covariance(
+            &propagator,
+            &satellite,
+            &initial,
+            epoch,
+            epoch + Duration::hours(6),
+            3600.0,
+        )
+        .unwrap();
+        assert_eq!(ephemeris.records.len(), 7);
+
+        // Uncertainty grows, stretched along track
+        let first = ephemeris.records[0].position_ellipsoid.as_ref().unwrap();
+        let last = ephemeris.records[6].position_ellipsoid.as_ref().unwrap();
+        assert!((first.semi_axes_km[0] - 0.1).abs() < 1e-6);
+        assert!(last.semi_axes_km[0] > 5.0 * first.semi_axes_km[0]);
+        let record = &ephemeris.records[6];
+        let speed = record
+            .velocity_eci
+            .iter()
+            .map(|v| v * v)
+            .sum::<f64>()
+            .sqrt();
+        let along_track = (0..3)
+            .map(|i| last.axes_eci[0][i] * record.velocity_eci[i] / speed)
+            .sum::<f64>();
+        assert!(along_track.abs() > 0.95);
+
+        // Stepping through the samples matches propagating each from epoch
+        let (_, direct) = propagator
+            .propagate_covariance(&satellite, record.timestamp, &initial)
+            .unwrap();
+        let stepped = record.covariance.as_ref().unwrap();
+        for (row, direct_row) in stepped.matrix.iter().zip(&direct.matrix) {
+            for (value, expected) in row.iter().zip(direct_row) {
+                assert!((value - expected).abs() < 1e-6 * direct.matrix[0][0].abs().max(1.0));
+            }
+        }
+
+        let csv = ephemeris.to_csv();
+        assert_eq!(csv.lines().count(), 8);
+        assert!(csv.lines().next().unwrap().ends_with("semi_minor_km"));
+        let json = ephemeris.to_json().unwrap();
+        let parsed: StateEphemeris = serde_json::from_str(&json).unwrap();
+        assert!(parsed.records[6].covariance.is_some());
+    }
+}
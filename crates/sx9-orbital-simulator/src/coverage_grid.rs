@@ -0,0 +1,415 @@
+//! Global coverage grids
+//!
+//! Counts, for every cell of a latitude/longitude grid, how many satellites
+//! are above the elevation mask at one instant. Cell centers use the same
+//! spherical, non-rotating station model as the visibility calculator. With
+//! the `gpu` feature the elevation tests run in a compute shader, falling
+//! back to the CPU when no adapter is available.
+//!
+//! Equal-angle cells shrink toward the poles, so coverage shares are
+//! available by cell count and by area; lookups wrap longitude across the
+//! antimeridian and place the poles in the first and last rows.
+
+use crate::constants::{defaults, DEG_TO_RAD};
+use crate::coordinates::{wrap_angle_deg, Position3D, TopocentricFrame};
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::export::ExportOptions;
+use crate::orbit::SatelliteState;
+use crate::progress::{NoProgress, Phase, ProgressSink};
+use crate::trace_targets;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::time::Instant;
+
+/// Where the elevation tests are evaluated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum CoverageBackend {
+    /// GPU when built with `gpu` and an adapter is found, otherwise CPU
+    #[default]
+    Auto,
+    Cpu,
+    /// GPU only; an error if it is unavailable
+    Gpu,
+}
+
+/// Coverage grid settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageGridConfig {
+    pub resolution_deg: f64,
+    pub min_elevation_deg: f64,
+    pub backend: CoverageBackend,
+}
+
+/// Visible satellite counts over a global grid at one epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CoverageGrid {
+    pub epoch: DateTime<Utc>,
+    pub resolution_deg: f64,
+    pub min_elevation_deg: f64,
+    pub rows: usize,
+    pub columns: usize,
+    /// Satellites above the mask per cell, by latitude then longitude,
+    /// starting south-west
+    pub visible_counts: Vec<u32>,
+    /// Backend that produced the counts (`Cpu` or `Gpu`)
+    pub backend: CoverageBackend,
+}
+
+impl Default for CoverageGridConfig {
+    fn default() -> Self {
+        Self {
+            resolution_deg: 1.0,
+            min_elevation_deg: defaults::MIN_ELEVATION_DEG,
+            backend: CoverageBackend::Auto,
+        }
+    }
+}
+
+impl CoverageGridConfig {
+    pub fn with_resolution(mut self, resolution_deg: f64) -> Self {
+        self.resolution_deg = resolution_deg;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: CoverageBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+}
+
+impl CoverageGrid {
+    /// Count visible satellites in every cell
+    ///
+    /// All states should share one epoch; the first state's timestamp is
+    /// recorded as the grid epoch.
+    pub fn compute(states: &[SatelliteState], config: &CoverageGridConfig) -> Result<Self> {
+        Self::compute_with_progress(states, config, &NoProgress)
+    }
+
+    /// Count visible satellites in every cell, reporting progress to `progress`
+    ///
+    /// The CPU backend reports, and checks for cancellation, after every grid
+    /// row; the GPU backend only at the start and the end.
+    pub fn compute_with_progress(
+        states: &[SatelliteState],
+        config: &CoverageGridConfig,
+        progress: &dyn ProgressSink,
+    ) -> Result<Self> {
+        let resolution_deg = config.resolution_deg;
+        if !resolution_deg.is_finite() || resolution_deg <= 0.0 || resolution_deg > 90.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Coverage grid resolution must be in (0, 90] degrees, got {}",
+                resolution_deg
+            )));
+        }
+
+        let started = Instant::now();
+        let rows = (180.0 / resolution_deg).ceil() as usize;
+        let columns = (360.0 / resolution_deg).ceil() as usize;
+        let positions: Vec<Position3D> = states
+            .iter()
+            .map(|s| Position3D::from(s.position_eci))
+            .collect();
+
+        let phase = Phase::start(progress, "coverage", rows)?;
+        let (visible_counts, backend) = match config.backend {
+            CoverageBackend::Cpu => (
+                cpu_counts(&positions, rows, columns, config, &phase)?,
+                CoverageBackend::Cpu,
+            ),
+            CoverageBackend::Gpu => (
+                gpu_counts(&positions, rows, columns, config)?,
+                CoverageBackend::Gpu,
+            ),
+            CoverageBackend::Auto => match gpu_counts(&positions, rows, columns, config) {
+                Ok(counts) => (counts, CoverageBackend::Gpu),
+                Err(err) => {
+                    tracing::debug!(
+                        target: trace_targets::VISIBILITY,
+                        error = %err,
+                        "GPU coverage unavailable, using CPU"
+                    );
+                    (
+                        cpu_counts(&positions, rows, columns, config, &phase)?,
+                        CoverageBackend::Cpu,
+                    )
+                }
+            },
+        };
+        phase.finish();
+
+        tracing::debug!(
+            target: trace_targets::VISIBILITY,
+            cells = rows * columns,
+            satellites = states.len(),
+            backend = ?backend,
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Coverage grid computed"
+        );
+
+        Ok(Self {
+            epoch: states.first().map_or_else(Utc::now, |s| s.timestamp),
+            resolution_deg,
+            min_elevation_deg: config.min_elevation_deg,
+            rows,
+            columns,
+            visible_counts,
+            backend,
+        })
+    }
+
+    /// Center of a cell as (latitude, longitude) in degrees
+    pub fn cell_center(&self, row: usize, column: usize) -> (f64, f64) {
+        cell_center(self.resolution_deg, row, column)
+    }
+
+    /// Visible satellites at a location
+    pub fn visible_at(&self, latitude_deg: f64, longitude_deg: f64) -> Option<u32> {
+        let (row, column) = grid_cell(self.resolution_deg, latitude_deg, longitude_deg)?;
+        self.visible_counts
+            .get(row * self.columns + column)
+            .copied()
+    }
+
+    /// Share of cells seeing at least `min_satellites` (0-100%, by cell count)
+    pub fn coverage_percent(&self, min_satellites: u32) -> f64 {
+        if self.visible_counts.is_empty() {
+            return 0.0;
+        }
+        let covered = self
+            .visible_counts
+            .iter()
+            .filter(|&&count| count >= min_satellites)
+            .count();
+        covered as f64 / self.visible_counts.len() as f64 * 100.0
+    }
+
+    /// Share of the Earth's surface seeing at least `min_satellites` (0-100%)
+    ///
+    /// Cells are weighted by their area on the sphere, so the many narrow
+    /// cells around the poles do not inflate polar-orbit coverage.
+    pub fn area_coverage_percent(&self, min_satellites: u32) -> f64 {
+        let (mut covered, mut total) = (0.0, 0.0);
+        for row in 0..self.rows {
+            let south_deg = (-90.0 + row as f64 * self.resolution_deg).min(90.0);
+            let north_deg = (south_deg + self.resolution_deg).min(90.0);
+            let band = (north_deg * DEG_TO_RAD).sin() - (south_deg * DEG_TO_RAD).sin();
+            for column in 0..self.columns {
+                let west_deg = column as f64 * self.resolution_deg;
+                let width_deg = (west_deg + self.resolution_deg).min(360.0) - west_deg;
+                let area = band * width_deg;
+                total += area;
+                if self.visible_counts[row * self.columns + column] >= min_satellites {
+                    covered += area;
+                }
+            }
+        }
+        if total > 0.0 {
+            covered / total * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// CSV with one row per cell
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// CSV with one row per cell, in the units and format of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let mut csv = format!(
+            "latitude_{0},longitude_{0},visible_satellites\n",
+            options.angle_unit.suffix()
+        );
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let (latitude_deg, longitude_deg) = self.cell_center(row, column);
+                let _ = writeln!(
+                    csv,
+                    "{},{},{}",
+                    options.angle(latitude_deg, None),
+                    options.angle(longitude_deg, None),
+                    self.visible_counts[row * self.columns + column]
+                );
+            }
+        }
+        csv
+    }
+}
+
+/// Row and column of the equal-angle cell containing a location
+///
+/// Longitude wraps, so 180°E and 180°W share a column; each pole belongs to
+/// the row that touches it. `None` for latitudes beyond ±90°.
+pub(crate) fn grid_cell(
+    resolution_deg: f64,
+    latitude_deg: f64,
+    longitude_deg: f64,
+) -> Option<(usize, usize)> {
+    if !(-90.0..=90.0).contains(&latitude_deg) || !longitude_deg.is_finite() {
+        return None;
+    }
+    let rows = (180.0 / resolution_deg).ceil() as usize;
+    let columns = (360.0 / resolution_deg).ceil() as usize;
+    let row = ((latitude_deg + 90.0) / resolution_deg).floor() as usize;
+    let column = ((wrap_angle_deg(longitude_deg) + 180.0) / resolution_deg).floor() as usize;
+    Some((row.min(rows - 1), column.min(columns - 1)))
+}
+
+fn cell_center(resolution_deg: f64, row: usize, column: usize) -> (f64, f64) {
+    (
+        (-90.0 + (row as f64 + 0.5) * resolution_deg).min(90.0),
+        (-180.0 + (column as f64 + 0.5) * resolution_deg).min(180.0),
+    )
+}
+
+fn cpu_counts(
+    positions: &[Position3D],
+    rows: usize,
+    columns: usize,
+    config: &CoverageGridConfig,
+    phase: &Phase,
+) -> Result<Vec<u32>> {
+    let sin_min_elevation = (config.min_elevation_deg * DEG_TO_RAD).sin();
+    let mut counts = Vec::with_capacity(rows * columns);
+    for row in 0..rows {
+        for column in 0..columns {
+            let (latitude_deg, longitude_deg) = cell_center(config.resolution_deg, row, column);
+            let frame = TopocentricFrame::new(latitude_deg, longitude_deg, 0.0);
+            let visible = frame
+                .transform_batch(positions)
+                .iter()
+                .filter(|t| t.zenith_km >= sin_min_elevation * t.range_km)
+                .count();
+            counts.push(visible as u32);
+        }
+        phase.step(row + 1)?;
+    }
+    Ok(counts)
+}
+
+#[cfg(feature = "gpu")]
+fn gpu_counts(
+    positions: &[Position3D],
+    rows: usize,
+    columns: usize,
+    config: &CoverageGridConfig,
+) -> Result<Vec<u32>> {
+    crate::coverage_gpu::visible_counts(
+        positions,
+        rows,
+        columns,
+        config.resolution_deg,
+        config.min_elevation_deg,
+    )
+}
+
+#[cfg(not(feature = "gpu"))]
+fn gpu_counts(
+    _positions: &[Position3D],
+    _rows: usize,
+    _columns: usize,
+    _config: &CoverageGridConfig,
+) -> Result<Vec<u32>> {
+    Err(OrbitalMechanicsError::GpuError(
+        "built without the gpu feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geo_coverage_grid() {
+        // GEO satellite above 0°N 0°E in the non-rotating frame
+        let state = SatelliteState::new(
+            "GEO-01".to_string(),
+            Utc::now(),
+            [42164.0, 0.0, 0.0],
+            [0.0, 3.0747, 0.0],
+        );
+        let config = CoverageGridConfig::default()
+            .with_resolution(5.0)
+            .with_backend(CoverageBackend::Cpu);
+        let grid = CoverageGrid::compute(std::slice::from_ref(&state), &config).unwrap();
+
+        assert_eq!((grid.rows, grid.columns), (36, 72));
+        assert_eq!(grid.backend, CoverageBackend::Cpu);
+        assert_eq!(grid.visible_at(1.0, 1.0), Some(1));
+        assert_eq!(grid.visible_at(1.0, 179.0), Some(0));
+        assert_eq!(grid.visible_at(89.0, 1.0), Some(0));
+        assert_eq!(grid.visible_at(95.0, 1.0), None);
+
+        // 10° mask leaves a cap of ~71° Earth-central angle
+        let covered = grid.coverage_percent(1);
+        assert!(covered > 20.0 && covered < 40.0, "{}", covered);
+        assert_eq!(grid.to_csv().lines().count(), 36 * 72 + 1);
+
+        // Automatic backend gives the same counts whichever device ran it
+        let auto = CoverageGrid::compute(
+            std::slice::from_ref(&state),
+            &config.clone().with_backend(CoverageBackend::Auto),
+        )
+        .unwrap();
+        assert_eq!(auto.visible_counts, grid.visible_counts);
+
+        // The CPU backend reports once per row
+        let reports = std::cell::RefCell::new(Vec::new());
+        let progress = |percent: f64, phase: &str| {
+            assert_eq!(phase, "coverage");
+            reports.borrow_mut().push(percent);
+        };
+        CoverageGrid::compute_with_progress(&[state], &config, &progress).unwrap();
+        let reports = reports.into_inner();
+        assert_eq!(reports.len(), 36 + 1);
+        assert_eq!((reports[0], reports[36]), (0.0, 100.0));
+    }
+
+    #[test]
+    fn test_polar_constellation_grid_at_poles_and_antimeridian() {
+        use crate::orbit::{OrbitalElements, SatelliteOrbit};
+        use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+
+        // Six 90° planes of six satellites, phased 10° between planes
+        let epoch = Utc::now();
+        let propagator = KeplerianPropagator::new();
+        let states: Vec<SatelliteState> = (0..36)
+            .map(|i| {
+                let (plane, slot) = ((i / 6) as f64, (i % 6) as f64);
+                let mean_anomaly_deg = slot * 60.0 + plane * 10.0;
+                let elements =
+                    OrbitalElements::new(7000.0, 0.0, 90.0, plane * 30.0, 0.0, mean_anomaly_deg)
+                        .unwrap();
+                let orbit =
+                    SatelliteOrbit::new(format!("POL-{}", i), String::new(), elements, epoch);
+                propagator.propagate(&orbit, epoch).unwrap()
+            })
+            .collect();
+        let config = CoverageGridConfig::default()
+            .with_resolution(5.0)
+            .with_backend(CoverageBackend::Cpu);
+        let grid = CoverageGrid::compute(&states, &config).unwrap();
+
+        // Poles and both sides of the antimeridian resolve to cells
+        assert!(grid.visible_at(90.0, 15.39).unwrap() > 0);
+        assert!(grid.visible_at(-90.0, 15.39).is_some());
+        assert_eq!(grid.visible_at(0.0, 180.0), grid.visible_at(0.0, -180.0));
+        assert_eq!(
+            grid.visible_at(78.23, 195.39),
+            grid.visible_at(78.23, -164.61)
+        );
+        assert_eq!(grid.visible_at(90.1, 0.0), None);
+
+        // Narrow polar cells see the most satellites, so counting cells
+        // overstates how much of the Earth has multiple coverage
+        let by_area = grid.area_coverage_percent(2);
+        let by_cells = grid.coverage_percent(2);
+        assert!(by_area < by_cells, "{} vs {}", by_area, by_cells);
+        assert!((grid.area_coverage_percent(0) - 100.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,242 @@
+//! Store-and-forward data volume model
+//!
+//! Satellites accumulate payload data at a configured rate into a finite
+//! onboard buffer and drain it first-in-first-out while a ground link is
+//! available. Tracks backlog, dropped data and end-to-end latency.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Maximum number of latency samples retained per buffer
+pub const MAX_LATENCY_SAMPLES: usize = 10_000;
+
+/// Onboard data generation and storage parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataVolumeConfig {
+    /// Payload data generation rate in bits per second
+    pub generation_rate_bps: f64,
+    /// Onboard storage capacity in bits
+    pub buffer_capacity_bits: f64,
+    /// Terminal limit on downlink rate in bits per second
+    pub max_downlink_rate_bps: Option<f64>,
+}
+
+/// Data volume summary for one satellite
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataVolumeStatistics {
+    pub generated_bits: f64,
+    pub delivered_bits: f64,
+    pub dropped_bits: f64,
+    pub backlog_bits: f64,
+    /// Buffer fill level (0-100%)
+    pub buffer_fill_percent: f64,
+    pub latency_p50_seconds: Option<f64>,
+    pub latency_p90_seconds: Option<f64>,
+    pub latency_p99_seconds: Option<f64>,
+}
+
+/// Data generated at one instant and still awaiting downlink
+#[derive(Debug, Clone)]
+struct DataChunk {
+    created: DateTime<Utc>,
+    remaining_bits: f64,
+}
+
+/// Onboard FIFO data buffer
+#[derive(Debug, Clone)]
+pub struct DataBuffer {
+    pub config: DataVolumeConfig,
+    chunks: VecDeque<DataChunk>,
+    backlog_bits: f64,
+    generated_bits: f64,
+    delivered_bits: f64,
+    dropped_bits: f64,
+    latencies_seconds: VecDeque<f64>,
+}
+
+impl DataVolumeConfig {
+    /// Create configuration with the given generation rate and buffer size
+    pub fn new(generation_rate_bps: f64, buffer_capacity_bits: f64) -> Self {
+        Self {
+            generation_rate_bps,
+            buffer_capacity_bits,
+            max_downlink_rate_bps: None,
+        }
+    }
+
+    /// Limit the downlink rate to the terminal capability
+    pub fn with_max_downlink_rate(mut self, max_downlink_rate_bps: f64) -> Self {
+        self.max_downlink_rate_bps = Some(max_downlink_rate_bps);
+        self
+    }
+}
+
+impl Default for DataVolumeConfig {
+    fn default() -> Self {
+        // 100 Mbps payload, 1 Tbit recorder, 10 Gbps optical terminal
+        Self::new(100.0e6, 1.0e12).with_max_downlink_rate(10.0e9)
+    }
+}
+
+impl DataBuffer {
+    /// Create empty buffer
+    pub fn new(config: DataVolumeConfig) -> Self {
+        Self {
+            config,
+            chunks: VecDeque::new(),
+            backlog_bits: 0.0,
+            generated_bits: 0.0,
+            delivered_bits: 0.0,
+            dropped_bits: 0.0,
+            latencies_seconds: VecDeque::new(),
+        }
+    }
+
+    /// Accumulate data generated over `duration_seconds` ending at `time`
+    ///
+    /// Data that does not fit in the buffer is dropped (tail drop).
+    pub fn generate(&mut self, time: DateTime<Utc>, duration_seconds: f64) {
+        let bits = self.config.generation_rate_bps * duration_seconds.max(0.0);
+        if bits <= 0.0 {
+            return;
+        }
+        self.generated_bits += bits;
+
+        let free = (self.config.buffer_capacity_bits - self.backlog_bits).max(0.0);
+        let stored = bits.min(free);
+        self.dropped_bits += bits - stored;
+
+        if stored > 0.0 {
+            self.backlog_bits += stored;
+            self.chunks.push_back(DataChunk {
+                created: time,
+                remaining_bits: stored,
+            });
+        }
+    }
+
+    /// Downlink at `link_rate_bps` for `duration_seconds` ending at `time`
+    ///
+    /// Returns the number of bits delivered.
+    pub fn drain(&mut self, time: DateTime<Utc>, link_rate_bps: f64, duration_seconds: f64) -> f64 {
+        let rate = match self.config.max_downlink_rate_bps {
+            Some(max) => link_rate_bps.min(max),
+            None => link_rate_bps,
+        };
+        let mut capacity = rate.max(0.0) * duration_seconds.max(0.0);
+        let mut delivered = 0.0;
+
+        while capacity > 0.0 {
+            let Some(chunk) = self.chunks.front_mut() else {
+                break;
+            };
+
+            let sent = chunk.remaining_bits.min(capacity);
+            chunk.remaining_bits -= sent;
+            capacity -= sent;
+            delivered += sent;
+
+            if chunk.remaining_bits <= 0.0 {
+                let latency = (time - chunk.created).num_milliseconds() as f64 / 1000.0;
+                self.record_latency(latency.max(0.0));
+                self.chunks.pop_front();
+            }
+        }
+
+        self.backlog_bits = (self.backlog_bits - delivered).max(0.0);
+        self.delivered_bits += delivered;
+        delivered
+    }
+
+    /// Bits waiting for downlink
+    pub fn backlog_bits(&self) -> f64 {
+        self.backlog_bits
+    }
+
+    /// Summary of generated, delivered and dropped data
+    pub fn statistics(&self) -> DataVolumeStatistics {
+        let mut latencies: Vec<f64> = self.latencies_seconds.iter().copied().collect();
+        latencies.sort_by(|a, b| a.total_cmp(b));
+
+        DataVolumeStatistics {
+            generated_bits: self.generated_bits,
+            delivered_bits: self.delivered_bits,
+            dropped_bits: self.dropped_bits,
+            backlog_bits: self.backlog_bits,
+            buffer_fill_percent: if self.config.buffer_capacity_bits > 0.0 {
+                self.backlog_bits / self.config.buffer_capacity_bits * 100.0
+            } else {
+                0.0
+            },
+            latency_p50_seconds: percentile(&latencies, 50.0),
+            latency_p90_seconds: percentile(&latencies, 90.0),
+            latency_p99_seconds: percentile(&latencies, 99.0),
+        }
+    }
+
+    fn record_latency(&mut self, latency_seconds: f64) {
+        if self.latencies_seconds.len() == MAX_LATENCY_SAMPLES {
+            self.latencies_seconds.pop_front();
+        }
+        self.latencies_seconds.push_back(latency_seconds);
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice
+pub fn percentile(sorted: &[f64], percent: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_buffer_overflow_drops_data() {
+        let mut buffer = DataBuffer::new(DataVolumeConfig::new(1000.0, 5000.0));
+        let t0 = Utc::now();
+
+        buffer.generate(t0, 3.0);
+        buffer.generate(t0 + Duration::seconds(3), 3.0);
+
+        let stats = buffer.statistics();
+        assert_eq!(stats.generated_bits, 6000.0);
+        assert_eq!(stats.backlog_bits, 5000.0);
+        assert_eq!(stats.dropped_bits, 1000.0);
+        assert_eq!(stats.buffer_fill_percent, 100.0);
+    }
+
+    #[test]
+    fn test_fifo_drain_and_latency() {
+        let mut buffer =
+            DataBuffer::new(DataVolumeConfig::new(1000.0, 1.0e9).with_max_downlink_rate(1500.0));
+        let t0 = Utc::now();
+
+        for i in 0..10 {
+            buffer.generate(t0 + Duration::seconds(i), 1.0);
+        }
+
+        // Link capped at 1500 bps: 15 kbit over 10 s clears everything
+        let delivered = buffer.drain(t0 + Duration::seconds(20), 1.0e9, 10.0);
+        assert_eq!(delivered, 10_000.0);
+        assert_eq!(buffer.backlog_bits(), 0.0);
+
+        let stats = buffer.statistics();
+        assert_eq!(stats.latency_p50_seconds, Some(15.0));
+        assert_eq!(stats.latency_p99_seconds, Some(20.0));
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        assert_eq!(percentile(&values, 50.0), Some(5.0));
+        assert_eq!(percentile(&values, 90.0), Some(9.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+}
@@ -0,0 +1,344 @@
+//! End-of-life disposal compliance
+//!
+//! Checks proposed disposal orbits against configurable rules (graveyard
+//! clearance above the operational shell, eccentricity, protected regions)
+//! and estimates the delta-v to reach them.
+
+use crate::constants::*;
+use crate::constellation::Constellation;
+use crate::error::Result;
+use crate::orbit::{OrbitalElements, SatelliteOrbit};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Disposal orbit requirements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisposalRules {
+    /// Minimum gap between graveyard perigee and the top of the operational shell
+    pub min_graveyard_clearance_km: f64,
+    /// Maximum graveyard eccentricity, limiting long-term perigee decay into the shell
+    pub max_graveyard_eccentricity: f64,
+    /// Perigee altitude at or below which the disposal counts as direct reentry
+    pub reentry_perigee_altitude_km: f64,
+    /// Reject non-reentry disposal orbits that dip into LEO
+    pub protect_leo: bool,
+    /// Delta-v reserved for disposal; `None` skips the budget check
+    pub delta_v_budget_m_per_s: Option<f64>,
+}
+
+/// Altitude band occupied by the operational constellation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationalShell {
+    pub min_perigee_altitude_km: f64,
+    pub max_apogee_altitude_km: f64,
+}
+
+/// How a disposal orbit removes the satellite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisposalStrategy {
+    Graveyard,
+    Reentry,
+}
+
+/// Compliance of one satellite's disposal orbit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisposalCompliance {
+    pub satellite_id: String,
+    pub strategy: DisposalStrategy,
+    pub disposal_perigee_altitude_km: f64,
+    pub disposal_apogee_altitude_km: f64,
+    /// Two-impulse estimate from the current orbit
+    pub delta_v_m_per_s: f64,
+    /// Rules the disposal orbit breaks; empty if compliant
+    pub violations: Vec<String>,
+}
+
+/// Disposal compliance checks
+#[derive(Debug, Clone)]
+pub struct DisposalAnalyzer {
+    pub rules: DisposalRules,
+}
+
+impl Default for DisposalRules {
+    fn default() -> Self {
+        Self {
+            min_graveyard_clearance_km: 500.0,
+            max_graveyard_eccentricity: 0.003,
+            reentry_perigee_altitude_km: 50.0,
+            protect_leo: true,
+            delta_v_budget_m_per_s: None,
+        }
+    }
+}
+
+impl OperationalShell {
+    /// Band spanned by every satellite of a constellation
+    ///
+    /// Returns `None` for an empty constellation.
+    pub fn from_constellation(constellation: &Constellation) -> Option<Self> {
+        constellation.satellites().fold(None, |shell, satellite| {
+            let perigee = satellite.elements.perigee_altitude_km();
+            let apogee = satellite.elements.apogee_altitude_km();
+            Some(match shell {
+                None => Self {
+                    min_perigee_altitude_km: perigee,
+                    max_apogee_altitude_km: apogee,
+                },
+                Some(shell) => Self {
+                    min_perigee_altitude_km: shell.min_perigee_altitude_km.min(perigee),
+                    max_apogee_altitude_km: shell.max_apogee_altitude_km.max(apogee),
+                },
+            })
+        })
+    }
+}
+
+impl DisposalCompliance {
+    pub fn is_compliant(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl DisposalAnalyzer {
+    /// Create analyzer with default rules
+    pub fn new() -> Self {
+        Self::with_rules(DisposalRules::default())
+    }
+
+    /// Create analyzer with custom rules
+    pub fn with_rules(rules: DisposalRules) -> Self {
+        Self { rules }
+    }
+
+    /// Lowest circular graveyard orbit the rules accept for a satellite
+    pub fn minimum_graveyard(
+        &self,
+        satellite: &SatelliteOrbit,
+        shell: &OperationalShell,
+    ) -> Result<OrbitalElements> {
+        let elements = &satellite.elements;
+        OrbitalElements::new(
+            EARTH_RADIUS_KM + shell.max_apogee_altitude_km + self.rules.min_graveyard_clearance_km,
+            0.0,
+            elements.inclination_deg,
+            elements.raan_deg,
+            elements.argument_of_perigee_deg,
+            elements.mean_anomaly_deg,
+        )
+    }
+
+    /// Check one satellite's proposed disposal orbit
+    pub fn check(
+        &self,
+        satellite: &SatelliteOrbit,
+        disposal: &OrbitalElements,
+        shell: &OperationalShell,
+    ) -> DisposalCompliance {
+        let rules = &self.rules;
+        let perigee_altitude_km = disposal.perigee_altitude_km();
+        let apogee_altitude_km = disposal.apogee_altitude_km();
+        let strategy = if perigee_altitude_km <= rules.reentry_perigee_altitude_km {
+            DisposalStrategy::Reentry
+        } else {
+            DisposalStrategy::Graveyard
+        };
+
+        let mut violations = Vec::new();
+        if strategy == DisposalStrategy::Graveyard {
+            let required_perigee_km =
+                shell.max_apogee_altitude_km + rules.min_graveyard_clearance_km;
+            if perigee_altitude_km < required_perigee_km {
+                violations.push(format!(
+                    "graveyard perigee {:.1} km is below the required {:.1} km ({:.1} km above the shell)",
+                    perigee_altitude_km, required_perigee_km, rules.min_graveyard_clearance_km
+                ));
+            }
+            if disposal.eccentricity > rules.max_graveyard_eccentricity {
+                violations.push(format!(
+                    "graveyard eccentricity {:.4} exceeds {:.4}",
+                    disposal.eccentricity, rules.max_graveyard_eccentricity
+                ));
+            }
+            if rules.protect_leo && perigee_altitude_km < LEO_MAX_ALTITUDE_KM {
+                violations.push(format!(
+                    "perigee {:.1} km enters the LEO protected region without reentering",
+                    perigee_altitude_km
+                ));
+            }
+        }
+
+        let delta_v_m_per_s = disposal_delta_v_km_per_s(&satellite.elements, disposal) * KM_TO_M;
+        if let Some(budget) = rules.delta_v_budget_m_per_s {
+            if delta_v_m_per_s > budget {
+                violations.push(format!(
+                    "disposal needs {:.1} m/s, budget is {:.1} m/s",
+                    delta_v_m_per_s, budget
+                ));
+            }
+        }
+
+        DisposalCompliance {
+            satellite_id: satellite.satellite_id.clone(),
+            strategy,
+            disposal_perigee_altitude_km: perigee_altitude_km,
+            disposal_apogee_altitude_km: apogee_altitude_km,
+            delta_v_m_per_s,
+            violations,
+        }
+    }
+
+    /// Check every satellite of a constellation
+    ///
+    /// Satellites without an entry in `plans` are checked against the minimum
+    /// graveyard orbit. Results are ordered by satellite ID.
+    pub fn check_constellation(
+        &self,
+        constellation: &Constellation,
+        plans: &HashMap<String, OrbitalElements>,
+    ) -> Result<Vec<DisposalCompliance>> {
+        let Some(shell) = OperationalShell::from_constellation(constellation) else {
+            return Ok(Vec::new());
+        };
+
+        let mut report = Vec::new();
+        for satellite in constellation.satellites() {
+            let compliance = match plans.get(&satellite.satellite_id) {
+                Some(disposal) => self.check(satellite, disposal, &shell),
+                None => self.check(
+                    satellite,
+                    &self.minimum_graveyard(satellite, &shell)?,
+                    &shell,
+                ),
+            };
+            report.push(compliance);
+        }
+        report.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
+        Ok(report)
+    }
+}
+
+impl Default for DisposalAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Coplanar two-impulse delta-v from the current orbit to a disposal orbit
+///
+/// The current orbit is treated as circular at its semi-major axis. The first
+/// burn at the current radius sets the disposal apsis furthest from it; the
+/// second burn there sets the other apsis. Plane changes are not included.
+fn disposal_delta_v_km_per_s(current: &OrbitalElements, disposal: &OrbitalElements) -> f64 {
+    let r0 = current.semi_major_axis_km;
+    let rp = disposal.semi_major_axis_km * (1.0 - disposal.eccentricity);
+    let ra = disposal.semi_major_axis_km * (1.0 + disposal.eccentricity);
+
+    // Apsis reached by the first burn, and the one set by the second
+    let (far, near) = if (ra - r0).abs() >= (rp - r0).abs() {
+        (ra, rp)
+    } else {
+        (rp, ra)
+    };
+
+    let transfer_a = (r0 + far) / 2.0;
+    let first = (vis_viva(r0, transfer_a) - vis_viva(r0, r0)).abs();
+    let second = if (near - r0).abs() < POSITION_TOLERANCE_KM {
+        0.0
+    } else {
+        (vis_viva(far, disposal.semi_major_axis_km) - vis_viva(far, transfer_a)).abs()
+    };
+    first + second
+}
+
+fn vis_viva(radius_km: f64, semi_major_axis_km: f64) -> f64 {
+    (EARTH_MU * (2.0 / radius_km - 1.0 / semi_major_axis_km)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConstellationType;
+    use chrono::Utc;
+
+    fn meo_satellite() -> SatelliteOrbit {
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        SatelliteOrbit::new(
+            "EOL-01".to_string(),
+            "End of Life".to_string(),
+            elements,
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_graveyard_rules() {
+        let satellite = meo_satellite();
+        let shell = OperationalShell {
+            min_perigee_altitude_km: 8000.0,
+            max_apogee_altitude_km: 8000.0,
+        };
+        let analyzer = DisposalAnalyzer::new();
+
+        // Minimum graveyard: compliant, Hohmann raise of 500 km
+        let graveyard = analyzer.minimum_graveyard(&satellite, &shell).unwrap();
+        let compliance = analyzer.check(&satellite, &graveyard, &shell);
+        assert!(compliance.is_compliant(), "{:?}", compliance.violations);
+        assert_eq!(compliance.strategy, DisposalStrategy::Graveyard);
+        assert!(compliance.delta_v_m_per_s > 80.0 && compliance.delta_v_m_per_s < 95.0);
+
+        // Too close to the shell and too eccentric
+        let low = OrbitalElements::new(14878.0, 0.02, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let compliance = analyzer.check(&satellite, &low, &shell);
+        assert_eq!(compliance.violations.len(), 2);
+
+        // Budget check
+        let budgeted = DisposalAnalyzer::with_rules(DisposalRules {
+            delta_v_budget_m_per_s: Some(50.0),
+            ..DisposalRules::default()
+        });
+        assert!(!budgeted
+            .check(&satellite, &graveyard, &shell)
+            .is_compliant());
+    }
+
+    #[test]
+    fn test_reentry_and_constellation_report() {
+        let satellite = meo_satellite();
+        let shell = OperationalShell {
+            min_perigee_altitude_km: 8000.0,
+            max_apogee_altitude_km: 8000.0,
+        };
+
+        // Perigee lowered into the atmosphere from MEO: compliant reentry
+        let rp = EARTH_RADIUS_KM + 30.0;
+        let reentry = OrbitalElements::new(
+            (14378.0 + rp) / 2.0,
+            (14378.0 - rp) / (14378.0 + rp),
+            55.0,
+            0.0,
+            0.0,
+            0.0,
+        )
+        .unwrap();
+        let compliance = DisposalAnalyzer::new().check(&satellite, &reentry, &shell);
+        assert_eq!(compliance.strategy, DisposalStrategy::Reentry);
+        assert!(compliance.is_compliant());
+        assert!(compliance.delta_v_m_per_s > 1000.0);
+
+        let mut constellation = Constellation::new(
+            "EOL".to_string(),
+            "Disposal test".to_string(),
+            ConstellationType::Custom { satellites: vec![] },
+        );
+        constellation.add_satellite(satellite).unwrap();
+        let report = DisposalAnalyzer::new()
+            .check_constellation(&constellation, &HashMap::new())
+            .unwrap();
+        assert_eq!(report.len(), 1);
+        assert!(report[0].is_compliant());
+        assert!(
+            (report[0].disposal_perigee_altitude_km - (14378.0 - EARTH_RADIUS_KM + 500.0)).abs()
+                < 1e-6
+        );
+    }
+}
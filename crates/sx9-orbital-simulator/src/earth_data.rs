@@ -0,0 +1,385 @@
+//! Earth orientation parameters and leap seconds
+//!
+//! The crate embeds the IERS leap second table and an Earth orientation
+//! parameter (EOP) file, so it works offline. `sx9-orbital update-data`
+//! fetches the latest IERS `Leap_Second.dat` and `finals2000A.all` into a
+//! per-user cache directory.
+//!
+//! The library uses the embedded copy, so results do not depend on what a
+//! machine happens to have cached. A process opts into the cache with
+//! `use_cached_earth_data`, or into any tables with `install_earth_data`,
+//! before the first computation that needs them; the tables then stay fixed
+//! for the life of the process.
+//!
+//! The cache directory is `$SX9_ORBITAL_DATA_DIR` if set, otherwise
+//! `sx9-orbital` under `$XDG_CACHE_HOME`, `~/.cache` or `%LOCALAPPDATA%`.
+//!
+//! Between EOP records values are interpolated linearly; outside the series
+//! the nearest record is held. With no records, UT1 − UTC and polar motion
+//! are zero.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Earth orientation at one day
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EopRecord {
+    /// Modified Julian date (UTC)
+    pub mjd: f64,
+    pub x_pole_arcsec: f64,
+    pub y_pole_arcsec: f64,
+    pub ut1_minus_utc_seconds: f64,
+}
+
+/// Where a table was loaded from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DataSource {
+    Embedded,
+    Cache { path: PathBuf },
+}
+
+/// Leap second and EOP tables in use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarthData {
+    /// (Unix time of the first UTC day with the offset, TAI − UTC seconds)
+    pub leap_seconds: Vec<(i64, i32)>,
+    pub leap_seconds_source: DataSource,
+    /// Records by MJD
+    pub eop: Vec<EopRecord>,
+    pub eop_source: DataSource,
+}
+
+impl EarthData {
+    /// Tables compiled into the crate
+    pub fn embedded() -> Self {
+        Self {
+            leap_seconds: parse_leap_seconds(EMBEDDED_LEAP_SECONDS)
+                .expect("embedded leap second table parses"),
+            leap_seconds_source: DataSource::Embedded,
+            eop: parse_finals(EMBEDDED_FINALS),
+            eop_source: DataSource::Embedded,
+        }
+    }
+
+    /// Tables from `directory`, falling back to the embedded copy for missing files
+    pub fn load<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        let directory = directory.as_ref();
+        let mut data = Self::embedded();
+
+        let path = directory.join(LEAP_SECONDS_FILE);
+        if path.is_file() {
+            let text = fs::read_to_string(&path).for_path(&path)?;
+            data.leap_seconds = parse_leap_seconds(&text).for_path(&path)?;
+            data.leap_seconds_source = DataSource::Cache { path };
+        }
+        let path = directory.join(FINALS_FILE);
+        if path.is_file() {
+            let text = fs::read_to_string(&path).for_path(&path)?;
+            data.eop = parse_finals(&text);
+            data.eop_source = DataSource::Cache { path };
+        }
+        Ok(data)
+    }
+
+    /// TAI − UTC in seconds at `time`
+    ///
+    /// The offset steps at 00:00:00 UTC after each leap second, so 23:59:60
+    /// still has the old value. Epochs before the table use its first offset.
+    pub fn tai_minus_utc_seconds(&self, time: DateTime<Utc>) -> i32 {
+        self.tai_minus_utc_at(time.timestamp())
+    }
+
+    /// UT1 − UTC in seconds at `time`
+    pub fn ut1_minus_utc_seconds(&self, time: DateTime<Utc>) -> f64 {
+        self.eop_at(time)
+            .map_or(0.0, |eop| eop.ut1_minus_utc_seconds)
+    }
+
+    /// Polar motion (x, y) in arcseconds at `time`
+    pub fn polar_motion_arcsec(&self, time: DateTime<Utc>) -> (f64, f64) {
+        self.eop_at(time)
+            .map_or((0.0, 0.0), |eop| (eop.x_pole_arcsec, eop.y_pole_arcsec))
+    }
+
+    fn tai_minus_utc_at(&self, unix_seconds: i64) -> i32 {
+        self.leap_seconds
+            .iter()
+            .rev()
+            .find(|(start, _)| unix_seconds >= *start)
+            .or(self.leap_seconds.first())
+            .map_or(0, |&(_, offset)| offset)
+    }
+
+    /// EOP interpolated to `time`
+    ///
+    /// UT1 − UTC jumps by a second at each leap second, so UT1 − TAI is
+    /// interpolated instead.
+    fn eop_at(&self, time: DateTime<Utc>) -> Option<EopRecord> {
+        let mjd = time.timestamp_millis() as f64 / 86_400_000.0 + UNIX_EPOCH_MJD;
+        let after = self.eop.partition_point(|record| record.mjd <= mjd);
+        let (a, b) = match (after.checked_sub(1), self.eop.get(after)) {
+            (Some(before), Some(b)) => (self.eop[before], *b),
+            (Some(before), None) => return Some(self.eop[before]),
+            (None, b) => return b.copied(),
+        };
+
+        let tai_minus_utc = |mjd: f64| {
+            let unix_seconds = ((mjd - UNIX_EPOCH_MJD) * 86_400.0).floor() as i64;
+            f64::from(self.tai_minus_utc_at(unix_seconds))
+        };
+        let fraction = (mjd - a.mjd) / (b.mjd - a.mjd);
+        let lerp = |x: f64, y: f64| x + (y - x) * fraction;
+        let ut1_minus_tai = lerp(
+            a.ut1_minus_utc_seconds - tai_minus_utc(a.mjd),
+            b.ut1_minus_utc_seconds - tai_minus_utc(b.mjd),
+        );
+        Some(EopRecord {
+            mjd,
+            x_pole_arcsec: lerp(a.x_pole_arcsec, b.x_pole_arcsec),
+            y_pole_arcsec: lerp(a.y_pole_arcsec, b.y_pole_arcsec),
+            ut1_minus_utc_seconds: ut1_minus_tai + f64::from(self.tai_minus_utc_seconds(time)),
+        })
+    }
+}
+
+static DATA: OnceLock<EarthData> = OnceLock::new();
+
+/// Tables in use by this process: the embedded copy unless others were installed
+pub fn earth_data() -> &'static EarthData {
+    DATA.get_or_init(EarthData::embedded)
+}
+
+/// Use `data` for the rest of the process
+///
+/// Fails once any tables are in use.
+pub fn install_earth_data(data: EarthData) -> Result<()> {
+    DATA.set(data).map_err(|_| {
+        OrbitalMechanicsError::TimeError(
+            "Earth data is already in use and cannot be replaced".to_string(),
+        )
+    })
+}
+
+/// Use the cache tables for the rest of the process, where present
+///
+/// A cache that fails to parse is reported and skipped. Returns the tables
+/// in use, which stay as they were if any were already in use.
+pub fn use_cached_earth_data() -> &'static EarthData {
+    DATA.get_or_init(|| match cache_dir() {
+        Some(directory) => EarthData::load(&directory).unwrap_or_else(|err| {
+            tracing::warn!(
+                "Ignoring Earth data cache in {}: {}",
+                directory.display(),
+                err
+            );
+            EarthData::embedded()
+        }),
+        None => EarthData::embedded(),
+    })
+}
+
+/// Per-user directory `update_data` writes to
+pub fn cache_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    if let Some(directory) = var("SX9_ORBITAL_DATA_DIR") {
+        return Some(PathBuf::from(directory));
+    }
+    var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".cache")))
+        .or_else(|| var("LOCALAPPDATA").map(PathBuf::from))
+        .map(|base| base.join("sx9-orbital"))
+}
+
+/// Parse an IERS `Leap_Second.dat` table
+pub fn parse_leap_seconds(text: &str) -> Result<Vec<(i64, i32)>> {
+    let mut table = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let parsed = match fields.as_slice() {
+            [mjd, _, _, _, offset] => mjd.parse::<f64>().ok().zip(offset.parse::<i32>().ok()),
+            _ => None,
+        };
+        let (mjd, offset) = parsed.ok_or_else(|| {
+            OrbitalMechanicsError::TimeError(format!("Malformed leap second line: {}", line))
+        })?;
+        table.push((((mjd - UNIX_EPOCH_MJD) * 86_400.0).round() as i64, offset));
+    }
+
+    if table.is_empty() || table.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+        return Err(OrbitalMechanicsError::TimeError(
+            "Leap second table is empty or out of order".to_string(),
+        ));
+    }
+    Ok(table)
+}
+
+/// Parse IERS `finals2000A` records, skipping rows without UT1 − UTC
+pub fn parse_finals(text: &str) -> Vec<EopRecord> {
+    let mut records: Vec<EopRecord> = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let field = |start: usize, end: usize| line.get(start..end)?.trim().parse::<f64>().ok();
+            Some(EopRecord {
+                mjd: field(7, 15)?,
+                x_pole_arcsec: field(18, 27)?,
+                y_pole_arcsec: field(37, 46)?,
+                ut1_minus_utc_seconds: field(58, 68)?,
+            })
+        })
+        .collect();
+    records.sort_by(|a, b| a.mjd.total_cmp(&b.mjd));
+    records.dedup_by(|a, b| a.mjd == b.mjd);
+    records
+}
+
+/// Summary of an `update_data` run
+#[cfg(feature = "online")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataUpdate {
+    pub directory: PathBuf,
+    pub leap_second_count: usize,
+    pub eop_record_count: usize,
+    /// Last EOP day, including predictions
+    pub eop_last_mjd: Option<f64>,
+}
+
+/// Fetch the latest IERS tables into `directory`
+///
+/// Each download is parsed before it replaces the cached file, so a failed
+/// or truncated fetch leaves the previous cache in place. Running processes
+/// keep the tables they loaded at startup.
+#[cfg(feature = "online")]
+pub async fn update_data<P: AsRef<Path>>(directory: P) -> Result<DataUpdate> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory).for_path(directory)?;
+    let client = reqwest::Client::new();
+    let fetch = |url: &'static str| {
+        let client = client.clone();
+        async move {
+            client
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await
+        }
+    };
+
+    let leap_text = fetch(LEAP_SECONDS_URL).await?;
+    let leap_seconds = parse_leap_seconds(&leap_text)?;
+    let finals_text = fetch(FINALS_URL).await?;
+    let eop = parse_finals(&finals_text);
+    if eop.is_empty() {
+        return Err(OrbitalMechanicsError::TimeError(format!(
+            "No EOP records in {}",
+            FINALS_URL
+        )));
+    }
+
+    for (file, text) in [(LEAP_SECONDS_FILE, leap_text), (FINALS_FILE, finals_text)] {
+        let path = directory.join(file);
+        let partial = path.with_extension("part");
+        fs::write(&partial, text).for_path(&partial)?;
+        fs::rename(&partial, &path).for_path(&path)?;
+    }
+
+    tracing::info!(
+        directory = %directory.display(),
+        leap_seconds = leap_seconds.len(),
+        eop_records = eop.len(),
+        "Earth data updated"
+    );
+    Ok(DataUpdate {
+        directory: directory.to_path_buf(),
+        leap_second_count: leap_seconds.len(),
+        eop_record_count: eop.len(),
+        eop_last_mjd: eop.last().map(|record| record.mjd),
+    })
+}
+
+/// MJD of 1970-01-01
+const UNIX_EPOCH_MJD: f64 = 40_587.0;
+
+const LEAP_SECONDS_FILE: &str = "Leap_Second.dat";
+const FINALS_FILE: &str = "finals2000A.all";
+
+const EMBEDDED_LEAP_SECONDS: &str = include_str!("data/Leap_Second.dat");
+const EMBEDDED_FINALS: &str = include_str!("data/finals2000A.dat");
+
+#[cfg(feature = "online")]
+const LEAP_SECONDS_URL: &str = "https://hpiers.obspm.fr/iers/bul/bulc/Leap_Second.dat";
+#[cfg(feature = "online")]
+const FINALS_URL: &str = "https://datacenter.iers.org/data/9/finals2000A.all";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cache_overrides_embedded_tables() {
+        let embedded = EarthData::embedded();
+        assert_eq!(embedded.leap_seconds.len(), 28);
+        assert_eq!(embedded.leap_seconds[0], (63_072_000, 10));
+        assert!(embedded.eop.is_empty());
+        let time = Utc.with_ymd_and_hms(2017, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(embedded.ut1_minus_utc_seconds(time), 0.0);
+
+        // A later leap second and three EOP days straddling it
+        let dir = tempdir().unwrap();
+        let mut leap = EMBEDDED_LEAP_SECONDS.to_string();
+        leap.push_str("    61041.0    1  1 2026       38\n");
+        fs::write(dir.path().join(LEAP_SECONDS_FILE), leap).unwrap();
+        let row = |mjd: f64, x: f64, y: f64, dut1: f64| {
+            format!(
+                "260101 {:8.2} I {:9.6}{:9.6} {:9.6}{:9.6}  I{:10.7}{:10.7}\n",
+                mjd, x, 0.0, y, 0.0, dut1, 0.0
+            )
+        };
+        let finals = row(61039.0, 0.10, 0.30, -0.45)
+            + &row(61040.0, 0.12, 0.32, -0.47)
+            + &row(61041.0, 0.14, 0.34, 0.51)
+            + "260104 61042.00 P  0.160000 \n";
+        fs::write(dir.path().join(FINALS_FILE), finals).unwrap();
+
+        let data = EarthData::load(dir.path()).unwrap();
+        assert!(matches!(data.leap_seconds_source, DataSource::Cache { .. }));
+        assert_eq!(data.eop.len(), 3);
+        let new_year = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(data.tai_minus_utc_seconds(new_year), 38);
+        assert_eq!(embedded.tai_minus_utc_seconds(new_year), 37);
+
+        let noon = Utc.with_ymd_and_hms(2025, 12, 30, 12, 0, 0).unwrap();
+        let (x, y) = data.polar_motion_arcsec(noon);
+        assert!((x - 0.11).abs() < 1e-9 && (y - 0.31).abs() < 1e-9);
+        assert!((data.ut1_minus_utc_seconds(noon) + 0.46).abs() < 1e-9);
+        // The leap second is not smeared over the last day of the year
+        let evening = Utc.with_ymd_and_hms(2025, 12, 31, 18, 0, 0).unwrap();
+        assert!((data.ut1_minus_utc_seconds(evening) + 0.485).abs() < 1e-9);
+        assert!((data.ut1_minus_utc_seconds(new_year) - 0.51).abs() < 1e-9);
+
+        fs::write(dir.path().join(LEAP_SECONDS_FILE), "41317.0 1 1 1972\n").unwrap();
+        assert!(EarthData::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_library_uses_embedded_tables() {
+        let data = earth_data();
+        assert_eq!(data.leap_seconds_source, DataSource::Embedded);
+        assert_eq!(data.eop_source, DataSource::Embedded);
+        assert!(install_earth_data(EarthData::embedded()).is_err());
+    }
+}
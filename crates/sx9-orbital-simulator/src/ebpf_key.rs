@@ -0,0 +1,92 @@
+//! eBPF map keys for satellite identities
+//!
+//! Kernel-side packet filters key their BPF maps with the 8-byte
+//! `trivariate_to_ebpf_key` layout of the eBPF common library: the SCH
+//! domain and execution masks, big-endian, then CUID slots 10-13 (delta
+//! angle, then entropy). A satellite that stamps its SDT frames as below
+//! can be matched by a filter holding `SatelliteOrbit::to_ebpf_key`,
+//! without a userspace lookup:
+//!
+//! - SCH domain: "space", hashed as `SchHash::from_semantic` hashes domains
+//! - SCH execution: the satellite ID, hashed as a phase text
+//! - CUID delta angle: the SDT 16-bit angle of the frame, zero at the slot
+//! - CUID entropy: the satellite ID, hashed as an N-V-N-N text
+//!
+//! The hashes are the library's Murmur3 x86 32-bit with its seeds, cut to
+//! their low 16 bits.
+
+use crate::orbit::SatelliteOrbit;
+
+impl SatelliteOrbit {
+    /// Map key for frames from this satellite at its nominal state
+    pub fn to_ebpf_key(&self) -> [u8; 8] {
+        self.to_ebpf_key_with_delta_angle(0)
+    }
+
+    /// Map key for frames carrying `delta_angle` on the SDT 16-bit scale
+    ///
+    /// `rune_telemetry::StateDelta::quantized_delta_angle` gives the angle
+    /// for a drifted satellite.
+    pub fn to_ebpf_key_with_delta_angle(&self, delta_angle: u16) -> [u8; 8] {
+        let id = self.satellite_id.as_bytes();
+        let domain = murmur3_16(SPACE_DOMAIN, DOMAIN_SEED).to_be_bytes();
+        let execution = murmur3_16(id, EXECUTION_SEED).to_be_bytes();
+        let delta_angle = delta_angle.to_be_bytes();
+        let entropy = murmur3_16(&id[..id.len().min(NVNN_NOUN_LEN)], NVNN_SEED).to_be_bytes();
+        [
+            domain[0],
+            domain[1],
+            execution[0],
+            execution[1],
+            delta_angle[0],
+            delta_angle[1],
+            entropy[0],
+            entropy[1],
+        ]
+    }
+}
+
+/// Low 16 bits of the Murmur3 x86 32-bit hash, as the SCH masks take them
+fn murmur3_16(data: &[u8], seed: u32) -> u16 {
+    // Reading from a slice cannot fail
+    murmur3::murmur3_32(&mut &data[..], seed).unwrap_or_default() as u16
+}
+
+const SPACE_DOMAIN: &[u8] = b"space";
+
+/// `SchHash::from_semantic` seeds
+const DOMAIN_SEED: u32 = 0xD0AA1A;
+const EXECUTION_SEED: u32 = 0xFA5E5;
+const NVNN_SEED: u32 = 0xABBA;
+
+/// Bytes of each noun `SchHash::from_semantic` hashes
+const NVNN_NOUN_LEN: usize = 16;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_satellite_ebpf_key() {
+        // Reference values of the Murmur3 x86 32-bit hash
+        assert_eq!(murmur3_16(b"", 0), 0);
+        assert_eq!(murmur3_16(b"hello", 0), 0xFA47);
+
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(14378.0, 0.001, 55.0, 30.0, 10.0, 20.0).unwrap();
+        let satellite =
+            |id: &str| SatelliteOrbit::new(id.to_string(), id.to_string(), elements.clone(), epoch);
+        let key = satellite("MEO-1").to_ebpf_key();
+        assert_eq!(key, satellite("MEO-1").to_ebpf_key());
+        assert_ne!(key, satellite("MEO-2").to_ebpf_key());
+        assert_eq!(key[4..6], [0, 0]);
+
+        // Only the delta angle slots change with the angle
+        let drifted = satellite("MEO-1").to_ebpf_key_with_delta_angle(0x1234);
+        assert_eq!(drifted[4..6], [0x12, 0x34]);
+        assert_eq!(drifted[..4], key[..4]);
+        assert_eq!(drifted[6..], key[6..]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Bulk satellite import and export in CSV element format
+//!
+//! Systems engineering keeps constellation designs in spreadsheets, one
+//! satellite per row with a header row:
+//!
+//! | Column         | Content                                                  |
+//! |----------------|----------------------------------------------------------|
+//! | `epoch`        | Element epoch, RFC 3339 (`2024-03-20T00:00:00Z`); a time  |
+//! |                | without offset (`2024-03-20 00:00:00`) is taken as UTC   |
+//! | `a_km`         | Semi-major axis, km                                      |
+//! | `e`            | Eccentricity                                             |
+//! | `i_deg`        | Inclination, degrees                                     |
+//! | `raan_deg`     | Right ascension of the ascending node, degrees           |
+//! | `argp_deg`     | Argument of perigee, degrees                             |
+//! | `m_deg`        | Mean anomaly, degrees                                    |
+//! | `name`         | Satellite name                                           |
+//! | `tags`         | Group tags separated by `;`, may be empty                |
+//! | `satellite_id` | Optional; the name when the column is missing or empty   |
+//!
+//! Columns are matched by header, so they may come in any order and unknown
+//! columns are ignored. Fields holding commas or quotes are double-quoted.
+//! Exports write the columns in the order above and keep full `f64`
+//! precision, so a file reads back to the same elements.
+
+use crate::config::ConstellationType;
+use crate::constellation::Constellation;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::{OrbitalElements, SatelliteOrbit};
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+impl Constellation {
+    /// Read satellites from a CSV element file, naming the constellation after the file
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).for_path(path)?;
+        let mut constellation = Self::parse_csv(&content).for_path(path)?;
+        if let Some(stem) = path.file_stem() {
+            constellation.name = stem.to_string_lossy().into_owned();
+        }
+        Ok(constellation)
+    }
+
+    /// Parse CSV element text into a custom constellation
+    ///
+    /// Fails on the first row with a missing or invalid value, a duplicate
+    /// satellite ID, or elements `OrbitalElements::new` rejects.
+    pub fn parse_csv(content: &str) -> Result<Self> {
+        let mut constellation = Self::new(
+            "CSV import".to_string(),
+            String::new(),
+            ConstellationType::Custom {
+                satellites: Vec::new(),
+            },
+        );
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (_, header) = lines
+            .next()
+            .ok_or_else(|| OrbitalMechanicsError::config_error("Element CSV has no header row"))?;
+        let header: Vec<String> = split_fields(header.trim_start_matches('\u{feff}'));
+        let column = |name: &str| header.iter().position(|h| h == name);
+        let mut columns = [0; ELEMENT_COLUMNS.len()];
+        for (index, name) in columns.iter_mut().zip(ELEMENT_COLUMNS) {
+            *index = column(name).ok_or_else(|| {
+                OrbitalMechanicsError::config_error(format!("Element CSV has no `{}` column", name))
+            })?;
+        }
+        let [epoch, a, e, i, raan, argp, m, name] = columns;
+        let tags = column("tags");
+        let satellite_id = column("satellite_id");
+
+        let mut tagged: Vec<(String, String)> = Vec::new();
+        for (index, line) in lines {
+            let at_line = |message: String| {
+                OrbitalMechanicsError::config_error(format!(
+                    "Element CSV line {}: {}",
+                    index + 1,
+                    message
+                ))
+            };
+            let fields = split_fields(line);
+            let field = |column: usize| fields.get(column).map_or("", String::as_str);
+            let number = |column: usize| {
+                field(column).parse::<f64>().map_err(|_| {
+                    at_line(format!(
+                        "`{}` is not a number: '{}'",
+                        header[column],
+                        field(column)
+                    ))
+                })
+            };
+
+            let elements = OrbitalElements::new(
+                number(a)?,
+                number(e)?,
+                number(i)?,
+                number(raan)?,
+                number(argp)?,
+                number(m)?,
+            )
+            .map_err(|error| at_line(error.to_string()))?;
+            let epoch = parse_epoch(field(epoch)).map_err(at_line)?;
+            let name = field(name);
+            if name.is_empty() {
+                return Err(at_line("`name` is empty".to_string()));
+            }
+            let id = satellite_id
+                .map(field)
+                .filter(|id| !id.is_empty())
+                .unwrap_or(name);
+
+            constellation
+                .add_satellite(SatelliteOrbit::new(
+                    id.to_string(),
+                    name.to_string(),
+                    elements,
+                    epoch,
+                ))
+                .map_err(|error| at_line(error.to_string()))?;
+            if let Some(tags) = tags {
+                tagged.extend(
+                    field(tags)
+                        .split(';')
+                        .map(str::trim)
+                        .filter(|tag| !tag.is_empty())
+                        .map(|tag| (tag.to_string(), id.to_string())),
+                );
+            }
+        }
+
+        for (tag, id) in tagged {
+            constellation.tag(&tag, [id])?;
+        }
+        Ok(constellation)
+    }
+
+    /// Render satellites as CSV elements, ordered by satellite ID
+    pub fn to_csv(&self) -> String {
+        let mut csv = ELEMENT_COLUMNS.join(",");
+        csv.push_str(",tags,satellite_id\n");
+
+        let mut satellites: Vec<&SatelliteOrbit> = self.satellites().collect();
+        satellites.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
+        for satellite in satellites {
+            let elements = &satellite.elements;
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{}",
+                satellite.epoch.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                elements.semi_major_axis_km,
+                elements.eccentricity,
+                elements.inclination_deg,
+                elements.raan_deg,
+                elements.argument_of_perigee_deg,
+                elements.mean_anomaly_deg,
+                quote_field(&satellite.name),
+                quote_field(&self.groups_of(&satellite.satellite_id).join(";")),
+                quote_field(&satellite.satellite_id)
+            );
+        }
+        csv
+    }
+
+    /// Write satellites to a CSV element file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv()).for_path(path)
+    }
+}
+
+fn parse_epoch(text: &str) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(epoch) = DateTime::parse_from_rfc3339(text) {
+        return Ok(epoch.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|epoch| epoch.and_utc())
+        .ok_or_else(|| format!("`epoch` is not a date and time: '{}'", text))
+}
+
+/// Fields of one CSV line, unquoted and trimmed
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+fn quote_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Required columns, in export order
+const ELEMENT_COLUMNS: [&str; 8] = [
+    "epoch", "a_km", "e", "i_deg", "raan_deg", "argp_deg", "m_deg", "name",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_element_csv_round_trip() {
+        let csv = "\u{feff}name,epoch,a_km,e,i_deg,raan_deg,argp_deg,m_deg,tags,notes\n\
+                   MEO-1,2024-03-20T00:00:00Z,14378,0.001,55,0,0,0,plane-1;spares,\n\
+                   \"MEO 2, spare\",2024-03-20 06:00:00,14378.5,0,55,120,0,45.25,plane-2,\"x\"\n\
+                   \n";
+        let constellation = Constellation::parse_csv(csv).unwrap();
+        assert_eq!(constellation.satellite_count(), 2);
+        let spare = constellation.get_satellite("MEO 2, spare").unwrap();
+        assert_eq!(
+            spare.epoch,
+            Utc.with_ymd_and_hms(2024, 3, 20, 6, 0, 0).unwrap()
+        );
+        assert_eq!(spare.elements.mean_anomaly_deg, 45.25);
+        assert_eq!(constellation.groups_of("MEO-1"), ["plane-1", "spares"]);
+
+        let exported = constellation.to_csv();
+        assert_eq!(
+            exported,
+            "epoch,a_km,e,i_deg,raan_deg,argp_deg,m_deg,name,tags,satellite_id\n\
+             2024-03-20T06:00:00Z,14378.5,0,55,120,0,45.25,\"MEO 2, spare\",plane-2,\"MEO 2, spare\"\n\
+             2024-03-20T00:00:00Z,14378,0.001,55,0,0,0,MEO-1,plane-1;spares,MEO-1\n"
+        );
+        let reread = Constellation::parse_csv(&exported).unwrap();
+        assert_eq!(reread.to_csv(), exported);
+
+        // Errors name the line
+        let bad = "epoch,a_km,e,i_deg,raan_deg,argp_deg,m_deg,name\n\
+                   2024-03-20T00:00:00Z,14378,1.5,55,0,0,0,MEO-1\n";
+        let error = Constellation::parse_csv(bad).unwrap_err().to_string();
+        assert!(error.contains("line 2"), "{}", error);
+        assert!(Constellation::parse_csv("epoch,a_km,name\n").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Sun and Moon ephemeris and sidereal time
+//!
+//! Low-precision solar position (Astronomical Almanac, ~0.01° over 1950-2050),
+//! lunar position (~0.3°) and Greenwich mean sidereal time, sufficient for
+//! lighting and eclipse constraints on pass planning and third-body
+//! perturbations.
+
+use crate::constants::*;
+use crate::earth_data::earth_data;
+use chrono::{DateTime, Utc};
+
+/// Astronomical unit in kilometers
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// Julian date of a UTC timestamp
+pub fn julian_date(time: DateTime<Utc>) -> f64 {
+    time.timestamp_millis() as f64 / 1000.0 / DAYS_TO_SECONDS + 2_440_587.5
+}
+
+/// Greenwich mean sidereal time in radians (0-2π)
+///
+/// Evaluated at UT1, with UT1 − UTC from `earth_data`.
+pub fn gmst_rad(time: DateTime<Utc>) -> f64 {
+    let ut1_offset_days = earth_data().ut1_minus_utc_seconds(time) / DAYS_TO_SECONDS;
+    let d = julian_date(time) + ut1_offset_days - J2000_EPOCH_JD;
+    let t = d / JULIAN_CENTURY_DAYS;
+    let gmst_deg =
+        280.460_618_37 + 360.985_647_366_29 * d + 0.000_387_933 * t * t - t * t * t / 38_710_000.0;
+
+    (gmst_deg * DEG_TO_RAD).rem_euclid(TWO_PI)
+}
+
+/// Geocentric Sun position in ECI (mean equator of date) in kilometers
+pub fn sun_position_eci(time: DateTime<Utc>) -> [f64; 3] {
+    let n = julian_date(time) - J2000_EPOCH_JD;
+
+    let mean_longitude = (280.460 + 0.985_647_4 * n) * DEG_TO_RAD;
+    let mean_anomaly = (357.528 + 0.985_600_3 * n) * DEG_TO_RAD;
+    let ecliptic_longitude = mean_longitude
+        + (1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()) * DEG_TO_RAD;
+    let obliquity = (23.439 - 0.000_000_4 * n) * DEG_TO_RAD;
+    let distance_km =
+        AU_KM * (1.000_14 - 0.016_71 * mean_anomaly.cos() - 0.000_14 * (2.0 * mean_anomaly).cos());
+
+    [
+        distance_km * ecliptic_longitude.cos(),
+        distance_km * obliquity.cos() * ecliptic_longitude.sin(),
+        distance_km * obliquity.sin() * ecliptic_longitude.sin(),
+    ]
+}
+
+/// Geocentric Moon position in ECI (mean equator of date) in kilometers
+pub fn moon_position_eci(time: DateTime<Utc>) -> [f64; 3] {
+    let n = julian_date(time) - J2000_EPOCH_JD;
+    let t = n / JULIAN_CENTURY_DAYS;
+    let term =
+        |amplitude: f64, phase: f64, rate: f64| amplitude * ((phase + rate * t) * DEG_TO_RAD).sin();
+    let parallax_term =
+        |amplitude: f64, phase: f64, rate: f64| amplitude * ((phase + rate * t) * DEG_TO_RAD).cos();
+
+    let ecliptic_longitude = (218.32 + 481_267.881 * t + term(6.29, 135.0, 477_198.87)
+        - term(1.27, 259.3, -413_335.36)
+        + term(0.66, 235.7, 890_534.22)
+        + term(0.21, 269.9, 954_397.74)
+        - term(0.19, 357.5, 35_999.05)
+        - term(0.11, 186.5, 966_404.03))
+        * DEG_TO_RAD;
+    let ecliptic_latitude = (term(5.13, 93.3, 483_202.02) + term(0.28, 228.2, 960_400.87)
+        - term(0.28, 318.3, 6_003.18)
+        - term(0.17, 217.6, -407_332.20))
+        * DEG_TO_RAD;
+    let horizontal_parallax = (0.9508
+        + parallax_term(0.0518, 135.0, 477_198.87)
+        + parallax_term(0.0095, 259.3, -413_335.38)
+        + parallax_term(0.0078, 235.7, 890_534.23)
+        + parallax_term(0.0028, 269.9, 954_397.70))
+        * DEG_TO_RAD;
+
+    let distance_km = EARTH_RADIUS_KM / horizontal_parallax.sin();
+    let obliquity = (23.439 - 0.000_000_4 * n) * DEG_TO_RAD;
+    let ecliptic = [
+        ecliptic_latitude.cos() * ecliptic_longitude.cos(),
+        ecliptic_latitude.cos() * ecliptic_longitude.sin(),
+        ecliptic_latitude.sin(),
+    ];
+
+    [
+        distance_km * ecliptic[0],
+        distance_km * (obliquity.cos() * ecliptic[1] - obliquity.sin() * ecliptic[2]),
+        distance_km * (obliquity.sin() * ecliptic[1] + obliquity.cos() * ecliptic[2]),
+    ]
+}
+
+/// Sun elevation above the local horizon of a ground location in degrees
+pub fn sun_elevation_deg(latitude_deg: f64, longitude_deg: f64, time: DateTime<Utc>) -> f64 {
+    let sun = sun_position_eci(time);
+    let sun_norm = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+
+    // Local zenith in ECI: geodetic longitude rotated by sidereal time
+    let lat = latitude_deg * DEG_TO_RAD;
+    let local_sidereal = gmst_rad(time) + longitude_deg * DEG_TO_RAD;
+    let zenith = [
+        lat.cos() * local_sidereal.cos(),
+        lat.cos() * local_sidereal.sin(),
+        lat.sin(),
+    ];
+
+    let cos_zenith_angle =
+        (zenith[0] * sun[0] + zenith[1] * sun[1] + zenith[2] * sun[2]) / sun_norm;
+    cos_zenith_angle.clamp(-1.0, 1.0).asin() * RAD_TO_DEG
+}
+
+/// Whether an ECI position (km) is sunlit, using a cylindrical Earth shadow
+pub fn is_sunlit(position_eci: [f64; 3], time: DateTime<Utc>) -> bool {
+    let sun = sun_position_eci(time);
+    let sun_norm = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+    let sun_dir = [sun[0] / sun_norm, sun[1] / sun_norm, sun[2] / sun_norm];
+
+    let along_sun =
+        position_eci[0] * sun_dir[0] + position_eci[1] * sun_dir[1] + position_eci[2] * sun_dir[2];
+    if along_sun >= 0.0 {
+        return true;
+    }
+
+    let perpendicular = [
+        position_eci[0] - along_sun * sun_dir[0],
+        position_eci[1] - along_sun * sun_dir[1],
+        position_eci[2] - along_sun * sun_dir[2],
+    ];
+    let distance_from_axis = (perpendicular[0] * perpendicular[0]
+        + perpendicular[1] * perpendicular[1]
+        + perpendicular[2] * perpendicular[2])
+        .sqrt();
+
+    distance_from_axis > EARTH_RADIUS_KM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_sun_position_at_solstice() {
+        // June solstice: Sun near +23.44° declination, ~1.016 AU
+        let time = Utc.with_ymd_and_hms(2024, 6, 20, 20, 51, 0).unwrap();
+        let sun = sun_position_eci(time);
+        let r = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+        let declination = (sun[2] / r).asin() * RAD_TO_DEG;
+
+        assert!((declination - 23.44).abs() < 0.05);
+        assert!((r / AU_KM - 1.016).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_moon_position() {
+        // Meeus, Example 47.a: RA 134.69°, dec 13.77°, 368410 km
+        let meeus = Utc.with_ymd_and_hms(1992, 4, 12, 0, 0, 0).unwrap();
+        let moon = moon_position_eci(meeus);
+        let r = (moon[0] * moon[0] + moon[1] * moon[1] + moon[2] * moon[2]).sqrt();
+        let right_ascension = moon[1].atan2(moon[0]) * RAD_TO_DEG;
+        let declination = (moon[2] / r).asin() * RAD_TO_DEG;
+        assert!((right_ascension - 134.69).abs() < 0.5);
+        assert!((declination - 13.77).abs() < 0.5);
+        assert!((r - 368_410.0).abs() < 2_000.0);
+    }
+
+    #[test]
+    fn test_sun_elevation_and_shadow() {
+        // Local noon near Greenwich at the March equinox
+        let noon = Utc.with_ymd_and_hms(2024, 3, 20, 12, 7, 0).unwrap();
+        assert!((sun_elevation_deg(0.0, 0.0, noon) - 90.0).abs() < 1.0);
+        assert!(sun_elevation_deg(0.0, 180.0, noon) < -85.0);
+
+        let sun = sun_position_eci(noon);
+        let r = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+        let towards_sun = [
+            sun[0] / r * 8000.0,
+            sun[1] / r * 8000.0,
+            sun[2] / r * 8000.0,
+        ];
+        let behind_earth = [-towards_sun[0], -towards_sun[1], -towards_sun[2]];
+
+        assert!(is_sunlit(towards_sun, noon));
+        assert!(!is_sunlit(behind_earth, noon));
+    }
+}
@@ -1,16 +1,54 @@
 //! Error types for orbital mechanics calculations
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Result type alias for orbital mechanics operations
 pub type Result<T> = std::result::Result<T, OrbitalMechanicsError>;
 
+/// Coarse error category, stable regardless of how much context is attached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorKind {
+    Config,
+    NotFound,
+    InvalidElements,
+    Propagation,
+    Coordinate,
+    Visibility,
+    FsoAnalysis,
+    Time,
+    Mathematical,
+    Simulation,
+    Io,
+    Serialization,
+    Network,
+    Metrics,
+    Database,
+    Gpu,
+    Cancelled,
+}
+
+/// What was being processed when an error occurred
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorContext {
+    pub satellite_id: Option<String>,
+    pub station_id: Option<String>,
+    pub epoch: Option<DateTime<Utc>>,
+    pub path: Option<PathBuf>,
+}
+
 /// Error types for orbital mechanics operations
 #[derive(Error, Debug)]
 pub enum OrbitalMechanicsError {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    #[error("Invalid configuration field `{path}`: {message}")]
+    ConfigFieldError { path: String, message: String },
+
     #[error("Satellite not found: {0}")]
     SatelliteNotFound(String),
 
@@ -38,6 +76,12 @@ pub enum OrbitalMechanicsError {
     #[error("Mathematical error: {0}")]
     MathematicalError(String),
 
+    #[error("Simulation error: {0}")]
+    SimulationError(String),
+
+    #[error("Satellite simulation not enabled")]
+    SimulationNotEnabled,
+
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -46,6 +90,39 @@ pub enum OrbitalMechanicsError {
 
     #[error("Date/time parsing error: {0}")]
     ChronoError(#[from] chrono::ParseError),
+
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("Metrics error: {0}")]
+    MetricsError(String),
+
+    #[error("Columnar export error: {0}")]
+    ExportError(String),
+
+    #[error("Results database error: {0}")]
+    DatabaseError(String),
+
+    #[error("GPU compute error: {0}")]
+    GpuError(String),
+
+    #[error("Analysis cancelled")]
+    Cancelled,
+
+    /// Underlying error annotated with the satellite/station/epoch/file involved
+    #[error("{source} [{context}]")]
+    WithContext {
+        context: ErrorContext,
+        #[source]
+        source: Box<OrbitalMechanicsError>,
+    },
+}
+
+#[cfg(feature = "metrics")]
+impl From<prometheus::Error> for OrbitalMechanicsError {
+    fn from(err: prometheus::Error) -> Self {
+        Self::MetricsError(err.to_string())
+    }
 }
 
 impl OrbitalMechanicsError {
@@ -68,4 +145,211 @@ impl OrbitalMechanicsError {
     pub fn invalid_elements(msg: impl Into<String>) -> Self {
         Self::InvalidOrbitalElements(msg.into())
     }
+
+    /// Create a simulation error
+    pub fn simulation_error(msg: impl Into<String>) -> Self {
+        Self::SimulationError(msg.into())
+    }
+
+    /// Error category, looking through any attached context
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::ConfigError(_) | Self::ConfigFieldError { .. } => ErrorKind::Config,
+            Self::SatelliteNotFound(_) | Self::GroundStationNotFound(_) => ErrorKind::NotFound,
+            Self::InvalidOrbitalElements(_) => ErrorKind::InvalidElements,
+            Self::PropagationError(_) => ErrorKind::Propagation,
+            Self::CoordinateError(_) => ErrorKind::Coordinate,
+            Self::VisibilityError(_) => ErrorKind::Visibility,
+            Self::FsoAnalysisError(_) => ErrorKind::FsoAnalysis,
+            Self::TimeError(_) | Self::ChronoError(_) => ErrorKind::Time,
+            Self::MathematicalError(_) => ErrorKind::Mathematical,
+            Self::SimulationError(_) | Self::SimulationNotEnabled => ErrorKind::Simulation,
+            Self::IoError(_) => ErrorKind::Io,
+            Self::SerializationError(_) | Self::ExportError(_) => ErrorKind::Serialization,
+            Self::HttpError(_) => ErrorKind::Network,
+            Self::MetricsError(_) => ErrorKind::Metrics,
+            Self::DatabaseError(_) => ErrorKind::Database,
+            Self::GpuError(_) => ErrorKind::Gpu,
+            Self::Cancelled => ErrorKind::Cancelled,
+            Self::WithContext { source, .. } => source.kind(),
+        }
+    }
+
+    /// Attached context, if any
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Innermost error with all context stripped
+    pub fn root_cause(&self) -> &OrbitalMechanicsError {
+        match self {
+            Self::WithContext { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+
+    /// Satellite the error relates to, if known
+    pub fn satellite_id(&self) -> Option<&str> {
+        match self {
+            Self::SatelliteNotFound(id) => Some(id),
+            Self::WithContext { context, source } => context
+                .satellite_id
+                .as_deref()
+                .or_else(|| source.satellite_id()),
+            _ => None,
+        }
+    }
+
+    /// Epoch the error relates to, if known
+    pub fn epoch(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::WithContext { context, source } => context.epoch.or_else(|| source.epoch()),
+            _ => None,
+        }
+    }
+
+    /// Attach context, merging into existing context instead of nesting
+    pub fn with_context(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        match self {
+            Self::WithContext {
+                mut context,
+                source,
+            } => {
+                update(&mut context);
+                Self::WithContext { context, source }
+            }
+            other => {
+                let mut context = ErrorContext::default();
+                update(&mut context);
+                Self::WithContext {
+                    context,
+                    source: Box::new(other),
+                }
+            }
+        }
+    }
+
+    /// Annotate with the satellite being processed
+    pub fn for_satellite(self, satellite_id: impl Into<String>) -> Self {
+        let satellite_id = satellite_id.into();
+        self.with_context(|c| {
+            c.satellite_id.get_or_insert(satellite_id);
+        })
+    }
+
+    /// Annotate with the ground station being processed
+    pub fn for_station(self, station_id: impl Into<String>) -> Self {
+        let station_id = station_id.into();
+        self.with_context(|c| {
+            c.station_id.get_or_insert(station_id);
+        })
+    }
+
+    /// Annotate with the epoch being processed
+    pub fn at_epoch(self, epoch: DateTime<Utc>) -> Self {
+        self.with_context(|c| {
+            c.epoch.get_or_insert(epoch);
+        })
+    }
+
+    /// Annotate with the file being read or written
+    pub fn for_path(self, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        self.with_context(|c| {
+            c.path.get_or_insert(path);
+        })
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(id) = &self.satellite_id {
+            parts.push(format!("satellite {}", id));
+        }
+        if let Some(id) = &self.station_id {
+            parts.push(format!("station {}", id));
+        }
+        if let Some(epoch) = &self.epoch {
+            parts.push(format!("epoch {}", epoch.to_rfc3339()));
+        }
+        if let Some(path) = &self.path {
+            parts.push(format!("file {}", path.display()));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Context helpers on `Result` mirroring the `OrbitalMechanicsError` builders
+pub trait ResultExt<T> {
+    fn for_satellite(self, satellite_id: &str) -> Result<T>;
+    fn for_station(self, station_id: &str) -> Result<T>;
+    fn at_epoch(self, epoch: DateTime<Utc>) -> Result<T>;
+    fn for_path(self, path: impl AsRef<Path>) -> Result<T>;
+}
+
+impl<T, E: Into<OrbitalMechanicsError>> ResultExt<T> for std::result::Result<T, E> {
+    fn for_satellite(self, satellite_id: &str) -> Result<T> {
+        self.map_err(|e| e.into().for_satellite(satellite_id))
+    }
+
+    fn for_station(self, station_id: &str) -> Result<T> {
+        self.map_err(|e| e.into().for_station(station_id))
+    }
+
+    fn at_epoch(self, epoch: DateTime<Utc>) -> Result<T> {
+        self.map_err(|e| e.into().at_epoch(epoch))
+    }
+
+    fn for_path(self, path: impl AsRef<Path>) -> Result<T> {
+        self.map_err(|e| e.into().for_path(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_context_merges_and_preserves_kind() {
+        let epoch = Utc::now();
+        let err = OrbitalMechanicsError::propagation_error("Kepler's equation failed to converge")
+            .for_satellite("SAT-001")
+            .at_epoch(epoch)
+            .for_station("GS-001");
+
+        assert_eq!(err.kind(), ErrorKind::Propagation);
+        assert_eq!(err.satellite_id(), Some("SAT-001"));
+        assert_eq!(err.epoch(), Some(epoch));
+
+        // Context is merged rather than nested
+        let source = err.source().unwrap();
+        assert!(source.source().is_none());
+        assert!(matches!(
+            err.root_cause(),
+            OrbitalMechanicsError::PropagationError(_)
+        ));
+
+        let message = err.to_string();
+        assert!(message.starts_with("Propagation error: Kepler's equation failed to converge"));
+        assert!(message.contains("satellite SAT-001"));
+        assert!(message.contains("station GS-001"));
+    }
+
+    #[test]
+    fn test_result_ext_converts_foreign_errors() {
+        let result: std::result::Result<(), std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+
+        let err = result.for_path("constellation.json").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert_eq!(
+            err.context().and_then(|c| c.path.as_deref()),
+            Some(Path::new("constellation.json"))
+        );
+    }
 }
@@ -0,0 +1,255 @@
+//! Units, precision and timestamp format of exported files
+//!
+//! Every analysis result CSV writer has a `to_csv_with` taking
+//! `ExportOptions`, and writers that save to disk have a matching
+//! `write_csv_with`. The defaults give the output the writers always
+//! produced: kilometres, degrees, ISO 8601 UTC with milliseconds, and each
+//! column's own decimal precision. Interchange files with a fixed layout,
+//! CSV element files (`Constellation::to_csv`) and laser clearing house
+//! requests, don't take options.
+//!
+//! Custom timestamp formats are checked when built or deserialized, so
+//! writing a timestamp never fails.
+//!
+//! Column headers follow the chosen units (`range_km` becomes `range_m`,
+//! `azimuth_deg` becomes `azimuth_rad`), so a file names its own units.
+//!
+//! Satellites are written by ID unless `satellite_names` names them, e.g.
+//! with `Constellation::names_in_scheme("norad")` for catalog names.
+
+use crate::constants::{DEG_TO_RAD, KM_TO_M};
+use crate::error::{OrbitalMechanicsError, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How exported values are written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub distance_unit: DistanceUnit,
+    pub angle_unit: AngleUnit,
+    /// Decimal places of every non-integer value; `None` keeps each
+    /// column's own precision
+    pub decimal_precision: Option<usize>,
+    pub timestamp_format: TimestampFormat,
+    /// Names written in place of satellite IDs; other IDs are written as is
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub satellite_names: BTreeMap<String, String>,
+}
+
+/// Unit of exported lengths, and of the length part of speeds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceUnit {
+    #[default]
+    Kilometers,
+    Meters,
+}
+
+/// Unit of exported angles, and of the angle part of angular rates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AngleUnit {
+    #[default]
+    Degrees,
+    Radians,
+}
+
+/// How exported timestamps are written
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// `2024-03-20T00:00:00.000Z`
+    #[default]
+    Iso8601,
+    /// Seconds since the Unix epoch, to the millisecond
+    UnixSeconds,
+    /// chrono format string, rendered in UTC
+    Custom(StrftimeFormat),
+}
+
+/// chrono format string known to render
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct StrftimeFormat(String);
+
+impl TimestampFormat {
+    /// Custom chrono format, rejected if chrono cannot parse it
+    pub fn custom(format: impl Into<String>) -> Result<Self> {
+        Ok(Self::Custom(StrftimeFormat::new(format)?))
+    }
+}
+
+impl StrftimeFormat {
+    pub fn new(format: impl Into<String>) -> Result<Self> {
+        let format = format.into();
+        if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Invalid timestamp format '{}'",
+                format
+            )));
+        }
+        Ok(Self(format))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for StrftimeFormat {
+    type Error = OrbitalMechanicsError;
+
+    fn try_from(format: String) -> Result<Self> {
+        Self::new(format)
+    }
+}
+
+impl From<StrftimeFormat> for String {
+    fn from(format: StrftimeFormat) -> Self {
+        format.0
+    }
+}
+
+impl DistanceUnit {
+    /// Suffix used in column headers
+    pub fn suffix(self) -> &'static str {
+        match self {
+            DistanceUnit::Kilometers => "km",
+            DistanceUnit::Meters => "m",
+        }
+    }
+
+    pub fn from_km(self, km: f64) -> f64 {
+        match self {
+            DistanceUnit::Kilometers => km,
+            DistanceUnit::Meters => km * KM_TO_M,
+        }
+    }
+}
+
+impl AngleUnit {
+    /// Suffix used in column headers
+    pub fn suffix(self) -> &'static str {
+        match self {
+            AngleUnit::Degrees => "deg",
+            AngleUnit::Radians => "rad",
+        }
+    }
+
+    pub fn from_deg(self, deg: f64) -> f64 {
+        match self {
+            AngleUnit::Degrees => deg,
+            AngleUnit::Radians => deg * DEG_TO_RAD,
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn with_distance_unit(mut self, unit: DistanceUnit) -> Self {
+        self.distance_unit = unit;
+        self
+    }
+
+    pub fn with_angle_unit(mut self, unit: AngleUnit) -> Self {
+        self.angle_unit = unit;
+        self
+    }
+
+    pub fn with_decimal_precision(mut self, places: usize) -> Self {
+        self.decimal_precision = Some(places);
+        self
+    }
+
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    pub fn with_satellite_names(mut self, names: BTreeMap<String, String>) -> Self {
+        self.satellite_names = names;
+        self
+    }
+
+    /// Name written for a satellite ID
+    pub fn satellite_name<'a>(&'a self, satellite_id: &'a str) -> &'a str {
+        self.satellite_names
+            .get(satellite_id)
+            .map_or(satellite_id, String::as_str)
+    }
+
+    /// `value` at the chosen precision, else at `native` places, else as
+    /// `Display` writes it
+    pub fn decimal(&self, value: f64, native: Option<usize>) -> String {
+        match self.decimal_precision.or(native) {
+            Some(places) => format!("{:.*}", places, value),
+            None => value.to_string(),
+        }
+    }
+
+    /// A length or speed given in kilometres, in the chosen unit
+    pub fn distance(&self, km: f64, native: Option<usize>) -> String {
+        self.decimal(self.distance_unit.from_km(km), native)
+    }
+
+    /// An angle or angular rate given in degrees, in the chosen unit
+    pub fn angle(&self, deg: f64, native: Option<usize>) -> String {
+        self.decimal(self.angle_unit.from_deg(deg), native)
+    }
+
+    pub fn timestamp(&self, time: DateTime<Utc>) -> String {
+        match &self.timestamp_format {
+            TimestampFormat::Iso8601 => time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            TimestampFormat::UnixSeconds => {
+                format!("{:.3}", time.timestamp_millis() as f64 / 1000.0)
+            }
+            TimestampFormat::Custom(format) => time.format(format.as_str()).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_export_options() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let default = ExportOptions::default();
+        assert_eq!(default.timestamp(time), "2024-03-20T00:00:00.000Z");
+        assert_eq!(default.distance(7000.0, Some(3)), "7000.000");
+        assert_eq!(default.angle(45.5, None), "45.5");
+
+        let si = ExportOptions::default()
+            .with_distance_unit(DistanceUnit::Meters)
+            .with_angle_unit(AngleUnit::Radians)
+            .with_decimal_precision(2)
+            .with_timestamp_format(TimestampFormat::UnixSeconds);
+        assert_eq!(si.timestamp(time), "1710892800.000");
+        assert_eq!(si.distance(7.25, Some(6)), "7250.00");
+        assert_eq!(si.angle(180.0, None), "3.14");
+        assert_eq!(
+            (si.distance_unit.suffix(), si.angle_unit.suffix()),
+            ("m", "rad")
+        );
+
+        let options: ExportOptions =
+            serde_json::from_str(r#"{"timestamp_format": {"custom": "%Y%j"}}"#).unwrap();
+        assert_eq!(options.timestamp(time), "2024080");
+        assert_eq!(options.distance_unit, DistanceUnit::Kilometers);
+
+        // Formats chrono cannot render are rejected up front
+        assert!(TimestampFormat::custom("%Q").is_err());
+        assert!(TimestampFormat::custom("%Y-%m-%d %").is_err());
+        assert!(
+            serde_json::from_str::<ExportOptions>(r#"{"timestamp_format": {"custom": "%Q"}}"#)
+                .is_err()
+        );
+        let doy =
+            ExportOptions::default().with_timestamp_format(TimestampFormat::custom("%j").unwrap());
+        assert_eq!(doy.timestamp(time), "080");
+    }
+}
@@ -0,0 +1,294 @@
+//! Scripted fault injection
+//!
+//! A `FaultSchedule` lists failures to inject at fixed simulation times: a
+//! satellite dying, an optical terminal losing link margin, or a ground
+//! station going offline for an interval. The same schedule drives the live
+//! `SatelliteSimulator` and, through a scenario's `[[faults]]` section, the
+//! windows handed to the contact scheduler, so resilience tests of handover
+//! and scheduling logic replay identically.
+//!
+//! ```toml
+//! [[faults]]
+//! type = "station_offline"
+//! station_id = "GS-HAWAII"
+//! start_time = "2024-03-20T06:00:00Z"
+//! end_time = "2024-03-20T08:00:00Z"
+//!
+//! [[faults]]
+//! type = "satellite_failure"
+//! satellite_id = "LASERLIGHT-FSO-03"
+//! start_time = "2024-03-20T12:00:00Z"
+//! ```
+
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::visibility::{PassType, UnusableInterval, VisibilityWindow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What fails
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FaultKind {
+    /// The satellite stops operating for good
+    SatelliteFailure { satellite_id: String },
+    /// The satellite's optical terminal loses `loss_db` of link margin
+    TerminalDegradation { satellite_id: String, loss_db: f64 },
+    /// The station cannot take contacts
+    StationOffline { station_id: String },
+}
+
+/// Fault active from `start_time` until `end_time`, or for good
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fault {
+    #[serde(flatten)]
+    pub kind: FaultKind,
+    pub start_time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+/// Faults to inject, in any order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FaultSchedule {
+    pub faults: Vec<Fault>,
+}
+
+impl Fault {
+    pub fn new(kind: FaultKind, start_time: DateTime<Utc>) -> Self {
+        Self {
+            kind,
+            start_time,
+            end_time: None,
+        }
+    }
+
+    /// Clear the fault at `end_time`
+    pub fn with_end_time(mut self, end_time: DateTime<Utc>) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    /// Whether the fault is in effect at `time`
+    pub fn is_active(&self, time: DateTime<Utc>) -> bool {
+        time >= self.start_time && self.end_time.is_none_or(|end| time < end)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(end_time) = self.end_time {
+            if end_time <= self.start_time {
+                return Err(OrbitalMechanicsError::config_error(format!(
+                    "Fault ends at {} before it starts at {}",
+                    end_time, self.start_time
+                )));
+            }
+        }
+        match &self.kind {
+            FaultKind::SatelliteFailure { satellite_id } if self.end_time.is_some() => {
+                Err(OrbitalMechanicsError::config_error(format!(
+                    "Satellite failure of {} cannot end; failed satellites do not recover",
+                    satellite_id
+                )))
+            }
+            FaultKind::TerminalDegradation { loss_db, .. }
+                if !loss_db.is_finite() || *loss_db < 0.0 =>
+            {
+                Err(OrbitalMechanicsError::config_error(format!(
+                    "Terminal degradation must be a non-negative loss, got {} dB",
+                    loss_db
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FaultSchedule {
+    pub fn new(faults: Vec<Fault>) -> Result<Self> {
+        let schedule = Self { faults };
+        schedule.validate()?;
+        Ok(schedule)
+    }
+
+    /// Check every fault, e.g. after deserializing a schedule
+    pub fn validate(&self) -> Result<()> {
+        self.faults.iter().try_for_each(Fault::validate)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faults.is_empty()
+    }
+
+    /// Faults in effect at `time`
+    pub fn active_at(&self, time: DateTime<Utc>) -> impl Iterator<Item = &Fault> {
+        self.faults
+            .iter()
+            .filter(move |fault| fault.is_active(time))
+    }
+
+    /// When the satellite fails, if it does
+    pub fn failure_time(&self, satellite_id: &str) -> Option<DateTime<Utc>> {
+        self.faults
+            .iter()
+            .filter(|fault| {
+                matches!(&fault.kind, FaultKind::SatelliteFailure { satellite_id: id }
+                    if id == satellite_id)
+            })
+            .map(|fault| fault.start_time)
+            .min()
+    }
+
+    /// Whether the satellite has failed by `time`
+    pub fn satellite_failed(&self, satellite_id: &str, time: DateTime<Utc>) -> bool {
+        self.failure_time(satellite_id)
+            .is_some_and(|failure| time >= failure)
+    }
+
+    /// Link margin the satellite's terminal has lost at `time`, in dB
+    pub fn terminal_loss_db(&self, satellite_id: &str, time: DateTime<Utc>) -> f64 {
+        self.active_at(time)
+            .filter_map(|fault| match &fault.kind {
+                FaultKind::TerminalDegradation {
+                    satellite_id: id,
+                    loss_db,
+                } if id == satellite_id => Some(*loss_db),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Whether the station is offline at `time`
+    pub fn station_offline(&self, station_id: &str, time: DateTime<Utc>) -> bool {
+        self.active_at(time).any(|fault| {
+            matches!(&fault.kind, FaultKind::StationOffline { station_id: id } if id == station_id)
+        })
+    }
+
+    /// Windows as the scheduler sees them under these faults
+    ///
+    /// Passes of a failed satellite are cut at the failure and dropped after
+    /// it; cut passes become `Partial`. Station outages are marked as
+    /// unusable intervals. Terminal degradation has no geometric effect and
+    /// only applies to link budgets in the simulator.
+    pub fn apply_to_windows(&self, windows: Vec<VisibilityWindow>) -> Vec<VisibilityWindow> {
+        windows
+            .into_iter()
+            .filter_map(|mut window| {
+                if let Some(failure) = self.failure_time(&window.satellite_id) {
+                    if failure <= window.start_time {
+                        return None;
+                    }
+                    if failure < window.end_time {
+                        window.end_time = failure;
+                        window.duration_seconds =
+                            (failure - window.start_time).num_milliseconds() as f64 / 1000.0;
+                        window.pass_type = PassType::Partial;
+                        window.los_uncertainty_seconds = None;
+                        window.unusable_intervals.retain(|i| i.start_time < failure);
+                        for interval in &mut window.unusable_intervals {
+                            interval.end_time = interval.end_time.min(failure);
+                        }
+                    }
+                }
+
+                for fault in &self.faults {
+                    let FaultKind::StationOffline { station_id } = &fault.kind else {
+                        continue;
+                    };
+                    let end = fault.end_time.unwrap_or(window.end_time);
+                    if station_id != &window.station_id
+                        || fault.start_time >= window.end_time
+                        || end <= window.start_time
+                    {
+                        continue;
+                    }
+                    window.unusable_intervals.push(UnusableInterval {
+                        start_time: fault.start_time.max(window.start_time),
+                        end_time: end.min(window.end_time),
+                        reason: format!("Station {} offline (injected fault)", station_id),
+                    });
+                }
+                window.unusable_intervals.sort_by_key(|i| i.start_time);
+                Some(window)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn window(satellite_id: &str, station_id: &str, start: DateTime<Utc>) -> VisibilityWindow {
+        let end = start + Duration::minutes(20);
+        VisibilityWindow {
+            satellite_id: satellite_id.to_string(),
+            station_id: station_id.to_string(),
+            start_time: start,
+            end_time: end,
+            duration_seconds: 1200.0,
+            max_elevation_time: start + Duration::minutes(10),
+            max_elevation_deg: 60.0,
+            min_range_km: 2000.0,
+            mean_range_km: 2500.0,
+            azimuth_span_deg: 120.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fault_schedule() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let schedule: FaultSchedule = serde_json::from_str(
+            r#"[
+                {"type": "satellite_failure", "satellite_id": "SAT-1",
+                 "start_time": "2024-03-20T01:10:00Z"},
+                {"type": "terminal_degradation", "satellite_id": "SAT-2", "loss_db": 3.0,
+                 "start_time": "2024-03-20T00:00:00Z", "end_time": "2024-03-20T02:00:00Z"},
+                {"type": "station_offline", "station_id": "GS-A",
+                 "start_time": "2024-03-20T03:05:00Z", "end_time": "2024-03-20T03:10:00Z"}
+            ]"#,
+        )
+        .unwrap();
+        schedule.validate().unwrap();
+
+        assert!(!schedule.satellite_failed("SAT-1", t0 + Duration::hours(1)));
+        assert!(schedule.satellite_failed("SAT-1", t0 + Duration::hours(5)));
+        assert_eq!(
+            schedule.terminal_loss_db("SAT-2", t0 + Duration::hours(1)),
+            3.0
+        );
+        assert_eq!(
+            schedule.terminal_loss_db("SAT-2", t0 + Duration::hours(2)),
+            0.0
+        );
+        assert!(schedule.station_offline("GS-A", t0 + Duration::minutes(186)));
+        assert!(!schedule.station_offline("GS-B", t0 + Duration::minutes(186)));
+
+        let windows = schedule.apply_to_windows(vec![
+            window("SAT-1", "GS-B", t0 + Duration::hours(1)),
+            window("SAT-1", "GS-B", t0 + Duration::hours(2)),
+            window("SAT-2", "GS-A", t0 + Duration::hours(3)),
+        ]);
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].end_time, t0 + Duration::minutes(70));
+        assert!(matches!(windows[0].pass_type, PassType::Partial));
+        assert_eq!(windows[1].unusable_intervals.len(), 1);
+        assert_eq!(windows[1].usable_seconds(), 900.0);
+
+        // Failed satellites do not come back
+        let recovering = Fault::new(
+            FaultKind::SatelliteFailure {
+                satellite_id: "SAT-1".to_string(),
+            },
+            t0,
+        )
+        .with_end_time(t0 + Duration::hours(1));
+        assert!(FaultSchedule::new(vec![recovering]).is_err());
+    }
+}
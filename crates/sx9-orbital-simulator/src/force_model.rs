@@ -0,0 +1,326 @@
+//! Force models for numerical propagation
+//!
+//! Each model contributes an ECI acceleration in km/s²; the numerical
+//! propagator sums every model it was built with. Models cover central-body
+//! gravity, zonal harmonics up to J4, atmospheric drag, Sun/Moon third-body
+//! perturbations and solar radiation pressure.
+
+use crate::atmosphere::{self, AtmosphereModel, DragParameters};
+use crate::constants::*;
+use crate::ephemeris::{self, AU_KM};
+use crate::power::SOLAR_CONSTANT_W_PER_M2;
+use chrono::{DateTime, Utc};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+/// Acceleration contribution to the equations of motion
+///
+/// Models are shared with the propagator across simulator tasks, so
+/// implementations must be thread-safe.
+pub trait ForceModel: Send + Sync {
+    /// Acceleration in km/s² for an ECI state at `time`
+    fn acceleration(
+        &self,
+        time: DateTime<Utc>,
+        position_km: [f64; 3],
+        velocity_km_s: [f64; 3],
+    ) -> [f64; 3];
+
+    /// Get force model name
+    fn name(&self) -> &str;
+}
+
+/// Point-mass Earth gravity
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TwoBody;
+
+/// Earth oblateness through zonal harmonics J2..=`max_degree`
+#[derive(Debug, Clone, Copy)]
+pub struct ZonalHarmonics {
+    /// Highest zonal degree included (2-4)
+    pub max_degree: u8,
+}
+
+/// Atmospheric drag
+#[derive(Debug, Clone, Default)]
+pub struct AtmosphericDrag {
+    pub parameters: DragParameters,
+    pub atmosphere: AtmosphereModel,
+}
+
+/// Perturbing body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Body {
+    Sun,
+    Moon,
+}
+
+/// Tidal acceleration of a third body on the Earth-satellite system
+#[derive(Debug, Clone, Copy)]
+pub struct ThirdBody {
+    pub body: Body,
+}
+
+/// Perturbations a configured numerical propagator adds to two-body gravity
+///
+/// Everything is off by default, which leaves point-mass gravity.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForceModelSet {
+    /// Highest zonal harmonic degree, clamped to 2-4
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zonal_degree: Option<u8>,
+    /// Spacecraft drag properties; no drag when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drag: Option<DragParameters>,
+    /// Density model used with `drag`
+    #[serde(default)]
+    pub atmosphere: AtmosphereModel,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub third_bodies: Vec<Body>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solar_radiation_pressure: Option<SolarRadiationPressure>,
+}
+
+/// Cannonball solar radiation pressure with a cylindrical Earth shadow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolarRadiationPressure {
+    pub mass_kg: f64,
+    /// Cross-section facing the Sun
+    pub area_m2: f64,
+    /// Radiation pressure coefficient: 1 for a perfect absorber, 2 for a
+    /// perfect specular reflector
+    pub reflectivity_coefficient: f64,
+}
+
+impl ForceModel for TwoBody {
+    fn acceleration(&self, _time: DateTime<Utc>, position: [f64; 3], _: [f64; 3]) -> [f64; 3] {
+        let position = Vector3::from(position);
+        (position * (-EARTH_MU / position.norm().powi(3))).into()
+    }
+
+    fn name(&self) -> &str {
+        "Two-Body"
+    }
+}
+
+impl ZonalHarmonics {
+    /// Zonal terms up to `max_degree`, clamped to 2-4
+    pub fn new(max_degree: u8) -> Self {
+        Self {
+            max_degree: max_degree.clamp(2, 4),
+        }
+    }
+}
+
+impl Default for ZonalHarmonics {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl ForceModel for ZonalHarmonics {
+    fn acceleration(&self, _time: DateTime<Utc>, position: [f64; 3], _: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = position;
+        let r2 = Vector3::from(position).norm_squared();
+        let r = r2.sqrt();
+        let z2 = z * z / r2;
+
+        // J2
+        let j2 = -1.5 * EARTH_J2 * EARTH_MU * EARTH_RADIUS_KM.powi(2) / r.powi(5);
+        let mut acceleration = [
+            j2 * x * (1.0 - 5.0 * z2),
+            j2 * y * (1.0 - 5.0 * z2),
+            j2 * z * (3.0 - 5.0 * z2),
+        ];
+
+        if self.max_degree >= 3 {
+            let j3 = -2.5 * EARTH_J3 * EARTH_MU * EARTH_RADIUS_KM.powi(3) / r.powi(7);
+            let horizontal = 3.0 * z - 7.0 * z * z2;
+            acceleration[0] += j3 * x * horizontal;
+            acceleration[1] += j3 * y * horizontal;
+            acceleration[2] += j3 * (6.0 * z * z - 7.0 * z * z * z2 - 0.6 * r2);
+        }
+
+        if self.max_degree >= 4 {
+            let j4 = 1.875 * EARTH_J4 * EARTH_MU * EARTH_RADIUS_KM.powi(4) / r.powi(7);
+            let horizontal = 1.0 - 14.0 * z2 + 21.0 * z2 * z2;
+            acceleration[0] += j4 * x * horizontal;
+            acceleration[1] += j4 * y * horizontal;
+            acceleration[2] += j4 * z * (5.0 - 70.0 / 3.0 * z2 + 21.0 * z2 * z2);
+        }
+
+        acceleration
+    }
+
+    fn name(&self) -> &str {
+        match self.max_degree {
+            2 => "J2",
+            3 => "J2-J3",
+            _ => "J2-J4",
+        }
+    }
+}
+
+impl AtmosphericDrag {
+    pub fn new(parameters: DragParameters, atmosphere: AtmosphereModel) -> Self {
+        Self {
+            parameters,
+            atmosphere,
+        }
+    }
+}
+
+impl ForceModel for AtmosphericDrag {
+    fn acceleration(
+        &self,
+        _time: DateTime<Utc>,
+        position: [f64; 3],
+        velocity: [f64; 3],
+    ) -> [f64; 3] {
+        atmosphere::drag_acceleration(position, velocity, &self.parameters, &self.atmosphere)
+    }
+
+    fn name(&self) -> &str {
+        "Atmospheric Drag"
+    }
+}
+
+impl Body {
+    /// Gravitational parameter in km³/s²
+    pub fn mu(&self) -> f64 {
+        match self {
+            Self::Sun => SUN_MU,
+            Self::Moon => MOON_MU,
+        }
+    }
+
+    /// Geocentric ECI position in kilometers
+    pub fn position_eci(&self, time: DateTime<Utc>) -> [f64; 3] {
+        match self {
+            Self::Sun => ephemeris::sun_position_eci(time),
+            Self::Moon => ephemeris::moon_position_eci(time),
+        }
+    }
+}
+
+impl ThirdBody {
+    pub fn sun() -> Self {
+        Self { body: Body::Sun }
+    }
+
+    pub fn moon() -> Self {
+        Self { body: Body::Moon }
+    }
+}
+
+impl ForceModel for ThirdBody {
+    fn acceleration(&self, time: DateTime<Utc>, position: [f64; 3], _: [f64; 3]) -> [f64; 3] {
+        // Direct pull on the satellite minus the pull on the Earth
+        let body = Vector3::from(self.body.position_eci(time));
+        let relative = body - Vector3::from(position);
+        let direct = relative / relative.norm().powi(3);
+        let indirect = body / body.norm().powi(3);
+        ((direct - indirect) * self.body.mu()).into()
+    }
+
+    fn name(&self) -> &str {
+        match self.body {
+            Body::Sun => "Solar Gravity",
+            Body::Moon => "Lunar Gravity",
+        }
+    }
+}
+
+impl SolarRadiationPressure {
+    /// Area-to-mass ratio in m²/kg
+    pub fn area_to_mass_m2_per_kg(&self) -> f64 {
+        self.area_m2 / self.mass_kg
+    }
+}
+
+impl Default for SolarRadiationPressure {
+    fn default() -> Self {
+        Self {
+            mass_kg: 500.0,
+            area_m2: 2.0,
+            reflectivity_coefficient: 1.3,
+        }
+    }
+}
+
+impl ForceModel for SolarRadiationPressure {
+    fn acceleration(&self, time: DateTime<Utc>, position: [f64; 3], _: [f64; 3]) -> [f64; 3] {
+        if !ephemeris::is_sunlit(position, time) {
+            return [0.0; 3];
+        }
+
+        // Pressure at 1 AU scaled by inverse square distance, pushing anti-sunward
+        let from_sun = Vector3::from(position) - Vector3::from(ephemeris::sun_position_eci(time));
+        let distance_km = from_sun.norm();
+        let pressure_n_per_m2 =
+            SOLAR_CONSTANT_W_PER_M2 / SPEED_OF_LIGHT * (AU_KM / distance_km).powi(2);
+        let acceleration_km_s2 = pressure_n_per_m2
+            * self.reflectivity_coefficient
+            * self.area_to_mass_m2_per_kg()
+            * M_TO_KM;
+        (from_sun * (acceleration_km_s2 / distance_km)).into()
+    }
+
+    fn name(&self) -> &str {
+        "Solar Radiation Pressure"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perturbation_magnitudes() {
+        let time = Utc::now();
+        let position = [EARTH_RADIUS_KM + 8000.0, 0.0, 0.0];
+        let velocity = [0.0, 5.26, 0.0];
+
+        let norm = |vector: [f64; 3]| Vector3::from(vector).norm();
+        let central = norm(TwoBody.acceleration(time, position, velocity));
+        assert!((central - EARTH_MU / norm(position).powi(2)).abs() < 1e-12);
+
+        // On the equator J2 pulls inward at 1.5 J2 (Re/r)² of central gravity
+        let zonal = ZonalHarmonics::new(2).acceleration(time, position, velocity);
+        let expected = 1.5 * EARTH_J2 * (EARTH_RADIUS_KM / norm(position)).powi(2) * central;
+        assert!((zonal[0] + expected).abs() < 1e-12);
+        assert!(zonal[2].abs() < 1e-15);
+
+        // J3 and J4 are three orders of magnitude below J2
+        let higher = Vector3::from(ZonalHarmonics::new(4).acceleration(time, position, velocity))
+            - Vector3::from(zonal);
+        assert!(higher.norm() < 1e-2 * norm(zonal));
+        assert!(higher.norm() > 0.0);
+
+        // Tidal accelerations lie between 1x and 2x mu r / d³ depending on
+        // geometry, ~1e-9 km/s² in MEO for both bodies
+        for body in [ThirdBody::sun(), ThirdBody::moon()] {
+            let tidal = norm(body.acceleration(time, position, velocity));
+            let reference =
+                body.body.mu() * norm(position) / norm(body.body.position_eci(time)).powi(3);
+            assert!(tidal > 0.99 * reference && tidal < 2.01 * reference);
+            assert!(tidal > 1e-10 && tidal < 1e-8);
+        }
+
+        // SRP: ~4.6e-6 N/m² on a 2 m², 500 kg cannonball pushes anti-sunward
+        // in sunlight and vanishes in the Earth's shadow
+        let srp = SolarRadiationPressure::default();
+        let sun_dir = Vector3::from(ephemeris::sun_position_eci(time)).normalize();
+        let sunlit = sun_dir * norm(position);
+        let push = Vector3::from(srp.acceleration(time, sunlit.into(), velocity));
+        let expected = 4.56e-6 * 1.3 * (2.0 / 500.0) * M_TO_KM;
+        assert!((push.norm() - expected).abs() < 0.02 * expected);
+        assert!(push.dot(&sun_dir) < -0.999 * push.norm());
+        let eclipsed = sun_dir * -norm(position);
+        assert_eq!(srp.acceleration(time, eclipsed.into(), velocity), [0.0; 3]);
+
+        // No drag in MEO
+        let drag = AtmosphericDrag::default().acceleration(time, position, velocity);
+        assert_eq!(drag, [0.0; 3]);
+    }
+}
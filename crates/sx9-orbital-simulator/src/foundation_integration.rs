@@ -1,6 +1,7 @@
 //! Foundation Daemon Integration v7.3.1
 //! Connects this crate to the CTAS-7 foundation daemon system
 
+use crate::error::Result;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -22,7 +23,7 @@ impl FoundationDaemonClient {
     }
 
     /// Register this crate with foundation daemon
-    pub async fn register(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn register(&self) -> Result<()> {
         // Register with foundation daemon
         let client = reqwest::Client::new();
         let registration = serde_json::json!({
@@ -42,7 +43,7 @@ impl FoundationDaemonClient {
     }
 
     /// Send health ping to foundation daemon
-    pub async fn health_ping(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn health_ping(&self) -> Result<()> {
         let client = reqwest::Client::new();
         let health = serde_json::json!({
             "crate_id": self.crate_id,
@@ -1,13 +1,25 @@
 //! Free Space Optical (FSO) link analysis
 
 use crate::constants::*;
-use crate::ground_station::GroundStation;
-use crate::orbit::SatelliteState;
-use chrono::{DateTime, Utc};
+use crate::coordinates::EarthModel;
+use crate::ephemeris;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::fso_fade::FadeModel;
+use crate::ground_station::{GroundStation, StationScoped};
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use crate::pass_profile;
+use crate::propagator::OrbitalPropagator;
+use crate::trace_targets;
+use crate::units::Seconds;
+use crate::visibility::{UnusableInterval, VisibilityWindow};
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// FSO link quality assessment
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct FsoLinkQuality {
     pub satellite_id: String,
     pub station_id: String,
@@ -19,6 +31,139 @@ pub struct FsoLinkQuality {
     pub link_margin_db: f64,
     pub estimated_throughput_gbps: f64,
     pub weather_impact_factor: f64,
+    /// Synthetic turbulence fade already taken off the margin and throughput
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fade_db: Option<f64>,
+    /// Adaptive-optics gain included in the margin, for AO-equipped stations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_optics_gain_db: Option<f64>,
+    /// Daytime sky background SNR penalty taken off the margin; `None` at night
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sky_background_penalty_db: Option<f64>,
+}
+
+/// Operating wavelength with its atmosphere and detector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpticalBand {
+    pub wavelength_nm: f64,
+    /// Extinction optical depth looking straight up
+    pub zenith_optical_depth: f64,
+    /// Received power the detector needs
+    pub receiver_sensitivity_dbm: f64,
+}
+
+/// Link budgets of one band through a pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandLinkBudget {
+    pub band: OpticalBand,
+    pub samples: Vec<FsoLinkQuality>,
+    pub min_link_margin_db: f64,
+    pub mean_link_margin_db: f64,
+    pub mean_throughput_gbps: f64,
+    /// Whether the pass can be flown in daylight in this band; `None` without
+    /// a sky background or with no daylight sample
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daytime_feasible: Option<bool>,
+}
+
+/// Side-by-side link budgets of one pass at several wavelengths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WavelengthComparison {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// In the analyzer's band order
+    pub bands: Vec<BandLinkBudget>,
+}
+
+/// Optical terminal at one end of a space-ground link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpticalTerminal {
+    /// Ground telescope looking up at the satellite
+    Ground,
+    /// Satellite terminal looking down at the station
+    Space,
+}
+
+/// Thermal keep-out: no lasing while the Sun is too close to a terminal's boresight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermalKeepOut {
+    pub terminal: OpticalTerminal,
+    /// Minimum Sun-boresight separation in degrees
+    pub min_sun_separation_deg: f64,
+}
+
+/// Keep-out constraint violated at one instant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermalViolation {
+    pub terminal: OpticalTerminal,
+    pub sun_separation_deg: f64,
+    pub min_sun_separation_deg: f64,
+}
+
+/// Optical uplink avoidance of the geostationary belt and protected objects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoArcAvoidance {
+    /// Minimum separation between the uplink beam and any protected direction
+    pub min_separation_deg: f64,
+    /// Objects the beam must not illuminate in addition to the GEO belt
+    #[serde(default)]
+    pub protected_objects: Vec<SatelliteOrbit>,
+}
+
+/// Uplink beam too close to the GEO belt or a protected object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoArcViolation {
+    /// Protected object, or `None` for the GEO belt itself
+    pub object_id: Option<String>,
+    pub separation_deg: f64,
+    pub min_separation_deg: f64,
+}
+
+/// Adaptive-optics correction of an optical ground station's uplink
+///
+/// Turbulence follows the Hufnagel-Valley Cn² profile integrated along the
+/// line of sight. A passive telescope of diameter D reaches the long-exposure
+/// Strehl ratio `(1 + (D/r0)^(5/3))^(-6/5)`; with AO the residual wavefront
+/// is the deformable mirror fitting error `0.28 (d/r0)^(5/3)` rad², turned
+/// into a Strehl ratio by the Maréchal approximation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveOptics {
+    /// Transmit telescope aperture diameter
+    pub aperture_m: f64,
+    /// Deformable mirror actuator spacing projected onto the aperture
+    pub actuator_pitch_m: f64,
+    /// Hufnagel-Valley ground-layer Cn² in m^-2/3
+    pub ground_cn2: f64,
+    /// High-altitude RMS wind speed of the Hufnagel-Valley profile
+    pub rms_wind_m_per_s: f64,
+}
+
+/// Daytime sky background seen by the ground receiver
+///
+/// Clear-sky radiance scales with the sine of the Sun elevation, brightens
+/// toward the horizon with the square root of the airmass and rises steeply
+/// in the solar aureole as `1 + (aureole / separation)²`. Across bands it
+/// scales as λ⁻⁴ from the 1550 nm reference, so 1064 nm sees about 4.5 times
+/// the background: brighter sunlight and stronger scattering. The background
+/// collected through the filter and field of view adds to the receiver
+/// noise, and the SNR penalty is the resulting noise increase. Twilight is
+/// treated as night.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkyBackground {
+    /// Spectral radiance at zenith with the Sun overhead at 1550 nm, in W/(m² sr µm)
+    pub zenith_radiance: f64,
+    /// Half-width of the solar aureole
+    pub aureole_deg: f64,
+    /// Receiver optical filter bandwidth
+    pub filter_bandwidth_nm: f64,
+    /// Full receiver field of view
+    pub field_of_view_urad: f64,
+    /// Receiver noise-equivalent power without background
+    pub noise_equivalent_power_w: f64,
+    /// Largest penalty at which daytime operation is still feasible
+    pub max_penalty_db: f64,
 }
 
 /// FSO link analyzer
@@ -26,6 +171,287 @@ pub struct FsoAnalyzer {
     pub wavelength_nm: f64,
     pub transmit_power_w: f64,
     pub receiver_aperture_m: f64,
+    pub thermal_keep_outs: Vec<ThermalKeepOut>,
+    pub geo_arc_avoidance: Option<GeoArcAvoidance>,
+    /// Correlated fades applied by `analyze_pass`
+    pub fade_model: Option<FadeModel>,
+    /// AO correction by station ID; other stations are passive
+    pub adaptive_optics: BTreeMap<String, AdaptiveOptics>,
+    /// Daytime background at the ground receiver; ignored when `None`
+    pub sky_background: Option<SkyBackground>,
+    /// Wavelengths compared by `compare_wavelengths`
+    pub bands: Vec<OpticalBand>,
+    /// Shape the stations sit on
+    pub earth_model: EarthModel,
+}
+
+impl OpticalBand {
+    /// 1550 nm with an InGaAs receiver
+    pub fn nm_1550() -> Self {
+        Self {
+            wavelength_nm: FSO_WAVELENGTH_1550NM * 1e9,
+            zenith_optical_depth: 0.1,
+            receiver_sensitivity_dbm: RECEIVER_SENSITIVITY_DBM,
+        }
+    }
+
+    /// 1064 nm with a silicon APD: more aerosol extinction, better detector
+    pub fn nm_1064() -> Self {
+        Self {
+            wavelength_nm: FSO_WAVELENGTH_1064NM * 1e9,
+            zenith_optical_depth: 0.2,
+            receiver_sensitivity_dbm: -45.0,
+        }
+    }
+
+    /// Atmospheric transmission at `elevation_deg` with a plane-parallel airmass
+    pub fn atmospheric_transmission(&self, elevation_deg: f64) -> f64 {
+        let zenith_angle = 90.0 - elevation_deg;
+        let airmass = 1.0 / (zenith_angle.to_radians().cos());
+        (-self.zenith_optical_depth * airmass).exp()
+    }
+}
+
+impl BandLinkBudget {
+    fn new(
+        band: OpticalBand,
+        samples: Vec<FsoLinkQuality>,
+        sky_background: Option<&SkyBackground>,
+    ) -> Self {
+        let count = samples.len().max(1) as f64;
+        Self {
+            daytime_feasible: sky_background.and_then(|sky| sky.daytime_feasible(&samples)),
+            min_link_margin_db: samples
+                .iter()
+                .map(|s| s.link_margin_db)
+                .fold(f64::INFINITY, f64::min),
+            mean_link_margin_db: samples.iter().map(|s| s.link_margin_db).sum::<f64>() / count,
+            mean_throughput_gbps: samples
+                .iter()
+                .map(|s| s.estimated_throughput_gbps)
+                .sum::<f64>()
+                / count,
+            band,
+            samples,
+        }
+    }
+}
+
+impl WavelengthComparison {
+    /// Band with the highest mean throughput
+    pub fn best_band(&self) -> Option<&BandLinkBudget> {
+        self.bands
+            .iter()
+            .max_by(|a, b| a.mean_throughput_gbps.total_cmp(&b.mean_throughput_gbps))
+    }
+}
+
+impl ThermalKeepOut {
+    /// Keep the Sun at least `min_sun_separation_deg` from the ground telescope boresight
+    pub fn ground(min_sun_separation_deg: f64) -> Self {
+        Self {
+            terminal: OpticalTerminal::Ground,
+            min_sun_separation_deg,
+        }
+    }
+
+    /// Keep the Sun at least `min_sun_separation_deg` from the satellite terminal boresight
+    pub fn space(min_sun_separation_deg: f64) -> Self {
+        Self {
+            terminal: OpticalTerminal::Space,
+            min_sun_separation_deg,
+        }
+    }
+}
+
+impl ThermalViolation {
+    /// Human-readable reason for marking the link unusable
+    pub fn reason(&self) -> String {
+        let terminal = match self.terminal {
+            OpticalTerminal::Ground => "ground",
+            OpticalTerminal::Space => "space",
+        };
+        format!(
+            "Thermal keep-out: Sun {:.1}° from {} terminal boresight (minimum {:.1}°)",
+            self.sun_separation_deg, terminal, self.min_sun_separation_deg
+        )
+    }
+}
+
+impl GeoArcAvoidance {
+    /// Keep the uplink beam at least `min_separation_deg` from the GEO belt
+    pub fn new(min_separation_deg: f64) -> Self {
+        Self {
+            min_separation_deg,
+            protected_objects: Vec::new(),
+        }
+    }
+
+    /// Also keep the beam away from a catalogued object
+    pub fn with_protected_object(mut self, object: SatelliteOrbit) -> Self {
+        self.protected_objects.push(object);
+        self
+    }
+}
+
+impl AdaptiveOptics {
+    /// AO station under the Hufnagel-Valley 5/7 reference atmosphere
+    pub fn new(aperture_m: f64, actuator_pitch_m: f64) -> Self {
+        Self {
+            aperture_m,
+            actuator_pitch_m,
+            ground_cn2: HV57_GROUND_CN2,
+            rms_wind_m_per_s: HV57_RMS_WIND_M_PER_S,
+        }
+    }
+
+    /// Use site turbulence instead of the HV 5/7 profile
+    pub fn with_turbulence(mut self, ground_cn2: f64, rms_wind_m_per_s: f64) -> Self {
+        self.ground_cn2 = ground_cn2;
+        self.rms_wind_m_per_s = rms_wind_m_per_s;
+        self
+    }
+
+    /// Cn² integrated from the ground to space at zenith, in m^1/3
+    pub fn integrated_cn2(&self) -> f64 {
+        // Closed-form integrals of the three Hufnagel-Valley terms
+        let wind = self.rms_wind_m_per_s / 27.0;
+        let high_altitude = 0.00594 * wind * wind * 1e-50 * 3_628_800.0 * 1000f64.powi(11);
+        let tropopause = 2.7e-16 * 1500.0;
+        let ground = self.ground_cn2 * 100.0;
+        high_altitude + tropopause + ground
+    }
+
+    /// Fried parameter along the line of sight at `elevation_deg`
+    pub fn fried_parameter_m(&self, wavelength_nm: f64, elevation_deg: f64) -> f64 {
+        let k = 2.0 * std::f64::consts::PI / (wavelength_nm * 1e-9);
+        let airmass = 1.0 / (elevation_deg * DEG_TO_RAD).sin();
+        (0.423 * k * k * airmass * self.integrated_cn2()).powf(-0.6)
+    }
+
+    /// Long-exposure Strehl ratio of the same telescope without correction
+    pub fn passive_strehl_ratio(&self, wavelength_nm: f64, elevation_deg: f64) -> f64 {
+        let r0 = self.fried_parameter_m(wavelength_nm, elevation_deg);
+        (1.0 + (self.aperture_m / r0).powf(5.0 / 3.0)).powf(-1.2)
+    }
+
+    /// Strehl ratio with the AO loop closed
+    ///
+    /// Never below the passive value: when the fitting error swamps the
+    /// Maréchal approximation, the loop is taken to add nothing.
+    pub fn strehl_ratio(&self, wavelength_nm: f64, elevation_deg: f64) -> f64 {
+        let r0 = self.fried_parameter_m(wavelength_nm, elevation_deg);
+        let fitting_error = 0.28 * (self.actuator_pitch_m / r0).powf(5.0 / 3.0);
+        (-fitting_error)
+            .exp()
+            .max(self.passive_strehl_ratio(wavelength_nm, elevation_deg))
+    }
+
+    /// Link budget improvement over a passive station in dB
+    pub fn gain_db(&self, wavelength_nm: f64, elevation_deg: f64) -> f64 {
+        10.0 * (self.strehl_ratio(wavelength_nm, elevation_deg)
+            / self.passive_strehl_ratio(wavelength_nm, elevation_deg))
+        .log10()
+    }
+}
+
+impl SkyBackground {
+    /// Clear sky at 1550 nm behind the given filter and field of view
+    pub fn new(filter_bandwidth_nm: f64, field_of_view_urad: f64) -> Self {
+        Self {
+            zenith_radiance: CLEAR_SKY_RADIANCE_1550NM,
+            aureole_deg: SOLAR_AUREOLE_DEG,
+            filter_bandwidth_nm,
+            field_of_view_urad,
+            noise_equivalent_power_w: RECEIVER_NOISE_EQUIVALENT_POWER_W,
+            max_penalty_db: 3.0,
+        }
+    }
+
+    /// Largest SNR penalty tolerated in daytime
+    pub fn with_max_penalty_db(mut self, max_penalty_db: f64) -> Self {
+        self.max_penalty_db = max_penalty_db;
+        self
+    }
+
+    /// Sky radiance in W/(m² sr µm) at `wavelength_nm` toward `elevation_deg`,
+    /// `sun_separation_deg` from the Sun
+    pub fn radiance(
+        &self,
+        wavelength_nm: f64,
+        elevation_deg: f64,
+        sun_elevation_deg: f64,
+        sun_separation_deg: f64,
+    ) -> f64 {
+        if sun_elevation_deg <= 0.0 {
+            return 0.0;
+        }
+        let airmass = 1.0 / (elevation_deg * DEG_TO_RAD).sin();
+        // Inside the solar disc the aureole stops growing
+        let separation = sun_separation_deg.max(SUN_ANGULAR_RADIUS_DEG);
+        self.zenith_radiance
+            * (FSO_WAVELENGTH_1550NM * 1e9 / wavelength_nm).powi(4)
+            * (sun_elevation_deg * DEG_TO_RAD).sin()
+            * airmass.sqrt()
+            * (1.0 + (self.aureole_deg / separation).powi(2))
+    }
+
+    /// Background power collected by a receiver of diameter `aperture_m`
+    pub fn background_power_w(&self, radiance: f64, aperture_m: f64) -> f64 {
+        let area = std::f64::consts::PI * aperture_m * aperture_m / 4.0;
+        let half_angle = self.field_of_view_urad * 1e-6 / 2.0;
+        let solid_angle = std::f64::consts::PI * half_angle * half_angle;
+        radiance * self.filter_bandwidth_nm * 1e-3 * area * solid_angle
+    }
+
+    /// SNR penalty in dB from the background noise, or `None` with the Sun down
+    pub fn snr_penalty_db(
+        &self,
+        wavelength_nm: f64,
+        elevation_deg: f64,
+        sun_elevation_deg: f64,
+        sun_separation_deg: f64,
+        aperture_m: f64,
+    ) -> Option<f64> {
+        if sun_elevation_deg <= 0.0 {
+            return None;
+        }
+        let radiance = self.radiance(
+            wavelength_nm,
+            elevation_deg,
+            sun_elevation_deg,
+            sun_separation_deg,
+        );
+        let background = self.background_power_w(radiance, aperture_m);
+        Some(10.0 * (1.0 + background / self.noise_equivalent_power_w).log10())
+    }
+
+    /// Whether a sampled pass can be flown in daylight
+    ///
+    /// `None` when no sample is in daylight; otherwise every daylight sample
+    /// must stay within `max_penalty_db`.
+    pub fn daytime_feasible(&self, samples: &[FsoLinkQuality]) -> Option<bool> {
+        let mut penalties = samples
+            .iter()
+            .filter_map(|s| s.sky_background_penalty_db)
+            .peekable();
+        penalties.peek()?;
+        Some(penalties.all(|penalty| penalty <= self.max_penalty_db))
+    }
+}
+
+impl GeoArcViolation {
+    /// Human-readable reason for marking the link unusable
+    pub fn reason(&self) -> String {
+        let target = match &self.object_id {
+            Some(object_id) => format!("protected object {}", object_id),
+            None => "GEO belt".to_string(),
+        };
+        format!(
+            "GEO arc avoidance: uplink beam {:.2}° from {} (minimum {:.2}°)",
+            self.separation_deg, target, self.min_separation_deg
+        )
+    }
 }
 
 impl FsoAnalyzer {
@@ -35,7 +461,290 @@ impl FsoAnalyzer {
             wavelength_nm: FSO_WAVELENGTH_1550NM * 1e9,
             transmit_power_w: defaults::FSO_TRANSMIT_POWER_W,
             receiver_aperture_m: defaults::FSO_RECEIVER_APERTURE_M,
+            thermal_keep_outs: Vec::new(),
+            geo_arc_avoidance: None,
+            fade_model: None,
+            adaptive_optics: BTreeMap::new(),
+            sky_background: None,
+            bands: Vec::new(),
+            earth_model: EarthModel::Sphere,
+        }
+    }
+
+    /// Add a wavelength to compare
+    pub fn with_band(mut self, band: OpticalBand) -> Self {
+        self.bands.push(band);
+        self
+    }
+
+    /// The configured wavelength with the reference atmosphere and detector
+    pub fn primary_band(&self) -> OpticalBand {
+        OpticalBand {
+            wavelength_nm: self.wavelength_nm,
+            ..OpticalBand::nm_1550()
+        }
+    }
+
+    /// Place stations on `earth_model` (the spherical model by default)
+    pub fn with_earth_model(mut self, earth_model: EarthModel) -> Self {
+        self.earth_model = earth_model;
+        self
+    }
+
+    /// Penalize daytime links for the sky background at the ground receiver
+    pub fn with_sky_background(mut self, sky_background: SkyBackground) -> Self {
+        self.sky_background = Some(sky_background);
+        self
+    }
+
+    /// Equip a station with adaptive optics
+    pub fn with_adaptive_optics(
+        mut self,
+        station_id: &str,
+        adaptive_optics: AdaptiveOptics,
+    ) -> Self {
+        self.adaptive_optics
+            .insert(station_id.to_string(), adaptive_optics);
+        self
+    }
+
+    /// Apply synthetic turbulence fades to sampled passes
+    pub fn with_fade_model(mut self, fade_model: FadeModel) -> Self {
+        self.fade_model = Some(fade_model);
+        self
+    }
+
+    /// Add a thermal keep-out constraint
+    pub fn with_thermal_keep_out(mut self, keep_out: ThermalKeepOut) -> Self {
+        self.thermal_keep_outs.push(keep_out);
+        self
+    }
+
+    /// Replace all thermal keep-out constraints
+    pub fn with_thermal_keep_outs(mut self, keep_outs: Vec<ThermalKeepOut>) -> Self {
+        self.thermal_keep_outs = keep_outs;
+        self
+    }
+
+    /// Mask uplinks that would illuminate the GEO belt or protected objects
+    pub fn with_geo_arc_avoidance(mut self, avoidance: GeoArcAvoidance) -> Self {
+        self.geo_arc_avoidance = Some(avoidance);
+        self
+    }
+
+    /// Angle between the Sun and a terminal's boresight in degrees
+    pub fn sun_separation_deg(
+        &self,
+        terminal: OpticalTerminal,
+        satellite_state: &SatelliteState,
+        station: &GroundStation,
+    ) -> f64 {
+        let station_position =
+            station_position(&self.earth_model, station, satellite_state.timestamp);
+        let sun = Vector3::from(ephemeris::sun_position_eci(satellite_state.timestamp));
+        let satellite = Vector3::from(satellite_state.position_eci);
+
+        let (origin, target) = match terminal {
+            OpticalTerminal::Ground => (station_position, satellite),
+            OpticalTerminal::Space => (satellite, station_position),
+        };
+        (target - origin).angle(&(sun - origin)) * RAD_TO_DEG
+    }
+
+    /// Smallest angle between the station-to-satellite uplink beam and the GEO belt in degrees
+    pub fn geo_arc_separation_deg(
+        &self,
+        satellite_state: &SatelliteState,
+        station: &GroundStation,
+    ) -> f64 {
+        let origin = station_position(&self.earth_model, station, satellite_state.timestamp);
+        let beam = Vector3::from(satellite_state.position_eci) - origin;
+        let radius = EARTH_RADIUS_KM + GEO_ALTITUDE_KM;
+        let separation = |longitude: f64| {
+            let belt = Vector3::new(radius * longitude.cos(), radius * longitude.sin(), 0.0);
+            beam.angle(&(belt - origin)) * RAD_TO_DEG
+        };
+
+        // Coarse scan around the belt, then golden-section search near the best sample
+        let mut low = (0..360)
+            .map(|step| step as f64 * GEO_BELT_SCAN_STEP_DEG * DEG_TO_RAD)
+            .min_by(|a, b| separation(*a).total_cmp(&separation(*b)))
+            .unwrap_or(0.0)
+            - GEO_BELT_SCAN_STEP_DEG * DEG_TO_RAD;
+        let mut high = low + 2.0 * GEO_BELT_SCAN_STEP_DEG * DEG_TO_RAD;
+        let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+        for _ in 0..GEO_BELT_REFINE_ITERATIONS {
+            let lower_probe = high - ratio * (high - low);
+            let upper_probe = low + ratio * (high - low);
+            if separation(lower_probe) < separation(upper_probe) {
+                high = upper_probe;
+            } else {
+                low = lower_probe;
+            }
+        }
+        separation((low + high) / 2.0)
+    }
+
+    /// Tightest GEO arc violation of the uplink beam, if any
+    ///
+    /// Protected objects are propagated to the state epoch; an object with the
+    /// same ID as the link satellite is ignored.
+    pub fn geo_arc_violation(
+        &self,
+        satellite_state: &SatelliteState,
+        station: &GroundStation,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Option<GeoArcViolation>> {
+        let Some(avoidance) = &self.geo_arc_avoidance else {
+            return Ok(None);
+        };
+
+        let mut worst = GeoArcViolation {
+            object_id: None,
+            separation_deg: self.geo_arc_separation_deg(satellite_state, station),
+            min_separation_deg: avoidance.min_separation_deg,
+        };
+
+        let origin = station_position(&self.earth_model, station, satellite_state.timestamp);
+        let beam = Vector3::from(satellite_state.position_eci) - origin;
+        for object in &avoidance.protected_objects {
+            if object.satellite_id == satellite_state.satellite_id {
+                continue;
+            }
+            let time = satellite_state.timestamp;
+            let object_state = propagator
+                .propagate(object, time)
+                .for_satellite(&object.satellite_id)
+                .at_epoch(time)?;
+            let separation_deg =
+                beam.angle(&(Vector3::from(object_state.position_eci) - origin)) * RAD_TO_DEG;
+            if separation_deg < worst.separation_deg {
+                worst = GeoArcViolation {
+                    object_id: Some(object.satellite_id.clone()),
+                    separation_deg,
+                    min_separation_deg: avoidance.min_separation_deg,
+                };
+            }
         }
+
+        Ok((worst.separation_deg < worst.min_separation_deg).then_some(worst))
+    }
+
+    /// Tightest violated thermal keep-out for the link geometry, if any
+    pub fn thermal_violation(
+        &self,
+        satellite_state: &SatelliteState,
+        station: &GroundStation,
+    ) -> Option<ThermalViolation> {
+        self.thermal_keep_outs
+            .iter()
+            .filter_map(|keep_out| {
+                let sun_separation_deg =
+                    self.sun_separation_deg(keep_out.terminal, satellite_state, station);
+                (sun_separation_deg < keep_out.min_sun_separation_deg).then_some(ThermalViolation {
+                    terminal: keep_out.terminal,
+                    sun_separation_deg,
+                    min_sun_separation_deg: keep_out.min_sun_separation_deg,
+                })
+            })
+            .min_by(|a, b| {
+                (a.sun_separation_deg - a.min_sun_separation_deg)
+                    .total_cmp(&(b.sun_separation_deg - b.min_sun_separation_deg))
+            })
+    }
+
+    /// Mark the parts of a visibility window where keep-outs or GEO arc avoidance forbid lasing
+    ///
+    /// Samples the window every `step_seconds` and replaces
+    /// `window.unusable_intervals`; each interval spans consecutive violating
+    /// samples and records the tightest violation.
+    pub fn annotate_window(
+        &self,
+        window: &mut VisibilityWindow,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        propagator: &dyn OrbitalPropagator,
+        step_seconds: f64,
+    ) -> Result<()> {
+        window.unusable_intervals.clear();
+        if self.thermal_keep_outs.is_empty() && self.geo_arc_avoidance.is_none() {
+            return Ok(());
+        }
+        if !step_seconds.is_finite() || step_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Keep-out sampling step must be positive, got {} s",
+                step_seconds
+            )));
+        }
+
+        let step = Duration::milliseconds((step_seconds * 1000.0).round().max(1.0) as i64);
+        // Open interval with the margin of its tightest violation
+        let mut current: Option<(UnusableInterval, f64)> = None;
+        let mut time = window.start_time;
+
+        loop {
+            let state = propagator
+                .propagate(satellite, time)
+                .for_satellite(&satellite.satellite_id)
+                .for_station(&station.station_id)
+                .at_epoch(time)?;
+
+            match (
+                self.constraint_violation(&state, station, propagator)?,
+                current.as_mut(),
+            ) {
+                (Some((margin, reason)), Some((interval, worst))) => {
+                    interval.end_time = time;
+                    if margin < *worst {
+                        interval.reason = reason;
+                        *worst = margin;
+                    }
+                }
+                (Some((margin, reason)), None) => {
+                    let interval = UnusableInterval {
+                        start_time: time,
+                        end_time: time,
+                        reason,
+                    };
+                    current = Some((interval, margin));
+                }
+                (None, _) => {
+                    if let Some((interval, _)) = current.take() {
+                        window.unusable_intervals.push(interval);
+                    }
+                }
+            }
+
+            if time >= window.end_time {
+                break;
+            }
+            time = (time + step).min(window.end_time);
+        }
+
+        if let Some((interval, _)) = current {
+            window.unusable_intervals.push(interval);
+        }
+
+        Ok(())
+    }
+
+    /// Tightest thermal or GEO arc violation as (separation minus minimum, reason)
+    fn constraint_violation(
+        &self,
+        satellite_state: &SatelliteState,
+        station: &GroundStation,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Option<(f64, String)>> {
+        let thermal = self
+            .thermal_violation(satellite_state, station)
+            .map(|v| (v.sun_separation_deg - v.min_sun_separation_deg, v.reason()));
+        let geo_arc = self
+            .geo_arc_violation(satellite_state, station, propagator)?
+            .map(|v| (v.separation_deg - v.min_separation_deg, v.reason()));
+        Ok(thermal
+            .into_iter()
+            .chain(geo_arc)
+            .min_by(|a, b| a.0.total_cmp(&b.0)))
     }
 
     /// Analyze FSO link quality
@@ -45,7 +754,18 @@ impl FsoAnalyzer {
         station: &GroundStation,
         time: DateTime<Utc>,
     ) -> Option<FsoLinkQuality> {
-        let look_angles = satellite_state.look_angles_from_station(
+        self.analyze_link_in_band(satellite_state, station, time, &self.primary_band())
+    }
+
+    fn analyze_link_in_band(
+        &self,
+        satellite_state: &SatelliteState,
+        station: &GroundStation,
+        time: DateTime<Utc>,
+        band: &OpticalBand,
+    ) -> Option<FsoLinkQuality> {
+        let look_angles = satellite_state.look_angles_from_station_with_model(
+            &self.earth_model,
             station.position.latitude_deg,
             station.position.longitude_deg,
             station.position.elevation_m,
@@ -55,24 +775,55 @@ impl FsoAnalyzer {
             return None;
         }
 
-        // Simplified atmospheric transmission
-        let zenith_angle = 90.0 - look_angles.elevation_deg;
-        let airmass = 1.0 / (zenith_angle.to_radians().cos());
-        let atmospheric_transmission = (-0.1 * airmass).exp();
+        if let Some(violation) = self.thermal_violation(satellite_state, station) {
+            tracing::trace!(
+                target: trace_targets::FSO,
+                satellite_id = %satellite_state.satellite_id,
+                station_id = %station.station_id,
+                sun_separation_deg = violation.sun_separation_deg,
+                "Link blocked by thermal keep-out"
+            );
+            return None;
+        }
 
-        // Free space loss
-        let free_space_loss_db = 20.0 * (look_angles.range_km * 1000.0).log10()
-            + 20.0 * (self.wavelength_nm * 1e-9).log10()
-            - 147.55;
+        // Protected objects need a propagator and are only masked in `annotate_window`
+        if let Some(avoidance) = &self.geo_arc_avoidance {
+            let separation_deg = self.geo_arc_separation_deg(satellite_state, station);
+            if separation_deg < avoidance.min_separation_deg {
+                tracing::trace!(
+                    target: trace_targets::FSO,
+                    satellite_id = %satellite_state.satellite_id,
+                    station_id = %station.station_id,
+                    geo_separation_deg = separation_deg,
+                    "Link blocked by GEO arc avoidance"
+                );
+                return None;
+            }
+        }
 
-        // Link margin calculation
-        let transmit_power_dbm = 10.0 * self.transmit_power_w.log10() + 30.0;
-        let receiver_sensitivity_dbm = -40.0;
-        let link_margin_db = transmit_power_dbm - receiver_sensitivity_dbm - free_space_loss_db;
+        let atmospheric_transmission = band.atmospheric_transmission(look_angles.elevation_deg);
 
-        // Throughput estimation
-        let throughput_factor = (link_margin_db / 20.0).min(1.0).max(0.0);
-        let estimated_throughput_gbps = 400.0 * throughput_factor * atmospheric_transmission;
+        let adaptive_optics_gain_db = self
+            .adaptive_optics
+            .get(&station.station_id)
+            .map(|ao| ao.gain_db(band.wavelength_nm, look_angles.elevation_deg));
+        let sky_background_penalty_db = self.sky_background.as_ref().and_then(|sky| {
+            let position = &station.position;
+            sky.snr_penalty_db(
+                band.wavelength_nm,
+                look_angles.elevation_deg,
+                ephemeris::sun_elevation_deg(position.latitude_deg, position.longitude_deg, time),
+                self.sun_separation_deg(OpticalTerminal::Ground, satellite_state, station),
+                self.receiver_aperture_m,
+            )
+        });
+        let link_margin_db = link_margin_db(
+            band.wavelength_nm,
+            self.transmit_power_w,
+            look_angles.range_km,
+            band.receiver_sensitivity_dbm,
+        ) + adaptive_optics_gain_db.unwrap_or(0.0)
+            - sky_background_penalty_db.unwrap_or(0.0);
 
         Some(FsoLinkQuality {
             satellite_id: satellite_state.satellite_id.clone(),
@@ -83,10 +834,116 @@ impl FsoAnalyzer {
             range_km: look_angles.range_km,
             atmospheric_transmission,
             link_margin_db,
-            estimated_throughput_gbps,
+            estimated_throughput_gbps: throughput_gbps(link_margin_db, atmospheric_transmission),
             weather_impact_factor: 0.9, // Assume good weather
+            fade_db: None,
+            adaptive_optics_gain_db,
+            sky_background_penalty_db,
+        })
+    }
+
+    /// Link quality every `step_seconds` through a visibility window
+    ///
+    /// Samples blocked by the elevation mask or a keep-out are left out.
+    /// With a fade model, the kept samples get one correlated fade series,
+    /// seeded per satellite, station and pass start so reruns match.
+    pub fn analyze_pass(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+        step_seconds: f64,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Vec<FsoLinkQuality>> {
+        let band = self.primary_band();
+        self.analyze_pass_in_band(satellite, station, window, step_seconds, propagator, &band)
+    }
+
+    /// Link budgets through a pass at each of `bands`, side by side
+    ///
+    /// Every band sees the same geometry and the same fade series.
+    pub fn compare_wavelengths(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+        step_seconds: f64,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<WavelengthComparison> {
+        if self.bands.is_empty() {
+            return Err(OrbitalMechanicsError::config_error(
+                "Wavelength comparison needs at least one band",
+            ));
+        }
+        let bands = self
+            .bands
+            .iter()
+            .map(|band| {
+                let samples = self.analyze_pass_in_band(
+                    satellite,
+                    station,
+                    window,
+                    step_seconds,
+                    propagator,
+                    band,
+                )?;
+                Ok(BandLinkBudget::new(
+                    band.clone(),
+                    samples,
+                    self.sky_background.as_ref(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(WavelengthComparison {
+            satellite_id: window.satellite_id.clone(),
+            station_id: window.station_id.clone(),
+            start_time: window.start_time,
+            end_time: window.end_time,
+            bands,
         })
     }
+
+    fn analyze_pass_in_band(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+        step_seconds: f64,
+        propagator: &dyn OrbitalPropagator,
+        band: &OpticalBand,
+    ) -> Result<Vec<FsoLinkQuality>> {
+        let times =
+            pass_profile::sample_times(window.start_time, window.end_time, Seconds(step_seconds))?;
+        let mut samples = Vec::with_capacity(times.len());
+        for time in times {
+            let state = propagator
+                .propagate(satellite, time)
+                .for_satellite(&satellite.satellite_id)
+                .for_station(&station.station_id)
+                .at_epoch(time)?;
+            samples.extend(self.analyze_link_in_band(&state, station, time, band));
+        }
+
+        if let Some(fade_model) = &self.fade_model {
+            let times: Vec<DateTime<Utc>> = samples.iter().map(|s| s.timestamp).collect();
+            let fades = fade_model.synthesize(&times, pass_stream(window))?;
+            for (sample, fade_db) in samples.iter_mut().zip(fades) {
+                sample.link_margin_db -= fade_db;
+                sample.estimated_throughput_gbps =
+                    throughput_gbps(sample.link_margin_db, sample.atmospheric_transmission);
+                sample.fade_db = Some(fade_db);
+            }
+        }
+
+        Ok(samples)
+    }
+}
+
+impl StationScoped for FsoLinkQuality {
+    fn station_id(&self) -> &str {
+        &self.station_id
+    }
 }
 
 impl Default for FsoAnalyzer {
@@ -94,3 +951,495 @@ impl Default for FsoAnalyzer {
         Self::new()
     }
 }
+
+/// Link margin in dB at `range_km` after free-space loss
+pub(crate) fn link_margin_db(
+    wavelength_nm: f64,
+    transmit_power_w: f64,
+    range_km: f64,
+    receiver_sensitivity_dbm: f64,
+) -> f64 {
+    let free_space_loss_db =
+        20.0 * (range_km * 1000.0).log10() + 20.0 * (wavelength_nm * 1e-9).log10() - 147.55;
+    let transmit_power_dbm = 10.0 * transmit_power_w.log10() + 30.0;
+    transmit_power_dbm - receiver_sensitivity_dbm - free_space_loss_db
+}
+
+/// Throughput estimate from the link margin and atmospheric transmission
+pub(crate) fn throughput_gbps(link_margin_db: f64, atmospheric_transmission: f64) -> f64 {
+    let throughput_factor = (link_margin_db / 20.0).clamp(0.0, 1.0);
+    400.0 * throughput_factor * atmospheric_transmission
+}
+
+/// Fade stream of one pass, from its satellite, station and start (FNV-1a)
+fn pass_stream(window: &VisibilityWindow) -> u64 {
+    let start = window.start_time.timestamp_millis().to_le_bytes();
+    let bytes = window
+        .satellite_id
+        .bytes()
+        .chain([0])
+        .chain(window.station_id.bytes())
+        .chain([0])
+        .chain(start);
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Sensitivity of the reference 1550 nm receiver
+pub(crate) const RECEIVER_SENSITIVITY_DBM: f64 = -40.0;
+
+/// Hufnagel-Valley 5/7 ground-layer Cn² (m^-2/3)
+const HV57_GROUND_CN2: f64 = 1.7e-14;
+
+/// Hufnagel-Valley 5/7 high-altitude RMS wind
+const HV57_RMS_WIND_M_PER_S: f64 = 21.0;
+
+/// Clear-sky spectral radiance at 1550 nm, zenith view with the Sun overhead
+const CLEAR_SKY_RADIANCE_1550NM: f64 = 1.0;
+
+/// Angular scale of the forward-scattering peak around the Sun
+const SOLAR_AUREOLE_DEG: f64 = 10.0;
+
+/// Apparent angular radius of the Sun
+const SUN_ANGULAR_RADIUS_DEG: f64 = 0.27;
+
+/// Detector noise-equivalent power of the reference receiver
+const RECEIVER_NOISE_EQUIVALENT_POWER_W: f64 = 1e-15;
+
+/// Longitude spacing of the coarse GEO belt scan
+const GEO_BELT_SCAN_STEP_DEG: f64 = 1.0;
+
+/// Golden-section iterations refining the closest belt point
+const GEO_BELT_REFINE_ITERATIONS: usize = 40;
+
+/// Station position in ECI at `time` (km), the frame of satellite states and the Sun
+fn station_position(
+    earth_model: &EarthModel,
+    station: &GroundStation,
+    time: DateTime<Utc>,
+) -> Vector3<f64> {
+    let position = &station.position;
+    let [x, y, z] = earth_model.geodetic_to_ecef(
+        position.latitude_deg,
+        position.longitude_deg,
+        position.elevation_m * M_TO_KM,
+    );
+    let (sin, cos) = ephemeris::gmst_rad(time).sin_cos();
+    Vector3::new(x * cos - y * sin, x * sin + y * cos, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fso_fade::fade_events;
+    use crate::ground_station::StationPosition;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use crate::visibility::PassType;
+    use chrono::TimeZone;
+
+    fn equinox_noon() -> DateTime<Utc> {
+        // Sun close to the +x axis, directly above a station at lat 0 / lon 0
+        Utc.with_ymd_and_hms(2024, 3, 20, 12, 0, 0).unwrap()
+    }
+
+    fn station() -> GroundStation {
+        GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Equatorial Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        }
+    }
+
+    fn overhead_satellite(epoch: DateTime<Utc>) -> SatelliteOrbit {
+        let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+        SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            epoch,
+        )
+    }
+
+    fn overhead_pass(epoch: DateTime<Utc>) -> VisibilityWindow {
+        VisibilityWindow {
+            satellite_id: "TEST-01".to_string(),
+            station_id: "GS-001".to_string(),
+            start_time: epoch - Duration::minutes(10),
+            end_time: epoch + Duration::minutes(10),
+            duration_seconds: 1200.0,
+            max_elevation_time: epoch,
+            max_elevation_deg: 90.0,
+            min_range_km: 8000.0,
+            mean_range_km: 8000.0,
+            azimuth_span_deg: 0.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_link_geometry_uses_earth_model() {
+        let time = equinox_noon();
+        let mut station = station();
+        station.position.latitude_deg = 45.0;
+        let state = SatelliteState::new(
+            "TEST-01".to_string(),
+            time,
+            [10000.0, 0.0, 10000.0],
+            [0.0, 5.3, 0.0],
+        );
+
+        let ellipsoid = FsoAnalyzer::new()
+            .with_earth_model(EarthModel::Wgs84)
+            .analyze_link(&state, &station, time)
+            .unwrap();
+        let expected =
+            state.look_angles_from_station_with_model(&EarthModel::Wgs84, 45.0, 0.0, 0.0);
+        assert_eq!(ellipsoid.elevation_angle_deg, expected.elevation_deg);
+        assert_eq!(ellipsoid.range_km, expected.range_km);
+        let sphere = FsoAnalyzer::new()
+            .analyze_link(&state, &station, time)
+            .unwrap();
+        assert!((ellipsoid.elevation_angle_deg - sphere.elevation_angle_deg).abs() > 0.05);
+    }
+
+    #[test]
+    fn test_keep_out_blocks_link_with_sun_behind_satellite() {
+        let time = equinox_noon();
+        let station = station();
+        let state = SatelliteState::new(
+            "TEST-01".to_string(),
+            time,
+            [14378.0, 0.0, 0.0],
+            [0.0, 5.3, 0.0],
+        );
+
+        let analyzer = FsoAnalyzer::new();
+        assert!(analyzer.analyze_link(&state, &station, time).is_some());
+        assert!(analyzer.sun_separation_deg(OpticalTerminal::Ground, &state, &station) < 1.0);
+        assert!(analyzer.sun_separation_deg(OpticalTerminal::Space, &state, &station) > 179.0);
+
+        let analyzer = analyzer.with_thermal_keep_out(ThermalKeepOut::ground(10.0));
+        let violation = analyzer.thermal_violation(&state, &station).unwrap();
+        assert_eq!(violation.terminal, OpticalTerminal::Ground);
+        assert!(analyzer.analyze_link(&state, &station, time).is_none());
+
+        // The space terminal looks away from the Sun
+        let analyzer = FsoAnalyzer::new().with_thermal_keep_out(ThermalKeepOut::space(10.0));
+        assert!(analyzer.thermal_violation(&state, &station).is_none());
+    }
+
+    #[test]
+    fn test_geo_arc_avoidance() {
+        let epoch = equinox_noon();
+        let station = station();
+        let propagator = KeplerianPropagator::new();
+
+        // Equatorial satellite at zenith: the beam continues straight into the GEO belt
+        let overhead = propagator
+            .propagate(&overhead_satellite(epoch), epoch)
+            .unwrap();
+        let analyzer = FsoAnalyzer::new().with_geo_arc_avoidance(GeoArcAvoidance::new(5.0));
+        assert!(analyzer.geo_arc_separation_deg(&overhead, &station) < 0.01);
+        assert!(analyzer.analyze_link(&overhead, &station, epoch).is_none());
+        let violation = analyzer
+            .geo_arc_violation(&overhead, &station, &propagator)
+            .unwrap()
+            .unwrap();
+        assert!(violation.object_id.is_none());
+
+        // Polar satellite 55° up towards the north clears the belt by ~35°
+        let polar = |id: &str, anomaly: f64| {
+            let elements = OrbitalElements::new(14378.0, 0.0, 90.0, 0.0, 0.0, anomaly).unwrap();
+            SatelliteOrbit::new(id.to_string(), id.to_string(), elements, epoch)
+        };
+        let target = propagator
+            .propagate(&polar("TEST-02", 20.0), epoch)
+            .unwrap();
+        let separation = analyzer.geo_arc_separation_deg(&target, &station);
+        assert!((separation - 34.6).abs() < 0.5, "{}", separation);
+        assert!(analyzer.analyze_link(&target, &station, epoch).is_some());
+
+        // A protected object trailing the target by 0.5° of anomaly is masked
+        let analyzer = FsoAnalyzer::new().with_geo_arc_avoidance(
+            GeoArcAvoidance::new(2.0).with_protected_object(polar("PROTECTED-01", 20.5)),
+        );
+        let violation = analyzer
+            .geo_arc_violation(&target, &station, &propagator)
+            .unwrap()
+            .unwrap();
+        assert_eq!(violation.object_id.as_deref(), Some("PROTECTED-01"));
+        assert!(violation.separation_deg < 1.0);
+        assert!(violation.reason().contains("PROTECTED-01"));
+    }
+
+    #[test]
+    fn test_separations_follow_station_through_sidereal_rotation() {
+        // Six hours after noon the station has turned a quarter of the way round
+        let time = equinox_noon() + Duration::hours(6);
+        let station = station();
+        let sidereal_deg = ephemeris::gmst_rad(time) * RAD_TO_DEG;
+        assert!((sidereal_deg - 90.0).abs() < 5.0, "{}", sidereal_deg);
+
+        let propagator = KeplerianPropagator::new();
+        let above_station = |id: &str, inclination_deg: f64, anomaly_deg: f64| {
+            let elements = OrbitalElements::new(
+                14378.0,
+                0.0,
+                inclination_deg,
+                sidereal_deg,
+                0.0,
+                anomaly_deg,
+            )
+            .unwrap();
+            let orbit = SatelliteOrbit::new(id.to_string(), id.to_string(), elements, time);
+            propagator.propagate(&orbit, time).unwrap()
+        };
+
+        // Zenith beam: the Sun is 90° less its elevation away
+        let overhead = above_station("TEST-01", 0.0, 0.0);
+        let sun_elevation_deg = ephemeris::sun_elevation_deg(0.0, 0.0, time);
+        let separation =
+            FsoAnalyzer::new().sun_separation_deg(OpticalTerminal::Ground, &overhead, &station);
+        assert!(
+            (separation - (90.0 - sun_elevation_deg)).abs() < 0.1,
+            "{} vs Sun elevation {}",
+            separation,
+            sun_elevation_deg
+        );
+
+        // Same GEO geometry as at noon, turned with the station
+        let analyzer = FsoAnalyzer::new().with_geo_arc_avoidance(
+            GeoArcAvoidance::new(2.0).with_protected_object(SatelliteOrbit::new(
+                "PROTECTED-01".to_string(),
+                "PROTECTED-01".to_string(),
+                OrbitalElements::new(14378.0, 0.0, 90.0, sidereal_deg, 0.0, 20.5).unwrap(),
+                time,
+            )),
+        );
+        assert!(analyzer.geo_arc_separation_deg(&overhead, &station) < 0.01);
+        let target = above_station("TEST-02", 90.0, 20.0);
+        let separation = analyzer.geo_arc_separation_deg(&target, &station);
+        assert!((separation - 34.6).abs() < 0.5, "{}", separation);
+        let violation = analyzer
+            .geo_arc_violation(&target, &station, &propagator)
+            .unwrap()
+            .unwrap();
+        assert_eq!(violation.object_id.as_deref(), Some("PROTECTED-01"));
+    }
+
+    #[test]
+    fn test_annotate_window_marks_keep_out_interval() {
+        let epoch = equinox_noon();
+        let satellite = overhead_satellite(epoch);
+        let station = station();
+        let mut window = overhead_pass(epoch);
+
+        let analyzer = FsoAnalyzer::new().with_thermal_keep_out(ThermalKeepOut::ground(10.0));
+        analyzer
+            .annotate_window(
+                &mut window,
+                &satellite,
+                &station,
+                &KeplerianPropagator::new(),
+                10.0,
+            )
+            .unwrap();
+
+        assert_eq!(window.unusable_intervals.len(), 1);
+        let interval = &window.unusable_intervals[0];
+        assert!(interval.start_time > window.start_time && interval.start_time < epoch);
+        assert!(interval.end_time > epoch && interval.end_time < window.end_time);
+        assert!(interval.reason.contains("ground terminal"));
+        assert!(window.usable_seconds() > 0.0 && window.usable_seconds() < window.duration_seconds);
+    }
+
+    #[test]
+    fn test_pass_with_synthetic_fades() {
+        let epoch = equinox_noon();
+        let satellite = overhead_satellite(epoch);
+        let station = station();
+        let window = overhead_pass(epoch);
+        let propagator = KeplerianPropagator::new();
+
+        let clear = FsoAnalyzer::new()
+            .analyze_pass(&satellite, &station, &window, 30.0, &propagator)
+            .unwrap();
+        assert_eq!(clear.len(), 41);
+        assert!(clear.iter().all(|s| s.fade_db.is_none()));
+
+        let analyzer =
+            FsoAnalyzer::new().with_fade_model(FadeModel::weibull(1.5, 90.0).with_seed(3));
+        let faded = analyzer
+            .analyze_pass(&satellite, &station, &window, 30.0, &propagator)
+            .unwrap();
+        assert_eq!(faded.len(), clear.len());
+        for (f, c) in faded.iter().zip(&clear) {
+            let fade_db = f.fade_db.unwrap();
+            assert!((c.link_margin_db - f.link_margin_db - fade_db).abs() < 1e-9);
+        }
+        let rerun = analyzer
+            .analyze_pass(&satellite, &station, &window, 30.0, &propagator)
+            .unwrap();
+        assert!(faded
+            .iter()
+            .zip(&rerun)
+            .all(|(a, b)| a.fade_db == b.fade_db));
+
+        let events = fade_events(&faded, 1.0);
+        assert!(!events.is_empty());
+        for event in &events {
+            assert!(event.start_time >= window.start_time && event.end_time <= window.end_time);
+            assert!(event.peak_fade_db > 1.0);
+        }
+        assert!(fade_events(&clear, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_adaptive_optics_gain() {
+        // HV 5/7 gives r0 of about 5 cm at 500 nm, scaling as wavelength^1.2
+        let ao = AdaptiveOptics::new(1.0, 0.1);
+        let r0 = ao.fried_parameter_m(500.0, 90.0);
+        assert!((r0 - 0.05).abs() < 0.005, "r0 {}", r0);
+        let r0_1550 = ao.fried_parameter_m(1550.0, 90.0);
+        assert!((r0_1550 / r0 - 3.1f64.powf(1.2)).abs() < 1e-6);
+
+        // Correction matters more through the longer low-elevation path
+        let zenith_gain = ao.gain_db(1550.0, 90.0);
+        assert!(zenith_gain > 10.0 && zenith_gain < ao.gain_db(1550.0, 30.0));
+        assert!(ao.strehl_ratio(1550.0, 90.0) > 0.8);
+        // Stronger ground turbulence lowers the corrected Strehl ratio
+        let turbulent = ao.clone().with_turbulence(1.7e-13, 21.0);
+        assert!(turbulent.strehl_ratio(1550.0, 90.0) < ao.strehl_ratio(1550.0, 90.0));
+
+        let epoch = equinox_noon();
+        let propagator = KeplerianPropagator::new();
+        let state = propagator
+            .propagate(&overhead_satellite(epoch), epoch)
+            .unwrap();
+        let passive = FsoAnalyzer::new()
+            .analyze_link(&state, &station(), epoch)
+            .unwrap();
+        assert!(passive.adaptive_optics_gain_db.is_none());
+        let corrected = FsoAnalyzer::new()
+            .with_adaptive_optics("GS-001", ao)
+            .analyze_link(&state, &station(), epoch)
+            .unwrap();
+        let gain = corrected.adaptive_optics_gain_db.unwrap();
+        assert!(gain > 10.0);
+        assert!((corrected.link_margin_db - passive.link_margin_db - gain).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sky_background_penalty() {
+        let sky = SkyBackground::new(1.0, 20.0);
+        let radiance = |elevation_deg, sun_elevation_deg, sun_separation_deg| {
+            sky.radiance(1550.0, elevation_deg, sun_elevation_deg, sun_separation_deg)
+        };
+        assert!(radiance(60.0, 45.0, 2.0) > 10.0 * radiance(60.0, 45.0, 90.0));
+        assert!(radiance(20.0, 45.0, 90.0) > radiance(90.0, 45.0, 90.0));
+        assert_eq!(radiance(60.0, -3.0, 90.0), 0.0);
+        assert!(sky.snr_penalty_db(1550.0, 60.0, -3.0, 90.0, 0.3).is_none());
+        let ratio = sky.radiance(1064.0, 60.0, 45.0, 90.0) / radiance(60.0, 45.0, 90.0);
+        assert!((ratio - (1550.0f64 / 1064.0).powi(4)).abs() < 1e-9);
+
+        // Satellite overhead with the Sun at the zenith: looking into the Sun
+        let epoch = equinox_noon();
+        let propagator = KeplerianPropagator::new();
+        let state = propagator
+            .propagate(&overhead_satellite(epoch), epoch)
+            .unwrap();
+        let night = FsoAnalyzer::new()
+            .analyze_link(&state, &station(), epoch)
+            .unwrap();
+        let analyzer = FsoAnalyzer::new().with_sky_background(sky.clone());
+        let day = analyzer.analyze_link(&state, &station(), epoch).unwrap();
+        let penalty = day.sky_background_penalty_db.unwrap();
+        assert!(penalty > sky.max_penalty_db);
+        assert!((night.link_margin_db - day.link_margin_db - penalty).abs() < 1e-9);
+
+        assert_eq!(
+            sky.daytime_feasible(std::slice::from_ref(&day)),
+            Some(false)
+        );
+        assert_eq!(
+            sky.clone()
+                .with_max_penalty_db(penalty + 1.0)
+                .daytime_feasible(std::slice::from_ref(&day)),
+            Some(true)
+        );
+        assert_eq!(sky.daytime_feasible(&[night]), None);
+    }
+
+    #[test]
+    fn test_compare_wavelengths() {
+        let epoch = equinox_noon();
+        let satellite = overhead_satellite(epoch);
+        let window = overhead_pass(epoch);
+        let propagator = KeplerianPropagator::new();
+        let analyzer = FsoAnalyzer::new()
+            .with_band(OpticalBand::nm_1064())
+            .with_band(OpticalBand::nm_1550());
+
+        let comparison = analyzer
+            .compare_wavelengths(&satellite, &station(), &window, 60.0, &propagator)
+            .unwrap();
+        let [short, long] = &comparison.bands[..] else {
+            panic!("expected two bands");
+        };
+        assert_eq!(short.samples.len(), long.samples.len());
+
+        // The reference band reproduces the single-wavelength analysis
+        let single = analyzer
+            .analyze_pass(&satellite, &station(), &window, 60.0, &propagator)
+            .unwrap();
+        let single_mean =
+            single.iter().map(|s| s.link_margin_db).sum::<f64>() / single.len() as f64;
+        assert!((long.mean_link_margin_db - single_mean).abs() < 1e-9);
+
+        // 1064 nm: less diffraction loss and a better detector, more extinction
+        let expected = -20.0 * (1064.0f64 / 1550.0).log10() + 5.0;
+        for (a, b) in short.samples.iter().zip(&long.samples) {
+            assert!((a.link_margin_db - b.link_margin_db - expected).abs() < 1e-9);
+            assert!(a.atmospheric_transmission < b.atmospheric_transmission);
+        }
+        assert!(short.min_link_margin_db <= short.mean_link_margin_db);
+        assert!(comparison.best_band().is_some());
+        assert_eq!(long.daytime_feasible, None);
+
+        // Daytime feasibility per band: 1064 nm sees the brighter sky
+        let worst_1550_penalty = {
+            let daytime = FsoAnalyzer::new().with_sky_background(SkyBackground::new(1.0, 20.0));
+            daytime
+                .analyze_pass(&satellite, &station(), &window, 60.0, &propagator)
+                .unwrap()
+                .iter()
+                .filter_map(|s| s.sky_background_penalty_db)
+                .fold(0.0, f64::max)
+        };
+        let sky = SkyBackground::new(1.0, 20.0).with_max_penalty_db(worst_1550_penalty);
+        let comparison = analyzer
+            .with_sky_background(sky)
+            .compare_wavelengths(&satellite, &station(), &window, 60.0, &propagator)
+            .unwrap();
+        assert_eq!(comparison.bands[0].daytime_feasible, Some(false));
+        assert_eq!(comparison.bands[1].daytime_feasible, Some(true));
+
+        assert!(FsoAnalyzer::new()
+            .compare_wavelengths(&satellite, &station(), &window, 60.0, &propagator)
+            .is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Synthetic optical fade time series
+//!
+//! Turbulence makes received optical power fluctuate on millisecond to
+//! second scales, and deep fades arrive in bursts rather than as independent
+//! samples. Fades here are drawn from a Gaussian AR(1) process whose
+//! correlation decays as `exp(-dt / coherence_time)`, mapped sample by
+//! sample onto a lognormal (weak turbulence) or Weibull (moderate to strong)
+//! irradiance distribution with unit mean. The fade is the irradiance loss
+//! in dB, so short surges above the mean show up as negative fades.
+//!
+//! The same seed and stream reproduce the same series, so throughput
+//! simulations can be rerun and compared.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::fso_analysis::FsoLinkQuality;
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+use serde::{Deserialize, Serialize};
+
+/// Irradiance distribution of the fades, normalised to unit mean
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FadeDistribution {
+    /// Weak turbulence; `scintillation_index` is the normalised irradiance variance
+    LogNormal { scintillation_index: f64 },
+    /// Moderate to strong turbulence; smaller shapes give deeper, more frequent fades
+    Weibull { shape: f64 },
+}
+
+/// Correlated fade synthesis settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FadeModel {
+    pub distribution: FadeDistribution,
+    /// Time for the fade correlation to drop to 1/e
+    pub coherence_time_seconds: f64,
+    pub seed: u64,
+}
+
+/// Run of consecutive samples faded deeper than a threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FadeEvent {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub peak_fade_db: f64,
+}
+
+impl FadeModel {
+    /// Weak-turbulence fades with the given scintillation index
+    pub fn lognormal(scintillation_index: f64, coherence_time_seconds: f64) -> Self {
+        Self {
+            distribution: FadeDistribution::LogNormal {
+                scintillation_index,
+            },
+            coherence_time_seconds,
+            seed: 0,
+        }
+    }
+
+    /// Weibull fades with the given shape
+    pub fn weibull(shape: f64, coherence_time_seconds: f64) -> Self {
+        Self {
+            distribution: FadeDistribution::Weibull { shape },
+            coherence_time_seconds,
+            seed: 0,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let (name, parameter) = match self.distribution {
+            FadeDistribution::LogNormal {
+                scintillation_index,
+            } => ("Scintillation index", scintillation_index),
+            FadeDistribution::Weibull { shape } => ("Weibull shape", shape),
+        };
+        if !parameter.is_finite() || parameter <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "{} must be positive, got {}",
+                name, parameter
+            )));
+        }
+        if !self.coherence_time_seconds.is_finite() || self.coherence_time_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Fade coherence time must be positive, got {} s",
+                self.coherence_time_seconds
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fade in dB at each of `times`, which must be in order
+    ///
+    /// `stream` separates independent series under one seed, e.g. one per
+    /// pass; uneven spacing is allowed.
+    pub fn synthesize(&self, times: &[DateTime<Utc>], stream: u64) -> Result<Vec<f64>> {
+        self.validate()?;
+        let mut rng = StdRng::seed_from_u64(self.seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+        let mut gaussian: f64 = StandardNormal.sample(&mut rng);
+        let mut previous = times.first().copied();
+        let mut fades = Vec::with_capacity(times.len());
+        for &time in times {
+            if let Some(previous) = previous {
+                let dt = (time - previous).num_milliseconds() as f64 / 1000.0;
+                let rho = (-dt.max(0.0) / self.coherence_time_seconds).exp();
+                let innovation: f64 = StandardNormal.sample(&mut rng);
+                gaussian = rho * gaussian + (1.0 - rho * rho).sqrt() * innovation;
+            }
+            previous = Some(time);
+            fades.push(-10.0 * self.irradiance(gaussian).log10());
+        }
+        Ok(fades)
+    }
+
+    /// Unit-mean irradiance for a standard normal deviate
+    fn irradiance(&self, gaussian: f64) -> f64 {
+        match self.distribution {
+            FadeDistribution::LogNormal {
+                scintillation_index,
+            } => {
+                let variance = scintillation_index.ln_1p();
+                (variance.sqrt() * gaussian - 0.5 * variance).exp()
+            }
+            FadeDistribution::Weibull { shape } => {
+                // Through the normal CDF onto the Weibull quantile; the upper
+                // tail is used so the deepest fades keep full precision
+                let survival = (0.5 * libm::erfc(gaussian / std::f64::consts::SQRT_2))
+                    .clamp(f64::MIN_POSITIVE, 1.0);
+                let scale = 1.0 / libm::tgamma(1.0 + 1.0 / shape);
+                scale * (-survival.ln()).powf(1.0 / shape)
+            }
+        }
+    }
+}
+
+/// Fade events deeper than `threshold_db` in a sampled pass
+///
+/// Samples without a synthesized fade never start an event.
+pub fn fade_events(samples: &[FsoLinkQuality], threshold_db: f64) -> Vec<FadeEvent> {
+    let mut events: Vec<FadeEvent> = Vec::new();
+    let mut in_fade = false;
+
+    for sample in samples {
+        let Some(fade_db) = sample.fade_db.filter(|fade| *fade > threshold_db) else {
+            in_fade = false;
+            continue;
+        };
+        match events.last_mut() {
+            Some(event) if in_fade => {
+                event.end_time = sample.timestamp;
+                event.peak_fade_db = event.peak_fade_db.max(fade_db);
+            }
+            _ => events.push(FadeEvent {
+                satellite_id: sample.satellite_id.clone(),
+                station_id: sample.station_id.clone(),
+                start_time: sample.timestamp,
+                end_time: sample.timestamp,
+                peak_fade_db: fade_db,
+            }),
+        }
+        in_fade = true;
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn times(count: i64, step_ms: i64) -> Vec<DateTime<Utc>> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        (0..count)
+            .map(|i| start + Duration::milliseconds(i * step_ms))
+            .collect()
+    }
+
+    fn irradiance(fades: &[f64]) -> Vec<f64> {
+        fades.iter().map(|f| 10f64.powf(-f / 10.0)).collect()
+    }
+
+    #[test]
+    fn test_fade_statistics_and_correlation() {
+        let times = times(200_000, 10);
+
+        // Unit mean and the configured scintillation index
+        let model = FadeModel::lognormal(0.2, 0.05).with_seed(7);
+        let power = irradiance(&model.synthesize(&times, 0).unwrap());
+        let mean = power.iter().sum::<f64>() / power.len() as f64;
+        let variance = power.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / power.len() as f64;
+        assert!((mean - 1.0).abs() < 0.02, "mean {}", mean);
+        assert!((variance / (mean * mean) - 0.2).abs() < 0.03);
+
+        // Correlation falls to about 1/e after one coherence time (5 samples)
+        let fades = model.synthesize(&times, 1).unwrap();
+        let fade_mean = fades.iter().sum::<f64>() / fades.len() as f64;
+        let covariance = |lag: usize| {
+            fades
+                .iter()
+                .zip(&fades[lag..])
+                .map(|(a, b)| (a - fade_mean) * (b - fade_mean))
+                .sum::<f64>()
+                / (fades.len() - lag) as f64
+        };
+        let correlation = covariance(5) / covariance(0);
+        assert!(
+            (correlation - (-1.0f64).exp()).abs() < 0.03,
+            "{}",
+            correlation
+        );
+
+        let weibull = FadeModel::weibull(2.0, 0.05).with_seed(7);
+        let power = irradiance(&weibull.synthesize(&times, 0).unwrap());
+        let mean = power.iter().sum::<f64>() / power.len() as f64;
+        assert!((mean - 1.0).abs() < 0.02, "mean {}", mean);
+
+        // Reproducible per seed and stream
+        assert_eq!(
+            model.synthesize(&times[..100], 3).unwrap(),
+            model.synthesize(&times[..100], 3).unwrap()
+        );
+        assert_ne!(
+            model.synthesize(&times[..100], 3).unwrap(),
+            model.synthesize(&times[..100], 4).unwrap()
+        );
+        assert!(FadeModel::weibull(0.0, 0.05).synthesize(&times, 0).is_err());
+        assert!(FadeModel::lognormal(0.2, 0.0)
+            .synthesize(&times, 0)
+            .is_err());
+    }
+}
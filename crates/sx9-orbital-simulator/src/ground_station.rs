@@ -1,9 +1,14 @@
 //! Ground station definitions and network management
 
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use crate::constants::*;
-use crate::error::{OrbitalMechanicsError, Result};
+use crate::coordinates::wrap_angle_deg;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::mount::MountType;
+use crate::units::{Degrees, Meters};
 
 /// Ground station definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +16,18 @@ pub struct GroundStation {
     pub station_id: String,
     pub name: String,
     pub position: StationPosition,
+    /// Outage and maintenance calendar; empty means always available
+    #[serde(default)]
+    pub availability: StationAvailability,
+    /// Antenna mount, when pointing should be produced in its axis space
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mount: Option<MountType>,
+    /// Elevation mask; overrides the visibility calculator's when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_elevation_deg: Option<f64>,
+    /// IANA time zone for station-local reports; reports show UTC only when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
 }
 
 /// Ground station position
@@ -21,6 +38,361 @@ pub struct StationPosition {
     pub elevation_m: f64,
 }
 
+impl StationPosition {
+    pub fn latitude(&self) -> Degrees {
+        Degrees(self.latitude_deg)
+    }
+
+    pub fn longitude(&self) -> Degrees {
+        Degrees(self.longitude_deg)
+    }
+
+    /// Height above the reference ellipsoid
+    pub fn elevation(&self) -> Meters {
+        Meters(self.elevation_m)
+    }
+}
+
+/// Operator-defined periods when a station cannot support contacts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StationAvailability {
+    /// One-off outages
+    #[serde(default)]
+    pub outages: Vec<StationOutage>,
+    /// Recurring maintenance windows
+    #[serde(default)]
+    pub maintenance: Vec<RecurringMaintenance>,
+}
+
+/// One-off outage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationOutage {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Maintenance window repeating daily or weekly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringMaintenance {
+    pub recurrence: Recurrence,
+    /// Window start as UTC time of day ("HH:MM:SS")
+    pub start_time_utc: NaiveTime,
+    pub duration_minutes: f64,
+    pub reason: String,
+    /// First day the window applies; `None` applies from the start
+    #[serde(default)]
+    pub effective_from: Option<DateTime<Utc>>,
+    /// Last day the window applies; `None` repeats indefinitely
+    #[serde(default)]
+    pub effective_until: Option<DateTime<Utc>>,
+}
+
+/// Repetition of a maintenance window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recurrence {
+    Daily,
+    Weekly(Weekday),
+}
+
+/// Interval during which a station is unavailable, with the reason
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnavailableInterval {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub reason: String,
+}
+
+impl GroundStation {
+    /// Start building a station; see `GroundStationBuilder` for the defaults
+    pub fn builder() -> GroundStationBuilder {
+        GroundStationBuilder::default()
+    }
+
+    /// Whether the station can support a contact at `time`
+    pub fn is_available(&self, time: DateTime<Utc>) -> bool {
+        self.availability.is_available(time)
+    }
+
+    /// `time` on the station's clock, when its time zone is set
+    pub fn local_time(&self, time: DateTime<Utc>) -> Option<DateTime<Tz>> {
+        self.timezone.map(|timezone| time.with_timezone(&timezone))
+    }
+}
+
+/// Builder for `GroundStation`
+///
+/// Latitude and longitude are required. The station defaults to sea level,
+/// ID `GS-001` with the ID as name, always available, no mount model, no time
+/// zone and the visibility calculator's elevation mask. Longitude is wrapped
+/// into [-180, 180)°.
+#[derive(Debug, Clone)]
+pub struct GroundStationBuilder {
+    station_id: String,
+    name: Option<String>,
+    latitude_deg: Option<f64>,
+    longitude_deg: Option<f64>,
+    altitude_m: f64,
+    min_elevation_deg: Option<f64>,
+    mount: Option<MountType>,
+    availability: StationAvailability,
+    timezone: Option<Tz>,
+}
+
+impl Default for GroundStationBuilder {
+    fn default() -> Self {
+        Self {
+            station_id: "GS-001".to_string(),
+            name: None,
+            latitude_deg: None,
+            longitude_deg: None,
+            altitude_m: 0.0,
+            min_elevation_deg: None,
+            mount: None,
+            availability: StationAvailability::default(),
+            timezone: None,
+        }
+    }
+}
+
+impl GroundStationBuilder {
+    pub fn station_id(mut self, station_id: impl Into<String>) -> Self {
+        self.station_id = station_id.into();
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn latitude_deg(mut self, latitude_deg: f64) -> Self {
+        self.latitude_deg = Some(latitude_deg);
+        self
+    }
+
+    pub fn longitude_deg(mut self, longitude_deg: f64) -> Self {
+        self.longitude_deg = Some(longitude_deg);
+        self
+    }
+
+    /// Height above the reference ellipsoid
+    pub fn altitude_m(mut self, altitude_m: f64) -> Self {
+        self.altitude_m = altitude_m;
+        self
+    }
+
+    /// Latitude, longitude and height in one call
+    pub fn position(self, latitude_deg: f64, longitude_deg: f64, altitude_m: f64) -> Self {
+        self.latitude_deg(latitude_deg)
+            .longitude_deg(longitude_deg)
+            .altitude_m(altitude_m)
+    }
+
+    /// Typed `latitude_deg`, accepting degrees or radians
+    pub fn latitude(self, latitude: impl Into<Degrees>) -> Self {
+        self.latitude_deg(latitude.into().value())
+    }
+
+    /// Typed `longitude_deg`, accepting degrees or radians
+    pub fn longitude(self, longitude: impl Into<Degrees>) -> Self {
+        self.longitude_deg(longitude.into().value())
+    }
+
+    /// Typed `altitude_m`, accepting metres or kilometres
+    pub fn altitude(self, altitude: impl Into<Meters>) -> Self {
+        self.altitude_m(altitude.into().value())
+    }
+
+    /// Station elevation mask, overriding the visibility calculator's
+    pub fn min_elevation_deg(mut self, min_elevation_deg: f64) -> Self {
+        self.min_elevation_deg = Some(min_elevation_deg);
+        self
+    }
+
+    /// Typed `min_elevation_deg`, accepting degrees or radians
+    pub fn min_elevation(self, min_elevation: impl Into<Degrees>) -> Self {
+        self.min_elevation_deg(min_elevation.into().value())
+    }
+
+    pub fn mount(mut self, mount: MountType) -> Self {
+        self.mount = Some(mount);
+        self
+    }
+
+    /// IANA time zone for station-local reports
+    pub fn timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    pub fn availability(mut self, availability: StationAvailability) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    pub fn outage(mut self, outage: StationOutage) -> Self {
+        self.availability.outages.push(outage);
+        self
+    }
+
+    pub fn maintenance(mut self, maintenance: RecurringMaintenance) -> Self {
+        self.availability.maintenance.push(maintenance);
+        self
+    }
+
+    /// Validate the position and mask and build the station
+    pub fn build(self) -> Result<GroundStation> {
+        self.validate().for_station(&self.station_id)?;
+
+        let name = self.name.unwrap_or_else(|| self.station_id.clone());
+        Ok(GroundStation {
+            station_id: self.station_id,
+            name,
+            position: StationPosition {
+                latitude_deg: self.latitude_deg.unwrap_or_default(),
+                longitude_deg: wrap_angle_deg(self.longitude_deg.unwrap_or_default()),
+                elevation_m: self.altitude_m,
+            },
+            availability: self.availability,
+            mount: self.mount,
+            min_elevation_deg: self.min_elevation_deg,
+            timezone: self.timezone,
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        let (Some(latitude_deg), Some(longitude_deg)) = (self.latitude_deg, self.longitude_deg)
+        else {
+            return Err(OrbitalMechanicsError::config_error(
+                "Ground station needs a latitude and longitude",
+            ));
+        };
+        if !(-90.0..=90.0).contains(&latitude_deg) {
+            return Err(OrbitalMechanicsError::CoordinateError(format!(
+                "Latitude {:.3}° outside valid range [-90°, +90°]",
+                latitude_deg
+            )));
+        }
+        if !longitude_deg.is_finite() || !self.altitude_m.is_finite() {
+            return Err(OrbitalMechanicsError::CoordinateError(format!(
+                "Longitude {}° and altitude {} m must be finite",
+                longitude_deg, self.altitude_m
+            )));
+        }
+        if let Some(min_elevation_deg) = self.min_elevation_deg {
+            if !(-90.0..90.0).contains(&min_elevation_deg) {
+                return Err(OrbitalMechanicsError::config_error(format!(
+                    "Elevation mask {}° outside [-90, 90)°",
+                    min_elevation_deg
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StationAvailability {
+    /// Whether no outage or maintenance window covers `time`
+    pub fn is_available(&self, time: DateTime<Utc>) -> bool {
+        self.unavailable_intervals(time, time).is_empty()
+    }
+
+    /// Outages and maintenance windows overlapping `[start, end]`, by start time
+    pub fn unavailable_intervals(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<UnavailableInterval> {
+        let mut intervals: Vec<UnavailableInterval> = self
+            .outages
+            .iter()
+            .filter(|o| o.start_time <= end && o.end_time >= start)
+            .map(|o| UnavailableInterval {
+                start_time: o.start_time,
+                end_time: o.end_time,
+                reason: o.reason.clone(),
+            })
+            .collect();
+        for window in &self.maintenance {
+            intervals.extend(window.occurrences(start, end));
+        }
+        intervals.sort_by_key(|i| i.start_time);
+        intervals
+    }
+}
+
+impl RecurringMaintenance {
+    /// Occurrences overlapping `[start, end]`
+    pub fn occurrences(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<UnavailableInterval> {
+        let duration = Duration::milliseconds((self.duration_minutes * 60_000.0) as i64);
+        let mut occurrences = Vec::new();
+
+        // Begin the day before so windows crossing midnight are caught
+        let mut day = (start - duration).date_naive() - Duration::days(1);
+        while day <= end.date_naive() {
+            let applies = match self.recurrence {
+                Recurrence::Daily => true,
+                Recurrence::Weekly(weekday) => day.weekday() == weekday,
+            };
+            let occurrence_start = day.and_time(self.start_time_utc).and_utc();
+            let in_effect = self
+                .effective_from
+                .is_none_or(|from| occurrence_start.date_naive() >= from.date_naive())
+                && self
+                    .effective_until
+                    .is_none_or(|until| occurrence_start.date_naive() <= until.date_naive());
+            let occurrence_end = occurrence_start + duration;
+
+            if applies && in_effect && occurrence_start <= end && occurrence_end >= start {
+                occurrences.push(UnavailableInterval {
+                    start_time: occurrence_start,
+                    end_time: occurrence_end,
+                    reason: self.reason.clone(),
+                });
+            }
+            day += Duration::days(1);
+        }
+        occurrences
+    }
+}
+
+/// Change to the station network, published so dependents can invalidate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StationChange {
+    Added(String),
+    /// Position, availability or other settings changed
+    Updated(String),
+    Removed(String),
+}
+
+impl StationChange {
+    pub fn station_id(&self) -> &str {
+        match self {
+            Self::Added(id) | Self::Updated(id) | Self::Removed(id) => id,
+        }
+    }
+
+    /// Whether results computed for `station_id` before the change are stale
+    pub fn invalidates(&self, station_id: &str) -> bool {
+        !matches!(self, Self::Added(_)) && self.station_id() == station_id
+    }
+
+    /// Drop results made stale by this change
+    pub fn invalidate<T: StationScoped>(&self, results: &mut Vec<T>) {
+        results.retain(|result| !self.invalidates(result.station_id()));
+    }
+}
+
+/// Result computed for one ground station
+pub trait StationScoped {
+    fn station_id(&self) -> &str;
+}
+
 /// Ground station network
 #[derive(Debug, Clone)]
 pub struct GroundStationNetwork {
@@ -40,6 +412,21 @@ impl GroundStationNetwork {
         self.stations.insert(station.station_id.clone(), station);
     }
 
+    /// Replace an existing station, returning the previous definition
+    pub fn update_station(&mut self, station: GroundStation) -> Result<GroundStation> {
+        match self.stations.get_mut(&station.station_id) {
+            Some(existing) => Ok(std::mem::replace(existing, station)),
+            None => Err(OrbitalMechanicsError::GroundStationNotFound(station.station_id)),
+        }
+    }
+
+    /// Remove station from network
+    pub fn remove_station(&mut self, station_id: &str) -> Result<GroundStation> {
+        self.stations
+            .remove(station_id)
+            .ok_or_else(|| OrbitalMechanicsError::GroundStationNotFound(station_id.to_string()))
+    }
+
     /// Get station by ID
     pub fn get_station(&self, station_id: &str) -> Option<&GroundStation> {
         self.stations.get(station_id)
@@ -60,4 +447,101 @@ impl Default for GroundStationNetwork {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::{Kilometers, Radians};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_ground_station_builder() {
+        let station = GroundStation::builder()
+            .station_id("GS-SVALBARD")
+            .position(78.23, 375.39, 500.0)
+            .min_elevation_deg(5.0)
+            .timezone(chrono_tz::Arctic::Longyearbyen)
+            .build()
+            .unwrap();
+
+        assert_eq!(station.name, "GS-SVALBARD");
+        assert!((station.position.longitude_deg - 15.39).abs() < 1e-9);
+        assert_eq!(station.position.elevation_m, 500.0);
+        assert_eq!(station.min_elevation_deg, Some(5.0));
+        assert!(station.availability.outages.is_empty());
+        let midnight = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            station.local_time(midnight).unwrap().to_rfc3339(),
+            "2024-07-01T02:00:00+02:00"
+        );
+
+        let typed = GroundStation::builder()
+            .latitude(Degrees(78.23))
+            .longitude(Radians(0.5))
+            .altitude(Kilometers(0.5))
+            .min_elevation(Degrees(5.0))
+            .build()
+            .unwrap();
+        assert_eq!(typed.position.elevation(), station.position.elevation());
+        assert!((typed.position.longitude() - Radians(0.5).into()).abs() < Degrees(1e-12));
+
+        // Position is required and validated
+        assert!(GroundStation::builder().latitude_deg(10.0).build().is_err());
+        assert!(GroundStation::builder().position(91.0, 0.0, 0.0).build().is_err());
+        assert!(GroundStation::builder().position(0.0, f64::NAN, 0.0).build().is_err());
+        let error = GroundStation::builder()
+            .station_id("GS-MASK")
+            .position(0.0, 0.0, 0.0)
+            .min_elevation_deg(95.0)
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("GS-MASK"));
+    }
+
+    #[test]
+    fn test_availability_calendar() {
+        let availability = StationAvailability {
+            outages: vec![StationOutage {
+                start_time: Utc.with_ymd_and_hms(2024, 3, 20, 12, 0, 0).unwrap(),
+                end_time: Utc.with_ymd_and_hms(2024, 3, 20, 14, 0, 0).unwrap(),
+                reason: "Antenna repair".to_string(),
+            }],
+            maintenance: vec![RecurringMaintenance {
+                // Wednesdays 23:30-00:30 UTC, crossing midnight
+                recurrence: Recurrence::Weekly(Weekday::Wed),
+                start_time_utc: NaiveTime::from_hms_opt(23, 30, 0).unwrap(),
+                duration_minutes: 60.0,
+                reason: "Weekly maintenance".to_string(),
+                effective_from: None,
+                effective_until: Some(Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap()),
+            }],
+        };
+
+        // 2024-03-20 is a Wednesday
+        let at = |day, hour, minute| Utc.with_ymd_and_hms(2024, 3, day, hour, minute, 0).unwrap();
+        assert!(availability.is_available(at(20, 11, 59)));
+        assert!(!availability.is_available(at(20, 13, 0)));
+        assert!(!availability.is_available(at(20, 23, 45)));
+        assert!(!availability.is_available(at(21, 0, 15)));
+        assert!(availability.is_available(at(21, 0, 45)));
+        assert!(availability.is_available(at(21, 23, 45)));
+        assert!(!availability.is_available(at(27, 23, 45)));
+        // Past the effective end
+        assert!(availability.is_available(Utc.with_ymd_and_hms(2024, 4, 3, 23, 45, 0).unwrap()));
+
+        let intervals = availability.unavailable_intervals(at(19, 0, 0), at(28, 0, 0));
+        let reasons: Vec<&str> = intervals.iter().map(|i| i.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec!["Antenna repair", "Weekly maintenance", "Weekly maintenance"]
+        );
+
+        // Loads from station config with the calendar omitted
+        let station: GroundStation = serde_json::from_str(
+            r#"{"station_id": "GS-001", "name": "Test", "position": {"latitude_deg": 0.0, "longitude_deg": 0.0, "elevation_m": 0.0}}"#,
+        )
+        .unwrap();
+        assert!(station.is_available(at(20, 13, 0)));
+    }
+}
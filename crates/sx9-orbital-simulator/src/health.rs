@@ -0,0 +1,227 @@
+//! Per-satellite health scoring
+//!
+//! Combines drift from the nominal slot, downlink success rate and
+//! environmental stress into a 0-1 score. A satellite is flagged as a
+//! suspected anomaly when its score falls below the configured threshold,
+//! and re-armed once it recovers.
+
+use crate::orbit::SatelliteState;
+use crate::phasing;
+use serde::{Deserialize, Serialize};
+
+/// Health scoring weights and thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// Along-track drift from the nominal slot at which the drift score reaches zero
+    pub max_slot_drift_deg: f64,
+    pub drift_weight: f64,
+    pub link_weight: f64,
+    pub environment_weight: f64,
+    /// Score below which an anomaly is suspected (0-1)
+    pub anomaly_threshold: f64,
+}
+
+/// Health summary for one satellite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthStatistics {
+    /// Latest health score (0-1)
+    pub health_score: f64,
+    /// Lowest score seen so far (0-1)
+    pub min_health_score: f64,
+    /// Latest signed along-track drift from the nominal slot; positive is ahead
+    pub slot_drift_deg: f64,
+    /// Share of contact opportunities that were used (0-1)
+    pub link_success_rate: f64,
+    /// Latest environmental stress (0-1)
+    pub environmental_stress: f64,
+    pub anomaly_suspected: bool,
+    /// Number of times the score has crossed below the threshold
+    pub anomalies_flagged: u64,
+}
+
+/// Running health state of one satellite
+#[derive(Debug, Clone)]
+pub struct HealthMonitor {
+    pub config: HealthConfig,
+    statistics: HealthStatistics,
+    link_attempts: u64,
+    link_successes: u64,
+}
+
+impl HealthConfig {
+    /// Combine component scores into a weighted 0-1 health score
+    pub fn score(&self, slot_drift_deg: f64, link_success_rate: f64, stress: f64) -> f64 {
+        let drift_score = if self.max_slot_drift_deg > 0.0 {
+            1.0 - (slot_drift_deg.abs() / self.max_slot_drift_deg).min(1.0)
+        } else {
+            1.0
+        };
+        let total_weight = self.drift_weight + self.link_weight + self.environment_weight;
+        if total_weight <= 0.0 {
+            return 1.0;
+        }
+
+        let weighted = self.drift_weight * drift_score
+            + self.link_weight * link_success_rate.clamp(0.0, 1.0)
+            + self.environment_weight * (1.0 - stress.clamp(0.0, 1.0));
+        (weighted / total_weight).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_slot_drift_deg: 2.0,
+            drift_weight: 0.4,
+            link_weight: 0.4,
+            environment_weight: 0.2,
+            anomaly_threshold: 0.7,
+        }
+    }
+}
+
+impl HealthMonitor {
+    /// Create monitor for a satellite starting in full health
+    pub fn new(config: HealthConfig) -> Self {
+        Self {
+            config,
+            statistics: HealthStatistics {
+                health_score: 1.0,
+                min_health_score: 1.0,
+                slot_drift_deg: 0.0,
+                link_success_rate: 1.0,
+                environmental_stress: 0.0,
+                anomaly_suspected: false,
+                anomalies_flagged: 0,
+            },
+            link_attempts: 0,
+            link_successes: 0,
+        }
+    }
+
+    /// Latest health score (0-1)
+    pub fn health_score(&self) -> f64 {
+        self.statistics.health_score
+    }
+
+    /// Update with one step of observations
+    ///
+    /// `contact` is `None` when no station was in view, otherwise whether the
+    /// contact was used. Returns the reasons if this step newly flags an anomaly.
+    pub fn update(
+        &mut self,
+        slot_drift_deg: f64,
+        contact: Option<bool>,
+        environmental_stress: f64,
+    ) -> Option<Vec<String>> {
+        if let Some(used) = contact {
+            self.link_attempts += 1;
+            if used {
+                self.link_successes += 1;
+            }
+        }
+        let link_success_rate = if self.link_attempts > 0 {
+            self.link_successes as f64 / self.link_attempts as f64
+        } else {
+            1.0
+        };
+
+        let score = self
+            .config
+            .score(slot_drift_deg, link_success_rate, environmental_stress);
+        let stats = &mut self.statistics;
+        stats.health_score = score;
+        stats.min_health_score = stats.min_health_score.min(score);
+        stats.slot_drift_deg = slot_drift_deg;
+        stats.link_success_rate = link_success_rate;
+        stats.environmental_stress = environmental_stress;
+
+        if score >= self.config.anomaly_threshold {
+            stats.anomaly_suspected = false;
+            return None;
+        }
+        if stats.anomaly_suspected {
+            return None;
+        }
+        stats.anomaly_suspected = true;
+        stats.anomalies_flagged += 1;
+
+        let mut reasons = Vec::new();
+        if slot_drift_deg.abs() > self.config.max_slot_drift_deg / 2.0 {
+            reasons.push(format!(
+                "slot drift {:.3} deg (limit {:.3} deg)",
+                slot_drift_deg, self.config.max_slot_drift_deg
+            ));
+        }
+        if link_success_rate < 0.5 {
+            reasons.push(format!(
+                "link success rate {:.0}% over {} contacts",
+                link_success_rate * 100.0,
+                self.link_attempts
+            ));
+        }
+        if environmental_stress > 0.5 {
+            reasons.push(format!("environmental stress {:.2}", environmental_stress));
+        }
+        Some(reasons)
+    }
+
+    pub fn statistics(&self) -> HealthStatistics {
+        self.statistics.clone()
+    }
+}
+
+/// Signed along-track angle of `actual` relative to `nominal`, in degrees
+///
+/// Measured in the nominal orbit plane; positive when `actual` is ahead.
+pub fn along_track_offset_deg(nominal: &SatelliteState, actual: &SatelliteState) -> f64 {
+    phasing::phase_difference_deg(nominal, actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::{OrbitalElements, SatelliteOrbit};
+    use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+    use chrono::Utc;
+
+    #[test]
+    fn test_along_track_offset() {
+        let epoch = Utc::now();
+        let propagator = KeplerianPropagator::new();
+        let orbit = |anomaly: f64| {
+            let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 30.0, 0.0, anomaly).unwrap();
+            SatelliteOrbit::new("SLOT-01".to_string(), "Slot".to_string(), elements, epoch)
+        };
+
+        let nominal = propagator.propagate(&orbit(40.0), epoch).unwrap();
+        let ahead = propagator.propagate(&orbit(41.5), epoch).unwrap();
+        let behind = propagator.propagate(&orbit(39.0), epoch).unwrap();
+
+        assert!((along_track_offset_deg(&nominal, &ahead) - 1.5).abs() < 1e-6);
+        assert!((along_track_offset_deg(&nominal, &behind) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_anomaly_flagged_once_and_rearmed() {
+        let mut monitor = HealthMonitor::new(HealthConfig::default());
+
+        assert!(monitor.update(0.1, Some(true), 0.3).is_none());
+        assert!(monitor.health_score() > 0.9);
+
+        // Drifting out of slot with refused contacts degrades the score
+        let reasons = monitor.update(1.8, Some(false), 0.3).unwrap();
+        assert_eq!(reasons.len(), 1);
+        assert!(reasons[0].starts_with("slot drift"));
+        assert!(monitor.update(1.9, Some(false), 0.3).is_none());
+        assert!(monitor.statistics().anomaly_suspected);
+
+        // Recovery re-arms the flag
+        for _ in 0..10 {
+            monitor.update(0.0, Some(true), 0.0);
+        }
+        assert!(!monitor.statistics().anomaly_suspected);
+        assert!(monitor.update(2.0, None, 1.0).is_some());
+        assert_eq!(monitor.statistics().anomalies_flagged, 2);
+    }
+}
@@ -0,0 +1,259 @@
+//! State interpolation for visualization clients
+//!
+//! Renderers draw at display rate while the propagator runs at a coarse
+//! cadence. `StateInterpolator` keeps the most recent propagated states per
+//! satellite and evaluates a cubic Hermite spline through the bracketing pair,
+//! which matches both position and velocity at each sample so tracks stay
+//! smooth across sample boundaries. Spline time is counted in SI seconds, so
+//! a leap second between samples does not bend the track.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::SatelliteState;
+use crate::time::elapsed_seconds;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+
+/// Default number of states kept per satellite
+pub const DEFAULT_HISTORY_LENGTH: usize = 8;
+
+/// Recent states per satellite with Hermite interpolation between them
+#[derive(Debug, Clone)]
+pub struct StateInterpolator {
+    history_length: usize,
+    histories: HashMap<String, VecDeque<SatelliteState>>,
+}
+
+impl Default for StateInterpolator {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LENGTH)
+    }
+}
+
+impl StateInterpolator {
+    /// Keep up to `history_length` states per satellite (at least two)
+    pub fn new(history_length: usize) -> Self {
+        Self {
+            history_length: history_length.max(2),
+            histories: HashMap::new(),
+        }
+    }
+
+    /// Add a propagated state
+    ///
+    /// States may arrive out of order; a state at an existing timestamp
+    /// replaces it. The oldest states are dropped beyond the history length.
+    pub fn push(&mut self, state: SatelliteState) {
+        let history = self
+            .histories
+            .entry(state.satellite_id.clone())
+            .or_default();
+        match history.binary_search_by_key(&state.timestamp, |s| s.timestamp) {
+            Ok(index) => history[index] = state,
+            Err(index) => history.insert(index, state),
+        }
+        while history.len() > self.history_length {
+            history.pop_front();
+        }
+    }
+
+    /// Add one state per satellite, typically from a single propagation tick
+    pub fn extend(&mut self, states: impl IntoIterator<Item = SatelliteState>) {
+        for state in states {
+            self.push(state);
+        }
+    }
+
+    /// Earliest and latest buffered timestamps for a satellite
+    pub fn time_span(&self, satellite_id: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let history = self.histories.get(satellite_id)?;
+        Some((history.front()?.timestamp, history.back()?.timestamp))
+    }
+
+    /// Satellites with buffered states
+    pub fn satellite_ids(&self) -> impl Iterator<Item = &str> {
+        self.histories.keys().map(String::as_str)
+    }
+
+    /// Drop the history of a satellite, e.g. after a maneuver or removal
+    pub fn remove_satellite(&mut self, satellite_id: &str) {
+        self.histories.remove(satellite_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.histories.clear();
+    }
+
+    /// Interpolated state at `time`
+    ///
+    /// `time` must lie within the buffered span; an exact sample is returned
+    /// unchanged.
+    pub fn interpolate(&self, satellite_id: &str, time: DateTime<Utc>) -> Result<SatelliteState> {
+        self.interpolate_inner(satellite_id, time)
+            .for_satellite(satellite_id)
+            .at_epoch(time)
+    }
+
+    /// Interpolated states at `time` for every satellite whose span covers it
+    pub fn interpolate_all(&self, time: DateTime<Utc>) -> Vec<SatelliteState> {
+        let mut states: Vec<SatelliteState> = self
+            .histories
+            .keys()
+            .filter_map(|satellite_id| self.interpolate_inner(satellite_id, time).ok())
+            .collect();
+        states.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
+        states
+    }
+
+    fn interpolate_inner(&self, satellite_id: &str, time: DateTime<Utc>) -> Result<SatelliteState> {
+        let history = self
+            .histories
+            .get(satellite_id)
+            .filter(|history| !history.is_empty())
+            .ok_or_else(|| OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()))?;
+
+        let after = match history.binary_search_by_key(&time, |s| s.timestamp) {
+            Ok(index) => return Ok(history[index].clone()),
+            Err(index) => index,
+        };
+        if after == 0 || after == history.len() {
+            return Err(OrbitalMechanicsError::propagation_error(format!(
+                "{} is outside the buffered span {} to {}",
+                time,
+                history[0].timestamp,
+                history[history.len() - 1].timestamp
+            )));
+        }
+
+        let (start, end) = (&history[after - 1], &history[after]);
+        let interval = elapsed_seconds(start.timestamp, end.timestamp);
+        let s = elapsed_seconds(start.timestamp, time) / interval;
+        let (position, velocity) = hermite(
+            (start.position_eci, start.velocity_eci),
+            (end.position_eci, end.velocity_eci),
+            interval,
+            s,
+        );
+        Ok(SatelliteState::new(
+            satellite_id.to_string(),
+            time,
+            position,
+            velocity,
+        ))
+    }
+}
+
+/// Cubic Hermite position and its derivative at fraction `s` of `interval`
+fn hermite(
+    (p0, v0): ([f64; 3], [f64; 3]),
+    (p1, v1): ([f64; 3], [f64; 3]),
+    interval: f64,
+    s: f64,
+) -> ([f64; 3], [f64; 3]) {
+    let (s2, s3) = (s * s, s * s * s);
+    let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+    let h10 = s3 - 2.0 * s2 + s;
+    let h01 = -2.0 * s3 + 3.0 * s2;
+    let h11 = s3 - s2;
+    // Basis derivatives with respect to s
+    let d00 = 6.0 * s2 - 6.0 * s;
+    let d10 = 3.0 * s2 - 4.0 * s + 1.0;
+    let d01 = -6.0 * s2 + 6.0 * s;
+    let d11 = 3.0 * s2 - 2.0 * s;
+
+    let position = [0, 1, 2]
+        .map(|i| h00 * p0[i] + h10 * interval * v0[i] + h01 * p1[i] + h11 * interval * v1[i]);
+    let velocity =
+        [0, 1, 2].map(|i| (d00 * p0[i] + d01 * p1[i]) / interval + d10 * v0[i] + d11 * v1[i]);
+    (position, velocity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate, TimeZone};
+
+    fn circular_state(time: DateTime<Utc>, epoch: DateTime<Utc>) -> SatelliteState {
+        let radius = 14378.0;
+        let speed = (crate::constants::EARTH_MU / radius).sqrt();
+        let angle = elapsed_seconds(epoch, time) * speed / radius;
+        SatelliteState::new(
+            "MEO-01".to_string(),
+            time,
+            [radius * angle.cos(), radius * angle.sin(), 0.0],
+            [-speed * angle.sin(), speed * angle.cos(), 0.0],
+        )
+    }
+
+    #[test]
+    fn test_hermite_tracks_circular_orbit() {
+        let epoch = Utc::now();
+        let mut interpolator = StateInterpolator::new(4);
+        // Out of order, with more samples than the history keeps
+        for seconds in [60, 0, 20, 40, 80] {
+            interpolator.push(circular_state(epoch + Duration::seconds(seconds), epoch));
+        }
+        assert_eq!(
+            interpolator.time_span("MEO-01"),
+            Some((epoch + Duration::seconds(20), epoch + Duration::seconds(80)))
+        );
+
+        // A 60 fps frame between 20 s samples stays within a few metres
+        let frame = epoch + Duration::milliseconds(51_667);
+        let interpolated = interpolator.interpolate("MEO-01", frame).unwrap();
+        let truth = circular_state(frame, epoch);
+        for i in 0..3 {
+            assert!((interpolated.position_eci[i] - truth.position_eci[i]).abs() < 0.005);
+            assert!((interpolated.velocity_eci[i] - truth.velocity_eci[i]).abs() < 1e-5);
+        }
+        assert_eq!(interpolator.interpolate_all(frame).len(), 1);
+
+        assert!(interpolator
+            .interpolate("MEO-01", epoch + Duration::seconds(10))
+            .is_err());
+        assert!(matches!(
+            interpolator
+                .interpolate("GEO-01", frame)
+                .unwrap_err()
+                .root_cause(),
+            OrbitalMechanicsError::SatelliteNotFound(_)
+        ));
+    }
+
+    #[test]
+    fn test_no_jump_across_leap_second() {
+        // Minute samples labelled in UTC; the minute ending 2017-01-01T00:00
+        // holds a leap second, so those samples are 61 SI seconds apart
+        let epoch = Utc.with_ymd_and_hms(2016, 12, 31, 23, 57, 0).unwrap();
+        let mut interpolator = StateInterpolator::new(8);
+        for minute in 0..5 {
+            interpolator.push(circular_state(epoch + Duration::minutes(minute), epoch));
+        }
+        let at = |second, millis| {
+            NaiveDate::from_ymd_opt(2016, 12, 31)
+                .unwrap()
+                .and_hms_milli_opt(23, 59, second, millis)
+                .unwrap()
+                .and_utc()
+        };
+        let midnight = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+
+        let leap = at(59, 1_500);
+        for time in [at(30, 0), leap, midnight + Duration::milliseconds(500)] {
+            let interpolated = interpolator.interpolate("MEO-01", time).unwrap();
+            let truth = circular_state(time, epoch);
+            for i in 0..3 {
+                assert!((interpolated.position_eci[i] - truth.position_eci[i]).abs() < 0.005);
+            }
+        }
+
+        // 23:59:60.999 to 00:00:00.000 moves one millisecond along track
+        let last = interpolator.interpolate("MEO-01", at(59, 1_999)).unwrap();
+        let first = interpolator.interpolate("MEO-01", midnight).unwrap();
+        let step_km = (0..3)
+            .map(|i| (first.position_eci[i] - last.position_eci[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let speed = (crate::constants::EARTH_MU / 14378.0).sqrt();
+        assert!((step_km - speed * 1e-3).abs() < 1e-4, "{} km", step_km);
+    }
+}
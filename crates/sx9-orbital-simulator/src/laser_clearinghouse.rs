@@ -0,0 +1,363 @@
+//! Laser clearing house (LCH) predictive avoidance requests
+//!
+//! Condenses planned optical uplink pointing schedules into time-windowed
+//! azimuth/elevation boxes per station, so deconfliction requests can be
+//! generated and submitted without hand editing.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::export::StrftimeFormat;
+use crate::pointing::{PointingSample, PointingSchedule};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Output format for avoidance request files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AvoidanceFileFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Avoidance request generation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvoidanceRequestConfig {
+    /// Organization submitting the request
+    pub originator: String,
+    /// Maximum length of each requested window
+    pub window_seconds: f64,
+    /// Margin added on every side of the azimuth/elevation box
+    pub angular_padding_deg: f64,
+    /// chrono format string for timestamps
+    pub timestamp_format: String,
+    pub format: AvoidanceFileFormat,
+}
+
+/// Sky region swept by one uplink over a time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvoidanceWindow {
+    pub station_id: String,
+    pub satellite_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Azimuth range runs clockwise from `azimuth_from_deg` to
+    /// `azimuth_to_deg`; `from > to` when it crosses north
+    pub azimuth_from_deg: f64,
+    pub azimuth_to_deg: f64,
+    pub min_elevation_deg: f64,
+    pub max_elevation_deg: f64,
+}
+
+/// Predictive avoidance request covering one or more stations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvoidanceRequest {
+    pub originator: String,
+    pub generated_at: DateTime<Utc>,
+    /// Windows ordered by station, then start time
+    pub windows: Vec<AvoidanceWindow>,
+    #[serde(skip, default = "default_timestamp_format")]
+    timestamp_format: StrftimeFormat,
+    #[serde(skip)]
+    format: AvoidanceFileFormat,
+}
+
+impl Default for AvoidanceRequestConfig {
+    fn default() -> Self {
+        Self {
+            originator: "SX9".to_string(),
+            window_seconds: 60.0,
+            angular_padding_deg: 0.5,
+            timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            format: AvoidanceFileFormat::Csv,
+        }
+    }
+}
+
+impl AvoidanceWindow {
+    /// Width of the azimuth range in degrees
+    pub fn azimuth_span_deg(&self) -> f64 {
+        (self.azimuth_to_deg - self.azimuth_from_deg).rem_euclid(360.0)
+    }
+
+    /// Box covering consecutive pointing samples, padded on every side
+    fn from_samples(
+        schedule: &PointingSchedule,
+        samples: &[PointingSample],
+        padding_deg: f64,
+    ) -> Self {
+        // Unwrap azimuth along the track so ranges crossing north stay contiguous
+        let mut unwrapped = samples[0].azimuth_deg;
+        let (mut min_azimuth, mut max_azimuth) = (unwrapped, unwrapped);
+        for pair in samples.windows(2) {
+            let delta =
+                (pair[1].azimuth_deg - pair[0].azimuth_deg + 180.0).rem_euclid(360.0) - 180.0;
+            unwrapped += delta;
+            min_azimuth = min_azimuth.min(unwrapped);
+            max_azimuth = max_azimuth.max(unwrapped);
+        }
+        let (azimuth_from_deg, azimuth_to_deg) =
+            if max_azimuth - min_azimuth + 2.0 * padding_deg >= 360.0 {
+                (0.0, 360.0)
+            } else {
+                (
+                    (min_azimuth - padding_deg).rem_euclid(360.0),
+                    (max_azimuth + padding_deg).rem_euclid(360.0),
+                )
+            };
+
+        let elevations = samples.iter().map(|s| s.elevation_deg);
+        let min_elevation = elevations.clone().fold(f64::INFINITY, f64::min);
+        let max_elevation = elevations.fold(f64::NEG_INFINITY, f64::max);
+
+        Self {
+            station_id: schedule.station_id.clone(),
+            satellite_id: schedule.satellite_id.clone(),
+            start_time: samples[0].timestamp,
+            end_time: samples[samples.len() - 1].timestamp,
+            azimuth_from_deg,
+            azimuth_to_deg,
+            min_elevation_deg: (min_elevation - padding_deg).max(0.0),
+            max_elevation_deg: (max_elevation + padding_deg).min(90.0),
+        }
+    }
+}
+
+impl AvoidanceRequest {
+    /// Build the request from planned uplink pointing schedules
+    ///
+    /// Each schedule is split into windows of at most `window_seconds`;
+    /// consecutive windows share their boundary sample so the boxes cover the
+    /// whole track.
+    pub fn from_schedules(
+        schedules: &[PointingSchedule],
+        config: &AvoidanceRequestConfig,
+    ) -> Result<Self> {
+        if !config.window_seconds.is_finite() || config.window_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Avoidance window length must be positive, got {} s",
+                config.window_seconds
+            )));
+        }
+        if !config.angular_padding_deg.is_finite() || config.angular_padding_deg < 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Avoidance angular padding must be non-negative, got {}°",
+                config.angular_padding_deg
+            )));
+        }
+        let timestamp_format = StrftimeFormat::new(config.timestamp_format.as_str())?;
+
+        let window_length = Duration::milliseconds((config.window_seconds * 1000.0) as i64);
+        let mut windows = Vec::new();
+        for schedule in schedules {
+            let mut first = 0;
+            while first < schedule.samples.len() {
+                let window_end = schedule.samples[first].timestamp + window_length;
+                let last = schedule.samples[first..]
+                    .iter()
+                    .rposition(|s| s.timestamp <= window_end)
+                    .map_or(first, |offset| first + offset);
+                windows.push(AvoidanceWindow::from_samples(
+                    schedule,
+                    &schedule.samples[first..=last],
+                    config.angular_padding_deg,
+                ));
+                if last + 1 >= schedule.samples.len() {
+                    break;
+                }
+                first = last.max(first + 1);
+            }
+        }
+        windows.sort_by(|a, b| {
+            a.station_id
+                .cmp(&b.station_id)
+                .then(a.start_time.cmp(&b.start_time))
+        });
+
+        Ok(Self {
+            originator: config.originator.clone(),
+            generated_at: Utc::now(),
+            windows,
+            timestamp_format,
+            format: config.format,
+        })
+    }
+
+    /// Station IDs with at least one window, in order
+    pub fn station_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.windows.iter().map(|w| w.station_id.as_str()).collect();
+        ids.dedup();
+        ids
+    }
+
+    /// Request restricted to a single station
+    pub fn for_station(&self, station_id: &str) -> Self {
+        Self {
+            windows: self
+                .windows
+                .iter()
+                .filter(|w| w.station_id == station_id)
+                .cloned()
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Render request as CSV (one row per window)
+    pub fn to_csv(&self) -> Result<String> {
+        let mut csv = String::from(
+            "originator,station_id,satellite_id,start_utc,end_utc,\
+             azimuth_from_deg,azimuth_to_deg,min_elevation_deg,max_elevation_deg\n",
+        );
+        let timestamp_format = self.timestamp_format.as_str();
+        for window in &self.windows {
+            // Writing to a String only fails if a timestamp does not render
+            writeln!(
+                csv,
+                "{},{},{},{},{},{:.3},{:.3},{:.3},{:.3}",
+                self.originator,
+                window.station_id,
+                window.satellite_id,
+                window.start_time.format(timestamp_format),
+                window.end_time.format(timestamp_format),
+                window.azimuth_from_deg,
+                window.azimuth_to_deg,
+                window.min_elevation_deg,
+                window.max_elevation_deg
+            )
+            .map_err(|_| {
+                OrbitalMechanicsError::config_error(format!(
+                    "Timestamp format '{}' cannot render {}",
+                    timestamp_format, window.start_time
+                ))
+            })?;
+        }
+        Ok(csv)
+    }
+
+    /// Render request as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render request in the configured format
+    pub fn render(&self) -> Result<String> {
+        match self.format {
+            AvoidanceFileFormat::Csv => self.to_csv(),
+            AvoidanceFileFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Write the whole request to one file in the configured format
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.render()?).for_path(path)
+    }
+
+    /// Write one file per station into `directory`, returning the paths written
+    ///
+    /// Files are named `<station_id>_avoidance.csv` or `.json`.
+    pub fn write_per_station<P: AsRef<Path>>(&self, directory: P) -> Result<Vec<PathBuf>> {
+        let extension = match self.format {
+            AvoidanceFileFormat::Csv => "csv",
+            AvoidanceFileFormat::Json => "json",
+        };
+        self.station_ids()
+            .into_iter()
+            .map(|station_id| {
+                let path = directory
+                    .as_ref()
+                    .join(format!("{}_avoidance.{}", station_id, extension));
+                self.for_station(station_id).write(&path)?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+
+fn default_timestamp_format() -> StrftimeFormat {
+    StrftimeFormat::new(DEFAULT_TIMESTAMP_FORMAT).expect("default timestamp format parses")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(station_id: &str, azimuths: &[f64]) -> PointingSchedule {
+        let start = Utc::now();
+        let samples: Vec<PointingSample> = azimuths
+            .iter()
+            .enumerate()
+            .map(|(i, azimuth_deg)| PointingSample {
+                timestamp: start + Duration::seconds(10 * i as i64),
+                azimuth_deg: *azimuth_deg,
+                elevation_deg: 30.0 + i as f64,
+                range_km: None,
+                range_rate_km_per_s: None,
+                doppler_shift_hz: None,
+                axes: None,
+            })
+            .collect();
+        PointingSchedule {
+            satellite_id: "SAT-01".to_string(),
+            station_id: station_id.to_string(),
+            start_time: samples[0].timestamp,
+            end_time: samples[samples.len() - 1].timestamp,
+            cadence_seconds: 10.0,
+            mount: None,
+            samples,
+        }
+    }
+
+    #[test]
+    fn test_avoidance_windows() {
+        // 80 s track crossing north, split into 30 s windows
+        let azimuths = [350.0, 352.0, 354.0, 356.0, 358.0, 0.0, 2.0, 4.0, 6.0];
+        let config = AvoidanceRequestConfig {
+            window_seconds: 30.0,
+            ..AvoidanceRequestConfig::default()
+        };
+        let request = AvoidanceRequest::from_schedules(
+            &[schedule("GS-B", &azimuths), schedule("GS-A", &[90.0, 91.0])],
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(request.station_ids(), vec!["GS-A", "GS-B"]);
+        let crossing = request.for_station("GS-B");
+        assert_eq!(crossing.windows.len(), 3);
+        assert_eq!(crossing.windows[0].end_time, crossing.windows[1].start_time);
+        assert_eq!(
+            crossing.windows[2].end_time,
+            request.windows.last().unwrap().end_time
+        );
+
+        // Second window spans 356° -> 2°, padded by 0.5°
+        let wrapped = &crossing.windows[1];
+        assert!((wrapped.azimuth_from_deg - 355.5).abs() < 1e-9);
+        assert!((wrapped.azimuth_to_deg - 2.5).abs() < 1e-9);
+        assert!((wrapped.azimuth_span_deg() - 7.0).abs() < 1e-9);
+        assert!((wrapped.min_elevation_deg - 32.5).abs() < 1e-9);
+        assert!((wrapped.max_elevation_deg - 36.5).abs() < 1e-9);
+
+        let csv = request.render().unwrap();
+        assert_eq!(csv.lines().count(), request.windows.len() + 1);
+        assert!(csv.lines().nth(1).unwrap().starts_with("SX9,GS-A,SAT-01,"));
+        let json = request.to_json().unwrap();
+        assert!(json.contains("\"azimuth_from_deg\""));
+
+        let invalid = AvoidanceRequestConfig {
+            window_seconds: 0.0,
+            ..AvoidanceRequestConfig::default()
+        };
+        assert!(AvoidanceRequest::from_schedules(&[], &invalid).is_err());
+        let invalid = AvoidanceRequestConfig {
+            timestamp_format: "%Y-%m-%dT%Q".to_string(),
+            ..AvoidanceRequestConfig::default()
+        };
+        assert!(AvoidanceRequest::from_schedules(&[], &invalid).is_err());
+    }
+}
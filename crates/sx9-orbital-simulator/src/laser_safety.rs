@@ -0,0 +1,211 @@
+//! Terrestrial laser safety zones
+//!
+//! A `LaserSafetyZone` is a no-lase polygon on the ground (an airport, a
+//! populated corridor) protected up to a ceiling altitude. An uplink beam
+//! endangers the zone when its ground track passes over the polygon while
+//! the beam is still below the ceiling, which only happens close to the
+//! station or at low elevation. Beam altitude includes Earth curvature;
+//! polygons are projected onto the station's local tangent plane, which is
+//! accurate for the few hundred kilometres a beam stays below any ceiling.
+
+use crate::constants::EARTH_RADIUS_KM;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::ground_station::StationPosition;
+use serde::{Deserialize, Serialize};
+
+/// Default protected ceiling: 10,000 ft, the FAA laser critical flight zone
+pub const DEFAULT_ZONE_CEILING_M: f64 = 3048.0;
+
+/// What a zone protects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LaserSafetyZoneKind {
+    Airport,
+    PopulatedCorridor,
+    Other,
+}
+
+/// No-lase polygon protected up to a ceiling altitude
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaserSafetyZone {
+    pub zone_id: String,
+    pub name: String,
+    pub kind: LaserSafetyZoneKind,
+    /// Boundary as `[latitude_deg, longitude_deg]`, closed implicitly
+    pub vertices: Vec<[f64; 2]>,
+    /// Protected airspace ceiling above mean sea level
+    #[serde(default = "default_ceiling_m")]
+    pub ceiling_m: f64,
+}
+
+impl LaserSafetyZone {
+    /// Zone with the default ceiling, named after its ID
+    pub fn new(
+        zone_id: impl Into<String>,
+        kind: LaserSafetyZoneKind,
+        vertices: Vec<[f64; 2]>,
+    ) -> Result<Self> {
+        let zone_id = zone_id.into();
+        if vertices.len() < 3 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Laser safety zone {} needs at least 3 vertices, got {}",
+                zone_id,
+                vertices.len()
+            )));
+        }
+        if let Some([latitude, _]) = vertices.iter().find(|[lat, _]| lat.abs() > 90.0) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Laser safety zone {} has vertex latitude {}° outside [-90°, +90°]",
+                zone_id, latitude
+            )));
+        }
+        Ok(Self {
+            name: zone_id.clone(),
+            zone_id,
+            kind,
+            vertices,
+            ceiling_m: DEFAULT_ZONE_CEILING_M,
+        })
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_ceiling_m(mut self, ceiling_m: f64) -> Self {
+        self.ceiling_m = ceiling_m;
+        self
+    }
+
+    /// Ground distance from the station at which an uplink enters the zone
+    ///
+    /// `None` when the beam clears the ceiling before reaching the polygon.
+    /// A station inside the zone gives `Some(0.0)`.
+    pub fn beam_entry_km(
+        &self,
+        station: &StationPosition,
+        azimuth_deg: f64,
+        elevation_deg: f64,
+    ) -> Option<f64> {
+        let height_m = self.ceiling_m - station.elevation_m;
+        if height_m <= 0.0 {
+            return None;
+        }
+        let reach_km = beam_ground_range_km(elevation_deg, height_m);
+        let azimuth = azimuth_deg.to_radians();
+        let end = [reach_km * azimuth.sin(), reach_km * azimuth.cos()];
+
+        let polygon: Vec<[f64; 2]> = self
+            .vertices
+            .iter()
+            .map(|&vertex| local_east_north_km(station, vertex))
+            .collect();
+        if contains([0.0, 0.0], &polygon) {
+            return Some(0.0);
+        }
+
+        (0..polygon.len())
+            .filter_map(|i| {
+                let edge = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                segment_intersection(end, edge)
+            })
+            .map(|t| t * reach_km)
+            .min_by(f64::total_cmp)
+    }
+}
+
+/// Ground distance over which a beam at `elevation_deg` climbs `height_m`
+///
+/// Solves `d·tan(e) + d²/2R = h`, the beam altitude over a spherical Earth.
+pub fn beam_ground_range_km(elevation_deg: f64, height_m: f64) -> f64 {
+    let height_km = height_m.max(0.0) / 1000.0;
+    let slope = elevation_deg.to_radians().tan();
+    // Conjugate form stays accurate as the slope grows toward zenith
+    2.0 * height_km / (slope + (slope * slope + 2.0 * height_km / EARTH_RADIUS_KM).sqrt())
+}
+
+fn default_ceiling_m() -> f64 {
+    DEFAULT_ZONE_CEILING_M
+}
+
+/// Equirectangular projection of `[lat, lon]` about the station
+pub(crate) fn local_east_north_km(
+    station: &StationPosition,
+    [latitude, longitude]: [f64; 2],
+) -> [f64; 2] {
+    let delta_longitude = (longitude - station.longitude_deg + 180.0).rem_euclid(360.0) - 180.0;
+    [
+        EARTH_RADIUS_KM * station.latitude_deg.to_radians().cos() * delta_longitude.to_radians(),
+        EARTH_RADIUS_KM * (latitude - station.latitude_deg).to_radians(),
+    ]
+}
+
+/// Even-odd point-in-polygon test
+pub(crate) fn contains(point: [f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let mut inside = false;
+    let mut previous = polygon[polygon.len() - 1];
+    for &vertex in polygon {
+        if (vertex[1] > point[1]) != (previous[1] > point[1]) {
+            let crossing_x = vertex[0]
+                + (point[1] - vertex[1]) * (previous[0] - vertex[0]) / (previous[1] - vertex[1]);
+            if point[0] < crossing_x {
+                inside = !inside;
+            }
+        }
+        previous = vertex;
+    }
+    inside
+}
+
+/// Fraction along the origin-to-`end` segment where it meets `edge`
+fn segment_intersection(end: [f64; 2], (a, b): ([f64; 2], [f64; 2])) -> Option<f64> {
+    let cross = |u: [f64; 2], v: [f64; 2]| u[0] * v[1] - u[1] * v[0];
+    let edge = [b[0] - a[0], b[1] - a[1]];
+    let denominator = cross(end, edge);
+    if denominator.abs() < f64::EPSILON {
+        return None;
+    }
+    let t = cross(a, edge) / denominator;
+    let u = cross(a, end) / denominator;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then_some(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_elevation_beam_crosses_zone() {
+        let station = StationPosition {
+            latitude_deg: 40.0,
+            longitude_deg: -105.0,
+            elevation_m: 1600.0,
+        };
+        // Airport box about 10-16 km north of the station
+        let airport = LaserSafetyZone::new(
+            "KAPT",
+            LaserSafetyZoneKind::Airport,
+            vec![
+                [40.09, -105.03],
+                [40.09, -104.97],
+                [40.145, -104.97],
+                [40.145, -105.03],
+            ],
+        )
+        .unwrap();
+
+        // The beam stays below 3048 m for ~48 km at 1.5° but ~0.8 km at 60°
+        let entry = airport.beam_entry_km(&station, 0.0, 1.5).unwrap();
+        assert!((entry - 10.0).abs() < 0.1, "entry at {} km", entry);
+        assert!(airport.beam_entry_km(&station, 0.0, 60.0).is_none());
+        assert!(airport.beam_entry_km(&station, 180.0, 1.5).is_none());
+
+        let inside = StationPosition {
+            latitude_deg: 40.1,
+            ..station
+        };
+        assert_eq!(airport.beam_entry_km(&inside, 90.0, 45.0), Some(0.0));
+
+        assert!(LaserSafetyZone::new("BAD", LaserSafetyZoneKind::Other, vec![[0.0, 0.0]]).is_err());
+    }
+}
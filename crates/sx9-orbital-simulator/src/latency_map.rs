@@ -0,0 +1,280 @@
+//! Global one-way latency maps for relay service
+//!
+//! Samples a latitude/longitude grid and records the lowest one-way latency
+//! from a gateway through the constellation to each cell center, for export
+//! as CSV or GeoJSON.
+
+use crate::coverage_grid::grid_cell;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::ground_station::{GroundStation, StationPosition};
+use crate::routing::RelayNetwork;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Latency at one grid cell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyCell {
+    /// Cell center latitude in degrees
+    pub latitude_deg: f64,
+    /// Cell center longitude in degrees
+    pub longitude_deg: f64,
+    /// One-way latency from the gateway in ms; `None` if unreachable
+    pub latency_ms: Option<f64>,
+    /// Satellite providing the downlink to the cell
+    pub serving_satellite_id: Option<String>,
+}
+
+/// Latency grid from one gateway at one epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyMap {
+    pub gateway_id: String,
+    pub epoch: DateTime<Utc>,
+    pub resolution_deg: f64,
+    /// Cells ordered by latitude, then longitude, starting south-west
+    pub cells: Vec<LatencyCell>,
+}
+
+impl LatencyMap {
+    /// Compute the latency grid for `gateway` over a relay network snapshot
+    pub fn generate(
+        network: &RelayNetwork,
+        gateway: &GroundStation,
+        resolution_deg: f64,
+    ) -> Result<Self> {
+        if !resolution_deg.is_finite() || resolution_deg <= 0.0 || resolution_deg > 90.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Latency map resolution must be in (0, 90] degrees, got {}",
+                resolution_deg
+            )));
+        }
+
+        let satellite_delays_ms = network.satellite_delays_ms(gateway);
+        let rows = (180.0 / resolution_deg).ceil() as usize;
+        let columns = (360.0 / resolution_deg).ceil() as usize;
+        let mut cells = Vec::with_capacity(rows * columns);
+
+        for row in 0..rows {
+            let latitude_deg = (-90.0 + (row as f64 + 0.5) * resolution_deg).min(90.0);
+            for column in 0..columns {
+                let longitude_deg = (-180.0 + (column as f64 + 0.5) * resolution_deg).min(180.0);
+                let point = GroundStation {
+                    station_id: format!("GRID-{}-{}", row, column),
+                    name: String::new(),
+                    position: StationPosition {
+                        latitude_deg,
+                        longitude_deg,
+                        elevation_m: 0.0,
+                    },
+                    availability: Default::default(),
+                    mount: None,
+                    min_elevation_deg: None,
+                    timezone: None,
+                };
+
+                let served = network.ground_delay_ms(&satellite_delays_ms, &point);
+                cells.push(LatencyCell {
+                    latitude_deg,
+                    longitude_deg,
+                    latency_ms: served.map(|(_, delay)| delay),
+                    serving_satellite_id: served
+                        .map(|(i, _)| network.satellites()[i].satellite_id.clone()),
+                });
+            }
+        }
+
+        Ok(Self {
+            gateway_id: gateway.station_id.clone(),
+            epoch: network.time,
+            resolution_deg,
+            cells,
+        })
+    }
+
+    /// Share of cells reachable from the gateway (0-100%, by cell count)
+    pub fn coverage_percent(&self) -> f64 {
+        if self.cells.is_empty() {
+            return 0.0;
+        }
+        let reachable = self.cells.iter().filter(|c| c.latency_ms.is_some()).count();
+        reachable as f64 / self.cells.len() as f64 * 100.0
+    }
+
+    /// Cell containing a location
+    pub fn cell_at(&self, latitude_deg: f64, longitude_deg: f64) -> Option<&LatencyCell> {
+        let columns = (360.0 / self.resolution_deg).ceil() as usize;
+        let (row, column) = grid_cell(self.resolution_deg, latitude_deg, longitude_deg)?;
+        self.cells.get(row * columns + column)
+    }
+
+    /// CSV with one row per cell; unreachable cells have empty latency
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// CSV with one row per cell, in the units and format of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let mut csv = format!(
+            "latitude_{0},longitude_{0},latency_ms,serving_satellite_id\n",
+            options.angle_unit.suffix()
+        );
+        for cell in &self.cells {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                options.angle(cell.latitude_deg, None),
+                options.angle(cell.longitude_deg, None),
+                cell.latency_ms
+                    .map(|l| options.decimal(l, Some(3)))
+                    .unwrap_or_default(),
+                cell.serving_satellite_id
+                    .as_deref()
+                    .map(|id| options.satellite_name(id))
+                    .unwrap_or_default()
+            );
+        }
+        csv
+    }
+
+    /// GeoJSON FeatureCollection of cell polygons with latency properties
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let half = self.resolution_deg / 2.0;
+        let features: Vec<serde_json::Value> = self
+            .cells
+            .iter()
+            .map(|cell| {
+                let south = (cell.latitude_deg - half).max(-90.0);
+                let north = (cell.latitude_deg + half).min(90.0);
+                let west = (cell.longitude_deg - half).max(-180.0);
+                let east = (cell.longitude_deg + half).min(180.0);
+                json!({
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [[
+                            [west, south], [east, south], [east, north], [west, north], [west, south]
+                        ]]
+                    },
+                    "properties": {
+                        "latency_ms": cell.latency_ms,
+                        "serving_satellite_id": cell.serving_satellite_id,
+                    }
+                })
+            })
+            .collect();
+
+        json!({
+            "type": "FeatureCollection",
+            "properties": {
+                "gateway_id": self.gateway_id,
+                "epoch": self.epoch.to_rfc3339(),
+                "resolution_deg": self.resolution_deg,
+            },
+            "features": features,
+        })
+    }
+
+    /// Write the grid as CSV
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write the grid as CSV in the units and format of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv_with(options)).for_path(path)
+    }
+
+    /// Write the grid as GeoJSON
+    pub fn write_geojson<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let content = serde_json::to_string(&self.to_geojson())?;
+        fs::write(path, content).for_path(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::{OrbitalElements, SatelliteOrbit};
+    use crate::propagator::KeplerianPropagator;
+    use crate::routing::RoutingConfig;
+
+    fn equatorial_network() -> RelayNetwork {
+        let epoch = Utc::now();
+        let satellites: Vec<SatelliteOrbit> = (0..6)
+            .map(|i| {
+                let anomaly = i as f64 * 60.0;
+                let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, anomaly).unwrap();
+                SatelliteOrbit::new(format!("SAT-{}", i), format!("Sat {}", i), elements, epoch)
+            })
+            .collect();
+
+        RelayNetwork::build(
+            RoutingConfig::default(),
+            &satellites,
+            epoch,
+            &KeplerianPropagator::new(),
+        )
+        .unwrap()
+    }
+
+    fn gateway() -> GroundStation {
+        GroundStation {
+            station_id: "GW-001".to_string(),
+            name: "Gateway".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        }
+    }
+
+    #[test]
+    fn test_latency_grid() {
+        let map = LatencyMap::generate(&equatorial_network(), &gateway(), 10.0).unwrap();
+        assert_eq!(map.cells.len(), 18 * 36);
+
+        // Next to the gateway: up and down through the overhead satellite
+        let near = map.cell_at(1.0, 1.0).unwrap();
+        assert_eq!(near.serving_satellite_id.as_deref(), Some("SAT-0"));
+        let latency = near.latency_ms.unwrap();
+        assert!(latency > 53.0 && latency < 60.0);
+
+        // Far side of the ring is reachable over ISLs, but costs more
+        let far = map.cell_at(1.0, 179.0).unwrap();
+        assert!(far.latency_ms.unwrap() > latency);
+
+        // An equatorial ring cannot serve the poles
+        assert!(map.cell_at(85.0, 0.0).unwrap().latency_ms.is_none());
+        assert!(map.coverage_percent() > 0.0 && map.coverage_percent() < 100.0);
+    }
+
+    #[test]
+    fn test_csv_and_geojson_export() {
+        let map = LatencyMap::generate(&equatorial_network(), &gateway(), 30.0).unwrap();
+
+        let csv = map.to_csv();
+        assert_eq!(csv.lines().count(), map.cells.len() + 1);
+        assert!(csv.starts_with("latitude_deg,longitude_deg,latency_ms,serving_satellite_id"));
+
+        let geojson = map.to_geojson();
+        assert_eq!(geojson["type"], "FeatureCollection");
+        assert_eq!(
+            geojson["features"].as_array().unwrap().len(),
+            map.cells.len()
+        );
+        assert_eq!(geojson["properties"]["gateway_id"], "GW-001");
+
+        assert!(LatencyMap::generate(&equatorial_network(), &gateway(), 0.0).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Launch window and injection analysis
+//!
+//! Finds the daily times at which a launch site rotates under a target
+//! orbital plane (including J2 nodal regression), with the launch azimuth
+//! and the elements at orbit injection, for constellation buildout planning.
+
+use crate::constants::*;
+use crate::ephemeris;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::{OrbitalElements, SatelliteOrbit};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Launch site location and allowed azimuth range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchSite {
+    pub site_id: String,
+    pub name: String,
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    /// Allowed launch azimuth range, clockwise from `min_azimuth_deg` (range safety)
+    pub min_azimuth_deg: f64,
+    pub max_azimuth_deg: f64,
+}
+
+/// Ascent profile assumptions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchConfig {
+    /// Liftoff to orbit injection
+    pub ascent_duration_seconds: f64,
+    /// Ground-track arc flown between liftoff and injection
+    pub insertion_downrange_deg: f64,
+    /// Plane RAAN error the vehicle can absorb, which sets the window width
+    pub raan_tolerance_deg: f64,
+}
+
+/// Direction the site crosses the target plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaneCrossing {
+    /// Site passes under the northbound half of the orbit
+    Ascending,
+    /// Site passes under the southbound half of the orbit
+    Descending,
+}
+
+/// One in-plane launch opportunity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchWindow {
+    pub site_id: String,
+    pub crossing: PlaneCrossing,
+    pub open_time: DateTime<Utc>,
+    /// Site exactly in the target plane
+    pub optimal_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+    /// Launch azimuth at the optimal time, clockwise from north
+    pub launch_azimuth_deg: f64,
+    pub injection_time: DateTime<Utc>,
+    /// Osculating elements at injection, with epoch `injection_time`
+    pub injection_elements: OrbitalElements,
+}
+
+/// Launch window search
+#[derive(Debug, Clone)]
+pub struct LaunchPlanner {
+    pub config: LaunchConfig,
+}
+
+impl LaunchSite {
+    /// Create site with no azimuth restriction
+    pub fn new(site_id: String, name: String, latitude_deg: f64, longitude_deg: f64) -> Self {
+        Self {
+            site_id,
+            name,
+            latitude_deg,
+            longitude_deg,
+            min_azimuth_deg: 0.0,
+            max_azimuth_deg: 360.0,
+        }
+    }
+
+    /// Restrict launch azimuths, e.g. to fly over open water
+    pub fn with_azimuth_range(mut self, min_azimuth_deg: f64, max_azimuth_deg: f64) -> Self {
+        self.min_azimuth_deg = min_azimuth_deg;
+        self.max_azimuth_deg = max_azimuth_deg;
+        self
+    }
+
+    /// Whether an azimuth lies in the allowed range
+    pub fn allows_azimuth(&self, azimuth_deg: f64) -> bool {
+        let span = self.max_azimuth_deg - self.min_azimuth_deg;
+        if span >= 360.0 {
+            return true;
+        }
+        (azimuth_deg - self.min_azimuth_deg).rem_euclid(360.0) <= span.rem_euclid(360.0)
+    }
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            ascent_duration_seconds: 600.0,
+            insertion_downrange_deg: 20.0,
+            raan_tolerance_deg: 0.5,
+        }
+    }
+}
+
+impl LaunchPlanner {
+    /// Create planner with default ascent profile
+    pub fn new() -> Self {
+        Self::with_config(LaunchConfig::default())
+    }
+
+    /// Create planner with custom ascent profile
+    pub fn with_config(config: LaunchConfig) -> Self {
+        Self { config }
+    }
+
+    /// In-plane launch windows into the plane of `target` over `days` days
+    ///
+    /// The target's RAAN is regressed under J2 to each window. Windows whose
+    /// azimuth the site does not allow are omitted.
+    pub fn daily_windows(
+        &self,
+        site: &LaunchSite,
+        target: &SatelliteOrbit,
+        start_time: DateTime<Utc>,
+        days: u32,
+    ) -> Result<Vec<LaunchWindow>> {
+        let inclination = target.elements.inclination_deg * DEG_TO_RAD;
+        let latitude = site.latitude_deg * DEG_TO_RAD;
+        let sin_u = latitude.sin() / inclination.sin();
+        if !sin_u.is_finite() || sin_u.abs() > 1.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Inclination {:.2} deg is not reachable by direct ascent from {} at latitude {:.2} deg",
+                target.elements.inclination_deg, site.site_id, site.latitude_deg
+            )));
+        }
+
+        let nodal_rate_deg_per_sec =
+            target.elements.nodal_precession_rate_deg_per_day() / DAYS_TO_SECONDS;
+        let relative_rate_deg_per_sec = EARTH_ROTATION_RATE * RAD_TO_DEG - nodal_rate_deg_per_sec;
+        let repeat_seconds = 360.0 / relative_rate_deg_per_sec;
+        let half_width_seconds = self.config.raan_tolerance_deg / relative_rate_deg_per_sec;
+        let end_time = start_time + Duration::days(days as i64);
+
+        // Site argument of latitude in the target plane for each crossing
+        let u_ascending = sin_u.asin();
+        let crossings = [
+            (PlaneCrossing::Ascending, u_ascending),
+            (PlaneCrossing::Descending, PI - u_ascending),
+        ];
+
+        let mut windows = Vec::new();
+        for (crossing, u) in crossings {
+            // Longitude of the site from the ascending node, measured in the equator
+            let node_offset_deg = (inclination.cos() * u.sin()).atan2(u.cos()) * RAD_TO_DEG;
+            let azimuth_deg = launch_azimuth_deg(inclination, latitude, crossing);
+            if !site.allows_azimuth(azimuth_deg) {
+                continue;
+            }
+
+            let misalignment_deg = |time: DateTime<Utc>| {
+                let raan_deg = target.elements.raan_deg
+                    + nodal_rate_deg_per_sec * seconds_between(target.epoch, time);
+                let local_sidereal_deg =
+                    ephemeris::gmst_rad(time) * RAD_TO_DEG + site.longitude_deg;
+                raan_deg + node_offset_deg - local_sidereal_deg
+            };
+
+            let mut optimal_time = start_time
+                + seconds(
+                    misalignment_deg(start_time).rem_euclid(360.0) / relative_rate_deg_per_sec,
+                );
+            while optimal_time < end_time {
+                let correction_deg =
+                    (misalignment_deg(optimal_time) + 180.0).rem_euclid(360.0) - 180.0;
+                optimal_time += seconds(correction_deg / relative_rate_deg_per_sec);
+                if optimal_time < start_time {
+                    optimal_time += seconds(repeat_seconds);
+                    continue;
+                }
+                if optimal_time >= end_time {
+                    break;
+                }
+
+                let injection_time = optimal_time + seconds(self.config.ascent_duration_seconds);
+                windows.push(LaunchWindow {
+                    site_id: site.site_id.clone(),
+                    crossing,
+                    open_time: optimal_time - seconds(half_width_seconds),
+                    optimal_time,
+                    close_time: optimal_time + seconds(half_width_seconds),
+                    launch_azimuth_deg: azimuth_deg,
+                    injection_time,
+                    injection_elements: self.injection_elements(target, u, injection_time)?,
+                });
+                optimal_time += seconds(repeat_seconds);
+            }
+        }
+
+        windows.sort_by_key(|w| w.optimal_time);
+        Ok(windows)
+    }
+
+    /// Target-plane elements at injection, downrange of the site crossing
+    fn injection_elements(
+        &self,
+        target: &SatelliteOrbit,
+        site_argument_of_latitude: f64,
+        injection_time: DateTime<Utc>,
+    ) -> Result<OrbitalElements> {
+        let elements = &target.elements;
+        let raan_deg = elements.raan_deg
+            + elements.nodal_precession_rate_deg_per_day() / DAYS_TO_SECONDS
+                * seconds_between(target.epoch, injection_time);
+        let argument_of_latitude =
+            site_argument_of_latitude + self.config.insertion_downrange_deg * DEG_TO_RAD;
+        let true_anomaly = argument_of_latitude - elements.argument_of_perigee_deg * DEG_TO_RAD;
+
+        OrbitalElements::new(
+            elements.semi_major_axis_km,
+            elements.eccentricity,
+            elements.inclination_deg,
+            raan_deg.rem_euclid(360.0),
+            elements.argument_of_perigee_deg,
+            mean_anomaly_from_true(true_anomaly, elements.eccentricity) * RAD_TO_DEG,
+        )
+    }
+}
+
+impl Default for LaunchPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Inertial launch azimuth into a plane, clockwise from north (0-360°)
+///
+/// Ignores the Earth-rotation velocity correction, which shifts the flown
+/// azimuth by a few degrees but not the window timing.
+fn launch_azimuth_deg(inclination: f64, latitude: f64, crossing: PlaneCrossing) -> f64 {
+    let sin_azimuth = (inclination.cos() / latitude.cos()).clamp(-1.0, 1.0);
+    let northbound_deg = sin_azimuth.asin() * RAD_TO_DEG;
+    match crossing {
+        PlaneCrossing::Ascending => northbound_deg.rem_euclid(360.0),
+        PlaneCrossing::Descending => (180.0 - northbound_deg).rem_euclid(360.0),
+    }
+}
+
+fn mean_anomaly_from_true(true_anomaly: f64, eccentricity: f64) -> f64 {
+    let eccentric_anomaly = 2.0
+        * (((1.0 - eccentricity) / (1.0 + eccentricity)).sqrt() * (true_anomaly / 2.0).tan())
+            .atan();
+    (eccentric_anomaly - eccentricity * eccentric_anomaly.sin()).rem_euclid(TWO_PI)
+}
+
+fn seconds(value: f64) -> Duration {
+    Duration::milliseconds((value * 1000.0).round() as i64)
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_milliseconds() as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn cape() -> LaunchSite {
+        LaunchSite::new(
+            "LC-39A".to_string(),
+            "Kennedy Space Center".to_string(),
+            28.6,
+            -80.6,
+        )
+    }
+
+    fn target_plane(inclination_deg: f64) -> SatelliteOrbit {
+        let elements =
+            OrbitalElements::new(14378.0, 0.0, inclination_deg, 100.0, 0.0, 0.0).unwrap();
+        SatelliteOrbit::new(
+            "TARGET".to_string(),
+            "Target".to_string(),
+            elements,
+            Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_site_in_plane_at_optimal_time() {
+        let target = target_plane(55.0);
+        let start = Utc.with_ymd_and_hms(2024, 3, 21, 0, 0, 0).unwrap();
+        let windows = LaunchPlanner::new()
+            .daily_windows(&cape(), &target, start, 3)
+            .unwrap();
+
+        // Two crossings per sidereal day
+        assert!(windows.len() >= 6 && windows.len() <= 7);
+        assert!(windows
+            .windows(2)
+            .all(|w| w[0].optimal_time < w[1].optimal_time));
+
+        for window in &windows {
+            let raan = window.injection_elements.raan_deg * DEG_TO_RAD;
+            let inclination = 55.0 * DEG_TO_RAD;
+            let normal = [
+                inclination.sin() * raan.sin(),
+                -inclination.sin() * raan.cos(),
+                inclination.cos(),
+            ];
+            let latitude = 28.6 * DEG_TO_RAD;
+            let sidereal = ephemeris::gmst_rad(window.optimal_time) - 80.6 * DEG_TO_RAD;
+            let site = [
+                latitude.cos() * sidereal.cos(),
+                latitude.cos() * sidereal.sin(),
+                latitude.sin(),
+            ];
+            let out_of_plane = site[0] * normal[0] + site[1] * normal[1] + site[2] * normal[2];
+            assert!(out_of_plane.abs() < 1e-3);
+
+            assert!(
+                window.open_time < window.optimal_time && window.optimal_time < window.close_time
+            );
+            assert!((window.injection_elements.inclination_deg - 55.0).abs() < 1e-9);
+            match window.crossing {
+                PlaneCrossing::Ascending => assert!(window.launch_azimuth_deg < 90.0),
+                PlaneCrossing::Descending => assert!(window.launch_azimuth_deg > 90.0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_azimuth_limits_and_unreachable_planes() {
+        let target = target_plane(55.0);
+        let start = Utc.with_ymd_and_hms(2024, 3, 21, 0, 0, 0).unwrap();
+
+        // Northeast corridor only: descending opportunities are dropped
+        let site = cape().with_azimuth_range(35.0, 120.0);
+        let windows = LaunchPlanner::new()
+            .daily_windows(&site, &target, start, 2)
+            .unwrap();
+        assert!(!windows.is_empty());
+        assert!(windows
+            .iter()
+            .all(|w| w.crossing == PlaneCrossing::Ascending));
+
+        // Direct ascent cannot reach an inclination below the site latitude
+        assert!(LaunchPlanner::new()
+            .daily_windows(&cape(), &target_plane(20.0), start, 1)
+            .is_err());
+    }
+}
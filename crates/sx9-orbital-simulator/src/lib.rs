@@ -9,6 +9,9 @@
 //! - Ground station visibility analysis
 //! - Free-space optical (FSO) link analysis
 //! - Custom MEO satellite positioning
+//!
+//! Diagnostics are emitted through `tracing` under the targets documented in
+//! [`trace_targets`].
 
 // Engineered Solution: Integration with Foundation Core
 // Use shared types from the Foundation Orbital crate to prevent split-brain
@@ -22,43 +25,251 @@ pub use sx9_foundation_orbital::{
 };
 
 // Local modules that extend the foundation
+pub mod acquisition;
+pub mod aircraft;
+#[cfg(feature = "arrow-export")]
+pub mod arrow_export;
+pub mod atmosphere;
+pub mod beam_footprint;
+pub mod beta_angle;
+#[cfg(feature = "cdn-geospatial")]
+pub mod cdn_sync;
 pub mod config;
+pub mod conflict_timeline;
+pub mod contact_plan;
+pub mod coordination;
+pub mod covariance;
+#[cfg(feature = "gpu")]
+mod coverage_gpu;
+pub mod coverage_grid;
+pub mod data_volume;
+pub mod disposal;
+pub mod earth_data;
+pub mod ebpf_key;
+pub mod element_csv;
+pub mod ephemeris;
 pub mod error;
+pub mod export;
+pub mod fault_injection;
+pub mod force_model;
 pub mod fso_analysis;
+pub mod fso_fade;
+pub mod health;
+pub mod interpolation;
+pub mod launch;
+pub mod laser_clearinghouse;
+pub mod laser_safety;
+pub mod latency_map;
+#[cfg(feature = "manifold")]
+pub mod manifold_links;
+pub mod measurements;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod mount;
+pub mod occultation;
+pub mod pass_audit;
+pub mod pass_profile;
+pub mod pass_report;
+pub mod pass_scoring;
+pub mod phasing;
+pub mod playback;
+pub mod pointing;
+pub mod power;
+pub mod progress;
+pub use sx9_orbital_core as propagation_core;
 pub mod propagator;
+pub mod relative_motion;
+pub mod repeat_pass;
+pub mod repeat_track;
+#[cfg(feature = "rest_api")]
+pub mod rest_api;
+#[cfg(feature = "results-db")]
+pub mod results_db;
+pub mod routing;
+pub mod rune_telemetry;
 pub mod satellite_simulator;
+pub mod scenario;
+pub mod self_check;
+pub mod sky_geometry;
+pub mod sla_report;
+pub mod slot_drift;
+pub mod sparing;
+pub mod star_calibration;
+pub mod star_tracker;
+pub mod time;
+pub mod tle;
+pub mod trace_targets;
+pub mod trade_study;
+pub mod units;
 pub mod visibility;
+pub mod visibility_catalog;
+pub mod weather;
+#[cfg(feature = "ws_server")]
+pub mod ws_server;
 
 // Re-exports
+pub use acquisition::AcquisitionModel;
+pub use aircraft::{AircraftConjunction, AircraftReport, AircraftTracker, DeconflictionConfig};
+#[cfg(feature = "online")]
+pub use aircraft::{run_adsb_feed, AdsbFeed};
+#[cfg(feature = "arrow-export")]
+pub use arrow_export::{
+    read_states_parquet, write_states_parquet, write_visibility_parquet, StateParquetWriter,
+};
+pub use atmosphere::{AtmosphereModel, DragParameters, SpaceWeather};
+pub use beam_footprint::{BeamFootprint, BeamGroundTrace, DownlinkBeam, ZoneIllumination};
+pub use beta_angle::{analyze_beta_angle, BetaAngleReport, BetaAngleSample, EclipseSeason};
+#[cfg(feature = "cdn-geospatial")]
+pub use cdn_sync::{refreshed_orbital_object, CdnOrbitalSync};
 pub use config::{
     load_constellation_config, save_constellation_config, ConstellationConfig as Config,
 };
-pub use config::{ConstellationConfig, ConstellationType};
+pub use config::migration::{ConfigMigrator, MigrationReport};
+pub use config::{
+    load_constellation_config_with_migration, ConfigFormat, ConstellationConfig, ConstellationType,
+};
+pub use conflict_timeline::{
+    ConflictReason, ConflictSpan, ConflictTimeline, StationLane, TimelineContact,
+};
 pub use constellation::Constellation; // Keep local constellation logic as it differs
-pub use coordinates::{CoordinateSystem, GeodeticPosition, Position3D};
-pub use error::{OrbitalMechanicsError, Result};
-pub use error::{OrbitalMechanicsError, Result};
-pub use fso_analysis::{FsoAnalyzer, FsoLinkQuality};
-pub use propagator::{OrbitalPropagator, PropagatorType};
+pub use constellation::{PlaneId, SlotAssignment, SlotId};
+pub use contact_plan::{BundleDelivery, BundleHop, ContactPlan, PlannedContact};
+pub use coordination::{
+    CoordinationAnalyzer, CoordinationConfig, CoordinationReport, InterferenceConflict, RfBand,
+    Transmission,
+};
+pub use covariance::{EphemerisRecord, StateCovariance, StateEphemeris, UncertaintyEllipsoid};
+pub use coverage_grid::{CoverageBackend, CoverageGrid, CoverageGridConfig};
+pub use coordinates::{
+    split_ground_track, unwrap_angles_deg, wrap_angle_deg, CoordinateSystem, EarthModel,
+    GeodeticPosition, Position3D, Topocentric, TopocentricFrame,
+};
+pub use data_volume::{DataBuffer, DataVolumeConfig, DataVolumeStatistics};
+pub use disposal::{
+    DisposalAnalyzer, DisposalCompliance, DisposalRules, DisposalStrategy, OperationalShell,
+};
+pub use earth_data::{
+    earth_data, install_earth_data, use_cached_earth_data, DataSource, EarthData, EopRecord,
+};
+pub use error::{ErrorContext, ErrorKind, OrbitalMechanicsError, Result, ResultExt};
+pub use export::{AngleUnit, DistanceUnit, ExportOptions, StrftimeFormat, TimestampFormat};
+pub use fault_injection::{Fault, FaultKind, FaultSchedule};
+pub use force_model::{
+    AtmosphericDrag, Body, ForceModel, ForceModelSet, SolarRadiationPressure, ThirdBody, TwoBody,
+    ZonalHarmonics,
+};
+pub use fso_analysis::{
+    AdaptiveOptics, BandLinkBudget, FsoAnalyzer, FsoLinkQuality, GeoArcAvoidance, GeoArcViolation,
+    OpticalBand, OpticalTerminal, SkyBackground, ThermalKeepOut, WavelengthComparison,
+};
+pub use fso_fade::{fade_events, FadeDistribution, FadeEvent, FadeModel};
+pub use ground_station::{
+    Recurrence, RecurringMaintenance, StationAvailability, StationOutage, UnavailableInterval,
+};
+pub use ground_station::{GroundStationBuilder, StationChange, StationScoped};
+pub use health::{HealthConfig, HealthMonitor, HealthStatistics};
+pub use interpolation::StateInterpolator;
+pub use laser_clearinghouse::{
+    AvoidanceFileFormat, AvoidanceRequest, AvoidanceRequestConfig, AvoidanceWindow,
+};
+pub use laser_safety::{LaserSafetyZone, LaserSafetyZoneKind};
+pub use latency_map::{LatencyCell, LatencyMap};
+pub use launch::{LaunchConfig, LaunchPlanner, LaunchSite, LaunchWindow, PlaneCrossing};
+#[cfg(feature = "manifold")]
+pub use manifold_links::{publish_link_health, station_destination, LinkHealth, LinkHealthConfig};
+pub use measurements::{
+    MeasurementConfig, MeasurementGenerator, NoiseModel, Observation, ObservationType, StationNoise,
+};
+pub use mount::{AxisLimits, AzElLimits, MountAxes, MountType};
+pub use occultation::{
+    constellation_occultations, occultation_events, OccultationConfig, OccultationEvent,
+    OccultationKind, TangentPoint,
+};
+pub use orbit::SatelliteOrbitBuilder;
+pub use pass_audit::{FilterReason, PassAuditLog, RejectedPass};
+pub use pass_profile::{PassProfile, PassProfileSample};
+pub use pass_report::{PassReport, PassReportRow};
+pub use pass_scoring::{rank_passes, schedule_contacts, DefaultPassScorer, PassScorer, ScoredPass};
+pub use pass_scoring::{
+    schedule_contacts_with_constraints, ConstrainedSchedule, ConstraintViolation,
+    ConstraintViolationKind, PayloadConstraints,
+};
+pub use phasing::{
+    argument_of_latitude_deg, phase_difference_deg, phase_drift_rate_deg_per_day, plan_phase_change,
+    plan_slot_swap, PhasingPlan,
+};
+pub use playback::{PlaybackPropagator, RecordedEphemeris};
+pub use pointing::{PointingSample, PointingSchedule, PointingScheduleGenerator};
+pub use power::{PowerConfig, PowerStatistics, PowerSystem, SolarArrayOrientation};
+pub use progress::{Cancellable, CancellationToken, NoProgress, ProgressSink};
+pub use propagator::{Integrator, NumericalPropagator};
 pub use propagator::{OrbitalPropagator, PropagatorType};
-pub use satellite_simulator::{
-    LiveSatellite, MeoEnvironmentalConditions, ObstructionWarning, SatelliteSimulator,
-    SatelliteUnicodePacket, SimulationStatistics,
+pub use relative_motion::{ClohessyWiltshire, RelativeState, RephasingManeuver};
+pub use repeat_pass::{PassGeometry, RepeatPass, RepeatPassFinder};
+pub use repeat_track::{analyze_ground_track_repeat, RepeatCycle, RepeatTrackAnalysis};
+#[cfg(feature = "rest_api")]
+pub use rest_api::{
+    ApiError, CoverageQuery, ErrorBody, LinkBudgetQuery, PassQuery, RateLimiter, RestApiConfig,
+};
+#[cfg(feature = "results-db")]
+pub use results_db::{AnalysisRun, ResultsDb, RunComparison, RunRecord};
+pub use routing::{
+    RelayNetwork, RelayRouter, Route, RouteHop, RouteNode, RoutingConfig, RoutingWindow,
 };
+pub use rune_telemetry::{DecodedStateDelta, PositionErrorClass, StateDelta};
 pub use satellite_simulator::{
     LiveSatellite, MeoEnvironmentalConditions, ObstructionWarning, SatelliteSimulator,
-    SatelliteUnicodePacket, SimulationStatistics,
+    SatelliteUnicodePacket, SimulationEvent, SimulationStatistics,
+};
+pub use satellite_simulator::{SimulationCommand, SimulationControl};
+pub use scenario::{
+    load_scenario, run_scenario, Scenario, ScenarioAnalysis, ScenarioConstellation, ScenarioReport,
+};
+pub use self_check::{self_check, SelfCheck, SelfCheckReport};
+pub use sky_geometry::{
+    poor_geometry_intervals, sky_geometry_series, GeometryLimits, PoorGeometryInterval, SkyGeometry,
+};
+pub use sla_report::{DailyAvailability, LinkAvailability, OutageCause, SlaReport};
+pub use slot_drift::{
+    DriftAlarmLevel, SatelliteSlotDrift, SlotDriftMonitor, SlotDriftReport, SlotDriftThresholds,
+};
+pub use sparing::{compare_sparing_strategies, SparingOutcome, SparingScenario};
+pub use star_calibration::{
+    bright_star, bright_stars, star_calibration_windows, CalibrationTerminal, CatalogStar,
+    StarCalibrationWindow,
+};
+pub use star_tracker::{BlindingSource, StarTracker, StarTrackerViolation};
+pub use tle::{parse_tles, Tle};
+pub use trade_study::{
+    CandidateSite, CostModel, SiteContacts, SiteSelection, SiteSelectionMode, TradePoint, TradeStudy,
+    TradeStudyReport,
+};
+pub use units::{Degrees, Kilometers, Meters, Radians, Seconds};
+pub use visibility::{
+    LightingConstraint, UnusableInterval, VisibilityCache, VisibilityCalculator, VisibilityRefresh,
+    VisibilityWindow,
+};
+pub use visibility::{MountConflict, MountConflictKind};
+pub use visibility_catalog::VisibilityCatalog;
+pub use weather::{
+    ConstantWeather, FileWeatherProvider, WeatherDecision, WeatherLimits, WeatherObservation,
+    WeatherProvider,
 };
-pub use visibility::{VisibilityCalculator, VisibilityWindow};
+#[cfg(feature = "ws_server")]
+pub use ws_server::{DashboardFrame, DashboardTopic, SubscriptionRequest, WsServerConfig};
+
+use progress::{Phase, Span};
 
 /// Main orbital mechanics engine with live satellite simulation
 pub struct OrbitalMechanicsEngine {
     constellation: Constellation,
     ground_stations: GroundStationNetwork,
-    propagator: Box<dyn OrbitalPropagator>,
+    propagator: Box<dyn OrbitalPropagator + Send + Sync>,
     fso_analyzer: FsoAnalyzer,
+    visibility_calculator: VisibilityCalculator,
     /// OPERATIONAL: Live satellite simulator with Unicode packet generation
     satellite_simulator: Option<SatelliteSimulator>,
+    station_changes: tokio::sync::broadcast::Sender<StationChange>,
 }
 
 impl OrbitalMechanicsEngine {
@@ -70,20 +281,65 @@ impl OrbitalMechanicsEngine {
 
     /// Create orbital mechanics engine with custom configuration
     pub fn with_config(config: Config) -> Result<Self> {
+        let _span = tracing::info_span!(
+            target: trace_targets::ENGINE,
+            "engine_init",
+            constellation = %config.name
+        )
+        .entered();
+        let started = std::time::Instant::now();
+
         let constellation = Constellation::from_config(&config)?;
         let ground_stations = GroundStationNetwork::new();
-        let propagator = propagator::create_propagator(config.analysis_config.propagator_type)?;
-        let fso_analyzer = FsoAnalyzer::new();
+        let propagator = propagator::create_propagator_with_forces(
+            config.analysis_config.propagator_type,
+            &config.analysis_config.force_models,
+        )?;
+        let mut fso_analyzer =
+            FsoAnalyzer::new().with_thermal_keep_outs(config.fso_config.thermal_keep_outs.clone());
+        fso_analyzer.geo_arc_avoidance = config.fso_config.geo_arc_avoidance.clone();
+        fso_analyzer.adaptive_optics = config.fso_config.adaptive_optics.clone();
+        fso_analyzer.sky_background = config.fso_config.sky_background.clone();
+        fso_analyzer.earth_model = config.analysis_config.earth_model;
+        let visibility_calculator = VisibilityCalculator::new()
+            .with_edge_accuracy(Some(config.analysis_config.visibility_edge_accuracy_seconds))
+            .with_earth_model(config.analysis_config.earth_model);
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            satellites = constellation.satellite_count(),
+            propagator = propagator.name(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Orbital mechanics engine initialised"
+        );
 
         Ok(Self {
             constellation,
             ground_stations,
             propagator,
             fso_analyzer,
+            visibility_calculator,
             satellite_simulator: None,
+            station_changes: tokio::sync::broadcast::channel(
+                satellite_simulator::EVENT_CHANNEL_CAPACITY,
+            )
+            .0,
         })
     }
 
+    /// Create engine with every configured satellite's elements referenced to `epoch`
+    ///
+    /// `with_config` places the elements at the current time, so results
+    /// change from run to run; this pins them.
+    pub fn with_config_at_epoch(
+        config: Config,
+        epoch: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Self> {
+        let mut engine = Self::with_config(config)?;
+        engine.constellation.set_element_epoch(epoch);
+        Ok(engine)
+    }
+
     /// Load configuration from file
     pub fn from_config_file(path: &str) -> Result<Self> {
         let config = load_constellation_config(path)?;
@@ -100,14 +356,86 @@ impl OrbitalMechanicsEngine {
         &self.ground_stations
     }
 
+    /// Visibility calculator used for window searches (elevation mask, lighting, edge accuracy)
+    pub fn visibility_calculator_mut(&mut self) -> &mut VisibilityCalculator {
+        &mut self.visibility_calculator
+    }
+
     /// Add custom satellite to constellation
     pub fn add_satellite(&mut self, orbit: SatelliteOrbit) -> Result<()> {
         self.constellation.add_satellite(orbit)
     }
 
+    /// Add satellites to a named group for group-based queries
+    pub fn tag_satellites<I, S>(&mut self, group: &str, satellite_ids: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.constellation.tag(group, satellite_ids)
+    }
+
+    /// Name a satellite under a naming scheme; see `Constellation::add_alias`
+    pub fn add_satellite_alias(
+        &mut self,
+        satellite_id: &str,
+        scheme: &str,
+        alias: &str,
+    ) -> Result<()> {
+        self.constellation.add_alias(satellite_id, scheme, alias)
+    }
+
     /// Add ground station to network
+    ///
+    /// Re-adding an existing station ID replaces it and is published as an update.
     pub fn add_ground_station(&mut self, station: GroundStation) {
+        let change = if self.ground_stations.get_station(&station.station_id).is_some() {
+            StationChange::Updated(station.station_id.clone())
+        } else {
+            StationChange::Added(station.station_id.clone())
+        };
         self.ground_stations.add_station(station);
+        self.publish_station_change(change);
+    }
+
+    /// Replace an existing ground station's definition
+    pub fn update_ground_station(&mut self, station: GroundStation) -> Result<()> {
+        let station_id = station.station_id.clone();
+        self.ground_stations
+            .update_station(station)
+            .for_station(&station_id)?;
+        self.publish_station_change(StationChange::Updated(station_id));
+        Ok(())
+    }
+
+    /// Remove a ground station, returning its definition
+    pub fn remove_ground_station(&mut self, station_id: &str) -> Result<GroundStation> {
+        let station = self
+            .ground_stations
+            .remove_station(station_id)
+            .for_station(station_id)?;
+        self.publish_station_change(StationChange::Removed(station_id.to_string()));
+        Ok(station)
+    }
+
+    /// Subscribe to station network changes published from now on
+    ///
+    /// Holders of visibility caches, contact schedules, pointing schedules or
+    /// FSO link analyses apply each change with
+    /// `VisibilityCache::apply_station_change` or `StationChange::invalidate`.
+    pub fn subscribe_station_changes(&self) -> tokio::sync::broadcast::Receiver<StationChange> {
+        self.station_changes.subscribe()
+    }
+
+    fn publish_station_change(&self, change: StationChange) {
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            change = ?change,
+            stations = self.ground_stations.station_count(),
+            "Ground station network changed"
+        );
+        // No subscribers is not an error
+        let _ = self.station_changes.send(change);
     }
 
     /// Calculate satellite position at given time
@@ -120,7 +448,19 @@ impl OrbitalMechanicsEngine {
             OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()),
         )?;
 
-        self.propagator.propagate(orbit, time)
+        self.propagator
+            .propagate(orbit, time)
+            .for_satellite(satellite_id)
+            .at_epoch(time)
+    }
+
+    /// States of a satellite group at `time`, ordered by satellite ID
+    pub fn states_for_group(
+        &self,
+        group: &str,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<SatelliteState>> {
+        self.constellation.states_for_group(group, time, &*self.propagator)
     }
 
     /// Calculate visibility windows for all satellites and ground stations
@@ -129,25 +469,771 @@ impl OrbitalMechanicsEngine {
         start_time: chrono::DateTime<chrono::Utc>,
         duration_hours: f64,
     ) -> Result<Vec<VisibilityWindow>> {
+        let _span = tracing::info_span!(
+            target: trace_targets::ENGINE,
+            "calculate_all_visibility_windows",
+            satellites = self.constellation.satellite_count(),
+            stations = self.ground_stations.station_count(),
+            duration_hours
+        )
+        .entered();
+        let started = std::time::Instant::now();
+        let all_windows = self.visibility_windows_with_progress(
+            start_time,
+            duration_hours,
+            &NoProgress,
+        )?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            windows_found = all_windows.len(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Visibility analysis complete"
+        );
+
+        Ok(all_windows)
+    }
+
+    /// Visibility windows of every satellite and station pair, reported per pair
+    fn visibility_windows_with_progress(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        progress: &dyn ProgressSink,
+    ) -> Result<Vec<VisibilityWindow>> {
+        let pairs = self.constellation.satellite_count() * self.ground_stations.station_count();
+        let phase = Phase::start(progress, "visibility", pairs)?;
         let mut all_windows = Vec::new();
-        let calculator = VisibilityCalculator::new();
+        let mut done = 0;
 
         for satellite in self.constellation.satellites() {
             for station in self.ground_stations.stations() {
-                let windows = calculator.calculate_windows(
+                all_windows.extend(self.pair_visibility_windows(
                     satellite,
                     station,
                     start_time,
                     duration_hours,
-                    &*self.propagator,
-                )?;
-                all_windows.extend(windows);
+                )?);
+                done += 1;
+                phase.step(done)?;
             }
         }
+        phase.finish();
 
         Ok(all_windows)
     }
 
+    /// Visibility windows of a satellite group over all ground stations
+    pub fn windows_for_group(
+        &self,
+        group: &str,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+    ) -> Result<Vec<VisibilityWindow>> {
+        let started = std::time::Instant::now();
+        let mut windows = Vec::new();
+        for satellite in self.constellation.satellites_in_group(group)? {
+            for station in self.ground_stations.stations() {
+                windows.extend(self.pair_visibility_windows(
+                    satellite,
+                    station,
+                    start_time,
+                    duration_hours,
+                )?);
+            }
+        }
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            group,
+            windows_found = windows.len(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Group visibility analysis complete"
+        );
+        Ok(windows)
+    }
+
+    /// Candidate passes filtered out of the period, with the filter that removed each
+    ///
+    /// Covers passes the visibility search drops and found windows with no
+    /// usable time left. Rescans every pair from horizon to horizon, so it
+    /// costs about as much again as `calculate_all_visibility_windows`.
+    pub fn audit_rejected_passes(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+    ) -> Result<PassAuditLog> {
+        let started = std::time::Instant::now();
+        let mut rejected = Vec::new();
+        for satellite in self.constellation.satellites() {
+            for station in self.ground_stations.stations() {
+                rejected.extend(pass_audit::rejected_passes(
+                    &self.visibility_calculator,
+                    satellite,
+                    station,
+                    start_time,
+                    duration_hours,
+                    &*self.propagator,
+                )?);
+                let windows =
+                    self.pair_visibility_windows(satellite, station, start_time, duration_hours)?;
+                rejected.extend(windows.iter().filter_map(pass_audit::unusable_window));
+            }
+        }
+        let log = PassAuditLog::new(rejected);
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            rejected = log.len(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Pass audit complete"
+        );
+        Ok(log)
+    }
+
+    /// Passes over the period with AOS/LOS in UTC and station-local time
+    pub fn pass_report(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+    ) -> Result<PassReport> {
+        let windows = self.calculate_all_visibility_windows(start_time, duration_hours)?;
+        Ok(PassReport::new(&windows, &self.ground_stations))
+    }
+
+    /// Later pass of a satellite over a station with geometry most like the
+    /// pass in progress at `reference_time`, within `search_days`
+    ///
+    /// Uses the visibility calculator's elevation mask; see `repeat_pass`.
+    pub fn find_repeat_pass(
+        &self,
+        satellite_id: &str,
+        station_id: &str,
+        reference_time: chrono::DateTime<chrono::Utc>,
+        search_days: f64,
+    ) -> Result<Option<RepeatPass>> {
+        let satellite = self.constellation.get_satellite(satellite_id).ok_or(
+            OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()),
+        )?;
+        let station = self.ground_stations.get_station(station_id).ok_or(
+            OrbitalMechanicsError::GroundStationNotFound(station_id.to_string()),
+        )?;
+        let mask_deg = station
+            .min_elevation_deg
+            .unwrap_or(self.visibility_calculator.min_elevation_deg);
+        let finder = RepeatPassFinder::new(search_days).with_min_elevation(mask_deg);
+
+        let reference = finder
+            .pass_at(satellite, station, reference_time, &*self.propagator)?
+            .ok_or_else(|| {
+                OrbitalMechanicsError::config_error(format!(
+                    "No pass of {} over {} at {}",
+                    satellite_id, station_id, reference_time
+                ))
+            })?;
+        finder.find_repeat(satellite, station, &reference, &*self.propagator)
+    }
+
+    /// Intervals when `star` is in `terminal`'s field of view on a satellite
+    /// and unobstructed by the Earth, Sun and Moon
+    ///
+    /// Sampled at the visibility calculator's time step.
+    pub fn star_calibration_windows(
+        &self,
+        satellite_id: &str,
+        terminal: &CalibrationTerminal,
+        star: &CatalogStar,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+    ) -> Result<Vec<StarCalibrationWindow>> {
+        let satellite = self.constellation.get_satellite(satellite_id).ok_or(
+            OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()),
+        )?;
+        star_calibration::star_calibration_windows(
+            satellite,
+            terminal,
+            star,
+            start_time,
+            duration_hours,
+            self.visibility_calculator.time_step_seconds,
+            &*self.propagator,
+        )
+    }
+
+    /// Where a downlink beam lands during `window`, flagging no-lase zones
+    /// the footprint crosses
+    ///
+    /// Sampled at the visibility calculator's time step.
+    pub fn downlink_ground_trace(
+        &self,
+        window: &VisibilityWindow,
+        beam: &DownlinkBeam,
+        zones: &[LaserSafetyZone],
+    ) -> Result<BeamGroundTrace> {
+        let satellite = self.constellation.get_satellite(&window.satellite_id).ok_or(
+            OrbitalMechanicsError::SatelliteNotFound(window.satellite_id.clone()),
+        )?;
+        let station = self.ground_stations.get_station(&window.station_id).ok_or(
+            OrbitalMechanicsError::GroundStationNotFound(window.station_id.clone()),
+        )?;
+        BeamGroundTrace::for_pass(
+            satellite,
+            station,
+            window,
+            beam,
+            zones,
+            self.visibility_calculator.time_step_seconds,
+            &*self.propagator,
+        )
+    }
+
+    /// Atmospheric occultations of the ray between two satellites
+    pub fn occultation_events(
+        &self,
+        transmitter_id: &str,
+        receiver_id: &str,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        config: &OccultationConfig,
+    ) -> Result<Vec<OccultationEvent>> {
+        let satellite = |id: &str| {
+            self.constellation
+                .get_satellite(id)
+                .ok_or(OrbitalMechanicsError::SatelliteNotFound(id.to_string()))
+        };
+        occultation::occultation_events(
+            satellite(transmitter_id)?,
+            satellite(receiver_id)?,
+            start_time,
+            duration_hours,
+            config,
+            &*self.propagator,
+        )
+    }
+
+    /// Link availability per station and satellite, sampled every `step_seconds`
+    ///
+    /// See `sla_report` for how availability and outage causes are defined.
+    pub fn sla_report(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        step_seconds: f64,
+    ) -> Result<SlaReport> {
+        let windows = self.calculate_all_visibility_windows(start_time, duration_hours)?;
+        self.sla_report_from_windows(&windows, start_time, duration_hours, step_seconds)
+    }
+
+    /// Link availability per station and satellite from precomputed windows,
+    /// e.g. ones with injected faults applied
+    pub fn sla_report_from_windows(
+        &self,
+        windows: &[VisibilityWindow],
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        step_seconds: f64,
+    ) -> Result<SlaReport> {
+        let started = std::time::Instant::now();
+        let end_time =
+            start_time + chrono::Duration::milliseconds((duration_hours * 3.6e6) as i64);
+        let report = SlaReport::from_windows(
+            windows,
+            self.ground_stations.stations(),
+            self.constellation
+                .satellites()
+                .map(|satellite| satellite.satellite_id.as_str()),
+            start_time,
+            end_time,
+            step_seconds,
+        )?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            stations = report.stations.len(),
+            satellites = report.satellites.len(),
+            mean_station_availability_percent = report.mean_station_availability_percent,
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "SLA report complete"
+        );
+        Ok(report)
+    }
+
+    /// Update cached visibility windows after satellites or stations change
+    ///
+    /// Only pairs involving added or edited satellites and stations are
+    /// recomputed; the result matches `calculate_all_visibility_windows` over
+    /// the cache's span.
+    pub fn refresh_visibility_windows(
+        &self,
+        cache: &mut VisibilityCache,
+    ) -> Result<Vec<VisibilityWindow>> {
+        let started = std::time::Instant::now();
+        cache.reset_for_settings(self.visibility_settings_fingerprint()?);
+        let (start_time, duration_hours) = (cache.start_time(), cache.duration_hours());
+        let refresh = cache.refresh(
+            self.constellation.satellites(),
+            self.ground_stations.stations(),
+            |satellite, station| {
+                self.pair_visibility_windows(satellite, station, start_time, duration_hours)
+            },
+        )?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            recomputed_pairs = refresh.recomputed_pairs,
+            reused_pairs = refresh.reused_pairs,
+            removed_pairs = refresh.removed_pairs,
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Visibility cache refreshed"
+        );
+
+        Ok(cache.windows())
+    }
+
+    /// Propagate every satellite to `time` and buffer the states for interpolation
+    ///
+    /// Call at the propagation cadence. The interpolator does not extrapolate,
+    /// so renderers sample `StateInterpolator::interpolate_all` at display rate
+    /// between the last two ticks, i.e. one tick behind the latest `time`, or
+    /// call this one tick ahead of the clock. Times past the latest tick
+    /// return no states.
+    pub fn update_interpolator(
+        &self,
+        interpolator: &mut StateInterpolator,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let started = std::time::Instant::now();
+        let states = self
+            .constellation
+            .satellites()
+            .map(|satellite| {
+                self.propagator
+                    .propagate(satellite, time)
+                    .for_satellite(&satellite.satellite_id)
+                    .at_epoch(time)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let satellites = states.len();
+        interpolator.extend(states);
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            satellites,
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Interpolator updated"
+        );
+
+        Ok(())
+    }
+
+    /// Fingerprint of the settings behind `pair_visibility_windows`
+    fn visibility_settings_fingerprint(&self) -> Result<u128> {
+        self.visibility_calculator.settings_fingerprint(
+            &*self.propagator,
+            &(
+                &self.fso_analyzer.thermal_keep_outs,
+                &self.fso_analyzer.geo_arc_avoidance,
+            ),
+        )
+    }
+
+    /// Visibility windows of one pair, annotated with FSO keep-out intervals
+    fn pair_visibility_windows(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+    ) -> Result<Vec<VisibilityWindow>> {
+        let mut windows = self.visibility_calculator.calculate_windows(
+            satellite,
+            station,
+            start_time,
+            duration_hours,
+            &*self.propagator,
+        )?;
+        for window in &mut windows {
+            self.fso_analyzer.annotate_window(
+                window,
+                satellite,
+                station,
+                &*self.propagator,
+                constants::defaults::KEEP_OUT_SAMPLE_SECONDS,
+            )?;
+        }
+        Ok(windows)
+    }
+
+    /// Contacts for the period, booked best-first by `scorer`
+    ///
+    /// Each station and satellite holds at most one contact at a time. Pass
+    /// `DefaultPassScorer::new().with_terminal(...)` or a closure.
+    pub fn schedule_contacts(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        scorer: &dyn PassScorer,
+    ) -> Result<Vec<ScoredPass>> {
+        self.schedule_contacts_with_progress(start_time, duration_hours, scorer, &NoProgress)
+    }
+
+    /// Contacts for the period like `schedule_contacts`, reporting progress to `progress`
+    ///
+    /// The visibility search takes the first 95 percent, booking the rest.
+    pub fn schedule_contacts_with_progress(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        scorer: &dyn PassScorer,
+        progress: &dyn ProgressSink,
+    ) -> Result<Vec<ScoredPass>> {
+        let windows = self.visibility_windows_with_progress(
+            start_time,
+            duration_hours,
+            &Span::new(progress, 0.0, 95.0),
+        )?;
+        let started = std::time::Instant::now();
+        let candidates = windows.len();
+        let booking_progress = Span::new(progress, 95.0, 100.0);
+        let booking = Phase::start(&booking_progress, "scheduling", 1)?;
+        let contacts = pass_scoring::schedule_contacts(windows, scorer);
+        booking.finish();
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            candidates,
+            booked = contacts.len(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Contacts scheduled"
+        );
+
+        Ok(contacts)
+    }
+
+    /// Default pass scorer using this engine's optical terminal
+    pub fn default_pass_scorer(&self) -> DefaultPassScorer {
+        DefaultPassScorer::new().with_terminal(&self.fso_analyzer)
+    }
+
+    /// Generate antenna pointing schedules for every visibility window in the period
+    ///
+    /// Stations sit on the engine's Earth model, whatever `generator` sets.
+    pub fn generate_pointing_schedules(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        generator: &PointingScheduleGenerator,
+    ) -> Result<Vec<PointingSchedule>> {
+        let windows = self.calculate_all_visibility_windows(start_time, duration_hours)?;
+        let started = std::time::Instant::now();
+
+        let generator = generator
+            .clone()
+            .with_earth_model(self.visibility_calculator.earth_model);
+        let schedules = generator.generate_for_windows(
+            &windows,
+            &self.constellation,
+            &self.ground_stations,
+            &*self.propagator,
+        )?;
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            schedules = schedules.len(),
+            cadence_seconds = generator.cadence_seconds,
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Pointing schedules generated"
+        );
+
+        Ok(schedules)
+    }
+
+    /// Laser clearing house avoidance request for every planned uplink in the period
+    pub fn laser_avoidance_request(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        generator: &PointingScheduleGenerator,
+        config: &AvoidanceRequestConfig,
+    ) -> Result<AvoidanceRequest> {
+        let schedules = self.generate_pointing_schedules(start_time, duration_hours, generator)?;
+        let request = AvoidanceRequest::from_schedules(&schedules, config)?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            stations = request.station_ids().len(),
+            windows = request.windows.len(),
+            "Laser avoidance request generated"
+        );
+
+        Ok(request)
+    }
+
+    /// Minimum-latency relay route between two ground stations at an instant
+    pub fn find_relay_route(
+        &self,
+        source_station_id: &str,
+        destination_station_id: &str,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<Route>> {
+        let source = self.ground_stations.get_station(source_station_id).ok_or(
+            OrbitalMechanicsError::GroundStationNotFound(source_station_id.to_string()),
+        )?;
+        let destination = self
+            .ground_stations
+            .get_station(destination_station_id)
+            .ok_or(OrbitalMechanicsError::GroundStationNotFound(
+                destination_station_id.to_string(),
+            ))?;
+
+        let route = RelayRouter::with_config(self.routing_config()).route_at(
+            self.constellation.satellites(),
+            source,
+            destination,
+            time,
+            &*self.propagator,
+        )?;
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            source = source_station_id,
+            destination = destination_station_id,
+            hops = route.as_ref().map_or(0, |r| r.hops.len()),
+            total_delay_ms = route.as_ref().map(|r| r.total_delay_ms),
+            "Relay route computed"
+        );
+
+        Ok(route)
+    }
+
+    /// Default link parameters on this engine's Earth model
+    fn routing_config(&self) -> RoutingConfig {
+        RoutingConfig {
+            earth_model: self.visibility_calculator.earth_model,
+            ..RoutingConfig::default()
+        }
+    }
+
+    /// One-way latency grid from a gateway station through the constellation
+    pub fn latency_map(
+        &self,
+        gateway_station_id: &str,
+        epoch: chrono::DateTime<chrono::Utc>,
+        resolution_deg: f64,
+    ) -> Result<LatencyMap> {
+        let started = std::time::Instant::now();
+        let gateway = self.ground_stations.get_station(gateway_station_id).ok_or(
+            OrbitalMechanicsError::GroundStationNotFound(gateway_station_id.to_string()),
+        )?;
+
+        let network = RelayNetwork::build(
+            self.routing_config(),
+            self.constellation.satellites(),
+            epoch,
+            &*self.propagator,
+        )?;
+        let map = LatencyMap::generate(&network, gateway, resolution_deg)?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            gateway = gateway_station_id,
+            cells = map.cells.len(),
+            coverage_percent = map.coverage_percent(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Latency map generated"
+        );
+
+        Ok(map)
+    }
+
+    /// Along-track deviation of slotted satellites from their assigned slots
+    pub fn slot_drift_report(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        step_seconds: f64,
+        thresholds: SlotDriftThresholds,
+    ) -> Result<SlotDriftReport> {
+        let started = std::time::Instant::now();
+        let report = SlotDriftMonitor::with_thresholds(thresholds).analyze(
+            &self.constellation,
+            &*self.propagator,
+            start_time,
+            duration_hours,
+            step_seconds,
+        )?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            satellites = report.satellites.len(),
+            in_alarm = report.satellites_at_or_above(DriftAlarmLevel::Alarm).count(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Slot drift report generated"
+        );
+
+        Ok(report)
+    }
+
+    /// Launch windows from a site into the plane of an assigned orbital plane
+    pub fn launch_windows(
+        &self,
+        site: &LaunchSite,
+        plane: PlaneId,
+        start_time: chrono::DateTime<chrono::Utc>,
+        days: u32,
+    ) -> Result<Vec<LaunchWindow>> {
+        let target = self
+            .constellation
+            .satellites_in_plane(plane)
+            .first()
+            .and_then(|s| s.slot.as_ref().map(|a| a.nominal_orbit(&s.satellite_id)))
+            .ok_or_else(|| {
+                OrbitalMechanicsError::config_error(format!(
+                    "No satellites assigned to plane {}",
+                    plane
+                ))
+            })?;
+
+        let windows = LaunchPlanner::new().daily_windows(site, &target, start_time, days)?;
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            site = %site.site_id,
+            %plane,
+            windows = windows.len(),
+            "Launch windows computed"
+        );
+
+        Ok(windows)
+    }
+
+    /// End-of-life disposal compliance for every satellite
+    ///
+    /// Satellites without a proposed disposal orbit in `plans` are checked
+    /// against the minimum compliant graveyard orbit.
+    pub fn disposal_compliance(
+        &self,
+        plans: &std::collections::HashMap<String, OrbitalElements>,
+        rules: DisposalRules,
+    ) -> Result<Vec<DisposalCompliance>> {
+        let report =
+            DisposalAnalyzer::with_rules(rules).check_constellation(&self.constellation, plans)?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            satellites = report.len(),
+            non_compliant = report.iter().filter(|c| !c.is_compliant()).count(),
+            "Disposal compliance checked"
+        );
+
+        Ok(report)
+    }
+
+    /// Visible satellite counts over a global grid at one epoch
+    pub fn coverage_grid(
+        &self,
+        epoch: chrono::DateTime<chrono::Utc>,
+        config: &CoverageGridConfig,
+    ) -> Result<CoverageGrid> {
+        self.coverage_grid_with_progress(epoch, config, &NoProgress)
+    }
+
+    /// Coverage grid like `coverage_grid`, reporting progress to `progress`
+    ///
+    /// Propagation takes the first 10 percent, the grid the rest.
+    pub fn coverage_grid_with_progress(
+        &self,
+        epoch: chrono::DateTime<chrono::Utc>,
+        config: &CoverageGridConfig,
+        progress: &dyn ProgressSink,
+    ) -> Result<CoverageGrid> {
+        let started = std::time::Instant::now();
+        let propagation_progress = Span::new(progress, 0.0, 10.0);
+        let propagation = Phase::start(
+            &propagation_progress,
+            "propagation",
+            self.constellation.satellite_count(),
+        )?;
+        let states = self
+            .constellation
+            .satellites()
+            .enumerate()
+            .map(|(index, satellite)| {
+                propagation.step(index + 1)?;
+                self.propagator
+                    .propagate(satellite, epoch)
+                    .for_satellite(&satellite.satellite_id)
+                    .at_epoch(epoch)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let grid = CoverageGrid::compute_with_progress(
+            &states,
+            config,
+            &Span::new(progress, 10.0, 100.0),
+        )?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            cells = grid.visible_counts.len(),
+            backend = ?grid.backend,
+            coverage_percent = grid.coverage_percent(1),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Coverage grid generated"
+        );
+
+        Ok(grid)
+    }
+
+    /// Interference conflicts between downlinks sharing spectrum at nearby stations
+    pub fn frequency_coordination(
+        &self,
+        transmissions: &[Transmission],
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        config: CoordinationConfig,
+    ) -> Result<CoordinationReport> {
+        self.frequency_coordination_with_progress(
+            transmissions,
+            start_time,
+            duration_hours,
+            config,
+            &NoProgress,
+        )
+    }
+
+    /// Interference conflicts like `frequency_coordination`, reporting
+    /// progress of the conjunction screening to `progress`
+    pub fn frequency_coordination_with_progress(
+        &self,
+        transmissions: &[Transmission],
+        start_time: chrono::DateTime<chrono::Utc>,
+        duration_hours: f64,
+        config: CoordinationConfig,
+        progress: &dyn ProgressSink,
+    ) -> Result<CoordinationReport> {
+        let started = std::time::Instant::now();
+        let report = CoordinationAnalyzer::with_config(config).analyze_with_progress(
+            &self.constellation,
+            &self.ground_stations,
+            transmissions,
+            &*self.propagator,
+            start_time,
+            duration_hours,
+            progress,
+        )?;
+
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            transmissions = transmissions.len(),
+            conflicts = report.conflicts.len(),
+            conflict_seconds = report.total_conflict_seconds(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Frequency coordination analyzed"
+        );
+
+        Ok(report)
+    }
+
     /// Analyze FSO link quality between satellite and ground station
     pub fn analyze_fso_link(
         &self,
@@ -160,9 +1246,56 @@ impl OrbitalMechanicsEngine {
             OrbitalMechanicsError::GroundStationNotFound(station_id.to_string()),
         )?;
 
-        Ok(self
+        let link = self
             .fso_analyzer
-            .analyze_link(&satellite_state, station, time))
+            .analyze_link(&satellite_state, station, time);
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            satellite_id,
+            station_id,
+            visible = link.is_some(),
+            link_margin_db = link.as_ref().map(|l| l.link_margin_db),
+            "FSO link analysed"
+        );
+
+        Ok(link)
+    }
+
+    /// Predicted health of every satellite-to-station link at `time`
+    ///
+    /// Publish the result with `manifold_links::publish_link_health`.
+    #[cfg(feature = "manifold")]
+    pub fn link_health(
+        &self,
+        time: chrono::DateTime<chrono::Utc>,
+        config: &LinkHealthConfig,
+    ) -> Result<Vec<LinkHealth>> {
+        let horizon_hours = config.los_horizon_seconds / 3600.0;
+        let windows = self.calculate_all_visibility_windows(time, horizon_hours)?;
+        let mut links = Vec::new();
+        for satellite in self.constellation.satellites() {
+            let state = self.satellite_position(&satellite.satellite_id, time)?;
+            for station in self.ground_stations.stations() {
+                let link = self.fso_analyzer.analyze_link(&state, station, time);
+                links.push(LinkHealth::assess(
+                    &satellite.satellite_id,
+                    &station.station_id,
+                    time,
+                    link.as_ref(),
+                    &windows,
+                    config,
+                ));
+            }
+        }
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            links = links.len(),
+            horizon_hours,
+            "Link health assessed"
+        );
+        Ok(links)
     }
 
     /// Generate constellation status report
@@ -177,7 +1310,10 @@ impl OrbitalMechanicsEngine {
         let simulator = SatelliteSimulator::new(propagator);
         self.satellite_simulator = Some(simulator);
 
-        tracing::info!("Live satellite simulation enabled with Unicode packet generation");
+        tracing::info!(
+            target: trace_targets::ENGINE,
+            "Live satellite simulation enabled with Unicode packet generation"
+        );
         Ok(())
     }
 
@@ -192,11 +1328,8 @@ impl OrbitalMechanicsEngine {
             simulator
                 .add_satellite(orbit, name, norad_id)
                 .await
-                .map_err(|e| OrbitalMechanicsError::config_error(e.to_string()))
         } else {
-            Err(OrbitalMechanicsError::config_error(
-                "Satellite simulation not enabled",
-            ))
+            Err(OrbitalMechanicsError::SimulationNotEnabled)
         }
     }
 
@@ -206,22 +1339,25 @@ impl OrbitalMechanicsEngine {
             simulator
                 .start_simulation()
                 .await
-                .map_err(|e| OrbitalMechanicsError::config_error(e.to_string()))
         } else {
-            Err(OrbitalMechanicsError::config_error(
-                "Satellite simulation not enabled",
-            ))
+            Err(OrbitalMechanicsError::SimulationNotEnabled)
         }
     }
 
+    /// Pause, step and time-warp control for the live simulation
+    pub fn simulation_control(&self) -> Result<SimulationControl> {
+        self.satellite_simulator
+            .as_ref()
+            .map(SatelliteSimulator::control)
+            .ok_or(OrbitalMechanicsError::SimulationNotEnabled)
+    }
+
     /// OPERATIONAL: Get all live satellites with current status
     pub async fn get_live_satellites(&self) -> Result<Vec<LiveSatellite>> {
         if let Some(ref simulator) = self.satellite_simulator {
             Ok(simulator.get_all_satellites().await)
         } else {
-            Err(OrbitalMechanicsError::config_error(
-                "Satellite simulation not enabled",
-            ))
+            Err(OrbitalMechanicsError::SimulationNotEnabled)
         }
     }
 
@@ -233,9 +1369,7 @@ impl OrbitalMechanicsEngine {
         if let Some(ref simulator) = self.satellite_simulator {
             Ok(simulator.get_unicode_packet_history(limit).await)
         } else {
-            Err(OrbitalMechanicsError::config_error(
-                "Satellite simulation not enabled",
-            ))
+            Err(OrbitalMechanicsError::SimulationNotEnabled)
         }
     }
 
@@ -244,9 +1378,7 @@ impl OrbitalMechanicsEngine {
         if let Some(ref simulator) = self.satellite_simulator {
             Ok(simulator.get_simulation_statistics().await)
         } else {
-            Err(OrbitalMechanicsError::config_error(
-                "Satellite simulation not enabled",
-            ))
+            Err(OrbitalMechanicsError::SimulationNotEnabled)
         }
     }
 
@@ -319,5 +1451,98 @@ mod tests {
             assert!(position.is_ok());
         }
     }
+
+    #[test]
+    fn test_interpolator_serves_between_ticks() {
+        let engine = create_laserlight_constellation().unwrap();
+        let mut interpolator = StateInterpolator::default();
+        let tick = Utc::now();
+        let next_tick = tick + chrono::Duration::seconds(60);
+        engine.update_interpolator(&mut interpolator, tick).unwrap();
+        engine
+            .update_interpolator(&mut interpolator, next_tick)
+            .unwrap();
+
+        let midway = tick + chrono::Duration::seconds(30);
+        assert_eq!(interpolator.interpolate_all(midway).len(), 12);
+        assert_eq!(interpolator.interpolate_all(next_tick).len(), 12);
+        assert!(interpolator
+            .interpolate_all(next_tick + chrono::Duration::seconds(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_station_changes_invalidate_dependents() {
+        let mut engine = create_laserlight_constellation().unwrap();
+        let station = |id: &str, longitude_deg: f64| GroundStation {
+            station_id: id.to_string(),
+            name: id.to_string(),
+            position: ground_station::StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+        engine.add_ground_station(station("GS-1", 0.0));
+        engine.add_ground_station(station("GS-2", 180.0));
+        let mut changes = engine.subscribe_station_changes();
+
+        let start = Utc::now();
+        let mut cache = VisibilityCache::new(start, 6.0);
+        let mut windows = engine.refresh_visibility_windows(&mut cache).unwrap();
+        assert!(windows.iter().any(|w| w.station_id == "GS-1"));
+
+        engine.remove_ground_station("GS-1").unwrap();
+        assert!(engine.remove_ground_station("GS-1").is_err());
+        assert!(engine.update_ground_station(station("GS-3", 90.0)).is_err());
+        engine.update_ground_station(station("GS-2", 170.0)).unwrap();
+
+        let removed = changes.try_recv().unwrap();
+        assert_eq!(removed, StationChange::Removed("GS-1".to_string()));
+        assert_eq!(
+            changes.try_recv().unwrap(),
+            StationChange::Updated("GS-2".to_string())
+        );
+        assert!(changes.try_recv().is_err());
+
+        cache.apply_station_change(&removed);
+        assert!(cache.windows().iter().all(|w| w.station_id != "GS-1"));
+        let catalog = cache.catalog();
+        assert_eq!(catalog.len(), cache.windows().len());
+        assert!(catalog.next_station_pass("GS-1", start).is_none());
+        removed.invalidate(&mut windows);
+        assert!(windows.iter().all(|w| w.station_id != "GS-1"));
+    }
+
+    #[test]
+    fn test_propagator_and_keep_outs_change_visibility_settings() {
+        let fingerprint = |config: Config| {
+            OrbitalMechanicsEngine::with_config(config)
+                .unwrap()
+                .visibility_settings_fingerprint()
+                .unwrap()
+        };
+        let baseline = Config::custom_meo(2, 10000.0, 55.0, 1);
+        assert_eq!(fingerprint(baseline.clone()), fingerprint(baseline.clone()));
+
+        let mut keplerian = baseline.clone();
+        keplerian.analysis_config.propagator_type = PropagatorType::Keplerian;
+        assert_ne!(fingerprint(keplerian), fingerprint(baseline.clone()));
+
+        let mut keep_out = baseline.clone();
+        keep_out
+            .fso_config
+            .thermal_keep_outs
+            .push(fso_analysis::ThermalKeepOut::ground(30.0));
+        assert_ne!(fingerprint(keep_out), fingerprint(baseline.clone()));
+
+        let mut geo_arc = baseline.clone();
+        geo_arc.fso_config.geo_arc_avoidance = Some(fso_analysis::GeoArcAvoidance::new(5.0));
+        assert_ne!(fingerprint(geo_arc), fingerprint(baseline));
+    }
 }
 pub mod foundation_integration;
@@ -0,0 +1,287 @@
+//! Link-state publication to the foundation manifold router
+//!
+//! The manifold router picks among `RouteEntry`s by health score, so a
+//! ground link the constellation is about to lose should lose health before
+//! traffic is routed onto it. `LinkHealth` scores a satellite-to-station
+//! optical link from its FSO margin now and its usable time left before LOS,
+//! and `publish_link_health` writes the scores into a `ManifoldRouter` as
+//! route entries.
+//!
+//! The router only takes routes scoring above its health threshold (0.8), so
+//! both parts saturate: margin at `reference_margin_db` and time to LOS at
+//! `los_horizon_seconds`. A link in view with full margin and at least that
+//! long before LOS scores 1; with the default half weighting, a link needs
+//! more than 60 % of the horizon left at full margin to be routable.
+//!
+//! Each link is one route, `orbital/<station>/<satellite>`, with destination
+//! `orbital/<station>/<satellite>/`. The router matches destinations by
+//! substring, so the trailing delimiter keeps `station_destination("GS-1")`,
+//! `orbital/GS-1/`, from also picking the satellites serving `GS-10`.
+
+use crate::fso_analysis::FsoLinkQuality;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sx9_foundation_manifold::{ManifoldRouter, RouteEntry};
+
+/// How link health is scored and published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkHealthConfig {
+    /// Margin at which the margin score saturates
+    pub reference_margin_db: f64,
+    /// Usable time before LOS at which the availability score saturates
+    pub los_horizon_seconds: f64,
+    /// Share of the health score given to margin; availability gets the rest
+    pub margin_weight: f64,
+    /// Priority given to new route entries
+    pub route_priority: u8,
+}
+
+/// Predicted health of one satellite-to-station link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkHealth {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// FSO margin at `timestamp`; `None` when the satellite is out of view
+    pub link_margin_db: Option<f64>,
+    /// Usable time before LOS as a share of `los_horizon_seconds`, 0-1
+    pub availability: f64,
+    /// 0-1
+    pub health_score: f64,
+}
+
+impl Default for LinkHealthConfig {
+    fn default() -> Self {
+        Self {
+            reference_margin_db: 6.0,
+            los_horizon_seconds: 600.0,
+            margin_weight: 0.5,
+            route_priority: 128,
+        }
+    }
+}
+
+impl LinkHealth {
+    /// Score a link from its current FSO quality and its passes
+    ///
+    /// `windows` must include the pair's pass in progress at `time`, if any;
+    /// windows of other pairs are ignored.
+    pub fn assess(
+        satellite_id: &str,
+        station_id: &str,
+        time: DateTime<Utc>,
+        link: Option<&FsoLinkQuality>,
+        windows: &[VisibilityWindow],
+        config: &LinkHealthConfig,
+    ) -> Self {
+        let link_margin_db = link.map(|l| l.link_margin_db);
+        let horizon_seconds = config.los_horizon_seconds;
+        let availability = if horizon_seconds > 0.0 {
+            let usable_seconds: f64 = windows
+                .iter()
+                .filter(|w| w.satellite_id == satellite_id && w.station_id == station_id)
+                .filter(|w| w.start_time <= time && time <= w.end_time)
+                .map(|w| {
+                    let to_los = (w.end_time - time).num_milliseconds() as f64 / 1000.0;
+                    let until = time
+                        + Duration::milliseconds((to_los.min(horizon_seconds) * 1000.0) as i64);
+                    usable_seconds_between(w, time, until)
+                })
+                .sum();
+            (usable_seconds / horizon_seconds).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let margin_score = link_margin_db.map_or(0.0, |margin| {
+            (margin / config.reference_margin_db).clamp(0.0, 1.0)
+        });
+        let margin_weight = config.margin_weight.clamp(0.0, 1.0);
+
+        Self {
+            satellite_id: satellite_id.to_string(),
+            station_id: station_id.to_string(),
+            timestamp: time,
+            link_margin_db,
+            availability,
+            health_score: margin_weight * margin_score + (1.0 - margin_weight) * availability,
+        }
+    }
+
+    /// Manifold route ID
+    pub fn route_id(&self) -> String {
+        format!("orbital/{}/{}", self.station_id, self.satellite_id)
+    }
+
+    /// Route destination, the route ID closed with a delimiter
+    pub fn destination(&self) -> String {
+        format!("{}/", self.route_id())
+    }
+
+    /// Fresh route entry for this link
+    pub fn to_route_entry(&self, config: &LinkHealthConfig) -> RouteEntry {
+        RouteEntry {
+            destination: self.destination(),
+            priority: config.route_priority,
+            load_factor: 0.0,
+            health_score: self.health_score,
+            last_updated: self.timestamp,
+        }
+    }
+}
+
+/// Destination matching every link of one station and no other
+pub fn station_destination(station_id: &str) -> String {
+    format!("orbital/{}/", station_id)
+}
+
+/// Usable seconds of `window` between `from` and `to`
+fn usable_seconds_between(
+    window: &VisibilityWindow,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> f64 {
+    let seconds = |start: DateTime<Utc>, end: DateTime<Utc>| {
+        (end - start).num_milliseconds().max(0) as f64 / 1000.0
+    };
+    let unusable: f64 = window
+        .unusable_intervals
+        .iter()
+        .map(|i| seconds(i.start_time.max(from), i.end_time.min(to)))
+        .sum();
+    (seconds(from, to) - unusable).max(0.0)
+}
+
+/// Write link health into the router's routing table
+///
+/// Routes already in the table keep their priority and load factor and take
+/// the new health score. Returns the number of routes written.
+pub fn publish_link_health(
+    router: &mut ManifoldRouter,
+    links: &[LinkHealth],
+    config: &LinkHealthConfig,
+) -> usize {
+    for link in links {
+        router
+            .routing_table
+            .entry(link.route_id())
+            .and_modify(|entry| {
+                entry.health_score = link.health_score;
+                entry.last_updated = link.timestamp;
+            })
+            .or_insert_with(|| link.to_route_entry(config));
+    }
+    tracing::debug!(
+        target: crate::trace_targets::ENGINE,
+        routes = links.len(),
+        "Published link health to the manifold router"
+    );
+    links.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visibility::{PassType, UnusableInterval};
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_publish_link_health() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let config = LinkHealthConfig::default();
+        let window = VisibilityWindow {
+            satellite_id: "SAT-1".to_string(),
+            station_id: "GS-1".to_string(),
+            start_time: t0,
+            end_time: t0 + Duration::minutes(30),
+            duration_seconds: 1800.0,
+            max_elevation_time: t0 + Duration::minutes(15),
+            max_elevation_deg: 60.0,
+            min_range_km: 8000.0,
+            mean_range_km: 9000.0,
+            azimuth_span_deg: 120.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        };
+        let link = FsoLinkQuality {
+            satellite_id: "SAT-1".to_string(),
+            station_id: "GS-1".to_string(),
+            timestamp: t0,
+            elevation_angle_deg: 30.0,
+            azimuth_angle_deg: 90.0,
+            range_km: 9000.0,
+            atmospheric_transmission: 0.8,
+            link_margin_db: 3.0,
+            estimated_throughput_gbps: 10.0,
+            weather_impact_factor: 1.0,
+            fade_db: None,
+            adaptive_optics_gain_db: None,
+            sky_background_penalty_db: None,
+        };
+
+        // Full margin with the whole horizon before LOS saturates the score
+        let strong = FsoLinkQuality {
+            link_margin_db: 6.0,
+            ..link.clone()
+        };
+        let windows = std::slice::from_ref(&window);
+        let healthy = LinkHealth::assess("SAT-1", "GS-1", t0, Some(&strong), windows, &config);
+        assert_eq!(healthy.availability, 1.0);
+        assert_eq!(healthy.health_score, 1.0);
+        // Half the reference margin, or five minutes to LOS, falls below the router threshold
+        let weak = LinkHealth::assess("SAT-1", "GS-1", t0, Some(&link), windows, &config);
+        assert!((weak.health_score - 0.75).abs() < 1e-9);
+        let setting = t0 + Duration::minutes(25);
+        let setting = LinkHealth::assess("SAT-1", "GS-1", setting, Some(&strong), windows, &config);
+        assert!((setting.availability - 0.5).abs() < 1e-9);
+        assert!(setting.health_score < 0.8);
+        // Unusable time before LOS is not available
+        let mut blocked = window.clone();
+        blocked.unusable_intervals.push(UnusableInterval {
+            start_time: t0 + Duration::minutes(2),
+            end_time: t0 + Duration::minutes(7),
+            reason: "Thermal keep-out".to_string(),
+        });
+        let blocked = LinkHealth::assess("SAT-1", "GS-1", t0, Some(&strong), &[blocked], &config);
+        assert!((blocked.availability - 0.5).abs() < 1e-9);
+        // Out of view, with no passes coming
+        let dark = LinkHealth::assess("SAT-2", "GS-1", t0, None, windows, &config);
+        assert_eq!(dark.health_score, 0.0);
+
+        let mut router = ManifoldRouter::new();
+        let mut existing = healthy.to_route_entry(&config);
+        existing.load_factor = 0.7;
+        router.add_route(healthy.route_id(), existing);
+        assert_eq!(
+            publish_link_health(&mut router, &[healthy.clone(), dark], &config),
+            2
+        );
+        assert_eq!(router.routing_table.len(), 2);
+        let entry = &router.routing_table["orbital/GS-1/SAT-1"];
+        assert_eq!(entry.load_factor, 0.7);
+        assert_eq!(entry.health_score, healthy.health_score);
+        assert_eq!(router.routing_table["orbital/GS-1/SAT-2"].health_score, 0.0);
+
+        // A healthy GS-10 link is not a route to GS-1
+        let hash = "0".repeat(48);
+        let other = LinkHealth {
+            station_id: "GS-10".to_string(),
+            ..healthy.clone()
+        };
+        let mut router = ManifoldRouter::new();
+        publish_link_health(&mut router, &[weak, other], &config);
+        assert!(router
+            .route_packet(&hash, &station_destination("GS-1"))
+            .is_err());
+        publish_link_health(&mut router, &[healthy], &config);
+        assert_eq!(
+            router
+                .route_packet(&hash, &station_destination("GS-1"))
+                .unwrap(),
+            "orbital/GS-1/SAT-1"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Synthetic tracking measurements for orbit determination testing
+//!
+//! Samples propagated truth from each ground station and corrupts it with a
+//! constant bias plus white Gaussian noise per observable and station. The
+//! noise is drawn from a seeded generator, so a configuration reproduces the
+//! same data set. Observations are written one per CSV row together with
+//! their noise sigma, which OD filters use as the measurement weight.
+
+use crate::constants::*;
+use crate::constellation::Constellation;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::ground_station::{GroundStation, GroundStationNetwork};
+use crate::orbit::SatelliteOrbit;
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Observable produced by the generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ObservationType {
+    /// Slant range in km
+    Range,
+    /// Range rate in km/s, positive when receding
+    RangeRate,
+    /// Carrier Doppler shift in Hz
+    Doppler,
+    /// Azimuth in degrees, clockwise from north
+    Azimuth,
+    /// Elevation in degrees
+    Elevation,
+}
+
+/// Constant bias plus zero-mean Gaussian noise, in the observable's unit
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct NoiseModel {
+    pub bias: f64,
+    pub sigma: f64,
+}
+
+/// Noise for every observable of one station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationNoise {
+    pub range_km: NoiseModel,
+    pub range_rate_km_s: NoiseModel,
+    pub doppler_hz: NoiseModel,
+    pub azimuth_deg: NoiseModel,
+    pub elevation_deg: NoiseModel,
+}
+
+/// Measurement generation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasurementConfig {
+    pub cadence_seconds: f64,
+    /// Stations only track above this elevation
+    pub min_elevation_deg: f64,
+    pub observables: Vec<ObservationType>,
+    /// Carrier used for Doppler observations
+    pub carrier_frequency_hz: f64,
+    /// Noise for stations without an entry in `station_noise`
+    pub default_noise: StationNoise,
+    #[serde(default)]
+    pub station_noise: HashMap<String, StationNoise>,
+    pub seed: u64,
+}
+
+/// One synthetic observation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Observation {
+    pub timestamp: DateTime<Utc>,
+    pub station_id: String,
+    pub satellite_id: String,
+    pub observation_type: ObservationType,
+    /// Measured value, including bias and noise
+    pub value: f64,
+    /// Noise sigma the value was drawn with
+    pub sigma: f64,
+}
+
+/// Generates noisy observations from propagated truth
+pub struct MeasurementGenerator {
+    config: MeasurementConfig,
+    rng: StdRng,
+}
+
+impl ObservationType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Range => "range_km",
+            Self::RangeRate => "range_rate_km_s",
+            Self::Doppler => "doppler_hz",
+            Self::Azimuth => "azimuth_deg",
+            Self::Elevation => "elevation_deg",
+        }
+    }
+
+    /// Type name with the units of `options`, e.g. `range_m`
+    fn export_name(self, options: &ExportOptions) -> String {
+        let distance = options.distance_unit.suffix();
+        let angle = options.angle_unit.suffix();
+        match self {
+            Self::Range => format!("range_{}", distance),
+            Self::RangeRate => format!("range_rate_{}_s", distance),
+            Self::Doppler => "doppler_hz".to_string(),
+            Self::Azimuth => format!("azimuth_{}", angle),
+            Self::Elevation => format!("elevation_{}", angle),
+        }
+    }
+
+    /// A value or sigma of this type in the units of `options`
+    fn export_value(self, value: f64, options: &ExportOptions) -> String {
+        match self {
+            Self::Range | Self::RangeRate => options.distance(value, Some(12)),
+            Self::Doppler => options.decimal(value, Some(12)),
+            Self::Azimuth | Self::Elevation => options.angle(value, Some(12)),
+        }
+    }
+}
+
+impl StationNoise {
+    /// Unbiased noise typical of an optical ranging station
+    pub fn optical() -> Self {
+        Self {
+            range_km: NoiseModel {
+                bias: 0.0,
+                sigma: 0.005,
+            },
+            range_rate_km_s: NoiseModel {
+                bias: 0.0,
+                sigma: 1.0e-6,
+            },
+            doppler_hz: NoiseModel {
+                bias: 0.0,
+                sigma: 1.0,
+            },
+            azimuth_deg: NoiseModel {
+                bias: 0.0,
+                sigma: 0.001,
+            },
+            elevation_deg: NoiseModel {
+                bias: 0.0,
+                sigma: 0.001,
+            },
+        }
+    }
+
+    pub fn model(&self, observation_type: ObservationType) -> NoiseModel {
+        match observation_type {
+            ObservationType::Range => self.range_km,
+            ObservationType::RangeRate => self.range_rate_km_s,
+            ObservationType::Doppler => self.doppler_hz,
+            ObservationType::Azimuth => self.azimuth_deg,
+            ObservationType::Elevation => self.elevation_deg,
+        }
+    }
+}
+
+impl Default for StationNoise {
+    fn default() -> Self {
+        Self::optical()
+    }
+}
+
+impl Default for MeasurementConfig {
+    fn default() -> Self {
+        Self {
+            cadence_seconds: 10.0,
+            min_elevation_deg: defaults::MIN_ELEVATION_DEG,
+            observables: vec![
+                ObservationType::Range,
+                ObservationType::RangeRate,
+                ObservationType::Azimuth,
+                ObservationType::Elevation,
+            ],
+            carrier_frequency_hz: SPEED_OF_LIGHT / FSO_WAVELENGTH_1550NM,
+            default_noise: StationNoise::default(),
+            station_noise: HashMap::new(),
+            seed: 0,
+        }
+    }
+}
+
+impl MeasurementConfig {
+    /// Noise applied to a station's observations
+    pub fn noise_for(&self, station_id: &str) -> &StationNoise {
+        self.station_noise
+            .get(station_id)
+            .unwrap_or(&self.default_noise)
+    }
+}
+
+impl MeasurementGenerator {
+    pub fn new(config: MeasurementConfig) -> Result<Self> {
+        if !config.cadence_seconds.is_finite() || config.cadence_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Measurement cadence must be positive, got {} s",
+                config.cadence_seconds
+            )));
+        }
+        let all_noise = std::iter::once(&config.default_noise).chain(config.station_noise.values());
+        for noise in all_noise {
+            for &observation_type in &config.observables {
+                let sigma = noise.model(observation_type).sigma;
+                if !sigma.is_finite() || sigma < 0.0 {
+                    return Err(OrbitalMechanicsError::config_error(format!(
+                        "Noise sigma for {} must be non-negative, got {}",
+                        observation_type.as_str(),
+                        sigma
+                    )));
+                }
+            }
+        }
+        Ok(Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+        })
+    }
+
+    pub fn config(&self) -> &MeasurementConfig {
+        &self.config
+    }
+
+    /// Observations of one satellite from one station over `[start, end]`
+    ///
+    /// Epochs fall on the cadence grid from `start`; epochs below the
+    /// elevation mask or outside station availability are skipped.
+    pub fn generate(
+        &mut self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Vec<Observation>> {
+        let step =
+            Duration::milliseconds((self.config.cadence_seconds * 1000.0).round().max(1.0) as i64);
+        let noise = self.config.noise_for(&station.station_id).clone();
+        let observables = self.config.observables.clone();
+        let mut observations = Vec::new();
+        let mut time = start;
+
+        while time <= end {
+            if station.is_available(time) {
+                let state = propagator
+                    .propagate(satellite, time)
+                    .for_satellite(&satellite.satellite_id)?;
+                let look_angles = state.look_angles_from_station(
+                    station.position.latitude_deg,
+                    station.position.longitude_deg,
+                    station.position.elevation_m,
+                );
+                if look_angles.elevation_deg >= self.config.min_elevation_deg {
+                    for &observation_type in &observables {
+                        let truth = match observation_type {
+                            ObservationType::Range => look_angles.range_km,
+                            ObservationType::RangeRate => look_angles.range_rate_km_per_s,
+                            ObservationType::Doppler => {
+                                -self.config.carrier_frequency_hz
+                                    * look_angles.range_rate_km_per_s
+                                    * KM_TO_M
+                                    / SPEED_OF_LIGHT
+                            }
+                            ObservationType::Azimuth => look_angles.azimuth_deg,
+                            ObservationType::Elevation => look_angles.elevation_deg,
+                        };
+                        let model = noise.model(observation_type);
+                        observations.push(Observation {
+                            timestamp: time,
+                            station_id: station.station_id.clone(),
+                            satellite_id: satellite.satellite_id.clone(),
+                            observation_type,
+                            value: truth + model.bias + self.draw(model.sigma),
+                            sigma: model.sigma,
+                        });
+                    }
+                }
+            }
+            time += step;
+        }
+
+        Ok(observations)
+    }
+
+    /// Observations of every satellite from every station
+    ///
+    /// Pairs are visited in station, then satellite ID order so the noise
+    /// sequence is reproducible; the result is ordered by time.
+    pub fn generate_for_network(
+        &mut self,
+        constellation: &Constellation,
+        stations: &GroundStationNetwork,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Vec<Observation>> {
+        let mut station_list: Vec<&GroundStation> = stations.stations().collect();
+        station_list.sort_by(|a, b| a.station_id.cmp(&b.station_id));
+        let mut satellites: Vec<&SatelliteOrbit> = constellation.satellites().collect();
+        satellites.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
+
+        let mut observations = Vec::new();
+        for station in station_list {
+            for satellite in &satellites {
+                observations.extend(self.generate(satellite, station, start, end, propagator)?);
+            }
+        }
+        // Stable, so simultaneous observations keep station/satellite order
+        observations.sort_by_key(|observation| observation.timestamp);
+        Ok(observations)
+    }
+
+    fn draw(&mut self, sigma: f64) -> f64 {
+        if sigma == 0.0 {
+            return 0.0;
+        }
+        // Sigma was validated as finite and non-negative in `new`
+        Normal::new(0.0, sigma)
+            .map(|normal| normal.sample(&mut self.rng))
+            .unwrap_or(0.0)
+    }
+}
+
+/// Render observations as CSV, one row per observation
+pub fn observations_to_csv(observations: &[Observation]) -> String {
+    observations_to_csv_with(observations, &ExportOptions::default())
+}
+
+/// Render observations as CSV in the units and format of `options`
+///
+/// The `type` column names each row's unit, e.g. `range_m`.
+pub fn observations_to_csv_with(observations: &[Observation], options: &ExportOptions) -> String {
+    let mut csv = String::from("timestamp_utc,station_id,satellite_id,type,value,sigma\n");
+    for observation in observations {
+        let kind = observation.observation_type;
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{}",
+            options.timestamp(observation.timestamp),
+            observation.station_id,
+            options.satellite_name(&observation.satellite_id),
+            kind.export_name(options),
+            kind.export_value(observation.value, options),
+            kind.export_value(observation.sigma, options)
+        );
+    }
+    csv
+}
+
+/// Write observations to a CSV file
+pub fn write_observations_csv<P: AsRef<Path>>(observations: &[Observation], path: P) -> Result<()> {
+    write_observations_csv_with(observations, path, &ExportOptions::default())
+}
+
+/// Write observations to a CSV file in the units and format of `options`
+pub fn write_observations_csv_with<P: AsRef<Path>>(
+    observations: &[Observation],
+    path: P,
+    options: &ExportOptions,
+) -> Result<()> {
+    let path = path.as_ref();
+    fs::write(path, observations_to_csv_with(observations, options)).for_path(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::StationPosition;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+
+    #[test]
+    fn test_noise_statistics_and_reproducibility() {
+        let epoch = Utc::now();
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "OD-01".to_string(),
+            "OD Target".to_string(),
+            elements,
+            epoch,
+        );
+        let station = GroundStation {
+            station_id: "GS-OD".to_string(),
+            name: "OD Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+
+        let biased = StationNoise {
+            range_km: NoiseModel {
+                bias: 0.1,
+                sigma: 0.01,
+            },
+            ..StationNoise::optical()
+        };
+        let config = MeasurementConfig {
+            cadence_seconds: 1.0,
+            observables: vec![ObservationType::Range],
+            station_noise: HashMap::from([("GS-OD".to_string(), biased)]),
+            seed: 42,
+            ..MeasurementConfig::default()
+        };
+        let propagator = KeplerianPropagator::new();
+        let end = epoch + Duration::seconds(999);
+        let generate = |config: MeasurementConfig| {
+            MeasurementGenerator::new(config)
+                .unwrap()
+                .generate(&satellite, &station, epoch, end, &propagator)
+                .unwrap()
+        };
+
+        // Satellite starts overhead and stays in view for the whole run
+        let observations = generate(config.clone());
+        assert_eq!(observations.len(), 1000);
+        let residuals: Vec<f64> = observations
+            .iter()
+            .map(|o| {
+                let truth = propagator.propagate(&satellite, o.timestamp).unwrap();
+                o.value - truth.look_angles_from_station(0.0, 0.0, 0.0).range_km
+            })
+            .collect();
+        let mean = residuals.iter().sum::<f64>() / residuals.len() as f64;
+        let sigma = (residuals.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+            / (residuals.len() - 1) as f64)
+            .sqrt();
+        assert!((mean - 0.1).abs() < 0.002, "mean residual {}", mean);
+        assert!((sigma - 0.01).abs() < 0.001, "residual sigma {}", sigma);
+
+        // Same seed, same data
+        let repeat = generate(config.clone());
+        assert_eq!(repeat[17].value, observations[17].value);
+
+        let csv = observations_to_csv(&observations[..2]);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("timestamp_utc,station_id,satellite_id,type,value,sigma")
+        );
+        assert!(lines.next().unwrap().contains(",GS-OD,OD-01,range_km,"));
+
+        let invalid = MeasurementConfig {
+            cadence_seconds: 0.0,
+            ..config
+        };
+        assert!(MeasurementGenerator::new(invalid).is_err());
+    }
+}
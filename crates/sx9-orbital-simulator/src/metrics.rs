@@ -0,0 +1,235 @@
+//! Prometheus metrics export for the satellite simulator
+//!
+//! Enabled with the `metrics` feature. Simulator counters are mirrored into a
+//! `prometheus` registry from `SimulationStatistics` snapshots and can be
+//! scraped for Grafana dashboards through `router`, an axum `/metrics`
+//! route, or the standalone `serve_metrics` exporter. The same router
+//! answers `/healthz` with the library self-check, 503 when any check fails.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::satellite_simulator::{SatelliteSimulator, SimulationStatistics};
+use crate::self_check::self_check;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use prometheus::{Encoder, Gauge, IntCounter, IntGauge, Opts, Registry, TextEncoder, TEXT_FORMAT};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Metric namespace shared by all simulator series
+pub const METRICS_NAMESPACE: &str = "sx9_orbital";
+
+/// Prometheus view of simulator statistics
+pub struct SimulatorMetrics {
+    registry: Registry,
+    satellites_total: IntGauge,
+    satellites_active: IntGauge,
+    packets_generated_total: IntCounter,
+    packet_history_size: IntGauge,
+    obstruction_warnings: IntGauge,
+    link_availability_percent: Gauge,
+    simulation_time_seconds: Gauge,
+}
+
+impl SimulatorMetrics {
+    /// Create metrics registered in a fresh registry
+    pub fn new() -> Result<Self> {
+        Self::with_registry(Registry::new())
+    }
+
+    /// Create metrics registered in an existing registry
+    pub fn with_registry(registry: Registry) -> Result<Self> {
+        let opts = |name: &str, help: &str| Opts::new(name, help).namespace(METRICS_NAMESPACE);
+
+        let satellites_total = IntGauge::with_opts(opts(
+            "satellites_total",
+            "Satellites registered in the simulation",
+        ))?;
+        let satellites_active = IntGauge::with_opts(opts(
+            "satellites_active",
+            "Satellites in the Active operational state",
+        ))?;
+        let packets_generated_total = IntCounter::with_opts(opts(
+            "unicode_packets_generated_total",
+            "Unicode packets generated since simulation start",
+        ))?;
+        let packet_history_size = IntGauge::with_opts(opts(
+            "unicode_packet_history_size",
+            "Unicode packets retained in the history buffer",
+        ))?;
+        let obstruction_warnings = IntGauge::with_opts(opts(
+            "obstruction_warnings",
+            "Active obstruction warnings across all satellites",
+        ))?;
+        let link_availability_percent = Gauge::with_opts(opts(
+            "link_availability_percent",
+            "Percentage of retained packets sent over an unobstructed path",
+        ))?;
+        let simulation_time_seconds = Gauge::with_opts(opts(
+            "simulation_time_seconds",
+            "Current simulation clock as Unix time",
+        ))?;
+
+        registry.register(Box::new(satellites_total.clone()))?;
+        registry.register(Box::new(satellites_active.clone()))?;
+        registry.register(Box::new(packets_generated_total.clone()))?;
+        registry.register(Box::new(packet_history_size.clone()))?;
+        registry.register(Box::new(obstruction_warnings.clone()))?;
+        registry.register(Box::new(link_availability_percent.clone()))?;
+        registry.register(Box::new(simulation_time_seconds.clone()))?;
+
+        Ok(Self {
+            registry,
+            satellites_total,
+            satellites_active,
+            packets_generated_total,
+            packet_history_size,
+            obstruction_warnings,
+            link_availability_percent,
+            simulation_time_seconds,
+        })
+    }
+
+    /// Registry holding the simulator metrics
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Update all metrics from a statistics snapshot
+    pub fn update(&self, stats: &SimulationStatistics) {
+        self.satellites_total.set(stats.total_satellites as i64);
+        self.satellites_active.set(stats.active_satellites as i64);
+        self.packet_history_size
+            .set(stats.total_unicode_packets as i64);
+        self.obstruction_warnings
+            .set(stats.obstruction_warnings as i64);
+        self.link_availability_percent
+            .set(stats.link_availability_percent);
+        self.simulation_time_seconds
+            .set(stats.simulation_time.timestamp_millis() as f64 / 1000.0);
+
+        // Counters only move forward; apply the delta since the last snapshot
+        let seen = self.packets_generated_total.get();
+        if stats.total_packets_generated > seen {
+            self.packets_generated_total
+                .inc_by(stats.total_packets_generated - seen);
+        }
+    }
+
+    /// Refresh metrics from the simulator's current statistics
+    pub async fn refresh(&self, simulator: &SatelliteSimulator) {
+        let stats = simulator.get_simulation_statistics().await;
+        self.update(&stats);
+    }
+
+    /// Encode all registered metrics in the Prometheus text exposition format
+    pub fn encode_text(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        String::from_utf8(buffer).map_err(|e| OrbitalMechanicsError::MetricsError(e.to_string()))
+    }
+}
+
+/// Router serving `/metrics`, refreshed from the simulator on every scrape,
+/// and the `/healthz` probe
+pub fn router(simulator: Arc<SatelliteSimulator>, metrics: Arc<SimulatorMetrics>) -> Router {
+    Router::new()
+        .route("/metrics", get(scrape))
+        .route("/healthz", get(healthz))
+        .with_state(MetricsState { simulator, metrics })
+}
+
+/// Serve `/metrics` over HTTP, refreshing from the simulator on every scrape
+///
+/// Runs until the listener fails; spawn it on the runtime next to the simulation loop.
+pub async fn serve_metrics(
+    addr: SocketAddr,
+    simulator: Arc<SatelliteSimulator>,
+    metrics: Arc<SimulatorMetrics>,
+) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Prometheus exporter listening on http://{}/metrics", addr);
+    axum::serve(listener, router(simulator, metrics)).await?;
+    Ok(())
+}
+
+#[derive(Clone)]
+struct MetricsState {
+    simulator: Arc<SatelliteSimulator>,
+    metrics: Arc<SimulatorMetrics>,
+}
+
+async fn scrape(State(state): State<MetricsState>) -> Response {
+    state.metrics.refresh(&state.simulator).await;
+    match state.metrics.encode_text() {
+        Ok(body) => ([(header::CONTENT_TYPE, TEXT_FORMAT)], body).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+async fn healthz() -> Response {
+    let report = self_check();
+    let status =
+        StatusCode::from_u16(report.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(report)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagator::{create_propagator, PropagatorType};
+    use crate::satellite_simulator::MeoEnvironmentalConditions;
+    use chrono::Utc;
+
+    fn stats(packets: u64) -> SimulationStatistics {
+        SimulationStatistics {
+            total_satellites: 3,
+            active_satellites: 2,
+            total_unicode_packets: packets as usize,
+            total_packets_generated: packets,
+            obstruction_warnings: 1,
+            link_availability_percent: 87.5,
+            simulation_time: Utc::now(),
+            environmental_conditions: MeoEnvironmentalConditions::default(),
+            data_volume: Default::default(),
+            power: Default::default(),
+            health: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_metrics_update_and_encode() {
+        let metrics = SimulatorMetrics::new().unwrap();
+        metrics.update(&stats(10));
+        metrics.update(&stats(25));
+
+        let text = metrics.encode_text().unwrap();
+        assert!(text.contains("sx9_orbital_satellites_total 3"));
+        assert!(text.contains("sx9_orbital_unicode_packets_generated_total 25"));
+        assert!(text.contains("sx9_orbital_link_availability_percent 87.5"));
+    }
+
+    #[tokio::test]
+    async fn test_scrape_refreshes_from_simulator() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let state = MetricsState {
+            simulator: Arc::new(SatelliteSimulator::new(propagator)),
+            metrics: Arc::new(SimulatorMetrics::new().unwrap()),
+        };
+
+        let response = scrape(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], TEXT_FORMAT);
+    }
+
+    #[tokio::test]
+    async fn test_healthz_runs_self_check() {
+        let response = healthz().await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    }
+}
@@ -0,0 +1,518 @@
+//! Antenna mount models and limits
+//!
+//! Stations point with different mounts, and each steers its own pair of
+//! axes. `MountType` converts look angles into those axis angles and rates
+//! so pointing schedules can drive the mount directly:
+//!
+//! - Az/El: azimuth about the local vertical, then elevation.
+//! - X/Y: X tilts east or west about a horizontal north-south axis, Y tilts
+//!   north or south out of that plane. Its keyholes sit on the east and west
+//!   horizon, so it follows overhead passes smoothly.
+//! - Equatorial: hour angle about an axis parallel to Earth's, then
+//!   declination.
+//!
+//! An Az/El mount cannot follow every pass. Its azimuth cable wrap allows a
+//! limited travel either side of north, so a pass that keeps turning the same
+//! way forces an unwind mid-contact. Near zenith the azimuth rate needed to
+//! stay on target grows without bound (the keyhole). `AzElLimits` samples a
+//! pass and records both in `VisibilityWindow::mount_conflicts`, so the
+//! scheduler can split or skip the contact.
+
+use crate::coordinates::{unwrap_angles_deg, EarthModel};
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::orbit::SatelliteOrbit;
+use crate::propagator::OrbitalPropagator;
+use crate::time::elapsed_seconds;
+use crate::visibility::{MountConflict, MountConflictKind, VisibilityWindow};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Travel range and slew rate of one mount axis
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisLimits {
+    pub min_deg: f64,
+    pub max_deg: f64,
+    pub max_rate_deg_per_s: f64,
+}
+
+impl AxisLimits {
+    pub fn new(min_deg: f64, max_deg: f64, max_rate_deg_per_s: f64) -> Self {
+        Self {
+            min_deg,
+            max_deg,
+            max_rate_deg_per_s,
+        }
+    }
+
+    /// Whether the axis can reach `angle_deg`
+    pub fn contains(&self, angle_deg: f64) -> bool {
+        (self.min_deg..=self.max_deg).contains(&angle_deg)
+    }
+}
+
+/// Mount axis angles and rates at one pointing sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MountAxes {
+    /// Lower axis: azimuth, X or hour angle
+    pub primary_deg: f64,
+    /// Upper axis: elevation, Y or declination
+    pub secondary_deg: f64,
+    pub primary_rate_deg_per_s: f64,
+    pub secondary_rate_deg_per_s: f64,
+    /// Both angles and rates are inside the mount limits
+    pub within_limits: bool,
+}
+
+/// Antenna mount geometry with its axis limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MountType {
+    AzEl(AzElLimits),
+    XY {
+        x: AxisLimits,
+        y: AxisLimits,
+    },
+    Equatorial {
+        hour_angle: AxisLimits,
+        declination: AxisLimits,
+    },
+}
+
+impl Default for MountType {
+    fn default() -> Self {
+        MountType::AzEl(AzElLimits::default())
+    }
+}
+
+impl MountType {
+    /// X/Y mount reaching 80° either way on both axes at 5°/s
+    pub fn xy() -> Self {
+        MountType::XY {
+            x: AxisLimits::new(-80.0, 80.0, 5.0),
+            y: AxisLimits::new(-80.0, 80.0, 5.0),
+        }
+    }
+
+    /// Equatorial mount covering ±6 h of hour angle at 2°/s
+    pub fn equatorial() -> Self {
+        MountType::Equatorial {
+            hour_angle: AxisLimits::new(-90.0, 90.0, 2.0),
+            declination: AxisLimits::new(-90.0, 90.0, 2.0),
+        }
+    }
+
+    /// Column-friendly names of the primary and secondary axes
+    pub fn axis_names(&self) -> [&'static str; 2] {
+        match self {
+            MountType::AzEl(_) => ["az_axis", "el_axis"],
+            MountType::XY { .. } => ["x", "y"],
+            MountType::Equatorial { .. } => ["hour_angle", "declination"],
+        }
+    }
+
+    /// Limits of the primary and secondary axes
+    pub fn axis_limits(&self) -> [AxisLimits; 2] {
+        match self {
+            MountType::AzEl(limits) => [limits.azimuth_axis(), limits.elevation_axis()],
+            MountType::XY { x, y } => [*x, *y],
+            MountType::Equatorial {
+                hour_angle,
+                declination,
+            } => [*hour_angle, *declination],
+        }
+    }
+
+    /// Axis angles for a look direction from a station at `latitude_deg`
+    ///
+    /// Azimuth is returned in [0, 360) and hour angle in (-180, 180]; `track`
+    /// places them on the wrap the mount can reach.
+    pub fn axis_angles(&self, azimuth_deg: f64, elevation_deg: f64, latitude_deg: f64) -> [f64; 2] {
+        let (az, el, lat) = (
+            azimuth_deg.to_radians(),
+            elevation_deg.to_radians(),
+            latitude_deg.to_radians(),
+        );
+        match self {
+            MountType::AzEl(_) => [azimuth_deg.rem_euclid(360.0), elevation_deg],
+            MountType::XY { .. } => {
+                let east = el.cos() * az.sin();
+                let north = el.cos() * az.cos();
+                [
+                    east.atan2(el.sin()).to_degrees(),
+                    north.clamp(-1.0, 1.0).asin().to_degrees(),
+                ]
+            }
+            MountType::Equatorial { .. } => {
+                let hour_angle = (-az.sin() * el.cos())
+                    .atan2(lat.cos() * el.sin() - lat.sin() * el.cos() * az.cos());
+                let declination = (lat.sin() * el.sin() + lat.cos() * el.cos() * az.cos())
+                    .clamp(-1.0, 1.0)
+                    .asin();
+                [hour_angle.to_degrees(), declination.to_degrees()]
+            }
+        }
+    }
+
+    /// Axis angles, rates and limit checks along a sampled pass
+    ///
+    /// `samples` holds (time, azimuth, elevation). The primary axis stays
+    /// continuous and starts on the wrap it can follow longest. Rates are
+    /// central differences, one-sided at the ends.
+    pub fn track(
+        &self,
+        samples: &[(DateTime<Utc>, f64, f64)],
+        latitude_deg: f64,
+    ) -> Vec<MountAxes> {
+        let [primary_limits, secondary_limits] = self.axis_limits();
+        let angles: Vec<[f64; 2]> = samples
+            .iter()
+            .map(|&(_, azimuth, elevation)| self.axis_angles(azimuth, elevation, latitude_deg))
+            .collect();
+        let primary: Vec<f64> = angles.iter().map(|a| a[0]).collect();
+        let mut primary = unwrap_angles_deg(&primary);
+        let offset = wrap_offset(&primary, &primary_limits).unwrap_or(0.0);
+        primary.iter_mut().for_each(|angle| *angle += offset);
+        let secondary: Vec<f64> = angles.iter().map(|a| a[1]).collect();
+
+        let rate = |series: &[f64], i: usize| {
+            let (a, b) = (i.saturating_sub(1), (i + 1).min(series.len() - 1));
+            let seconds = elapsed_seconds(samples[a].0, samples[b].0);
+            if seconds > 0.0 {
+                (series[b] - series[a]) / seconds
+            } else {
+                0.0
+            }
+        };
+
+        (0..samples.len())
+            .map(|i| {
+                let primary_rate = rate(&primary, i);
+                let secondary_rate = rate(&secondary, i);
+                MountAxes {
+                    primary_deg: primary[i],
+                    secondary_deg: secondary[i],
+                    primary_rate_deg_per_s: primary_rate,
+                    secondary_rate_deg_per_s: secondary_rate,
+                    within_limits: primary_limits.contains(primary[i])
+                        && secondary_limits.contains(secondary[i])
+                        && primary_rate.abs() <= primary_limits.max_rate_deg_per_s
+                        && secondary_rate.abs() <= secondary_limits.max_rate_deg_per_s,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Offset of k·360° (|k| ≤ 1) that starts `unwrapped` inside `limits` and stays longest
+fn wrap_offset(unwrapped: &[f64], limits: &AxisLimits) -> Option<f64> {
+    let first = *unwrapped.first()?;
+    let first_unreachable = |offset: f64| {
+        unwrapped
+            .iter()
+            .position(|angle| !limits.contains(angle + offset))
+            .unwrap_or(usize::MAX)
+    };
+    [-360.0, 0.0, 360.0]
+        .into_iter()
+        .filter(|offset| limits.contains(first + offset))
+        .max_by_key(|&offset| first_unreachable(offset))
+}
+
+/// Travel, keyhole and slew limits of an Az/El mount
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AzElLimits {
+    /// Azimuth travel either side of north, at least 180° (e.g. ±270°)
+    pub cable_wrap_deg: f64,
+    /// Elevation above which a transit counts as passing through the keyhole
+    pub keyhole_elevation_deg: f64,
+    /// Fastest sustained azimuth slew
+    pub max_azimuth_rate_deg_per_s: f64,
+    /// Fastest sustained elevation slew
+    pub max_elevation_rate_deg_per_s: f64,
+}
+
+impl Default for AzElLimits {
+    fn default() -> Self {
+        Self {
+            cable_wrap_deg: 270.0,
+            keyhole_elevation_deg: 87.0,
+            max_azimuth_rate_deg_per_s: 10.0,
+            max_elevation_rate_deg_per_s: 5.0,
+        }
+    }
+}
+
+impl AzElLimits {
+    pub fn with_cable_wrap_deg(mut self, cable_wrap_deg: f64) -> Self {
+        self.cable_wrap_deg = cable_wrap_deg;
+        self
+    }
+
+    pub fn with_keyhole_elevation_deg(mut self, keyhole_elevation_deg: f64) -> Self {
+        self.keyhole_elevation_deg = keyhole_elevation_deg;
+        self
+    }
+
+    pub fn with_max_azimuth_rate(mut self, max_azimuth_rate_deg_per_s: f64) -> Self {
+        self.max_azimuth_rate_deg_per_s = max_azimuth_rate_deg_per_s;
+        self
+    }
+
+    pub fn with_max_elevation_rate(mut self, max_elevation_rate_deg_per_s: f64) -> Self {
+        self.max_elevation_rate_deg_per_s = max_elevation_rate_deg_per_s;
+        self
+    }
+
+    /// Azimuth travel across the cable wrap
+    pub fn azimuth_axis(&self) -> AxisLimits {
+        AxisLimits::new(
+            -self.cable_wrap_deg,
+            self.cable_wrap_deg,
+            self.max_azimuth_rate_deg_per_s,
+        )
+    }
+
+    /// Elevation travel from the horizon to zenith
+    pub fn elevation_axis(&self) -> AxisLimits {
+        AxisLimits::new(0.0, 90.0, self.max_elevation_rate_deg_per_s)
+    }
+
+    /// Time to turn the azimuth axis through a full revolution
+    pub fn unwind_seconds(&self) -> f64 {
+        360.0 / self.max_azimuth_rate_deg_per_s
+    }
+
+    /// Sample a pass every `step_seconds` from `station` on `earth_model` and
+    /// replace `window.mount_conflicts`
+    ///
+    /// A cable wrap conflict runs from the last sample the mount can reach
+    /// on its current wrap until a full unwind completes. A keyhole conflict
+    /// spans consecutive samples above the keyhole elevation or needing more
+    /// than the maximum azimuth rate.
+    pub fn annotate_window(
+        &self,
+        window: &mut VisibilityWindow,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        earth_model: &EarthModel,
+        propagator: &dyn OrbitalPropagator,
+        step_seconds: f64,
+    ) -> Result<()> {
+        self.validate()?;
+        if !step_seconds.is_finite() || step_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Mount sampling step must be positive, got {} s",
+                step_seconds
+            )));
+        }
+
+        let step = Duration::milliseconds((step_seconds * 1000.0).round().max(1.0) as i64);
+        let mut track = Vec::new();
+        let mut time = window.start_time;
+        loop {
+            let look_angles = propagator
+                .propagate(satellite, time)
+                .for_satellite(&satellite.satellite_id)
+                .for_station(&station.station_id)
+                .at_epoch(time)?
+                .look_angles_from_station_with_model(
+                    earth_model,
+                    station.position.latitude_deg,
+                    station.position.longitude_deg,
+                    station.position.elevation_m,
+                );
+            track.push((time, look_angles.azimuth_deg, look_angles.elevation_deg));
+
+            if time >= window.end_time {
+                break;
+            }
+            time = (time + step).min(window.end_time);
+        }
+
+        let mut conflicts = self.keyhole_conflicts(&track);
+        conflicts.extend(self.cable_wrap_conflicts(&track, window.end_time));
+        conflicts.sort_by_key(|conflict| conflict.start_time);
+        window.mount_conflicts = conflicts;
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(180.0..=360.0).contains(&self.cable_wrap_deg) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Cable wrap must be between ±180° and ±360°, got ±{}°",
+                self.cable_wrap_deg
+            )));
+        }
+        if !self.max_azimuth_rate_deg_per_s.is_finite() || self.max_azimuth_rate_deg_per_s <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Maximum azimuth rate must be positive, got {}°/s",
+                self.max_azimuth_rate_deg_per_s
+            )));
+        }
+        Ok(())
+    }
+
+    /// Runs of samples above the keyhole elevation or beyond the azimuth rate
+    fn keyhole_conflicts(&self, track: &[(DateTime<Utc>, f64, f64)]) -> Vec<MountConflict> {
+        let mut conflicts: Vec<MountConflict> = Vec::new();
+        let mut open = false;
+        for (i, &(time, _, elevation_deg)) in track.iter().enumerate() {
+            let too_fast = i > 0 && {
+                let (previous_time, previous_azimuth, _) = track[i - 1];
+                let turn_deg = (track[i].1 - previous_azimuth + 180.0).rem_euclid(360.0) - 180.0;
+                let seconds = (time - previous_time).num_milliseconds() as f64 / 1000.0;
+                seconds > 0.0 && turn_deg.abs() / seconds > self.max_azimuth_rate_deg_per_s
+            };
+            let in_keyhole = elevation_deg >= self.keyhole_elevation_deg || too_fast;
+
+            match conflicts.last_mut() {
+                Some(conflict) if open && in_keyhole => conflict.end_time = time,
+                _ if in_keyhole => conflicts.push(MountConflict {
+                    kind: MountConflictKind::Keyhole,
+                    // A rate violation starts at the sample before the jump
+                    start_time: if too_fast { track[i - 1].0 } else { time },
+                    end_time: time,
+                }),
+                _ => {}
+            }
+            open = in_keyhole;
+        }
+        conflicts
+    }
+
+    /// Unwinds needed to follow the track, starting on the wrap that lasts longest
+    fn cable_wrap_conflicts(
+        &self,
+        track: &[(DateTime<Utc>, f64, f64)],
+        end_time: DateTime<Utc>,
+    ) -> Vec<MountConflict> {
+        let azimuths: Vec<f64> = track.iter().map(|&(_, azimuth, _)| azimuth).collect();
+        let unwrapped = unwrap_angles_deg(&azimuths);
+        let limits = self.azimuth_axis();
+        let Some(mut offset) = wrap_offset(&unwrapped, &limits) else {
+            return Vec::new();
+        };
+        let first_unreachable = |from: usize, offset: f64| {
+            (from..unwrapped.len()).find(|&i| !limits.contains(unwrapped[i] + offset))
+        };
+
+        let unwind = Duration::milliseconds((self.unwind_seconds() * 1000.0) as i64);
+        let mut conflicts = Vec::new();
+        let mut from = 0;
+        while let Some(index) = first_unreachable(from, offset) {
+            let start_time = track[index.saturating_sub(1)].0;
+            conflicts.push(MountConflict {
+                kind: MountConflictKind::CableWrap,
+                start_time,
+                end_time: (start_time + unwind).min(end_time),
+            });
+            offset -= 360f64.copysign(unwrapped[index] + offset);
+            from = index;
+        }
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn track(samples: &[(f64, f64)]) -> Vec<(DateTime<Utc>, f64, f64)> {
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, &(azimuth, elevation))| {
+                (start + Duration::seconds(10 * i as i64), azimuth, elevation)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cable_wrap_and_keyhole_detection() {
+        let limits = AzElLimits::default();
+
+        // Turning east through 600° cannot fit in ±270° from any start
+        let spiral: Vec<(f64, f64)> = (0..=30)
+            .map(|i| ((200.0 + 20.0 * i as f64) % 360.0, 30.0))
+            .collect();
+        let spiral = track(&spiral);
+        let conflicts = limits.cable_wrap_conflicts(&spiral, spiral[30].0);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, MountConflictKind::CableWrap);
+        // Starting at -160°, sample 22 reaches 280°: unwind from sample 21
+        assert_eq!(conflicts[0].start_time, spiral[21].0);
+        assert_eq!(
+            conflicts[0].end_time - conflicts[0].start_time,
+            Duration::seconds(36)
+        );
+
+        // A 300° sweep west through north fits when the mount starts at +250°
+        let sweep: Vec<(f64, f64)> = (0..=15)
+            .map(|i| ((250.0 - 20.0 * i as f64).rem_euclid(360.0), 30.0))
+            .collect();
+        assert!(limits
+            .cable_wrap_conflicts(&track(&sweep), spiral[30].0)
+            .is_empty());
+
+        // Near-zenith flip: 180° of azimuth in one 10 s step
+        let overhead = track(&[(90.0, 70.0), (88.0, 86.0), (268.0, 88.5), (270.0, 70.0)]);
+        let keyhole = limits.keyhole_conflicts(&overhead);
+        assert_eq!(keyhole.len(), 1);
+        assert_eq!(keyhole[0].kind, MountConflictKind::Keyhole);
+        assert_eq!(
+            (keyhole[0].start_time, keyhole[0].end_time),
+            (overhead[1].0, overhead[2].0)
+        );
+        assert!(limits
+            .keyhole_conflicts(&track(&[(90.0, 40.0), (95.0, 45.0)]))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_mount_axis_conversion() {
+        let assert_axes = |actual: [f64; 2], expected: [f64; 2]| {
+            assert!(
+                (actual[0] - expected[0]).abs() < 1e-9 && (actual[1] - expected[1]).abs() < 1e-9,
+                "{:?} != {:?}",
+                actual,
+                expected
+            );
+        };
+        let xy = MountType::xy();
+        let equatorial = MountType::equatorial();
+
+        // Zenith is the centre of an X/Y mount; the east horizon is its keyhole
+        assert_axes(xy.axis_angles(123.0, 90.0, 40.0), [0.0, 0.0]);
+        assert_axes(xy.axis_angles(90.0, 0.0, 40.0), [90.0, 0.0]);
+        assert_axes(xy.axis_angles(0.0, 45.0, 40.0), [0.0, 45.0]);
+
+        // From the equator, zenith is on the celestial equator at transit and
+        // the east horizon six hours before it
+        assert_axes(equatorial.axis_angles(0.0, 90.0, 0.0), [0.0, 0.0]);
+        assert_axes(equatorial.axis_angles(90.0, 0.0, 0.0), [-90.0, 0.0]);
+        // The celestial pole sits due north at the station latitude
+        assert!((equatorial.axis_angles(0.0, 52.0, 52.0)[1] - 90.0).abs() < 1e-6);
+
+        // Az/El track across north stays continuous within the cable wrap
+        let azel = MountType::AzEl(AzElLimits::default());
+        let samples = track(&[(340.0, 20.0), (350.0, 25.0), (0.0, 30.0), (10.0, 35.0)]);
+        let axes = azel.track(&samples, 60.0);
+        assert_eq!(
+            axes.iter().map(|a| a.primary_deg).collect::<Vec<_>>(),
+            vec![-20.0, -10.0, 0.0, 10.0]
+        );
+        assert!(axes.iter().all(|a| a.within_limits));
+        assert!((axes[1].primary_rate_deg_per_s - 1.0).abs() < 1e-9);
+        assert!((axes[0].secondary_rate_deg_per_s - 0.5).abs() < 1e-9);
+
+        // The same pass is too fast for a slow X/Y mount
+        let slow = MountType::XY {
+            x: AxisLimits::new(-80.0, 80.0, 0.01),
+            y: AxisLimits::new(-80.0, 80.0, 0.01),
+        };
+        assert!(slow.track(&samples, 60.0).iter().any(|a| !a.within_limits));
+        assert_eq!(slow.axis_names(), ["x", "y"]);
+    }
+}
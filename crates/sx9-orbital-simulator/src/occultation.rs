@@ -0,0 +1,306 @@
+//! Satellite-to-satellite atmospheric occultation geometry
+//!
+//! A radio or laser occultation experiment sends a signal from one
+//! satellite to another along a ray that grazes the atmosphere; as the pair
+//! moves, the ray's tangent point sweeps down (setting) or up (rising)
+//! through the atmosphere and the received signal profiles it by height.
+//! `occultation_events` finds when the tangent point of a pair lies
+//! between the Earth's surface and the top of the atmosphere, and traces
+//! where that tangent point sits over the ground.
+//!
+//! The Earth is a sphere here and rays are straight, so bending in the
+//! lower atmosphere is ignored. Tangent points are rotated into the
+//! Earth-fixed frame with GMST for their latitude and longitude. Events are
+//! sampled at the configured step; their edges are the first and last
+//! samples inside the atmosphere.
+
+use crate::constants::EARTH_RADIUS_KM;
+use crate::coordinates::EarthModel;
+use crate::ephemeris::gmst_rad;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::SatelliteOrbit;
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+/// Atmospheric shell and sampling for occultation searches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OccultationConfig {
+    /// Rays grazing above this altitude miss the atmosphere
+    pub top_altitude_km: f64,
+    /// Rays grazing below this altitude are cut off by the Earth
+    pub bottom_altitude_km: f64,
+    pub step_seconds: f64,
+}
+
+/// Whether the tangent point descends or climbs through the atmosphere
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OccultationKind {
+    Setting,
+    Rising,
+}
+
+/// Point of a ray closest to the Earth's center
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TangentPoint {
+    pub timestamp: DateTime<Utc>,
+    pub altitude_km: f64,
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+}
+
+/// Interval when the ray between two satellites crosses the atmosphere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OccultationEvent {
+    pub transmitter_id: String,
+    pub receiver_id: String,
+    pub kind: OccultationKind,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub duration_seconds: f64,
+    /// Lowest altitude the ray sounds
+    pub min_tangent_altitude_km: f64,
+    /// Tangent point at every sample, in time order
+    pub tangent_points: Vec<TangentPoint>,
+}
+
+impl Default for OccultationConfig {
+    fn default() -> Self {
+        Self {
+            top_altitude_km: 100.0,
+            bottom_altitude_km: 0.0,
+            step_seconds: 1.0,
+        }
+    }
+}
+
+impl OccultationConfig {
+    pub fn new(bottom_altitude_km: f64, top_altitude_km: f64) -> Self {
+        Self {
+            top_altitude_km,
+            bottom_altitude_km,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_step(mut self, step_seconds: f64) -> Self {
+        self.step_seconds = step_seconds;
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.step_seconds.is_finite() || self.step_seconds < 0.001 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Occultation step must be at least 1 ms, got {} s",
+                self.step_seconds
+            )));
+        }
+        if self.bottom_altitude_km.is_nan() || self.bottom_altitude_km >= self.top_altitude_km {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Occultation shell bottom {} km must lie below its top {} km",
+                self.bottom_altitude_km, self.top_altitude_km
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl OccultationEvent {
+    /// Tangent point at the lowest altitude the ray sounds
+    pub fn lowest_tangent_point(&self) -> Option<&TangentPoint> {
+        self.tangent_points
+            .iter()
+            .min_by(|a, b| a.altitude_km.total_cmp(&b.altitude_km))
+    }
+}
+
+/// Occultations between `transmitter` and `receiver` over `duration_hours`
+pub fn occultation_events(
+    transmitter: &SatelliteOrbit,
+    receiver: &SatelliteOrbit,
+    start_time: DateTime<Utc>,
+    duration_hours: f64,
+    config: &OccultationConfig,
+    propagator: &dyn OrbitalPropagator,
+) -> Result<Vec<OccultationEvent>> {
+    config.validate()?;
+    let end_time = start_time + Duration::milliseconds((duration_hours * 3.6e6) as i64);
+    let step = Duration::milliseconds((config.step_seconds * 1000.0).round() as i64);
+
+    let mut events = Vec::new();
+    let mut current: Option<Vec<TangentPoint>> = None;
+    let mut time = start_time;
+    while time <= end_time {
+        let position = |satellite: &SatelliteOrbit| {
+            propagator
+                .propagate(satellite, time)
+                .for_satellite(&satellite.satellite_id)
+                .at_epoch(time)
+                .map(|state| state.position_eci)
+        };
+        let tangent =
+            tangent_point(position(transmitter)?, position(receiver)?, time).filter(|point| {
+                (config.bottom_altitude_km..=config.top_altitude_km).contains(&point.altitude_km)
+            });
+        match (tangent, &mut current) {
+            (Some(point), Some(points)) => points.push(point),
+            (Some(point), None) => current = Some(vec![point]),
+            (None, _) => events.extend(current.take()),
+        }
+        time += step;
+    }
+    events.extend(current);
+
+    let events: Vec<OccultationEvent> = events
+        .into_iter()
+        .map(|tangent_points| {
+            // Runs are never empty
+            let first = &tangent_points[0];
+            let last = &tangent_points[tangent_points.len() - 1];
+            OccultationEvent {
+                transmitter_id: transmitter.satellite_id.clone(),
+                receiver_id: receiver.satellite_id.clone(),
+                kind: if last.altitude_km < first.altitude_km {
+                    OccultationKind::Setting
+                } else {
+                    OccultationKind::Rising
+                },
+                start_time: first.timestamp,
+                end_time: last.timestamp,
+                duration_seconds: (last.timestamp - first.timestamp).num_milliseconds() as f64
+                    / 1000.0,
+                min_tangent_altitude_km: tangent_points
+                    .iter()
+                    .map(|point| point.altitude_km)
+                    .fold(f64::INFINITY, f64::min),
+                tangent_points,
+            }
+        })
+        .collect();
+
+    tracing::debug!(
+        target: crate::trace_targets::VISIBILITY,
+        transmitter_id = %transmitter.satellite_id,
+        receiver_id = %receiver.satellite_id,
+        events = events.len(),
+        "Occultation search complete"
+    );
+    Ok(events)
+}
+
+/// Occultations between every pair of `satellites`, by start time
+///
+/// Each pair is reported once, with the earlier satellite in the input as
+/// the transmitter; the geometry is the same either way.
+pub fn constellation_occultations(
+    satellites: &[&SatelliteOrbit],
+    start_time: DateTime<Utc>,
+    duration_hours: f64,
+    config: &OccultationConfig,
+    propagator: &dyn OrbitalPropagator,
+) -> Result<Vec<OccultationEvent>> {
+    let mut events = Vec::new();
+    for (i, transmitter) in satellites.iter().enumerate() {
+        for receiver in &satellites[i + 1..] {
+            events.extend(occultation_events(
+                transmitter,
+                receiver,
+                start_time,
+                duration_hours,
+                config,
+                propagator,
+            )?);
+        }
+    }
+    events.sort_by_key(|event| event.start_time);
+    Ok(events)
+}
+
+/// Tangent point of the ray from `a` to `b`, if it lies between them
+///
+/// Otherwise the ray is closest to the Earth at one of its ends and does
+/// not graze.
+fn tangent_point(a: [f64; 3], b: [f64; 3], time: DateTime<Utc>) -> Option<TangentPoint> {
+    let a = Vector3::from(a);
+    let d = Vector3::from(b) - a;
+    let length_sq = d.norm_squared();
+    if length_sq <= 0.0 {
+        return None;
+    }
+    let t = -a.dot(&d) / length_sq;
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    let point = a + d * t;
+
+    let (sin_gmst, cos_gmst) = gmst_rad(time).sin_cos();
+    let earth_fixed = [
+        cos_gmst * point.x + sin_gmst * point.y,
+        -sin_gmst * point.x + cos_gmst * point.y,
+        point.z,
+    ];
+    let geodetic = EarthModel::Sphere.ecef_to_geodetic(earth_fixed);
+    Some(TangentPoint {
+        timestamp: time,
+        altitude_km: point.norm() - EARTH_RADIUS_KM,
+        latitude_deg: geodetic.latitude_deg,
+        longitude_deg: geodetic.longitude_deg,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_occultation_events() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let satellite = |id: &str, a: f64, mean_anomaly: f64| {
+            let elements = OrbitalElements::new(a, 0.0, 0.0, 0.0, 0.0, mean_anomaly).unwrap();
+            SatelliteOrbit::new(id.to_string(), id.to_string(), elements, epoch)
+        };
+        // Coplanar LEO and MEO: the LEO satellite drifts behind the Earth
+        // as seen from the MEO one and back out again
+        let leo = satellite("LEO-1", 7000.0, 60.0);
+        let meo = satellite("MEO-1", 14378.0, 0.0);
+        let propagator = KeplerianPropagator::new();
+        let config = OccultationConfig::default().with_step(2.0);
+
+        let events = occultation_events(&meo, &leo, epoch, 6.0, &config, &propagator).unwrap();
+        assert!(events.len() >= 2, "{:?}", events.len());
+        assert!(events.iter().any(|e| e.kind == OccultationKind::Setting));
+        assert!(events.iter().any(|e| e.kind == OccultationKind::Rising));
+        for event in &events {
+            assert!(event.duration_seconds > 0.0);
+            assert!((0.0..=100.0).contains(&event.min_tangent_altitude_km));
+            // Equatorial orbits graze over the equator
+            assert!(event.lowest_tangent_point().unwrap().latitude_deg.abs() < 1e-6);
+        }
+
+        let all =
+            constellation_occultations(&[&meo, &leo], epoch, 6.0, &config, &propagator).unwrap();
+        assert_eq!(all.len(), events.len());
+        assert!(occultation_events(
+            &meo,
+            &leo,
+            epoch,
+            6.0,
+            &OccultationConfig::new(100.0, 0.0),
+            &propagator
+        )
+        .is_err());
+        assert!(occultation_events(
+            &meo,
+            &leo,
+            epoch,
+            6.0,
+            &OccultationConfig::default().with_step(0.0004),
+            &propagator
+        )
+        .is_err());
+    }
+}
@@ -5,10 +5,20 @@ use chrono::{DateTime, Utc};
 use std::f64::consts::PI;
 use crate::constants::*;
 use crate::constants::validation::*;
-use crate::error::{OrbitalMechanicsError, Result};
+use crate::config::ClassificationTolerances;
+use crate::constellation::{PlaneId, SlotAssignment, SlotId};
+use crate::coordinates::{EarthModel, Position3D, TopocentricFrame};
+use crate::ephemeris;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::propagation_core::{
+    mean_motion, mean_to_osculating, propagate_j2, state_to_elements, Elements, StateVector,
+};
+use crate::units::{Degrees, Kilometers, Seconds};
+use nalgebra::{Matrix6, Vector6};
 
 /// Classical orbital elements (Keplerian elements)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct OrbitalElements {
     /// Semi-major axis in kilometers
     pub semi_major_axis_km: f64,
@@ -31,6 +41,7 @@ pub struct OrbitalElements {
 
 /// Complete satellite orbital definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SatelliteOrbit {
     /// Unique satellite identifier
     pub satellite_id: String,
@@ -52,6 +63,10 @@ pub struct SatelliteOrbit {
 
     /// Mean motion in radians per second
     pub mean_motion_rad_per_sec: f64,
+
+    /// Assigned orbital plane and slot, if part of a designed pattern
+    #[serde(default)]
+    pub slot: Option<SlotAssignment>,
 }
 
 /// Current satellite state (position and velocity)
@@ -132,6 +147,31 @@ impl OrbitalElements {
         2.0 * PI * (self.semi_major_axis_km.powi(3) / EARTH_MU).sqrt()
     }
 
+    /// Orbital period as a typed duration
+    pub fn period(&self) -> Seconds {
+        Seconds(self.calculate_period())
+    }
+
+    pub fn semi_major_axis(&self) -> Kilometers {
+        Kilometers(self.semi_major_axis_km)
+    }
+
+    pub fn inclination(&self) -> Degrees {
+        Degrees(self.inclination_deg)
+    }
+
+    pub fn raan(&self) -> Degrees {
+        Degrees(self.raan_deg)
+    }
+
+    pub fn argument_of_perigee(&self) -> Degrees {
+        Degrees(self.argument_of_perigee_deg)
+    }
+
+    pub fn mean_anomaly(&self) -> Degrees {
+        Degrees(self.mean_anomaly_deg)
+    }
+
     /// Calculate mean motion in revolutions per day
     pub fn calculate_mean_motion_rev_per_day(&self) -> f64 {
         SOLAR_DAY_SECONDS / self.calculate_period()
@@ -177,6 +217,250 @@ impl OrbitalElements {
         }
     }
 
+    /// Classify orbit by altitude regime and special orbit family
+    pub fn classify(&self, tolerances: &ClassificationTolerances) -> OrbitClassificationDetail {
+        let regime = self.orbit_classification();
+        let mut special = Vec::new();
+        let mut reasons = vec![format!(
+            "{:?}: mean altitude {:.0} km",
+            regime,
+            self.semi_major_axis_km - EARTH_RADIUS_KM
+        )];
+
+        let nodal_rate = self.nodal_precession_rate_deg_per_day();
+        if (nodal_rate - SUN_SYNCHRONOUS_NODAL_RATE_DEG_PER_DAY).abs()
+            <= tolerances.sun_synchronous_rate_deg_per_day
+        {
+            special.push(SpecialOrbit::SunSynchronous);
+            reasons.push(format!(
+                "sun-synchronous: J2 nodal rate {:.4} deg/day matches mean solar rate {:.4} deg/day",
+                nodal_rate, SUN_SYNCHRONOUS_NODAL_RATE_DEG_PER_DAY
+            ));
+        }
+
+        let critical_offset = (self.inclination_deg - CRITICAL_INCLINATION_DEG)
+            .abs()
+            .min((self.inclination_deg - (180.0 - CRITICAL_INCLINATION_DEG)).abs());
+        let near_critical = critical_offset <= tolerances.critical_inclination_deg;
+        if near_critical {
+            special.push(SpecialOrbit::CriticalInclination);
+            reasons.push(format!(
+                "near-critical inclination: {:.3}° is {:.3}° from critical",
+                self.inclination_deg, critical_offset
+            ));
+        }
+
+        let period = self.calculate_period();
+        let period_matches =
+            |target: f64| ((period - target) / target).abs() <= tolerances.period_fraction;
+        if near_critical
+            && self.eccentricity >= tolerances.molniya_min_eccentricity
+            && period_matches(SIDEREAL_DAY_SECONDS / 2.0)
+        {
+            special.push(SpecialOrbit::Molniya);
+            reasons.push(format!(
+                "Molniya: period {:.0} s is half a sidereal day with e = {:.3}",
+                period, self.eccentricity
+            ));
+        } else if near_critical
+            && self.eccentricity >= tolerances.tundra_min_eccentricity
+            && period_matches(SIDEREAL_DAY_SECONDS)
+        {
+            special.push(SpecialOrbit::Tundra);
+            reasons.push(format!(
+                "Tundra: period {:.0} s is one sidereal day with e = {:.3}",
+                period, self.eccentricity
+            ));
+        }
+
+        let frozen_eccentricity = self.frozen_eccentricity();
+        if (self.argument_of_perigee_deg - 90.0).abs() <= tolerances.frozen_argument_of_perigee_deg
+            && (self.eccentricity - frozen_eccentricity).abs() <= tolerances.frozen_eccentricity
+        {
+            special.push(SpecialOrbit::Frozen);
+            reasons.push(format!(
+                "frozen: argument of perigee {:.2}° and e = {:.5} match J2/J3 frozen eccentricity {:.5}",
+                self.argument_of_perigee_deg, self.eccentricity, frozen_eccentricity
+            ));
+        }
+
+        OrbitClassificationDetail {
+            regime,
+            special,
+            reason: reasons.join("; "),
+        }
+    }
+
+    /// Secular RAAN drift due to J2 in degrees per day
+    pub fn nodal_precession_rate_deg_per_day(&self) -> f64 {
+        let n = self.calculate_mean_motion_rad_per_sec();
+        let p = self.semi_major_axis_km * (1.0 - self.eccentricity.powi(2));
+        let rate = -1.5 * n * EARTH_J2 * (EARTH_RADIUS_KM / p).powi(2)
+            * (self.inclination_deg * DEG_TO_RAD).cos();
+        rate * RAD_TO_DEG * SOLAR_DAY_SECONDS
+    }
+
+    /// Eccentricity that freezes the argument of perigee at 90° under J2 and J3
+    pub fn frozen_eccentricity(&self) -> f64 {
+        -0.5 * (EARTH_J3 / EARTH_J2)
+            * (EARTH_RADIUS_KM / self.semi_major_axis_km)
+            * (self.inclination_deg * DEG_TO_RAD).sin()
+    }
+
+    /// Least-squares mean elements at the first state's timestamp
+    ///
+    /// The elements follow the secular J2 model of `Sgp4Propagator`, so
+    /// propagating them reproduces the series as closely as that model
+    /// allows; numerical propagation output compresses to one element set.
+    /// The fit runs in equinoctial elements, which stay defined for circular
+    /// and equatorial orbits, starting from the osculating elements of the
+    /// first state. Velocity residuals are scaled by the inverse mean motion
+    /// so they weigh like position residuals.
+    pub fn fit_from_states(states: &[SatelliteState]) -> Result<Self> {
+        let first = states.first().ok_or_else(|| {
+            OrbitalMechanicsError::config_error("Element fit needs at least one state")
+        })?;
+        let satellite_id = first.satellite_id.as_str();
+        let initial = state_to_elements(&StateVector {
+            position_km: first.position_eci,
+            velocity_km_s: first.velocity_eci,
+        })
+        .for_satellite(satellite_id)?;
+
+        let velocity_scale = 1.0 / mean_motion(initial.semi_major_axis_km);
+        let offsets: Vec<f64> = states
+            .iter()
+            .map(|s| (s.timestamp - first.timestamp).num_milliseconds() as f64 / 1000.0)
+            .collect();
+        let residuals = |x: &Vector6<f64>| -> Result<Vec<f64>> {
+            let elements = from_equinoctial(x);
+            let mut residuals = Vec::with_capacity(6 * states.len());
+            for (state, &dt) in states.iter().zip(&offsets) {
+                let predicted = propagate_j2(&elements, dt)?;
+                for i in 0..3 {
+                    residuals.push(predicted.position_km[i] - state.position_eci[i]);
+                }
+                for i in 0..3 {
+                    let error = predicted.velocity_km_s[i] - state.velocity_eci[i];
+                    residuals.push(error * velocity_scale);
+                }
+            }
+            Ok(residuals)
+        };
+        let cost = |r: &[f64]| r.iter().map(|v| v * v).sum::<f64>();
+
+        let mut x = to_equinoctial(&initial);
+        let mut current = residuals(&x).for_satellite(satellite_id)?;
+        let steps = Vector6::new(
+            1e-7 * initial.semi_major_axis_km,
+            1e-7,
+            1e-7,
+            1e-7,
+            1e-7,
+            1e-7,
+        );
+        for _ in 0..ELEMENT_FIT_ITERATIONS {
+            // Central-difference Jacobian and Gauss-Newton normal equations
+            let mut jacobian = vec![[0.0; 6]; current.len()];
+            for column in 0..6 {
+                let mut plus = x;
+                let mut minus = x;
+                plus[column] += steps[column];
+                minus[column] -= steps[column];
+                let upper = residuals(&plus).for_satellite(satellite_id)?;
+                let lower = residuals(&minus).for_satellite(satellite_id)?;
+                for (row, (u, l)) in jacobian.iter_mut().zip(upper.iter().zip(&lower)) {
+                    row[column] = (u - l) / (2.0 * steps[column]);
+                }
+            }
+            let mut normal = Matrix6::<f64>::zeros();
+            let mut gradient = Vector6::<f64>::zeros();
+            for (row, r) in jacobian.iter().zip(&current) {
+                for i in 0..6 {
+                    gradient[i] += row[i] * r;
+                    for j in 0..6 {
+                        normal[(i, j)] += row[i] * row[j];
+                    }
+                }
+            }
+            let Some(delta) = normal.lu().solve(&-gradient) else {
+                return Err(OrbitalMechanicsError::propagation_error(format!(
+                    "Element fit for {} is singular; the states do not constrain the orbit",
+                    satellite_id
+                )));
+            };
+
+            // Halve the step until the fit improves
+            let mut scale = 1.0;
+            let mut accepted = None;
+            while scale > 1e-6 {
+                let trial = x + delta * scale;
+                if let Ok(r) = residuals(&trial) {
+                    if cost(&r) <= cost(&current) {
+                        accepted = Some((trial, r));
+                        break;
+                    }
+                }
+                scale *= 0.5;
+            }
+            let Some((next, r)) = accepted else {
+                break;
+            };
+            let converged = delta.component_div(&steps).amax() * scale < 1e-3;
+            x = next;
+            current = r;
+            if converged {
+                break;
+            }
+        }
+
+        Self::from_core(&from_equinoctial(&x)).for_satellite(satellite_id)
+    }
+
+    /// Osculating elements for these Brouwer mean elements
+    ///
+    /// First-order J2 short- and long-period terms (Brouwer-Lyddane), so TLE
+    /// mean elements can be compared with numerically propagated states.
+    /// Orbits near the critical inclination are refused.
+    pub fn to_osculating(&self) -> Result<Self> {
+        Self::from_core(&mean_to_osculating(&Elements::from(&self.to_radians()))?)
+    }
+
+    /// Brouwer mean elements for these osculating elements
+    ///
+    /// Inverts `to_osculating` by fixed-point iteration in equinoctial
+    /// elements, so the two round-trip to numerical precision.
+    pub fn to_mean(&self) -> Result<Self> {
+        let target = to_equinoctial(&Elements::from(&self.to_radians()));
+        let mut mean = target;
+        for _ in 0..MEAN_ELEMENT_ITERATIONS {
+            let mapped = to_equinoctial(&mean_to_osculating(&from_equinoctial(&mean))?);
+            let mut correction = target - mapped;
+            correction[5] = (correction[5] + PI).rem_euclid(TWO_PI) - PI;
+            mean += correction;
+            let converged = correction[0].abs() < MEAN_ELEMENT_TOLERANCE * target[0]
+                && correction.rows(1, 5).amax() < MEAN_ELEMENT_TOLERANCE;
+            if converged {
+                return Self::from_core(&from_equinoctial(&mean));
+            }
+        }
+        Err(OrbitalMechanicsError::propagation_error(format!(
+            "Mean elements did not converge in {} iterations",
+            MEAN_ELEMENT_ITERATIONS
+        )))
+    }
+
+    fn from_core(elements: &Elements) -> Result<Self> {
+        Self::new(
+            elements.semi_major_axis_km,
+            elements.eccentricity,
+            elements.inclination_rad * RAD_TO_DEG,
+            angle_0_360(elements.raan_rad),
+            angle_0_360(elements.argument_of_perigee_rad),
+            angle_0_360(elements.mean_anomaly_rad),
+        )
+    }
+
     /// Convert to radians for calculations
     pub fn to_radians(&self) -> OrbitalElementsRad {
         OrbitalElementsRad {
@@ -214,6 +498,36 @@ pub enum OrbitClassification {
     Heo,
 }
 
+/// Special orbit families detected on top of the altitude regime
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SpecialOrbit {
+    /// J2 nodal precession matches the mean motion of the Sun
+    SunSynchronous,
+    /// Critically inclined, highly eccentric, half sidereal day period
+    Molniya,
+    /// Critically inclined, eccentric, one sidereal day period
+    Tundra,
+    /// Eccentricity and argument of perigee held constant by J2/J3
+    Frozen,
+    /// Inclination near 63.4° or 116.6° (no apsidal drift)
+    CriticalInclination,
+}
+
+/// Orbit classification with special families and the reasoning behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrbitClassificationDetail {
+    pub regime: OrbitClassification,
+    pub special: Vec<SpecialOrbit>,
+    pub reason: String,
+}
+
+impl OrbitClassificationDetail {
+    /// Check whether a special orbit family was detected
+    pub fn is(&self, special: SpecialOrbit) -> bool {
+        self.special.contains(&special)
+    }
+}
+
 impl SatelliteOrbit {
     /// Create new satellite orbit
     pub fn new(
@@ -234,9 +548,26 @@ impl SatelliteOrbit {
             period_seconds,
             mean_motion_rev_per_day,
             mean_motion_rad_per_sec,
+            slot: None,
         }
     }
 
+    /// Start building an orbit; see `SatelliteOrbitBuilder` for the defaults
+    pub fn builder() -> SatelliteOrbitBuilder {
+        SatelliteOrbitBuilder::default()
+    }
+
+    /// Assign plane and slot, taking the current elements as the nominal slot
+    pub fn with_slot(mut self, plane: PlaneId, slot: SlotId) -> Self {
+        self.slot = Some(SlotAssignment {
+            plane,
+            slot,
+            nominal_elements: self.elements.clone(),
+            nominal_epoch: self.epoch,
+        });
+        self
+    }
+
     /// Create circular orbit at specified altitude and inclination
     pub fn circular_orbit(
         satellite_id: String,
@@ -288,6 +619,186 @@ impl SatelliteOrbit {
         let earth_rotation_angle = EARTH_ROTATION_RATE * self.period_seconds;
         earth_rotation_angle * EARTH_RADIUS_KM
     }
+
+    /// Angle between the orbit plane and the Sun direction at `epoch`
+    ///
+    /// Positive when the Sun is on the side of the orbit normal. The node is
+    /// carried from the element epoch with secular J2 regression.
+    pub fn beta_angle(&self, epoch: DateTime<Utc>) -> Degrees {
+        let days = (epoch - self.epoch).num_milliseconds() as f64 / 1000.0 / SOLAR_DAY_SECONDS;
+        let raan = (self.elements.raan_deg
+            + self.elements.nodal_precession_rate_deg_per_day() * days)
+            * DEG_TO_RAD;
+        let inclination = self.elements.inclination_deg * DEG_TO_RAD;
+        let normal = [
+            inclination.sin() * raan.sin(),
+            -inclination.sin() * raan.cos(),
+            inclination.cos(),
+        ];
+
+        let sun = ephemeris::sun_position_eci(epoch);
+        let sun_norm = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+        let sin_beta = (normal[0] * sun[0] + normal[1] * sun[1] + normal[2] * sun[2]) / sun_norm;
+        Degrees(sin_beta.clamp(-1.0, 1.0).asin() * RAD_TO_DEG)
+    }
+}
+
+/// Builder for `SatelliteOrbit`
+///
+/// Only the orbit size is required, as an altitude or a semi-major axis.
+/// Everything else defaults to a circular, equatorial orbit with all angles
+/// at zero, epoch now, ID `SAT-001` and the ID as name. RAAN, argument of
+/// perigee and mean anomaly may be given in any turn and are wrapped into
+/// [0, 360)°; the elements are then validated as in `OrbitalElements::new`.
+#[derive(Debug, Clone)]
+pub struct SatelliteOrbitBuilder {
+    satellite_id: String,
+    name: Option<String>,
+    semi_major_axis_km: Option<f64>,
+    eccentricity: f64,
+    inclination_deg: f64,
+    raan_deg: f64,
+    argument_of_perigee_deg: f64,
+    mean_anomaly_deg: f64,
+    epoch: Option<DateTime<Utc>>,
+    slot: Option<(PlaneId, SlotId)>,
+}
+
+impl Default for SatelliteOrbitBuilder {
+    fn default() -> Self {
+        Self {
+            satellite_id: "SAT-001".to_string(),
+            name: None,
+            semi_major_axis_km: None,
+            eccentricity: 0.0,
+            inclination_deg: 0.0,
+            raan_deg: 0.0,
+            argument_of_perigee_deg: 0.0,
+            mean_anomaly_deg: 0.0,
+            epoch: None,
+            slot: None,
+        }
+    }
+}
+
+impl SatelliteOrbitBuilder {
+    pub fn satellite_id(mut self, satellite_id: impl Into<String>) -> Self {
+        self.satellite_id = satellite_id.into();
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Altitude of the semi-major axis above the equatorial radius
+    pub fn altitude_km(mut self, altitude_km: f64) -> Self {
+        self.semi_major_axis_km = Some(EARTH_RADIUS_KM + altitude_km);
+        self
+    }
+
+    pub fn semi_major_axis_km(mut self, semi_major_axis_km: f64) -> Self {
+        self.semi_major_axis_km = Some(semi_major_axis_km);
+        self
+    }
+
+    /// Typed `altitude_km`, accepting kilometres or metres
+    pub fn altitude(self, altitude: impl Into<Kilometers>) -> Self {
+        self.altitude_km(altitude.into().value())
+    }
+
+    /// Typed `semi_major_axis_km`, accepting kilometres or metres
+    pub fn semi_major_axis(self, semi_major_axis: impl Into<Kilometers>) -> Self {
+        self.semi_major_axis_km(semi_major_axis.into().value())
+    }
+
+    /// Typed `inclination_deg`, accepting degrees or radians
+    pub fn inclination(self, inclination: impl Into<Degrees>) -> Self {
+        self.inclination_deg(inclination.into().value())
+    }
+
+    /// Typed `raan_deg`, accepting degrees or radians
+    pub fn raan(self, raan: impl Into<Degrees>) -> Self {
+        self.raan_deg(raan.into().value())
+    }
+
+    /// Typed `argument_of_perigee_deg`, accepting degrees or radians
+    pub fn argument_of_perigee(self, argument_of_perigee: impl Into<Degrees>) -> Self {
+        self.argument_of_perigee_deg(argument_of_perigee.into().value())
+    }
+
+    /// Typed `mean_anomaly_deg`, accepting degrees or radians
+    pub fn mean_anomaly(self, mean_anomaly: impl Into<Degrees>) -> Self {
+        self.mean_anomaly_deg(mean_anomaly.into().value())
+    }
+
+    pub fn eccentricity(mut self, eccentricity: f64) -> Self {
+        self.eccentricity = eccentricity;
+        self
+    }
+
+    pub fn inclination_deg(mut self, inclination_deg: f64) -> Self {
+        self.inclination_deg = inclination_deg;
+        self
+    }
+
+    pub fn raan_deg(mut self, raan_deg: f64) -> Self {
+        self.raan_deg = raan_deg;
+        self
+    }
+
+    pub fn argument_of_perigee_deg(mut self, argument_of_perigee_deg: f64) -> Self {
+        self.argument_of_perigee_deg = argument_of_perigee_deg;
+        self
+    }
+
+    pub fn mean_anomaly_deg(mut self, mean_anomaly_deg: f64) -> Self {
+        self.mean_anomaly_deg = mean_anomaly_deg;
+        self
+    }
+
+    pub fn epoch(mut self, epoch: DateTime<Utc>) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    /// Assign a plane and slot, as `SatelliteOrbit::with_slot`
+    pub fn slot(mut self, plane: PlaneId, slot: SlotId) -> Self {
+        self.slot = Some((plane, slot));
+        self
+    }
+
+    /// Validate the elements and build the orbit
+    pub fn build(self) -> Result<SatelliteOrbit> {
+        let semi_major_axis_km = self.semi_major_axis_km.ok_or_else(|| {
+            OrbitalMechanicsError::invalid_elements("Orbit needs an altitude or semi-major axis")
+        });
+        let elements = semi_major_axis_km
+            .and_then(|semi_major_axis_km| {
+                OrbitalElements::new(
+                    semi_major_axis_km,
+                    self.eccentricity,
+                    self.inclination_deg,
+                    self.raan_deg.rem_euclid(360.0),
+                    self.argument_of_perigee_deg.rem_euclid(360.0),
+                    self.mean_anomaly_deg.rem_euclid(360.0),
+                )
+            })
+            .for_satellite(&self.satellite_id)?;
+
+        let name = self.name.unwrap_or_else(|| self.satellite_id.clone());
+        let orbit = SatelliteOrbit::new(
+            self.satellite_id,
+            name,
+            elements,
+            self.epoch.unwrap_or_else(Utc::now),
+        );
+        Ok(match self.slot {
+            Some((plane, slot)) => orbit.with_slot(plane, slot),
+            None => orbit,
+        })
+    }
 }
 
 impl SatelliteState {
@@ -298,10 +809,10 @@ impl SatelliteState {
         position_eci: [f64; 3],
         velocity_eci: [f64; 3],
     ) -> Self {
-        let geodetic = Self::eci_to_geodetic(position_eci);
+        let geodetic = Self::eci_to_geodetic(&EarthModel::Sphere, position_eci);
         let orbital_radius = (position_eci[0].powi(2) + position_eci[1].powi(2) + position_eci[2].powi(2)).sqrt();
         let ground_track_velocity = (velocity_eci[0].powi(2) + velocity_eci[1].powi(2) + velocity_eci[2].powi(2)).sqrt();
-        let in_eclipse = Self::calculate_eclipse_status(position_eci);
+        let in_eclipse = !ephemeris::is_sunlit(position_eci, timestamp);
 
         Self {
             satellite_id,
@@ -317,74 +828,57 @@ impl SatelliteState {
     }
 
     /// Convert ECI position to geodetic coordinates
-    fn eci_to_geodetic(position_eci: [f64; 3]) -> GeodeticPosition {
-        let x = position_eci[0];
-        let y = position_eci[1];
-        let z = position_eci[2];
-
-        let r = (x*x + y*y + z*z).sqrt();
-        let latitude_deg = (z / r).asin() * RAD_TO_DEG;
-        let longitude_deg = y.atan2(x) * RAD_TO_DEG;
-        let altitude_km = r - EARTH_RADIUS_KM;
+    fn eci_to_geodetic(model: &EarthModel, position_eci: [f64; 3]) -> GeodeticPosition {
+        let geodetic = model.ecef_to_geodetic(position_eci);
 
         GeodeticPosition {
-            latitude_deg,
-            longitude_deg,
-            altitude_km,
+            latitude_deg: geodetic.latitude_deg,
+            longitude_deg: geodetic.longitude_deg,
+            altitude_km: geodetic.altitude_km,
         }
     }
 
-    /// Simple eclipse calculation (satellite in Earth's shadow)
-    fn calculate_eclipse_status(position_eci: [f64; 3]) -> bool {
-        let x = position_eci[0];
-        let y = position_eci[1];
-        let z = position_eci[2];
-
-        // Simplified eclipse model: satellite on night side and within shadow cone
-        let shadow_radius = EARTH_RADIUS_KM * 1.1; // Add margin for penumbra
-        x < 0.0 && (y*y + z*z).sqrt() < shadow_radius
+    /// Sub-satellite point on `model`
+    ///
+    /// `geodetic` is filled in on the spherical model; use this when the
+    /// analysis runs on an ellipsoid.
+    pub fn geodetic_with_model(&self, model: &EarthModel) -> GeodeticPosition {
+        Self::eci_to_geodetic(model, self.position_eci)
     }
 
-    /// Calculate look angles from ground station
+    /// Calculate look angles from ground station on the spherical Earth model
     pub fn look_angles_from_station(&self, station_lat_deg: f64, station_lon_deg: f64, station_alt_m: f64) -> LookAngles {
-        // Convert ground station to ECI coordinates
-        let lat_rad = station_lat_deg * DEG_TO_RAD;
-        let lon_rad = station_lon_deg * DEG_TO_RAD;
-        let r_station = EARTH_RADIUS_KM + station_alt_m / 1000.0;
-
-        let x_station = r_station * lat_rad.cos() * lon_rad.cos();
-        let y_station = r_station * lat_rad.cos() * lon_rad.sin();
-        let z_station = r_station * lat_rad.sin();
-
-        // Range vector from station to satellite
-        let dx = self.position_eci[0] - x_station;
-        let dy = self.position_eci[1] - y_station;
-        let dz = self.position_eci[2] - z_station;
-        let range = (dx*dx + dy*dy + dz*dz).sqrt();
-
-        // Convert to topocentric coordinates (SEZ)
-        let sin_lat = lat_rad.sin();
-        let cos_lat = lat_rad.cos();
-        let sin_lon = lon_rad.sin();
-        let cos_lon = lon_rad.cos();
-
-        let s = -dx * sin_lat * cos_lon - dy * sin_lat * sin_lon + dz * cos_lat;
-        let e = -dx * sin_lon + dy * cos_lon;
-        let z = dx * cos_lat * cos_lon + dy * cos_lat * sin_lon + dz * sin_lat;
-
-        // Calculate elevation and azimuth
-        let elevation_rad = (z / range).asin();
-        let azimuth_rad = e.atan2(s);
+        self.look_angles_from_station_with_model(
+            &EarthModel::Sphere,
+            station_lat_deg,
+            station_lon_deg,
+            station_alt_m,
+        )
+    }
+
+    /// Calculate look angles from a ground station at geodetic coordinates on `model`
+    pub fn look_angles_from_station_with_model(
+        &self,
+        model: &EarthModel,
+        station_lat_deg: f64,
+        station_lon_deg: f64,
+        station_alt_m: f64,
+    ) -> LookAngles {
+        let frame =
+            TopocentricFrame::with_model(model, station_lat_deg, station_lon_deg, station_alt_m);
+        let topocentric = frame.transform(&Position3D::from(self.position_eci));
+
+        // Range rate: relative velocity projected onto the line of sight
+        let range_rate = (0..3)
+            .map(|i| (self.position_eci[i] - frame.origin[i]) * self.velocity_eci[i])
+            .sum::<f64>()
+            / topocentric.range_km;
 
         LookAngles {
-            elevation_deg: elevation_rad * RAD_TO_DEG,
-            azimuth_deg: if azimuth_rad < 0.0 {
-                azimuth_rad * RAD_TO_DEG + 360.0
-            } else {
-                azimuth_rad * RAD_TO_DEG
-            },
-            range_km: range,
-            range_rate_km_per_s: 0.0, // Would need velocity calculation
+            elevation_deg: topocentric.elevation_deg(),
+            azimuth_deg: topocentric.azimuth_deg(),
+            range_km: topocentric.range_km,
+            range_rate_km_per_s: range_rate,
         }
     }
 
@@ -411,6 +905,20 @@ pub struct LookAngles {
     pub range_rate_km_per_s: f64,
 }
 
+impl LookAngles {
+    pub fn elevation(&self) -> Degrees {
+        Degrees(self.elevation_deg)
+    }
+
+    pub fn azimuth(&self) -> Degrees {
+        Degrees(self.azimuth_deg)
+    }
+
+    pub fn range(&self) -> Kilometers {
+        Kilometers(self.range_km)
+    }
+}
+
 impl GeodeticPosition {
     /// Create new geodetic position with validation
     pub fn new(latitude_deg: f64, longitude_deg: f64, altitude_km: f64) -> Result<Self> {
@@ -435,17 +943,14 @@ impl GeodeticPosition {
         })
     }
 
-    /// Convert to Earth-Centered Earth-Fixed (ECEF) coordinates
+    /// Convert to Earth-Centered Earth-Fixed (ECEF) coordinates on the spherical model
     pub fn to_ecef(&self) -> [f64; 3] {
-        let lat_rad = self.latitude_deg * DEG_TO_RAD;
-        let lon_rad = self.longitude_deg * DEG_TO_RAD;
-        let r = EARTH_RADIUS_KM + self.altitude_km;
+        self.to_ecef_with_model(&EarthModel::Sphere)
+    }
 
-        [
-            r * lat_rad.cos() * lon_rad.cos(),
-            r * lat_rad.cos() * lon_rad.sin(),
-            r * lat_rad.sin(),
-        ]
+    /// Convert to ECEF coordinates on `model`
+    pub fn to_ecef_with_model(&self, model: &EarthModel) -> [f64; 3] {
+        model.geodetic_to_ecef(self.latitude_deg, self.longitude_deg, self.altitude_km)
     }
 
     /// Calculate distance to another geodetic position
@@ -466,10 +971,55 @@ impl GeodeticPosition {
     }
 }
 
+/// Equinoctial elements (a, h, k, p, q, mean longitude)
+fn to_equinoctial(elements: &Elements) -> Vector6<f64> {
+    let periapsis_longitude = elements.argument_of_perigee_rad + elements.raan_rad;
+    let node_factor = (elements.inclination_rad / 2.0).tan();
+    Vector6::new(
+        elements.semi_major_axis_km,
+        elements.eccentricity * periapsis_longitude.sin(),
+        elements.eccentricity * periapsis_longitude.cos(),
+        node_factor * elements.raan_rad.sin(),
+        node_factor * elements.raan_rad.cos(),
+        elements.mean_anomaly_rad + periapsis_longitude,
+    )
+}
+
+fn from_equinoctial(x: &Vector6<f64>) -> Elements {
+    let periapsis_longitude = x[1].atan2(x[2]);
+    let raan_rad = x[3].atan2(x[4]);
+    Elements {
+        semi_major_axis_km: x[0],
+        eccentricity: x[1].hypot(x[2]),
+        inclination_rad: 2.0 * x[3].hypot(x[4]).atan(),
+        raan_rad,
+        argument_of_perigee_rad: periapsis_longitude - raan_rad,
+        mean_anomaly_rad: x[5] - periapsis_longitude,
+    }
+}
+
+/// Angle in radians as degrees in [0, 360)
+fn angle_0_360(angle_rad: f64) -> f64 {
+    let degrees = (angle_rad * RAD_TO_DEG).rem_euclid(360.0);
+    if degrees >= 360.0 {
+        0.0
+    } else {
+        degrees
+    }
+}
+
+/// Gauss-Newton iterations of `OrbitalElements::fit_from_states`
+const ELEMENT_FIT_ITERATIONS: usize = 20;
+
+/// Fixed-point iterations and relative tolerance of `OrbitalElements::to_mean`
+const MEAN_ELEMENT_ITERATIONS: usize = 50;
+const MEAN_ELEMENT_TOLERANCE: f64 = 1e-12;
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::units::{Meters, Radians};
+    use chrono::{TimeZone, Utc};
 
     #[test]
     fn test_orbital_elements_creation() {
@@ -505,6 +1055,31 @@ mod tests {
         assert_eq!(orbit.elements.orbit_classification(), OrbitClassification::Meo);
     }
 
+    #[test]
+    fn test_special_orbit_detection() {
+        let tolerances = ClassificationTolerances::default();
+
+        // 700 km sun-synchronous orbit with frozen eccentricity
+        let sso = OrbitalElements::new(7078.137, 0.00104, 98.19, 0.0, 90.0, 0.0).unwrap();
+        let detail = sso.classify(&tolerances);
+        assert_eq!(detail.regime, OrbitClassification::Leo);
+        assert!(detail.is(SpecialOrbit::SunSynchronous));
+        assert!(detail.is(SpecialOrbit::Frozen));
+        assert!(detail.reason.contains("sun-synchronous"));
+
+        let molniya = OrbitalElements::new(26554.0, 0.72, 63.4, 0.0, 270.0, 0.0).unwrap();
+        let detail = molniya.classify(&tolerances);
+        assert!(detail.is(SpecialOrbit::Molniya));
+        assert!(detail.is(SpecialOrbit::CriticalInclination));
+        assert!(!detail.is(SpecialOrbit::Tundra));
+
+        let tundra = OrbitalElements::new(42164.0, 0.27, 63.4, 0.0, 270.0, 0.0).unwrap();
+        assert!(tundra.classify(&tolerances).is(SpecialOrbit::Tundra));
+
+        let meo = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        assert!(meo.classify(&tolerances).special.is_empty());
+    }
+
     #[test]
     fn test_geodetic_position() {
         let pos = GeodeticPosition::new(40.0, -105.0, 0.0);
@@ -516,6 +1091,60 @@ mod tests {
         assert!(ecef[2] > 0.0); // Positive Z for North latitude
     }
 
+    #[test]
+    fn test_beta_angle() {
+        // Equatorial orbit: beta follows the Sun's declination
+        let equinox = Utc.with_ymd_and_hms(2024, 3, 20, 3, 6, 0).unwrap();
+        let solstice = Utc.with_ymd_and_hms(2024, 6, 20, 20, 51, 0).unwrap();
+        let orbit = SatelliteOrbit::builder()
+            .altitude_km(550.0)
+            .epoch(equinox)
+            .build()
+            .unwrap();
+        assert!(orbit.beta_angle(equinox).abs() < Degrees(0.05));
+        assert!((orbit.beta_angle(solstice) - Degrees(23.44)).abs() < Degrees(0.05));
+    }
+
+    #[test]
+    fn test_orbit_builder() {
+        let epoch = Utc::now();
+        let orbit = SatelliteOrbit::builder()
+            .satellite_id("MEO-7")
+            .altitude_km(8062.0)
+            .inclination_deg(55.0)
+            .raan_deg(-120.0)
+            .mean_anomaly_deg(390.0)
+            .epoch(epoch)
+            .slot(PlaneId(2), SlotId(0))
+            .build()
+            .unwrap();
+
+        assert_eq!(orbit.name, "MEO-7");
+        assert!((orbit.elements.semi_major_axis_km - (EARTH_RADIUS_KM + 8062.0)).abs() < 1e-9);
+        assert!((orbit.elements.raan_deg - 240.0).abs() < 1e-9);
+        assert!((orbit.elements.mean_anomaly_deg - 30.0).abs() < 1e-9);
+        assert_eq!(orbit.epoch, epoch);
+        assert_eq!(orbit.slot.unwrap().plane, PlaneId(2));
+
+        // Typed setters convert units instead of mixing them
+        let typed = SatelliteOrbit::builder()
+            .altitude(Meters(8_062_000.0))
+            .inclination(Radians(std::f64::consts::FRAC_PI_4))
+            .epoch(epoch)
+            .build()
+            .unwrap();
+        let size_error = typed.elements.semi_major_axis() - orbit.elements.semi_major_axis();
+        assert!(size_error.abs() < Kilometers(1e-9));
+        assert!((typed.elements.inclination() - Degrees(45.0)).abs() < Degrees(1e-9));
+        assert_eq!(typed.elements.period(), Seconds(typed.period_seconds));
+
+        // Size is required and the elements are validated
+        let error = SatelliteOrbit::builder().inclination_deg(55.0).build().unwrap_err();
+        assert!(error.to_string().contains("SAT-001"));
+        assert!(SatelliteOrbit::builder().altitude_km(500.0).eccentricity(1.2).build().is_err());
+        assert!(SatelliteOrbit::builder().altitude_km(f64::NAN).build().is_err());
+    }
+
     #[test]
     fn test_orbital_period_calculation() {
         let elements = OrbitalElements::new(7000.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
@@ -524,4 +1153,110 @@ mod tests {
         // Period should be approximately 98 minutes for 600 km altitude
         assert!(period > 5800.0 && period < 6000.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_fit_elements_from_states() {
+        use crate::propagator::{OrbitalPropagator, Sgp4Propagator};
+        let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let propagator = Sgp4Propagator::new();
+        let sample = |orbit: &SatelliteOrbit| -> Vec<SatelliteState> {
+            (0..=36)
+                .map(|i| {
+                    let time = epoch + chrono::Duration::minutes(10 * i);
+                    propagator.propagate(orbit, time).unwrap()
+                })
+                .collect()
+        };
+
+        let truth = SatelliteOrbit::builder()
+            .semi_major_axis_km(7000.0)
+            .eccentricity(0.001)
+            .inclination_deg(53.0)
+            .raan_deg(40.0)
+            .argument_of_perigee_deg(90.0)
+            .mean_anomaly_deg(10.0)
+            .epoch(epoch)
+            .build()
+            .unwrap();
+        let fitted = OrbitalElements::fit_from_states(&sample(&truth)).unwrap();
+        assert!((fitted.semi_major_axis_km - 7000.0).abs() < 1e-4);
+        assert!((fitted.eccentricity - 0.001).abs() < 1e-8);
+        assert!((fitted.inclination_deg - 53.0).abs() < 1e-6);
+        assert!((fitted.raan_deg - 40.0).abs() < 1e-6);
+        assert!((fitted.argument_of_perigee_deg - 90.0).abs() < 1e-4);
+        assert!((fitted.mean_anomaly_deg - 10.0).abs() < 1e-4);
+
+        // Circular equatorial: the angles fold together but the states are reproduced
+        let flat = SatelliteOrbit::builder()
+            .altitude_km(800.0)
+            .mean_anomaly_deg(120.0)
+            .epoch(epoch)
+            .build()
+            .unwrap();
+        let states = sample(&flat);
+        let fitted = OrbitalElements::fit_from_states(&states).unwrap();
+        let refit = SatelliteOrbit::new("FIT".to_string(), "Fit".to_string(), fitted, epoch);
+        for state in &states {
+            let position = propagator.propagate(&refit, state.timestamp).unwrap().position_eci;
+            let error = (0..3)
+                .map(|i| (position[i] - state.position_eci[i]).powi(2))
+                .sum::<f64>();
+            assert!(error.sqrt() < 1e-3);
+        }
+
+        assert!(OrbitalElements::fit_from_states(&[]).is_err());
+    }
+
+    #[test]
+    fn test_mean_osculating_conversion() {
+        use crate::force_model::ZonalHarmonics;
+        use crate::propagator::{NumericalPropagator, OrbitalPropagator, Sgp4Propagator};
+
+        let mean = OrbitalElements::new(7000.0, 0.001, 53.0, 40.0, 30.0, 10.0).unwrap();
+        let osculating = mean.to_osculating().unwrap();
+        // LEO short-period terms move the semi-major axis by a few km
+        let offset = (osculating.semi_major_axis_km - mean.semi_major_axis_km).abs();
+        assert!(offset > 0.1 && offset < 15.0, "{}", offset);
+
+        let recovered = osculating.to_mean().unwrap();
+        assert!((recovered.semi_major_axis_km - 7000.0).abs() < 1e-6);
+        assert!((recovered.eccentricity - 0.001).abs() < 1e-10);
+        assert!((recovered.inclination_deg - 53.0).abs() < 1e-8);
+        assert!((recovered.raan_deg - 40.0).abs() < 1e-8);
+        assert!((recovered.argument_of_perigee_deg - 30.0).abs() < 1e-6);
+        assert!((recovered.mean_anomaly_deg - 10.0).abs() < 1e-6);
+
+        // Propagating mean elements with secular J2 follows a numerical J2
+        // propagation of their osculating state far better than treating the
+        // osculating elements as mean
+        let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let orbit = |elements: &OrbitalElements| {
+            SatelliteOrbit::new("SAT".to_string(), "Sat".to_string(), elements.clone(), epoch)
+        };
+        let later = epoch + chrono::Duration::hours(6);
+        let truth = NumericalPropagator::new(10.0)
+            .with_force(ZonalHarmonics::new(2))
+            .propagate(&orbit(&osculating), later)
+            .unwrap();
+        let error = |elements: &OrbitalElements| {
+            let state = Sgp4Propagator::new().propagate(&orbit(elements), later).unwrap();
+            (0..3)
+                .map(|i| (state.position_eci[i] - truth.position_eci[i]).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+        let (consistent, naive) = (error(&mean), error(&osculating));
+        assert!(consistent < 0.25 * naive, "{} vs {}", consistent, naive);
+
+        // Circular equatorial orbits stay finite; the critical inclination is refused
+        let flat = OrbitalElements::new(7000.0, 0.0, 0.0, 0.0, 0.0, 50.0).unwrap();
+        let recovered = flat.to_osculating().unwrap().to_mean().unwrap();
+        assert!((recovered.semi_major_axis_km - 7000.0).abs() < 1e-6);
+        assert!(recovered.eccentricity < 1e-10 && recovered.inclination_deg < 1e-8);
+        let longitude = recovered.raan_deg + recovered.argument_of_perigee_deg
+            + recovered.mean_anomaly_deg;
+        assert!((longitude.rem_euclid(360.0) - 50.0).abs() < 1e-6);
+        let critical = OrbitalElements::new(26_600.0, 0.7, 63.4, 0.0, 270.0, 0.0).unwrap();
+        assert!(critical.to_osculating().is_err());
+    }
+}
@@ -0,0 +1,445 @@
+//! Audit trail of passes filtered out of an analysis
+//!
+//! A pass an analyst expects can vanish for several reasons: it never climbs
+//! above the elevation mask, the lighting constraint rules it out, the
+//! station is down for maintenance, or whatever remains is lost entirely to
+//! thermal keep-outs or mount slew limits. The audit records each such
+//! candidate with the filter that removed it, so a run can be dumped and
+//! inspected.
+//!
+//! Candidates are geometric passes above the horizon, sampled at the
+//! visibility calculator's step. Those the visibility search drops come from
+//! `rejected_passes`; windows it keeps but that have no usable time left come
+//! from `unusable_window`. A pass that fails lighting at some samples and
+//! maintenance at others is reported as maintenance, the filter that took
+//! its lit time.
+
+use crate::error::{Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::ground_station::GroundStation;
+use crate::orbit::SatelliteOrbit;
+use crate::propagator::OrbitalPropagator;
+use crate::visibility::{MountConflictKind, VisibilityCalculator, VisibilityWindow};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Filter that removed a candidate pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FilterReason {
+    /// Never above the elevation mask in force at the station
+    BelowElevationMask { mask_deg: f64 },
+    /// Above the mask only while the lighting constraint failed
+    Lighting,
+    /// Station outage or maintenance, with the calendar's reason
+    Maintenance { reason: String },
+    /// No usable time left after thermal keep-outs or GEO arc protection
+    KeepOut { reason: String },
+    /// Mount limits cover the whole pass
+    SlewLimit { kind: MountConflictKind },
+}
+
+/// Candidate pass left out of the results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedPass {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub max_elevation_deg: f64,
+    pub reason: FilterReason,
+}
+
+/// Rejected passes of one analysis run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassAuditLog {
+    /// Rejections by start time
+    pub rejected: Vec<RejectedPass>,
+}
+
+impl FilterReason {
+    /// Short machine-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BelowElevationMask { .. } => "below_elevation_mask",
+            Self::Lighting => "lighting",
+            Self::Maintenance { .. } => "maintenance",
+            Self::KeepOut { .. } => "keep_out",
+            Self::SlewLimit { .. } => "slew_limit",
+        }
+    }
+
+    /// Filter parameters, or the calendar or analyzer's reason
+    pub fn detail(&self) -> String {
+        match self {
+            Self::BelowElevationMask { mask_deg } => format!("mask {:.1} deg", mask_deg),
+            Self::Lighting => String::new(),
+            Self::Maintenance { reason } | Self::KeepOut { reason } => reason.clone(),
+            Self::SlewLimit { kind } => format!("{:?}", kind),
+        }
+    }
+}
+
+impl PassAuditLog {
+    pub fn new(mut rejected: Vec<RejectedPass>) -> Self {
+        rejected.sort_by(|a, b| {
+            (a.start_time, &a.satellite_id, &a.station_id).cmp(&(
+                b.start_time,
+                &b.satellite_id,
+                &b.station_id,
+            ))
+        });
+        Self { rejected }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rejected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    /// Rejections per filter name
+    pub fn counts_by_reason(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for pass in &self.rejected {
+            *counts.entry(pass.reason.name()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Render the log as CSV, one row per rejected pass
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// Render the log as CSV in the units and format of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let mut csv = format!(
+            "satellite_id,station_id,start_time,end_time,max_elevation_{},reason,detail\n",
+            options.angle_unit.suffix()
+        );
+        for pass in &self.rejected {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                options.satellite_name(&pass.satellite_id),
+                pass.station_id,
+                options.timestamp(pass.start_time),
+                options.timestamp(pass.end_time),
+                options.angle(pass.max_elevation_deg, Some(3)),
+                pass.reason.name(),
+                pass.reason.detail().replace(',', ";")
+            );
+        }
+        csv
+    }
+
+    /// Render the log as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the log to a CSV file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write the log to a CSV file in the units and format of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv_with(options)).for_path(path)
+    }
+
+    /// Write the log to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?).for_path(path)
+    }
+}
+
+/// Passes above the horizon that `calculator` finds no visible time in
+pub fn rejected_passes(
+    calculator: &VisibilityCalculator,
+    satellite: &SatelliteOrbit,
+    station: &GroundStation,
+    start_time: DateTime<Utc>,
+    duration_hours: f64,
+    propagator: &dyn OrbitalPropagator,
+) -> Result<Vec<RejectedPass>> {
+    let mask_deg = station
+        .min_elevation_deg
+        .unwrap_or(calculator.min_elevation_deg);
+    let end_time = start_time + Duration::seconds((duration_hours * 3600.0) as i64);
+    let step =
+        Duration::milliseconds((calculator.time_step_seconds * 1000.0).round().max(1.0) as i64);
+
+    let mut rejected = Vec::new();
+    let mut candidate: Option<Candidate> = None;
+    let mut time = start_time;
+    while time <= end_time {
+        let state = propagator
+            .propagate(satellite, time)
+            .for_satellite(&satellite.satellite_id)
+            .for_station(&station.station_id)
+            .at_epoch(time)?;
+        let elevation_deg = state
+            .look_angles_from_station_with_model(
+                &calculator.earth_model,
+                station.position.latitude_deg,
+                station.position.longitude_deg,
+                station.position.elevation_m,
+            )
+            .elevation_deg;
+
+        if elevation_deg > 0.0 {
+            let pass = candidate.get_or_insert_with(|| Candidate::new(time));
+            pass.end_time = time;
+            pass.max_elevation_deg = pass.max_elevation_deg.max(elevation_deg);
+            if elevation_deg >= mask_deg && !pass.visible {
+                if !calculator.lighting.is_satisfied(station, &state) {
+                    pass.above_mask = true;
+                } else if let Some(outage) = station
+                    .availability
+                    .unavailable_intervals(time, time)
+                    .into_iter()
+                    .next()
+                {
+                    pass.above_mask = true;
+                    pass.outage.get_or_insert(outage.reason);
+                } else {
+                    pass.visible = true;
+                }
+            }
+        } else if let Some(pass) = candidate.take() {
+            rejected.extend(pass.rejection(satellite, station, mask_deg));
+        }
+        time += step;
+    }
+    if let Some(pass) = candidate {
+        rejected.extend(pass.rejection(satellite, station, mask_deg));
+    }
+    Ok(rejected)
+}
+
+/// Rejection for a found window with no usable time left, if any
+pub fn unusable_window(window: &VisibilityWindow) -> Option<RejectedPass> {
+    let reason = if window.usable_seconds() <= 0.0 {
+        FilterReason::KeepOut {
+            reason: window
+                .unusable_intervals
+                .first()
+                .map(|interval| interval.reason.clone())
+                .unwrap_or_default(),
+        }
+    } else if !window.mount_conflicts.is_empty() && window.split_at_mount_conflicts().is_empty() {
+        FilterReason::SlewLimit {
+            kind: window.mount_conflicts[0].kind,
+        }
+    } else {
+        return None;
+    };
+    Some(RejectedPass {
+        satellite_id: window.satellite_id.clone(),
+        station_id: window.station_id.clone(),
+        start_time: window.start_time,
+        end_time: window.end_time,
+        max_elevation_deg: window.max_elevation_deg,
+        reason,
+    })
+}
+
+/// Horizon-to-horizon pass being scanned
+struct Candidate {
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    max_elevation_deg: f64,
+    above_mask: bool,
+    outage: Option<String>,
+    visible: bool,
+}
+
+impl Candidate {
+    fn new(time: DateTime<Utc>) -> Self {
+        Self {
+            start_time: time,
+            end_time: time,
+            max_elevation_deg: f64::NEG_INFINITY,
+            above_mask: false,
+            outage: None,
+            visible: false,
+        }
+    }
+
+    fn rejection(
+        self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        mask_deg: f64,
+    ) -> Option<RejectedPass> {
+        let reason = match (self.visible, self.above_mask, self.outage) {
+            (true, _, _) => return None,
+            (false, false, _) => FilterReason::BelowElevationMask { mask_deg },
+            (false, true, Some(reason)) => FilterReason::Maintenance { reason },
+            (false, true, None) => FilterReason::Lighting,
+        };
+        Some(RejectedPass {
+            satellite_id: satellite.satellite_id.clone(),
+            station_id: station.station_id.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            max_elevation_deg: self.max_elevation_deg,
+            reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::StationOutage;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use crate::visibility::{LightingConstraint, MountConflict, UnusableInterval};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_rejected_passes_sub_second_step() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let station = GroundStation::builder()
+            .station_id("GS-1")
+            .name("Station 1")
+            .position(40.0, 36.0, 0.0)
+            .build()
+            .unwrap();
+        let propagator = KeplerianPropagator::new();
+        let coarse = VisibilityCalculator::with_params(30.0, 60.0);
+        let (satellite, pass) = [0.0, 20.0]
+            .iter()
+            .find_map(|&raan| {
+                let elements = OrbitalElements::new(7000.0, 0.0, 55.0, raan, 0.0, 0.0).unwrap();
+                let satellite =
+                    SatelliteOrbit::new("SAT".to_string(), "Sat".to_string(), elements, start);
+                let pass = rejected_passes(&coarse, &satellite, &station, start, 24.0, &propagator)
+                    .unwrap()
+                    .into_iter()
+                    .next()?;
+                Some((satellite, pass))
+            })
+            .unwrap();
+
+        // A half-second step finishes and finds the same pass
+        let fine = VisibilityCalculator::with_params(30.0, 0.5);
+        let from = pass.start_time - Duration::minutes(2);
+        let hours = ((pass.end_time - from).num_seconds() + 120) as f64 / 3600.0;
+        let rejected =
+            rejected_passes(&fine, &satellite, &station, from, hours, &propagator).unwrap();
+        assert_eq!(rejected.len(), 1);
+        let offset = (rejected[0].start_time - pass.start_time).num_seconds();
+        assert!(offset.abs() <= 60);
+        assert!(rejected[0].max_elevation_deg < 30.0);
+    }
+
+    #[test]
+    fn test_rejected_passes() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        // One ground track runs over the station, the other well off to the side
+        let satellites: Vec<SatelliteOrbit> = [0.0, 20.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &raan)| {
+                let elements = OrbitalElements::new(7000.0, 0.0, 55.0, raan, 0.0, 0.0).unwrap();
+                SatelliteOrbit::new(format!("SAT-{}", i), format!("Sat {}", i), elements, start)
+            })
+            .collect();
+        let mut station = GroundStation::builder()
+            .station_id("GS-1")
+            .name("Station 1")
+            .position(40.0, 36.0, 0.0)
+            .build()
+            .unwrap();
+        let propagator = KeplerianPropagator::new();
+        let calculator = VisibilityCalculator::with_params(30.0, 60.0);
+        let windows = |calculator: &VisibilityCalculator| {
+            satellites
+                .iter()
+                .flat_map(|satellite| {
+                    calculator
+                        .calculate_windows(satellite, &station, start, 24.0, &propagator)
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        let audit = |calculator: &VisibilityCalculator, station: &GroundStation| {
+            satellites
+                .iter()
+                .flat_map(|satellite| {
+                    rejected_passes(calculator, satellite, station, start, 24.0, &propagator)
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Every pass over the horizon is either found or rejected by the mask
+        let horizon =
+            windows(&VisibilityCalculator::with_params(0.0, 60.0).with_edge_accuracy(None));
+        let found = windows(&calculator);
+        let rejected = audit(&calculator, &station);
+        assert!(!found.is_empty() && !rejected.is_empty());
+        assert_eq!(found.len() + rejected.len(), horizon.len());
+        for pass in &rejected {
+            assert_eq!(
+                pass.reason,
+                FilterReason::BelowElevationMask { mask_deg: 30.0 }
+            );
+            assert!(pass.max_elevation_deg < 30.0);
+        }
+
+        // Lighting that is never met, then an outage, take the passes above the mask
+        let dark = VisibilityCalculator::with_params(30.0, 60.0)
+            .with_lighting(LightingConstraint::station_in_darkness(-90.0));
+        let lighting = audit(&dark, &station);
+        assert_eq!(lighting.len(), horizon.len());
+        assert_eq!(
+            lighting
+                .iter()
+                .filter(|p| p.reason == FilterReason::Lighting)
+                .count(),
+            found.len()
+        );
+        station.availability.outages.push(StationOutage {
+            start_time: start,
+            end_time: start + Duration::days(1),
+            reason: "Dome repair".to_string(),
+        });
+        let log = PassAuditLog::new(audit(&calculator, &station));
+        assert_eq!(log.counts_by_reason()["maintenance"], found.len());
+        assert!(log.to_csv().contains(",maintenance,Dome repair"));
+
+        // Found windows with nothing usable left
+        let mut window = found[0].clone();
+        assert!(unusable_window(&window).is_none());
+        window.mount_conflicts.push(MountConflict {
+            kind: MountConflictKind::Keyhole,
+            start_time: window.start_time,
+            end_time: window.end_time,
+        });
+        assert_eq!(
+            unusable_window(&window).unwrap().reason,
+            FilterReason::SlewLimit {
+                kind: MountConflictKind::Keyhole
+            }
+        );
+        window.unusable_intervals.push(UnusableInterval {
+            start_time: window.start_time,
+            end_time: window.end_time,
+            reason: "Thermal keep-out".to_string(),
+        });
+        assert_eq!(unusable_window(&window).unwrap().reason.name(), "keep_out");
+    }
+}
@@ -0,0 +1,211 @@
+//! Pass profiles
+//!
+//! Azimuth, elevation, slant range and range rate sampled through one
+//! visibility window, as produced by `VisibilityCalculator::pass_profile`.
+//! Plotting and analysis tools can read values between samples with `at`, or
+//! put the series on a new time grid with `resample`, instead of
+//! propagating again.
+//!
+//! Between samples elevation and range rate are interpolated linearly and
+//! azimuth along the shorter arc. Range uses a cubic Hermite spline with the
+//! range rate as its slope, which keeps it smooth through closest approach.
+
+use crate::constants::{KM_TO_M, SPEED_OF_LIGHT};
+use crate::coordinates::wrap_angle_deg;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::ground_station::StationScoped;
+use crate::units::{Degrees, Kilometers, Seconds};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Look angles and range at one instant of a pass
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PassProfileSample {
+    pub timestamp: DateTime<Utc>,
+    /// In [0, 360)
+    pub azimuth: Degrees,
+    pub elevation: Degrees,
+    pub range: Kilometers,
+    /// Positive while the satellite recedes
+    pub range_rate_km_per_s: f64,
+}
+
+/// Time series of look angles and range through one pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassProfile {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub step: Seconds,
+    pub samples: Vec<PassProfileSample>,
+}
+
+impl PassProfileSample {
+    /// Doppler shift of a carrier at `carrier_frequency_hz`
+    pub fn doppler_shift_hz(&self, carrier_frequency_hz: f64) -> f64 {
+        -carrier_frequency_hz * self.range_rate_km_per_s * KM_TO_M / SPEED_OF_LIGHT
+    }
+}
+
+impl PassProfile {
+    /// Interpolated sample at `time`, or `None` outside the profile
+    pub fn at(&self, time: DateTime<Utc>) -> Option<PassProfileSample> {
+        let first = self.samples.first()?;
+        let last = self.samples.last()?;
+        if time < first.timestamp || time > last.timestamp {
+            return None;
+        }
+
+        let index = self.samples.partition_point(|s| s.timestamp < time);
+        let after = self.samples[index];
+        if after.timestamp == time || index == 0 {
+            return Some(after);
+        }
+        let before = self.samples[index - 1];
+        let span = seconds_between(before.timestamp, after.timestamp);
+        let t = seconds_between(before.timestamp, time) / span;
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        // Cubic Hermite basis, with the range rates scaled to the interval
+        let (t2, t3) = (t * t, t * t * t);
+        let range_km = (2.0 * t3 - 3.0 * t2 + 1.0) * before.range.value()
+            + (t3 - 2.0 * t2 + t) * span * before.range_rate_km_per_s
+            + (-2.0 * t3 + 3.0 * t2) * after.range.value()
+            + (t3 - t2) * span * after.range_rate_km_per_s;
+        let azimuth_change = wrap_angle_deg(after.azimuth.value() - before.azimuth.value());
+
+        Some(PassProfileSample {
+            timestamp: time,
+            azimuth: Degrees((before.azimuth.value() + azimuth_change * t).rem_euclid(360.0)),
+            elevation: Degrees(lerp(before.elevation.value(), after.elevation.value())),
+            range: Kilometers(range_km),
+            range_rate_km_per_s: lerp(before.range_rate_km_per_s, after.range_rate_km_per_s),
+        })
+    }
+
+    /// The same pass on a new time grid from the start time, ending exactly at the end time
+    pub fn resample(&self, step: Seconds) -> Result<PassProfile> {
+        let times = sample_times(self.start_time, self.end_time, step)?;
+        let samples = times.into_iter().filter_map(|time| self.at(time)).collect();
+
+        Ok(PassProfile {
+            satellite_id: self.satellite_id.clone(),
+            station_id: self.station_id.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            step,
+            samples,
+        })
+    }
+
+    /// Sample with the highest elevation
+    pub fn peak(&self) -> Option<&PassProfileSample> {
+        self.samples
+            .iter()
+            .max_by(|a, b| a.elevation.value().total_cmp(&b.elevation.value()))
+    }
+}
+
+impl StationScoped for PassProfile {
+    fn station_id(&self) -> &str {
+        &self.station_id
+    }
+}
+
+/// `start`, then every `step` until `end`, which is always included
+pub(crate) fn sample_times(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: Seconds,
+) -> Result<Vec<DateTime<Utc>>> {
+    if !step.is_finite() || step.value() <= 0.0 {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Pass profile step must be positive, got {}",
+            step
+        )));
+    }
+
+    let step = step.to_duration().max(chrono::Duration::milliseconds(1));
+    let mut times = vec![start];
+    let mut time = start;
+    while time < end {
+        time = (time + step).min(end);
+        times.push(time);
+    }
+    Ok(times)
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_milliseconds() as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::GroundStation;
+    use crate::orbit::SatelliteOrbit;
+    use crate::propagator::KeplerianPropagator;
+    use crate::visibility::VisibilityCalculator;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_pass_profile_and_resampling() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let satellite = SatelliteOrbit::builder()
+            .semi_major_axis_km(7000.0)
+            .inclination_deg(55.0)
+            .mean_anomaly_deg(180.0)
+            .epoch(start)
+            .build()
+            .unwrap();
+        let station = GroundStation::builder()
+            .position(15.0, 0.0, 0.0)
+            .build()
+            .unwrap();
+        let propagator = KeplerianPropagator::new();
+        // Scan-step edges keep every sample on the 30 s grid
+        let calculator = VisibilityCalculator::with_params(10.0, 30.0).with_edge_accuracy(None);
+        let window = calculator
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        let profile = calculator
+            .pass_profile(&satellite, &station, &window, Seconds(30.0), &propagator)
+            .unwrap();
+        assert_eq!(
+            profile.samples.first().unwrap().timestamp,
+            window.start_time
+        );
+        assert_eq!(profile.samples.last().unwrap().timestamp, window.end_time);
+        let peak = profile.peak().unwrap();
+        assert!((peak.elevation.value() - window.max_elevation_deg).abs() < 1e-9);
+        assert!(profile
+            .samples
+            .iter()
+            .all(|s| (0.0..360.0).contains(&s.azimuth.value())));
+        assert!(profile
+            .at(window.end_time + chrono::Duration::seconds(1))
+            .is_none());
+
+        // Interpolating the 30 s profile onto 5 s should track a direct 5 s sampling
+        let direct = calculator
+            .pass_profile(&satellite, &station, &window, Seconds(5.0), &propagator)
+            .unwrap();
+        let resampled = profile.resample(Seconds(5.0)).unwrap();
+        assert_eq!(resampled.samples.len(), direct.samples.len());
+        for (r, d) in resampled.samples.iter().zip(&direct.samples) {
+            assert_eq!(r.timestamp, d.timestamp);
+            assert!((r.range - d.range).abs().value() < 0.05);
+            assert!((r.elevation - d.elevation).abs().value() < 0.25);
+            assert!((r.azimuth - d.azimuth).wrapped().abs().value() < 0.5);
+        }
+
+        let receding = profile.samples.last().unwrap();
+        assert!(receding.doppler_shift_hz(2.2e9) < 0.0);
+        assert!(profile.resample(Seconds(0.0)).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Pass reports for operator consoles
+//!
+//! Operators schedule by the station clock, so `PassReport` gives each pass's
+//! AOS and LOS in UTC and, for stations with a `timezone`, in station-local
+//! time. The same rows render as a console table, CSV and JSON. Local times
+//! carry their UTC offset (`2024-03-20T14:00:00-10:00`), so they stay
+//! unambiguous across daylight saving changes; stations without a time zone
+//! get empty local columns.
+
+use crate::error::{Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::ground_station::GroundStationNetwork;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// One pass in UTC and station-local time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassReportRow {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub aos_utc: DateTime<Utc>,
+    pub los_utc: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aos_local: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub los_local: Option<DateTime<FixedOffset>>,
+    pub duration_seconds: f64,
+    pub max_elevation_deg: f64,
+}
+
+/// Passes ordered by AOS
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassReport {
+    pub rows: Vec<PassReportRow>,
+}
+
+impl PassReportRow {
+    pub fn new(window: &VisibilityWindow, timezone: Option<Tz>) -> Self {
+        let local = |time: DateTime<Utc>| timezone.map(|tz| time.with_timezone(&tz).fixed_offset());
+        Self {
+            satellite_id: window.satellite_id.clone(),
+            station_id: window.station_id.clone(),
+            aos_utc: window.start_time,
+            los_utc: window.end_time,
+            timezone,
+            aos_local: local(window.start_time),
+            los_local: local(window.end_time),
+            duration_seconds: window.duration_seconds,
+            max_elevation_deg: window.max_elevation_deg,
+        }
+    }
+}
+
+impl PassReport {
+    /// Report of `windows` in the time zones of their stations
+    ///
+    /// Passes over stations missing from `stations` are reported in UTC only.
+    pub fn new(windows: &[VisibilityWindow], stations: &GroundStationNetwork) -> Self {
+        let mut rows: Vec<PassReportRow> = windows
+            .iter()
+            .map(|window| {
+                let timezone = stations
+                    .get_station(&window.station_id)
+                    .and_then(|station| station.timezone);
+                PassReportRow::new(window, timezone)
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            a.aos_utc
+                .cmp(&b.aos_utc)
+                .then_with(|| a.station_id.cmp(&b.station_id))
+                .then_with(|| a.satellite_id.cmp(&b.satellite_id))
+        });
+        Self { rows }
+    }
+
+    /// Aligned plain-text table for terminals
+    ///
+    /// Local times show the zone abbreviation, e.g. `2024-03-20 04:00:00 HST`.
+    pub fn to_table(&self) -> String {
+        let header = [
+            "SATELLITE",
+            "STATION",
+            "AOS (UTC)",
+            "LOS (UTC)",
+            "AOS (LOCAL)",
+            "LOS (LOCAL)",
+            "MAX EL",
+        ]
+        .map(String::from);
+        let utc = |time: DateTime<Utc>| time.format("%Y-%m-%d %H:%M:%S").to_string();
+        let local = |time: DateTime<Utc>, timezone: Option<Tz>| {
+            timezone
+                .map(|tz| {
+                    time.with_timezone(&tz)
+                        .format("%Y-%m-%d %H:%M:%S %Z")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let cells: Vec<[String; 7]> = std::iter::once(header)
+            .chain(self.rows.iter().map(|row| {
+                [
+                    row.satellite_id.clone(),
+                    row.station_id.clone(),
+                    utc(row.aos_utc),
+                    utc(row.los_utc),
+                    local(row.aos_utc, row.timezone),
+                    local(row.los_utc, row.timezone),
+                    format!("{:.1}°", row.max_elevation_deg),
+                ]
+            }))
+            .collect();
+
+        let mut widths = [0; 7];
+        for line in &cells {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut table = String::new();
+        for line in &cells {
+            let padded: Vec<String> = line
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            let _ = writeln!(table, "{}", padded.join("  ").trim_end());
+        }
+        table
+    }
+
+    /// Render the report as CSV, one row per pass
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// Render the report as CSV in the units and format of `options`
+    ///
+    /// Local times are always ISO 8601 with their UTC offset.
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let mut csv = format!(
+            "satellite_id,station_id,aos_utc,los_utc,timezone,aos_local,los_local,\
+             duration_s,max_elevation_{}\n",
+            options.angle_unit.suffix()
+        );
+        let local = |time: Option<DateTime<FixedOffset>>| {
+            time.map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3f%:z").to_string())
+                .unwrap_or_default()
+        };
+        for row in &self.rows {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                options.satellite_name(&row.satellite_id),
+                row.station_id,
+                options.timestamp(row.aos_utc),
+                options.timestamp(row.los_utc),
+                row.timezone.map(|tz| tz.name()).unwrap_or_default(),
+                local(row.aos_local),
+                local(row.los_local),
+                options.decimal(row.duration_seconds, Some(0)),
+                options.angle(row.max_elevation_deg, Some(3))
+            );
+        }
+        csv
+    }
+
+    /// Render the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the report to a CSV file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write the report to a CSV file in the units and format of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_csv_with(options)).for_path(path)
+    }
+
+    /// Write the report to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?).for_path(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::GroundStation;
+    use crate::visibility::PassType;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_pass_report_local_times() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 14, 0, 0).unwrap();
+        let window = |station_id: &str, offset_minutes: i64| {
+            let start_time = t0 + Duration::minutes(offset_minutes);
+            VisibilityWindow {
+                satellite_id: "MEO-1".to_string(),
+                station_id: station_id.to_string(),
+                start_time,
+                end_time: start_time + Duration::minutes(20),
+                duration_seconds: 1200.0,
+                max_elevation_time: start_time + Duration::minutes(10),
+                max_elevation_deg: 45.0,
+                min_range_km: 8000.0,
+                mean_range_km: 9000.0,
+                azimuth_span_deg: 120.0,
+                pass_type: PassType::Normal,
+                unusable_intervals: Vec::new(),
+                aos_uncertainty_seconds: None,
+                los_uncertainty_seconds: None,
+                mount_conflicts: Vec::new(),
+            }
+        };
+        let mut stations = GroundStationNetwork::new();
+        stations.add_station(
+            GroundStation::builder()
+                .station_id("GS-HAWAII")
+                .position(19.8, -155.5, 4200.0)
+                .timezone(chrono_tz::Pacific::Honolulu)
+                .build()
+                .unwrap(),
+        );
+        stations.add_station(
+            GroundStation::builder()
+                .station_id("GS-UTC")
+                .position(0.0, 0.0, 0.0)
+                .build()
+                .unwrap(),
+        );
+
+        let report = PassReport::new(&[window("GS-UTC", 30), window("GS-HAWAII", 0)], &stations);
+        assert_eq!(report.rows[0].station_id, "GS-HAWAII");
+        assert_eq!(
+            report.rows[0].aos_local.unwrap().to_rfc3339(),
+            "2024-03-20T04:00:00-10:00"
+        );
+        assert!(report.rows[1].aos_local.is_none());
+
+        let csv = report.to_csv();
+        let mut lines = csv.lines().skip(1);
+        assert_eq!(
+            lines.next(),
+            Some(
+                "MEO-1,GS-HAWAII,2024-03-20T14:00:00.000Z,2024-03-20T14:20:00.000Z,\
+                 Pacific/Honolulu,2024-03-20T04:00:00.000-10:00,2024-03-20T04:20:00.000-10:00,\
+                 1200,45.000"
+            )
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .contains(",GS-UTC,2024-03-20T14:30:00.000Z,"));
+        assert!(report.to_table().contains("2024-03-20 04:00:00 HST"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["rows"][0]["timezone"], "Pacific/Honolulu");
+        assert_eq!(json["rows"][0]["aos_local"], "2024-03-20T04:00:00-10:00");
+        assert!(json["rows"][1].get("aos_local").is_none());
+    }
+}
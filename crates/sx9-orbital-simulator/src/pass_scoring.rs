@@ -0,0 +1,500 @@
+//! Pass quality scoring and contact selection
+//!
+//! A `PassScorer` reduces a visibility window to one number, higher being
+//! better. The contact scheduler visits passes best-first and keeps those that
+//! do not overlap a contact already booked on the same station or satellite.
+//! Closures `Fn(&VisibilityWindow) -> f64` implement `PassScorer`, so ad-hoc
+//! policies need no new type.
+//!
+//! With an `AcquisitionModel`, the default scorer also scales each pass by
+//! the chance the terminals find each other before it ends, so passes too
+//! short to finish the acquisition scan rank below their geometry.
+//!
+//! Satellites may also carry `PayloadConstraints`: a transmit budget per orbit
+//! and a cooldown between contacts. Passes that would break them are left out
+//! and reported as `ConstraintViolation`s, so an operator can see which
+//! contacts the payload could not support.
+
+use crate::acquisition::AcquisitionModel;
+use crate::constants::{defaults, FSO_WAVELENGTH_1550NM};
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::fso_analysis::{self, FsoAnalyzer};
+use crate::ground_station::StationScoped;
+use crate::orbit::SatelliteOrbit;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Ranks visibility windows for contact scheduling
+pub trait PassScorer {
+    /// Quality of a pass; higher scores are scheduled first
+    fn score(&self, window: &VisibilityWindow) -> f64;
+}
+
+impl<F> PassScorer for F
+where
+    F: Fn(&VisibilityWindow) -> f64,
+{
+    fn score(&self, window: &VisibilityWindow) -> f64 {
+        self(window)
+    }
+}
+
+/// Weighted blend of peak elevation, usable duration and predicted link margin
+///
+/// Each term is normalized to 0-1 before weighting: elevation against 90°,
+/// usable time against `reference_duration_seconds`, and the link margin at
+/// closest approach against `reference_link_margin_db`. The score is the
+/// weighted mean, so it also lies in 0-1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefaultPassScorer {
+    pub elevation_weight: f64,
+    pub duration_weight: f64,
+    pub link_margin_weight: f64,
+    /// Usable time that earns the full duration term
+    pub reference_duration_seconds: f64,
+    /// Link margin that earns the full margin term
+    pub reference_link_margin_db: f64,
+    /// Optical terminal used to predict the margin
+    pub wavelength_nm: f64,
+    pub transmit_power_w: f64,
+    /// Derates passes by the probability of acquiring within their usable time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquisition: Option<AcquisitionModel>,
+}
+
+/// A pass with its score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredPass {
+    pub window: VisibilityWindow,
+    pub score: f64,
+}
+
+/// Payload duty-cycle limits for one satellite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadConstraints {
+    /// Span the transmit budget applies to
+    pub orbital_period_seconds: f64,
+    /// Contact time allowed within any span of one orbital period
+    pub max_transmit_minutes_per_orbit: f64,
+    /// Idle time required between the end of one contact and the next
+    pub cooldown_seconds: f64,
+}
+
+/// Payload limit a rejected pass would have broken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConstraintViolationKind {
+    /// Contact time within one orbit, including the rejected pass
+    TransmitBudget { transmit_minutes: f64 },
+    /// Idle time to the nearest booked contact of the same satellite
+    Cooldown { gap_seconds: f64 },
+}
+
+/// Pass left out of the schedule because of payload constraints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintViolation {
+    pub pass: ScoredPass,
+    pub kind: ConstraintViolationKind,
+}
+
+/// Booked contacts with the passes payload constraints ruled out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstrainedSchedule {
+    pub contacts: Vec<ScoredPass>,
+    pub violations: Vec<ConstraintViolation>,
+}
+
+impl PayloadConstraints {
+    /// Constraints over the orbital period of `satellite`
+    pub fn for_satellite(
+        satellite: &SatelliteOrbit,
+        max_transmit_minutes_per_orbit: f64,
+        cooldown_seconds: f64,
+    ) -> Self {
+        Self {
+            orbital_period_seconds: satellite.period_seconds,
+            max_transmit_minutes_per_orbit,
+            cooldown_seconds,
+        }
+    }
+
+    fn validate(&self, satellite_id: &str) -> Result<()> {
+        if !(self.orbital_period_seconds > 0.0
+            && self.max_transmit_minutes_per_orbit >= 0.0
+            && self.cooldown_seconds >= 0.0)
+        {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Invalid payload constraints for {}: period {} s, budget {} min, cooldown {} s",
+                satellite_id,
+                self.orbital_period_seconds,
+                self.max_transmit_minutes_per_orbit,
+                self.cooldown_seconds
+            )));
+        }
+        Ok(())
+    }
+
+    /// Limit `candidate` would break alongside the satellite's `booked` contacts
+    fn check(
+        &self,
+        booked: &[&VisibilityWindow],
+        candidate: &VisibilityWindow,
+    ) -> Option<ConstraintViolationKind> {
+        let seconds = |d: Duration| d.num_milliseconds() as f64 / 1000.0;
+
+        let gap_seconds = booked
+            .iter()
+            .map(|other| {
+                seconds(candidate.start_time - other.end_time)
+                    .max(seconds(other.start_time - candidate.end_time))
+            })
+            .fold(f64::INFINITY, f64::min);
+        if gap_seconds < self.cooldown_seconds {
+            return Some(ConstraintViolationKind::Cooldown { gap_seconds });
+        }
+
+        // The busiest orbit-long span starts at a contact start or ends at a
+        // contact end
+        let period = Duration::milliseconds((self.orbital_period_seconds * 1000.0) as i64);
+        let contacts: Vec<(DateTime<Utc>, DateTime<Utc>)> = booked
+            .iter()
+            .copied()
+            .chain([candidate])
+            .map(|w| (w.start_time, w.end_time))
+            .collect();
+        let transmit_seconds_within = |from: DateTime<Utc>, to: DateTime<Utc>| -> f64 {
+            contacts
+                .iter()
+                .map(|&(start, end)| seconds(end.min(to) - start.max(from)).max(0.0))
+                .sum()
+        };
+        let busiest_seconds = contacts
+            .iter()
+            .flat_map(|&(start, end)| [(start, start + period), (end - period, end)])
+            .map(|(from, to)| transmit_seconds_within(from, to))
+            .fold(0.0, f64::max);
+        let transmit_minutes = busiest_seconds / 60.0;
+        (transmit_minutes > self.max_transmit_minutes_per_orbit)
+            .then_some(ConstraintViolationKind::TransmitBudget { transmit_minutes })
+    }
+}
+
+impl StationScoped for ScoredPass {
+    fn station_id(&self) -> &str {
+        &self.window.station_id
+    }
+}
+
+impl Default for DefaultPassScorer {
+    fn default() -> Self {
+        Self {
+            elevation_weight: 1.0,
+            duration_weight: 1.0,
+            link_margin_weight: 1.0,
+            reference_duration_seconds: 600.0,
+            reference_link_margin_db: 20.0,
+            wavelength_nm: FSO_WAVELENGTH_1550NM * 1e9,
+            transmit_power_w: defaults::FSO_TRANSMIT_POWER_W,
+            acquisition: None,
+        }
+    }
+}
+
+impl DefaultPassScorer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Predict link margins with the terminal of `analyzer`
+    pub fn with_terminal(mut self, analyzer: &FsoAnalyzer) -> Self {
+        self.wavelength_nm = analyzer.wavelength_nm;
+        self.transmit_power_w = analyzer.transmit_power_w;
+        self
+    }
+
+    pub fn with_weights(mut self, elevation: f64, duration: f64, link_margin: f64) -> Self {
+        self.elevation_weight = elevation;
+        self.duration_weight = duration;
+        self.link_margin_weight = link_margin;
+        self
+    }
+
+    /// Scale scores by the probability of acquisition within the pass
+    pub fn with_acquisition(mut self, acquisition: AcquisitionModel) -> Self {
+        self.acquisition = Some(acquisition);
+        self
+    }
+
+    /// Chance of acquiring the partner terminal before the usable time runs out
+    ///
+    /// The scan is sized at the mean range of the pass; without an
+    /// acquisition model this is 1.
+    pub fn acquisition_probability(&self, window: &VisibilityWindow) -> f64 {
+        self.acquisition.as_ref().map_or(1.0, |acquisition| {
+            acquisition.probability_within(window.mean_range_km, window.usable_seconds())
+        })
+    }
+
+    /// Link margin at closest approach
+    pub fn predicted_link_margin_db(&self, window: &VisibilityWindow) -> f64 {
+        fso_analysis::link_margin_db(
+            self.wavelength_nm,
+            self.transmit_power_w,
+            window.min_range_km,
+            fso_analysis::RECEIVER_SENSITIVITY_DBM,
+        )
+    }
+}
+
+impl PassScorer for DefaultPassScorer {
+    fn score(&self, window: &VisibilityWindow) -> f64 {
+        let total_weight = self.elevation_weight + self.duration_weight + self.link_margin_weight;
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+        let elevation = (window.max_elevation_deg / 90.0).clamp(0.0, 1.0);
+        let duration = (window.usable_seconds() / self.reference_duration_seconds).clamp(0.0, 1.0);
+        let link_margin =
+            (self.predicted_link_margin_db(window) / self.reference_link_margin_db).clamp(0.0, 1.0);
+
+        (self.elevation_weight * elevation
+            + self.duration_weight * duration
+            + self.link_margin_weight * link_margin)
+            / total_weight
+            * self.acquisition_probability(window)
+    }
+}
+
+/// Score every window, best first
+///
+/// Ties keep their input order.
+pub fn rank_passes(windows: Vec<VisibilityWindow>, scorer: &dyn PassScorer) -> Vec<ScoredPass> {
+    let mut scored: Vec<ScoredPass> = windows
+        .into_iter()
+        .map(|window| ScoredPass {
+            score: scorer.score(&window),
+            window,
+        })
+        .collect();
+    scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+    scored
+}
+
+/// Book contacts best-first without double-booking a station or satellite
+///
+/// Returns the accepted passes in start-time order.
+pub fn schedule_contacts(
+    windows: Vec<VisibilityWindow>,
+    scorer: &dyn PassScorer,
+) -> Vec<ScoredPass> {
+    book_contacts(windows, scorer, &HashMap::new()).contacts
+}
+
+/// Book contacts like `schedule_contacts`, enforcing payload duty cycles
+///
+/// `constraints` is keyed by satellite ID; satellites without an entry are
+/// unconstrained. Passes that would exceed a budget or cut a cooldown short
+/// are reported in `violations`, best first. Passes lost to station or
+/// satellite double-booking are not reported.
+pub fn schedule_contacts_with_constraints(
+    windows: Vec<VisibilityWindow>,
+    scorer: &dyn PassScorer,
+    constraints: &HashMap<String, PayloadConstraints>,
+) -> Result<ConstrainedSchedule> {
+    for (satellite_id, limits) in constraints {
+        limits.validate(satellite_id)?;
+    }
+    Ok(book_contacts(windows, scorer, constraints))
+}
+
+fn book_contacts(
+    windows: Vec<VisibilityWindow>,
+    scorer: &dyn PassScorer,
+    constraints: &HashMap<String, PayloadConstraints>,
+) -> ConstrainedSchedule {
+    let mut booked: Vec<ScoredPass> = Vec::new();
+    let mut violations = Vec::new();
+    for candidate in rank_passes(windows, scorer) {
+        let conflict = booked.iter().any(|contact| {
+            let shares_terminal = contact.window.station_id == candidate.window.station_id
+                || contact.window.satellite_id == candidate.window.satellite_id;
+            shares_terminal
+                && contact.window.start_time < candidate.window.end_time
+                && candidate.window.start_time < contact.window.end_time
+        });
+        if conflict {
+            continue;
+        }
+
+        if let Some(limits) = constraints.get(&candidate.window.satellite_id) {
+            let same_satellite: Vec<&VisibilityWindow> = booked
+                .iter()
+                .map(|contact| &contact.window)
+                .filter(|window| window.satellite_id == candidate.window.satellite_id)
+                .collect();
+            if let Some(kind) = limits.check(&same_satellite, &candidate.window) {
+                violations.push(ConstraintViolation {
+                    pass: candidate,
+                    kind,
+                });
+                continue;
+            }
+        }
+        booked.push(candidate);
+    }
+    booked.sort_by_key(|contact| contact.window.start_time);
+    ConstrainedSchedule {
+        contacts: booked,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visibility::PassType;
+    use chrono::TimeZone;
+
+    fn window(
+        satellite_id: &str,
+        station_id: &str,
+        start: DateTime<Utc>,
+        minutes: i64,
+        max_elevation_deg: f64,
+    ) -> VisibilityWindow {
+        VisibilityWindow {
+            satellite_id: satellite_id.to_string(),
+            station_id: station_id.to_string(),
+            start_time: start,
+            end_time: start + Duration::minutes(minutes),
+            duration_seconds: minutes as f64 * 60.0,
+            max_elevation_time: start + Duration::minutes(minutes / 2),
+            max_elevation_deg,
+            min_range_km: 9000.0,
+            mean_range_km: 10000.0,
+            azimuth_span_deg: 120.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_schedule_prefers_higher_scoring_passes() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let windows = vec![
+            window("SAT-A", "GS-1", t0, 10, 30.0),
+            window("SAT-B", "GS-1", t0 + Duration::minutes(5), 10, 80.0),
+            window("SAT-A", "GS-2", t0 + Duration::minutes(2), 10, 60.0),
+            window("SAT-C", "GS-2", t0 + Duration::minutes(30), 10, 20.0),
+        ];
+
+        let scorer = DefaultPassScorer::new();
+        assert!(scorer.score(&windows[1]) > scorer.score(&windows[0]));
+        let booked = schedule_contacts(windows.clone(), &scorer);
+        let ids: Vec<_> = booked
+            .iter()
+            .map(|c| (c.window.satellite_id.as_str(), c.window.station_id.as_str()))
+            .collect();
+        // SAT-B wins GS-1, which frees SAT-A for GS-2
+        assert_eq!(
+            ids,
+            [("SAT-A", "GS-2"), ("SAT-B", "GS-1"), ("SAT-C", "GS-2")]
+        );
+
+        // A closure favouring the lowest pass flips the GS-1 choice
+        let lowest_first = |w: &VisibilityWindow| -w.max_elevation_deg;
+        let booked = schedule_contacts(windows, &lowest_first);
+        assert!(booked
+            .iter()
+            .any(|c| c.window.satellite_id == "SAT-A" && c.window.station_id == "GS-1"));
+    }
+
+    #[test]
+    fn test_acquisition_derates_short_passes() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let long = window("SAT-A", "GS-1", t0, 10, 60.0);
+        let mut short = window("SAT-A", "GS-1", t0, 1, 60.0);
+        short.duration_seconds = 8.0;
+
+        // Large ephemeris error: one scan of the cone takes about 25 s
+        let acquisition = AcquisitionModel::new(300.0, 20.0, 10.0, 0.05).unwrap();
+        let plain = DefaultPassScorer::new();
+        let derated = DefaultPassScorer::new().with_acquisition(acquisition);
+
+        assert!((derated.score(&long) - plain.score(&long)).abs() < 1e-6);
+        let probability = derated.acquisition_probability(&short);
+        assert!(probability > 0.5 && probability < 0.9);
+        assert!((derated.score(&short) - probability * plain.score(&short)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_payload_duty_cycle_limits() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let at = |minutes: i64| t0 + Duration::minutes(minutes);
+        let windows = vec![
+            window("SAT-A", "GS-1", at(0), 10, 80.0),
+            // Starts 2 min after the first ends: inside the cooldown
+            window("SAT-A", "GS-2", at(12), 10, 70.0),
+            // Same orbit, 20 min of the 25 min budget
+            window("SAT-A", "GS-3", at(40), 10, 60.0),
+            // Would bring the orbit to 30 min, over the budget
+            window("SAT-A", "GS-1", at(60), 10, 50.0),
+            // Next orbit
+            window("SAT-A", "GS-2", at(110), 10, 40.0),
+            window("SAT-B", "GS-2", at(12), 10, 10.0),
+        ];
+        let constraints = HashMap::from([(
+            "SAT-A".to_string(),
+            PayloadConstraints {
+                orbital_period_seconds: 100.0 * 60.0,
+                max_transmit_minutes_per_orbit: 25.0,
+                cooldown_seconds: 300.0,
+            },
+        )]);
+        let elevation = |w: &VisibilityWindow| w.max_elevation_deg;
+
+        let schedule =
+            schedule_contacts_with_constraints(windows.clone(), &elevation, &constraints).unwrap();
+        let booked: Vec<_> = schedule
+            .contacts
+            .iter()
+            .map(|c| (c.window.satellite_id.as_str(), c.window.start_time))
+            .collect();
+        assert_eq!(
+            booked,
+            [
+                ("SAT-A", at(0)),
+                ("SAT-B", at(12)),
+                ("SAT-A", at(40)),
+                ("SAT-A", at(110))
+            ]
+        );
+        let kinds: Vec<_> = schedule.violations.iter().map(|v| &v.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &ConstraintViolationKind::Cooldown { gap_seconds: 120.0 },
+                &ConstraintViolationKind::TransmitBudget {
+                    transmit_minutes: 30.0
+                },
+            ]
+        );
+
+        // Unconstrained, SAT-A takes every pass and keeps GS-2 from SAT-B
+        assert_eq!(schedule_contacts(windows.clone(), &elevation).len(), 5);
+
+        let invalid = HashMap::from([(
+            "SAT-A".to_string(),
+            PayloadConstraints {
+                orbital_period_seconds: 0.0,
+                max_transmit_minutes_per_orbit: 10.0,
+                cooldown_seconds: 0.0,
+            },
+        )]);
+        assert!(schedule_contacts_with_constraints(windows, &elevation, &invalid).is_err());
+    }
+}
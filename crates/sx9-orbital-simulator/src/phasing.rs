@@ -0,0 +1,218 @@
+//! In-plane phasing by argument of latitude
+//!
+//! Argument of latitude measures a satellite's position along its orbit from
+//! the ascending node, so it stays well defined for the circular orbits most
+//! constellations fly, where the argument of perigee is not. Phase
+//! differences between plane mates, the drift rate of a lowered or raised
+//! drift orbit and the time and delta-v to shift phase are shared by slot
+//! drift monitoring and maneuver planning.
+//!
+//! Drift orbits are circular and reached by Hohmann transfers; rates include
+//! the secular J2 terms. The half-orbit transfers themselves are not counted
+//! in the drift time.
+
+use crate::constants::*;
+use crate::coordinates::wrap_angle_deg;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::{OrbitalElements, SatelliteState};
+use crate::propagation_core::{j2_secular_rates, Elements};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+/// Phase shift flown in a circular drift orbit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhasingPlan {
+    /// Positive moves the satellite ahead
+    pub phase_change_deg: f64,
+    pub drift_semi_major_axis_km: f64,
+    /// Phase gained per day relative to the original orbit
+    pub drift_rate_deg_per_day: f64,
+    pub drift_seconds: f64,
+    /// Both transfers, into the drift orbit and back
+    pub delta_v_m_per_s: f64,
+}
+
+/// Argument of latitude of a state in [0, 360)
+///
+/// Equatorial orbits have no node; the angle is then measured from the
+/// x axis (true longitude).
+pub fn argument_of_latitude_deg(state: &SatelliteState) -> f64 {
+    let r = Vector3::from(state.position_eci);
+    let h = r.cross(&Vector3::from(state.velocity_eci));
+    let node = Vector3::new(-h.y, h.x, 0.0);
+    let node = if node.norm() < 1e-9 * h.norm() {
+        Vector3::x()
+    } else {
+        node.normalize()
+    };
+
+    let sin_u = node.cross(&r).dot(&h.normalize());
+    let cos_u = node.dot(&r);
+    sin_u.atan2(cos_u).to_degrees().rem_euclid(360.0)
+}
+
+/// Signed phase of `other` ahead of `reference`, in [-180, 180)
+///
+/// `other` is projected into the reference orbit plane, so a small plane
+/// mismatch does not show up as phase.
+pub fn phase_difference_deg(reference: &SatelliteState, other: &SatelliteState) -> f64 {
+    let r = Vector3::from(reference.position_eci);
+    let h = r.cross(&Vector3::from(reference.velocity_eci));
+    let radial = r.normalize();
+    let along_track = h.cross(&radial).normalize();
+
+    let other = Vector3::from(other.position_eci);
+    wrap_angle_deg(
+        other
+            .dot(&along_track)
+            .atan2(other.dot(&radial))
+            .to_degrees(),
+    )
+}
+
+/// Phase gained per day by a circular orbit `altitude_offset_km` below `elements`
+///
+/// Negative offsets raise the orbit and give a negative rate.
+pub fn phase_drift_rate_deg_per_day(elements: &OrbitalElements, altitude_offset_km: f64) -> f64 {
+    let rate = |semi_major_axis_km: f64| {
+        let rates = j2_secular_rates(&Elements {
+            semi_major_axis_km,
+            eccentricity: 0.0,
+            inclination_rad: elements.inclination_deg * DEG_TO_RAD,
+            raan_rad: 0.0,
+            argument_of_perigee_rad: 0.0,
+            mean_anomaly_rad: 0.0,
+        });
+        rates.argument_of_perigee_rad_s + rates.mean_anomaly_rad_s
+    };
+    let a = elements.semi_major_axis_km;
+    (rate(a - altitude_offset_km) - rate(a)) * RAD_TO_DEG * SOLAR_DAY_SECONDS
+}
+
+/// Drift `phase_change_deg` by lowering (to gain) or raising (to lose) the orbit by `drift_altitude_km`
+pub fn plan_phase_change(
+    elements: &OrbitalElements,
+    phase_change_deg: f64,
+    drift_altitude_km: f64,
+) -> Result<PhasingPlan> {
+    if !drift_altitude_km.is_finite() || drift_altitude_km <= 0.0 {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Drift altitude must be positive, got {} km",
+            drift_altitude_km
+        )));
+    }
+    if !phase_change_deg.is_finite() {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Phase change must be finite, got {}°",
+            phase_change_deg
+        )));
+    }
+
+    let offset_km = drift_altitude_km.copysign(phase_change_deg);
+    let a = elements.semi_major_axis_km;
+    let drift_a = a - offset_km;
+    if !(MIN_SEMI_MAJOR_AXIS_KM..=MAX_SEMI_MAJOR_AXIS_KM).contains(&drift_a) {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Drift orbit semi-major axis {:.1} km is outside {:.1} to {:.1} km",
+            drift_a, MIN_SEMI_MAJOR_AXIS_KM, MAX_SEMI_MAJOR_AXIS_KM
+        )));
+    }
+
+    let drift_rate_deg_per_day = phase_drift_rate_deg_per_day(elements, offset_km);
+    Ok(PhasingPlan {
+        phase_change_deg,
+        drift_semi_major_axis_km: drift_a,
+        drift_rate_deg_per_day,
+        drift_seconds: phase_change_deg / drift_rate_deg_per_day * SOLAR_DAY_SECONDS,
+        delta_v_m_per_s: 2.0 * hohmann_delta_v_km_per_s(a, drift_a) * KM_TO_M,
+    })
+}
+
+/// Plans for two plane mates to exchange phase, drifting in opposite directions
+///
+/// Each moves by their current phase difference, taking the shorter way
+/// around. The lowered satellite drifts slightly faster than the raised one,
+/// so it finishes a little earlier.
+pub fn plan_slot_swap(
+    first: &SatelliteState,
+    second: &SatelliteState,
+    elements: &OrbitalElements,
+    drift_altitude_km: f64,
+) -> Result<(PhasingPlan, PhasingPlan)> {
+    let separation_deg = phase_difference_deg(first, second);
+    Ok((
+        plan_phase_change(elements, separation_deg, drift_altitude_km)?,
+        plan_phase_change(elements, -separation_deg, drift_altitude_km)?,
+    ))
+}
+
+/// Total delta-v of a Hohmann transfer between circular radii
+fn hohmann_delta_v_km_per_s(from_km: f64, to_km: f64) -> f64 {
+    let transfer_a = 0.5 * (from_km + to_km);
+    let departure = (EARTH_MU * (2.0 / from_km - 1.0 / transfer_a)).sqrt();
+    let arrival = (EARTH_MU * (2.0 / to_km - 1.0 / transfer_a)).sqrt();
+    (departure - (EARTH_MU / from_km).sqrt()).abs() + ((EARTH_MU / to_km).sqrt() - arrival).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::SatelliteOrbit;
+    use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+    use chrono::{TimeZone, Utc};
+
+    fn state(inclination_deg: f64, mean_anomaly_deg: f64) -> SatelliteState {
+        let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let orbit = SatelliteOrbit::builder()
+            .altitude_km(550.0)
+            .inclination_deg(inclination_deg)
+            .raan_deg(40.0)
+            .argument_of_perigee_deg(30.0)
+            .mean_anomaly_deg(mean_anomaly_deg)
+            .epoch(epoch)
+            .build()
+            .unwrap();
+        KeplerianPropagator::new().propagate(&orbit, epoch).unwrap()
+    }
+
+    #[test]
+    fn test_argument_of_latitude_and_phase() {
+        let leader = state(53.0, 100.0);
+        let follower = state(53.0, 70.0);
+        assert!((argument_of_latitude_deg(&leader) - 130.0).abs() < 1e-6);
+        assert!((argument_of_latitude_deg(&state(0.0, 10.0)) - 80.0).abs() < 1e-6);
+        assert!((phase_difference_deg(&follower, &leader) - 30.0).abs() < 1e-6);
+        assert!((phase_difference_deg(&leader, &follower) + 30.0).abs() < 1e-6);
+        // Across the node the difference takes the short way round
+        assert!(
+            (phase_difference_deg(&state(53.0, 320.0), &state(53.0, 10.0)) - 50.0).abs() < 1e-6
+        );
+    }
+
+    #[test]
+    fn test_phase_change_and_slot_swap() {
+        let elements =
+            OrbitalElements::new(EARTH_RADIUS_KM + 550.0, 0.0, 53.0, 40.0, 30.0, 0.0).unwrap();
+
+        // Lowering 550 km by 10 km gains about 1.5 * 10 / 6928 of 15.2 rev/day
+        let plan = plan_phase_change(&elements, 30.0, 10.0).unwrap();
+        assert!((plan.drift_semi_major_axis_km - elements.semi_major_axis_km + 10.0).abs() < 1e-9);
+        assert!((plan.drift_rate_deg_per_day - 11.8).abs() < 0.1);
+        assert!(
+            (plan.drift_seconds / SOLAR_DAY_SECONDS - 30.0 / plan.drift_rate_deg_per_day).abs()
+                < 1e-9
+        );
+        // Two 10 km transfers at LEO speed cost about 11 m/s
+        assert!(plan.delta_v_m_per_s > 10.0 && plan.delta_v_m_per_s < 12.0);
+
+        let back = plan_phase_change(&elements, -30.0, 10.0).unwrap();
+        assert!(back.drift_semi_major_axis_km > elements.semi_major_axis_km);
+        assert!(back.drift_rate_deg_per_day < 0.0 && back.drift_seconds > 0.0);
+        assert!(plan_phase_change(&elements, 30.0, 0.0).is_err());
+
+        let (first, second) =
+            plan_slot_swap(&state(53.0, 70.0), &state(53.0, 100.0), &elements, 10.0).unwrap();
+        assert!((first.phase_change_deg - 30.0).abs() < 1e-6);
+        assert!((second.phase_change_deg + 30.0).abs() < 1e-6);
+    }
+}
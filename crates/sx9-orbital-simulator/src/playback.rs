@@ -0,0 +1,407 @@
+//! Playback of recorded ephemeris
+//!
+//! `RecordedEphemeris` holds recorded state vectors, read from CCSDS OEM
+//! files, Parquet state tables written by `arrow_export`, or in-memory
+//! `StateEphemeris` runs. `PlaybackPropagator` serves them through the
+//! `OrbitalPropagator` interface, so `SatelliteSimulator::from_recording`
+//! replays real telemetry through the normal tick and event pipeline.
+//! Between records states are Hermite interpolated; outside the recording
+//! propagation fails rather than extrapolating.
+
+use crate::constants::RAD_TO_DEG;
+use crate::covariance::StateEphemeris;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::interpolation::StateInterpolator;
+use crate::orbit::{OrbitalElements, SatelliteOrbit, SatelliteState};
+use crate::propagation_core::{self, StateVector};
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Inertial frames accepted in OEM metadata
+const OEM_INERTIAL_FRAMES: [&str; 4] = ["EME2000", "GCRF", "ICRF", "TEME"];
+
+/// Recorded state vectors for one or more satellites
+#[derive(Debug, Clone)]
+pub struct RecordedEphemeris {
+    states: StateInterpolator,
+}
+
+impl Default for RecordedEphemeris {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordedEphemeris {
+    pub fn new() -> Self {
+        Self {
+            // Recordings are kept whole
+            states: StateInterpolator::new(usize::MAX),
+        }
+    }
+
+    /// Build from states of any satellites, in any order
+    pub fn from_states(states: impl IntoIterator<Item = SatelliteState>) -> Self {
+        let mut recording = Self::new();
+        recording.states.extend(states);
+        recording
+    }
+
+    /// Add one recorded state; a state at an existing timestamp replaces it
+    pub fn push(&mut self, state: SatelliteState) {
+        self.states.push(state);
+    }
+
+    /// Add every record of a generated or loaded ephemeris
+    pub fn add_ephemeris(&mut self, ephemeris: &StateEphemeris) {
+        self.states.extend(ephemeris.records.iter().map(|record| {
+            SatelliteState::new(
+                ephemeris.satellite_id.clone(),
+                record.timestamp,
+                record.position_eci,
+                record.velocity_eci,
+            )
+        }));
+    }
+
+    /// Read a CCSDS OEM file in KVN format
+    pub fn from_oem_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).for_path(path)?;
+        Self::parse_oem(&content).for_path(path)
+    }
+
+    /// Parse CCSDS OEM (KVN) text
+    ///
+    /// Each segment's `OBJECT_NAME` becomes the satellite ID. Segments must
+    /// use an inertial `REF_FRAME` and the UTC time system; accelerations and
+    /// covariance blocks are ignored.
+    pub fn parse_oem(content: &str) -> Result<Self> {
+        let mut recording = Self::new();
+        let mut segment: Option<OemSegment> = None;
+        let mut in_meta = false;
+        let mut in_covariance = false;
+
+        for (index, raw) in content.lines().enumerate() {
+            let line = raw.trim();
+            let at_line = |message: String| {
+                OrbitalMechanicsError::config_error(format!("OEM line {}: {}", index + 1, message))
+            };
+            if line.is_empty() || line.starts_with("COMMENT") {
+                continue;
+            }
+            match line {
+                "META_START" => {
+                    in_meta = true;
+                    segment = Some(OemSegment::default());
+                    continue;
+                }
+                "META_STOP" => {
+                    in_meta = false;
+                    segment
+                        .as_ref()
+                        .map(OemSegment::validate)
+                        .transpose()
+                        .map_err(at_line)?;
+                    continue;
+                }
+                "COVARIANCE_START" => in_covariance = true,
+                "COVARIANCE_STOP" => in_covariance = false,
+                _ => {}
+            }
+            if in_covariance || line.starts_with("COVARIANCE_") {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                if in_meta {
+                    if let Some(segment) = segment.as_mut() {
+                        segment.set(key.trim(), value.trim());
+                    }
+                }
+                // Header keywords (CCSDS_OEM_VERS, ORIGINATOR, ...) carry no state
+                continue;
+            }
+
+            let segment = segment
+                .as_ref()
+                .filter(|_| !in_meta)
+                .ok_or_else(|| at_line("state line outside a data block".to_string()))?;
+            let state = parse_oem_state(&segment.object_name, line).map_err(at_line)?;
+            recording.push(state);
+        }
+
+        if recording.satellite_ids().is_empty() {
+            return Err(OrbitalMechanicsError::config_error(
+                "OEM contains no state vectors",
+            ));
+        }
+        Ok(recording)
+    }
+
+    /// Read a state table written by `write_states_parquet`
+    #[cfg(feature = "arrow-export")]
+    pub fn from_parquet<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::from_states(crate::arrow_export::read_states_parquet(
+            path,
+        )?))
+    }
+
+    /// Recorded satellites, sorted by ID
+    pub fn satellite_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.states.satellite_ids().map(String::from).collect();
+        ids.sort();
+        ids
+    }
+
+    /// First and last recorded epochs of one satellite
+    pub fn time_span(&self, satellite_id: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.states.time_span(satellite_id)
+    }
+
+    /// Interval covered by every satellite's recording
+    ///
+    /// `None` when nothing is recorded or the recordings do not overlap.
+    pub fn span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut spans = self
+            .states
+            .satellite_ids()
+            .filter_map(|id| self.states.time_span(id));
+        let first = spans.next()?;
+        let (start, end) = spans.fold(first, |(start, end), (s, e)| (start.max(s), end.min(e)));
+        (start <= end).then_some((start, end))
+    }
+
+    /// Recorded state at `time`, interpolated between records
+    pub fn state_at(&self, satellite_id: &str, time: DateTime<Utc>) -> Result<SatelliteState> {
+        self.states.interpolate(satellite_id, time)
+    }
+
+    /// Orbit osculating with the recording at `epoch`
+    ///
+    /// Gives playback satellites the elements that slot, health and display
+    /// code expect; the recorded states themselves come from
+    /// `PlaybackPropagator`.
+    pub fn osculating_orbit(
+        &self,
+        satellite_id: &str,
+        epoch: DateTime<Utc>,
+    ) -> Result<SatelliteOrbit> {
+        let state = self.state_at(satellite_id, epoch)?;
+        osculating_orbit(satellite_id, satellite_id, &state)
+    }
+}
+
+/// Propagator that replays a recording, keyed by `SatelliteOrbit::satellite_id`
+#[derive(Debug, Clone)]
+pub struct PlaybackPropagator {
+    recording: Arc<RecordedEphemeris>,
+}
+
+impl PlaybackPropagator {
+    pub fn new(recording: RecordedEphemeris) -> Self {
+        Self {
+            recording: Arc::new(recording),
+        }
+    }
+
+    pub fn recording(&self) -> &RecordedEphemeris {
+        &self.recording
+    }
+}
+
+impl OrbitalPropagator for PlaybackPropagator {
+    fn propagate(&self, satellite: &SatelliteOrbit, time: DateTime<Utc>) -> Result<SatelliteState> {
+        self.recording.state_at(&satellite.satellite_id, time)
+    }
+
+    fn name(&self) -> &str {
+        "Playback"
+    }
+
+    fn max_propagation_duration(&self) -> Duration {
+        self.recording
+            .span()
+            .map_or_else(Duration::zero, |(start, end)| end - start)
+    }
+}
+
+/// Metadata of the OEM segment being read
+#[derive(Debug, Default)]
+struct OemSegment {
+    object_name: String,
+    ref_frame: String,
+    time_system: String,
+}
+
+impl OemSegment {
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "OBJECT_NAME" => self.object_name = value.to_string(),
+            "REF_FRAME" => self.ref_frame = value.to_string(),
+            "TIME_SYSTEM" => self.time_system = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        if self.object_name.is_empty() {
+            return Err("segment has no OBJECT_NAME".to_string());
+        }
+        if !OEM_INERTIAL_FRAMES.contains(&self.ref_frame.as_str()) {
+            return Err(format!(
+                "REF_FRAME {:?} is not an inertial frame",
+                self.ref_frame
+            ));
+        }
+        if self.time_system != "UTC" {
+            return Err(format!(
+                "TIME_SYSTEM {:?} is not supported, expected UTC",
+                self.time_system
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `epoch x y z vx vy vz [ax ay az]` in km and km/s
+/// Two-body orbit osculating with `state` at its timestamp
+pub(crate) fn osculating_orbit(
+    satellite_id: &str,
+    name: &str,
+    state: &SatelliteState,
+) -> Result<SatelliteOrbit> {
+    let elements = propagation_core::state_to_elements(&StateVector {
+        position_km: state.position_eci,
+        velocity_km_s: state.velocity_eci,
+    })
+    .for_satellite(satellite_id)?;
+    let elements = OrbitalElements::new(
+        elements.semi_major_axis_km,
+        elements.eccentricity,
+        elements.inclination_rad * RAD_TO_DEG,
+        elements.raan_rad * RAD_TO_DEG,
+        elements.argument_of_perigee_rad * RAD_TO_DEG,
+        elements.mean_anomaly_rad * RAD_TO_DEG,
+    )
+    .for_satellite(satellite_id)?;
+    Ok(SatelliteOrbit::new(
+        satellite_id.to_string(),
+        name.to_string(),
+        elements,
+        state.timestamp,
+    ))
+}
+
+fn parse_oem_state(satellite_id: &str, line: &str) -> std::result::Result<SatelliteState, String> {
+    let mut fields = line.split_whitespace();
+    let epoch = fields.next().map(parse_oem_epoch).transpose()?;
+    let values: Vec<f64> = fields
+        .map(|field| {
+            field
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number {:?}", field))
+        })
+        .collect::<std::result::Result<_, _>>()?;
+    let (Some(epoch), 6 | 9) = (epoch, values.len()) else {
+        return Err(format!(
+            "expected an epoch and 6 or 9 values, got {:?}",
+            line
+        ));
+    };
+    Ok(SatelliteState::new(
+        satellite_id.to_string(),
+        epoch,
+        [values[0], values[1], values[2]],
+        [values[3], values[4], values[5]],
+    ))
+}
+
+/// Calendar (`2024-03-20T12:00:00.000`) or day-of-year (`2024-080T12:00:00`) epoch
+///
+/// A leap second (`23:59:60.xxx`) is kept as such, and interpolation counts it.
+fn parse_oem_epoch(field: &str) -> std::result::Result<DateTime<Utc>, String> {
+    let field = field.trim_end_matches('Z');
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%jT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(field, format).ok())
+        .map(|epoch| epoch.and_utc())
+        .ok_or_else(|| format!("invalid epoch {:?}", field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::TimeZone;
+    use std::fmt::Write as _;
+
+    #[test]
+    fn test_oem_playback_matches_source() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(14378.0, 0.001, 55.0, 30.0, 10.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new("MEO-01".to_string(), "MEO 1".to_string(), elements, epoch);
+        let source = KeplerianPropagator::new();
+
+        let mut oem = String::from(
+            "CCSDS_OEM_VERS = 2.0\nORIGINATOR = TEST\n\nMETA_START\nOBJECT_NAME = MEO-01\n\
+             OBJECT_ID = 2024-001A\nCENTER_NAME = EARTH\nREF_FRAME = EME2000\n\
+             TIME_SYSTEM = UTC\nMETA_STOP\n\nCOMMENT Two-body truth\n",
+        );
+        for minute in 0..=10 {
+            let state = source
+                .propagate(&orbit, epoch + Duration::minutes(minute))
+                .unwrap();
+            let p = state.position_eci;
+            let v = state.velocity_eci;
+            writeln!(
+                oem,
+                "{} {:.6} {:.6} {:.6} {:.9} {:.9} {:.9}",
+                state.timestamp.format("%Y-%m-%dT%H:%M:%S%.3f"),
+                p[0],
+                p[1],
+                p[2],
+                v[0],
+                v[1],
+                v[2]
+            )
+            .unwrap();
+        }
+
+        let recording = RecordedEphemeris::parse_oem(&oem).unwrap();
+        assert_eq!(recording.satellite_ids(), ["MEO-01"]);
+        assert_eq!(
+            recording.span(),
+            Some((epoch, epoch + Duration::minutes(10)))
+        );
+
+        // Between records the replay tracks the source to well under a metre
+        let playback = PlaybackPropagator::new(recording);
+        let time = epoch + Duration::seconds(270);
+        let replayed = playback.propagate(&orbit, time).unwrap();
+        let truth = source.propagate(&orbit, time).unwrap();
+        let error_km = (0..3)
+            .map(|i| (replayed.position_eci[i] - truth.position_eci[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(error_km < 1e-3, "interpolation error {} km", error_km);
+        assert!(playback
+            .propagate(&orbit, epoch + Duration::minutes(11))
+            .is_err());
+
+        let osculating = playback
+            .recording()
+            .osculating_orbit("MEO-01", epoch)
+            .unwrap();
+        assert!((osculating.elements.semi_major_axis_km - 14378.0).abs() < 1e-3);
+
+        let ecef = oem.replace("EME2000", "ITRF");
+        assert!(RecordedEphemeris::parse_oem(&ecef).is_err());
+
+        let leap = parse_oem_epoch("2016-366T23:59:60.500").unwrap();
+        assert!(crate::time::is_leap_second(leap));
+    }
+}
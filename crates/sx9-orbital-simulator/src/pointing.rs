@@ -0,0 +1,555 @@
+//! Antenna and optical terminal pointing schedules
+//!
+//! Generates time-tagged azimuth/elevation tables (optionally with range,
+//! range rate and Doppler) for each contact so antenna control units can be
+//! driven directly from visibility results. Stations with a known mount
+//! also get the matching axis angles and rates (see `mount`).
+
+use crate::constants::*;
+use crate::constellation::Constellation;
+use crate::coordinates::{unwrap_angles_deg, EarthModel};
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::export::ExportOptions;
+use crate::ground_station::{GroundStation, GroundStationNetwork, StationScoped};
+use crate::mount::{MountAxes, MountType};
+use crate::orbit::SatelliteOrbit;
+use crate::propagator::OrbitalPropagator;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Single time-tagged pointing command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointingSample {
+    pub timestamp: DateTime<Utc>,
+    /// In [0, 360), or continuous across north when the generator unwraps azimuth
+    pub azimuth_deg: f64,
+    pub elevation_deg: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_km: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_rate_km_per_s: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doppler_shift_hz: Option<f64>,
+    /// Mount axis angles and rates, when the station mount is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub axes: Option<MountAxes>,
+}
+
+/// Pointing table for one contact between a satellite and a ground station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointingSchedule {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub cadence_seconds: f64,
+    /// Station mount the sample axes refer to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mount: Option<MountType>,
+    pub samples: Vec<PointingSample>,
+}
+
+/// Pointing schedule generator
+#[derive(Debug, Clone)]
+pub struct PointingScheduleGenerator {
+    /// Interval between pointing samples
+    pub cadence_seconds: f64,
+    /// Include slant range in each sample
+    pub include_range: bool,
+    /// Include range rate and Doppler shift in each sample
+    pub include_doppler: bool,
+    /// Carrier frequency used for the Doppler shift
+    pub carrier_frequency_hz: f64,
+    /// Keep azimuth continuous through north instead of jumping 360°
+    pub unwrap_azimuth: bool,
+    /// Shape the stations sit on
+    pub earth_model: EarthModel,
+}
+
+impl PointingScheduleGenerator {
+    /// Create generator with 1 s cadence, range enabled and Doppler disabled
+    pub fn new() -> Self {
+        Self {
+            cadence_seconds: 1.0,
+            include_range: true,
+            include_doppler: false,
+            carrier_frequency_hz: SPEED_OF_LIGHT / FSO_WAVELENGTH_1550NM,
+            unwrap_azimuth: false,
+            earth_model: EarthModel::Sphere,
+        }
+    }
+
+    /// Create generator with a custom cadence
+    pub fn with_cadence(cadence_seconds: f64) -> Self {
+        Self {
+            cadence_seconds,
+            ..Self::new()
+        }
+    }
+
+    /// Enable range rate and Doppler output for the given carrier frequency
+    pub fn with_doppler(mut self, carrier_frequency_hz: f64) -> Self {
+        self.include_doppler = true;
+        self.carrier_frequency_hz = carrier_frequency_hz;
+        self
+    }
+
+    /// Emit continuous azimuth for mounts with cable wrap
+    ///
+    /// Passes crossing north, common from high-latitude stations, otherwise
+    /// jump between 359° and 0°. The unwrapped series starts in [0, 360) and
+    /// may then run below 0° or past 360°.
+    pub fn with_unwrapped_azimuth(mut self) -> Self {
+        self.unwrap_azimuth = true;
+        self
+    }
+
+    /// Place stations on `earth_model` (the spherical model by default)
+    pub fn with_earth_model(mut self, earth_model: EarthModel) -> Self {
+        self.earth_model = earth_model;
+        self
+    }
+
+    /// Generate the pointing schedule for a single visibility window
+    pub fn generate(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<PointingSchedule> {
+        if !self.cadence_seconds.is_finite() || self.cadence_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Pointing cadence must be positive, got {} s",
+                self.cadence_seconds
+            )));
+        }
+
+        let step = Duration::milliseconds((self.cadence_seconds * 1000.0).round().max(1.0) as i64);
+        let mut samples = Vec::new();
+        let mut time = window.start_time;
+
+        loop {
+            samples.push(self.sample(satellite, station, time, propagator)?);
+
+            if time >= window.end_time {
+                break;
+            }
+            time = (time + step).min(window.end_time);
+        }
+
+        if let Some(mount) = &station.mount {
+            let look_angles: Vec<_> = samples
+                .iter()
+                .map(|s| (s.timestamp, s.azimuth_deg, s.elevation_deg))
+                .collect();
+            let axes = mount.track(&look_angles, station.position.latitude_deg);
+            for (sample, axes) in samples.iter_mut().zip(axes) {
+                sample.axes = Some(axes);
+            }
+        }
+
+        if self.unwrap_azimuth {
+            let azimuths: Vec<f64> = samples.iter().map(|s| s.azimuth_deg).collect();
+            for (sample, azimuth_deg) in samples.iter_mut().zip(unwrap_angles_deg(&azimuths)) {
+                sample.azimuth_deg = azimuth_deg;
+            }
+        }
+
+        Ok(PointingSchedule {
+            satellite_id: satellite.satellite_id.clone(),
+            station_id: station.station_id.clone(),
+            start_time: window.start_time,
+            end_time: window.end_time,
+            cadence_seconds: self.cadence_seconds,
+            mount: station.mount.clone(),
+            samples,
+        })
+    }
+
+    /// Generate pointing schedules for every window, resolving satellites and stations by ID
+    pub fn generate_for_windows(
+        &self,
+        windows: &[VisibilityWindow],
+        constellation: &Constellation,
+        stations: &GroundStationNetwork,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Vec<PointingSchedule>> {
+        windows
+            .iter()
+            .map(|window| {
+                let satellite = constellation
+                    .get_satellite(&window.satellite_id)
+                    .ok_or_else(|| {
+                        OrbitalMechanicsError::SatelliteNotFound(window.satellite_id.clone())
+                    })?;
+                let station = stations.get_station(&window.station_id).ok_or_else(|| {
+                    OrbitalMechanicsError::GroundStationNotFound(window.station_id.clone())
+                })?;
+
+                self.generate(satellite, station, window, propagator)
+            })
+            .collect()
+    }
+
+    /// Compute a single pointing sample
+    fn sample(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<PointingSample> {
+        let state = propagator.propagate(satellite, time)?;
+        let look_angles = state.look_angles_from_station_with_model(
+            &self.earth_model,
+            station.position.latitude_deg,
+            station.position.longitude_deg,
+            station.position.elevation_m,
+        );
+
+        let (range_rate_km_per_s, doppler_shift_hz) = if self.include_doppler {
+            let range_rate = look_angles.range_rate_km_per_s;
+            let doppler = -self.carrier_frequency_hz * range_rate * KM_TO_M / SPEED_OF_LIGHT;
+            (Some(range_rate), Some(doppler))
+        } else {
+            (None, None)
+        };
+
+        Ok(PointingSample {
+            timestamp: time,
+            azimuth_deg: look_angles.azimuth_deg,
+            elevation_deg: look_angles.elevation_deg,
+            range_km: self.include_range.then_some(look_angles.range_km),
+            range_rate_km_per_s,
+            doppler_shift_hz,
+            axes: None,
+        })
+    }
+}
+
+impl Default for PointingScheduleGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StationScoped for PointingSchedule {
+    fn station_id(&self) -> &str {
+        &self.station_id
+    }
+}
+
+impl PointingSchedule {
+    /// Render schedule as CSV (one row per sample, optional columns only when present)
+    ///
+    /// Mount axis columns are named after the mount's axes, e.g. `x_deg`.
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&ExportOptions::default())
+    }
+
+    /// Render schedule as CSV in the units and format of `options`
+    pub fn to_csv_with(&self, options: &ExportOptions) -> String {
+        let has_range = self.samples.iter().any(|s| s.range_km.is_some());
+        let has_doppler = self.samples.iter().any(|s| s.doppler_shift_hz.is_some());
+        let axis_names = self
+            .mount
+            .as_ref()
+            .filter(|_| self.samples.iter().any(|s| s.axes.is_some()))
+            .map(MountType::axis_names);
+
+        let angle = options.angle_unit.suffix();
+        let distance = options.distance_unit.suffix();
+
+        let mut csv = format!("timestamp_utc,azimuth_{0},elevation_{0}", angle);
+        if has_range {
+            let _ = write!(csv, ",range_{}", distance);
+        }
+        if has_doppler {
+            let _ = write!(csv, ",range_rate_{}_per_s,doppler_shift_hz", distance);
+        }
+        if let Some([primary, secondary]) = axis_names {
+            let _ = write!(
+                csv,
+                ",{0}_{2},{1}_{2},{0}_rate_{2}_per_s,{1}_rate_{2}_per_s,within_limits",
+                primary, secondary, angle
+            );
+        }
+        csv.push('\n');
+
+        for sample in &self.samples {
+            let _ = write!(
+                csv,
+                "{},{},{}",
+                options.timestamp(sample.timestamp),
+                options.angle(sample.azimuth_deg, Some(6)),
+                options.angle(sample.elevation_deg, Some(6))
+            );
+            if has_range {
+                let range_km = sample.range_km.unwrap_or(f64::NAN);
+                let _ = write!(csv, ",{}", options.distance(range_km, Some(6)));
+            }
+            if has_doppler {
+                let _ = write!(
+                    csv,
+                    ",{},{}",
+                    options.distance(sample.range_rate_km_per_s.unwrap_or(f64::NAN), Some(9)),
+                    options.decimal(sample.doppler_shift_hz.unwrap_or(f64::NAN), Some(3))
+                );
+            }
+            if axis_names.is_some() {
+                match &sample.axes {
+                    Some(axes) => {
+                        let _ = write!(
+                            csv,
+                            ",{},{},{},{},{}",
+                            options.angle(axes.primary_deg, Some(6)),
+                            options.angle(axes.secondary_deg, Some(6)),
+                            options.angle(axes.primary_rate_deg_per_s, Some(6)),
+                            options.angle(axes.secondary_rate_deg_per_s, Some(6)),
+                            axes.within_limits
+                        );
+                    }
+                    None => csv.push_str(",NaN,NaN,NaN,NaN,false"),
+                }
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Render schedule as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write schedule to a CSV file
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_csv_with(path, &ExportOptions::default())
+    }
+
+    /// Write schedule to a CSV file in the units and format of `options`
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> Result<()> {
+        fs::write(path, self.to_csv_with(options))?;
+        Ok(())
+    }
+
+    /// Write schedule to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{AngleUnit, DistanceUnit};
+    use crate::ground_station::StationPosition;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use crate::visibility::PassType;
+
+    fn test_setup() -> (SatelliteOrbit, GroundStation, VisibilityWindow) {
+        let epoch = Utc::now();
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            epoch,
+        );
+
+        let station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+
+        let window = VisibilityWindow {
+            satellite_id: "TEST-01".to_string(),
+            station_id: "GS-001".to_string(),
+            start_time: epoch,
+            end_time: epoch + Duration::seconds(60),
+            duration_seconds: 60.0,
+            max_elevation_time: epoch,
+            max_elevation_deg: 90.0,
+            min_range_km: 8000.0,
+            mean_range_km: 8000.0,
+            azimuth_span_deg: 0.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        };
+
+        (satellite, station, window)
+    }
+
+    #[test]
+    fn test_schedule_generation() {
+        let (satellite, station, window) = test_setup();
+        let generator = PointingScheduleGenerator::with_cadence(10.0).with_doppler(1.0e9);
+
+        let schedule = generator
+            .generate(&satellite, &station, &window, &KeplerianPropagator::new())
+            .unwrap();
+
+        assert_eq!(schedule.samples.len(), 7);
+        assert_eq!(schedule.samples.last().unwrap().timestamp, window.end_time);
+        assert!(schedule
+            .samples
+            .iter()
+            .all(|s| s.doppler_shift_hz.is_some()));
+
+        // Satellite starts directly overhead, so the pass begins near zenith
+        assert!(schedule.samples[0].elevation_deg > 89.0);
+    }
+
+    #[test]
+    fn test_csv_and_json_export() {
+        let (satellite, station, window) = test_setup();
+        let generator = PointingScheduleGenerator::with_cadence(30.0);
+
+        let schedule = generator
+            .generate(&satellite, &station, &window, &KeplerianPropagator::new())
+            .unwrap();
+
+        let csv = schedule.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("timestamp_utc,azimuth_deg,elevation_deg,range_km")
+        );
+        assert_eq!(lines.count(), 3);
+
+        let options = ExportOptions::default()
+            .with_distance_unit(DistanceUnit::Meters)
+            .with_angle_unit(AngleUnit::Radians);
+        let si_csv = schedule.to_csv_with(&options);
+        assert!(si_csv.starts_with("timestamp_utc,azimuth_rad,elevation_rad,range_m\n"));
+        let range = |csv: &str| -> f64 {
+            let row = csv.lines().nth(1).unwrap();
+            row.split(',').nth(3).unwrap().parse().unwrap()
+        };
+        assert!((range(&si_csv) - 1000.0 * range(&csv)).abs() < 1e-3);
+
+        let json = schedule.to_json().unwrap();
+        let parsed: PointingSchedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.samples.len(), schedule.samples.len());
+        assert!(parsed.samples[0].doppler_shift_hz.is_none());
+    }
+
+    #[test]
+    fn test_schedule_in_mount_axes() {
+        let (satellite, mut station, window) = test_setup();
+        station.mount = Some(MountType::xy());
+        let generator = PointingScheduleGenerator::with_cadence(30.0);
+
+        let schedule = generator
+            .generate(&satellite, &station, &window, &KeplerianPropagator::new())
+            .unwrap();
+
+        // Overhead start is the centre of an X/Y mount, away from its keyholes
+        let first = schedule.samples[0].axes.as_ref().unwrap();
+        assert!(first.primary_deg.abs() < 1.0 && first.secondary_deg.abs() < 1.0);
+        assert!(schedule.samples.iter().all(|s| s.axes.is_some()));
+        assert!(schedule
+            .to_csv()
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with("range_km,x_deg,y_deg,x_rate_deg_per_s,y_rate_deg_per_s,within_limits"));
+    }
+
+    #[test]
+    fn test_invalid_cadence() {
+        let (satellite, station, window) = test_setup();
+        let generator = PointingScheduleGenerator::with_cadence(0.0);
+
+        assert!(generator
+            .generate(&satellite, &station, &window, &KeplerianPropagator::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_polar_passes_over_svalbard_unwrap_azimuth() {
+        use crate::visibility::VisibilityCalculator;
+
+        let epoch = Utc::now();
+        let propagator = KeplerianPropagator::new();
+        let station = GroundStation {
+            station_id: "SVAL".to_string(),
+            name: "Svalbard".to_string(),
+            position: StationPosition {
+                latitude_deg: 78.23,
+                longitude_deg: 15.39,
+                elevation_m: 500.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+        let calculator = VisibilityCalculator::with_params(5.0, 10.0);
+        let raw = PointingScheduleGenerator::with_cadence(10.0);
+        let unwrapped = PointingScheduleGenerator::with_cadence(10.0).with_unwrapped_azimuth();
+        let max_step = |schedule: &PointingSchedule| {
+            schedule
+                .samples
+                .windows(2)
+                .map(|pair| (pair[1].azimuth_deg - pair[0].azimuth_deg).abs())
+                .fold(0.0, f64::max)
+        };
+
+        // 90° planes every 30° of RAAN; every one passes near the station
+        let mut north_crossings = 0;
+        for plane in 0..6 {
+            let elements =
+                OrbitalElements::new(7000.0, 0.0, 90.0, plane as f64 * 30.0, 0.0, 0.0).unwrap();
+            let satellite =
+                SatelliteOrbit::new(format!("POL-{}", plane), String::new(), elements, epoch);
+            let windows = calculator
+                .calculate_windows(&satellite, &station, epoch, 3.0, &propagator)
+                .unwrap();
+            assert!(
+                windows.len() >= 2,
+                "plane {} has {} passes",
+                plane,
+                windows.len()
+            );
+
+            for window in &windows {
+                let wrapped = raw
+                    .generate(&satellite, &station, window, &propagator)
+                    .unwrap();
+                let continuous = unwrapped
+                    .generate(&satellite, &station, window, &propagator)
+                    .unwrap();
+                if max_step(&wrapped) > 180.0 {
+                    north_crossings += 1;
+                }
+                assert!(max_step(&continuous) < 30.0, "azimuth jump in {:?}", window);
+                assert!((0.0..360.0).contains(&continuous.samples[0].azimuth_deg));
+                for (a, b) in wrapped.samples.iter().zip(&continuous.samples) {
+                    assert!((b.azimuth_deg.rem_euclid(360.0) - a.azimuth_deg).abs() < 1e-9);
+                }
+            }
+        }
+        assert!(north_crossings > 0);
+    }
+}
@@ -0,0 +1,373 @@
+//! Satellite electrical power model
+//!
+//! Solar array output follows the Sun incidence angle and drops to zero in
+//! eclipse; the battery covers any deficit from the bus and the FSO terminal.
+//! Contacts whose terminal load would push the battery past its depth-of-
+//! discharge limit are refused and recorded.
+
+use crate::constants::*;
+use crate::ephemeris::{self, AU_KM};
+use crate::orbit::SatelliteState;
+use chrono::{DateTime, Utc};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Maximum number of refused contacts retained per satellite
+pub const MAX_VIOLATION_RECORDS: usize = 1000;
+
+/// Solar constant at 1 AU in W/m²
+pub const SOLAR_CONSTANT_W_PER_M2: f64 = 1361.0;
+
+/// How the solar array is oriented relative to the Sun
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolarArrayOrientation {
+    /// Two-axis tracking, always normal to the Sun
+    SunTracking,
+    /// Rotates about the orbit normal; output falls with the beta angle
+    SingleAxis,
+    /// Fixed zenith-facing panel
+    BodyMounted,
+}
+
+/// Solar array, battery and load parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerConfig {
+    pub solar_array_area_m2: f64,
+    /// Cell efficiency including packing and degradation (0-1)
+    pub solar_array_efficiency: f64,
+    pub array_orientation: SolarArrayOrientation,
+    pub battery_capacity_wh: f64,
+    /// Deepest allowed discharge (0-1)
+    pub max_depth_of_discharge: f64,
+    /// Round-trip charge efficiency (0-1)
+    pub charge_efficiency: f64,
+    /// Continuous bus load in watts
+    pub bus_load_w: f64,
+    /// Additional load while the FSO terminal is active in watts
+    pub fso_terminal_load_w: f64,
+}
+
+/// Power balance over one step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerStep {
+    pub timestamp: DateTime<Utc>,
+    pub generated_w: f64,
+    pub load_w: f64,
+    pub sunlit: bool,
+    pub terminal_active: bool,
+    pub state_of_charge_percent: f64,
+    pub depth_of_discharge_percent: f64,
+}
+
+/// Contact refused because the terminal load would over-discharge the battery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactPowerViolation {
+    pub timestamp: DateTime<Utc>,
+    pub station_id: String,
+    /// Depth of discharge the contact would have reached (0-100%)
+    pub projected_depth_of_discharge_percent: f64,
+}
+
+/// Power summary for one satellite
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PowerStatistics {
+    pub state_of_charge_percent: f64,
+    /// Deepest discharge reached so far (0-100%)
+    pub max_depth_of_discharge_percent: f64,
+    pub eclipse_seconds: f64,
+    pub terminal_active_seconds: f64,
+    pub refused_contacts: u64,
+}
+
+/// Battery state and power history of one satellite
+#[derive(Debug, Clone)]
+pub struct PowerSystem {
+    pub config: PowerConfig,
+    stored_wh: f64,
+    max_depth_of_discharge: f64,
+    eclipse_seconds: f64,
+    terminal_active_seconds: f64,
+    refused_contacts: u64,
+    violations: VecDeque<ContactPowerViolation>,
+}
+
+impl PowerConfig {
+    /// Peak array output with the Sun normal to the panel at 1 AU
+    pub fn peak_generation_w(&self) -> f64 {
+        SOLAR_CONSTANT_W_PER_M2 * self.solar_array_area_m2 * self.solar_array_efficiency
+    }
+
+    /// Array output in watts for a satellite state
+    pub fn generation_w(&self, state: &SatelliteState) -> f64 {
+        if state.in_eclipse {
+            return 0.0;
+        }
+
+        let position = Vector3::from(state.position_eci);
+        let to_sun = Vector3::from(ephemeris::sun_position_eci(state.timestamp)) - position;
+        let distance_km = to_sun.norm();
+        let sun_dir = to_sun / distance_km;
+
+        let cos_incidence = match self.array_orientation {
+            SolarArrayOrientation::SunTracking => 1.0,
+            SolarArrayOrientation::SingleAxis => {
+                let orbit_normal = position.cross(&Vector3::from(state.velocity_eci));
+                let sin_beta = sun_dir.dot(&orbit_normal.normalize());
+                (1.0 - sin_beta * sin_beta).max(0.0).sqrt()
+            }
+            SolarArrayOrientation::BodyMounted => sun_dir.dot(&position.normalize()).max(0.0),
+        };
+
+        let flux_scale = (AU_KM / distance_km).powi(2);
+        self.peak_generation_w() * flux_scale * cos_incidence
+    }
+
+    /// Total load in watts
+    pub fn load_w(&self, terminal_active: bool) -> f64 {
+        if terminal_active {
+            self.bus_load_w + self.fso_terminal_load_w
+        } else {
+            self.bus_load_w
+        }
+    }
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        // Mid-size MEO bus: 20 m² triple-junction array, 5 kWh Li-ion
+        Self {
+            solar_array_area_m2: 20.0,
+            solar_array_efficiency: 0.28,
+            array_orientation: SolarArrayOrientation::SunTracking,
+            battery_capacity_wh: 5000.0,
+            max_depth_of_discharge: 0.4,
+            charge_efficiency: 0.92,
+            bus_load_w: 2500.0,
+            fso_terminal_load_w: 1500.0,
+        }
+    }
+}
+
+impl PowerSystem {
+    /// Create power system with a fully charged battery
+    pub fn new(config: PowerConfig) -> Self {
+        Self {
+            stored_wh: config.battery_capacity_wh,
+            config,
+            max_depth_of_discharge: 0.0,
+            eclipse_seconds: 0.0,
+            terminal_active_seconds: 0.0,
+            refused_contacts: 0,
+            violations: VecDeque::new(),
+        }
+    }
+
+    /// Battery state of charge (0-100%)
+    pub fn state_of_charge_percent(&self) -> f64 {
+        if self.config.battery_capacity_wh > 0.0 {
+            self.stored_wh / self.config.battery_capacity_wh * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Battery depth of discharge (0-100%)
+    pub fn depth_of_discharge_percent(&self) -> f64 {
+        100.0 - self.state_of_charge_percent()
+    }
+
+    /// Depth of discharge (0-100%) after `duration_seconds` with the given load
+    pub fn projected_depth_of_discharge_percent(
+        &self,
+        state: &SatelliteState,
+        terminal_active: bool,
+        duration_seconds: f64,
+    ) -> f64 {
+        let generated_w = self.config.generation_w(state);
+        let stored_wh = self.stored_after(generated_w, terminal_active, duration_seconds);
+        self.depth_of_discharge_for(stored_wh) * 100.0
+    }
+
+    /// Decide whether the terminal may run a contact for the next step
+    ///
+    /// A contact that would exceed the depth-of-discharge limit is refused
+    /// and recorded against `station_id`.
+    pub fn request_contact(
+        &mut self,
+        station_id: &str,
+        state: &SatelliteState,
+        duration_seconds: f64,
+    ) -> bool {
+        let projected = self.projected_depth_of_discharge_percent(state, true, duration_seconds);
+        if projected <= self.config.max_depth_of_discharge * 100.0 {
+            return true;
+        }
+
+        self.refused_contacts += 1;
+        if self.violations.len() == MAX_VIOLATION_RECORDS {
+            self.violations.pop_front();
+        }
+        self.violations.push_back(ContactPowerViolation {
+            timestamp: state.timestamp,
+            station_id: station_id.to_string(),
+            projected_depth_of_discharge_percent: projected,
+        });
+        false
+    }
+
+    /// Advance the battery by `duration_seconds` ending at the state's timestamp
+    pub fn step(
+        &mut self,
+        state: &SatelliteState,
+        terminal_active: bool,
+        duration_seconds: f64,
+    ) -> PowerStep {
+        let duration_seconds = duration_seconds.max(0.0);
+        let generated_w = self.config.generation_w(state);
+        // An array facing away from the Sun generates nothing but is not in eclipse
+        let sunlit = !state.in_eclipse;
+
+        self.stored_wh = self.stored_after(generated_w, terminal_active, duration_seconds);
+        self.max_depth_of_discharge = self
+            .max_depth_of_discharge
+            .max(self.depth_of_discharge_for(self.stored_wh));
+        if !sunlit {
+            self.eclipse_seconds += duration_seconds;
+        }
+        if terminal_active {
+            self.terminal_active_seconds += duration_seconds;
+        }
+
+        PowerStep {
+            timestamp: state.timestamp,
+            generated_w,
+            load_w: self.config.load_w(terminal_active),
+            sunlit,
+            terminal_active,
+            state_of_charge_percent: self.state_of_charge_percent(),
+            depth_of_discharge_percent: self.depth_of_discharge_percent(),
+        }
+    }
+
+    /// Contacts refused for power, oldest first
+    pub fn violations(&self) -> impl Iterator<Item = &ContactPowerViolation> {
+        self.violations.iter()
+    }
+
+    /// Summary of battery usage and refused contacts
+    pub fn statistics(&self) -> PowerStatistics {
+        PowerStatistics {
+            state_of_charge_percent: self.state_of_charge_percent(),
+            max_depth_of_discharge_percent: self.max_depth_of_discharge * 100.0,
+            eclipse_seconds: self.eclipse_seconds,
+            terminal_active_seconds: self.terminal_active_seconds,
+            refused_contacts: self.refused_contacts,
+        }
+    }
+
+    fn stored_after(&self, generated_w: f64, terminal_active: bool, duration_seconds: f64) -> f64 {
+        let net_w = generated_w - self.config.load_w(terminal_active);
+        let net_wh = net_w * duration_seconds.max(0.0) / HOURS_TO_SECONDS;
+        let delta_wh = if net_wh > 0.0 {
+            net_wh * self.config.charge_efficiency
+        } else {
+            net_wh
+        };
+
+        (self.stored_wh + delta_wh).clamp(0.0, self.config.battery_capacity_wh)
+    }
+
+    fn depth_of_discharge_for(&self, stored_wh: f64) -> f64 {
+        if self.config.battery_capacity_wh > 0.0 {
+            1.0 - stored_wh / self.config.battery_capacity_wh
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn state_at(
+        position_eci: Vector3<f64>,
+        velocity_eci: Vector3<f64>,
+        time: DateTime<Utc>,
+    ) -> SatelliteState {
+        SatelliteState::new(
+            "PWR-01".to_string(),
+            time,
+            position_eci.into(),
+            velocity_eci.into(),
+        )
+    }
+
+    fn sun_direction(time: DateTime<Utc>) -> Vector3<f64> {
+        Vector3::from(ephemeris::sun_position_eci(time)).normalize()
+    }
+
+    #[test]
+    fn test_generation_depends_on_sun_angle_and_eclipse() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 12, 0, 0).unwrap();
+        let sun_dir = sun_direction(time);
+        let radius = EARTH_RADIUS_KM + LASERLIGHT_FSO_ALTITUDE_KM;
+
+        // Over the subsolar point, moving perpendicular to the Sun line
+        let sunward = state_at(sun_dir * radius, Vector3::z() * 5.0, time);
+        let shadowed = state_at(sun_dir * -radius, Vector3::z() * 5.0, time);
+
+        let mut config = PowerConfig::default();
+        let peak = config.generation_w(&sunward);
+        assert!((peak / config.peak_generation_w() - 1.0).abs() < 0.05);
+        assert_eq!(config.generation_w(&shadowed), 0.0);
+
+        // Zenith panel sees the Sun head-on at the subsolar point
+        config.array_orientation = SolarArrayOrientation::BodyMounted;
+        assert!((config.generation_w(&sunward) - peak).abs() < 1e-6);
+
+        // Single-axis array: full output at zero beta, none with the Sun on the orbit normal
+        config.array_orientation = SolarArrayOrientation::SingleAxis;
+        assert!((config.generation_w(&sunward) - peak).abs() < 1e-6);
+
+        let position = sun_dir.cross(&Vector3::z()).normalize() * radius;
+        let face_on = state_at(position, sun_dir.cross(&position) / radius, time);
+        assert!(config.generation_w(&face_on) < 1e-3 * peak);
+
+        // Sunlit but generating nothing does not count as eclipse
+        let mut power = PowerSystem::new(config);
+        let step = power.step(&face_on, false, 600.0);
+        assert!(!face_on.in_eclipse);
+        assert!(step.generated_w < 1e-3 * peak);
+        assert!(step.sunlit);
+        assert_eq!(power.statistics().eclipse_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_eclipse_contact_refused_at_dod_limit() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 12, 0, 0).unwrap();
+        let radius = EARTH_RADIUS_KM + LASERLIGHT_FSO_ALTITUDE_KM;
+        let shadowed = state_at(sun_direction(time) * -radius, Vector3::z() * 5.0, time);
+
+        let mut power = PowerSystem::new(PowerConfig::default());
+
+        // Bus alone for 40 minutes in eclipse: 2500 W -> ~1667 Wh of 5000 Wh
+        let step = power.step(&shadowed, false, 2400.0);
+        assert!(!step.sunlit);
+        assert!((step.depth_of_discharge_percent - 100.0 / 3.0).abs() < 0.1);
+
+        // A 10-minute contact adds ~667 Wh, beyond the 40% limit
+        assert!(!power.request_contact("GS-001", &shadowed, 600.0));
+        // A 1-minute contact stays within it
+        assert!(power.request_contact("GS-001", &shadowed, 60.0));
+
+        let stats = power.statistics();
+        assert_eq!(stats.refused_contacts, 1);
+        assert_eq!(stats.eclipse_seconds, 2400.0);
+        let violation = power.violations().next().unwrap();
+        assert_eq!(violation.station_id, "GS-001");
+        assert!(violation.projected_depth_of_discharge_percent > 40.0);
+    }
+}
@@ -0,0 +1,238 @@
+//! Progress reporting and cancellation for long-running analyses
+//!
+//! Coverage grids, conjunction screening, contact scheduling and site
+//! selection can run for minutes on large constellations. Their
+//! `*_with_progress` variants report to a `ProgressSink` as they go, giving the
+//! percent complete of the whole analysis and the phase it is in, so a CLI can
+//! draw a progress bar and a service can publish run status. Within one run the
+//! percentage never decreases, and a run that succeeds always ends with a report
+//! of 100.
+//!
+//! Closures taking `(percent_complete, phase)` are sinks, so a CLI can pass
+//! `&|percent, phase| eprint!("\r{:>5.1}% {}", percent, phase)`.
+//!
+//! The same checkpoints poll `ProgressSink::is_cancelled`: once it returns
+//! true the analysis stops at its next chunk (a grid row, time step or station
+//! pair) and fails with `OrbitalMechanicsError::Cancelled`. A
+//! `CancellationToken` is a sink on its own, or wraps another sink with
+//! `with_progress`, and can be cancelled from any thread.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Receives progress of a long-running analysis
+pub trait ProgressSink {
+    /// `percent_complete` of the whole analysis in [0, 100]; `phase` names the current step
+    fn report(&self, percent_complete: f64, phase: &str);
+
+    /// Whether the analysis should stop at its next checkpoint
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+impl<F> ProgressSink for F
+where
+    F: Fn(f64, &str),
+{
+    fn report(&self, percent_complete: f64, phase: &str) {
+        self(percent_complete, phase)
+    }
+}
+
+/// Sink that discards progress
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&self, _percent_complete: f64, _phase: &str) {}
+}
+
+/// Shared flag for cooperatively cancelling analyses
+///
+/// Clones share the flag, so one clone can be handed to the analysis and
+/// another kept to cancel it.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask analyses watching this token to stop
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Sink reporting to `sink` and cancelled by this token
+    pub fn with_progress<'a>(&'a self, sink: &'a dyn ProgressSink) -> Cancellable<'a> {
+        Cancellable { token: self, sink }
+    }
+}
+
+impl ProgressSink for CancellationToken {
+    fn report(&self, _percent_complete: f64, _phase: &str) {}
+
+    fn is_cancelled(&self) -> bool {
+        CancellationToken::is_cancelled(self)
+    }
+}
+
+/// Progress sink paired with a cancellation token
+pub struct Cancellable<'a> {
+    token: &'a CancellationToken,
+    sink: &'a dyn ProgressSink,
+}
+
+impl ProgressSink for Cancellable<'_> {
+    fn report(&self, percent_complete: f64, phase: &str) {
+        self.sink.report(percent_complete, phase)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled() || self.sink.is_cancelled()
+    }
+}
+
+/// Maps a nested analysis's 0–100 onto `start`–`end` of its parent
+pub(crate) struct Span<'a> {
+    sink: &'a dyn ProgressSink,
+    start: f64,
+    end: f64,
+}
+
+impl<'a> Span<'a> {
+    pub(crate) fn new(sink: &'a dyn ProgressSink, start: f64, end: f64) -> Self {
+        Self { sink, start, end }
+    }
+}
+
+impl ProgressSink for Span<'_> {
+    fn report(&self, percent_complete: f64, phase: &str) {
+        let fraction = percent_complete / 100.0;
+        self.sink
+            .report(self.start + (self.end - self.start) * fraction, phase)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.sink.is_cancelled()
+    }
+}
+
+/// A phase of `total` equal steps reported to a sink
+///
+/// Reports are throttled to changes of at least 0.1 percent, so a loop can
+/// call `step` every iteration without flooding the sink. Every `step` checks
+/// for cancellation.
+pub(crate) struct Phase<'a> {
+    sink: &'a dyn ProgressSink,
+    name: &'static str,
+    total: usize,
+    last_permille: Cell<Option<u32>>,
+}
+
+impl<'a> Phase<'a> {
+    /// Start a phase, reporting it at 0 percent
+    pub(crate) fn start(
+        sink: &'a dyn ProgressSink,
+        name: &'static str,
+        total: usize,
+    ) -> Result<Self> {
+        let phase = Self {
+            sink,
+            name,
+            total,
+            last_permille: Cell::new(None),
+        };
+        phase.step(0)?;
+        Ok(phase)
+    }
+
+    /// Report `done` of `total` steps complete, failing once cancelled
+    pub(crate) fn step(&self, done: usize) -> Result<()> {
+        if self.sink.is_cancelled() {
+            return Err(OrbitalMechanicsError::Cancelled);
+        }
+        self.report(done);
+        Ok(())
+    }
+
+    /// Report the phase complete
+    pub(crate) fn finish(&self) {
+        self.report(self.total);
+    }
+
+    fn report(&self, done: usize) {
+        let fraction = if self.total == 0 {
+            1.0
+        } else {
+            (done as f64 / self.total as f64).min(1.0)
+        };
+        let permille = (fraction * 1000.0) as u32;
+        if self.last_permille.get() != Some(permille) {
+            self.last_permille.set(Some(permille));
+            self.sink.report(fraction * 100.0, self.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_phase_reports_within_span() {
+        let reports = RefCell::new(Vec::new());
+        let sink =
+            |percent: f64, phase: &str| reports.borrow_mut().push((percent, phase.to_string()));
+        let span = Span::new(&sink, 20.0, 60.0);
+
+        let phase = Phase::start(&span, "coverage", 4000).unwrap();
+        for done in 1..=4000 {
+            phase.step(done).unwrap();
+        }
+        phase.finish();
+
+        let reports = reports.into_inner();
+        assert_eq!(reports.first().unwrap(), &(20.0, "coverage".to_string()));
+        assert_eq!(reports.last().unwrap().0, 60.0);
+        // One report per permille, plus the start
+        assert_eq!(reports.len(), 1001);
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        // Empty phases complete immediately
+        let count = Cell::new(0);
+        Phase::start(&|_: f64, _: &str| count.set(count.get() + 1), "empty", 0)
+            .unwrap()
+            .finish();
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_cancellation_stops_phase() {
+        let token = CancellationToken::new();
+        let reports = Cell::new(0);
+        let sink = |_: f64, _: &str| reports.set(reports.get() + 1);
+        let watched = token.with_progress(&sink);
+        let span = Span::new(&watched, 0.0, 50.0);
+
+        let phase = Phase::start(&span, "screening", 10).unwrap();
+        phase.step(1).unwrap();
+        token.clone().cancel();
+        let error = phase.step(2).unwrap_err();
+        assert!(matches!(error, OrbitalMechanicsError::Cancelled));
+        assert_eq!(error.kind(), crate::error::ErrorKind::Cancelled);
+        assert_eq!(reports.get(), 2);
+        assert!(Phase::start(&token, "screening", 10).is_err());
+    }
+}
@@ -1,10 +1,18 @@
 //! Orbital propagation algorithms
 
+use crate::atmosphere::{AtmosphereModel, DragParameters, REENTRY_ALTITUDE_KM};
 use crate::constants::*;
-use crate::error::{OrbitalMechanicsError, Result};
+use crate::covariance::{StateCovariance, StateMatrix};
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::force_model::{
+    AtmosphericDrag, ForceModel, ForceModelSet, ThirdBody, TwoBody, ZonalHarmonics,
+};
 use crate::orbit::{OrbitalElementsRad, SatelliteOrbit, SatelliteState};
+use crate::propagation_core::{self, CoreError, Elements};
+use crate::trace_targets;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::Instant;
 
 /// Types of orbital propagators
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,45 +43,192 @@ pub struct KeplerianPropagator;
 /// SGP4 propagator (simplified)
 pub struct Sgp4Propagator;
 
-/// Numerical integration propagator
+/// Numerical integration propagator with composable force models
 pub struct NumericalPropagator {
     pub step_size_seconds: f64,
+    pub integrator: Integrator,
+    forces: Vec<Box<dyn ForceModel>>,
 }
 
+/// Runge-Kutta scheme used by the numerical propagator
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Integrator {
+    /// Classical fourth-order Runge-Kutta with a fixed step
+    #[default]
+    Rk4,
+    /// Runge-Kutta-Fehlberg 7(8) with adaptive steps
+    Rkf78 {
+        /// Local truncation error accepted per step
+        tolerance_km: f64,
+        max_step_seconds: f64,
+    },
+}
+
+/// Upper bound on integration steps per propagation
+const MAX_INTEGRATION_STEPS: usize = 100000;
+
+/// Smallest step the adaptive integrator will take
+const MIN_STEP_SECONDS: f64 = 1e-3;
+
+/// Central-difference steps for force-model partials
+const PARTIAL_POSITION_STEP_KM: f64 = 1e-3;
+const PARTIAL_VELOCITY_STEP_KM_S: f64 = 1e-6;
+
+/// Fehlberg 7(8) nodes
+const RKF78_C: [f64; 13] = [
+    0.0,
+    2.0 / 27.0,
+    1.0 / 9.0,
+    1.0 / 6.0,
+    5.0 / 12.0,
+    1.0 / 2.0,
+    5.0 / 6.0,
+    1.0 / 6.0,
+    2.0 / 3.0,
+    1.0 / 3.0,
+    1.0,
+    0.0,
+    1.0,
+];
+
+/// Fehlberg 7(8) coupling coefficients, row `i` weighting stages `0..i`
+const RKF78_A: [&[f64]; 13] = [
+    &[],
+    &[2.0 / 27.0],
+    &[1.0 / 36.0, 1.0 / 12.0],
+    &[1.0 / 24.0, 0.0, 1.0 / 8.0],
+    &[5.0 / 12.0, 0.0, -25.0 / 16.0, 25.0 / 16.0],
+    &[1.0 / 20.0, 0.0, 0.0, 1.0 / 4.0, 1.0 / 5.0],
+    &[
+        -25.0 / 108.0,
+        0.0,
+        0.0,
+        125.0 / 108.0,
+        -65.0 / 27.0,
+        125.0 / 54.0,
+    ],
+    &[
+        31.0 / 300.0,
+        0.0,
+        0.0,
+        0.0,
+        61.0 / 225.0,
+        -2.0 / 9.0,
+        13.0 / 900.0,
+    ],
+    &[
+        2.0,
+        0.0,
+        0.0,
+        -53.0 / 6.0,
+        704.0 / 45.0,
+        -107.0 / 9.0,
+        67.0 / 90.0,
+        3.0,
+    ],
+    &[
+        -91.0 / 108.0,
+        0.0,
+        0.0,
+        23.0 / 108.0,
+        -976.0 / 135.0,
+        311.0 / 54.0,
+        -19.0 / 60.0,
+        17.0 / 6.0,
+        -1.0 / 12.0,
+    ],
+    &[
+        2383.0 / 4100.0,
+        0.0,
+        0.0,
+        -341.0 / 164.0,
+        4496.0 / 1025.0,
+        -301.0 / 82.0,
+        2133.0 / 4100.0,
+        45.0 / 82.0,
+        45.0 / 164.0,
+        18.0 / 41.0,
+    ],
+    &[
+        3.0 / 205.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        -6.0 / 41.0,
+        -3.0 / 205.0,
+        -3.0 / 41.0,
+        3.0 / 41.0,
+        6.0 / 41.0,
+        0.0,
+    ],
+    &[
+        -1777.0 / 4100.0,
+        0.0,
+        0.0,
+        -341.0 / 164.0,
+        4496.0 / 1025.0,
+        -289.0 / 82.0,
+        2193.0 / 4100.0,
+        51.0 / 82.0,
+        33.0 / 164.0,
+        12.0 / 41.0,
+        0.0,
+        1.0,
+    ],
+];
+
+/// Fehlberg 8th-order weights
+const RKF78_B8: [f64; 13] = [
+    0.0,
+    0.0,
+    0.0,
+    0.0,
+    0.0,
+    34.0 / 105.0,
+    9.0 / 35.0,
+    9.0 / 35.0,
+    9.0 / 280.0,
+    9.0 / 280.0,
+    0.0,
+    41.0 / 840.0,
+    41.0 / 840.0,
+];
+
 impl OrbitalPropagator for KeplerianPropagator {
     fn propagate(&self, satellite: &SatelliteOrbit, time: DateTime<Utc>) -> Result<SatelliteState> {
+        let started = trace_timer();
         let time_since_epoch = (time - satellite.epoch).num_seconds() as f64;
-
-        // Update mean anomaly
-        let delta_mean_anomaly = satellite.mean_motion_rad_per_sec * time_since_epoch;
-        let elements_rad = satellite.elements.to_radians();
-        let mean_anomaly = (elements_rad.mean_anomaly_rad + delta_mean_anomaly) % TWO_PI;
-
-        // Solve Kepler's equation for eccentric anomaly
-        let eccentric_anomaly =
-            self.solve_keplers_equation(mean_anomaly, elements_rad.eccentricity)?;
-
-        // Calculate true anomaly
-        let true_anomaly =
-            self.eccentric_to_true_anomaly(eccentric_anomaly, elements_rad.eccentricity);
-
-        // Calculate position and velocity in orbital plane
-        let (r, v) = self.orbital_state_vectors(&elements_rad, true_anomaly);
-
-        // Transform to Earth-Centered Inertial (ECI) coordinates
-        let (position_eci, velocity_eci) = self.orbital_to_eci(
-            r,
-            v,
-            elements_rad.inclination_rad,
-            elements_rad.raan_rad,
-            elements_rad.argument_of_perigee_rad,
+        let elements = Elements::from(&satellite.elements.to_radians());
+
+        let state = propagation_core::propagate_kepler(&elements, time_since_epoch)
+            .inspect_err(|_| {
+                tracing::warn!(
+                    target: trace_targets::PROPAGATOR,
+                    satellite_id = %satellite.satellite_id,
+                    eccentricity = elements.eccentricity,
+                    seconds_since_epoch = time_since_epoch,
+                    "Kepler's equation failed to converge"
+                );
+            })
+            .map_err(OrbitalMechanicsError::from)
+            .for_satellite(&satellite.satellite_id)
+            .at_epoch(time)?;
+
+        tracing::trace!(
+            target: trace_targets::PROPAGATOR,
+            propagator = "Keplerian",
+            satellite_id = %satellite.satellite_id,
+            seconds_since_epoch = time_since_epoch,
+            elapsed_us = started.map(|started| started.elapsed().as_secs_f64() * 1e6),
+            "Propagated"
         );
 
         Ok(SatelliteState::new(
             satellite.satellite_id.clone(),
             time,
-            position_eci,
-            velocity_eci,
+            state.position_km,
+            state.velocity_km_s,
         ))
     }
 
@@ -90,217 +245,554 @@ impl KeplerianPropagator {
     pub fn new() -> Self {
         Self
     }
+}
 
-    /// Solve Kepler's equation: M = E - e*sin(E)
-    fn solve_keplers_equation(&self, mean_anomaly: f64, eccentricity: f64) -> Result<f64> {
-        let mut eccentric_anomaly = mean_anomaly; // Initial guess
-
-        for _ in 0..KEPLER_ITERATION_LIMIT {
-            let delta_e = (mean_anomaly - eccentric_anomaly
-                + eccentricity * eccentric_anomaly.sin())
-                / (1.0 - eccentricity * eccentric_anomaly.cos());
+impl OrbitalPropagator for Sgp4Propagator {
+    fn propagate(&self, satellite: &SatelliteOrbit, time: DateTime<Utc>) -> Result<SatelliteState> {
+        // Simplified SGP4 implementation
+        // In practice, this would use the full SGP4 algorithm with atmospheric drag,
+        // solar radiation pressure, and Earth oblateness perturbations
 
-            eccentric_anomaly += delta_e;
+        let started = trace_timer();
+        let time_since_epoch = (time - satellite.epoch).num_seconds() as f64;
 
-            if delta_e.abs() < KEPLER_TOLERANCE {
-                return Ok(eccentric_anomaly);
-            }
-        }
+        // For now, Keplerian propagation with secular J2 drift
+        let elements = Elements::from(&satellite.elements.to_radians());
+        let state = propagation_core::propagate_j2(&elements, time_since_epoch)
+            .map_err(OrbitalMechanicsError::from)
+            .for_satellite(&satellite.satellite_id)
+            .at_epoch(time)?;
+
+        tracing::trace!(
+            target: trace_targets::PROPAGATOR,
+            propagator = "SGP4",
+            satellite_id = %satellite.satellite_id,
+            minutes_since_epoch = time_since_epoch / 60.0,
+            elapsed_us = started.map(|started| started.elapsed().as_secs_f64() * 1e6),
+            "Propagated"
+        );
 
-        Err(OrbitalMechanicsError::propagation_error(
-            "Kepler's equation failed to converge",
+        Ok(SatelliteState::new(
+            satellite.satellite_id.clone(),
+            time,
+            state.position_km,
+            state.velocity_km_s,
         ))
     }
 
-    /// Convert eccentric anomaly to true anomaly
-    fn eccentric_to_true_anomaly(&self, eccentric_anomaly: f64, eccentricity: f64) -> f64 {
-        let sqrt_term = ((1.0 + eccentricity) / (1.0 - eccentricity)).sqrt();
-        2.0 * (sqrt_term * (eccentric_anomaly / 2.0).tan()).atan()
+    fn name(&self) -> &str {
+        "SGP4 (Simplified)"
     }
 
-    /// Calculate position and velocity vectors in orbital plane
-    fn orbital_state_vectors(
-        &self,
-        elements: &OrbitalElementsRad,
-        true_anomaly: f64,
-    ) -> ([f64; 3], [f64; 3]) {
-        let a = elements.semi_major_axis_km;
-        let e = elements.eccentricity;
-
-        // Distance from Earth center
-        let r_magnitude = a * (1.0 - e * e) / (1.0 + e * true_anomaly.cos());
-
-        // Position in orbital plane
-        let r = [
-            r_magnitude * true_anomaly.cos(),
-            r_magnitude * true_anomaly.sin(),
-            0.0,
-        ];
-
-        // Velocity in orbital plane
-        let mu_over_p = EARTH_MU / (a * (1.0 - e * e));
-        let sqrt_mu_over_p = mu_over_p.sqrt();
+    fn max_propagation_duration(&self) -> chrono::Duration {
+        chrono::Duration::days(365) // 1 year for SGP4
+    }
+}
 
-        let v = [
-            -sqrt_mu_over_p * true_anomaly.sin(),
-            sqrt_mu_over_p * (e + true_anomaly.cos()),
-            0.0,
-        ];
+impl Sgp4Propagator {
+    pub fn new() -> Self {
+        Self
+    }
+}
 
-        (r, v)
+impl From<CoreError> for OrbitalMechanicsError {
+    fn from(err: CoreError) -> Self {
+        match err {
+            CoreError::KeplerNoConvergence => Self::propagation_error(err.to_string()),
+            CoreError::UnboundOrbit | CoreError::CriticalInclination => {
+                Self::invalid_elements(err.to_string())
+            }
+        }
     }
+}
 
-    /// Transform orbital plane coordinates to ECI
-    fn orbital_to_eci(
-        &self,
-        r_orbital: [f64; 3],
-        v_orbital: [f64; 3],
-        inclination: f64,
-        raan: f64,
-        arg_perigee: f64,
-    ) -> ([f64; 3], [f64; 3]) {
-        // Rotation matrices
-        let cos_raan = raan.cos();
-        let sin_raan = raan.sin();
-        let cos_inc = inclination.cos();
-        let sin_inc = inclination.sin();
-        let cos_arg = arg_perigee.cos();
-        let sin_arg = arg_perigee.sin();
-
-        // Combined rotation matrix elements
-        let p11 = cos_raan * cos_arg - sin_raan * sin_arg * cos_inc;
-        let p12 = -cos_raan * sin_arg - sin_raan * cos_arg * cos_inc;
-        let p13 = sin_raan * sin_inc;
-
-        let p21 = sin_raan * cos_arg + cos_raan * sin_arg * cos_inc;
-        let p22 = -sin_raan * sin_arg + cos_raan * cos_arg * cos_inc;
-        let p23 = -cos_raan * sin_inc;
-
-        let p31 = sin_arg * sin_inc;
-        let p32 = cos_arg * sin_inc;
-        let p33 = cos_inc;
-
-        // Transform position
-        let position_eci = [
-            p11 * r_orbital[0] + p12 * r_orbital[1] + p13 * r_orbital[2],
-            p21 * r_orbital[0] + p22 * r_orbital[1] + p23 * r_orbital[2],
-            p31 * r_orbital[0] + p32 * r_orbital[1] + p33 * r_orbital[2],
-        ];
-
-        // Transform velocity
-        let velocity_eci = [
-            p11 * v_orbital[0] + p12 * v_orbital[1] + p13 * v_orbital[2],
-            p21 * v_orbital[0] + p22 * v_orbital[1] + p23 * v_orbital[2],
-            p31 * v_orbital[0] + p32 * v_orbital[1] + p33 * v_orbital[2],
-        ];
-
-        (position_eci, velocity_eci)
+impl From<&OrbitalElementsRad> for Elements {
+    fn from(elements: &OrbitalElementsRad) -> Self {
+        Self {
+            semi_major_axis_km: elements.semi_major_axis_km,
+            eccentricity: elements.eccentricity,
+            inclination_rad: elements.inclination_rad,
+            raan_rad: elements.raan_rad,
+            argument_of_perigee_rad: elements.argument_of_perigee_rad,
+            mean_anomaly_rad: elements.mean_anomaly_rad,
+        }
     }
 }
 
-impl OrbitalPropagator for Sgp4Propagator {
+impl OrbitalPropagator for NumericalPropagator {
     fn propagate(&self, satellite: &SatelliteOrbit, time: DateTime<Utc>) -> Result<SatelliteState> {
-        // Simplified SGP4 implementation
-        // In practice, this would use the full SGP4 algorithm with atmospheric drag,
-        // solar radiation pressure, and Earth oblateness perturbations
-
-        let time_since_epoch_minutes = (time - satellite.epoch).num_seconds() as f64 / 60.0;
-
-        // For now, fall back to Keplerian propagation with J2 perturbations
-        let keplerian = KeplerianPropagator::new();
-        let mut state = keplerian.propagate(satellite, time)?;
-
-        // Apply simplified J2 perturbations
-        self.apply_j2_perturbations(&mut state, satellite, time_since_epoch_minutes);
+        let started = trace_timer();
+        let (state, num_steps) = self.integrate(
+            satellite,
+            0.0,
+            time,
+            initial_state(satellite)?,
+            |elapsed, state| self.derivative(satellite.epoch, elapsed, state),
+        )?;
+
+        tracing::trace!(
+            target: trace_targets::PROPAGATOR,
+            propagator = "Numerical",
+            satellite_id = %satellite.satellite_id,
+            seconds_since_epoch = (time - satellite.epoch).num_milliseconds() as f64 / 1000.0,
+            num_steps,
+            elapsed_us = started.map(|started| started.elapsed().as_secs_f64() * 1e6),
+            "Propagated"
+        );
 
-        Ok(state)
+        Ok(SatelliteState::new(
+            satellite.satellite_id.clone(),
+            time,
+            [state[0], state[1], state[2]],
+            [state[3], state[4], state[5]],
+        ))
     }
 
     fn name(&self) -> &str {
-        "SGP4 (Simplified)"
+        "Numerical Integration"
     }
 
     fn max_propagation_duration(&self) -> chrono::Duration {
-        chrono::Duration::days(365) // 1 year for SGP4
+        chrono::Duration::days(30) // 30 days for numerical integration
     }
 }
 
-impl Sgp4Propagator {
-    pub fn new() -> Self {
-        Self
+impl NumericalPropagator {
+    /// Fixed-step RK4 with two-body gravity only
+    pub fn new(step_size_seconds: f64) -> Self {
+        Self {
+            step_size_seconds,
+            integrator: Integrator::Rk4,
+            forces: vec![Box::new(TwoBody)],
+        }
+    }
+
+    /// Add a force model to the equations of motion
+    pub fn with_force(mut self, force: impl ForceModel + 'static) -> Self {
+        self.forces.push(Box::new(force));
+        self
+    }
+
+    /// Replace the integration scheme
+    ///
+    /// For adaptive schemes `step_size_seconds` is the initial step.
+    pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Include atmospheric drag for the given spacecraft and density model
+    pub fn with_drag(self, drag: DragParameters, atmosphere: AtmosphereModel) -> Self {
+        self.with_force(AtmosphericDrag::new(drag, atmosphere))
+    }
+
+    /// Add every perturbation in `forces`
+    pub fn with_force_models(mut self, forces: &ForceModelSet) -> Self {
+        if let Some(degree) = forces.zonal_degree {
+            self = self.with_force(ZonalHarmonics::new(degree));
+        }
+        if let Some(drag) = &forces.drag {
+            self = self.with_drag(drag.clone(), forces.atmosphere);
+        }
+        for &body in &forces.third_bodies {
+            self = self.with_force(ThirdBody { body });
+        }
+        if let Some(srp) = &forces.solar_radiation_pressure {
+            self = self.with_force(srp.clone());
+        }
+        self
+    }
+
+    /// Names of the force models in the order they are summed
+    pub fn force_models(&self) -> Vec<&str> {
+        self.forces.iter().map(|force| force.name()).collect()
     }
 
-    /// Apply simplified J2 perturbations
-    fn apply_j2_perturbations(
+    /// Propagate along with the state transition matrix from the element epoch
+    ///
+    /// The STM maps a deviation of the epoch state [r, v] to a deviation at
+    /// `time`. It is integrated with the state from the variational equations,
+    /// taking force-model partials by central differences.
+    pub fn propagate_with_stm(
         &self,
-        state: &mut SatelliteState,
         satellite: &SatelliteOrbit,
-        time_minutes: f64,
-    ) {
-        let a = satellite.elements.semi_major_axis_km;
-        let e = satellite.elements.eccentricity;
-        let i = satellite.elements.inclination_deg * DEG_TO_RAD;
+        time: DateTime<Utc>,
+    ) -> Result<(SatelliteState, StateMatrix)> {
+        let mut series = self.propagate_with_stm_series(satellite, &[time])?;
+        Ok(series.remove(0))
+    }
+
+    /// State and STM from the element epoch at each of `times`
+    ///
+    /// Integrates once through `times` in the order given, continuing from
+    /// the previous sample rather than restarting at the element epoch.
+    pub fn propagate_with_stm_series(
+        &self,
+        satellite: &SatelliteOrbit,
+        times: &[DateTime<Utc>],
+    ) -> Result<Vec<(SatelliteState, StateMatrix)>> {
+        let started = trace_timer();
+        let mut augmented = [0.0; 42];
+        augmented[..6].copy_from_slice(&initial_state(satellite)?);
+        for i in 0..6 {
+            augmented[6 + i * 6 + i] = 1.0;
+        }
 
-        // J2 perturbation rates (simplified)
-        let n = (EARTH_MU / a.powi(3)).sqrt(); // Mean motion
-        let j2_factor = -1.5 * EARTH_J2 * (EARTH_RADIUS_KM / a).powi(2) * n;
+        let mut series = Vec::with_capacity(times.len());
+        let mut from_seconds = 0.0;
+        let mut total_steps = 0;
+        for &time in times {
+            let (next, num_steps) = self.integrate(
+                satellite,
+                from_seconds,
+                time,
+                augmented,
+                |elapsed, state| self.variational_derivative(satellite.epoch, elapsed, state),
+            )?;
+            augmented = next;
+            from_seconds = (time - satellite.epoch).num_milliseconds() as f64 / 1000.0;
+            total_steps += num_steps;
+
+            let mut stm = [[0.0; 6]; 6];
+            for (i, row) in stm.iter_mut().enumerate() {
+                row.copy_from_slice(&augmented[6 + i * 6..12 + i * 6]);
+            }
+            let state = SatelliteState::new(
+                satellite.satellite_id.clone(),
+                time,
+                [augmented[0], augmented[1], augmented[2]],
+                [augmented[3], augmented[4], augmented[5]],
+            );
+            series.push((state, stm));
+        }
 
-        // RAAN precession rate
-        let raan_dot = j2_factor * i.cos();
+        tracing::trace!(
+            target: trace_targets::PROPAGATOR,
+            propagator = "Numerical",
+            satellite_id = %satellite.satellite_id,
+            samples = times.len(),
+            num_steps = total_steps,
+            elapsed_us = started.map(|started| started.elapsed().as_secs_f64() * 1e6),
+            "Propagated with STM"
+        );
 
-        // Argument of perigee precession rate
-        let arg_perigee_dot = j2_factor * (2.0 - 2.5 * (i.sin()).powi(2));
+        Ok(series)
+    }
 
-        // Apply corrections (very simplified)
-        let delta_raan = raan_dot * time_minutes / 60.0; // Convert to hours
-        let delta_arg_perigee = arg_perigee_dot * time_minutes / 60.0;
+    /// Propagate a covariance given at the element epoch to `time`
+    pub fn propagate_covariance(
+        &self,
+        satellite: &SatelliteOrbit,
+        time: DateTime<Utc>,
+        covariance: &StateCovariance,
+    ) -> Result<(SatelliteState, StateCovariance)> {
+        let (state, stm) = self.propagate_with_stm(satellite, time)?;
+        Ok((state, covariance.propagate(&stm)))
+    }
 
-        // Note: In a full implementation, these corrections would be applied
-        // to the orbital elements before calculating position and velocity
-        // This is just a placeholder to show the concept
+    /// Integrate `state`, given `from_seconds` past the element epoch, to `time`
+    ///
+    /// The first six components are the ECI state [r, v]; any further
+    /// components ride along without affecting step-size control.
+    fn integrate<const N: usize>(
+        &self,
+        satellite: &SatelliteOrbit,
+        from_seconds: f64,
+        time: DateTime<Utc>,
+        mut state: [f64; N],
+        derivative: impl Fn(f64, &[f64; N]) -> [f64; N],
+    ) -> Result<([f64; N], usize)> {
+        let end_seconds = (time - satellite.epoch).num_milliseconds() as f64 / 1000.0;
+        let total_time = end_seconds - from_seconds;
+
+        match self.integrator {
+            Integrator::Rk4 => {
+                let num_steps = (total_time.abs() / self.step_size_seconds).ceil() as usize;
+                if num_steps > MAX_INTEGRATION_STEPS {
+                    return Err(self.step_limit_error(satellite, time, num_steps));
+                }
+
+                let step = if num_steps > 0 {
+                    total_time / num_steps as f64
+                } else {
+                    0.0
+                };
+                for i in 0..num_steps {
+                    let elapsed = from_seconds + i as f64 * step;
+                    state = rk4_step(&derivative, elapsed, &state, step);
+                    self.check_reentry(satellite, &state, elapsed + step)?;
+                }
+                Ok((state, num_steps))
+            }
+            Integrator::Rkf78 {
+                tolerance_km,
+                max_step_seconds,
+            } => {
+                let direction = total_time.signum();
+                let max_step = max_step_seconds.max(MIN_STEP_SECONDS);
+                let mut step = self.step_size_seconds.clamp(MIN_STEP_SECONDS, max_step);
+                let mut elapsed = from_seconds;
+                let mut num_steps = 0;
+
+                while (end_seconds - elapsed).abs() > MIN_STEP_SECONDS {
+                    num_steps += 1;
+                    if num_steps > MAX_INTEGRATION_STEPS {
+                        return Err(self.step_limit_error(satellite, time, num_steps));
+                    }
+
+                    let h = direction * step.min((end_seconds - elapsed).abs());
+                    let (next, error) = rkf78_step(&derivative, elapsed, &state, h);
+                    let factor = if error > 0.0 {
+                        (0.9 * (tolerance_km / error).powf(1.0 / 8.0)).clamp(0.2, 5.0)
+                    } else {
+                        5.0
+                    };
+
+                    if error <= tolerance_km || h.abs() <= MIN_STEP_SECONDS {
+                        state = next;
+                        elapsed += h;
+                        self.check_reentry(satellite, &state, elapsed)?;
+                    }
+                    step = (h.abs() * factor).clamp(MIN_STEP_SECONDS, max_step);
+                }
+                Ok((state, num_steps))
+            }
+        }
     }
-}
 
-impl OrbitalPropagator for NumericalPropagator {
-    fn propagate(&self, satellite: &SatelliteOrbit, time: DateTime<Utc>) -> Result<SatelliteState> {
-        // Simplified numerical integration
-        // In practice, this would use Runge-Kutta or similar methods
+    fn step_limit_error(
+        &self,
+        satellite: &SatelliteOrbit,
+        time: DateTime<Utc>,
+        num_steps: usize,
+    ) -> OrbitalMechanicsError {
+        tracing::warn!(
+            target: trace_targets::PROPAGATOR,
+            satellite_id = %satellite.satellite_id,
+            num_steps,
+            step_size_seconds = self.step_size_seconds,
+            "Numerical integration span exceeds step limit"
+        );
+        OrbitalMechanicsError::propagation_error("Numerical integration time too long")
+            .for_satellite(&satellite.satellite_id)
+            .at_epoch(time)
+    }
 
-        let total_time = (time - satellite.epoch).num_seconds() as f64;
-        let num_steps = (total_time / self.step_size_seconds).ceil() as usize;
+    fn check_reentry(&self, satellite: &SatelliteOrbit, state: &[f64], elapsed: f64) -> Result<()> {
+        let radius = (state[0].powi(2) + state[1].powi(2) + state[2].powi(2)).sqrt();
+        if radius - EARTH_RADIUS_KM >= REENTRY_ALTITUDE_KM {
+            return Ok(());
+        }
 
-        if num_steps > 100000 {
-            return Err(OrbitalMechanicsError::propagation_error(
-                "Numerical integration time too long",
-            ));
+        let reentry_time =
+            satellite.epoch + chrono::Duration::milliseconds((elapsed * 1000.0) as i64);
+        tracing::info!(
+            target: trace_targets::PROPAGATOR,
+            satellite_id = %satellite.satellite_id,
+            %reentry_time,
+            "Satellite re-entered during numerical integration"
+        );
+        Err(OrbitalMechanicsError::propagation_error(format!(
+            "Satellite re-entered the atmosphere at {}",
+            reentry_time.to_rfc3339()
+        ))
+        .for_satellite(&satellite.satellite_id)
+        .at_epoch(reentry_time))
+    }
+
+    /// Sum of all force-model accelerations
+    fn acceleration(
+        &self,
+        time: DateTime<Utc>,
+        position: [f64; 3],
+        velocity: [f64; 3],
+    ) -> [f64; 3] {
+        let mut acceleration = [0.0; 3];
+        for force in &self.forces {
+            let contribution = force.acceleration(time, position, velocity);
+            for axis in 0..3 {
+                acceleration[axis] += contribution[axis];
+            }
         }
+        acceleration
+    }
 
-        // For now, use Keplerian propagation
-        let keplerian = KeplerianPropagator::new();
-        keplerian.propagate(satellite, time)
+    /// Time derivative of the ECI state [r, v] at `elapsed` seconds past `epoch`
+    fn derivative(&self, epoch: DateTime<Utc>, elapsed: f64, state: &[f64; 6]) -> [f64; 6] {
+        let time = epoch + chrono::Duration::microseconds((elapsed * 1e6) as i64);
+        let velocity = [state[3], state[4], state[5]];
+        let acceleration = self.acceleration(time, [state[0], state[1], state[2]], velocity);
+
+        [
+            velocity[0],
+            velocity[1],
+            velocity[2],
+            acceleration[0],
+            acceleration[1],
+            acceleration[2],
+        ]
     }
 
-    fn name(&self) -> &str {
-        "Numerical Integration"
+    /// Time derivative of the state followed by the row-major STM
+    ///
+    /// dΦ/dt = A Φ with A = [[0, I], [∂a/∂r, ∂a/∂v]].
+    fn variational_derivative(
+        &self,
+        epoch: DateTime<Utc>,
+        elapsed: f64,
+        state: &[f64; 42],
+    ) -> [f64; 42] {
+        let time = epoch + chrono::Duration::microseconds((elapsed * 1e6) as i64);
+        let position = [state[0], state[1], state[2]];
+        let velocity = [state[3], state[4], state[5]];
+        let stm = |row: usize, column: usize| state[6 + row * 6 + column];
+
+        // Central-difference partials of the total acceleration
+        let mut by_position = [[0.0; 3]; 3];
+        let mut by_velocity = [[0.0; 3]; 3];
+        for k in 0..3 {
+            let (mut plus, mut minus) = (position, position);
+            plus[k] += PARTIAL_POSITION_STEP_KM;
+            minus[k] -= PARTIAL_POSITION_STEP_KM;
+            let (a_plus, a_minus) = (
+                self.acceleration(time, plus, velocity),
+                self.acceleration(time, minus, velocity),
+            );
+
+            let (mut faster, mut slower) = (velocity, velocity);
+            faster[k] += PARTIAL_VELOCITY_STEP_KM_S;
+            slower[k] -= PARTIAL_VELOCITY_STEP_KM_S;
+            let (a_faster, a_slower) = (
+                self.acceleration(time, position, faster),
+                self.acceleration(time, position, slower),
+            );
+
+            for i in 0..3 {
+                by_position[i][k] = (a_plus[i] - a_minus[i]) / (2.0 * PARTIAL_POSITION_STEP_KM);
+                by_velocity[i][k] =
+                    (a_faster[i] - a_slower[i]) / (2.0 * PARTIAL_VELOCITY_STEP_KM_S);
+            }
+        }
+
+        let mut derivative = [0.0; 42];
+        derivative[..3].copy_from_slice(&velocity);
+        derivative[3..6].copy_from_slice(&self.acceleration(time, position, velocity));
+        for column in 0..6 {
+            for i in 0..3 {
+                derivative[6 + i * 6 + column] = stm(3 + i, column);
+                derivative[6 + (3 + i) * 6 + column] = (0..3)
+                    .map(|k| {
+                        by_position[i][k] * stm(k, column) + by_velocity[i][k] * stm(3 + k, column)
+                    })
+                    .sum();
+            }
+        }
+        derivative
     }
+}
 
-    fn max_propagation_duration(&self) -> chrono::Duration {
-        chrono::Duration::days(30) // 30 days for numerical integration
+/// Osculating ECI state [r, v] at the element epoch
+fn initial_state(satellite: &SatelliteOrbit) -> Result<[f64; 6]> {
+    let initial = KeplerianPropagator::new().propagate(satellite, satellite.epoch)?;
+    Ok([
+        initial.position_eci[0],
+        initial.position_eci[1],
+        initial.position_eci[2],
+        initial.velocity_eci[0],
+        initial.velocity_eci[1],
+        initial.velocity_eci[2],
+    ])
+}
+
+fn rk4_step<const N: usize>(
+    derivative: &impl Fn(f64, &[f64; N]) -> [f64; N],
+    t: f64,
+    state: &[f64; N],
+    h: f64,
+) -> [f64; N] {
+    let offset = |k: &[f64; N], factor: f64| {
+        let mut shifted = *state;
+        for i in 0..N {
+            shifted[i] += k[i] * factor;
+        }
+        shifted
+    };
+
+    let k1 = derivative(t, state);
+    let k2 = derivative(t + h / 2.0, &offset(&k1, h / 2.0));
+    let k3 = derivative(t + h / 2.0, &offset(&k2, h / 2.0));
+    let k4 = derivative(t + h, &offset(&k3, h));
+
+    let mut next = *state;
+    for i in 0..N {
+        next[i] += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
     }
+    next
 }
 
-impl NumericalPropagator {
-    pub fn new(step_size_seconds: f64) -> Self {
-        Self { step_size_seconds }
+/// One Runge-Kutta-Fehlberg 7(8) step
+///
+/// Returns the 8th-order state and the largest component of the local error
+/// estimate over the ECI state [r, v].
+fn rkf78_step<const N: usize>(
+    derivative: &impl Fn(f64, &[f64; N]) -> [f64; N],
+    t: f64,
+    state: &[f64; N],
+    h: f64,
+) -> ([f64; N], f64) {
+    let mut k = [[0.0; N]; 13];
+    for stage in 0..13 {
+        let mut shifted = *state;
+        for (j, a) in RKF78_A[stage].iter().enumerate() {
+            for i in 0..N {
+                shifted[i] += h * a * k[j][i];
+            }
+        }
+        k[stage] = derivative(t + RKF78_C[stage] * h, &shifted);
     }
+
+    let mut next = *state;
+    let mut error: f64 = 0.0;
+    for i in 0..N {
+        for (stage, b) in RKF78_B8.iter().enumerate() {
+            next[i] += h * b * k[stage][i];
+        }
+        if i < 6 {
+            let estimate = 41.0 / 840.0 * (k[0][i] + k[10][i] - k[11][i] - k[12][i]) * h;
+            error = error.max(estimate.abs());
+        }
+    }
+    (next, error)
+}
+
+/// Start time for the `Propagated` trace event, `None` unless it will be recorded
+fn trace_timer() -> Option<Instant> {
+    tracing::enabled!(target: trace_targets::PROPAGATOR, tracing::Level::TRACE).then(Instant::now)
 }
 
 /// Create propagator instance based on type
-pub fn create_propagator(propagator_type: PropagatorType) -> Result<Box<dyn OrbitalPropagator>> {
+///
+/// The numerical propagator gets two-body gravity only; see
+/// `create_propagator_with_forces` for perturbations.
+pub fn create_propagator(
+    propagator_type: PropagatorType,
+) -> Result<Box<dyn OrbitalPropagator + Send + Sync>> {
+    create_propagator_with_forces(propagator_type, &ForceModelSet::default())
+}
+
+/// Create propagator instance based on type, with `forces` on the numerical propagator
+///
+/// The analytic propagators carry their own perturbation models and ignore `forces`.
+pub fn create_propagator_with_forces(
+    propagator_type: PropagatorType,
+    forces: &ForceModelSet,
+) -> Result<Box<dyn OrbitalPropagator + Send + Sync>> {
     match propagator_type {
         PropagatorType::Keplerian => Ok(Box::new(KeplerianPropagator::new())),
         PropagatorType::Sgp4 => Ok(Box::new(Sgp4Propagator::new())),
-        PropagatorType::Numerical => Ok(Box::new(NumericalPropagator::new(60.0))), // 1-minute steps
+        PropagatorType::Numerical => Ok(Box::new(
+            NumericalPropagator::new(60.0).with_force_models(forces), // 1-minute steps
+        )),
     }
 }
 
@@ -314,6 +806,13 @@ pub fn validate_propagation_time(
     let max_duration = propagator.max_propagation_duration();
 
     if duration > max_duration {
+        tracing::warn!(
+            target: trace_targets::PROPAGATOR,
+            propagator = propagator.name(),
+            requested_days = duration.num_days(),
+            max_days = max_duration.num_days(),
+            "Propagation span exceeds propagator limit"
+        );
         return Err(OrbitalMechanicsError::propagation_error(format!(
             "Propagation duration ({} days) exceeds maximum for {} propagator ({} days)",
             duration.num_days(),
@@ -328,6 +827,7 @@ pub fn validate_propagation_time(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::force_model::ZonalHarmonics;
     use crate::orbit::{OrbitalElements, SatelliteOrbit};
     use chrono::Utc;
 
@@ -383,19 +883,189 @@ mod tests {
         let numerical = create_propagator(PropagatorType::Numerical);
         assert!(numerical.is_ok());
         assert_eq!(numerical.unwrap().name(), "Numerical Integration");
+
+        // Configured perturbations are added on top of two-body gravity
+        let forces: ForceModelSet = serde_json::from_str(
+            r#"{
+                "zonal_degree": 2,
+                "drag": {"mass_kg": 500.0, "drag_area_m2": 2.0, "drag_coefficient": 2.2},
+                "third_bodies": ["Sun", "Moon"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            NumericalPropagator::new(60.0)
+                .with_force_models(&forces)
+                .force_models(),
+            vec![
+                "Two-Body",
+                "J2",
+                "Atmospheric Drag",
+                "Solar Gravity",
+                "Lunar Gravity"
+            ]
+        );
+        assert!(create_propagator_with_forces(PropagatorType::Numerical, &forces).is_ok());
     }
 
     #[test]
-    fn test_keplers_equation_solver() {
-        let propagator = KeplerianPropagator::new();
+    fn test_numerical_drag_decay() {
+        let epoch = Utc::now();
+        let elements =
+            OrbitalElements::new(EARTH_RADIUS_KM + 300.0, 0.0, 51.6, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "LEO-01".to_string(),
+            "Drag Test".to_string(),
+            elements,
+            epoch,
+        );
+
+        // Without drag the integrator tracks the two-body solution
+        let later = epoch + chrono::Duration::hours(6);
+        let two_body = NumericalPropagator::new(30.0)
+            .propagate(&satellite, later)
+            .unwrap();
+        let keplerian = KeplerianPropagator::new()
+            .propagate(&satellite, later)
+            .unwrap();
+        let error_km = (0..3)
+            .map(|i| (two_body.position_eci[i] - keplerian.position_eci[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(error_km < 0.1);
+
+        // A dense, low-mass object at 300 km loses altitude within a day
+        let propagator = NumericalPropagator::new(30.0).with_drag(
+            DragParameters {
+                mass_kg: 100.0,
+                drag_area_m2: 2.0,
+                drag_coefficient: 2.2,
+            },
+            AtmosphereModel::Exponential,
+        );
+        let decayed = propagator
+            .propagate(&satellite, epoch + chrono::Duration::days(1))
+            .unwrap();
+        let decay_km = EARTH_RADIUS_KM + 300.0 - decayed.orbital_radius;
+        assert!(decay_km > 1.0 && decay_km < 50.0);
+
+        // ...and re-enters well inside the 30-day propagation limit
+        let err = propagator
+            .propagate(&satellite, epoch + chrono::Duration::days(29))
+            .unwrap_err();
+        assert!(err.to_string().contains("re-entered"));
+        assert!(err.epoch().unwrap() < epoch + chrono::Duration::days(29));
+    }
+
+    #[test]
+    fn test_rkf78_with_zonal_harmonics() {
+        let epoch = Utc::now();
+        let elements = OrbitalElements::new(14378.0, 0.01, 55.0, 40.0, 30.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "MEO-01".to_string(),
+            "RKF78".to_string(),
+            elements.clone(),
+            epoch,
+        );
+        let rkf78 = Integrator::Rkf78 {
+            tolerance_km: 1e-9,
+            max_step_seconds: 1800.0,
+        };
+
+        // Two-body RKF78 tracks the Keplerian solution over several orbits
+        let later = epoch + chrono::Duration::hours(24);
+        let two_body = NumericalPropagator::new(60.0)
+            .with_integrator(rkf78)
+            .propagate(&satellite, later)
+            .unwrap();
+        let keplerian = KeplerianPropagator::new()
+            .propagate(&satellite, later)
+            .unwrap();
+        let error_km = (0..3)
+            .map(|i| (two_body.position_eci[i] - keplerian.position_eci[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(error_km < 1e-3, "error {} km", error_km);
+
+        // J2-J4 regress the node at the secular J2 rate
+        let propagator = NumericalPropagator::new(60.0)
+            .with_integrator(rkf78)
+            .with_force(ZonalHarmonics::default());
+        assert_eq!(propagator.force_models(), vec!["Two-Body", "J2-J4"]);
+        let node_deg = |state: &SatelliteState| {
+            let r = state.position_eci;
+            let v = state.velocity_eci;
+            let h = [r[1] * v[2] - r[2] * v[1], r[2] * v[0] - r[0] * v[2]];
+            h[0].atan2(-h[1]).to_degrees()
+        };
+        let start = propagator.propagate(&satellite, epoch).unwrap();
+        let end = propagator
+            .propagate(&satellite, epoch + chrono::Duration::days(2))
+            .unwrap();
+        let regression_deg = node_deg(&end) - node_deg(&start);
+        let expected_deg = elements.nodal_precession_rate_deg_per_day() * 2.0;
+        assert!(
+            ((regression_deg - expected_deg) / expected_deg).abs() < 0.05,
+            "regression {} deg, expected {} deg",
+            regression_deg,
+            expected_deg
+        );
+    }
+
+    #[test]
+    fn test_state_transition_matrix() {
+        let epoch = Utc::now();
+        let radius = 14378.0;
+        let elements = OrbitalElements::new(radius, 0.0, 55.0, 40.0, 0.0, 0.0).unwrap();
+        let satellite =
+            SatelliteOrbit::new("STM-01".to_string(), "STM".to_string(), elements, epoch);
+        let propagator = NumericalPropagator::new(30.0);
+
+        let (state, stm) = propagator.propagate_with_stm(&satellite, epoch).unwrap();
+        assert_eq!(stm, StateCovariance::from_sigmas(1.0, 1.0).matrix);
+
+        // A radial offset with unchanged inertial velocity on a circular orbit
+        // follows Clohessy-Wiltshire with y0' = -n x0:
+        // x = (2 - cos nt) x0, y = (2 sin nt - 3 nt) x0
+        let radial = state.position_eci.map(|x| x / radius);
+        let later = epoch + chrono::Duration::minutes(150);
+        let (final_state, stm) = propagator.propagate_with_stm(&satellite, later).unwrap();
+        let response: Vec<f64> = (0..3)
+            .map(|i| (0..3).map(|k| stm[i][k] * radial[k]).sum())
+            .collect();
+        let final_radial = final_state
+            .position_eci
+            .map(|x| x / final_state.orbital_radius);
+        let speed = final_state
+            .velocity_eci
+            .iter()
+            .map(|v| v * v)
+            .sum::<f64>()
+            .sqrt();
+        let final_along = final_state.velocity_eci.map(|v| v / speed);
+
+        let nt = satellite.mean_motion_rad_per_sec * 150.0 * 60.0;
+        let radial_response: f64 = (0..3).map(|i| response[i] * final_radial[i]).sum();
+        let along_response: f64 = (0..3).map(|i| response[i] * final_along[i]).sum();
+        assert!((radial_response - (2.0 - nt.cos())).abs() < 1e-3);
+        assert!((along_response - (2.0 * nt.sin() - 3.0 * nt)).abs() < 1e-3);
+
+        // Covariance maps through the same STM
+        let (_, covariance) = propagator
+            .propagate_covariance(&satellite, later, &StateCovariance::from_sigmas(0.1, 0.0))
+            .unwrap();
+        assert!(covariance.position_sigma_km() > 0.1 * 3.0_f64.sqrt());
+    }
 
+    #[test]
+    fn test_keplers_equation_solver() {
         // Test circular orbit (e = 0)
-        let result = propagator.solve_keplers_equation(1.0, 0.0);
+        let result = propagation_core::solve_kepler(1.0, 0.0);
         assert!(result.is_ok());
         assert!((result.unwrap() - 1.0).abs() < 1e-10);
 
         // Test elliptical orbit
-        let result = propagator.solve_keplers_equation(PI / 2.0, 0.1);
+        let result = propagation_core::solve_kepler(PI / 2.0, 0.1);
         assert!(result.is_ok());
     }
 
@@ -0,0 +1,293 @@
+//! Relative motion between co-orbiting satellites
+//!
+//! Clohessy-Wiltshire (Hill) equations for a deputy satellite relative to a
+//! chief on a near-circular orbit. States are expressed in the chief's RIC
+//! frame: x radial (outward), y in-track, z cross-track (orbit normal).
+
+use crate::constants::*;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use nalgebra::{Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Deputy position and velocity in the chief's RIC frame
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RelativeState {
+    /// Radial, in-track, cross-track offset in km
+    pub position_km: [f64; 3],
+    /// Radial, in-track, cross-track velocity in km/s
+    pub velocity_km_per_s: [f64; 3],
+}
+
+/// Two-impulse transfer between relative states
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RephasingManeuver {
+    pub transfer_time_seconds: f64,
+    /// First burn in the RIC frame (km/s)
+    pub departure_delta_v_km_per_s: [f64; 3],
+    /// Second burn in the RIC frame (km/s)
+    pub arrival_delta_v_km_per_s: [f64; 3],
+    pub total_delta_v_m_per_s: f64,
+}
+
+/// Clohessy-Wiltshire relative motion model
+pub struct ClohessyWiltshire {
+    /// Chief mean motion in rad/s
+    pub mean_motion_rad_per_sec: f64,
+}
+
+impl RelativeState {
+    /// Create relative state from RIC position (km) and velocity (km/s)
+    pub fn new(position_km: [f64; 3], velocity_km_per_s: [f64; 3]) -> Self {
+        Self {
+            position_km,
+            velocity_km_per_s,
+        }
+    }
+
+    /// Relative state of `deputy` in the RIC frame of `chief`
+    pub fn from_eci(chief: &SatelliteState, deputy: &SatelliteState) -> Self {
+        let r_chief = Vector3::from(chief.position_eci);
+        let v_chief = Vector3::from(chief.velocity_eci);
+        let angular_momentum = r_chief.cross(&v_chief);
+
+        let radial = r_chief.normalize();
+        let cross_track = angular_momentum.normalize();
+        let in_track = cross_track.cross(&radial);
+
+        // Frame rotation rate of the chief's orbit
+        let omega = angular_momentum / r_chief.norm_squared();
+
+        let dr = Vector3::from(deputy.position_eci) - r_chief;
+        let dv = Vector3::from(deputy.velocity_eci) - v_chief - omega.cross(&dr);
+
+        Self {
+            position_km: [dr.dot(&radial), dr.dot(&in_track), dr.dot(&cross_track)],
+            velocity_km_per_s: [dv.dot(&radial), dv.dot(&in_track), dv.dot(&cross_track)],
+        }
+    }
+
+    /// Distance from the chief in km
+    pub fn range_km(&self) -> f64 {
+        Vector3::from(self.position_km).norm()
+    }
+}
+
+impl ClohessyWiltshire {
+    /// Create model for a chief satellite orbit
+    pub fn new(chief: &SatelliteOrbit) -> Self {
+        Self::with_mean_motion(chief.mean_motion_rad_per_sec)
+    }
+
+    /// Create model from a circular orbit radius in km
+    pub fn from_radius(radius_km: f64) -> Self {
+        Self::with_mean_motion((EARTH_MU / radius_km.powi(3)).sqrt())
+    }
+
+    /// Create model with explicit chief mean motion in rad/s
+    pub fn with_mean_motion(mean_motion_rad_per_sec: f64) -> Self {
+        Self {
+            mean_motion_rad_per_sec,
+        }
+    }
+
+    /// Propagate a relative state by `dt_seconds`
+    pub fn propagate(&self, state: &RelativeState, dt_seconds: f64) -> RelativeState {
+        let (phi_rr, phi_rv, phi_vr, phi_vv) = self.state_transition(dt_seconds);
+        let r0 = Vector3::from(state.position_km);
+        let v0 = Vector3::from(state.velocity_km_per_s);
+
+        let r = phi_rr * r0 + phi_rv * v0;
+        let v = phi_vr * r0 + phi_vv * v0;
+
+        RelativeState::new(r.into(), v.into())
+    }
+
+    /// Sample the relative trajectory every `step_seconds` over `duration_seconds`
+    pub fn trajectory(
+        &self,
+        state: &RelativeState,
+        duration_seconds: f64,
+        step_seconds: f64,
+    ) -> Result<Vec<(f64, RelativeState)>> {
+        if !step_seconds.is_finite() || step_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Trajectory step must be positive, got {} s",
+                step_seconds
+            )));
+        }
+
+        let steps = (duration_seconds / step_seconds).ceil() as usize;
+        Ok((0..=steps)
+            .map(|i| {
+                let t = (i as f64 * step_seconds).min(duration_seconds);
+                (t, self.propagate(state, t))
+            })
+            .collect())
+    }
+
+    /// Secular in-track drift rate in km/s (zero for a bounded formation)
+    pub fn drift_rate_km_per_s(&self, state: &RelativeState) -> f64 {
+        -(6.0 * self.mean_motion_rad_per_sec * state.position_km[0]
+            + 3.0 * state.velocity_km_per_s[1])
+    }
+
+    /// Secular in-track drift accumulated per chief orbit in km
+    pub fn drift_per_orbit_km(&self, state: &RelativeState) -> f64 {
+        self.drift_rate_km_per_s(state) * TWO_PI / self.mean_motion_rad_per_sec
+    }
+
+    /// In-track velocity that cancels secular drift for the given radial offset
+    pub fn bounded_in_track_velocity(&self, radial_offset_km: f64) -> f64 {
+        -2.0 * self.mean_motion_rad_per_sec * radial_offset_km
+    }
+
+    /// Two-impulse transfer from `initial` to `target` over `transfer_time_seconds`
+    pub fn rendezvous(
+        &self,
+        initial: &RelativeState,
+        target: &RelativeState,
+        transfer_time_seconds: f64,
+    ) -> Result<RephasingManeuver> {
+        let (phi_rr, phi_rv, phi_vr, phi_vv) = self.state_transition(transfer_time_seconds);
+        let phi_rv_inv = phi_rv.try_inverse().ok_or_else(|| {
+            OrbitalMechanicsError::math_error(format!(
+                "CW transfer of {} s is singular (multiple of the orbital period)",
+                transfer_time_seconds
+            ))
+        })?;
+
+        let r0 = Vector3::from(initial.position_km);
+        let v0 = Vector3::from(initial.velocity_km_per_s);
+        let rf = Vector3::from(target.position_km);
+        let vf = Vector3::from(target.velocity_km_per_s);
+
+        let v0_required = phi_rv_inv * (rf - phi_rr * r0);
+        let vf_arrival = phi_vr * r0 + phi_vv * v0_required;
+
+        let departure = v0_required - v0;
+        let arrival = vf - vf_arrival;
+
+        Ok(RephasingManeuver {
+            transfer_time_seconds,
+            departure_delta_v_km_per_s: departure.into(),
+            arrival_delta_v_km_per_s: arrival.into(),
+            total_delta_v_m_per_s: (departure.norm() + arrival.norm()) * KM_TO_M,
+        })
+    }
+
+    /// In-track re-phasing by `offset_km` over whole chief revolutions
+    ///
+    /// Uses a tangential burn to open a drift, and an equal opposite burn to
+    /// stop it after `revolutions` orbits.
+    pub fn rephasing(&self, offset_km: f64, revolutions: u32) -> Result<RephasingManeuver> {
+        if revolutions == 0 {
+            return Err(OrbitalMechanicsError::config_error(
+                "Re-phasing requires at least one revolution",
+            ));
+        }
+
+        let transfer_time_seconds = revolutions as f64 * TWO_PI / self.mean_motion_rad_per_sec;
+        let in_track_delta_v = -offset_km / (3.0 * transfer_time_seconds);
+
+        Ok(RephasingManeuver {
+            transfer_time_seconds,
+            departure_delta_v_km_per_s: [0.0, in_track_delta_v, 0.0],
+            arrival_delta_v_km_per_s: [0.0, -in_track_delta_v, 0.0],
+            total_delta_v_m_per_s: 2.0 * in_track_delta_v.abs() * KM_TO_M,
+        })
+    }
+
+    /// CW state transition matrix blocks (rr, rv, vr, vv)
+    #[rustfmt::skip]
+    fn state_transition(
+        &self,
+        t: f64,
+    ) -> (Matrix3<f64>, Matrix3<f64>, Matrix3<f64>, Matrix3<f64>) {
+        let n = self.mean_motion_rad_per_sec;
+        let (s, c) = (n * t).sin_cos();
+
+        let phi_rr = Matrix3::new(
+            4.0 - 3.0 * c, 0.0, 0.0,
+            6.0 * (s - n * t), 1.0, 0.0,
+            0.0, 0.0, c,
+        );
+        let phi_rv = Matrix3::new(
+            s / n, 2.0 * (1.0 - c) / n, 0.0,
+            -2.0 * (1.0 - c) / n, (4.0 * s - 3.0 * n * t) / n, 0.0,
+            0.0, 0.0, s / n,
+        );
+        let phi_vr = Matrix3::new(
+            3.0 * n * s, 0.0, 0.0,
+            6.0 * n * (c - 1.0), 0.0, 0.0,
+            0.0, 0.0, -n * s,
+        );
+        let phi_vv = Matrix3::new(
+            c, 2.0 * s, 0.0,
+            -2.0 * s, 4.0 * c - 3.0, 0.0,
+            0.0, 0.0, c,
+        );
+
+        (phi_rr, phi_rv, phi_vr, phi_vv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meo_model() -> ClohessyWiltshire {
+        ClohessyWiltshire::from_radius(EARTH_RADIUS_KM + LASERLIGHT_FSO_ALTITUDE_KM)
+    }
+
+    #[test]
+    fn test_bounded_formation_has_no_drift() {
+        let cw = meo_model();
+        let state = RelativeState::new(
+            [1.0, 0.0, 0.5],
+            [0.0, cw.bounded_in_track_velocity(1.0), 0.0],
+        );
+
+        assert!(cw.drift_rate_km_per_s(&state).abs() < 1e-12);
+
+        let period = TWO_PI / cw.mean_motion_rad_per_sec;
+        let after_orbit = cw.propagate(&state, period);
+        for i in 0..3 {
+            assert!((after_orbit.position_km[i] - state.position_km[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_rephasing_matches_propagation() {
+        let cw = meo_model();
+        let maneuver = cw.rephasing(10.0, 2).unwrap();
+
+        let start = RelativeState::new([0.0; 3], maneuver.departure_delta_v_km_per_s);
+        let end = cw.propagate(&start, maneuver.transfer_time_seconds);
+        assert!((end.position_km[1] - 10.0).abs() < 1e-6);
+        assert!(end.position_km[0].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rendezvous_reaches_target() {
+        let cw = meo_model();
+        let initial = RelativeState::new([0.5, -20.0, 0.1], [0.0; 3]);
+        let target = RelativeState::new([0.0, -1.0, 0.0], [0.0; 3]);
+        let transfer = 0.4 * TWO_PI / cw.mean_motion_rad_per_sec;
+
+        let maneuver = cw.rendezvous(&initial, &target, transfer).unwrap();
+        let mut departed = initial;
+        for i in 0..3 {
+            departed.velocity_km_per_s[i] += maneuver.departure_delta_v_km_per_s[i];
+        }
+
+        let arrived = cw.propagate(&departed, transfer);
+        for i in 0..3 {
+            assert!((arrived.position_km[i] - target.position_km[i]).abs() < 1e-6);
+            let final_velocity =
+                arrived.velocity_km_per_s[i] + maneuver.arrival_delta_v_km_per_s[i];
+            assert!(final_velocity.abs() < 1e-9);
+        }
+        assert!(maneuver.total_delta_v_m_per_s > 0.0);
+    }
+}
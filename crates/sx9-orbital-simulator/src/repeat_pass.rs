@@ -0,0 +1,332 @@
+//! Repeat pass prediction ("same geometry next time")
+//!
+//! Recurring calibration contacts want a pass that looks like a reference
+//! one from the station: similar peak elevation, peak azimuth and rise and
+//! set directions. `RepeatPassFinder` scans a satellite's future passes over
+//! a station and ranks them by how far their geometry departs from the
+//! reference.
+//!
+//! Unlike `VisibilityCalculator`, which keeps stations fixed in the inertial
+//! frame, the scan rotates the station with the Earth (GMST, including
+//! UT1 − UTC), so a pass recurs only when the orbit and the Earth have both
+//! come back round. Passes are sampled at the finder's step; AOS and LOS are
+//! the first and last samples above the mask.
+
+use crate::constants::defaults;
+use crate::coordinates::wrap_angle_deg;
+use crate::ephemeris::gmst_rad;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::orbit::{LookAngles, SatelliteOrbit};
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Geometry of one pass seen from the station
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassGeometry {
+    pub satellite_id: String,
+    pub station_id: String,
+    pub aos_time: DateTime<Utc>,
+    pub los_time: DateTime<Utc>,
+    pub max_elevation_time: DateTime<Utc>,
+    pub max_elevation_deg: f64,
+    pub max_elevation_azimuth_deg: f64,
+    pub aos_azimuth_deg: f64,
+    pub los_azimuth_deg: f64,
+}
+
+/// Future pass matched to a reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatPass {
+    pub pass: PassGeometry,
+    /// RMS of the peak elevation and the peak, AOS and LOS azimuth differences
+    pub geometry_difference_deg: f64,
+    /// Peak to peak
+    pub days_after_reference: f64,
+}
+
+/// Scans passes over a rotating Earth and matches them to a reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatPassFinder {
+    /// How far past the reference pass to search
+    pub search_days: f64,
+    pub step_seconds: f64,
+    /// Used for stations without their own mask
+    pub min_elevation_deg: f64,
+}
+
+impl PassGeometry {
+    /// RMS of the peak elevation and the peak, AOS and LOS azimuth
+    /// differences, in degrees; azimuths compare along the shorter arc
+    pub fn difference_deg(&self, other: &PassGeometry) -> f64 {
+        let azimuth = |a: f64, b: f64| wrap_angle_deg(a - b);
+        let deltas = [
+            self.max_elevation_deg - other.max_elevation_deg,
+            azimuth(
+                self.max_elevation_azimuth_deg,
+                other.max_elevation_azimuth_deg,
+            ),
+            azimuth(self.aos_azimuth_deg, other.aos_azimuth_deg),
+            azimuth(self.los_azimuth_deg, other.los_azimuth_deg),
+        ];
+        (deltas.iter().map(|d| d * d).sum::<f64>() / deltas.len() as f64).sqrt()
+    }
+
+    pub fn duration_seconds(&self) -> f64 {
+        (self.los_time - self.aos_time).num_milliseconds() as f64 / 1000.0
+    }
+}
+
+impl RepeatPassFinder {
+    pub fn new(search_days: f64) -> Self {
+        Self {
+            search_days,
+            step_seconds: DEFAULT_STEP_SECONDS,
+            min_elevation_deg: defaults::MIN_ELEVATION_DEG,
+        }
+    }
+
+    pub fn with_step(mut self, step_seconds: f64) -> Self {
+        self.step_seconds = step_seconds;
+        self
+    }
+
+    pub fn with_min_elevation(mut self, min_elevation_deg: f64) -> Self {
+        self.min_elevation_deg = min_elevation_deg;
+        self
+    }
+
+    /// Complete passes between `start_time` and `end_time`
+    ///
+    /// Passes already in progress at `start_time` or still in progress at
+    /// `end_time` are left out.
+    pub fn passes(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Vec<PassGeometry>> {
+        self.validate()?;
+        let mask_deg = station.min_elevation_deg.unwrap_or(self.min_elevation_deg);
+        let step = Duration::milliseconds((self.step_seconds * 1000.0) as i64);
+
+        let mut passes = Vec::new();
+        let mut current: Option<PassGeometry> = None;
+        let mut previous_above = None;
+        let mut time = start_time;
+        while time <= end_time {
+            let look = self.look_angles(satellite, station, time, propagator)?;
+            let above = look.elevation_deg >= mask_deg;
+            match (&mut current, above) {
+                // Rising, unless the scan started mid-pass
+                (None, true) if previous_above == Some(false) => {
+                    current = Some(PassGeometry {
+                        satellite_id: satellite.satellite_id.clone(),
+                        station_id: station.station_id.clone(),
+                        aos_time: time,
+                        los_time: time,
+                        max_elevation_time: time,
+                        max_elevation_deg: look.elevation_deg,
+                        max_elevation_azimuth_deg: look.azimuth_deg,
+                        aos_azimuth_deg: look.azimuth_deg,
+                        los_azimuth_deg: look.azimuth_deg,
+                    });
+                }
+                (Some(pass), true) => {
+                    pass.los_time = time;
+                    pass.los_azimuth_deg = look.azimuth_deg;
+                    if look.elevation_deg > pass.max_elevation_deg {
+                        pass.max_elevation_time = time;
+                        pass.max_elevation_deg = look.elevation_deg;
+                        pass.max_elevation_azimuth_deg = look.azimuth_deg;
+                    }
+                }
+                (Some(_), false) => passes.extend(current.take()),
+                _ => {}
+            }
+            previous_above = Some(above);
+            time += step;
+        }
+        Ok(passes)
+    }
+
+    /// The pass in progress at `time`, if any
+    pub fn pass_at(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Option<PassGeometry>> {
+        let span = Duration::hours(MAX_PASS_HOURS);
+        let passes = self.passes(satellite, station, time - span, time + span, propagator)?;
+        Ok(passes
+            .into_iter()
+            .find(|pass| pass.aos_time <= time && time <= pass.los_time))
+    }
+
+    /// Later pass over the reference's station with the most similar geometry
+    ///
+    /// Searches `search_days` past the reference's LOS. Ties go to the
+    /// earlier pass.
+    pub fn find_repeat(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        reference: &PassGeometry,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Option<RepeatPass>> {
+        if station.station_id != reference.station_id {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Reference pass is over {}, not {}",
+                reference.station_id, station.station_id
+            )));
+        }
+        let end_time =
+            reference.los_time + Duration::milliseconds((self.search_days * 86_400_000.0) as i64);
+        let passes = self.passes(satellite, station, reference.los_time, end_time, propagator)?;
+
+        let best = passes
+            .into_iter()
+            .map(|pass| {
+                let geometry_difference_deg = pass.difference_deg(reference);
+                let days_after_reference = (pass.max_elevation_time - reference.max_elevation_time)
+                    .num_milliseconds() as f64
+                    / 86_400_000.0;
+                RepeatPass {
+                    pass,
+                    geometry_difference_deg,
+                    days_after_reference,
+                }
+            })
+            .reduce(|best, candidate| {
+                if candidate.geometry_difference_deg < best.geometry_difference_deg {
+                    candidate
+                } else {
+                    best
+                }
+            });
+
+        if let Some(best) = &best {
+            tracing::debug!(
+                target: crate::trace_targets::VISIBILITY,
+                satellite_id = %satellite.satellite_id,
+                station_id = %station.station_id,
+                days_after_reference = best.days_after_reference,
+                geometry_difference_deg = best.geometry_difference_deg,
+                "Repeat pass found"
+            );
+        }
+        Ok(best)
+    }
+
+    /// Look angles with the station rotated to its inertial position at `time`
+    fn look_angles(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<LookAngles> {
+        let state = propagator
+            .propagate(satellite, time)
+            .for_satellite(&satellite.satellite_id)
+            .for_station(&station.station_id)
+            .at_epoch(time)?;
+        let position = &station.position;
+        Ok(state.look_angles_from_station(
+            position.latitude_deg,
+            wrap_angle_deg(position.longitude_deg + gmst_rad(time).to_degrees()),
+            position.elevation_m,
+        ))
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.step_seconds.is_finite() || self.step_seconds < 1.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Repeat pass step must be at least 1 s, got {} s",
+                self.step_seconds
+            )));
+        }
+        if !self.search_days.is_finite() || self.search_days <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Repeat pass search must be positive, got {} days",
+                self.search_days
+            )));
+        }
+        Ok(())
+    }
+}
+
+const DEFAULT_STEP_SECONDS: f64 = 10.0;
+
+/// Longest pass `pass_at` looks for on either side of its time
+const MAX_PASS_HOURS: i64 = 12;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_find_repeat_pass() {
+        // 14 revolutions per sidereal day: the ground track closes daily
+        let sidereal_day = crate::constants::SIDEREAL_DAY_SECONDS;
+        let mean_motion = 2.0 * std::f64::consts::PI * 14.0 / sidereal_day;
+        let a = (crate::constants::EARTH_MU / (mean_motion * mean_motion)).cbrt();
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(a, 0.0, 60.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "CAL-1".to_string(),
+            "Calibration".to_string(),
+            elements,
+            epoch,
+        );
+        let station = GroundStation::builder()
+            .station_id("GS-CAL")
+            .latitude_deg(45.0)
+            .longitude_deg(10.0)
+            .build()
+            .unwrap();
+        let propagator = KeplerianPropagator::new();
+        let finder = RepeatPassFinder::new(3.0);
+
+        let passes = finder
+            .passes(
+                &satellite,
+                &station,
+                epoch,
+                epoch + Duration::days(1),
+                &propagator,
+            )
+            .unwrap();
+        assert!(!passes.is_empty());
+        let reference = &passes[0];
+        let found = finder
+            .pass_at(
+                &satellite,
+                &station,
+                reference.max_elevation_time,
+                &propagator,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(&found, reference);
+
+        let repeat = finder
+            .find_repeat(&satellite, &station, reference, &propagator)
+            .unwrap()
+            .unwrap();
+        // A whole number of sidereal days later, to within the scan step
+        let sidereal_days = repeat.days_after_reference * 86_400.0 / sidereal_day;
+        assert!(sidereal_days.round() >= 1.0);
+        assert!((sidereal_days - sidereal_days.round()).abs() * sidereal_day < 30.0);
+        assert!(repeat.geometry_difference_deg < 1.0, "{:?}", repeat);
+        assert!(reference.difference_deg(reference) == 0.0);
+    }
+}
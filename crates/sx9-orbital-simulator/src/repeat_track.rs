@@ -0,0 +1,228 @@
+//! Repeating ground tracks
+//!
+//! A ground track repeats when the satellite completes a whole number of
+//! nodal revolutions in a whole number of nodal days, such as 233 revolutions
+//! every 16 days for Landsat or 17 every 3 days for a regional pattern. Both
+//! periods include secular J2 drift: the nodal period from the perigee and
+//! mean anomaly rates, and the nodal day from Earth rotation relative to the
+//! regressing node.
+//!
+//! `analyze_ground_track_repeat` finds the shortest cycle an orbit closes
+//! within a tolerance; `RepeatCycle::semi_major_axis_km` designs the circular
+//! orbit that closes a chosen cycle exactly.
+
+use crate::constants::*;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::OrbitalElements;
+use crate::propagation_core::{j2_secular_rates, Elements};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Iterations allowed when solving for a repeat orbit's semi-major axis
+const DESIGN_ITERATION_LIMIT: usize = 50;
+
+/// Whole revolutions completed in whole nodal days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepeatCycle {
+    pub revolutions: u32,
+    pub days: u32,
+}
+
+/// Ground track closure of one orbit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatTrackAnalysis {
+    /// Ascending node to ascending node, including J2
+    pub nodal_period_seconds: f64,
+    /// Earth rotation relative to the orbit plane, including J2 regression
+    pub nodal_day_seconds: f64,
+    pub revolutions_per_day: f64,
+    /// Shortest cycle that closes within the tolerance, if any
+    pub cycle: Option<RepeatCycle>,
+    /// Equator offset between the first and repeated ascending node
+    pub closure_error_km: Option<f64>,
+    /// Equator spacing between adjacent tracks once the cycle is complete
+    pub track_spacing_km: Option<f64>,
+}
+
+impl RepeatCycle {
+    /// Cycle of `revolutions` in `days`, in lowest terms
+    ///
+    /// A cycle such as 34/6 is the 17/3 pattern flown twice; it is rejected
+    /// rather than reduced so the caller's intent stays explicit.
+    pub fn new(revolutions: u32, days: u32) -> Result<Self> {
+        if revolutions == 0 || days == 0 {
+            return Err(OrbitalMechanicsError::config_error(
+                "Repeat cycle needs at least one revolution and one day",
+            ));
+        }
+        let divisor = gcd(revolutions, days);
+        if divisor > 1 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Repeat cycle {}/{} is {}/{} repeated {} times",
+                revolutions,
+                days,
+                revolutions / divisor,
+                days / divisor,
+                divisor
+            )));
+        }
+        Ok(Self { revolutions, days })
+    }
+
+    pub fn revolutions_per_day(&self) -> f64 {
+        self.revolutions as f64 / self.days as f64
+    }
+
+    /// Equator spacing between adjacent tracks over the full cycle
+    pub fn track_spacing_km(&self) -> f64 {
+        TWO_PI * EARTH_RADIUS_KM / self.revolutions as f64
+    }
+
+    /// Semi-major axis of the circular orbit at `inclination_deg` that flies this cycle
+    pub fn semi_major_axis_km(&self, inclination_deg: f64) -> Result<f64> {
+        validation::validate_inclination(inclination_deg)?;
+        let target = self.revolutions_per_day();
+
+        // Kepler estimate from the solar day, then scale by Kepler's third law
+        // until the J2 revolutions per nodal day match
+        let n = target * TWO_PI / SOLAR_DAY_SECONDS;
+        let mut a = (EARTH_MU / (n * n)).cbrt();
+        for _ in 0..DESIGN_ITERATION_LIMIT {
+            if a < MIN_SEMI_MAJOR_AXIS_KM {
+                break;
+            }
+            let (nodal_period, nodal_day) = nodal_periods(&circular(a, inclination_deg));
+            let ratio = nodal_day / nodal_period / target;
+            a *= ratio.powf(2.0 / 3.0);
+            if (ratio - 1.0).abs() < 1e-12 {
+                break;
+            }
+        }
+
+        if !(MIN_SEMI_MAJOR_AXIS_KM..=MAX_SEMI_MAJOR_AXIS_KM).contains(&a) {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Repeat cycle {} needs a semi-major axis of {:.1} km, outside {:.1} to {:.1} km",
+                self, a, MIN_SEMI_MAJOR_AXIS_KM, MAX_SEMI_MAJOR_AXIS_KM
+            )));
+        }
+        Ok(a)
+    }
+
+    /// Circular orbit elements that fly this cycle at `inclination_deg`
+    pub fn design_orbit(&self, inclination_deg: f64) -> Result<OrbitalElements> {
+        OrbitalElements::new(
+            self.semi_major_axis_km(inclination_deg)?,
+            0.0,
+            inclination_deg,
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+}
+
+impl fmt::Display for RepeatCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.revolutions, self.days)
+    }
+}
+
+/// Find the shortest repeat cycle of up to `max_days` that closes within `tolerance_km`
+///
+/// The closure error is measured along the equator between the first
+/// ascending node and the one after the cycle.
+pub fn analyze_ground_track_repeat(
+    elements: &OrbitalElements,
+    max_days: u32,
+    tolerance_km: f64,
+) -> RepeatTrackAnalysis {
+    let (nodal_period_seconds, nodal_day_seconds) = nodal_periods(elements);
+    let revolutions_per_day = nodal_day_seconds / nodal_period_seconds;
+
+    let closing = (1..=max_days).find_map(|days| {
+        let revolutions = (revolutions_per_day * days as f64).round() as u32;
+        if revolutions == 0 {
+            return None;
+        }
+        let drift_days = revolutions as f64 / revolutions_per_day - days as f64;
+        let closure_error_km = drift_days.abs() * TWO_PI * EARTH_RADIUS_KM;
+        (closure_error_km <= tolerance_km)
+            .then_some((RepeatCycle { revolutions, days }, closure_error_km))
+    });
+
+    RepeatTrackAnalysis {
+        nodal_period_seconds,
+        nodal_day_seconds,
+        revolutions_per_day,
+        cycle: closing.map(|(cycle, _)| cycle),
+        closure_error_km: closing.map(|(_, error)| error),
+        track_spacing_km: closing.map(|(cycle, _)| cycle.track_spacing_km()),
+    }
+}
+
+/// Nodal period and nodal day in seconds
+fn nodal_periods(elements: &OrbitalElements) -> (f64, f64) {
+    let rates = j2_secular_rates(&Elements::from(&elements.to_radians()));
+    let nodal_period = TWO_PI / (rates.argument_of_perigee_rad_s + rates.mean_anomaly_rad_s);
+    let nodal_day = TWO_PI / (EARTH_ROTATION_RATE - rates.raan_rad_s);
+    (nodal_period, nodal_day)
+}
+
+fn circular(semi_major_axis_km: f64, inclination_deg: f64) -> OrbitalElements {
+    OrbitalElements {
+        semi_major_axis_km,
+        eccentricity: 0.0,
+        inclination_deg,
+        raan_deg: 0.0,
+        argument_of_perigee_deg: 0.0,
+        mean_anomaly_deg: 0.0,
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_landsat_repeat_design_and_detection() {
+        // Landsat 8: 233 revolutions in 16 days at 98.2°, mean a = 7077.7 km
+        let cycle = RepeatCycle::new(233, 16).unwrap();
+        let a = cycle.semi_major_axis_km(98.2).unwrap();
+        assert!((a - 7077.7).abs() < 1.0, "a = {}", a);
+
+        let elements = cycle.design_orbit(98.2).unwrap();
+        let analysis = analyze_ground_track_repeat(&elements, 30, 1.0);
+        assert_eq!(analysis.cycle, Some(cycle));
+        assert!(analysis.closure_error_km.unwrap() < 1e-3);
+        assert!((analysis.track_spacing_km.unwrap() - 172.0).abs() < 1.0);
+
+        // Nudging the orbit 2 km up breaks the 16-day closure
+        let mut detuned = elements.clone();
+        detuned.semi_major_axis_km += 2.0;
+        let analysis = analyze_ground_track_repeat(&detuned, 16, 1.0);
+        assert_eq!(analysis.cycle, None);
+    }
+
+    #[test]
+    fn test_repeat_cycle_validation() {
+        let cycle = RepeatCycle::new(17, 3).unwrap();
+        assert_eq!(cycle.to_string(), "17/3");
+        assert!(cycle.semi_major_axis_km(55.0).is_ok());
+
+        assert!(RepeatCycle::new(34, 6).is_err());
+        assert!(RepeatCycle::new(0, 1).is_err());
+        // 20 revolutions a day would put the orbit underground
+        assert!(RepeatCycle::new(20, 1)
+            .unwrap()
+            .semi_major_axis_km(55.0)
+            .is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! REST API for pass prediction
+//!
+//! Serves read-only queries against an `OrbitalMechanicsEngine` for teams
+//! that want pass times without linking the crate or its bindings:
+//!
+//! - `GET /satellites`: the constellation's orbits
+//! - `GET /passes?start=..&hours=..&satellite_id=..&station_id=..`: passes
+//!   over `hours` (default 24) from `start` (default now), optionally for one
+//!   satellite or station
+//! - `GET /coverage?epoch=..&resolution_deg=..&min_elevation_deg=..`: the
+//!   coverage grid at `epoch` (default now); the computation stops if the
+//!   client disconnects first
+//! - `GET /link-budget?satellite_id=..&station_id=..&time=..`: the FSO link
+//!   budget at `time` (default now), `null` while the satellite is out of view
+//!
+//! Every request carries an API key in the `x-api-key` header and counts
+//! against that key's quota for the current minute. Unknown keys get 401,
+//! keys over quota get 429 with `Retry-After`, and bad queries get 400 with a
+//! JSON `{"error": ..}` body.
+//!
+//! With the `openapi` feature, `openapi()` documents these endpoints and
+//! their response schemas, and the router serves the document without a key
+//! at `/openapi.json` for client SDK generators.
+
+use crate::coverage_grid::{CoverageGrid, CoverageGridConfig};
+use crate::error::{ErrorKind, OrbitalMechanicsError, Result};
+use crate::fso_analysis::FsoLinkQuality;
+use crate::orbit::SatelliteOrbit;
+use crate::progress::CancellationToken;
+use crate::trace_targets;
+use crate::visibility::VisibilityWindow;
+use crate::OrbitalMechanicsEngine;
+use axum::extract::{Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Keys, quotas and query limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestApiConfig {
+    pub api_keys: HashSet<String>,
+    /// Requests each key may make per minute
+    pub requests_per_minute: u32,
+    /// Longest pass search a request may ask for
+    pub max_pass_hours: f64,
+    /// Finest coverage grid a request may ask for
+    pub min_coverage_resolution_deg: f64,
+}
+
+/// Fixed-window request counter per API key
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    /// Start of each key's current window and requests made in it
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+/// Query of `GET /passes`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct PassQuery {
+    /// Start of the search; now if omitted
+    pub start: Option<DateTime<Utc>>,
+    /// Length of the search; 24 hours if omitted
+    pub hours: Option<f64>,
+    /// Only passes of this satellite
+    pub satellite_id: Option<String>,
+    /// Only passes over this station
+    pub station_id: Option<String>,
+}
+
+/// Query of `GET /coverage`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct CoverageQuery {
+    /// Time of the grid; now if omitted
+    pub epoch: Option<DateTime<Utc>>,
+    /// Cell size; 1° if omitted
+    pub resolution_deg: Option<f64>,
+    /// Elevation mask; the engine default if omitted
+    pub min_elevation_deg: Option<f64>,
+}
+
+/// Query of `GET /link-budget`
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct LinkBudgetQuery {
+    pub satellite_id: String,
+    pub station_id: String,
+    /// Time of the link budget; now if omitted
+    pub time: Option<DateTime<Utc>>,
+}
+
+/// Body of every error response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// Error response with a JSON body
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+    /// Seconds until the key's quota resets, for 429s
+    pub retry_after_seconds: Option<u64>,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    engine: Arc<OrbitalMechanicsEngine>,
+    config: Arc<RestApiConfig>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl Default for RestApiConfig {
+    fn default() -> Self {
+        Self {
+            api_keys: HashSet::new(),
+            requests_per_minute: 60,
+            max_pass_hours: 168.0,
+            min_coverage_resolution_deg: 0.5,
+        }
+    }
+}
+
+impl RestApiConfig {
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys.insert(key.into());
+        self
+    }
+
+    pub fn with_requests_per_minute(mut self, requests_per_minute: u32) -> Self {
+        self.requests_per_minute = requests_per_minute;
+        self
+    }
+}
+
+impl RateLimiter {
+    /// Limiter allowing `limit` requests per key in each `window`
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a request from `key` at `now`
+    ///
+    /// Returns the wait until the key's window resets if it is over quota.
+    pub fn check(&self, key: &str, now: Instant) -> std::result::Result<(), Duration> {
+        let mut windows = self.windows.lock().unwrap();
+        let (start, count) = windows.entry(key.to_string()).or_insert((now, 0));
+        let elapsed = now.saturating_duration_since(*start);
+        if elapsed >= self.window {
+            *start = now;
+            *count = 0;
+        } else if *count >= self.limit {
+            return Err(self.window - elapsed);
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            retry_after_seconds: None,
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+impl From<OrbitalMechanicsError> for ApiError {
+    fn from(error: OrbitalMechanicsError) -> Self {
+        let status = match error.kind() {
+            ErrorKind::Config | ErrorKind::InvalidElements | ErrorKind::Time => {
+                StatusCode::BAD_REQUEST
+            }
+            ErrorKind::NotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(ErrorBody {
+            error: self.message,
+        });
+        match self.retry_after_seconds {
+            Some(seconds) => (
+                self.status,
+                [(header::RETRY_AFTER, seconds.to_string())],
+                body,
+            )
+                .into_response(),
+            None => (self.status, body).into_response(),
+        }
+    }
+}
+
+/// Routes serving `engine`, behind API-key auth and rate limiting
+pub fn router(engine: Arc<OrbitalMechanicsEngine>, config: RestApiConfig) -> Router {
+    let state = ApiState {
+        limiter: Arc::new(RateLimiter::new(
+            config.requests_per_minute,
+            Duration::from_secs(60),
+        )),
+        engine,
+        config: Arc::new(config),
+    };
+    let router = Router::new()
+        .route("/satellites", get(satellites))
+        .route("/passes", get(passes))
+        .route("/coverage", get(coverage))
+        .route("/link-budget", get(link_budget))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize));
+    // Routed after the auth layer, so no key is needed
+    #[cfg(feature = "openapi")]
+    let router = router.route("/openapi.json", get(openapi_document));
+    router.with_state(state)
+}
+
+/// OpenAPI document for the routes `router` serves
+#[cfg(feature = "openapi")]
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    use utoipa::OpenApi;
+    ApiDoc::openapi()
+}
+
+/// Serve the API on `address` until the server fails
+pub async fn serve(
+    engine: Arc<OrbitalMechanicsEngine>,
+    address: SocketAddr,
+    config: RestApiConfig,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    tracing::info!(
+        target: trace_targets::ENGINE,
+        %address,
+        keys = config.api_keys.len(),
+        "Pass prediction API listening"
+    );
+    axum::serve(listener, router(engine, config)).await?;
+    Ok(())
+}
+
+async fn authorize(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> std::result::Result<Response, ApiError> {
+    let key = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| state.config.api_keys.contains(*key))
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Missing or unknown API key"))?;
+    if let Err(wait) = state.limiter.check(key, Instant::now()) {
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            path = %request.uri().path(),
+            "API key over its request quota"
+        );
+        return Err(ApiError {
+            retry_after_seconds: Some(wait.as_secs_f64().ceil() as u64),
+            ..ApiError::new(StatusCode::TOO_MANY_REQUESTS, "Request quota exceeded")
+        });
+    }
+    Ok(next.run(request).await)
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/satellites",
+    responses(
+        (status = 200, description = "Orbits of the constellation", body = Vec<SatelliteOrbit>),
+        (status = 401, description = "Missing or unknown API key", body = ErrorBody),
+        (status = 429, description = "Request quota exceeded", body = ErrorBody),
+    ),
+    security(("api_key" = []))
+))]
+async fn satellites(State(state): State<ApiState>) -> Json<Vec<SatelliteOrbit>> {
+    Json(state.engine.constellation().satellites().cloned().collect())
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/passes",
+    params(PassQuery),
+    responses(
+        (status = 200, description = "Passes in the search", body = Vec<VisibilityWindow>),
+        (status = 400, description = "Invalid query", body = ErrorBody),
+        (status = 401, description = "Missing or unknown API key", body = ErrorBody),
+        (status = 404, description = "Unknown satellite or station", body = ErrorBody),
+        (status = 429, description = "Request quota exceeded", body = ErrorBody),
+    ),
+    security(("api_key" = []))
+))]
+async fn passes(
+    State(state): State<ApiState>,
+    Query(query): Query<PassQuery>,
+) -> std::result::Result<Json<Vec<VisibilityWindow>>, ApiError> {
+    let start = query.start.unwrap_or_else(Utc::now);
+    let hours = query.hours.unwrap_or(DEFAULT_PASS_HOURS);
+    if !hours.is_finite() || hours <= 0.0 || hours > state.config.max_pass_hours {
+        return Err(ApiError::bad_request(format!(
+            "hours must be in (0, {}], got {}",
+            state.config.max_pass_hours, hours
+        )));
+    }
+    if let Some(satellite_id) = &query.satellite_id {
+        state
+            .engine
+            .constellation()
+            .get_satellite(satellite_id)
+            .ok_or(OrbitalMechanicsError::SatelliteNotFound(
+                satellite_id.clone(),
+            ))?;
+    }
+    if let Some(station_id) = &query.station_id {
+        state
+            .engine
+            .ground_stations()
+            .get_station(station_id)
+            .ok_or(OrbitalMechanicsError::GroundStationNotFound(
+                station_id.clone(),
+            ))?;
+    }
+
+    let engine = state.engine.clone();
+    let windows = blocking(move || engine.calculate_all_visibility_windows(start, hours)).await?;
+    Ok(Json(
+        windows
+            .into_iter()
+            .filter(|w| {
+                query
+                    .satellite_id
+                    .as_ref()
+                    .is_none_or(|id| &w.satellite_id == id)
+            })
+            .filter(|w| {
+                query
+                    .station_id
+                    .as_ref()
+                    .is_none_or(|id| &w.station_id == id)
+            })
+            .collect(),
+    ))
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/coverage",
+    params(CoverageQuery),
+    responses(
+        (status = 200, description = "Visible satellite counts per cell", body = CoverageGrid),
+        (status = 400, description = "Invalid query", body = ErrorBody),
+        (status = 401, description = "Missing or unknown API key", body = ErrorBody),
+        (status = 429, description = "Request quota exceeded", body = ErrorBody),
+    ),
+    security(("api_key" = []))
+))]
+async fn coverage(
+    State(state): State<ApiState>,
+    Query(query): Query<CoverageQuery>,
+) -> std::result::Result<Json<CoverageGrid>, ApiError> {
+    let defaults = CoverageGridConfig::default();
+    let config = CoverageGridConfig {
+        resolution_deg: query.resolution_deg.unwrap_or(defaults.resolution_deg),
+        min_elevation_deg: query
+            .min_elevation_deg
+            .unwrap_or(defaults.min_elevation_deg),
+        ..defaults
+    };
+    if config.resolution_deg.is_nan()
+        || config.resolution_deg < state.config.min_coverage_resolution_deg
+    {
+        return Err(ApiError::bad_request(format!(
+            "resolution_deg must be at least {}, got {}",
+            state.config.min_coverage_resolution_deg, config.resolution_deg
+        )));
+    }
+
+    let epoch = query.epoch.unwrap_or_else(Utc::now);
+    let engine = state.engine.clone();
+    let cancel = CancelOnDrop(CancellationToken::new());
+    let token = cancel.0.clone();
+    let grid = blocking(move || engine.coverage_grid_with_progress(epoch, &config, &token)).await?;
+    Ok(Json(grid))
+}
+
+/// Cancels a blocking computation when its request is dropped
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/link-budget",
+    params(LinkBudgetQuery),
+    responses(
+        (
+            status = 200,
+            description = "FSO link budget, null while out of view",
+            body = Option<FsoLinkQuality>
+        ),
+        (status = 401, description = "Missing or unknown API key", body = ErrorBody),
+        (status = 404, description = "Unknown satellite or station", body = ErrorBody),
+        (status = 429, description = "Request quota exceeded", body = ErrorBody),
+    ),
+    security(("api_key" = []))
+))]
+async fn link_budget(
+    State(state): State<ApiState>,
+    Query(query): Query<LinkBudgetQuery>,
+) -> std::result::Result<Json<Option<FsoLinkQuality>>, ApiError> {
+    let time = query.time.unwrap_or_else(Utc::now);
+    let link = state
+        .engine
+        .analyze_fso_link(&query.satellite_id, &query.station_id, time)?;
+    Ok(Json(link))
+}
+
+#[cfg(feature = "openapi")]
+async fn openapi_document() -> Json<utoipa::openapi::OpenApi> {
+    Json(openapi())
+}
+
+#[cfg(feature = "openapi")]
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    info(title = "Orbital pass prediction API"),
+    paths(satellites, passes, coverage, link_budget),
+    components(schemas(ErrorBody)),
+    modifiers(&ApiKeyAuth)
+)]
+struct ApiDoc;
+
+/// Declares the `x-api-key` header scheme the paths require
+#[cfg(feature = "openapi")]
+struct ApiKeyAuth;
+
+#[cfg(feature = "openapi")]
+impl utoipa::Modify for ApiKeyAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(API_KEY_HEADER))),
+            );
+    }
+}
+
+/// Run an engine computation off the async workers
+async fn blocking<T, F>(compute: F) -> std::result::Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(compute)
+        .await
+        .map_err(|error| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?
+        .map_err(ApiError::from)
+}
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+const DEFAULT_PASS_HOURS: f64 = 24.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let t0 = Instant::now();
+        assert!(limiter.check("team-a", t0).is_ok());
+        assert!(limiter.check("team-a", t0 + Duration::from_secs(1)).is_ok());
+        let wait = limiter
+            .check("team-a", t0 + Duration::from_secs(10))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(50));
+        // Quotas are per key and reset with the window
+        assert!(limiter
+            .check("team-b", t0 + Duration::from_secs(10))
+            .is_ok());
+        assert!(limiter
+            .check("team-a", t0 + Duration::from_secs(60))
+            .is_ok());
+
+        let not_found = ApiError::from(OrbitalMechanicsError::SatelliteNotFound("X".to_string()));
+        assert_eq!(not_found.status, StatusCode::NOT_FOUND);
+        let invalid = ApiError::from(OrbitalMechanicsError::config_error("bad"));
+        assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "openapi")]
+    #[test]
+    fn test_openapi_document() {
+        let document = serde_json::to_value(openapi()).unwrap();
+        for path in ["/satellites", "/passes", "/coverage", "/link-budget"] {
+            assert!(document["paths"][path]["get"].is_object(), "{}", path);
+        }
+        let schemas = &document["components"]["schemas"];
+        for schema in [
+            "VisibilityWindow",
+            "FsoLinkQuality",
+            "CoverageGrid",
+            "ErrorBody",
+        ] {
+            assert!(schemas[schema].is_object(), "{}", schema);
+        }
+        assert_eq!(
+            document["components"]["securitySchemes"]["api_key"]["name"],
+            API_KEY_HEADER
+        );
+    }
+}
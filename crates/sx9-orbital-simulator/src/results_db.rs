@@ -0,0 +1,473 @@
+//! SQLite store of analysis runs
+//!
+//! Persists visibility windows, FSO link budgets and coverage statistics
+//! together with the hash of the configuration that produced them, so runs
+//! can be compared against each other and traced back to their inputs.
+//! Timestamps are stored as RFC 3339 UTC text with microsecond precision.
+
+use crate::config::ConstellationConfig;
+use crate::constellation::ConstellationCoverage;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::fso_analysis::FsoLinkQuality;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+impl From<rusqlite::Error> for OrbitalMechanicsError {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::DatabaseError(err.to_string())
+    }
+}
+
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+    CREATE TABLE IF NOT EXISTS runs (
+        run_id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        config_hash TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        coverage TEXT
+    );
+    CREATE INDEX IF NOT EXISTS runs_config_hash ON runs (config_hash);
+    CREATE TABLE IF NOT EXISTS visibility_windows (
+        run_id INTEGER NOT NULL REFERENCES runs (run_id) ON DELETE CASCADE,
+        satellite_id TEXT NOT NULL,
+        station_id TEXT NOT NULL,
+        start_time TEXT NOT NULL,
+        end_time TEXT NOT NULL,
+        duration_seconds REAL NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS visibility_windows_run ON visibility_windows (run_id);
+    CREATE TABLE IF NOT EXISTS link_budgets (
+        run_id INTEGER NOT NULL REFERENCES runs (run_id) ON DELETE CASCADE,
+        satellite_id TEXT NOT NULL,
+        station_id TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        link_margin_db REAL NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS link_budgets_run ON link_budgets (run_id);
+";
+
+const RUN_COLUMNS: &str = "
+    SELECT r.run_id, r.name, r.config_hash, r.created_at,
+        (SELECT COUNT(*) FROM visibility_windows w WHERE w.run_id = r.run_id),
+        (SELECT COUNT(*) FROM link_budgets l WHERE l.run_id = r.run_id)
+    FROM runs r
+";
+
+/// Results of one analysis run, ready to be recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisRun {
+    pub name: String,
+    /// `ConstellationConfig::content_hash` of the inputs
+    pub config_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub visibility_windows: Vec<VisibilityWindow>,
+    pub link_budgets: Vec<FsoLinkQuality>,
+    pub coverage: Option<ConstellationCoverage>,
+}
+
+/// Summary of a stored run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub run_id: i64,
+    pub name: String,
+    pub config_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub window_count: usize,
+    pub link_budget_count: usize,
+}
+
+/// Differences between two stored runs (candidate minus baseline)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunComparison {
+    pub baseline: RunRecord,
+    pub candidate: RunRecord,
+    /// Both runs were produced from the same configuration
+    pub same_config: bool,
+    pub window_count_delta: i64,
+    pub contact_seconds_delta: f64,
+    /// `None` unless both runs stored link budgets
+    pub mean_link_margin_delta_db: Option<f64>,
+    /// `None` unless both runs stored coverage statistics
+    pub global_coverage_percent_delta: Option<f64>,
+}
+
+/// SQLite-backed store of analysis runs
+pub struct ResultsDb {
+    connection: Connection,
+}
+
+impl AnalysisRun {
+    /// Empty run for the given configuration, timestamped now
+    pub fn new(name: impl Into<String>, config: &ConstellationConfig) -> Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            config_hash: config.content_hash()?,
+            created_at: Utc::now(),
+            visibility_windows: Vec::new(),
+            link_budgets: Vec::new(),
+            coverage: None,
+        })
+    }
+
+    pub fn with_visibility_windows(mut self, windows: Vec<VisibilityWindow>) -> Self {
+        self.visibility_windows = windows;
+        self
+    }
+
+    pub fn with_link_budgets(mut self, link_budgets: Vec<FsoLinkQuality>) -> Self {
+        self.link_budgets = link_budgets;
+        self
+    }
+
+    pub fn with_coverage(mut self, coverage: ConstellationCoverage) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+}
+
+impl ResultsDb {
+    /// Open or create a database file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        Self::initialize(Connection::open(path)?).for_path(path)
+    }
+
+    /// Database that lives only as long as this value
+    pub fn open_in_memory() -> Result<Self> {
+        Self::initialize(Connection::open_in_memory()?)
+    }
+
+    fn initialize(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Store a run atomically, returning its ID
+    pub fn record_run(&mut self, run: &AnalysisRun) -> Result<i64> {
+        let transaction = self.connection.transaction()?;
+        let coverage = run
+            .coverage
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        transaction.execute(
+            "INSERT INTO runs (name, config_hash, created_at, coverage) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                run.name,
+                run.config_hash,
+                format_time(run.created_at),
+                coverage
+            ],
+        )?;
+        let run_id = transaction.last_insert_rowid();
+
+        {
+            let mut insert = transaction.prepare(
+                "INSERT INTO visibility_windows
+                    (run_id, satellite_id, station_id, start_time, end_time, duration_seconds, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for window in &run.visibility_windows {
+                insert.execute(params![
+                    run_id,
+                    window.satellite_id,
+                    window.station_id,
+                    format_time(window.start_time),
+                    format_time(window.end_time),
+                    window.duration_seconds,
+                    serde_json::to_string(window)?
+                ])?;
+            }
+
+            let mut insert = transaction.prepare(
+                "INSERT INTO link_budgets
+                    (run_id, satellite_id, station_id, timestamp, link_margin_db, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for budget in &run.link_budgets {
+                insert.execute(params![
+                    run_id,
+                    budget.satellite_id,
+                    budget.station_id,
+                    format_time(budget.timestamp),
+                    budget.link_margin_db,
+                    serde_json::to_string(budget)?
+                ])?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(run_id)
+    }
+
+    /// Summary of one run
+    pub fn run(&self, run_id: i64) -> Result<RunRecord> {
+        let sql = format!("{} WHERE r.run_id = ?1", RUN_COLUMNS);
+        let row = self
+            .connection
+            .query_row(&sql, params![run_id], RawRunRecord::from_row)
+            .optional()?
+            .ok_or_else(|| {
+                OrbitalMechanicsError::DatabaseError(format!("No analysis run with ID {}", run_id))
+            })?;
+        row.parse()
+    }
+
+    /// Every stored run, oldest first
+    pub fn runs(&self) -> Result<Vec<RunRecord>> {
+        self.query_runs(&format!("{} ORDER BY r.run_id", RUN_COLUMNS), params![])
+    }
+
+    /// Runs produced from the configuration with this hash, oldest first
+    pub fn runs_for_config(&self, config_hash: &str) -> Result<Vec<RunRecord>> {
+        self.query_runs(
+            &format!("{} WHERE r.config_hash = ?1 ORDER BY r.run_id", RUN_COLUMNS),
+            params![config_hash],
+        )
+    }
+
+    /// Visibility windows of a run, ordered by start time
+    pub fn visibility_windows(&self, run_id: i64) -> Result<Vec<VisibilityWindow>> {
+        self.query_data(
+            "SELECT data FROM visibility_windows WHERE run_id = ?1 ORDER BY start_time, rowid",
+            run_id,
+        )
+    }
+
+    /// Link budgets of a run, ordered by timestamp
+    pub fn link_budgets(&self, run_id: i64) -> Result<Vec<FsoLinkQuality>> {
+        self.query_data(
+            "SELECT data FROM link_budgets WHERE run_id = ?1 ORDER BY timestamp, rowid",
+            run_id,
+        )
+    }
+
+    /// Coverage statistics of a run, if any were recorded
+    pub fn coverage(&self, run_id: i64) -> Result<Option<ConstellationCoverage>> {
+        self.run(run_id)?;
+        let coverage: Option<String> = self.connection.query_row(
+            "SELECT coverage FROM runs WHERE run_id = ?1",
+            params![run_id],
+            |row| row.get(0),
+        )?;
+        Ok(coverage
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?)
+    }
+
+    /// Compare a candidate run against a baseline
+    pub fn compare_runs(&self, baseline_id: i64, candidate_id: i64) -> Result<RunComparison> {
+        let baseline = self.run(baseline_id)?;
+        let candidate = self.run(candidate_id)?;
+
+        let contact_seconds = |run_id: i64| -> Result<f64> {
+            Ok(self.connection.query_row(
+                "SELECT COALESCE(SUM(duration_seconds), 0.0) FROM visibility_windows WHERE run_id = ?1",
+                params![run_id],
+                |row| row.get(0),
+            )?)
+        };
+        let mean_link_margin = |run_id: i64| -> Result<Option<f64>> {
+            Ok(self.connection.query_row(
+                "SELECT AVG(link_margin_db) FROM link_budgets WHERE run_id = ?1",
+                params![run_id],
+                |row| row.get(0),
+            )?)
+        };
+        let global_coverage = |run_id: i64| -> Result<Option<f64>> {
+            Ok(self
+                .coverage(run_id)?
+                .map(|c| c.latitude_coverage.global_coverage_percent))
+        };
+
+        Ok(RunComparison {
+            same_config: baseline.config_hash == candidate.config_hash,
+            window_count_delta: candidate.window_count as i64 - baseline.window_count as i64,
+            contact_seconds_delta: contact_seconds(candidate_id)? - contact_seconds(baseline_id)?,
+            mean_link_margin_delta_db: mean_link_margin(candidate_id)?
+                .zip(mean_link_margin(baseline_id)?)
+                .map(|(candidate, baseline)| candidate - baseline),
+            global_coverage_percent_delta: global_coverage(candidate_id)?
+                .zip(global_coverage(baseline_id)?)
+                .map(|(candidate, baseline)| candidate - baseline),
+            baseline,
+            candidate,
+        })
+    }
+
+    /// Remove a run and everything recorded with it
+    pub fn delete_run(&self, run_id: i64) -> Result<()> {
+        let deleted = self
+            .connection
+            .execute("DELETE FROM runs WHERE run_id = ?1", params![run_id])?;
+        if deleted == 0 {
+            return Err(OrbitalMechanicsError::DatabaseError(format!(
+                "No analysis run with ID {}",
+                run_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn query_runs(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<RunRecord>> {
+        let mut statement = self.connection.prepare(sql)?;
+        let rows = statement
+            .query_map(params, RawRunRecord::from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter().map(RawRunRecord::parse).collect()
+    }
+
+    fn query_data<T: serde::de::DeserializeOwned>(&self, sql: &str, run_id: i64) -> Result<Vec<T>> {
+        self.run(run_id)?;
+        let mut statement = self.connection.prepare(sql)?;
+        let rows = statement
+            .query_map(params![run_id], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.iter()
+            .map(|json| Ok(serde_json::from_str(json)?))
+            .collect()
+    }
+}
+
+/// Run row before its timestamp is parsed
+struct RawRunRecord {
+    run_id: i64,
+    name: String,
+    config_hash: String,
+    created_at: String,
+    window_count: i64,
+    link_budget_count: i64,
+}
+
+impl RawRunRecord {
+    fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            run_id: row.get(0)?,
+            name: row.get(1)?,
+            config_hash: row.get(2)?,
+            created_at: row.get(3)?,
+            window_count: row.get(4)?,
+            link_budget_count: row.get(5)?,
+        })
+    }
+
+    fn parse(self) -> Result<RunRecord> {
+        Ok(RunRecord {
+            run_id: self.run_id,
+            name: self.name,
+            config_hash: self.config_hash,
+            created_at: DateTime::parse_from_rfc3339(&self.created_at)?.with_timezone(&Utc),
+            window_count: self.window_count as usize,
+            link_budget_count: self.link_budget_count as usize,
+        })
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visibility::PassType;
+    use chrono::Duration;
+
+    fn window(start: DateTime<Utc>, minutes: i64) -> VisibilityWindow {
+        VisibilityWindow {
+            satellite_id: "DB-01".to_string(),
+            station_id: "GS-001".to_string(),
+            start_time: start,
+            end_time: start + Duration::minutes(minutes),
+            duration_seconds: minutes as f64 * 60.0,
+            max_elevation_time: start + Duration::minutes(minutes / 2),
+            max_elevation_deg: 40.0,
+            min_range_km: 9000.0,
+            mean_range_km: 9000.0,
+            azimuth_span_deg: 0.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    fn link_budget(timestamp: DateTime<Utc>, link_margin_db: f64) -> FsoLinkQuality {
+        FsoLinkQuality {
+            satellite_id: "DB-01".to_string(),
+            station_id: "GS-001".to_string(),
+            timestamp,
+            elevation_angle_deg: 40.0,
+            azimuth_angle_deg: 120.0,
+            range_km: 9000.0,
+            atmospheric_transmission: 0.8,
+            link_margin_db,
+            estimated_throughput_gbps: 10.0,
+            weather_impact_factor: 1.0,
+            fade_db: None,
+            adaptive_optics_gain_db: None,
+            sky_background_penalty_db: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_compare_runs() {
+        let config = ConstellationConfig::laserlight_fso_meo();
+        let epoch = Utc::now();
+        let mut db = ResultsDb::open_in_memory().unwrap();
+
+        let baseline = AnalysisRun::new("baseline", &config)
+            .unwrap()
+            .with_visibility_windows(vec![window(epoch, 10)])
+            .with_link_budgets(vec![link_budget(epoch, 3.0)]);
+        let baseline_id = db.record_run(&baseline).unwrap();
+
+        let mut modified = config.clone();
+        modified.orbital_parameters.altitude_km += 100.0;
+        let candidate = AnalysisRun::new("higher", &modified)
+            .unwrap()
+            .with_visibility_windows(vec![
+                window(epoch + Duration::hours(2), 5),
+                window(epoch, 12),
+            ])
+            .with_link_budgets(vec![link_budget(epoch, 2.0), link_budget(epoch, 4.0)]);
+        let candidate_id = db.record_run(&candidate).unwrap();
+
+        let stored = db.visibility_windows(candidate_id).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].start_time, epoch);
+        assert_eq!(stored[1].duration_seconds, 300.0);
+        assert!(db.coverage(baseline_id).unwrap().is_none());
+
+        let hash = config.content_hash().unwrap();
+        assert_eq!(
+            hash,
+            ConstellationConfig::laserlight_fso_meo()
+                .content_hash()
+                .unwrap()
+        );
+        let runs = db.runs_for_config(&hash).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].name, "baseline");
+        assert_eq!(runs[0].link_budget_count, 1);
+
+        let comparison = db.compare_runs(baseline_id, candidate_id).unwrap();
+        assert!(!comparison.same_config);
+        assert_eq!(comparison.window_count_delta, 1);
+        assert!((comparison.contact_seconds_delta - 420.0).abs() < 1e-9);
+        assert_eq!(comparison.mean_link_margin_delta_db, Some(0.0));
+        assert!(comparison.global_coverage_percent_delta.is_none());
+
+        db.delete_run(candidate_id).unwrap();
+        assert_eq!(db.runs().unwrap().len(), 1);
+        assert!(db.link_budgets(candidate_id).is_err());
+        assert!(db.delete_run(candidate_id).is_err());
+    }
+}
@@ -0,0 +1,683 @@
+//! Multi-hop relay routing through the constellation
+//!
+//! At a given instant the constellation forms a link graph: ground-to-satellite
+//! links above the elevation mask and inter-satellite links (ISLs) with a clear
+//! line of sight within terminal range. Routes minimize one-way latency
+//! (light time plus per-satellite processing). Over a window, routes may also
+//! store data onboard and forward it once a later link appears.
+
+use crate::constants::*;
+use crate::coordinates::EarthModel;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Link graph parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingConfig {
+    /// Minimum elevation for ground links in degrees
+    pub min_elevation_deg: f64,
+    /// Maximum inter-satellite link range in km
+    pub max_isl_range_km: f64,
+    /// ISL line of sight must clear Earth by this altitude in km
+    pub isl_grazing_altitude_km: f64,
+    /// Switching delay added at every satellite on the path in ms
+    pub processing_delay_ms: f64,
+    /// Shape the ground stations sit on
+    #[serde(default)]
+    pub earth_model: EarthModel,
+}
+
+/// Endpoint of a hop
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RouteNode {
+    GroundStation(String),
+    Satellite(String),
+}
+
+/// One link traversal on a route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteHop {
+    pub from: RouteNode,
+    pub to: RouteNode,
+    /// Instant the link is used
+    pub time: DateTime<Utc>,
+    pub range_km: f64,
+    pub propagation_delay_ms: f64,
+    /// Switching delay at the receiving satellite (zero for ground)
+    pub processing_delay_ms: f64,
+}
+
+/// Path from a source to a destination ground station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+    pub departure_time: DateTime<Utc>,
+    pub hops: Vec<RouteHop>,
+    /// One-way latency in ms, including any time stored onboard
+    pub total_delay_ms: f64,
+}
+
+/// Time span searched by store-and-forward routing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RoutingWindow {
+    pub start_time: DateTime<Utc>,
+    pub duration_hours: f64,
+    /// Interval between link graph rebuilds in seconds
+    pub step_seconds: f64,
+}
+
+/// Link graph of the constellation at one instant
+pub struct RelayNetwork {
+    pub config: RoutingConfig,
+    pub time: DateTime<Utc>,
+    satellites: Vec<SatelliteState>,
+    isl: Vec<Vec<(usize, f64)>>,
+}
+
+/// Relay route planner
+pub struct RelayRouter {
+    pub config: RoutingConfig,
+}
+
+/// Shortest-delay tree over satellites
+struct DelayTree {
+    delay_ms: Vec<f64>,
+    previous: Vec<Option<usize>>,
+}
+
+#[derive(PartialEq)]
+struct QueueEntry {
+    delay_ms: f64,
+    satellite: usize,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            min_elevation_deg: defaults::MIN_ELEVATION_DEG,
+            max_isl_range_km: 30_000.0,
+            isl_grazing_altitude_km: 100.0,
+            processing_delay_ms: 1.0,
+            earth_model: EarthModel::Sphere,
+        }
+    }
+}
+
+impl RouteHop {
+    /// Propagation plus processing delay of this hop in ms
+    pub fn delay_ms(&self) -> f64 {
+        self.propagation_delay_ms + self.processing_delay_ms
+    }
+}
+
+impl Route {
+    /// Time the data reaches the destination
+    pub fn arrival_time(&self) -> DateTime<Utc> {
+        self.departure_time + Duration::microseconds((self.total_delay_ms * 1000.0) as i64)
+    }
+
+    /// Satellites traversed, in order
+    pub fn satellite_ids(&self) -> Vec<&str> {
+        self.hops
+            .iter()
+            .filter_map(|hop| match &hop.to {
+                RouteNode::Satellite(id) => Some(id.as_str()),
+                RouteNode::GroundStation(_) => None,
+            })
+            .collect()
+    }
+
+    /// Time spent stored onboard waiting for a link, in ms
+    pub fn storage_delay_ms(&self) -> f64 {
+        let in_flight: f64 = self.hops.iter().map(RouteHop::delay_ms).sum();
+        (self.total_delay_ms - in_flight).max(0.0)
+    }
+}
+
+impl RelayNetwork {
+    /// Propagate all satellites to `time` and build the link graph
+    pub fn build<'a>(
+        config: RoutingConfig,
+        satellites: impl IntoIterator<Item = &'a SatelliteOrbit>,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Self> {
+        let satellites = satellites
+            .into_iter()
+            .map(|satellite| {
+                propagator
+                    .propagate(satellite, time)
+                    .for_satellite(&satellite.satellite_id)
+                    .at_epoch(time)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let clearance_km = EARTH_RADIUS_KM + config.isl_grazing_altitude_km;
+        let mut isl = vec![Vec::new(); satellites.len()];
+        for i in 0..satellites.len() {
+            for j in (i + 1)..satellites.len() {
+                let a = Vector3::from(satellites[i].position_eci);
+                let b = Vector3::from(satellites[j].position_eci);
+                let range_km = (b - a).norm();
+                if range_km <= config.max_isl_range_km && segment_clearance_km(a, b) > clearance_km
+                {
+                    isl[i].push((j, range_km));
+                    isl[j].push((i, range_km));
+                }
+            }
+        }
+
+        Ok(Self {
+            config,
+            time,
+            satellites,
+            isl,
+        })
+    }
+
+    /// Satellite states the graph was built from
+    pub fn satellites(&self) -> &[SatelliteState] {
+        &self.satellites
+    }
+
+    /// Satellites in view of a station, with slant range in km
+    pub fn ground_links(&self, station: &GroundStation) -> Vec<(usize, f64)> {
+        self.satellites
+            .iter()
+            .enumerate()
+            .filter_map(|(i, state)| {
+                let look = state.look_angles_from_station_with_model(
+                    &self.config.earth_model,
+                    station.position.latitude_deg,
+                    station.position.longitude_deg,
+                    station.position.elevation_m,
+                );
+                (look.elevation_deg >= self.config.min_elevation_deg).then_some((i, look.range_km))
+            })
+            .collect()
+    }
+
+    /// Inter-satellite links of one satellite, with range in km
+    pub fn isl_links(&self, satellite: usize) -> &[(usize, f64)] {
+        &self.isl[satellite]
+    }
+
+    /// Minimum-latency route between two stations at this instant
+    pub fn route(&self, source: &GroundStation, destination: &GroundStation) -> Option<Route> {
+        let uplinks = self.ground_links(source);
+        let initial: Vec<(usize, f64)> = uplinks
+            .iter()
+            .map(|&(i, range_km)| (i, self.hop_delay_ms(range_km, true)))
+            .collect();
+        let tree = self.delay_tree(&initial);
+
+        let (last, downlink_range_km, total_delay_ms) = self.best_downlink(&tree, destination)?;
+
+        let chain = tree.chain(last);
+        let first = chain[0];
+        let uplink_range_km = uplinks
+            .iter()
+            .find(|(i, _)| *i == first)
+            .map(|&(_, range_km)| range_km)?;
+
+        let mut hops = vec![self.hop(
+            RouteNode::GroundStation(source.station_id.clone()),
+            first,
+            uplink_range_km,
+        )];
+        hops.extend(self.isl_hops(&chain));
+        hops.push(self.downlink_hop(last, destination, downlink_range_km));
+
+        Some(Route {
+            departure_time: self.time,
+            hops,
+            total_delay_ms,
+        })
+    }
+
+    /// One-way delay in ms from `source` to every satellite (`None` if unreachable)
+    pub fn satellite_delays_ms(&self, source: &GroundStation) -> Vec<Option<f64>> {
+        let initial: Vec<(usize, f64)> = self
+            .ground_links(source)
+            .into_iter()
+            .map(|(i, range_km)| (i, self.hop_delay_ms(range_km, true)))
+            .collect();
+
+        self.delay_tree(&initial)
+            .delay_ms
+            .into_iter()
+            .map(|delay| delay.is_finite().then_some(delay))
+            .collect()
+    }
+
+    /// Fastest delivery to `destination` given per-satellite delays
+    ///
+    /// Returns the serving satellite and the total one-way delay in ms.
+    pub fn ground_delay_ms(
+        &self,
+        satellite_delays_ms: &[Option<f64>],
+        destination: &GroundStation,
+    ) -> Option<(usize, f64)> {
+        self.ground_links(destination)
+            .into_iter()
+            .filter_map(|(i, range_km)| {
+                let delay = satellite_delays_ms.get(i).copied().flatten()?;
+                Some((i, delay + self.hop_delay_ms(range_km, false)))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Shortest-delay tree from satellites already holding the data
+    ///
+    /// `initial` gives the delay (ms) at which each starting satellite holds it.
+    fn delay_tree(&self, initial: &[(usize, f64)]) -> DelayTree {
+        let mut delay_ms = vec![f64::INFINITY; self.satellites.len()];
+        let mut previous = vec![None; self.satellites.len()];
+        let mut queue = BinaryHeap::new();
+
+        for &(satellite, delay) in initial {
+            if delay < delay_ms[satellite] {
+                delay_ms[satellite] = delay;
+                queue.push(QueueEntry {
+                    delay_ms: delay,
+                    satellite,
+                });
+            }
+        }
+
+        while let Some(QueueEntry {
+            delay_ms: delay,
+            satellite,
+        }) = queue.pop()
+        {
+            if delay > delay_ms[satellite] {
+                continue;
+            }
+            for &(next, range_km) in &self.isl[satellite] {
+                let candidate = delay + self.hop_delay_ms(range_km, true);
+                if candidate < delay_ms[next] {
+                    delay_ms[next] = candidate;
+                    previous[next] = Some(satellite);
+                    queue.push(QueueEntry {
+                        delay_ms: candidate,
+                        satellite: next,
+                    });
+                }
+            }
+        }
+
+        DelayTree { delay_ms, previous }
+    }
+
+    /// Best last satellite for reaching `destination`: (satellite, range, total delay)
+    fn best_downlink(
+        &self,
+        tree: &DelayTree,
+        destination: &GroundStation,
+    ) -> Option<(usize, f64, f64)> {
+        self.ground_links(destination)
+            .into_iter()
+            .filter(|(i, _)| tree.delay_ms[*i].is_finite())
+            .map(|(i, range_km)| {
+                (
+                    i,
+                    range_km,
+                    tree.delay_ms[i] + self.hop_delay_ms(range_km, false),
+                )
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+
+    fn hop_delay_ms(&self, range_km: f64, to_satellite: bool) -> f64 {
+        let propagation_ms = range_km * KM_TO_M / SPEED_OF_LIGHT * 1000.0;
+        if to_satellite {
+            propagation_ms + self.config.processing_delay_ms
+        } else {
+            propagation_ms
+        }
+    }
+
+    fn hop(&self, from: RouteNode, to: usize, range_km: f64) -> RouteHop {
+        RouteHop {
+            from,
+            to: RouteNode::Satellite(self.satellites[to].satellite_id.clone()),
+            time: self.time,
+            range_km,
+            propagation_delay_ms: self.hop_delay_ms(range_km, false),
+            processing_delay_ms: self.config.processing_delay_ms,
+        }
+    }
+
+    fn isl_hops(&self, chain: &[usize]) -> Vec<RouteHop> {
+        chain
+            .windows(2)
+            .map(|pair| {
+                let range_km = self.isl[pair[0]]
+                    .iter()
+                    .find(|(j, _)| *j == pair[1])
+                    .map(|(_, range_km)| *range_km)
+                    .unwrap_or_default();
+                self.hop(
+                    RouteNode::Satellite(self.satellites[pair[0]].satellite_id.clone()),
+                    pair[1],
+                    range_km,
+                )
+            })
+            .collect()
+    }
+
+    fn downlink_hop(&self, from: usize, destination: &GroundStation, range_km: f64) -> RouteHop {
+        RouteHop {
+            from: RouteNode::Satellite(self.satellites[from].satellite_id.clone()),
+            to: RouteNode::GroundStation(destination.station_id.clone()),
+            time: self.time,
+            range_km,
+            propagation_delay_ms: self.hop_delay_ms(range_km, false),
+            processing_delay_ms: 0.0,
+        }
+    }
+}
+
+impl DelayTree {
+    /// Satellites from the starting satellite to `last`
+    fn chain(&self, last: usize) -> Vec<usize> {
+        let mut chain = vec![last];
+        while let Some(previous) = self.previous[*chain.last().unwrap()] {
+            chain.push(previous);
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+impl RelayRouter {
+    /// Create router with default link parameters
+    pub fn new() -> Self {
+        Self::with_config(RoutingConfig::default())
+    }
+
+    /// Create router with custom link parameters
+    pub fn with_config(config: RoutingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Minimum-latency route between two stations at one instant
+    pub fn route_at<'a>(
+        &self,
+        satellites: impl IntoIterator<Item = &'a SatelliteOrbit>,
+        source: &GroundStation,
+        destination: &GroundStation,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Option<Route>> {
+        let network = RelayNetwork::build(self.config.clone(), satellites, time, propagator)?;
+        Ok(network.route(source, destination))
+    }
+
+    /// Earliest-arrival route departing at the window start, allowing store-and-forward
+    ///
+    /// The link graph is rebuilt every `step_seconds`. Satellites that have
+    /// received the data keep it and forward it over links that appear in later
+    /// steps. Returns `None` if the destination is not reached within the window.
+    pub fn route_over_window(
+        &self,
+        satellites: &[SatelliteOrbit],
+        source: &GroundStation,
+        destination: &GroundStation,
+        window: &RoutingWindow,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<Option<Route>> {
+        let RoutingWindow {
+            start_time,
+            duration_hours,
+            step_seconds,
+        } = *window;
+        if !step_seconds.is_finite() || step_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Routing step must be positive, got {} s",
+                step_seconds
+            )));
+        }
+
+        let end_time = start_time + Duration::seconds((duration_hours * 3600.0) as i64);
+        let step = Duration::milliseconds((step_seconds * 1000.0).round().max(1.0) as i64);
+
+        // Hops that delivered the data to each satellite, once it holds it
+        let mut held: Vec<Option<Vec<RouteHop>>> = vec![None; satellites.len()];
+        let mut time = start_time;
+
+        while time <= end_time {
+            let network = RelayNetwork::build(self.config.clone(), satellites, time, propagator)?;
+            let wait_ms = (time - start_time).num_milliseconds() as f64;
+
+            let uplinks = network.ground_links(source);
+            let mut initial: Vec<(usize, f64)> = held
+                .iter()
+                .enumerate()
+                .filter(|(_, hops)| hops.is_some())
+                .map(|(i, _)| (i, 0.0))
+                .collect();
+            initial.extend(
+                uplinks
+                    .iter()
+                    .filter(|(i, _)| held[*i].is_none())
+                    .map(|&(i, range_km)| (i, network.hop_delay_ms(range_km, true))),
+            );
+            let tree = network.delay_tree(&initial);
+
+            // Path to every satellite reached in this step
+            let mut reached: Vec<Option<Vec<RouteHop>>> = vec![None; satellites.len()];
+            for (satellite, slot) in reached.iter_mut().enumerate() {
+                if !tree.delay_ms[satellite].is_finite() {
+                    continue;
+                }
+                let chain = tree.chain(satellite);
+                let first = chain[0];
+                let mut hops = match &held[first] {
+                    Some(hops) => hops.clone(),
+                    None => {
+                        let range_km = uplinks
+                            .iter()
+                            .find(|(i, _)| *i == first)
+                            .map(|(_, range_km)| *range_km)
+                            .unwrap_or_default();
+                        vec![network.hop(
+                            RouteNode::GroundStation(source.station_id.clone()),
+                            first,
+                            range_km,
+                        )]
+                    }
+                };
+                hops.extend(network.isl_hops(&chain));
+                *slot = Some(hops);
+            }
+
+            if let Some((last, range_km, delay_ms)) = network.best_downlink(&tree, destination) {
+                let mut hops = reached[last].take().unwrap_or_default();
+                hops.push(network.downlink_hop(last, destination, range_km));
+                return Ok(Some(Route {
+                    departure_time: start_time,
+                    hops,
+                    total_delay_ms: wait_ms + delay_ms,
+                }));
+            }
+
+            for (slot, hops) in held.iter_mut().zip(reached) {
+                if slot.is_none() {
+                    *slot = hops;
+                }
+            }
+            time += step;
+        }
+
+        Ok(None)
+    }
+}
+
+impl Default for RelayRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Eq for QueueEntry {}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for a min-heap on delay
+        other.delay_ms.total_cmp(&self.delay_ms)
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Closest approach of the segment `a`-`b` to Earth's center in km
+fn segment_clearance_km(a: Vector3<f64>, b: Vector3<f64>) -> f64 {
+    let d = b - a;
+    let length_sq = d.norm_squared();
+    let t = if length_sq > 0.0 {
+        (-a.dot(&d) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + d * t).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::StationPosition;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+
+    fn station(id: &str, longitude_deg: f64) -> GroundStation {
+        GroundStation {
+            station_id: id.to_string(),
+            name: id.to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        }
+    }
+
+    /// Equatorial ring of satellites at 8000 km altitude
+    fn ring(count: usize, epoch: DateTime<Utc>) -> Vec<SatelliteOrbit> {
+        (0..count)
+            .map(|i| {
+                let anomaly = i as f64 * 360.0 / count as f64;
+                let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, anomaly).unwrap();
+                SatelliteOrbit::new(format!("SAT-{}", i), format!("Sat {}", i), elements, epoch)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_route_through_isl_chain() {
+        let epoch = Utc::now();
+        let satellites = ring(6, epoch);
+        let source = station("GS-A", 0.0);
+        let destination = station("GS-B", 120.0);
+
+        let route = RelayRouter::new()
+            .route_at(
+                &satellites,
+                &source,
+                &destination,
+                epoch,
+                &KeplerianPropagator::new(),
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            route.hops.first().unwrap().from,
+            RouteNode::GroundStation("GS-A".to_string())
+        );
+        assert_eq!(
+            route.hops.last().unwrap().to,
+            RouteNode::GroundStation("GS-B".to_string())
+        );
+        // Direct ISL skips the intermediate satellite
+        assert_eq!(route.satellite_ids(), vec!["SAT-0", "SAT-2"]);
+
+        let hop_sum: f64 = route.hops.iter().map(RouteHop::delay_ms).sum();
+        assert!((route.total_delay_ms - hop_sum).abs() < 1e-9);
+        assert!(route.storage_delay_ms() < 1e-9);
+
+        // Uplink straight up through 8000 km of range
+        let uplink = &route.hops[0];
+        assert!((uplink.range_km - 8000.0).abs() < 1.0);
+        assert!((uplink.propagation_delay_ms - 26.685).abs() < 0.01);
+
+        // Shorter ISL terminals force the path around the ring
+        let short_range = RelayRouter::with_config(RoutingConfig {
+            max_isl_range_km: 20_000.0,
+            ..RoutingConfig::default()
+        });
+        let route = short_range
+            .route_at(
+                &satellites,
+                &source,
+                &destination,
+                epoch,
+                &KeplerianPropagator::new(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(route.satellite_ids(), vec!["SAT-0", "SAT-1", "SAT-2"]);
+        assert_eq!(route.hops.len(), 4);
+    }
+
+    #[test]
+    fn test_store_and_forward_over_window() {
+        let epoch = Utc::now();
+        let satellites = ring(1, epoch);
+        let source = station("GS-A", 0.0);
+        let destination = station("GS-B", 90.0);
+        let router = RelayRouter::new();
+        let propagator = KeplerianPropagator::new();
+
+        // No instantaneous path: the only satellite cannot see both stations
+        assert!(router
+            .route_at(&satellites, &source, &destination, epoch, &propagator)
+            .unwrap()
+            .is_none());
+
+        let route = router
+            .route_over_window(
+                &satellites,
+                &source,
+                &destination,
+                &RoutingWindow {
+                    start_time: epoch,
+                    duration_hours: 3.0,
+                    step_seconds: 60.0,
+                },
+                &propagator,
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(route.hops.len(), 2);
+        assert_eq!(route.hops[0].time, epoch);
+        assert!(route.hops[1].time > epoch);
+        assert!(route.storage_delay_ms() > 60_000.0);
+        assert!(route.arrival_time() > route.hops[1].time);
+    }
+}
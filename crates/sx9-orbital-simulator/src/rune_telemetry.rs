@@ -0,0 +1,195 @@
+//! Unicode rune telemetry for satellite state deltas
+//!
+//! Packs how far a satellite has strayed from a reference state into a short
+//! run of Private Use Area runes, following the SDT rune encoding of the
+//! eBPF common library, so a `SatelliteUnicodePacket` can carry it in a few
+//! characters. The delta angle uses the SDT 16-bit scale (0-65535 over
+//! 0-360°) and the SDT delta-angle block, high byte first, so a reader of
+//! plain SDT runes sees the coarse angle in the first of the two:
+//!
+//! | Rune          | Meaning                                   |
+//! |---------------|-------------------------------------------|
+//! | U+EC10+class  | `PositionErrorClass`, after the SDT states |
+//! | U+E300+high   | Delta angle, high byte                    |
+//! | U+E300+low    | Delta angle, low byte                     |
+//! | U+F8FF        | SDT completion rune                       |
+//!
+//! The angle is quantized to 360/65535° (about 20 arcseconds); the position
+//! error keeps only its class.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::health;
+use crate::orbit::SatelliteState;
+use serde::{Deserialize, Serialize};
+
+/// Order-of-magnitude position error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PositionErrorClass {
+    /// Under 100 m
+    Nominal,
+    /// Under 1 km
+    Minor,
+    /// Under 10 km
+    Moderate,
+    /// Under 100 km
+    Major,
+    /// 100 km or more
+    Critical,
+}
+
+/// Satellite state relative to a reference
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateDelta {
+    pub position_error_km: f64,
+    /// Signed along-track angle; positive when ahead of the reference
+    pub delta_angle_deg: f64,
+}
+
+/// A state delta as recovered from runes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DecodedStateDelta {
+    pub position_error_class: PositionErrorClass,
+    /// Along-track angle in [0, 360)
+    pub delta_angle_deg: f64,
+}
+
+impl PositionErrorClass {
+    const ALL: [PositionErrorClass; 5] = [
+        PositionErrorClass::Nominal,
+        PositionErrorClass::Minor,
+        PositionErrorClass::Moderate,
+        PositionErrorClass::Major,
+        PositionErrorClass::Critical,
+    ];
+
+    pub fn from_error_km(position_error_km: f64) -> Self {
+        match position_error_km {
+            e if e < 0.1 => PositionErrorClass::Nominal,
+            e if e < 1.0 => PositionErrorClass::Minor,
+            e if e < 10.0 => PositionErrorClass::Moderate,
+            e if e < 100.0 => PositionErrorClass::Major,
+            _ => PositionErrorClass::Critical,
+        }
+    }
+
+    fn from_index(index: u32) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+}
+
+impl StateDelta {
+    /// Delta of `state` from `reference` at the same epoch
+    pub fn between(reference: &SatelliteState, state: &SatelliteState) -> Self {
+        let position_error_km = (0..3)
+            .map(|i| (state.position_eci[i] - reference.position_eci[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        Self {
+            position_error_km,
+            delta_angle_deg: health::along_track_offset_deg(reference, state),
+        }
+    }
+
+    pub fn position_error_class(&self) -> PositionErrorClass {
+        PositionErrorClass::from_error_km(self.position_error_km)
+    }
+
+    /// Delta angle on the SDT 16-bit scale
+    pub fn quantized_delta_angle(&self) -> u16 {
+        let turns = self.delta_angle_deg.rem_euclid(360.0) / 360.0;
+        // A delta just short of a full turn rounds to zero, not past 65535
+        ((turns * DELTA_ANGLE_STEPS).round() as u32 % (DELTA_ANGLE_STEPS as u32 + 1)) as u16
+    }
+
+    /// The rune sequence for this delta
+    pub fn encode(&self) -> String {
+        let angle = self.quantized_delta_angle() as u32;
+        [
+            POSITION_ERROR_BASE + self.position_error_class() as u32,
+            DELTA_ANGLE_BASE + (angle >> 8),
+            DELTA_ANGLE_BASE + (angle & 0xFF),
+            COMPLETION,
+        ]
+        .into_iter()
+        .filter_map(char::from_u32)
+        .collect()
+    }
+}
+
+impl DecodedStateDelta {
+    /// Read a sequence written by `StateDelta::encode`
+    pub fn decode(runes: &str) -> Result<Self> {
+        let malformed = || {
+            OrbitalMechanicsError::simulation_error(format!(
+                "Malformed state delta runes {:?}",
+                runes
+                    .chars()
+                    .map(|c| format!("U+{:04X}", c as u32))
+                    .collect::<Vec<_>>()
+            ))
+        };
+        let codes: Vec<u32> = runes.chars().map(|c| c as u32).collect();
+        let [class, high, low, COMPLETION] = codes[..] else {
+            return Err(malformed());
+        };
+        let position_error_class = class
+            .checked_sub(POSITION_ERROR_BASE)
+            .and_then(PositionErrorClass::from_index)
+            .ok_or_else(malformed)?;
+        let byte = |rune: u32| {
+            rune.checked_sub(DELTA_ANGLE_BASE)
+                .filter(|&b| b <= 0xFF)
+                .ok_or_else(malformed)
+        };
+        let angle = (byte(high)? << 8) | byte(low)?;
+        Ok(Self {
+            position_error_class,
+            delta_angle_deg: angle as f64 / DELTA_ANGLE_STEPS * 360.0,
+        })
+    }
+}
+
+/// SDT delta angle block, U+E300-U+E3FF
+const DELTA_ANGLE_BASE: u32 = 0xE300;
+
+/// Position error classes, U+EC10-U+EC14, after the SDT frame state runes
+const POSITION_ERROR_BASE: u32 = 0xEC10;
+
+/// SDT completion rune
+const COMPLETION: u32 = 0xF8FF;
+
+/// Full scale of the SDT delta angle
+const DELTA_ANGLE_STEPS: f64 = 65535.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_delta_round_trip() {
+        for (error_km, angle_deg) in [(0.0, 0.0), (0.5, 1.25), (42.0, -3.5), (1.0e4, 179.99)] {
+            let delta = StateDelta {
+                position_error_km: error_km,
+                delta_angle_deg: angle_deg,
+            };
+            let runes = delta.encode();
+            assert_eq!(runes.chars().count(), 4);
+            assert!(runes
+                .chars()
+                .all(|c| (0xE000..=0xF8FF).contains(&(c as u32))));
+
+            let decoded = DecodedStateDelta::decode(&runes).unwrap();
+            assert_eq!(decoded.position_error_class, delta.position_error_class());
+            let miss = (decoded.delta_angle_deg - angle_deg.rem_euclid(360.0)).abs();
+            assert!(miss <= 360.0 / DELTA_ANGLE_STEPS / 2.0 + 1e-12, "{}", miss);
+        }
+
+        assert_eq!(
+            PositionErrorClass::from_error_km(42.0),
+            PositionErrorClass::Major
+        );
+        assert!(DecodedStateDelta::decode("").is_err());
+        assert!(DecodedStateDelta::decode("\u{EC10}\u{E300}\u{E300}").is_err());
+        assert!(DecodedStateDelta::decode("\u{EC19}\u{E300}\u{E300}\u{F8FF}").is_err());
+    }
+}
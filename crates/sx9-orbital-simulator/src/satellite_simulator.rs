@@ -2,19 +2,46 @@
 //!
 //! Real-time satellite simulation with MEO obstruction analysis and
 //! Unicode packet transmission to ground stations via HFT routing.
+//!
+//! # Concurrency model
+//!
+//! Each tick advances a shared simulation clock once, then runs in two
+//! phases. In the advance phase satellites are propagated on rayon's
+//! work-stealing pool; a worker reads shared inputs (stations, obstructions,
+//! environment) and mutates only its own satellite's power, data, health and
+//! star-tracker entries. In the commit phase the results are applied on the
+//! calling task in ascending satellite ID order: live state, packet history
+//! and published `SimulationEvent`s therefore come out in the same order for
+//! the same inputs, whatever the thread count. The environment is updated
+//! after the commit. The tick blocks its task while the pool runs.
 
-use anyhow::Result;
 use chrono::{DateTime, Datelike, Duration, Utc};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::{interval, sleep};
 use uuid::Uuid;
 
+use crate::aircraft::{AircraftConjunction, AircraftReport, AircraftTracker, DeconflictionConfig};
+use crate::constants::defaults;
 use crate::coordinates::{GeodeticPosition, Position3D};
-use crate::error::OrbitalMechanicsError;
-use crate::orbit::{OrbitalElements, SatelliteOrbit, SatelliteState};
-use crate::propagator::OrbitalPropagator;
+use crate::data_volume::{DataBuffer, DataVolumeConfig, DataVolumeStatistics};
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::fault_injection::{Fault, FaultKind, FaultSchedule};
+use crate::fso_analysis::{self, FsoAnalyzer};
+use crate::ground_station::{GroundStation, GroundStationNetwork};
+use crate::health::{self, HealthConfig, HealthMonitor, HealthStatistics};
+use crate::laser_safety::LaserSafetyZone;
+use crate::orbit::{LookAngles, OrbitalElements, SatelliteOrbit, SatelliteState};
+use crate::playback::{PlaybackPropagator, RecordedEphemeris};
+use crate::power::{ContactPowerViolation, PowerConfig, PowerStatistics, PowerSystem};
+use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+use crate::rune_telemetry::StateDelta;
+use crate::star_tracker::{BlindingSource, StarTracker, StarTrackerViolation};
+use crate::trace_targets;
+use crate::weather::{WeatherDecision, WeatherLimits, WeatherProvider};
 
 /// OPERATIONAL: Live satellite with Unicode packet generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +76,9 @@ pub struct ObstructionWarning {
     pub closest_approach_time: DateTime<Utc>,
     pub minimum_distance_km: f64,
     pub obstruction_details: String,
+    /// Laser safety zone crossed by an uplink
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +87,8 @@ pub enum ObstructionType {
     ActiveSatellite,
     LaunchVehicle,
     SpaceStation,
+    /// Uplink beam crossing a terrestrial no-lase zone below its ceiling
+    LaserSafetyZone,
     Unknown,
 }
 
@@ -98,6 +130,70 @@ impl Default for MeoEnvironmentalConditions {
     }
 }
 
+impl MeoEnvironmentalConditions {
+    /// Combined environmental stress on the spacecraft (0-1)
+    ///
+    /// Mean of solar radiation intensity, geomagnetic disturbance on its 0-5
+    /// scale, and Van Allen dose rate relative to 400 mRad/hour.
+    pub fn stress_index(&self) -> f64 {
+        let solar = (self.solar_radiation / 100.0).clamp(0.0, 1.0);
+        let geomagnetic = (self.geomagnetic_disturbance / 5.0).clamp(0.0, 1.0);
+        let radiation = (self.van_allen_radiation / 400.0).clamp(0.0, 1.0);
+        (solar + geomagnetic + radiation) / 3.0
+    }
+}
+
+/// Event published to simulation subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SimulationEvent {
+    /// Health score of a satellite fell below the anomaly threshold
+    AnomalySuspected {
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+        health_score: f64,
+        reasons: Vec<String>,
+    },
+    /// A star tracker boresight entered a Sun, Moon or Earth-limb exclusion cone
+    PointingConstraintViolated {
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+        violation: StarTrackerViolation,
+    },
+    /// An aircraft is predicted to enter an uplink's protection cone; close
+    /// the shutter before `conjunction.conjunction_time`
+    UplinkShutterRequested {
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+        conjunction: AircraftConjunction,
+    },
+    /// No conjunction is predicted any more for a shuttered uplink
+    UplinkShutterReleased {
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+        station_id: String,
+        icao24: String,
+    },
+    /// The clock reached the end of the recording; the simulation is paused
+    PlaybackFinished { timestamp: DateTime<Utc> },
+    /// A scheduled fault took effect
+    FaultInjected {
+        timestamp: DateTime<Utc>,
+        fault: Fault,
+    },
+    /// A satellite started downlinking to a station
+    ContactStarted {
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+        station_id: String,
+    },
+    /// A satellite stopped downlinking to a station
+    ContactEnded {
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+        station_id: String,
+    },
+}
+
 /// Unicode packet for satellite-to-ground communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SatelliteUnicodePacket {
@@ -113,6 +209,9 @@ pub struct SatelliteUnicodePacket {
     pub trivariate_hash: String,
     pub transmission_power_dbm: f64,
     pub link_budget_db: f64,
+    /// Drift from the nominal slot state, as `rune_telemetry` runes
+    #[serde(default)]
+    pub state_delta_runes: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,12 +225,117 @@ pub struct ObstructionStatus {
 /// CTAS-7 Satellite Constellation Simulator
 pub struct SatelliteSimulator {
     satellites: Arc<RwLock<HashMap<Uuid, LiveSatellite>>>,
-    propagator: Box<dyn OrbitalPropagator>,
+    propagator: Box<dyn OrbitalPropagator + Send + Sync>,
     environmental_model: Arc<RwLock<MeoEnvironmentalConditions>>,
     obstruction_database: Arc<RwLock<Vec<KnownObstruction>>>,
+    laser_safety_zones: Arc<RwLock<Vec<LaserSafetyZone>>>,
+    aircraft: Arc<RwLock<AircraftTracker>>,
+    deconfliction: DeconflictionConfig,
+    shuttered_uplinks: Arc<RwLock<HashMap<Uuid, ShutteredUplinks>>>,
     simulation_time: Arc<RwLock<DateTime<Utc>>>,
-    time_acceleration: f64,
+    /// Simulated seconds per wall-clock second
+    time_acceleration: Arc<RwLock<f64>>,
+    paused: Arc<RwLock<bool>>,
     unicode_packet_history: Arc<RwLock<Vec<SatelliteUnicodePacket>>>,
+    ground_stations: Arc<RwLock<GroundStationNetwork>>,
+    fso_analyzer: FsoAnalyzer,
+    default_data_volume: DataVolumeConfig,
+    data_buffers: Arc<RwLock<HashMap<Uuid, DataBuffer>>>,
+    default_power: PowerConfig,
+    power_systems: Arc<RwLock<HashMap<Uuid, PowerSystem>>>,
+    default_health: HealthConfig,
+    health_monitors: Arc<RwLock<HashMap<Uuid, HealthMonitor>>>,
+    /// Station each satellite is downlinking to
+    downlink_stations: Arc<RwLock<HashMap<Uuid, String>>>,
+    star_trackers: Vec<StarTracker>,
+    /// Exclusion cones each satellite's trackers are currently inside
+    blinded_trackers: Arc<RwLock<HashMap<Uuid, BlindedTrackers>>>,
+    faults: Arc<RwLock<FaultSchedule>>,
+    /// Indices of scheduled faults that have taken effect
+    injected_faults: Arc<RwLock<HashSet<usize>>>,
+    /// Consulted when picking a downlink station; none means always clear
+    weather: Option<Arc<dyn WeatherProvider>>,
+    weather_limits: WeatherLimits,
+    events: broadcast::Sender<SimulationEvent>,
+    /// Last recorded epoch when replaying a recording
+    playback_end: Option<DateTime<Utc>>,
+    commands: mpsc::Sender<SimulationCommand>,
+    command_receiver: tokio::sync::Mutex<mpsc::Receiver<SimulationCommand>>,
+}
+
+/// (station, aircraft) conjunctions a satellite's uplinks are shuttered for
+type ShutteredUplinks = HashSet<(String, String)>;
+
+/// (tracker, source) exclusion cones a satellite's star trackers are inside
+type BlindedTrackers = HashSet<(String, BlindingSource)>;
+
+/// Outcome of advancing one satellite, applied in satellite ID order
+struct SatelliteTick {
+    satellite_id: Uuid,
+    timestamp: DateTime<Utc>,
+    state: SatelliteState,
+    obstruction_warnings: Vec<ObstructionWarning>,
+    enter_maintenance: bool,
+    packet: SatelliteUnicodePacket,
+    events: Vec<SimulationEvent>,
+}
+
+/// Events buffered per subscriber before the slowest one starts lagging
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Commands queued before senders wait for the run loop
+pub const COMMAND_CHANNEL_CAPACITY: usize = 64;
+
+/// Wall-clock interval between simulation ticks
+pub const TICK_PERIOD: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Time-control command for a running simulation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SimulationCommand {
+    Pause,
+    Resume,
+    /// Advance exactly one tick and stay paused
+    Step,
+    /// Simulated seconds per wall-clock second (clamped to 0.1-1000)
+    SetTimeWarp(f64),
+    /// End `start_simulation`
+    Stop,
+}
+
+/// Cloneable handle for driving a simulation from UI frontends
+#[derive(Debug, Clone)]
+pub struct SimulationControl {
+    commands: mpsc::Sender<SimulationCommand>,
+}
+
+impl SimulationControl {
+    /// Queue a command for the run loop
+    pub async fn send(&self, command: SimulationCommand) -> Result<()> {
+        self.commands
+            .send(command)
+            .await
+            .map_err(|_| OrbitalMechanicsError::simulation_error("simulator has been dropped"))
+    }
+
+    pub async fn pause(&self) -> Result<()> {
+        self.send(SimulationCommand::Pause).await
+    }
+
+    pub async fn resume(&self) -> Result<()> {
+        self.send(SimulationCommand::Resume).await
+    }
+
+    pub async fn step(&self) -> Result<()> {
+        self.send(SimulationCommand::Step).await
+    }
+
+    pub async fn set_time_warp(&self, multiplier: f64) -> Result<()> {
+        self.send(SimulationCommand::SetTimeWarp(multiplier)).await
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.send(SimulationCommand::Stop).await
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,16 +351,190 @@ pub struct KnownObstruction {
 
 impl SatelliteSimulator {
     /// Create new satellite simulator
-    pub fn new(propagator: Box<dyn OrbitalPropagator>) -> Self {
+    pub fn new(propagator: Box<dyn OrbitalPropagator + Send + Sync>) -> Self {
+        let (commands, command_receiver) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
         Self {
             satellites: Arc::new(RwLock::new(HashMap::new())),
             propagator,
             environmental_model: Arc::new(RwLock::new(MeoEnvironmentalConditions::default())),
             obstruction_database: Arc::new(RwLock::new(Self::initialize_known_obstructions())),
+            laser_safety_zones: Arc::new(RwLock::new(Vec::new())),
+            aircraft: Arc::new(RwLock::new(AircraftTracker::new())),
+            deconfliction: DeconflictionConfig::default(),
+            shuttered_uplinks: Arc::new(RwLock::new(HashMap::new())),
             simulation_time: Arc::new(RwLock::new(Utc::now())),
-            time_acceleration: 1.0, // Real-time by default
+            time_acceleration: Arc::new(RwLock::new(1.0)), // Real-time by default
+            paused: Arc::new(RwLock::new(false)),
             unicode_packet_history: Arc::new(RwLock::new(Vec::new())),
+            ground_stations: Arc::new(RwLock::new(GroundStationNetwork::new())),
+            fso_analyzer: FsoAnalyzer::new(),
+            default_data_volume: DataVolumeConfig::default(),
+            data_buffers: Arc::new(RwLock::new(HashMap::new())),
+            default_power: PowerConfig::default(),
+            power_systems: Arc::new(RwLock::new(HashMap::new())),
+            default_health: HealthConfig::default(),
+            health_monitors: Arc::new(RwLock::new(HashMap::new())),
+            downlink_stations: Arc::new(RwLock::new(HashMap::new())),
+            star_trackers: Vec::new(),
+            blinded_trackers: Arc::new(RwLock::new(HashMap::new())),
+            faults: Arc::new(RwLock::new(FaultSchedule::default())),
+            injected_faults: Arc::new(RwLock::new(HashSet::new())),
+            weather: None,
+            weather_limits: WeatherLimits::default(),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            playback_end: None,
+            commands,
+            command_receiver: tokio::sync::Mutex::new(command_receiver),
+        }
+    }
+
+    /// Create a simulator that replays recorded ephemeris
+    ///
+    /// The clock starts at the beginning of the span every satellite covers
+    /// and each recorded satellite is added under its recorded ID, with
+    /// elements osculating at that epoch. Ticks then read states from the
+    /// recording instead of propagating. When the clock reaches the end of
+    /// the span the simulation pauses and publishes `PlaybackFinished`.
+    pub async fn from_recording(recording: RecordedEphemeris) -> Result<Self> {
+        let (start, end) = recording.span().ok_or_else(|| {
+            OrbitalMechanicsError::simulation_error(
+                "Recording is empty or its satellites share no common time span",
+            )
+        })?;
+        let orbits = recording
+            .satellite_ids()
+            .iter()
+            .map(|id| recording.osculating_orbit(id, start))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut simulator = Self::new(Box::new(PlaybackPropagator::new(recording)));
+        *simulator.simulation_time.write().unwrap() = start;
+        simulator.playback_end = Some(end);
+        for orbit in orbits {
+            let name = orbit.name.clone();
+            simulator.add_satellite(orbit, name, None).await?;
         }
+
+        tracing::info!(
+            target: trace_targets::SIMULATOR,
+            satellites = simulator.satellites.read().unwrap().len(),
+            start = %start,
+            end = %end,
+            "Loaded recording for playback"
+        );
+        Ok(simulator)
+    }
+
+    /// Add ground station available for downlink
+    pub fn add_ground_station(&self, station: GroundStation) {
+        self.ground_stations.write().unwrap().add_station(station);
+    }
+
+    /// Protect a terrestrial no-lase zone from uplink beams
+    pub fn add_laser_safety_zone(&self, zone: LaserSafetyZone) {
+        self.laser_safety_zones.write().unwrap().push(zone);
+    }
+
+    /// Replace all terrestrial no-lase zones
+    pub fn set_laser_safety_zones(&self, zones: Vec<LaserSafetyZone>) {
+        *self.laser_safety_zones.write().unwrap() = zones;
+    }
+
+    /// Add aircraft position reports, e.g. from a live ADS-B feed
+    pub fn ingest_aircraft(&self, reports: impl IntoIterator<Item = AircraftReport>) {
+        self.aircraft.write().unwrap().extend(reports);
+    }
+
+    /// Replace the aircraft tracker, e.g. with one loaded from a track file
+    pub fn set_aircraft_tracker(&self, tracker: AircraftTracker) {
+        *self.aircraft.write().unwrap() = tracker;
+    }
+
+    /// Set the uplink protection cone and conjunction look-ahead
+    pub fn set_deconfliction(&mut self, config: DeconflictionConfig) {
+        self.deconfliction = config;
+    }
+
+    /// Set data volume configuration applied to satellites added afterwards
+    pub fn set_default_data_volume(&mut self, config: DataVolumeConfig) {
+        self.default_data_volume = config;
+    }
+
+    /// Replace the data volume configuration of one satellite, keeping its backlog
+    pub fn configure_data_volume(
+        &self,
+        satellite_id: Uuid,
+        config: DataVolumeConfig,
+    ) -> Result<()> {
+        let mut buffers = self.data_buffers.write().unwrap();
+        let buffer = buffers
+            .get_mut(&satellite_id)
+            .ok_or_else(|| OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()))?;
+        buffer.config = config;
+        Ok(())
+    }
+
+    /// Set power configuration applied to satellites added afterwards
+    pub fn set_default_power(&mut self, config: PowerConfig) {
+        self.default_power = config;
+    }
+
+    /// Replace the power configuration of one satellite, keeping its battery charge
+    pub fn configure_power(&self, satellite_id: Uuid, config: PowerConfig) -> Result<()> {
+        let mut systems = self.power_systems.write().unwrap();
+        let power = systems
+            .get_mut(&satellite_id)
+            .ok_or_else(|| OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()))?;
+        power.config = config;
+        Ok(())
+    }
+
+    /// Contacts refused because they would violate battery constraints
+    pub fn power_violations(&self, satellite_id: Uuid) -> Result<Vec<ContactPowerViolation>> {
+        let systems = self.power_systems.read().unwrap();
+        let power = systems
+            .get(&satellite_id)
+            .ok_or_else(|| OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()))?;
+        Ok(power.violations().cloned().collect())
+    }
+
+    /// Set health scoring configuration applied to satellites added afterwards
+    pub fn set_default_health(&mut self, config: HealthConfig) {
+        self.default_health = config;
+    }
+
+    /// Set the star tracker heads checked on every satellite
+    pub fn set_star_trackers(&mut self, trackers: Vec<StarTracker>) {
+        self.star_trackers = trackers;
+    }
+
+    /// Replace the scripted faults, e.g. with a scenario's `faults`
+    ///
+    /// Faults are matched to satellites by orbit satellite ID. Faults whose
+    /// start has already passed take effect on the next tick.
+    pub fn set_fault_schedule(&self, schedule: FaultSchedule) -> Result<()> {
+        schedule.validate()?;
+        *self.faults.write().unwrap() = schedule;
+        self.injected_faults.write().unwrap().clear();
+        Ok(())
+    }
+
+    /// Check the weather over each station before downlinking to it
+    ///
+    /// The provider is asked every tick, at the simulation time, for each
+    /// station in view.
+    pub fn set_weather_provider(
+        &mut self,
+        provider: Arc<dyn WeatherProvider>,
+        limits: WeatherLimits,
+    ) {
+        self.weather = Some(provider);
+        self.weather_limits = limits;
+    }
+
+    /// Subscribe to simulation events published from now on
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SimulationEvent> {
+        self.events.subscribe()
     }
 
     /// Initialize known obstructions from crawled data
@@ -245,129 +623,653 @@ impl SatelliteSimulator {
 
         let mut satellites = self.satellites.write().unwrap();
         satellites.insert(satellite_id, satellite);
+        self.data_buffers.write().unwrap().insert(
+            satellite_id,
+            DataBuffer::new(self.default_data_volume.clone()),
+        );
+        self.power_systems
+            .write()
+            .unwrap()
+            .insert(satellite_id, PowerSystem::new(self.default_power.clone()));
+        self.health_monitors.write().unwrap().insert(
+            satellite_id,
+            HealthMonitor::new(self.default_health.clone()),
+        );
 
-        tracing::info!("Added satellite {} to simulation", satellite_id);
+        tracing::info!(
+            target: trace_targets::SIMULATOR,
+            "Added satellite {} to simulation",
+            satellite_id
+        );
         Ok(satellite_id)
     }
 
+    /// Handle for pausing, stepping and warping the running simulation
+    pub fn control(&self) -> SimulationControl {
+        SimulationControl {
+            commands: self.commands.clone(),
+        }
+    }
+
+    /// Whether ticks are suspended
+    pub fn is_paused(&self) -> bool {
+        *self.paused.read().unwrap()
+    }
+
+    /// Current simulated seconds per wall-clock second
+    pub fn time_acceleration(&self) -> f64 {
+        *self.time_acceleration.read().unwrap()
+    }
+
+    /// Current simulation time
+    pub fn simulation_time(&self) -> DateTime<Utc> {
+        *self.simulation_time.read().unwrap()
+    }
+
     /// Start real-time simulation
+    ///
+    /// Ticks every `TICK_PERIOD` until a `Stop` command arrives. Commands
+    /// from `control()` are applied between ticks, ahead of any tick that is
+    /// due; commands sent before the loop starts are applied first.
     pub async fn start_simulation(&self) -> Result<()> {
-        let mut interval = interval(tokio::time::Duration::from_millis(1000)); // 1Hz update rate
+        let mut interval = interval(TICK_PERIOD);
+        let mut commands = self.command_receiver.lock().await;
 
         loop {
-            interval.tick().await;
-            self.update_simulation_step().await?;
+            tokio::select! {
+                biased;
+                command = commands.recv() => {
+                    // `self` holds a sender, so the channel never closes
+                    let Some(command) = command else { return Ok(()) };
+                    tracing::info!(
+                        target: trace_targets::SIMULATOR,
+                        command = ?command,
+                        "Simulation command"
+                    );
+                    match command {
+                        SimulationCommand::Pause => *self.paused.write().unwrap() = true,
+                        SimulationCommand::Resume => {
+                            *self.paused.write().unwrap() = false;
+                            interval.reset();
+                        }
+                        SimulationCommand::Step => {
+                            *self.paused.write().unwrap() = true;
+                            self.update_simulation_step().await?;
+                        }
+                        SimulationCommand::SetTimeWarp(multiplier) => {
+                            if let Err(err) = self.set_time_acceleration(multiplier) {
+                                tracing::warn!(
+                                    target: trace_targets::SIMULATOR,
+                                    "Ignoring time warp: {}",
+                                    err
+                                );
+                            }
+                        }
+                        SimulationCommand::Stop => return Ok(()),
+                    }
+                }
+                _ = interval.tick(), if !self.is_paused() => {
+                    self.update_simulation_step().await?;
+                }
+            }
         }
     }
 
     /// Update simulation by one time step
+    ///
+    /// Satellites advance in parallel against a shared clock, then their
+    /// results are committed in satellite ID order so events and packet
+    /// history do not depend on thread scheduling.
     async fn update_simulation_step(&self) -> Result<()> {
-        // Advance simulation time
-        {
+        let started = std::time::Instant::now();
+
+        // Advance simulation time, stopping at the end of a recording
+        let current_time = {
             let mut sim_time = self.simulation_time.write().unwrap();
-            *sim_time = *sim_time + Duration::seconds((self.time_acceleration as i64).max(1));
+            let tick_seconds = TICK_PERIOD.as_secs_f64() * self.time_acceleration();
+            let mut next = *sim_time + Duration::milliseconds((tick_seconds * 1000.0) as i64);
+            if let Some(end) = self.playback_end {
+                if *sim_time >= end {
+                    *self.paused.write().unwrap() = true;
+                    return Ok(());
+                }
+                next = next.min(end);
+            }
+            *sim_time = next;
+            next
+        };
+
+        // Forget aircraft reports too old to extrapolate
+        {
+            let mut aircraft = self.aircraft.write().unwrap();
+            let max_age = (aircraft.max_report_age_seconds * 1000.0) as i64;
+            aircraft.prune(current_time - Duration::milliseconds(max_age));
         }
 
-        let current_time = *self.simulation_time.read().unwrap();
+        self.inject_faults(current_time);
 
         // Update all satellites
-        let satellite_ids: Vec<Uuid> = {
+        let mut satellite_ids: Vec<Uuid> = {
             let satellites = self.satellites.read().unwrap();
             satellites.keys().cloned().collect()
         };
-
-        for satellite_id in satellite_ids {
-            self.update_satellite(satellite_id, current_time).await?;
+        satellite_ids.sort_unstable();
+
+        let satellite_count = satellite_ids.len();
+        let ticks: Vec<Result<Option<SatelliteTick>>> = satellite_ids
+            .par_iter()
+            .map(|&satellite_id| self.advance_satellite(satellite_id, current_time))
+            .collect();
+        for tick in ticks {
+            if let Some(tick) = tick? {
+                self.commit_tick(tick);
+            }
         }
 
         // Update environmental conditions
         self.update_environmental_conditions(current_time).await?;
 
+        if self.playback_end == Some(current_time) {
+            *self.paused.write().unwrap() = true;
+            // No subscribers is not an error
+            let _ = self.events.send(SimulationEvent::PlaybackFinished {
+                timestamp: current_time,
+            });
+            tracing::info!(
+                target: trace_targets::SIMULATOR,
+                simulation_time = %current_time,
+                "Playback finished"
+            );
+        }
+
+        tracing::debug!(
+            target: trace_targets::SIMULATOR,
+            simulation_time = %current_time,
+            satellites = satellite_count,
+            workers = rayon::current_num_threads(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Simulation step complete"
+        );
+
         Ok(())
     }
 
-    /// Update individual satellite state and generate Unicode packets
-    async fn update_satellite(
+    /// Advance one satellite to `current_time`
+    ///
+    /// Runs on a worker thread. Only this satellite's power, data, health and
+    /// tracker entries are modified; its state, packet and events are
+    /// returned for `commit_tick`. Inactive satellites yield `None`.
+    fn advance_satellite(
         &self,
         satellite_id: Uuid,
         current_time: DateTime<Utc>,
-    ) -> Result<()> {
-        let (orbit, current_status) = {
+    ) -> Result<Option<SatelliteTick>> {
+        let (orbit, current_status, last_update) = {
             let satellites = self.satellites.read().unwrap();
             if let Some(satellite) = satellites.get(&satellite_id) {
                 (
                     satellite.orbit.clone(),
                     satellite.operational_status.clone(),
+                    satellite.last_update,
                 )
             } else {
-                return Err(
-                    OrbitalMechanicsError::SatelliteNotFound(satellite_id.to_string()).into(),
-                );
+                return Err(OrbitalMechanicsError::SatelliteNotFound(
+                    satellite_id.to_string(),
+                ));
             }
         };
 
         // Only update active satellites
         if !matches!(current_status, SatelliteOperationalStatus::Active) {
-            return Ok(());
+            return Ok(None);
         }
 
         // Propagate orbital position
         let new_state = self.propagator.propagate(&orbit, current_time)?;
+        let mut events = Vec::new();
+
+        // Store-and-forward: accumulate payload data, downlink while a station is in
+        // view and the battery can carry the terminal load
+        let step_seconds = (current_time - last_update).num_milliseconds() as f64 / 1000.0;
+        let downlink = self.best_downlink(&new_state, current_time);
+        let contact_offered = downlink.is_some();
+        let downlink = self.update_power(satellite_id, &new_state, step_seconds, downlink);
+        let contact = contact_offered.then_some(downlink.is_some());
+        let link_rate_bps = downlink.as_ref().map_or(0.0, |(_, rate)| *rate);
+        self.update_data_volume(satellite_id, current_time, step_seconds, link_rate_bps);
+        let station_id = downlink.as_ref().map(|(station_id, _)| station_id.as_str());
+        self.update_contact(satellite_id, current_time, station_id, &mut events);
+
+        // Score health against the unperturbed slot the satellite was assigned
+        let nominal_orbit = orbit
+            .slot
+            .as_ref()
+            .map_or_else(|| orbit.clone(), |s| s.nominal_orbit(&orbit.satellite_id));
+        let nominal_state = KeplerianPropagator::new().propagate(&nominal_orbit, current_time)?;
+        let slot_drift_deg = health::along_track_offset_deg(&nominal_state, &new_state);
+        self.update_health(
+            satellite_id,
+            current_time,
+            slot_drift_deg,
+            contact,
+            &mut events,
+        );
+        self.update_star_trackers(satellite_id, &new_state, current_time, &mut events);
+        self.update_uplink_shutters(satellite_id, &new_state, current_time, &mut events);
 
         // Check for obstructions
-        let obstruction_warnings = self.detect_obstructions(&new_state, current_time).await?;
+        let obstruction_warnings = self.detect_obstructions(&new_state, current_time)?;
         let obstruction_status = ObstructionStatus {
             clear_path: obstruction_warnings.is_empty(),
             active_warnings: obstruction_warnings.clone(),
-            next_hazard_time: self
-                .calculate_next_hazard_time(&new_state, current_time)
-                .await,
-            avoidance_maneuver_required: obstruction_warnings
-                .iter()
-                .any(|w| matches!(w.threat_level, ThreatLevel::High | ThreatLevel::Critical)),
+            next_hazard_time: self.calculate_next_hazard_time(&new_state, current_time),
+            // Zone crossings are resolved by shuttering the uplink, not by maneuvering
+            avoidance_maneuver_required: obstruction_warnings.iter().any(|w| {
+                !matches!(w.obstruction_type, ObstructionType::LaserSafetyZone)
+                    && matches!(w.threat_level, ThreatLevel::High | ThreatLevel::Critical)
+            }),
         };
 
         // Generate Unicode packet
-        let unicode_packet = self
-            .generate_unicode_packet(satellite_id, &new_state, current_time, &obstruction_status)
-            .await?;
+        let packet = self.generate_unicode_packet(
+            satellite_id,
+            &new_state,
+            &nominal_state,
+            current_time,
+            &obstruction_status,
+        )?;
+
+        Ok(Some(SatelliteTick {
+            satellite_id,
+            timestamp: current_time,
+            state: new_state,
+            obstruction_warnings,
+            enter_maintenance: obstruction_status.avoidance_maneuver_required,
+            packet,
+            events,
+        }))
+    }
 
+    /// Apply one satellite's step to the shared state and publish its events
+    fn commit_tick(&self, tick: SatelliteTick) {
         // Update satellite state
         {
             let mut satellites = self.satellites.write().unwrap();
-            if let Some(satellite) = satellites.get_mut(&satellite_id) {
-                satellite.current_state = new_state;
-                satellite.last_update = current_time;
-                satellite.obstruction_warnings = obstruction_warnings;
+            if let Some(satellite) = satellites.get_mut(&tick.satellite_id) {
+                satellite.current_state = tick.state;
+                satellite.last_update = tick.timestamp;
+                satellite.obstruction_warnings = tick.obstruction_warnings;
                 satellite.unicode_packets_sent += 1;
 
                 // Handle critical obstructions
-                if obstruction_status.avoidance_maneuver_required {
+                if tick.enter_maintenance {
                     satellite.operational_status = SatelliteOperationalStatus::Maintenance;
                     tracing::warn!(
+                        target: trace_targets::SIMULATOR,
                         "Satellite {} entering maintenance mode due to obstruction threat",
-                        satellite_id
+                        tick.satellite_id
                     );
                 }
             }
         }
 
+        for event in tick.events {
+            // No subscribers is not an error
+            let _ = self.events.send(event);
+        }
+
         // Store Unicode packet in history
         {
             let mut history = self.unicode_packet_history.write().unwrap();
-            history.push(unicode_packet);
+            history.push(tick.packet);
 
             // Keep only last 1000 packets per satellite
             if history.len() > 10000 {
                 history.drain(0..1000);
             }
         }
+    }
 
-        Ok(())
+    /// Put faults that have started into effect
+    ///
+    /// Each fault is announced once; satellites with a failure are marked
+    /// `Lost`, which stops their ticks.
+    fn inject_faults(&self, current_time: DateTime<Utc>) {
+        let faults = self.faults.read().unwrap();
+        if faults.is_empty() {
+            return;
+        }
+
+        let mut injected = self.injected_faults.write().unwrap();
+        for (index, fault) in faults.faults.iter().enumerate() {
+            if fault.start_time > current_time || !injected.insert(index) {
+                continue;
+            }
+            tracing::warn!(
+                target: trace_targets::SIMULATOR,
+                fault = ?fault.kind,
+                start_time = %fault.start_time,
+                "Injecting fault"
+            );
+            if let FaultKind::SatelliteFailure { satellite_id } = &fault.kind {
+                let mut satellites = self.satellites.write().unwrap();
+                for satellite in satellites
+                    .values_mut()
+                    .filter(|s| &s.orbit.satellite_id == satellite_id)
+                {
+                    satellite.operational_status = SatelliteOperationalStatus::Lost;
+                }
+            }
+            // No subscribers is not an error
+            let _ = self.events.send(SimulationEvent::FaultInjected {
+                timestamp: current_time,
+                fault: fault.clone(),
+            });
+        }
+    }
+
+    /// Station in view offering the highest downlink rate (bps)
+    ///
+    /// Injected station outages and terminal degradation apply, and stations
+    /// under weather outside the limits are passed over.
+    fn best_downlink(
+        &self,
+        state: &SatelliteState,
+        current_time: DateTime<Utc>,
+    ) -> Option<(String, f64)> {
+        let faults = self.faults.read().unwrap();
+        let loss_db = faults.terminal_loss_db(&state.satellite_id, current_time);
+        self.ground_stations
+            .read()
+            .unwrap()
+            .stations()
+            .filter(|station| {
+                station.is_available(current_time)
+                    && !faults.station_offline(&station.station_id, current_time)
+            })
+            .filter(|station| self.weather_allows(station, current_time))
+            .filter_map(|station| {
+                self.fso_analyzer
+                    .analyze_link(state, station, current_time)
+                    .map(|link| {
+                        let throughput_gbps = if loss_db > 0.0 {
+                            fso_analysis::throughput_gbps(
+                                link.link_margin_db - loss_db,
+                                link.atmospheric_transmission,
+                            )
+                        } else {
+                            link.estimated_throughput_gbps
+                        };
+                        (station.station_id.clone(), throughput_gbps * 1.0e9)
+                    })
+            })
+            .filter(|(_, rate)| *rate > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Whether the weather provider, if any, clears a contact with `station`
+    fn weather_allows(&self, station: &GroundStation, current_time: DateTime<Utc>) -> bool {
+        let Some(provider) = &self.weather else {
+            return true;
+        };
+        match self
+            .weather_limits
+            .decide(provider.as_ref(), station, current_time)
+        {
+            WeatherDecision::Go => true,
+            WeatherDecision::NoGo { reason } => {
+                tracing::debug!(
+                    target: trace_targets::SIMULATOR,
+                    station_id = %station.station_id,
+                    %reason,
+                    "Station passed over for weather"
+                );
+                false
+            }
+        }
+    }
+
+    /// Advance the battery of a satellite by one step
+    ///
+    /// Returns the downlink if the battery can support the terminal for the
+    /// step; otherwise the contact is flagged and dropped.
+    fn update_power(
+        &self,
+        satellite_id: Uuid,
+        state: &SatelliteState,
+        step_seconds: f64,
+        downlink: Option<(String, f64)>,
+    ) -> Option<(String, f64)> {
+        let mut systems = self.power_systems.write().unwrap();
+        let Some(power) = systems.get_mut(&satellite_id) else {
+            return downlink;
+        };
+        if step_seconds <= 0.0 {
+            return downlink;
+        }
+
+        let downlink = downlink.filter(|(station_id, _)| {
+            let allowed = power.request_contact(station_id, state, step_seconds);
+            if !allowed {
+                tracing::warn!(
+                    target: trace_targets::SIMULATOR,
+                    %satellite_id,
+                    station_id = %station_id,
+                    depth_of_discharge_percent = power.depth_of_discharge_percent(),
+                    "Contact refused: FSO terminal load would exceed battery depth-of-discharge limit"
+                );
+            }
+            allowed
+        });
+
+        power.step(state, downlink.is_some(), step_seconds);
+        downlink
+    }
+
+    /// Track the station a satellite downlinks to, queuing an event when a
+    /// contact starts or ends
+    ///
+    /// A switch between stations ends one contact and starts the other.
+    fn update_contact(
+        &self,
+        satellite_id: Uuid,
+        current_time: DateTime<Utc>,
+        station_id: Option<&str>,
+        events: &mut Vec<SimulationEvent>,
+    ) {
+        let mut stations = self.downlink_stations.write().unwrap();
+        let previous = stations.get(&satellite_id).map(String::as_str);
+        if previous == station_id {
+            return;
+        }
+        if let Some(previous) = stations.remove(&satellite_id) {
+            events.push(SimulationEvent::ContactEnded {
+                satellite_id,
+                timestamp: current_time,
+                station_id: previous,
+            });
+        }
+        if let Some(station_id) = station_id {
+            stations.insert(satellite_id, station_id.to_string());
+            events.push(SimulationEvent::ContactStarted {
+                satellite_id,
+                timestamp: current_time,
+                station_id: station_id.to_string(),
+            });
+        }
+    }
+
+    /// Rescore the health of a satellite, queuing an event if it degrades
+    fn update_health(
+        &self,
+        satellite_id: Uuid,
+        current_time: DateTime<Utc>,
+        slot_drift_deg: f64,
+        contact: Option<bool>,
+        events: &mut Vec<SimulationEvent>,
+    ) {
+        let stress = self.environmental_model.read().unwrap().stress_index();
+        let mut monitors = self.health_monitors.write().unwrap();
+        let Some(monitor) = monitors.get_mut(&satellite_id) else {
+            return;
+        };
+
+        if let Some(reasons) = monitor.update(slot_drift_deg, contact, stress) {
+            let health_score = monitor.health_score();
+            tracing::warn!(
+                target: trace_targets::SIMULATOR,
+                %satellite_id,
+                health_score,
+                reasons = %reasons.join("; "),
+                "Anomaly suspected: health score below threshold"
+            );
+            events.push(SimulationEvent::AnomalySuspected {
+                satellite_id,
+                timestamp: current_time,
+                health_score,
+                reasons,
+            });
+        }
+    }
+
+    /// Check star tracker exclusion cones, queuing an event when a tracker becomes blinded
+    fn update_star_trackers(
+        &self,
+        satellite_id: Uuid,
+        state: &SatelliteState,
+        current_time: DateTime<Utc>,
+        events: &mut Vec<SimulationEvent>,
+    ) {
+        if self.star_trackers.is_empty() {
+            return;
+        }
+
+        let violations: Vec<StarTrackerViolation> = self
+            .star_trackers
+            .iter()
+            .flat_map(|tracker| tracker.violations(state))
+            .collect();
+        let mut blinded = self.blinded_trackers.write().unwrap();
+        let previous = blinded.remove(&satellite_id).unwrap_or_default();
+
+        let mut active = HashSet::new();
+        for violation in violations {
+            let key = (violation.tracker_id.clone(), violation.source);
+            if !previous.contains(&key) {
+                tracing::warn!(
+                    target: trace_targets::SIMULATOR,
+                    %satellite_id,
+                    tracker_id = %violation.tracker_id,
+                    source = ?violation.source,
+                    separation_deg = violation.separation_deg,
+                    "Star tracker exclusion cone violated"
+                );
+                events.push(SimulationEvent::PointingConstraintViolated {
+                    satellite_id,
+                    timestamp: current_time,
+                    violation,
+                });
+            }
+            active.insert(key);
+        }
+        blinded.insert(satellite_id, active);
+    }
+
+    /// Predict aircraft conjunctions with this satellite's uplinks
+    ///
+    /// A shutter request is raised once when a conjunction is first predicted
+    /// and released once none is predicted for that station and aircraft.
+    fn update_uplink_shutters(
+        &self,
+        satellite_id: Uuid,
+        state: &SatelliteState,
+        current_time: DateTime<Utc>,
+        events: &mut Vec<SimulationEvent>,
+    ) {
+        let aircraft = self.aircraft.read().unwrap().positions_at(current_time);
+        let mut shuttered = self.shuttered_uplinks.write().unwrap();
+        let previous = shuttered.remove(&satellite_id).unwrap_or_default();
+        if aircraft.is_empty() && previous.is_empty() {
+            return;
+        }
+
+        let mut active = HashSet::new();
+        if !aircraft.is_empty() {
+            for (station, look_angles) in self.uplink_candidates(state, current_time) {
+                for report in &aircraft {
+                    let Some(conjunction) = self.deconfliction.predict_conjunction(
+                        &station,
+                        look_angles.azimuth_deg,
+                        look_angles.elevation_deg,
+                        report,
+                    ) else {
+                        continue;
+                    };
+                    let key = (conjunction.station_id.clone(), conjunction.icao24.clone());
+                    if !previous.contains(&key) {
+                        tracing::warn!(
+                            target: trace_targets::SIMULATOR,
+                            %satellite_id,
+                            station_id = %conjunction.station_id,
+                            icao24 = %conjunction.icao24,
+                            lead_time_seconds = conjunction.lead_time_seconds,
+                            "Aircraft conjunction predicted, shuttering uplink"
+                        );
+                        events.push(SimulationEvent::UplinkShutterRequested {
+                            satellite_id,
+                            timestamp: current_time,
+                            conjunction,
+                        });
+                    }
+                    active.insert(key);
+                }
+            }
+        }
+
+        let mut released: Vec<&(String, String)> = previous.difference(&active).collect();
+        released.sort();
+        for (station_id, icao24) in released {
+            events.push(SimulationEvent::UplinkShutterReleased {
+                satellite_id,
+                timestamp: current_time,
+                station_id: station_id.clone(),
+                icao24: icao24.clone(),
+            });
+        }
+        shuttered.insert(satellite_id, active);
+    }
+
+    /// Advance the onboard data buffer of a satellite by one step
+    fn update_data_volume(
+        &self,
+        satellite_id: Uuid,
+        current_time: DateTime<Utc>,
+        step_seconds: f64,
+        link_rate_bps: f64,
+    ) {
+        if step_seconds <= 0.0 {
+            return;
+        }
+
+        let mut buffers = self.data_buffers.write().unwrap();
+        if let Some(buffer) = buffers.get_mut(&satellite_id) {
+            buffer.generate(current_time, step_seconds);
+            if link_rate_bps > 0.0 {
+                let delivered = buffer.drain(current_time, link_rate_bps, step_seconds);
+                tracing::trace!(
+                    target: trace_targets::SIMULATOR,
+                    %satellite_id,
+                    link_rate_bps,
+                    delivered_bits = delivered,
+                    backlog_bits = buffer.backlog_bits(),
+                    "Downlinked buffered data"
+                );
+            }
+        }
     }
 
     /// Detect potential obstructions for satellite
-    async fn detect_obstructions(
+    fn detect_obstructions(
         &self,
         satellite_state: &SatelliteState,
         current_time: DateTime<Utc>,
@@ -408,20 +1310,93 @@ impl SatelliteSimulator {
                         "Potential collision with {}",
                         obstruction.object_id
                     ),
+                    zone_id: None,
                 };
 
                 warnings.push(warning);
             }
         }
 
+        warnings.extend(self.detect_laser_safety_conflicts(satellite_state, current_time));
         Ok(warnings)
     }
 
+    /// Available stations that see the satellite above the FSO elevation mask
+    ///
+    /// Each is a potential uplink. Sorted by station ID.
+    fn uplink_candidates(
+        &self,
+        satellite_state: &SatelliteState,
+        current_time: DateTime<Utc>,
+    ) -> Vec<(GroundStation, LookAngles)> {
+        let mut candidates: Vec<(GroundStation, LookAngles)> = self
+            .ground_stations
+            .read()
+            .unwrap()
+            .stations()
+            .filter(|station| station.is_available(current_time))
+            .filter_map(|station| {
+                let look_angles = satellite_state.look_angles_from_station(
+                    station.position.latitude_deg,
+                    station.position.longitude_deg,
+                    station.position.elevation_m,
+                );
+                (look_angles.elevation_deg >= defaults::MIN_ELEVATION_DEG)
+                    .then(|| (station.clone(), look_angles))
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.0.station_id.cmp(&b.0.station_id));
+        candidates
+    }
+
+    /// Uplinks to this satellite that would cross a no-lase zone below its ceiling
+    ///
+    /// Warnings are ordered by station, then zone.
+    fn detect_laser_safety_conflicts(
+        &self,
+        satellite_state: &SatelliteState,
+        current_time: DateTime<Utc>,
+    ) -> Vec<ObstructionWarning> {
+        let zones = self.laser_safety_zones.read().unwrap();
+        if zones.is_empty() {
+            return Vec::new();
+        }
+
+        let mut warnings = Vec::new();
+        for (station, look_angles) in self.uplink_candidates(satellite_state, current_time) {
+            for zone in zones.iter() {
+                let Some(entry_km) = zone.beam_entry_km(
+                    &station.position,
+                    look_angles.azimuth_deg,
+                    look_angles.elevation_deg,
+                ) else {
+                    continue;
+                };
+                warnings.push(ObstructionWarning {
+                    warning_id: Uuid::new_v4(),
+                    timestamp: current_time,
+                    obstruction_type: ObstructionType::LaserSafetyZone,
+                    threat_level: ThreatLevel::High,
+                    closest_approach_time: current_time,
+                    // Ground distance from the station to where the beam enters the zone
+                    minimum_distance_km: entry_km,
+                    obstruction_details: format!(
+                        "Uplink from {} at {:.1}° elevation crosses {} below {:.0} m",
+                        station.station_id, look_angles.elevation_deg, zone.name, zone.ceiling_m
+                    ),
+                    zone_id: Some(zone.zone_id.clone()),
+                });
+            }
+        }
+        warnings
+    }
+
     /// Generate Unicode packet for satellite transmission
-    async fn generate_unicode_packet(
+    fn generate_unicode_packet(
         &self,
         satellite_id: Uuid,
         satellite_state: &SatelliteState,
+        nominal_state: &SatelliteState,
         timestamp: DateTime<Utc>,
         obstruction_status: &ObstructionStatus,
     ) -> Result<SatelliteUnicodePacket> {
@@ -459,7 +1434,12 @@ impl SatelliteSimulator {
         let altitude_km = (position.magnitude() - 6371.0).max(0.0);
         let transmission_power_dbm =
             Self::calculate_transmission_power(altitude_km, &environmental_conditions);
-        let link_budget_db = Self::calculate_link_budget(altitude_km, &environmental_conditions);
+        let link_budget_db = Self::calculate_link_budget(altitude_km, &environmental_conditions)
+            - self
+                .faults
+                .read()
+                .unwrap()
+                .terminal_loss_db(&satellite_state.satellite_id, timestamp);
 
         Ok(SatelliteUnicodePacket {
             packet_id: Uuid::new_v4(),
@@ -482,6 +1462,7 @@ impl SatelliteSimulator {
             trivariate_hash,
             transmission_power_dbm,
             link_budget_db,
+            state_delta_runes: StateDelta::between(nominal_state, satellite_state).encode(),
         })
     }
 
@@ -531,7 +1512,7 @@ impl SatelliteSimulator {
     }
 
     /// Calculate next potential hazard time
-    async fn calculate_next_hazard_time(
+    fn calculate_next_hazard_time(
         &self,
         _satellite_state: &SatelliteState,
         current_time: DateTime<Utc>,
@@ -570,24 +1551,74 @@ impl SatelliteSimulator {
             .count();
 
         let total_unicode_packets = history.len();
+        let total_packets_generated = satellites
+            .values()
+            .map(|s| s.unicode_packets_sent)
+            .sum::<u64>();
         let obstruction_warnings = satellites
             .values()
             .map(|s| s.obstruction_warnings.len())
             .sum::<usize>();
 
+        // Link availability: share of retained packets transmitted over a clear path
+        let link_availability_percent = if history.is_empty() {
+            0.0
+        } else {
+            let clear = history
+                .iter()
+                .filter(|p| p.obstruction_status.clear_path)
+                .count();
+            clear as f64 / history.len() as f64 * 100.0
+        };
+
+        let data_volume = self
+            .data_buffers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, buffer)| (*id, buffer.statistics()))
+            .collect();
+
+        let power = self
+            .power_systems
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, power)| (*id, power.statistics()))
+            .collect();
+
+        let health = self
+            .health_monitors
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, monitor)| (*id, monitor.statistics()))
+            .collect();
+
         SimulationStatistics {
             total_satellites,
             active_satellites,
             total_unicode_packets,
+            total_packets_generated,
             obstruction_warnings,
+            link_availability_percent,
             simulation_time: *self.simulation_time.read().unwrap(),
             environmental_conditions: self.environmental_model.read().unwrap().clone(),
+            data_volume,
+            power,
+            health,
         }
     }
 
-    /// Set time acceleration for simulation
-    pub fn set_time_acceleration(&mut self, acceleration: f64) {
-        self.time_acceleration = acceleration.max(0.1).min(1000.0);
+    /// Set time acceleration for simulation, clamped to 0.1-1000x
+    pub fn set_time_acceleration(&self, acceleration: f64) -> Result<()> {
+        if acceleration.is_nan() {
+            return Err(OrbitalMechanicsError::config_error(
+                "Time acceleration must be a number, got NaN",
+            ));
+        }
+        *self.time_acceleration.write().unwrap() = acceleration.clamp(0.1, 1000.0);
+        Ok(())
     }
 }
 
@@ -597,16 +1628,33 @@ pub struct SimulationStatistics {
     pub total_satellites: usize,
     pub active_satellites: usize,
     pub total_unicode_packets: usize,
+    /// Packets generated since simulation start (not bounded by history retention)
+    pub total_packets_generated: u64,
     pub obstruction_warnings: usize,
+    /// Percentage of retained packets sent over an unobstructed path
+    pub link_availability_percent: f64,
     pub simulation_time: DateTime<Utc>,
     pub environmental_conditions: MeoEnvironmentalConditions,
+    /// Store-and-forward backlog, latency and dropped data per satellite
+    #[serde(default)]
+    pub data_volume: HashMap<Uuid, DataVolumeStatistics>,
+    /// Battery state and power-refused contacts per satellite
+    #[serde(default)]
+    pub power: HashMap<Uuid, PowerStatistics>,
+    /// Health score and anomaly state per satellite
+    #[serde(default)]
+    pub health: HashMap<Uuid, HealthStatistics>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ground_station::StationPosition;
+    use crate::laser_safety::LaserSafetyZoneKind;
     use crate::orbit::OrbitalElements;
     use crate::propagator::{create_propagator, PropagatorType};
+    use crate::rune_telemetry::{DecodedStateDelta, PositionErrorClass};
+    use crate::weather::ConstantWeather;
 
     #[tokio::test]
     async fn test_satellite_simulator_creation() {
@@ -623,17 +1671,14 @@ mod tests {
         let propagator = create_propagator(PropagatorType::Sgp4).unwrap();
         let simulator = SatelliteSimulator::new(propagator);
 
-        let orbital_elements = OrbitalElements {
-            semi_major_axis: 10000.0, // MEO orbit
-            eccentricity: 0.01,
-            inclination: 55.0,
-            right_ascension: 0.0,
-            argument_of_perigee: 0.0,
-            mean_anomaly: 0.0,
-            epoch: Utc::now(),
-        };
-
-        let orbit = SatelliteOrbit::new("TEST-SAT".to_string(), orbital_elements);
+        // MEO orbit
+        let orbital_elements = OrbitalElements::new(10000.0, 0.01, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "TEST-SAT".to_string(),
+            "Test Satellite".to_string(),
+            orbital_elements,
+            Utc::now(),
+        );
         let satellite_id = simulator
             .add_satellite(orbit, "Test Satellite".to_string(), Some(12345))
             .await
@@ -648,20 +1693,19 @@ mod tests {
 
     #[tokio::test]
     async fn test_unicode_packet_generation() {
-        let propagator = create_propagator(PropagatorType::Sgp4).unwrap();
+        // Keplerian, like the nominal slot state, so the satellite is exactly on station
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
         let simulator = SatelliteSimulator::new(propagator);
 
-        let orbital_elements = OrbitalElements {
-            semi_major_axis: 12000.0, // MEO orbit
-            eccentricity: 0.02,
-            inclination: 60.0,
-            right_ascension: 45.0,
-            argument_of_perigee: 90.0,
-            mean_anomaly: 180.0,
-            epoch: Utc::now(),
-        };
-
-        let orbit = SatelliteOrbit::new("UNICODE-TEST".to_string(), orbital_elements);
+        // MEO orbit
+        let orbital_elements =
+            OrbitalElements::new(12000.0, 0.02, 60.0, 45.0, 90.0, 180.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "UNICODE-TEST".to_string(),
+            "Unicode Test Satellite".to_string(),
+            orbital_elements,
+            Utc::now(),
+        );
         let satellite_id = simulator
             .add_satellite(orbit, "Unicode Test Satellite".to_string(), Some(99999))
             .await
@@ -678,5 +1722,599 @@ mod tests {
         assert!(!packet.unicode_compressed.is_empty());
         assert!(!packet.trivariate_hash.is_empty());
         assert!(packet.transmission_power_dbm > 0.0);
+
+        let decoded = DecodedStateDelta::decode(&packet.state_delta_runes).unwrap();
+        assert_eq!(decoded.position_error_class, PositionErrorClass::Nominal);
+        assert_eq!(decoded.delta_angle_deg, 0.0);
+        let delta = StateDelta {
+            position_error_km: 5.0,
+            delta_angle_deg: -90.0,
+        };
+        let decoded = DecodedStateDelta::decode(&delta.encode()).unwrap();
+        assert_eq!(decoded.position_error_class, PositionErrorClass::Moderate);
+        assert!((decoded.delta_angle_deg - 270.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_data_volume_downlink() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_default_data_volume(DataVolumeConfig::new(1.0e6, 1.0e9));
+        simulator.add_ground_station(GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Sub-satellite Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        });
+
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "DV-01".to_string(),
+            "Data Volume Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Data Volume Test".to_string(), None)
+            .await
+            .unwrap();
+
+        for _ in 0..5 {
+            simulator.update_simulation_step().await.unwrap();
+        }
+
+        let stats = simulator.get_simulation_statistics().await;
+        let data_volume = &stats.data_volume[&satellite_id];
+        assert!(data_volume.generated_bits > 0.0);
+        assert_eq!(data_volume.delivered_bits, data_volume.generated_bits);
+        assert_eq!(data_volume.backlog_bits, 0.0);
+        assert!(data_volume.latency_p50_seconds.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_injected_faults() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_default_data_volume(DataVolumeConfig::new(1.0e6, 1.0e9));
+        simulator.add_ground_station(
+            GroundStation::builder()
+                .station_id("GS-001")
+                .latitude_deg(0.0)
+                .longitude_deg(0.0)
+                .build()
+                .unwrap(),
+        );
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "FAULT-01".to_string(),
+            "Fault Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Fault Test".to_string(), None)
+            .await
+            .unwrap();
+
+        let start = *simulator.simulation_time.read().unwrap();
+        let schedule = FaultSchedule::new(vec![
+            Fault::new(
+                FaultKind::StationOffline {
+                    station_id: "GS-001".to_string(),
+                },
+                start,
+            )
+            .with_end_time(start + Duration::seconds(3)),
+            Fault::new(
+                FaultKind::SatelliteFailure {
+                    satellite_id: "FAULT-01".to_string(),
+                },
+                start + Duration::seconds(5),
+            ),
+        ])
+        .unwrap();
+        simulator.set_fault_schedule(schedule).unwrap();
+        let mut events = simulator.subscribe_events();
+
+        // Station offline: data piles up
+        for _ in 0..2 {
+            simulator.update_simulation_step().await.unwrap();
+        }
+        let stats = simulator.get_simulation_statistics().await;
+        assert!(stats.data_volume[&satellite_id].generated_bits > 0.0);
+        assert_eq!(stats.data_volume[&satellite_id].delivered_bits, 0.0);
+
+        // Station back, then the satellite fails for good
+        for _ in 0..4 {
+            simulator.update_simulation_step().await.unwrap();
+        }
+        let stats = simulator.get_simulation_statistics().await;
+        assert!(stats.data_volume[&satellite_id].delivered_bits > 0.0);
+        assert_eq!(stats.active_satellites, 0);
+
+        let mut injected = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let SimulationEvent::FaultInjected { fault, .. } = event {
+                injected.push(fault.kind);
+            }
+        }
+        assert_eq!(injected.len(), 2);
+        assert!(matches!(injected[1], FaultKind::SatelliteFailure { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_weather_blocks_downlink() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_default_data_volume(DataVolumeConfig::new(1.0e6, 1.0e9));
+        simulator.set_weather_provider(
+            Arc::new(ConstantWeather::new(0.9, 20.0)),
+            WeatherLimits::default(),
+        );
+        simulator.add_ground_station(
+            GroundStation::builder()
+                .station_id("GS-001")
+                .latitude_deg(0.0)
+                .longitude_deg(0.0)
+                .build()
+                .unwrap(),
+        );
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "WX-01".to_string(),
+            "Weather Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Weather Test".to_string(), None)
+            .await
+            .unwrap();
+
+        simulator.update_simulation_step().await.unwrap();
+        let stats = simulator.get_simulation_statistics().await;
+        assert_eq!(stats.data_volume[&satellite_id].delivered_bits, 0.0);
+
+        simulator
+            .set_weather_provider(Arc::new(ConstantWeather::clear()), WeatherLimits::default());
+        let mut events = simulator.subscribe_events();
+        simulator.update_simulation_step().await.unwrap();
+        let stats = simulator.get_simulation_statistics().await;
+        assert!(stats.data_volume[&satellite_id].delivered_bits > 0.0);
+        assert!(matches!(
+            events.try_recv(),
+            Ok(SimulationEvent::ContactStarted { station_id, .. }) if station_id == "GS-001"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_power_refuses_contact_over_dod_limit() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_default_data_volume(DataVolumeConfig::new(1.0e6, 1.0e9));
+        simulator.set_default_power(PowerConfig {
+            fso_terminal_load_w: 1.0e6,
+            max_depth_of_discharge: 0.01,
+            ..PowerConfig::default()
+        });
+        simulator.add_ground_station(GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Sub-satellite Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        });
+
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "PWR-01".to_string(),
+            "Power Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Power Test".to_string(), None)
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            simulator.update_simulation_step().await.unwrap();
+        }
+
+        let stats = simulator.get_simulation_statistics().await;
+        let power = &stats.power[&satellite_id];
+        assert_eq!(power.refused_contacts, 3);
+        assert_eq!(power.terminal_active_seconds, 0.0);
+        assert_eq!(stats.data_volume[&satellite_id].delivered_bits, 0.0);
+
+        let violations = simulator.power_violations(satellite_id).unwrap();
+        assert_eq!(violations.len(), 3);
+        assert!(violations.iter().all(|v| v.station_id == "GS-001"));
+    }
+
+    #[tokio::test]
+    async fn test_anomaly_event_on_refused_contacts() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_default_power(PowerConfig {
+            fso_terminal_load_w: 1.0e6,
+            max_depth_of_discharge: 0.01,
+            ..PowerConfig::default()
+        });
+        simulator.add_ground_station(GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Sub-satellite Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        });
+        let mut events = simulator.subscribe_events();
+
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "HLT-01".to_string(),
+            "Health Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Health Test".to_string(), None)
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            simulator.update_simulation_step().await.unwrap();
+        }
+
+        // Every contact refused: flagged once, not on every step
+        match events.try_recv().unwrap() {
+            SimulationEvent::AnomalySuspected {
+                satellite_id: id,
+                health_score,
+                reasons,
+                ..
+            } => {
+                assert_eq!(id, satellite_id);
+                assert!(health_score < HealthConfig::default().anomaly_threshold);
+                assert!(reasons
+                    .iter()
+                    .any(|r| r.starts_with("link success rate 0%")));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert!(events.try_recv().is_err());
+
+        let stats = simulator.get_simulation_statistics().await;
+        let health = &stats.health[&satellite_id];
+        assert!(health.anomaly_suspected);
+        assert_eq!(health.anomalies_flagged, 1);
+        assert_eq!(health.link_success_rate, 0.0);
+        assert!(health.slot_drift_deg.abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_star_tracker_blinding_event() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_star_trackers(vec![StarTracker::new("ST-NADIR", [-1.0, 0.0, 0.0]).unwrap()]);
+        let mut events = simulator.subscribe_events();
+
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "ST-01".to_string(),
+            "Star Tracker Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Star Tracker Test".to_string(), None)
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            simulator.update_simulation_step().await.unwrap();
+        }
+
+        // A nadir boresight stays inside the Earth-limb cone: reported once
+        let mut limb_events = 0;
+        while let Ok(event) = events.try_recv() {
+            if let SimulationEvent::PointingConstraintViolated {
+                satellite_id: id,
+                violation,
+                ..
+            } = event
+            {
+                assert_eq!(id, satellite_id);
+                if violation.source == BlindingSource::EarthLimb {
+                    assert_eq!(violation.tracker_id, "ST-NADIR");
+                    assert!(violation.separation_deg < 0.0);
+                    limb_events += 1;
+                }
+            }
+        }
+        assert_eq!(limb_events, 1);
+    }
+
+    #[tokio::test]
+    async fn test_time_control_commands() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let simulator = SatelliteSimulator::new(propagator);
+        let start = simulator.get_simulation_statistics().await.simulation_time;
+        let control = simulator.control();
+
+        // Queued ahead of the loop, so applied before the first tick is due
+        control.pause().await.unwrap();
+        control.set_time_warp(60.0).await.unwrap();
+        // Ignored, leaving the previous warp in place
+        control.set_time_warp(f64::NAN).await.unwrap();
+        control.step().await.unwrap();
+        control.step().await.unwrap();
+        control.stop().await.unwrap();
+        simulator.start_simulation().await.unwrap();
+
+        assert!(simulator.is_paused());
+        assert_eq!(simulator.time_acceleration(), 60.0);
+        let elapsed = simulator.get_simulation_statistics().await.simulation_time - start;
+        assert_eq!(elapsed, Duration::seconds(120));
+    }
+
+    #[tokio::test]
+    async fn test_parallel_tick_commits_in_satellite_order() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let mut simulator = SatelliteSimulator::new(propagator);
+        simulator.set_star_trackers(vec![StarTracker::new("ST-NADIR", [-1.0, 0.0, 0.0]).unwrap()]);
+        let mut events = simulator.subscribe_events();
+
+        let mut satellite_ids = Vec::new();
+        for i in 0..32 {
+            let elements =
+                OrbitalElements::new(14378.0, 0.0, 55.0, i as f64 * 11.25, 0.0, i as f64 * 7.0)
+                    .unwrap();
+            let orbit = SatelliteOrbit::new(
+                format!("MEO-{:02}", i),
+                format!("MEO {}", i),
+                elements,
+                Utc::now(),
+            );
+            let name = orbit.name.clone();
+            satellite_ids.push(simulator.add_satellite(orbit, name, None).await.unwrap());
+        }
+        satellite_ids.sort_unstable();
+
+        simulator.update_simulation_step().await.unwrap();
+
+        let mut event_order = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let SimulationEvent::PointingConstraintViolated {
+                satellite_id,
+                violation,
+                ..
+            } = event
+            {
+                if violation.source == BlindingSource::EarthLimb {
+                    event_order.push(satellite_id);
+                }
+            }
+        }
+        assert_eq!(event_order, satellite_ids);
+
+        let packet_order: Vec<Uuid> = simulator
+            .get_unicode_packet_history(None)
+            .await
+            .iter()
+            .map(|p| p.satellite_id)
+            .collect();
+        assert_eq!(packet_order, satellite_ids);
+    }
+
+    #[tokio::test]
+    async fn test_playback_replays_recording_and_stops_at_end() {
+        let epoch = Utc::now();
+        let source = KeplerianPropagator::new();
+        let mut states = Vec::new();
+        for (id, raan) in [("REC-A", 0.0), ("REC-B", 120.0)] {
+            let elements = OrbitalElements::new(14378.0, 0.0, 55.0, raan, 0.0, 0.0).unwrap();
+            let orbit = SatelliteOrbit::new(id.to_string(), id.to_string(), elements, epoch);
+            for minute in 0..=5 {
+                states.push(
+                    source
+                        .propagate(&orbit, epoch + Duration::minutes(minute))
+                        .unwrap(),
+                );
+            }
+        }
+        let last = states[5].clone();
+
+        let simulator = SatelliteSimulator::from_recording(RecordedEphemeris::from_states(states))
+            .await
+            .unwrap();
+        let mut events = simulator.subscribe_events();
+        let control = simulator.control();
+        control.pause().await.unwrap();
+        control.set_time_warp(60.0).await.unwrap();
+        // One step more than the recording holds
+        for _ in 0..6 {
+            control.step().await.unwrap();
+        }
+        control.stop().await.unwrap();
+        simulator.start_simulation().await.unwrap();
+
+        assert!(simulator.is_paused());
+        let end = simulator.get_simulation_statistics().await.simulation_time;
+        assert_eq!(end, epoch + Duration::minutes(5));
+        let replayed = simulator
+            .get_all_satellites()
+            .await
+            .into_iter()
+            .find(|s| s.name == "REC-A")
+            .unwrap();
+        assert_eq!(replayed.current_state.position_eci, last.position_eci);
+
+        let mut finished = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let SimulationEvent::PlaybackFinished { timestamp } = event {
+                finished.push(timestamp);
+            }
+        }
+        assert_eq!(finished, [end]);
+    }
+
+    #[tokio::test]
+    async fn test_uplink_through_laser_safety_zone_warns_without_maneuver() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let simulator = SatelliteSimulator::new(propagator);
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "LSZ-01".to_string(),
+            "Zone Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        let satellite_id = simulator
+            .add_satellite(orbit, "Zone Test".to_string(), None)
+            .await
+            .unwrap();
+
+        // Station under the satellite, inside an airport polygon
+        let subpoint = simulator.get_all_satellites().await[0]
+            .current_state
+            .geodetic
+            .clone();
+        let (lat, lon) = (subpoint.latitude_deg, subpoint.longitude_deg);
+        simulator.add_ground_station(GroundStation {
+            station_id: "GS-ZONE".to_string(),
+            name: "Zone Station".to_string(),
+            position: StationPosition {
+                latitude_deg: lat,
+                longitude_deg: lon,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        });
+        let zone = LaserSafetyZone::new(
+            "APT-1",
+            LaserSafetyZoneKind::Airport,
+            vec![
+                [lat - 1.0, lon - 1.0],
+                [lat - 1.0, lon + 1.0],
+                [lat + 1.0, lon + 1.0],
+                [lat + 1.0, lon - 1.0],
+            ],
+        )
+        .unwrap();
+        simulator.add_laser_safety_zone(zone);
+
+        simulator.update_simulation_step().await.unwrap();
+
+        let satellite = simulator.get_all_satellites().await.remove(0);
+        assert_eq!(satellite.id, satellite_id);
+        let zone_warnings: Vec<_> = satellite
+            .obstruction_warnings
+            .iter()
+            .filter_map(|w| w.zone_id.as_deref())
+            .collect();
+        assert_eq!(zone_warnings, ["APT-1"]);
+        assert!(matches!(
+            satellite.operational_status,
+            SatelliteOperationalStatus::Active
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_aircraft_over_station_shutters_uplink() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let simulator = SatelliteSimulator::new(propagator);
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let orbit = SatelliteOrbit::new(
+            "ADS-01".to_string(),
+            "Shutter Test".to_string(),
+            elements,
+            Utc::now(),
+        );
+        simulator
+            .add_satellite(orbit, "Shutter Test".to_string(), None)
+            .await
+            .unwrap();
+        let mut events = simulator.subscribe_events();
+
+        // Station under the satellite with an aircraft directly overhead
+        let satellite = simulator.get_all_satellites().await.remove(0);
+        let (lat, lon) = (
+            satellite.current_state.geodetic.latitude_deg,
+            satellite.current_state.geodetic.longitude_deg,
+        );
+        simulator.add_ground_station(GroundStation {
+            station_id: "GS-ADSB".to_string(),
+            name: "Shutter Station".to_string(),
+            position: StationPosition {
+                latitude_deg: lat,
+                longitude_deg: lon,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        });
+        let overhead = AircraftReport {
+            icao24: "abc123".to_string(),
+            callsign: None,
+            timestamp: satellite.last_update,
+            latitude_deg: lat,
+            longitude_deg: lon,
+            altitude_m: 10000.0,
+            ground_speed_m_s: 0.0,
+            track_deg: 0.0,
+            vertical_rate_m_s: 0.0,
+        };
+        simulator.ingest_aircraft([overhead.clone()]);
+        simulator.update_simulation_step().await.unwrap();
+
+        // The same aircraft reported 2° away clears the cone
+        simulator.ingest_aircraft([AircraftReport {
+            timestamp: satellite.last_update + Duration::seconds(1),
+            latitude_deg: lat + 2.0,
+            ..overhead
+        }]);
+        simulator.update_simulation_step().await.unwrap();
+
+        let mut shutter_events = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            match event {
+                SimulationEvent::UplinkShutterRequested { conjunction, .. } => {
+                    assert_eq!(conjunction.station_id, "GS-ADSB");
+                    assert_eq!(conjunction.lead_time_seconds, 0.0);
+                    shutter_events.push("requested");
+                }
+                SimulationEvent::UplinkShutterReleased { icao24, .. } => {
+                    assert_eq!(icao24, "abc123");
+                    shutter_events.push("released");
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(shutter_events, ["requested", "released"]);
     }
 }
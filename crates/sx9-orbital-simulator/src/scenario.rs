@@ -0,0 +1,425 @@
+//! Scenario files
+//!
+//! A scenario gathers in one TOML, YAML or JSON document what otherwise takes
+//! several code steps: the constellation, extra ground stations, the analysis
+//! period, the analyses to run and where to write their results.
+//!
+//! ```toml
+//! name = "MEO gateway study"
+//! start_time = "2024-03-20T00:00:00Z"
+//! duration_hours = 24.0
+//! output_directory = "out"
+//!
+//! [constellation]
+//! config_file = "constellation.toml"
+//!
+//! [[stations]]
+//! station_id = "GS-HAWAII"
+//! # ...CustomGroundStation fields
+//!
+//! [[analyses]]
+//! type = "contacts"
+//! dtn_contact_plan = true
+//!
+//! [[faults]]
+//! type = "station_offline"
+//! station_id = "GS-HAWAII"
+//! start_time = "2024-03-20T06:00:00Z"
+//! end_time = "2024-03-20T08:00:00Z"
+//!
+//! [export]
+//! distance_unit = "meters"
+//! ```
+//!
+//! Relative paths are resolved against the scenario file's directory. Each
+//! analysis writes one JSON artifact named after it, and contact schedules can
+//! also be written as STK interval lists and DTN contact plans. Injected
+//! faults (see `fault_injection`) apply to the windows of the visibility and
+//! contact analyses, and `SatelliteSimulator::set_fault_schedule` replays the
+//! same schedule live.
+
+use crate::config::{
+    load_constellation_config, parse_document, ConfigFormat, ConstellationConfig,
+    CustomGroundStation,
+};
+use crate::contact_plan::ContactPlan;
+use crate::coverage_grid::CoverageGridConfig;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::export::ExportOptions;
+use crate::fault_injection::FaultSchedule;
+use crate::pass_scoring;
+use crate::pointing::PointingScheduleGenerator;
+use crate::visibility::VisibilityWindow;
+use crate::OrbitalMechanicsEngine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Complete analysis scenario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub constellation: ScenarioConstellation,
+    /// Stations added to those in the constellation config
+    #[serde(default)]
+    pub stations: Vec<CustomGroundStation>,
+    pub start_time: DateTime<Utc>,
+    pub duration_hours: f64,
+    /// Epoch of the constellation's orbital elements; defaults to `start_time`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element_epoch: Option<DateTime<Utc>>,
+    pub analyses: Vec<ScenarioAnalysis>,
+    /// Faults injected into the scenario
+    #[serde(default, skip_serializing_if = "FaultSchedule::is_empty")]
+    pub faults: FaultSchedule,
+    /// Units, precision and timestamp format of CSV artifacts
+    #[serde(default)]
+    pub export: ExportOptions,
+    /// Directory for the artifacts, created if missing
+    pub output_directory: PathBuf,
+}
+
+/// Constellation config referenced by path or written inline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ScenarioConstellation {
+    File { config_file: PathBuf },
+    Inline(Box<ConstellationConfig>),
+}
+
+/// Analysis to run and the artifacts it writes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScenarioAnalysis {
+    /// All visibility windows, to `visibility_windows.json`
+    Visibility,
+    /// Contacts booked with the default scorer, to `contacts.json`
+    Contacts {
+        /// Link rate for contact plan exports; defaults to the satellite maximum
+        #[serde(default)]
+        data_rate_bps: Option<f64>,
+        /// Also write `contacts.int`
+        #[serde(default)]
+        stk_intervals: bool,
+        /// Also write `contacts.dtn`
+        #[serde(default)]
+        dtn_contact_plan: bool,
+    },
+    /// Pointing schedules for every window, to `pointing_schedules.json`
+    Pointing { cadence_seconds: f64 },
+    /// Coverage grid at the start time, to `coverage_grid.json`
+    Coverage {
+        #[serde(default)]
+        grid: CoverageGridConfig,
+    },
+    /// Latency grid from a gateway at the start time, to `latency_map_<gateway>.json`
+    Latency {
+        gateway_station_id: String,
+        resolution_deg: f64,
+    },
+    /// Candidate passes filtered out and why, to `rejected_passes.json`
+    RejectedPasses {
+        /// Also write `rejected_passes.csv`
+        #[serde(default)]
+        csv: bool,
+    },
+    /// Link availability per station and satellite, to `sla_report.json`
+    Sla {
+        #[serde(default = "default_sla_step_seconds")]
+        step_seconds: f64,
+        /// Also write `sla_report.md`
+        #[serde(default)]
+        markdown: bool,
+    },
+}
+
+/// Artifacts written by `run_scenario`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub artifacts: Vec<PathBuf>,
+}
+
+/// Load a scenario, detecting JSON/YAML/TOML by extension
+pub fn load_scenario<P: AsRef<Path>>(path: P) -> Result<Scenario> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).for_path(path)?;
+
+    parse_document(&content, ConfigFormat::from_path(path)).for_path(path)
+}
+
+/// Load a scenario file, run every analysis and write the requested artifacts
+pub fn run_scenario<P: AsRef<Path>>(path: P) -> Result<ScenarioReport> {
+    let path = path.as_ref();
+    let scenario = load_scenario(path)?;
+    let base_directory = path.parent().unwrap_or(Path::new("."));
+
+    scenario.run(base_directory)
+}
+
+impl Scenario {
+    /// Run every analysis, resolving relative paths against `base_directory`
+    pub fn run(&self, base_directory: &Path) -> Result<ScenarioReport> {
+        if !self.duration_hours.is_finite() || self.duration_hours <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Scenario duration must be positive, got {} h",
+                self.duration_hours
+            )));
+        }
+        self.faults.validate()?;
+
+        let config = match &self.constellation {
+            ScenarioConstellation::File { config_file } => {
+                load_constellation_config(base_directory.join(config_file))?
+            }
+            ScenarioConstellation::Inline(config) => (**config).clone(),
+        };
+        let mut engine = OrbitalMechanicsEngine::with_config_at_epoch(
+            config.clone(),
+            self.element_epoch.unwrap_or(self.start_time),
+        )?;
+        for station in config
+            .ground_station_config
+            .custom_stations
+            .iter()
+            .chain(&self.stations)
+        {
+            engine.add_ground_station(station.to_ground_station());
+        }
+
+        let output_directory = base_directory.join(&self.output_directory);
+        fs::create_dir_all(&output_directory).for_path(&output_directory)?;
+        let mut artifacts = Vec::new();
+        let mut write = |name: String, content: String| -> Result<()> {
+            let path = output_directory.join(name);
+            fs::write(&path, content).for_path(&path)?;
+            artifacts.push(path);
+            Ok(())
+        };
+
+        for analysis in &self.analyses {
+            match analysis {
+                ScenarioAnalysis::Visibility => {
+                    let windows = self.visibility_windows(&engine)?;
+                    write(
+                        "visibility_windows.json".to_string(),
+                        serde_json::to_string_pretty(&windows)?,
+                    )?;
+                }
+                ScenarioAnalysis::Contacts {
+                    data_rate_bps,
+                    stk_intervals,
+                    dtn_contact_plan,
+                } => {
+                    let windows = self.visibility_windows(&engine)?;
+                    let contacts =
+                        pass_scoring::schedule_contacts(windows, &engine.default_pass_scorer());
+                    write(
+                        "contacts.json".to_string(),
+                        serde_json::to_string_pretty(&contacts)?,
+                    )?;
+
+                    let data_rate_bps = data_rate_bps.unwrap_or(
+                        config
+                            .satellite_config
+                            .communication_config
+                            .max_data_rate_gbps
+                            * 1e9,
+                    );
+                    let plan = ContactPlan::from_scheduled(&contacts, data_rate_bps)
+                        .with_epoch(self.start_time);
+                    if *stk_intervals {
+                        write("contacts.int".to_string(), plan.to_stk_intervals())?;
+                    }
+                    if *dtn_contact_plan {
+                        write("contacts.dtn".to_string(), plan.to_dtn_contact_plan())?;
+                    }
+                }
+                ScenarioAnalysis::Pointing { cadence_seconds } => {
+                    let schedules = engine.generate_pointing_schedules(
+                        self.start_time,
+                        self.duration_hours,
+                        &PointingScheduleGenerator::with_cadence(*cadence_seconds),
+                    )?;
+                    write(
+                        "pointing_schedules.json".to_string(),
+                        serde_json::to_string_pretty(&schedules)?,
+                    )?;
+                }
+                ScenarioAnalysis::Coverage { grid } => {
+                    let grid = engine.coverage_grid(self.start_time, grid)?;
+                    write(
+                        "coverage_grid.json".to_string(),
+                        serde_json::to_string_pretty(&grid)?,
+                    )?;
+                }
+                ScenarioAnalysis::Latency {
+                    gateway_station_id,
+                    resolution_deg,
+                } => {
+                    let map =
+                        engine.latency_map(gateway_station_id, self.start_time, *resolution_deg)?;
+                    write(
+                        format!("latency_map_{}.json", gateway_station_id),
+                        serde_json::to_string_pretty(&map)?,
+                    )?;
+                }
+                ScenarioAnalysis::RejectedPasses { csv } => {
+                    let log = engine.audit_rejected_passes(self.start_time, self.duration_hours)?;
+                    write("rejected_passes.json".to_string(), log.to_json()?)?;
+                    if *csv {
+                        write(
+                            "rejected_passes.csv".to_string(),
+                            log.to_csv_with(&self.export),
+                        )?;
+                    }
+                }
+                ScenarioAnalysis::Sla {
+                    step_seconds,
+                    markdown,
+                } => {
+                    let windows = self.visibility_windows(&engine)?;
+                    let report = engine.sla_report_from_windows(
+                        &windows,
+                        self.start_time,
+                        self.duration_hours,
+                        *step_seconds,
+                    )?;
+                    write("sla_report.json".to_string(), report.to_json()?)?;
+                    if *markdown {
+                        write("sla_report.md".to_string(), report.to_markdown())?;
+                    }
+                }
+            }
+        }
+
+        tracing::info!(
+            target: crate::trace_targets::ENGINE,
+            scenario = %self.name,
+            artifacts = artifacts.len(),
+            "Scenario complete"
+        );
+
+        Ok(ScenarioReport {
+            name: self.name.clone(),
+            artifacts,
+        })
+    }
+
+    /// Windows under the injected faults in a fixed order, so scheduling ties
+    /// and artifacts are reproducible
+    fn visibility_windows(&self, engine: &OrbitalMechanicsEngine) -> Result<Vec<VisibilityWindow>> {
+        let windows =
+            engine.calculate_all_visibility_windows(self.start_time, self.duration_hours)?;
+        let mut windows = self.faults.apply_to_windows(windows);
+        windows.sort_by(|a, b| {
+            (a.start_time, &a.satellite_id, &a.station_id).cmp(&(
+                b.start_time,
+                &b.satellite_id,
+                &b.station_id,
+            ))
+        });
+        Ok(windows)
+    }
+}
+
+fn default_sla_step_seconds() -> f64 {
+    60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::save_constellation_config;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_run_toml_scenario() {
+        let dir = tempdir().unwrap();
+        let constellation = ConstellationConfig::custom_meo(6, 8000.0, 55.0, 3);
+        save_constellation_config(&constellation, dir.path().join("meo.yaml")).unwrap();
+
+        let scenario = r#"
+name = "Equatorial gateway"
+start_time = "2024-03-20T00:00:00Z"
+duration_hours = 6.0
+output_directory = "out"
+
+[constellation]
+config_file = "meo.yaml"
+
+[[stations]]
+station_id = "GS-EQ"
+name = "Equatorial"
+latitude_deg = 0.0
+longitude_deg = 0.0
+elevation_m = 0.0
+
+[[analyses]]
+type = "visibility"
+
+[[analyses]]
+type = "contacts"
+data_rate_bps = 1.0e9
+dtn_contact_plan = true
+
+[[analyses]]
+type = "coverage"
+grid = { resolution_deg = 10.0, min_elevation_deg = 10.0, backend = "Cpu" }
+
+[[analyses]]
+type = "rejected_passes"
+csv = true
+
+[[analyses]]
+type = "sla"
+step_seconds = 300.0
+markdown = true
+
+[[faults]]
+type = "station_offline"
+station_id = "GS-EQ"
+start_time = "2024-03-20T00:00:00Z"
+end_time = "2024-03-20T01:00:00Z"
+"#;
+        let path = dir.path().join("scenario.toml");
+        fs::write(&path, scenario).unwrap();
+
+        let report = run_scenario(&path).unwrap();
+        let names: Vec<_> = report
+            .artifacts
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "visibility_windows.json",
+                "contacts.json",
+                "contacts.dtn",
+                "coverage_grid.json",
+                "rejected_passes.json",
+                "rejected_passes.csv",
+                "sla_report.json",
+                "sla_report.md"
+            ]
+        );
+        let windows = fs::read_to_string(dir.path().join("out/visibility_windows.json")).unwrap();
+        assert!(windows.contains("GS-EQ"));
+        assert!(windows.contains("Station GS-EQ offline (injected fault)"));
+        let plan = fs::read_to_string(dir.path().join("out/contacts.dtn")).unwrap();
+        assert!(plan.contains("a contact +"));
+        let rejected = fs::read_to_string(dir.path().join("out/rejected_passes.csv")).unwrap();
+        assert!(rejected.starts_with("satellite_id,station_id,"));
+        let sla = fs::read_to_string(dir.path().join("out/sla_report.md")).unwrap();
+        assert!(sla.contains("| GS-EQ |"));
+        assert!(sla.contains("Station GS-EQ offline (injected fault)"));
+
+        // Unknown analysis types are rejected
+        fs::write(&path, scenario.replace("\"coverage\"", "\"weather\"")).unwrap();
+        assert!(run_scenario(&path).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Fast internal self-check for service deployments
+//!
+//! Runs a handful of computations with known answers and reports how far
+//! each result drifts from the truth embedded here: a Keplerian orbit must
+//! close on itself after one period, the J2 propagator must reproduce a
+//! reference state a day out, and the leap second and atmosphere tables must
+//! return their published values. A failure points at numerical breakage
+//! (a changed solver, a miscompiled math library) or corrupted table data
+//! rather than at user input. The whole check takes well under a
+//! millisecond, so it backs the `/healthz` probe of `metrics::router`.
+
+use crate::atmosphere::AtmosphereModel;
+use crate::constants::EARTH_MU;
+use crate::orbit::{OrbitalElements, SatelliteOrbit};
+use crate::propagator::{KeplerianPropagator, OrbitalPropagator, Sgp4Propagator};
+use crate::time::tai_minus_utc_seconds;
+use crate::trace_targets;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Outcome of one self-check item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfCheck {
+    pub name: String,
+    /// Distance from the embedded truth, in `unit`; infinite if the computation failed
+    pub drift: f64,
+    pub tolerance: f64,
+    pub unit: String,
+    pub passed: bool,
+    /// Error raised by the computation, if any
+    pub error: Option<String>,
+}
+
+/// Result of `self_check`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfCheckReport {
+    pub checked_at: DateTime<Utc>,
+    pub elapsed_ms: f64,
+    pub checks: Vec<SelfCheck>,
+}
+
+impl SelfCheck {
+    fn new(name: &str, drift: crate::error::Result<f64>, tolerance: f64, unit: &str) -> Self {
+        let (drift, error) = match drift {
+            Ok(drift) => (drift, None),
+            Err(err) => (f64::INFINITY, Some(err.to_string())),
+        };
+        Self {
+            name: name.to_string(),
+            drift,
+            tolerance,
+            unit: unit.to_string(),
+            // NaN drift fails too
+            passed: drift <= tolerance,
+            error,
+        }
+    }
+}
+
+impl SelfCheckReport {
+    /// Whether every check is within tolerance
+    pub fn healthy(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Checks outside tolerance
+    pub fn failures(&self) -> impl Iterator<Item = &SelfCheck> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+
+    /// HTTP status for a health probe: 200 when healthy, 503 otherwise
+    pub fn http_status(&self) -> u16 {
+        if self.healthy() {
+            200
+        } else {
+            503
+        }
+    }
+}
+
+/// Run every self-check against its embedded truth
+pub fn self_check() -> SelfCheckReport {
+    let started = Instant::now();
+    let checks = vec![
+        SelfCheck::new(
+            "keplerian_closure",
+            keplerian_closure_km(),
+            KEPLERIAN_CLOSURE_TOLERANCE_KM,
+            "km",
+        ),
+        SelfCheck::new(
+            "j2_reference_state",
+            j2_reference_drift_km(),
+            J2_REFERENCE_TOLERANCE_KM,
+            "km",
+        ),
+        SelfCheck::new("leap_seconds", Ok(leap_second_drift_s()), 0.0, "s"),
+        SelfCheck::new(
+            "atmosphere_table",
+            Ok(atmosphere_drift()),
+            ATMOSPHERE_TOLERANCE,
+            "relative",
+        ),
+    ];
+
+    let report = SelfCheckReport {
+        checked_at: Utc::now(),
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+        checks,
+    };
+    for check in report.failures() {
+        tracing::warn!(
+            target: trace_targets::ENGINE,
+            check = %check.name,
+            drift = check.drift,
+            tolerance = check.tolerance,
+            "Self-check failed"
+        );
+    }
+    report
+}
+
+/// Reference orbit for the propagation checks, with a whole-second period
+fn reference_orbit() -> crate::error::Result<SatelliteOrbit> {
+    let mean_motion = 2.0 * std::f64::consts::PI / REFERENCE_PERIOD_SECONDS as f64;
+    let semi_major_axis_km = (EARTH_MU / (mean_motion * mean_motion)).cbrt();
+    let elements = OrbitalElements::new(semi_major_axis_km, 0.001, 51.6, 30.0, 40.0, 0.0)?;
+    let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    Ok(SatelliteOrbit::new(
+        "SELF-CHECK".to_string(),
+        "Self-check reference".to_string(),
+        elements,
+        epoch,
+    ))
+}
+
+/// Position change of a Keplerian orbit after exactly one period
+fn keplerian_closure_km() -> crate::error::Result<f64> {
+    let orbit = reference_orbit()?;
+    let period = Duration::seconds(REFERENCE_PERIOD_SECONDS);
+    let propagator = KeplerianPropagator::new();
+    let start = propagator.propagate(&orbit, orbit.epoch)?;
+    let end = propagator.propagate(&orbit, orbit.epoch + period)?;
+    Ok(distance_km(&start.position_eci, &end.position_eci))
+}
+
+/// Distance of the J2 state one day out from the embedded reference
+fn j2_reference_drift_km() -> crate::error::Result<f64> {
+    let orbit = reference_orbit()?;
+    let state = Sgp4Propagator::new().propagate(&orbit, orbit.epoch + Duration::days(1))?;
+    Ok(distance_km(&state.position_eci, &J2_REFERENCE_POSITION_KM))
+}
+
+/// Largest error in TAI − UTC at the reference epochs
+fn leap_second_drift_s() -> f64 {
+    LEAP_SECOND_TRUTH
+        .iter()
+        .map(|&(timestamp, offset)| {
+            let time = Utc.timestamp_opt(timestamp, 0).unwrap();
+            (tai_minus_utc_seconds(time) - offset).abs() as f64
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Largest relative density error at the table base altitudes
+fn atmosphere_drift() -> f64 {
+    ATMOSPHERE_TRUTH
+        .iter()
+        .map(|&(altitude_km, density)| {
+            let model = AtmosphereModel::Exponential.density_kg_per_m3(altitude_km);
+            ((model - density) / density).abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+fn distance_km(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f64>().sqrt()
+}
+
+/// Period of the reference orbit, 90 minutes
+const REFERENCE_PERIOD_SECONDS: i64 = 5400;
+
+const KEPLERIAN_CLOSURE_TOLERANCE_KM: f64 = 1e-6;
+
+/// Reference orbit propagated one day with `Sgp4Propagator`
+const J2_REFERENCE_POSITION_KM: [f64; 3] =
+    [3084.238664400813, 4609.44702486282, 3661.7625253189744];
+
+const J2_REFERENCE_TOLERANCE_KM: f64 = 1e-6;
+
+/// (Unix time, TAI − UTC) at the first and last leap second and one between
+const LEAP_SECOND_TRUTH: [(i64, i32); 4] = [
+    (63_072_000, 10),    // 1972-01-01
+    (915_148_800, 32),   // 1999-01-01
+    (1_483_228_799, 36), // 2016-12-31T23:59:59
+    (1_483_228_800, 37), // 2017-01-01
+];
+
+/// (Altitude km, density kg/m³) from Vallado, Table 8-4
+const ATMOSPHERE_TRUTH: [(f64, f64); 4] = [
+    (0.0, 1.225),
+    (100.0, 5.297e-7),
+    (400.0, 3.725e-12),
+    (1000.0, 3.019e-15),
+];
+
+const ATMOSPHERE_TOLERANCE: f64 = 1e-9;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_check() {
+        let report = self_check();
+        for check in &report.checks {
+            assert!(check.passed, "{:?}", check);
+        }
+        assert!(report.healthy());
+        assert_eq!(report.http_status(), 200);
+        assert_eq!(report.checks.len(), 4);
+
+        let mut broken = report.clone();
+        broken.checks[1].drift = 3.0;
+        broken.checks[1].passed = false;
+        assert_eq!(broken.http_status(), 503);
+        assert_eq!(broken.failures().count(), 1);
+
+        let failed = SelfCheck::new("nan", Ok(f64::NAN), 1.0, "km");
+        assert!(!failed.passed);
+    }
+}
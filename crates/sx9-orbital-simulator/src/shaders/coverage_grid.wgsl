@@ -0,0 +1,49 @@
+// Visible satellite count per coverage grid cell
+//
+// One invocation per cell; cell centers and the elevation test mirror
+// `coverage_grid::cpu_counts`.
+
+struct Params {
+    rows: u32,
+    columns: u32,
+    satellite_count: u32,
+    row_stride: u32,
+    resolution_deg: f32,
+    sin_min_elevation: f32,
+    earth_radius_km: f32,
+    _padding: f32,
+}
+
+const DEG_TO_RAD: f32 = 0.017453292519943295;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> satellites: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> counts: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cell = id.x + id.y * params.row_stride;
+    if (cell >= params.rows * params.columns) {
+        return;
+    }
+
+    let row = cell / params.columns;
+    let column = cell % params.columns;
+    let latitude = min(-90.0 + (f32(row) + 0.5) * params.resolution_deg, 90.0) * DEG_TO_RAD;
+    let longitude = min(-180.0 + (f32(column) + 0.5) * params.resolution_deg, 180.0) * DEG_TO_RAD;
+    let up = vec3<f32>(
+        cos(latitude) * cos(longitude),
+        cos(latitude) * sin(longitude),
+        sin(latitude),
+    );
+    let origin = up * params.earth_radius_km;
+
+    var visible = 0u;
+    for (var i = 0u; i < params.satellite_count; i = i + 1u) {
+        let line_of_sight = satellites[i].xyz - origin;
+        if (dot(line_of_sight, up) >= params.sin_min_elevation * length(line_of_sight)) {
+            visible = visible + 1u;
+        }
+    }
+    counts[cell] = visible;
+}
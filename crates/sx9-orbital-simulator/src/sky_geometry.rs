@@ -0,0 +1,296 @@
+//! Sky geometry of the satellites visible from a station
+//!
+//! An optical mesh hands a station from satellite to satellite, and needs
+//! somewhere to go when the current link fades or a cloud bank drifts across
+//! its line of sight. When every visible satellite sits near the same
+//! azimuth, one obstruction takes them all out at once. Two metrics per
+//! epoch capture how well the visible satellites are spread:
+//!
+//! - Geometric dilution of precision (GDOP) of the line-of-sight unit
+//!   vectors, as in GNSS. It needs four satellites and grows without bound as
+//!   they cluster.
+//! - The largest azimuth gap between neighbouring satellites, which works
+//!   with any number and is 360° with fewer than two.
+//!
+//! Epochs failing either limit are grouped into poor-geometry intervals for
+//! handover planning to avoid.
+
+use crate::coordinates::EarthModel;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::{Matrix4, RowVector4};
+use serde::{Deserialize, Serialize};
+
+/// Spread of the visible satellites at one epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkyGeometry {
+    pub station_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub visible_satellite_ids: Vec<String>,
+    /// `None` with fewer than four satellites or a degenerate geometry
+    pub gdop: Option<f64>,
+    pub max_azimuth_gap_deg: f64,
+}
+
+/// Limits beyond which the geometry is too clustered for handover
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeometryLimits {
+    /// Largest acceptable GDOP; missing GDOP is only tolerated below four satellites
+    pub max_gdop: f64,
+    pub max_azimuth_gap_deg: f64,
+    pub min_visible: usize,
+}
+
+/// Run of consecutive epochs with poor geometry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoorGeometryInterval {
+    pub station_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub min_visible: usize,
+    /// Worst GDOP in the interval; `None` if it was undefined throughout
+    pub worst_gdop: Option<f64>,
+    pub max_azimuth_gap_deg: f64,
+}
+
+impl Default for GeometryLimits {
+    /// At least two satellites, with no azimuth gap wider than 180°
+    fn default() -> Self {
+        Self {
+            max_gdop: DEFAULT_MAX_GDOP,
+            max_azimuth_gap_deg: 180.0,
+            min_visible: 2,
+        }
+    }
+}
+
+impl SkyGeometry {
+    /// Geometry of `states` seen from `station` on `earth_model` at `timestamp`
+    ///
+    /// Satellites below `min_elevation_deg`, or the station's own mask when
+    /// set, are not counted.
+    pub fn at(
+        station: &GroundStation,
+        timestamp: DateTime<Utc>,
+        states: &[SatelliteState],
+        min_elevation_deg: f64,
+        earth_model: &EarthModel,
+    ) -> Self {
+        let mask_deg = station.min_elevation_deg.unwrap_or(min_elevation_deg);
+        let position = &station.position;
+        let visible: Vec<(&SatelliteState, f64, f64)> = states
+            .iter()
+            .filter_map(|state| {
+                let look = state.look_angles_from_station_with_model(
+                    earth_model,
+                    position.latitude_deg,
+                    position.longitude_deg,
+                    position.elevation_m,
+                );
+                (look.elevation_deg >= mask_deg).then_some((
+                    state,
+                    look.azimuth_deg,
+                    look.elevation_deg,
+                ))
+            })
+            .collect();
+
+        let directions: Vec<(f64, f64)> = visible.iter().map(|v| (v.1, v.2)).collect();
+        Self {
+            station_id: station.station_id.clone(),
+            timestamp,
+            visible_satellite_ids: visible
+                .iter()
+                .map(|(state, _, _)| state.satellite_id.clone())
+                .collect(),
+            gdop: gdop(&directions),
+            max_azimuth_gap_deg: max_azimuth_gap_deg(&directions),
+        }
+    }
+
+    /// Whether the geometry breaks any of `limits`
+    pub fn is_poor(&self, limits: &GeometryLimits) -> bool {
+        let count = self.visible_satellite_ids.len();
+        let gdop_poor = match self.gdop {
+            Some(gdop) => gdop > limits.max_gdop,
+            None => count >= 4,
+        };
+        count < limits.min_visible
+            || gdop_poor
+            || self.max_azimuth_gap_deg > limits.max_azimuth_gap_deg
+    }
+}
+
+/// Sky geometry from `station` every `step_seconds` from `start_time` to `end_time`
+#[allow(clippy::too_many_arguments)]
+pub fn sky_geometry_series(
+    station: &GroundStation,
+    satellites: &[SatelliteOrbit],
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    step_seconds: f64,
+    min_elevation_deg: f64,
+    earth_model: &EarthModel,
+    propagator: &dyn OrbitalPropagator,
+) -> Result<Vec<SkyGeometry>> {
+    if !step_seconds.is_finite() || step_seconds <= 0.0 {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Geometry step must be positive, got {} s",
+            step_seconds
+        )));
+    }
+
+    let step = Duration::milliseconds((step_seconds * 1000.0).round().max(1.0) as i64);
+    let mut series = Vec::new();
+    let mut time = start_time;
+    while time <= end_time {
+        let states = satellites
+            .iter()
+            .map(|satellite| {
+                propagator
+                    .propagate(satellite, time)
+                    .for_satellite(&satellite.satellite_id)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        series.push(SkyGeometry::at(
+            station,
+            time,
+            &states,
+            min_elevation_deg,
+            earth_model,
+        ));
+        time += step;
+    }
+    Ok(series)
+}
+
+/// Intervals where consecutive samples break `limits`
+pub fn poor_geometry_intervals(
+    series: &[SkyGeometry],
+    limits: &GeometryLimits,
+) -> Vec<PoorGeometryInterval> {
+    let mut intervals: Vec<PoorGeometryInterval> = Vec::new();
+    let mut in_poor = false;
+
+    for sample in series {
+        if !sample.is_poor(limits) {
+            in_poor = false;
+            continue;
+        }
+        let visible = sample.visible_satellite_ids.len();
+        match intervals.last_mut() {
+            Some(interval) if in_poor => {
+                interval.end_time = sample.timestamp;
+                interval.min_visible = interval.min_visible.min(visible);
+                interval.worst_gdop = match (interval.worst_gdop, sample.gdop) {
+                    (Some(worst), Some(gdop)) => Some(worst.max(gdop)),
+                    (worst, gdop) => worst.or(gdop),
+                };
+                interval.max_azimuth_gap_deg =
+                    interval.max_azimuth_gap_deg.max(sample.max_azimuth_gap_deg);
+            }
+            _ => intervals.push(PoorGeometryInterval {
+                station_id: sample.station_id.clone(),
+                start_time: sample.timestamp,
+                end_time: sample.timestamp,
+                min_visible: visible,
+                worst_gdop: sample.gdop,
+                max_azimuth_gap_deg: sample.max_azimuth_gap_deg,
+            }),
+        }
+        in_poor = true;
+    }
+
+    intervals
+}
+
+/// GDOP of (azimuth, elevation) directions in degrees
+fn gdop(directions: &[(f64, f64)]) -> Option<f64> {
+    if directions.len() < 4 {
+        return None;
+    }
+    let mut normal = Matrix4::<f64>::zeros();
+    for &(azimuth_deg, elevation_deg) in directions {
+        let (sin_az, cos_az) = azimuth_deg.to_radians().sin_cos();
+        let (sin_el, cos_el) = elevation_deg.to_radians().sin_cos();
+        let row = RowVector4::new(cos_el * sin_az, cos_el * cos_az, sin_el, 1.0);
+        normal += row.transpose() * row;
+    }
+    let trace = normal.try_inverse()?.trace();
+    (trace.is_finite() && trace > 0.0).then(|| trace.sqrt())
+}
+
+/// Largest azimuth gap between neighbouring directions
+fn max_azimuth_gap_deg(directions: &[(f64, f64)]) -> f64 {
+    if directions.len() < 2 {
+        return 360.0;
+    }
+    let mut azimuths: Vec<f64> = directions
+        .iter()
+        .map(|(azimuth_deg, _)| azimuth_deg.rem_euclid(360.0))
+        .collect();
+    azimuths.sort_by(f64::total_cmp);
+    let wrap_gap = azimuths[0] + 360.0 - azimuths[azimuths.len() - 1];
+    azimuths
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .fold(wrap_gap, f64::max)
+}
+
+/// GDOP above which the spread is too poor to fall back on
+const DEFAULT_MAX_GDOP: f64 = 10.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gdop_and_azimuth_gap() {
+        // Four satellites spread around the sky against four bunched in the east
+        let spread = [(0.0, 30.0), (120.0, 30.0), (240.0, 30.0), (0.0, 90.0)];
+        let bunched = [(80.0, 30.0), (90.0, 40.0), (100.0, 30.0), (90.0, 60.0)];
+        let good = gdop(&spread).unwrap();
+        let poor = gdop(&bunched).unwrap();
+        assert!(good > 1.0 && good < 4.0, "{}", good);
+        assert!(poor > 5.0 * good);
+        assert!(gdop(&spread[..3]).is_none());
+
+        assert!((max_azimuth_gap_deg(&spread) - 120.0).abs() < 1e-9);
+        assert!((max_azimuth_gap_deg(&bunched) - 340.0).abs() < 1e-9);
+        assert_eq!(max_azimuth_gap_deg(&spread[..1]), 360.0);
+        // The gap across north counts
+        assert!((max_azimuth_gap_deg(&[(350.0, 30.0), (10.0, 30.0)]) - 340.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_poor_geometry_intervals() {
+        let start = chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 1, 1, 0, 0, 0).unwrap();
+        let sample = |minute: i64, count: usize, gdop: Option<f64>, gap: f64| SkyGeometry {
+            station_id: "GS".to_string(),
+            timestamp: start + Duration::minutes(minute),
+            visible_satellite_ids: (0..count).map(|i| format!("SAT-{}", i)).collect(),
+            gdop,
+            max_azimuth_gap_deg: gap,
+        };
+        let series = [
+            sample(0, 5, Some(2.0), 100.0),
+            sample(1, 5, Some(15.0), 200.0),
+            sample(2, 3, None, 250.0),
+            sample(3, 4, Some(3.0), 120.0),
+            sample(4, 1, None, 360.0),
+        ];
+
+        let intervals = poor_geometry_intervals(&series, &GeometryLimits::default());
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].start_time, start + Duration::minutes(1));
+        assert_eq!(intervals[0].end_time, start + Duration::minutes(2));
+        assert_eq!(intervals[0].min_visible, 3);
+        assert_eq!(intervals[0].worst_gdop, Some(15.0));
+        assert_eq!(intervals[0].max_azimuth_gap_deg, 250.0);
+        assert_eq!(intervals[1].min_visible, 1);
+        assert!(intervals[1].worst_gdop.is_none());
+    }
+}
@@ -0,0 +1,510 @@
+//! Link availability SLA reports
+//!
+//! Rolls visibility windows over a long period, typically a month, up into
+//! per-station and per-satellite link availability. A station is available
+//! while it is up and has a usable link to at least one satellite; a
+//! satellite while it has a usable link to at least one station that is up.
+//! Outage time is attributed to its cause: the station calendar's outage or
+//! maintenance reason, the reason of the unusable interval covering every
+//! pass in view (keep-outs, injected faults), or no pass in view at all.
+//!
+//! Availability is sampled at a fixed step, so intervals shorter than the
+//! step may be missed or rounded up to it.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Link availability of one station or satellite over the report period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkAvailability {
+    pub id: String,
+    pub availability_percent: f64,
+    pub available_hours: f64,
+    pub outage_hours: f64,
+    /// Outage time by cause, largest first
+    pub outage_causes: Vec<OutageCause>,
+    /// UTC day with the lowest availability
+    pub worst_day: Option<DailyAvailability>,
+}
+
+/// Outage time attributed to one cause
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutageCause {
+    pub cause: String,
+    pub hours: f64,
+    /// Share of the entity's outage time
+    pub percent_of_outage: f64,
+}
+
+/// Availability over one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyAvailability {
+    pub date: NaiveDate,
+    pub availability_percent: f64,
+}
+
+/// Link availability SLA report for a constellation and its stations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlaReport {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub step_seconds: f64,
+    /// Mean of the station availabilities
+    pub mean_station_availability_percent: f64,
+    /// Mean of the satellite availabilities
+    pub mean_satellite_availability_percent: f64,
+    /// By station ID
+    pub stations: Vec<LinkAvailability>,
+    /// By satellite ID
+    pub satellites: Vec<LinkAvailability>,
+}
+
+/// Sample counts of one station or satellite
+#[derive(Default)]
+struct Tally {
+    available: usize,
+    total: usize,
+    causes: BTreeMap<String, usize>,
+    days: BTreeMap<NaiveDate, (usize, usize)>,
+}
+
+/// Windows of one station or satellite in view at increasing sample times
+struct WindowSweep<'a> {
+    windows: Vec<&'a VisibilityWindow>,
+    next: usize,
+    active: Vec<&'a VisibilityWindow>,
+}
+
+impl SlaReport {
+    /// Sample `windows` every `step_seconds` over `[start_time, end_time)`
+    ///
+    /// Windows of stations or satellites not listed are ignored; listed ones
+    /// without windows are reported with no availability.
+    pub fn from_windows<'a>(
+        windows: &[VisibilityWindow],
+        stations: impl IntoIterator<Item = &'a GroundStation>,
+        satellite_ids: impl IntoIterator<Item = &'a str>,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        step_seconds: f64,
+    ) -> Result<Self> {
+        if !step_seconds.is_finite() || step_seconds <= 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "SLA sampling step must be positive, got {} s",
+                step_seconds
+            )));
+        }
+        if end_time <= start_time {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "SLA period ends at {} before it starts at {}",
+                end_time, start_time
+            )));
+        }
+
+        let stations: BTreeMap<&str, &GroundStation> = stations
+            .into_iter()
+            .map(|station| (station.station_id.as_str(), station))
+            .collect();
+        let satellite_ids: Vec<&str> = {
+            let mut ids: Vec<&str> = satellite_ids.into_iter().collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids
+        };
+
+        let mut station_sweeps: HashMap<&str, WindowSweep> = stations
+            .keys()
+            .map(|&id| (id, WindowSweep::new(windows, |w| w.station_id == id)))
+            .collect();
+        let mut satellite_sweeps: HashMap<&str, WindowSweep> = satellite_ids
+            .iter()
+            .map(|&id| (id, WindowSweep::new(windows, |w| w.satellite_id == id)))
+            .collect();
+        let mut station_tallies: HashMap<&str, Tally> = HashMap::new();
+        let mut satellite_tallies: HashMap<&str, Tally> = HashMap::new();
+
+        let step =
+            Duration::milliseconds((step_seconds * 1000.0) as i64).max(Duration::milliseconds(1));
+        let mut time = start_time;
+        while time < end_time {
+            let down: HashMap<&str, String> = stations
+                .iter()
+                .filter_map(|(&id, station)| {
+                    let intervals = station.availability.unavailable_intervals(time, time);
+                    intervals
+                        .first()
+                        .map(|i| (id, format!("Station unavailable: {}", i.reason)))
+                })
+                .collect();
+
+            for &id in stations.keys() {
+                let in_view = station_sweeps.get_mut(id).unwrap().advance(time);
+                let outage = match down.get(id) {
+                    Some(reason) => Some(reason.clone()),
+                    None if in_view.iter().any(|w| usable_at(w, time)) => None,
+                    None => Some(
+                        in_view
+                            .iter()
+                            .find_map(|w| unusable_reason(w, time))
+                            .unwrap_or_else(|| NO_SATELLITE_IN_VIEW.to_string()),
+                    ),
+                };
+                station_tallies.entry(id).or_default().record(time, outage);
+            }
+
+            for &id in &satellite_ids {
+                let in_view = satellite_sweeps.get_mut(id).unwrap().advance(time);
+                let outage = if in_view
+                    .iter()
+                    .any(|w| usable_at(w, time) && !down.contains_key(w.station_id.as_str()))
+                {
+                    None
+                } else {
+                    Some(
+                        in_view
+                            .iter()
+                            .find_map(|w| {
+                                down.get(w.station_id.as_str())
+                                    .cloned()
+                                    .or_else(|| unusable_reason(w, time))
+                            })
+                            .unwrap_or_else(|| NO_STATION_IN_VIEW.to_string()),
+                    )
+                };
+                satellite_tallies
+                    .entry(id)
+                    .or_default()
+                    .record(time, outage);
+            }
+
+            time += step;
+        }
+
+        let summarize = |mut tallies: HashMap<&str, Tally>, ids: Vec<&str>| {
+            ids.into_iter()
+                .map(|id| {
+                    tallies
+                        .remove(id)
+                        .unwrap_or_default()
+                        .summarize(id, step_seconds)
+                })
+                .collect::<Vec<_>>()
+        };
+        let stations = summarize(station_tallies, stations.keys().copied().collect());
+        let satellites = summarize(satellite_tallies, satellite_ids);
+
+        Ok(Self {
+            start_time,
+            end_time,
+            step_seconds,
+            mean_station_availability_percent: mean_availability(&stations),
+            mean_satellite_availability_percent: mean_availability(&satellites),
+            stations,
+            satellites,
+        })
+    }
+
+    /// Availability of one station
+    pub fn station(&self, station_id: &str) -> Option<&LinkAvailability> {
+        self.stations.iter().find(|entry| entry.id == station_id)
+    }
+
+    /// Availability of one satellite
+    pub fn satellite(&self, satellite_id: &str) -> Option<&LinkAvailability> {
+        self.satellites
+            .iter()
+            .find(|entry| entry.id == satellite_id)
+    }
+
+    /// Render the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render the report as Markdown, with a summary table per station and
+    /// satellite followed by their outage causes
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Link availability SLA report\n\n");
+        let _ = writeln!(
+            md,
+            "Period {} to {} ({:.1} days), sampled every {} s.\n",
+            self.start_time.format("%Y-%m-%d %H:%M:%SZ"),
+            self.end_time.format("%Y-%m-%d %H:%M:%SZ"),
+            (self.end_time - self.start_time).num_seconds() as f64 / 86_400.0,
+            self.step_seconds
+        );
+        let _ = writeln!(
+            md,
+            "Mean availability: stations {:.3}%, satellites {:.3}%.\n",
+            self.mean_station_availability_percent, self.mean_satellite_availability_percent
+        );
+        write_markdown_section(&mut md, "Stations", "Station", &self.stations);
+        write_markdown_section(&mut md, "Satellites", "Satellite", &self.satellites);
+        md
+    }
+
+    /// Write the report to a JSON file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?).for_path(path)
+    }
+
+    /// Write the report to a Markdown file
+    pub fn write_markdown<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_markdown()).for_path(path)
+    }
+}
+
+impl Tally {
+    fn record(&mut self, time: DateTime<Utc>, outage: Option<String>) {
+        let day = self.days.entry(time.date_naive()).or_default();
+        day.1 += 1;
+        self.total += 1;
+        match outage {
+            None => {
+                day.0 += 1;
+                self.available += 1;
+            }
+            Some(cause) => *self.causes.entry(cause).or_insert(0) += 1,
+        }
+    }
+
+    fn summarize(self, id: &str, step_seconds: f64) -> LinkAvailability {
+        let hours = |samples: usize| samples as f64 * step_seconds / 3600.0;
+        let percent = |part: usize, whole: usize| {
+            if whole == 0 {
+                0.0
+            } else {
+                100.0 * part as f64 / whole as f64
+            }
+        };
+        let outage_samples = self.total - self.available;
+
+        let mut outage_causes: Vec<OutageCause> = self
+            .causes
+            .into_iter()
+            .map(|(cause, samples)| OutageCause {
+                cause,
+                hours: hours(samples),
+                percent_of_outage: percent(samples, outage_samples),
+            })
+            .collect();
+        // Stable sort keeps ties in cause order
+        outage_causes.sort_by(|a, b| b.hours.total_cmp(&a.hours));
+
+        let worst_day = self
+            .days
+            .into_iter()
+            .map(|(date, (available, total))| DailyAvailability {
+                date,
+                availability_percent: percent(available, total),
+            })
+            .reduce(|worst, day| {
+                if day.availability_percent < worst.availability_percent {
+                    day
+                } else {
+                    worst
+                }
+            });
+
+        LinkAvailability {
+            id: id.to_string(),
+            availability_percent: percent(self.available, self.total),
+            available_hours: hours(self.available),
+            outage_hours: hours(outage_samples),
+            outage_causes,
+            worst_day,
+        }
+    }
+}
+
+impl<'a> WindowSweep<'a> {
+    fn new(windows: &'a [VisibilityWindow], keep: impl Fn(&VisibilityWindow) -> bool) -> Self {
+        let mut windows: Vec<&VisibilityWindow> = windows.iter().filter(|w| keep(w)).collect();
+        windows.sort_by_key(|w| w.start_time);
+        Self {
+            windows,
+            next: 0,
+            active: Vec::new(),
+        }
+    }
+
+    /// Windows covering `time`; times must not decrease between calls
+    fn advance(&mut self, time: DateTime<Utc>) -> &[&'a VisibilityWindow] {
+        while let Some(window) = self.windows.get(self.next) {
+            if window.start_time > time {
+                break;
+            }
+            self.active.push(window);
+            self.next += 1;
+        }
+        self.active.retain(|w| w.end_time >= time);
+        &self.active
+    }
+}
+
+fn usable_at(window: &VisibilityWindow, time: DateTime<Utc>) -> bool {
+    unusable_reason(window, time).is_none()
+}
+
+fn unusable_reason(window: &VisibilityWindow, time: DateTime<Utc>) -> Option<String> {
+    window
+        .unusable_intervals
+        .iter()
+        .find(|i| i.start_time <= time && time < i.end_time)
+        .map(|i| i.reason.clone())
+}
+
+fn mean_availability(entries: &[LinkAvailability]) -> f64 {
+    if entries.is_empty() {
+        return 0.0;
+    }
+    entries.iter().map(|e| e.availability_percent).sum::<f64>() / entries.len() as f64
+}
+
+fn write_markdown_section(md: &mut String, title: &str, label: &str, entries: &[LinkAvailability]) {
+    let _ = writeln!(md, "## {}\n", title);
+    let _ = writeln!(
+        md,
+        "| {} | Availability | Outage (h) | Worst day | Worst-day availability |",
+        label
+    );
+    md.push_str("|---|---:|---:|---|---:|\n");
+    for entry in entries {
+        let (worst_date, worst_percent) = entry.worst_day.as_ref().map_or_else(
+            || ("-".to_string(), "-".to_string()),
+            |day| {
+                (
+                    day.date.to_string(),
+                    format!("{:.3}%", day.availability_percent),
+                )
+            },
+        );
+        let _ = writeln!(
+            md,
+            "| {} | {:.3}% | {:.2} | {} | {} |",
+            entry.id, entry.availability_percent, entry.outage_hours, worst_date, worst_percent
+        );
+    }
+    md.push('\n');
+
+    for entry in entries.iter().filter(|e| !e.outage_causes.is_empty()) {
+        let _ = writeln!(md, "### {} outage causes\n", entry.id);
+        for cause in &entry.outage_causes {
+            let _ = writeln!(
+                md,
+                "- {}: {:.2} h ({:.1}%)",
+                cause.cause, cause.hours, cause.percent_of_outage
+            );
+        }
+        md.push('\n');
+    }
+}
+
+const NO_SATELLITE_IN_VIEW: &str = "No satellite in view";
+const NO_STATION_IN_VIEW: &str = "No station in view";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_station::StationOutage;
+    use crate::visibility::{PassType, UnusableInterval};
+    use chrono::TimeZone;
+
+    fn window(
+        satellite_id: &str,
+        station_id: &str,
+        start: DateTime<Utc>,
+        hours: i64,
+    ) -> VisibilityWindow {
+        let end = start + Duration::hours(hours);
+        VisibilityWindow {
+            satellite_id: satellite_id.to_string(),
+            station_id: station_id.to_string(),
+            start_time: start,
+            end_time: end,
+            duration_seconds: (end - start).num_seconds() as f64,
+            max_elevation_time: start,
+            max_elevation_deg: 45.0,
+            min_range_km: 8000.0,
+            mean_range_km: 9000.0,
+            azimuth_span_deg: 90.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_sla_report() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        let mut station = GroundStation::builder()
+            .station_id("GS-A")
+            .latitude_deg(0.0)
+            .longitude_deg(0.0)
+            .build()
+            .unwrap();
+        station.availability.outages.push(StationOutage {
+            start_time: t0 + Duration::hours(30),
+            end_time: t0 + Duration::hours(36),
+            reason: "Power work".to_string(),
+        });
+
+        // Day 1: 12 h in view, 2 h of it blocked; day 2: in view all day
+        let mut first = window("SAT-1", "GS-A", t0, 12);
+        first.unusable_intervals.push(UnusableInterval {
+            start_time: t0 + Duration::hours(4),
+            end_time: t0 + Duration::hours(6),
+            reason: "Sun keep-out".to_string(),
+        });
+        let windows = vec![first, window("SAT-1", "GS-A", t0 + Duration::hours(24), 24)];
+
+        let report = SlaReport::from_windows(
+            &windows,
+            [&station],
+            ["SAT-1", "SAT-2"],
+            t0,
+            t0 + Duration::days(2),
+            60.0,
+        )
+        .unwrap();
+
+        let gs = report.station("GS-A").unwrap();
+        // 10 h usable on day 1, 18 h on day 2 (window end sample included)
+        assert!((gs.available_hours - 28.0).abs() < 0.05, "{:?}", gs);
+        let causes: Vec<&str> = gs.outage_causes.iter().map(|c| c.cause.as_str()).collect();
+        assert_eq!(
+            causes,
+            [
+                NO_SATELLITE_IN_VIEW,
+                "Station unavailable: Power work",
+                "Sun keep-out"
+            ]
+        );
+        let worst = gs.worst_day.as_ref().unwrap();
+        assert_eq!(worst.date, t0.date_naive());
+
+        let sat = report.satellite("SAT-1").unwrap();
+        assert!((sat.availability_percent - gs.availability_percent).abs() < 1e-9);
+        let idle = report.satellite("SAT-2").unwrap();
+        assert_eq!(idle.availability_percent, 0.0);
+        assert_eq!(idle.outage_causes[0].cause, NO_STATION_IN_VIEW);
+
+        let md = report.to_markdown();
+        assert!(md.contains("| GS-A |"));
+        assert!(md.contains("- Station unavailable: Power work: 6.02 h"));
+        assert!(report.to_json().unwrap().contains("\"worst_day\""));
+
+        assert!(SlaReport::from_windows(&windows, [&station], ["SAT-1"], t0, t0, 60.0).is_err());
+    }
+}
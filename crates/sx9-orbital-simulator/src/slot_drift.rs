@@ -0,0 +1,372 @@
+//! Slot drift monitoring for station-keeping
+//!
+//! Compares each slotted satellite against the two-body motion of its
+//! assigned slot center and reports along-track deviation over time, with
+//! warning/alarm levels for station-keeping operations.
+
+use crate::constellation::{Constellation, PlaneId, SlotId};
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::health;
+use crate::propagator::{KeplerianPropagator, OrbitalPropagator};
+use crate::trace_targets;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Along-track deviation limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotDriftThresholds {
+    /// Deviation at which station-keeping should be planned
+    pub warning_deg: f64,
+    /// Deviation at which the satellite is out of its slot
+    pub alarm_deg: f64,
+}
+
+/// Severity of a slot deviation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DriftAlarmLevel {
+    Nominal,
+    Warning,
+    Alarm,
+}
+
+/// Deviation from the slot center at one time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotDriftSample {
+    pub timestamp: DateTime<Utc>,
+    /// Signed along-track angle; positive is ahead of the slot
+    pub along_track_deg: f64,
+    /// Along-track arc length at the slot radius
+    pub along_track_km: f64,
+    pub level: DriftAlarmLevel,
+}
+
+/// Deviation crossing into a more severe level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotDriftAlarm {
+    pub timestamp: DateTime<Utc>,
+    pub level: DriftAlarmLevel,
+    pub along_track_deg: f64,
+}
+
+/// Deviation history of one satellite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatelliteSlotDrift {
+    pub satellite_id: String,
+    pub plane: PlaneId,
+    pub slot: SlotId,
+    pub samples: Vec<SlotDriftSample>,
+    pub max_abs_deviation_deg: f64,
+    /// Least-squares along-track drift rate; negative is falling behind
+    pub drift_rate_deg_per_day: f64,
+    pub alarms: Vec<SlotDriftAlarm>,
+}
+
+/// Slot deviation of every slotted satellite over a time span
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotDriftReport {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub step_seconds: f64,
+    pub thresholds: SlotDriftThresholds,
+    /// Ordered by plane, then slot
+    pub satellites: Vec<SatelliteSlotDrift>,
+}
+
+/// Slot drift analysis
+#[derive(Debug, Clone)]
+pub struct SlotDriftMonitor {
+    pub thresholds: SlotDriftThresholds,
+}
+
+impl SlotDriftThresholds {
+    /// Level for a signed deviation
+    pub fn level(&self, along_track_deg: f64) -> DriftAlarmLevel {
+        let deviation = along_track_deg.abs();
+        if deviation >= self.alarm_deg {
+            DriftAlarmLevel::Alarm
+        } else if deviation >= self.warning_deg {
+            DriftAlarmLevel::Warning
+        } else {
+            DriftAlarmLevel::Nominal
+        }
+    }
+}
+
+impl Default for SlotDriftThresholds {
+    fn default() -> Self {
+        Self {
+            warning_deg: 0.5,
+            alarm_deg: 1.0,
+        }
+    }
+}
+
+impl SatelliteSlotDrift {
+    /// Most severe level reached
+    pub fn worst_level(&self) -> DriftAlarmLevel {
+        self.samples
+            .iter()
+            .map(|s| s.level)
+            .max()
+            .unwrap_or(DriftAlarmLevel::Nominal)
+    }
+}
+
+impl SlotDriftReport {
+    /// Satellites that reached at least `level`
+    pub fn satellites_at_or_above(
+        &self,
+        level: DriftAlarmLevel,
+    ) -> impl Iterator<Item = &SatelliteSlotDrift> {
+        self.satellites
+            .iter()
+            .filter(move |s| s.worst_level() >= level)
+    }
+
+    /// Drift history of one satellite
+    pub fn satellite(&self, satellite_id: &str) -> Option<&SatelliteSlotDrift> {
+        self.satellites
+            .iter()
+            .find(|s| s.satellite_id == satellite_id)
+    }
+}
+
+impl SlotDriftMonitor {
+    /// Create monitor with default thresholds
+    pub fn new() -> Self {
+        Self::with_thresholds(SlotDriftThresholds::default())
+    }
+
+    /// Create monitor with custom thresholds
+    pub fn with_thresholds(thresholds: SlotDriftThresholds) -> Self {
+        Self { thresholds }
+    }
+
+    /// Sample the slot deviation of every slotted satellite
+    ///
+    /// Satellites without a slot assignment are skipped.
+    pub fn analyze(
+        &self,
+        constellation: &Constellation,
+        propagator: &dyn OrbitalPropagator,
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        step_seconds: f64,
+    ) -> Result<SlotDriftReport> {
+        if !step_seconds.is_finite()
+            || step_seconds < 0.001
+            || !duration_hours.is_finite()
+            || duration_hours < 0.0
+        {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Slot drift analysis needs a step of at least 1 ms and a finite, non-negative duration, got {} s over {} h",
+                step_seconds, duration_hours
+            )));
+        }
+
+        let end_time = start_time + Duration::milliseconds((duration_hours * 3.6e6) as i64);
+        let step = Duration::milliseconds((step_seconds * 1000.0).round() as i64);
+        let nominal_propagator = KeplerianPropagator::new();
+
+        let mut satellites = Vec::new();
+        for satellite in constellation.satellites() {
+            let Some(assignment) = &satellite.slot else {
+                continue;
+            };
+            let nominal_orbit = assignment.nominal_orbit(&satellite.satellite_id);
+
+            let mut samples = Vec::new();
+            let mut alarms = Vec::new();
+            let mut previous_level = DriftAlarmLevel::Nominal;
+            let mut time = start_time;
+            while time <= end_time {
+                let nominal = nominal_propagator
+                    .propagate(&nominal_orbit, time)
+                    .for_satellite(&satellite.satellite_id)
+                    .at_epoch(time)?;
+                let actual = propagator
+                    .propagate(satellite, time)
+                    .for_satellite(&satellite.satellite_id)
+                    .at_epoch(time)?;
+
+                let along_track_deg = health::along_track_offset_deg(&nominal, &actual);
+                let level = self.thresholds.level(along_track_deg);
+                if level > previous_level {
+                    alarms.push(SlotDriftAlarm {
+                        timestamp: time,
+                        level,
+                        along_track_deg,
+                    });
+                }
+                previous_level = level;
+
+                samples.push(SlotDriftSample {
+                    timestamp: time,
+                    along_track_deg,
+                    along_track_km: along_track_deg.to_radians() * nominal.orbital_radius,
+                    level,
+                });
+                time += step;
+            }
+
+            satellites.push(SatelliteSlotDrift {
+                satellite_id: satellite.satellite_id.clone(),
+                plane: assignment.plane,
+                slot: assignment.slot,
+                max_abs_deviation_deg: samples
+                    .iter()
+                    .map(|s| s.along_track_deg.abs())
+                    .fold(0.0, f64::max),
+                drift_rate_deg_per_day: drift_rate_deg_per_day(&samples, start_time),
+                samples,
+                alarms,
+            });
+        }
+        satellites.sort_by_key(|s| (s.plane, s.slot));
+
+        tracing::debug!(
+            target: trace_targets::ENGINE,
+            satellites = satellites.len(),
+            alarms = satellites.iter().map(|s| s.alarms.len()).sum::<usize>(),
+            "Slot drift analysis complete"
+        );
+
+        Ok(SlotDriftReport {
+            start_time,
+            end_time,
+            step_seconds,
+            thresholds: self.thresholds.clone(),
+            satellites,
+        })
+    }
+}
+
+impl Default for SlotDriftMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Least-squares slope of deviation against time
+fn drift_rate_deg_per_day(samples: &[SlotDriftSample], start_time: DateTime<Utc>) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| {
+            let days = (s.timestamp - start_time).num_milliseconds() as f64 / 86_400_000.0;
+            (days, s.along_track_deg)
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_t = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_d = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(t, d)| (t - mean_t) * (d - mean_d))
+        .sum();
+    let variance: f64 = points.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+    if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConstellationType;
+    use crate::constellation::SlotAssignment;
+    use crate::orbit::{OrbitalElements, SatelliteOrbit};
+
+    #[test]
+    fn test_drifting_satellite_raises_alarms() {
+        let epoch = Utc::now();
+        let mut constellation = Constellation::new(
+            "Drift".to_string(),
+            "Drift test".to_string(),
+            ConstellationType::Custom { satellites: vec![] },
+        );
+
+        let on_station = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        constellation
+            .add_satellite(
+                SatelliteOrbit::new("SK-01".to_string(), "Kept".to_string(), on_station, epoch)
+                    .with_slot(PlaneId(0), SlotId(0)),
+            )
+            .unwrap();
+
+        // Raised 5 km above its slot: slower mean motion, falls behind
+        let raised = OrbitalElements::new(14383.0, 0.0, 55.0, 0.0, 0.0, 90.0).unwrap();
+        let mut drifter =
+            SatelliteOrbit::new("SK-02".to_string(), "Drifter".to_string(), raised, epoch);
+        drifter.slot = Some(SlotAssignment {
+            plane: PlaneId(0),
+            slot: SlotId(1),
+            nominal_elements: OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 90.0).unwrap(),
+            nominal_epoch: epoch,
+        });
+        constellation.add_satellite(drifter).unwrap();
+
+        let report = SlotDriftMonitor::new()
+            .analyze(
+                &constellation,
+                &KeplerianPropagator::new(),
+                epoch,
+                72.0,
+                3600.0,
+            )
+            .unwrap();
+        assert_eq!(report.satellites.len(), 2);
+
+        let kept = report.satellite("SK-01").unwrap();
+        assert!(kept.max_abs_deviation_deg < 1e-9);
+        assert!(kept.alarms.is_empty());
+
+        let drifter = report.satellite("SK-02").unwrap();
+        assert!(drifter.drift_rate_deg_per_day < 0.0);
+        assert!(drifter.samples.last().unwrap().along_track_deg < -1.0);
+        let levels: Vec<DriftAlarmLevel> = drifter.alarms.iter().map(|a| a.level).collect();
+        assert_eq!(
+            levels,
+            vec![DriftAlarmLevel::Warning, DriftAlarmLevel::Alarm]
+        );
+        assert_eq!(
+            report
+                .satellites_at_or_above(DriftAlarmLevel::Alarm)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_rejects_degenerate_step_and_duration() {
+        let constellation = Constellation::new(
+            "Empty".to_string(),
+            "Validation test".to_string(),
+            ConstellationType::Custom { satellites: vec![] },
+        );
+        let monitor = SlotDriftMonitor::new();
+        let propagator = KeplerianPropagator::new();
+        let epoch = Utc::now();
+
+        for (duration_hours, step_seconds) in [
+            (1.0, f64::NAN),
+            (1.0, 0.0004),
+            (f64::NAN, 60.0),
+            (f64::INFINITY, 60.0),
+        ] {
+            assert!(monitor
+                .analyze(
+                    &constellation,
+                    &propagator,
+                    epoch,
+                    duration_hours,
+                    step_seconds
+                )
+                .is_err());
+        }
+    }
+}
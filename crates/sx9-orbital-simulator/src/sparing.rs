@@ -0,0 +1,328 @@
+//! In-plane spare replacement after a satellite failure
+//!
+//! A satellite fails at a given time; after a response delay, a spare parked
+//! in the same plane drifts into the failed satellite's slot using the
+//! phasing planner. The simulation steps from the failure to the spare's
+//! arrival, comparing global coverage against the operational constellation
+//! at each step. The result is the coverage gap duration and the delta-v
+//! spent, so spares, parking positions and drift altitudes can be compared.
+//!
+//! Spares are not part of the operational constellation: nominal coverage
+//! counts every satellite except the spare. While drifting, the spare is
+//! placed on its parking orbit shifted in phase at the plan's drift rate;
+//! the few kilometres of altitude change are ignored for coverage.
+
+use crate::constellation::Constellation;
+use crate::coordinates::wrap_angle_deg;
+use crate::coverage_grid::{CoverageGrid, CoverageGridConfig};
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use crate::phasing::{phase_difference_deg, plan_phase_change, PhasingPlan};
+use crate::propagator::OrbitalPropagator;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Failure and the spare sent to replace it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SparingScenario {
+    pub failed_satellite_id: String,
+    pub failure_time: DateTime<Utc>,
+    pub spare_satellite_id: String,
+    /// Altitude change of the drift orbit
+    pub drift_altitude_km: f64,
+    /// Detection and planning time before the first burn
+    pub response_hours: f64,
+    pub step_minutes: f64,
+    /// Coverage shortfall below nominal that counts as a gap, in percentage points
+    pub tolerance_percent: f64,
+    pub min_satellites: u32,
+    pub grid: CoverageGridConfig,
+}
+
+/// Outcome of one sparing scenario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SparingOutcome {
+    pub failed_satellite_id: String,
+    pub spare_satellite_id: String,
+    pub failure_time: DateTime<Utc>,
+    pub first_burn_time: DateTime<Utc>,
+    pub on_station_time: DateTime<Utc>,
+    pub plan: PhasingPlan,
+    /// Sampled time with coverage short of nominal
+    pub coverage_gap_seconds: f64,
+    /// Largest shortfall from nominal coverage, in percentage points
+    pub worst_coverage_deficit_percent: f64,
+}
+
+impl SparingScenario {
+    /// Replace `failed_satellite_id` with `spare_satellite_id`, one day's
+    /// response and a 10 km drift orbit, sampled every 10 minutes
+    pub fn new(
+        failed_satellite_id: impl Into<String>,
+        failure_time: DateTime<Utc>,
+        spare_satellite_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            failed_satellite_id: failed_satellite_id.into(),
+            failure_time,
+            spare_satellite_id: spare_satellite_id.into(),
+            drift_altitude_km: 10.0,
+            response_hours: 24.0,
+            step_minutes: 10.0,
+            tolerance_percent: 0.5,
+            min_satellites: 1,
+            grid: CoverageGridConfig::default().with_resolution(5.0),
+        }
+    }
+
+    pub fn with_drift_altitude(mut self, drift_altitude_km: f64) -> Self {
+        self.drift_altitude_km = drift_altitude_km;
+        self
+    }
+
+    pub fn with_response_hours(mut self, response_hours: f64) -> Self {
+        self.response_hours = response_hours;
+        self
+    }
+
+    pub fn with_grid(mut self, grid: CoverageGridConfig) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    /// Run the failure and repositioning against `constellation`
+    pub fn simulate(
+        &self,
+        constellation: &Constellation,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<SparingOutcome> {
+        let lookup = |id: &str| {
+            constellation
+                .get_satellite(id)
+                .ok_or_else(|| OrbitalMechanicsError::SatelliteNotFound(id.to_string()))
+        };
+        let failed = lookup(&self.failed_satellite_id)?;
+        let spare = lookup(&self.spare_satellite_id)?;
+        self.validate(failed, spare)?;
+
+        let first_burn_time =
+            self.failure_time + Duration::seconds((self.response_hours * 3600.0) as i64);
+        let propagate = |orbit: &SatelliteOrbit, time| {
+            propagator
+                .propagate(orbit, time)
+                .for_satellite(&orbit.satellite_id)
+                .at_epoch(time)
+        };
+        let target = failed
+            .slot
+            .as_ref()
+            .map(|slot| slot.nominal_orbit(&failed.satellite_id))
+            .unwrap_or_else(|| failed.clone());
+        let phase_change_deg = phase_difference_deg(
+            &propagate(spare, first_burn_time)?,
+            &propagate(&target, first_burn_time)?,
+        );
+        let plan = plan_phase_change(&spare.elements, phase_change_deg, self.drift_altitude_km)?;
+        let on_station_time = first_burn_time + Duration::seconds(plan.drift_seconds as i64);
+
+        let operational: Vec<&SatelliteOrbit> = constellation
+            .satellites()
+            .filter(|s| s.satellite_id != self.spare_satellite_id)
+            .collect();
+        let step = Duration::seconds((self.step_minutes * 60.0) as i64);
+        let mut gap_seconds = 0.0;
+        let mut worst_deficit: f64 = 0.0;
+        let mut time = self.failure_time;
+        while time < on_station_time {
+            let nominal = operational
+                .iter()
+                .map(|s| propagate(s, time))
+                .collect::<Result<Vec<SatelliteState>>>()?;
+            let mut degraded: Vec<SatelliteState> = nominal
+                .iter()
+                .filter(|state| state.satellite_id != self.failed_satellite_id)
+                .cloned()
+                .collect();
+            let progress = if time < first_burn_time {
+                0.0
+            } else {
+                (time - first_burn_time).num_seconds() as f64 / plan.drift_seconds
+            };
+            degraded.push(propagate(
+                &shifted(spare, phase_change_deg * progress.min(1.0)),
+                time,
+            )?);
+
+            let coverage =
+                |states: &[SatelliteState]| -> Result<f64> {
+                    Ok(CoverageGrid::compute(states, &self.grid)?
+                        .coverage_percent(self.min_satellites))
+                };
+            let deficit = coverage(&nominal)? - coverage(&degraded)?;
+            worst_deficit = worst_deficit.max(deficit);
+            if deficit > self.tolerance_percent {
+                gap_seconds += (on_station_time - time).min(step).num_seconds() as f64;
+            }
+            time += step;
+        }
+
+        tracing::debug!(
+            failed = %self.failed_satellite_id,
+            spare = %self.spare_satellite_id,
+            phase_change_deg,
+            delta_v_m_per_s = plan.delta_v_m_per_s,
+            gap_hours = gap_seconds / 3600.0,
+            "Sparing scenario simulated"
+        );
+        Ok(SparingOutcome {
+            failed_satellite_id: self.failed_satellite_id.clone(),
+            spare_satellite_id: self.spare_satellite_id.clone(),
+            failure_time: self.failure_time,
+            first_burn_time,
+            on_station_time,
+            plan,
+            coverage_gap_seconds: gap_seconds,
+            worst_coverage_deficit_percent: worst_deficit,
+        })
+    }
+
+    fn validate(&self, failed: &SatelliteOrbit, spare: &SatelliteOrbit) -> Result<()> {
+        if failed.satellite_id == spare.satellite_id {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Satellite {} cannot spare itself",
+                spare.satellite_id
+            )));
+        }
+        let valid = self.step_minutes.is_finite()
+            && self.step_minutes >= 1.0 / 60.0
+            && self.response_hours.is_finite()
+            && self.response_hours >= 0.0;
+        if !valid {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Invalid sparing timing: step {} min, response {} h",
+                self.step_minutes, self.response_hours
+            )));
+        }
+
+        let raan_offset = wrap_angle_deg(spare.elements.raan_deg - failed.elements.raan_deg);
+        let inclination_offset = spare.elements.inclination_deg - failed.elements.inclination_deg;
+        if raan_offset.abs() > PLANE_TOLERANCE_DEG || inclination_offset.abs() > PLANE_TOLERANCE_DEG
+        {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Spare {} is outside the plane of {} (RAAN {:+.2}°, inclination {:+.2}°)",
+                spare.satellite_id, failed.satellite_id, raan_offset, inclination_offset
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Outcomes of several scenarios, shortest coverage gap first, then least delta-v
+pub fn compare_sparing_strategies(
+    scenarios: &[SparingScenario],
+    constellation: &Constellation,
+    propagator: &dyn OrbitalPropagator,
+) -> Result<Vec<SparingOutcome>> {
+    let mut outcomes = scenarios
+        .iter()
+        .map(|scenario| scenario.simulate(constellation, propagator))
+        .collect::<Result<Vec<_>>>()?;
+    outcomes.sort_by(|a, b| {
+        a.coverage_gap_seconds
+            .total_cmp(&b.coverage_gap_seconds)
+            .then(a.plan.delta_v_m_per_s.total_cmp(&b.plan.delta_v_m_per_s))
+    });
+    Ok(outcomes)
+}
+
+/// `orbit` moved `phase_deg` ahead along its track
+fn shifted(orbit: &SatelliteOrbit, phase_deg: f64) -> SatelliteOrbit {
+    let mut orbit = orbit.clone();
+    orbit.elements.mean_anomaly_deg =
+        (orbit.elements.mean_anomaly_deg + phase_deg).rem_euclid(360.0);
+    orbit
+}
+
+/// Largest RAAN or inclination offset for a spare to count as in-plane
+const PLANE_TOLERANCE_DEG: f64 = 1.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConstellationType;
+    use crate::constants::EARTH_RADIUS_KM;
+    use crate::constellation::{PlaneId, SlotId};
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_spare_repositioning() {
+        let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut constellation = Constellation::new(
+            "Walker".to_string(),
+            "Sparing test".to_string(),
+            ConstellationType::Custom { satellites: vec![] },
+        );
+        // Walker 24/6/1 at 1200 km
+        for plane in 0..6u32 {
+            for slot in 0..4u32 {
+                let elements = OrbitalElements::new(
+                    EARTH_RADIUS_KM + 1200.0,
+                    0.0,
+                    53.0,
+                    60.0 * plane as f64,
+                    0.0,
+                    90.0 * slot as f64 + 15.0 * plane as f64,
+                )
+                .unwrap();
+                let id = format!("SAT-{}{}", plane, slot);
+                let orbit = SatelliteOrbit::new(id.clone(), id, elements, epoch)
+                    .with_slot(PlaneId(plane), SlotId(slot));
+                constellation.add_satellite(orbit).unwrap();
+            }
+        }
+        // Park a spare 45° behind the second slot of plane 0
+        let slot = constellation
+            .satellite_in_slot(PlaneId(0), SlotId(1))
+            .unwrap()
+            .clone();
+        let failed_id = slot.satellite_id.clone();
+        let mut spare = shifted(&slot, -45.0);
+        spare.satellite_id = "SPARE-P0".to_string();
+        spare.slot = None;
+        constellation.add_satellite(spare).unwrap();
+
+        let propagator = KeplerianPropagator::new();
+        let mut scenario = SparingScenario::new(failed_id.clone(), epoch, "SPARE-P0")
+            .with_response_hours(6.0)
+            .with_grid(CoverageGridConfig::default().with_resolution(10.0));
+        scenario.step_minutes = 30.0;
+        let outcome = scenario.simulate(&constellation, &propagator).unwrap();
+        assert!((outcome.plan.phase_change_deg - 45.0).abs() < 1e-6);
+        assert!(outcome.plan.delta_v_m_per_s > 0.0);
+        assert_eq!(
+            outcome.on_station_time - outcome.first_burn_time,
+            Duration::seconds(outcome.plan.drift_seconds as i64)
+        );
+        assert!(outcome.worst_coverage_deficit_percent > 0.5);
+        let span = (outcome.on_station_time - epoch).num_seconds() as f64;
+        assert!(outcome.coverage_gap_seconds > 0.0 && outcome.coverage_gap_seconds <= span);
+
+        // A lower drift orbit arrives sooner at a higher delta-v cost
+        let fast = scenario.clone().with_drift_altitude(40.0);
+        let outcomes =
+            compare_sparing_strategies(&[scenario.clone(), fast], &constellation, &propagator)
+                .unwrap();
+        assert!(outcomes[0].on_station_time < outcomes[1].on_station_time);
+        assert!(outcomes[0].plan.delta_v_m_per_s > outcomes[1].plan.delta_v_m_per_s);
+
+        let other_plane = constellation
+            .satellite_in_slot(PlaneId(1), SlotId(0))
+            .unwrap()
+            .satellite_id
+            .clone();
+        let wrong = SparingScenario::new(other_plane, epoch, "SPARE-P0");
+        assert!(wrong.simulate(&constellation, &propagator).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Star calibration windows for optical terminals
+//!
+//! On-orbit calibration of an optical terminal points it at a bright star.
+//! `star_calibration_windows` finds the intervals when a chosen star lies
+//! inside a terminal's field of view and nothing is in the way: the Earth
+//! (with a limb margin for the atmosphere), the Sun and the Moon.
+//!
+//! As for star trackers, satellites are taken to fly nadir-pointing, so the
+//! terminal boresight is fixed in the orbital RSW frame. Star positions are
+//! J2000 and are used as ECI directions without precession, which shifts
+//! them by a few tenths of a degree today; stars are far enough away that
+//! the satellite's position does not matter.
+
+use crate::constants::*;
+use crate::ephemeris;
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::orbit::{SatelliteOrbit, SatelliteState};
+use crate::propagator::OrbitalPropagator;
+use crate::star_tracker::{rsw_to_eci, unit_boresight};
+use chrono::{DateTime, Duration, Utc};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+/// Catalog star
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogStar {
+    pub name: String,
+    /// J2000
+    pub right_ascension_deg: f64,
+    /// J2000
+    pub declination_deg: f64,
+    pub visual_magnitude: f64,
+}
+
+/// Optical terminal used for star calibration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationTerminal {
+    pub terminal_id: String,
+    /// Unit boresight in the RSW frame
+    pub boresight_rsw: [f64; 3],
+    /// Half-angle of the field of view around the boresight
+    pub field_of_view_deg: f64,
+    pub sun_exclusion_deg: f64,
+    pub moon_exclusion_deg: f64,
+    /// Minimum height of the star above the Earth's limb, covering the atmosphere
+    pub earth_limb_exclusion_deg: f64,
+}
+
+/// Interval when a star is in the field of view and unobstructed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarCalibrationWindow {
+    pub satellite_id: String,
+    pub terminal_id: String,
+    pub star: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub duration_seconds: f64,
+    /// Smallest angle between the boresight and the star during the window
+    pub min_boresight_offset_deg: f64,
+}
+
+impl CatalogStar {
+    pub fn new(
+        name: impl Into<String>,
+        right_ascension_deg: f64,
+        declination_deg: f64,
+        visual_magnitude: f64,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            right_ascension_deg,
+            declination_deg,
+            visual_magnitude,
+        }
+    }
+
+    /// Unit direction to the star in ECI
+    pub fn direction_eci(&self) -> [f64; 3] {
+        let ra = self.right_ascension_deg * DEG_TO_RAD;
+        let dec = self.declination_deg * DEG_TO_RAD;
+        [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()]
+    }
+}
+
+/// Star from the embedded bright-star catalog, by name (case-insensitive)
+pub fn bright_star(name: &str) -> Option<CatalogStar> {
+    bright_stars().find(|star| star.name.eq_ignore_ascii_case(name))
+}
+
+/// Embedded catalog of the brightest navigation stars, brightest first
+pub fn bright_stars() -> impl Iterator<Item = CatalogStar> {
+    BRIGHT_STARS
+        .iter()
+        .map(|&(name, ra, dec, magnitude)| CatalogStar::new(name, ra, dec, magnitude))
+}
+
+impl CalibrationTerminal {
+    /// Terminal with a 1° field of view and the star tracker's typical
+    /// exclusion angles (Sun 30°, Moon 15°, Earth limb 20°)
+    ///
+    /// The boresight is normalized; a zero or non-finite boresight is an error.
+    pub fn new(terminal_id: impl Into<String>, boresight_rsw: [f64; 3]) -> Result<Self> {
+        let terminal_id = terminal_id.into();
+        let boresight_rsw = unit_boresight(&terminal_id, boresight_rsw)?;
+        Ok(Self {
+            terminal_id,
+            boresight_rsw,
+            field_of_view_deg: 1.0,
+            sun_exclusion_deg: 30.0,
+            moon_exclusion_deg: 15.0,
+            earth_limb_exclusion_deg: 20.0,
+        })
+    }
+
+    pub fn with_field_of_view(mut self, field_of_view_deg: f64) -> Self {
+        self.field_of_view_deg = field_of_view_deg;
+        self
+    }
+
+    /// Angle from the boresight to the star when it is in the field of view
+    /// and unobstructed
+    pub fn usable_offset_deg(&self, state: &SatelliteState, star: &CatalogStar) -> Option<f64> {
+        let direction = Vector3::from(star.direction_eci());
+        let offset_deg =
+            rsw_to_eci(state, self.boresight_rsw.into()).angle(&direction) * RAD_TO_DEG;
+        if offset_deg > self.field_of_view_deg {
+            return None;
+        }
+
+        let position = Vector3::from(state.position_eci);
+        let earth_angular_radius_deg =
+            (EARTH_RADIUS_KM / position.norm()).min(1.0).asin() * RAD_TO_DEG;
+        let limb_separation_deg =
+            direction.angle(&-position) * RAD_TO_DEG - earth_angular_radius_deg;
+        if limb_separation_deg < self.earth_limb_exclusion_deg {
+            return None;
+        }
+
+        let bodies = [
+            (
+                ephemeris::sun_position_eci(state.timestamp),
+                self.sun_exclusion_deg,
+            ),
+            (
+                ephemeris::moon_position_eci(state.timestamp),
+                self.moon_exclusion_deg,
+            ),
+        ];
+        let blocked = bodies.iter().any(|&(body, exclusion_deg)| {
+            direction.angle(&(Vector3::from(body) - position)) * RAD_TO_DEG < exclusion_deg
+        });
+        (!blocked).then_some(offset_deg)
+    }
+}
+
+/// Intervals over `duration_hours` when `star` is usable by `terminal`,
+/// sampled every `step_seconds`
+///
+/// Window edges are the first and last usable samples.
+pub fn star_calibration_windows(
+    satellite: &SatelliteOrbit,
+    terminal: &CalibrationTerminal,
+    star: &CatalogStar,
+    start_time: DateTime<Utc>,
+    duration_hours: f64,
+    step_seconds: f64,
+    propagator: &dyn OrbitalPropagator,
+) -> Result<Vec<StarCalibrationWindow>> {
+    if !step_seconds.is_finite() || step_seconds < 1.0 {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Star calibration step must be at least 1 s, got {} s",
+            step_seconds
+        )));
+    }
+    let end_time = start_time + Duration::milliseconds((duration_hours * 3.6e6) as i64);
+    let step = Duration::milliseconds((step_seconds * 1000.0) as i64);
+
+    let mut windows = Vec::new();
+    let mut current: Option<StarCalibrationWindow> = None;
+    let mut time = start_time;
+    while time <= end_time {
+        let state = propagator
+            .propagate(satellite, time)
+            .for_satellite(&satellite.satellite_id)
+            .at_epoch(time)?;
+        match (terminal.usable_offset_deg(&state, star), &mut current) {
+            (Some(offset_deg), Some(window)) => {
+                window.end_time = time;
+                window.min_boresight_offset_deg = window.min_boresight_offset_deg.min(offset_deg);
+            }
+            (Some(offset_deg), None) => {
+                current = Some(StarCalibrationWindow {
+                    satellite_id: satellite.satellite_id.clone(),
+                    terminal_id: terminal.terminal_id.clone(),
+                    star: star.name.clone(),
+                    start_time: time,
+                    end_time: time,
+                    duration_seconds: 0.0,
+                    min_boresight_offset_deg: offset_deg,
+                });
+            }
+            (None, _) => windows.extend(current.take()),
+        }
+        time += step;
+    }
+    windows.extend(current);
+
+    for window in &mut windows {
+        window.duration_seconds =
+            (window.end_time - window.start_time).num_milliseconds() as f64 / 1000.0;
+    }
+    Ok(windows)
+}
+
+/// (Name, J2000 right ascension °, declination °, visual magnitude)
+const BRIGHT_STARS: [(&str, f64, f64, f64); 18] = [
+    ("Sirius", 101.2872, -16.7161, -1.46),
+    ("Canopus", 95.9880, -52.6957, -0.74),
+    ("Arcturus", 213.9153, 19.1824, -0.05),
+    ("Vega", 279.2347, 38.7837, 0.03),
+    ("Capella", 79.1723, 45.9980, 0.08),
+    ("Rigel", 78.6345, -8.2016, 0.13),
+    ("Procyon", 114.8255, 5.2250, 0.34),
+    ("Betelgeuse", 88.7929, 7.4071, 0.42),
+    ("Achernar", 24.4285, -57.2368, 0.46),
+    ("Altair", 297.6958, 8.8683, 0.76),
+    ("Aldebaran", 68.9802, 16.5093, 0.86),
+    ("Antares", 247.3519, -26.4320, 0.96),
+    ("Spica", 201.2983, -11.1613, 0.97),
+    ("Pollux", 116.3290, 28.0262, 1.14),
+    ("Fomalhaut", 344.4127, -29.6222, 1.16),
+    ("Deneb", 310.3580, 45.2803, 1.25),
+    ("Regulus", 152.0930, 11.9672, 1.35),
+    ("Polaris", 37.9546, 89.2641, 1.98),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_star_calibration_windows() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "CAL-1".to_string(),
+            "Calibration".to_string(),
+            elements,
+            start,
+        );
+        let propagator = KeplerianPropagator::new();
+        let windows = |terminal: &CalibrationTerminal, star: &str| {
+            star_calibration_windows(
+                &satellite,
+                terminal,
+                &bright_star(star).unwrap(),
+                start,
+                12.0,
+                60.0,
+                &propagator,
+            )
+            .unwrap()
+        };
+
+        // Equatorial orbit: the orbit normal points at the celestial pole
+        let normal = CalibrationTerminal::new("FSO-1", [0.0, 0.0, 1.0]).unwrap();
+        let polaris = windows(&normal, "polaris");
+        assert_eq!(polaris.len(), 1);
+        assert_eq!(polaris[0].duration_seconds, 12.0 * 3600.0);
+        assert!(polaris[0].min_boresight_offset_deg < 1.0);
+        assert!(windows(&normal, "Vega").is_empty());
+
+        // A wide along-track view sees Sirius for part of each orbit
+        let along_track = CalibrationTerminal::new("FSO-2", [0.0, 1.0, 0.0])
+            .unwrap()
+            .with_field_of_view(90.0);
+        let sirius = windows(&along_track, "Sirius");
+        assert!(sirius.len() >= 2, "{:?}", sirius);
+        assert!(sirius.iter().all(|w| w.duration_seconds < 12.0 * 3600.0));
+
+        assert!(bright_star("Betelgeuse").is_some());
+        assert!(bright_star("Nemesis").is_none());
+
+        assert!(CalibrationTerminal::new("FSO-3", [0.0, 0.0, 0.0]).is_err());
+        assert!(CalibrationTerminal::new("FSO-4", [0.0, f64::NAN, 1.0]).is_err());
+    }
+}
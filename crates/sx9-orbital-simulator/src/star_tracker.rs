@@ -0,0 +1,196 @@
+//! Star tracker blinding constraints
+//!
+//! Checks star tracker boresights against Sun, Moon and Earth-limb exclusion
+//! cones. There is no attitude model yet: satellites are taken to fly
+//! nadir-pointing, so each boresight is fixed in the orbital RSW frame
+//! (radial, along-track, orbit normal).
+
+use crate::constants::*;
+use crate::ephemeris;
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::SatelliteState;
+use nalgebra::{Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Bright object that can blind a star tracker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BlindingSource {
+    Sun,
+    Moon,
+    EarthLimb,
+}
+
+/// Star tracker head and its exclusion angles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarTracker {
+    pub tracker_id: String,
+    /// Unit boresight in the RSW frame
+    pub boresight_rsw: [f64; 3],
+    pub sun_exclusion_deg: f64,
+    pub moon_exclusion_deg: f64,
+    /// Minimum angle between the boresight and the Earth's limb
+    pub earth_limb_exclusion_deg: f64,
+}
+
+/// Exclusion cone violated at one instant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarTrackerViolation {
+    pub tracker_id: String,
+    pub source: BlindingSource,
+    /// Angle from the boresight to the source; negative for the Earth limb
+    /// when the boresight points at the Earth's disc
+    pub separation_deg: f64,
+    pub exclusion_deg: f64,
+}
+
+impl StarTracker {
+    /// Tracker with typical exclusion angles (Sun 30°, Moon 15°, Earth limb 20°)
+    ///
+    /// The boresight is normalized; a zero or non-finite boresight is an error.
+    pub fn new(tracker_id: impl Into<String>, boresight_rsw: [f64; 3]) -> Result<Self> {
+        let tracker_id = tracker_id.into();
+        let boresight_rsw = unit_boresight(&tracker_id, boresight_rsw)?;
+        Ok(Self {
+            tracker_id,
+            boresight_rsw,
+            sun_exclusion_deg: 30.0,
+            moon_exclusion_deg: 15.0,
+            earth_limb_exclusion_deg: 20.0,
+        })
+    }
+
+    /// Boresight in ECI for a nadir-pointing satellite
+    pub fn boresight_eci(&self, state: &SatelliteState) -> [f64; 3] {
+        rsw_to_eci(state, self.boresight_rsw.into()).into()
+    }
+
+    /// Every exclusion cone the boresight is inside
+    ///
+    /// The Sun and Moon only count while they are above the Earth's limb as
+    /// seen from the satellite.
+    pub fn violations(&self, state: &SatelliteState) -> Vec<StarTrackerViolation> {
+        let boresight = rsw_to_eci(state, self.boresight_rsw.into());
+        let position = Vector3::from(state.position_eci);
+        let nadir = -position;
+        let earth_angular_radius_deg =
+            (EARTH_RADIUS_KM / position.norm()).min(1.0).asin() * RAD_TO_DEG;
+
+        let mut violations = Vec::new();
+        let mut check = |source, separation_deg: f64, exclusion_deg: f64| {
+            if separation_deg < exclusion_deg {
+                violations.push(StarTrackerViolation {
+                    tracker_id: self.tracker_id.clone(),
+                    source,
+                    separation_deg,
+                    exclusion_deg,
+                });
+            }
+        };
+
+        let bodies = [
+            (
+                BlindingSource::Sun,
+                Vector3::from(ephemeris::sun_position_eci(state.timestamp)),
+                self.sun_exclusion_deg,
+            ),
+            (
+                BlindingSource::Moon,
+                Vector3::from(ephemeris::moon_position_eci(state.timestamp)),
+                self.moon_exclusion_deg,
+            ),
+        ];
+        for (source, body, exclusion_deg) in bodies {
+            let direction = body - position;
+            if direction.angle(&nadir) * RAD_TO_DEG > earth_angular_radius_deg {
+                check(
+                    source,
+                    boresight.angle(&direction) * RAD_TO_DEG,
+                    exclusion_deg,
+                );
+            }
+        }
+
+        check(
+            BlindingSource::EarthLimb,
+            boresight.angle(&nadir) * RAD_TO_DEG - earth_angular_radius_deg,
+            self.earth_limb_exclusion_deg,
+        );
+
+        violations
+    }
+}
+
+/// Normalize a boresight, rejecting vectors with no usable direction
+pub(crate) fn unit_boresight(id: &str, boresight: [f64; 3]) -> Result<[f64; 3]> {
+    let boresight = Vector3::from(boresight);
+    let norm = boresight.norm();
+    if !norm.is_normal() {
+        return Err(OrbitalMechanicsError::config_error(format!(
+            "Boresight of {} must be a finite non-zero vector, got {:?}",
+            id,
+            <[f64; 3]>::from(boresight)
+        )));
+    }
+    Ok((boresight / norm).into())
+}
+
+/// Rotate a vector from the satellite's RSW frame into ECI
+pub(crate) fn rsw_to_eci(state: &SatelliteState, vector_rsw: Vector3<f64>) -> Vector3<f64> {
+    let position = Vector3::from(state.position_eci);
+    let radial = position.normalize();
+    let normal = position
+        .cross(&Vector3::from(state.velocity_eci))
+        .normalize();
+    let along_track = normal.cross(&radial);
+
+    Matrix3::from_columns(&[radial, along_track, normal]) * vector_rsw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_exclusion_cones() {
+        // Equinox noon: the Sun lies close to +x, straight above the satellite
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 12, 0, 0).unwrap();
+        let state = SatelliteState::new(
+            "ST-01".to_string(),
+            time,
+            [14378.0, 0.0, 0.0],
+            [0.0, 5.265, 0.0],
+        );
+        let sources = |tracker: StarTracker| -> Vec<BlindingSource> {
+            tracker
+                .violations(&state)
+                .iter()
+                .map(|v| v.source)
+                .collect()
+        };
+
+        let zenith = StarTracker::new("ST-ZENITH", [1.0, 0.0, 0.0]).unwrap();
+        assert!(sources(zenith).contains(&BlindingSource::Sun));
+
+        // Nadir boresight looks straight into the Earth's disc
+        let nadir = StarTracker::new("ST-NADIR", [-1.0, 0.0, 0.0]).unwrap();
+        let violations = nadir.violations(&state);
+        let limb = violations
+            .iter()
+            .find(|v| v.source == BlindingSource::EarthLimb)
+            .unwrap();
+        assert!((limb.separation_deg + 26.3).abs() < 0.1);
+        assert!(!violations.iter().any(|v| v.source == BlindingSource::Sun));
+
+        // Orbit-normal boresight is ~64° off the limb and 90° from the Sun
+        let normal = StarTracker::new("ST-NORMAL", [0.0, 0.0, 2.0]).unwrap();
+        assert!((Vector3::from(normal.boresight_rsw).norm() - 1.0).abs() < 1e-12);
+        let normal_sources = sources(normal);
+        assert!(!normal_sources.contains(&BlindingSource::Sun));
+        assert!(!normal_sources.contains(&BlindingSource::EarthLimb));
+
+        assert!(StarTracker::new("ST-ZERO", [0.0, 0.0, 0.0]).is_err());
+        assert!(StarTracker::new("ST-NAN", [f64::NAN, 0.0, 1.0]).is_err());
+        assert!(StarTracker::new("ST-INF", [f64::INFINITY, 0.0, 0.0]).is_err());
+    }
+}
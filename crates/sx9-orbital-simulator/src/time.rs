@@ -0,0 +1,98 @@
+//! Time scales and leap seconds
+//!
+//! Timestamps throughout the crate are UTC `DateTime`s, but UTC stops for a
+//! leap second while satellites keep moving. Arithmetic that has to follow
+//! the physics, such as interpolating between recorded states, counts SI
+//! seconds through TAI, which has no leap seconds. chrono holds an instant
+//! inside a leap second as 23:59:59 with a fractional second of 1 or more.
+//!
+//! Exports:
+//! - Text formats (OEM, CSV, JSON) keep such epochs exact and print them as
+//!   `23:59:60.xxx`.
+//! - POSIX timestamp columns (Parquet/Arrow) cannot represent 23:59:60.
+//!   Epochs inside a leap second are written as `23:59:59.999999` (see
+//!   `posix_micros`), so the column stays ordered and never collides with
+//!   the following second. Use a text export when those samples matter.
+//!
+//! Leap seconds come from `earth_data`: the embedded IERS table, or a newer
+//! one fetched into the data cache when the process opts in. Later epochs
+//! use the final offset and earlier ones the 1972 offset.
+
+use crate::earth_data::{earth_data, EarthData};
+use chrono::{DateTime, Timelike, Utc};
+
+/// TAI − UTC in seconds at `time`
+///
+/// The offset steps at 00:00:00 UTC after each leap second, so 23:59:60
+/// still has the old value.
+pub fn tai_minus_utc_seconds(time: DateTime<Utc>) -> i32 {
+    earth_data().tai_minus_utc_seconds(time)
+}
+
+/// Whether `time` falls inside an inserted leap second (23:59:60)
+pub fn is_leap_second(time: DateTime<Utc>) -> bool {
+    time.nanosecond() >= 1_000_000_000
+}
+
+/// SI seconds from `start` to `end`, counting any leap seconds between them
+pub fn elapsed_seconds(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    elapsed_seconds_with(earth_data(), start, end)
+}
+
+fn elapsed_seconds_with(data: &EarthData, start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    let leap_seconds = data.tai_minus_utc_seconds(end) - data.tai_minus_utc_seconds(start);
+    let whole = end.timestamp() - start.timestamp() + i64::from(leap_seconds);
+    // Includes the extra second chrono stores for 23:59:60
+    let nanos = i64::from(end.nanosecond()) - i64::from(start.nanosecond());
+    whole as f64 + nanos as f64 / 1e9
+}
+
+/// Microseconds since the Unix epoch for POSIX timestamp columns
+///
+/// An instant inside a leap second becomes `23:59:59.999999`.
+pub fn posix_micros(time: DateTime<Utc>) -> i64 {
+    if is_leap_second(time) {
+        time.timestamp() * 1_000_000 + 999_999
+    } else {
+        time.timestamp_micros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn test_elapsed_time_across_leap_second() {
+        let before = Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).unwrap();
+        let after = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 1_500)
+            .unwrap()
+            .and_utc();
+        // Embedded tables, independent of the user's data cache
+        let data = EarthData::embedded();
+
+        assert_eq!(data.tai_minus_utc_seconds(before), 36);
+        assert_eq!(data.tai_minus_utc_seconds(leap), 36);
+        assert_eq!(data.tai_minus_utc_seconds(after), 37);
+        assert_eq!(
+            data.tai_minus_utc_seconds(Utc.with_ymd_and_hms(1965, 1, 1, 0, 0, 0).unwrap()),
+            10
+        );
+
+        // 23:59:59 -> 23:59:60 -> 00:00:00 is two seconds
+        assert_eq!(elapsed_seconds_with(&data, before, after), 2.0);
+        assert_eq!(elapsed_seconds_with(&data, before, leap), 1.5);
+        assert_eq!(elapsed_seconds_with(&data, leap, after), 0.5);
+        assert_eq!(elapsed_seconds_with(&data, after, before), -2.0);
+        assert_eq!(leap.format("%H:%M:%S%.3f").to_string(), "23:59:60.500");
+
+        assert!(is_leap_second(leap) && !is_leap_second(after));
+        assert!(posix_micros(before) < posix_micros(leap));
+        assert!(posix_micros(leap) < posix_micros(after));
+        assert_eq!(posix_micros(after), after.timestamp_micros());
+    }
+}
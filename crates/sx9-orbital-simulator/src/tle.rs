@@ -0,0 +1,668 @@
+//! Two-line element sets
+//!
+//! Reads and writes NORAD two-line element sets (TLEs) in the fixed column
+//! layout, with an optional title line. Checksums are verified on input and
+//! written on output.
+//!
+//! TLE elements are SGP4 mean elements. `Tle::to_orbit` treats them as
+//! two-body elements, taking the semi-major axis from the mean motion by
+//! Kepler's third law; positions propagated from them drift from SGP4 by a
+//! few kilometres per day in LEO.
+//!
+//! The fixed columns limit a round trip through text to 1e-4° in the angles,
+//! 1e-7 in eccentricity, 1e-8 rev/day in mean motion and 1e-8 day (under a
+//! millisecond) in the epoch.
+
+use crate::constants::{EARTH_MU, SOLAR_DAY_SECONDS, TWO_PI};
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::{OrbitalElements, SatelliteOrbit};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Length of a TLE data line including its checksum
+const LINE_LENGTH: usize = 69;
+
+/// One element set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tle {
+    /// Title line, if the set had one
+    pub name: Option<String>,
+    pub catalog_number: u32,
+    /// `U`, `C` or `S`
+    pub classification: char,
+    /// Launch year, number and piece, e.g. `98067A`
+    pub international_designator: String,
+    pub epoch: DateTime<Utc>,
+    /// First derivative of mean motion over two, rev/day²
+    pub mean_motion_dot: f64,
+    /// Second derivative of mean motion over six, rev/day³
+    pub mean_motion_ddot: f64,
+    /// SGP4 drag term in inverse Earth radii
+    pub bstar: f64,
+    pub element_set_number: u32,
+    pub inclination_deg: f64,
+    pub raan_deg: f64,
+    pub eccentricity: f64,
+    pub argument_of_perigee_deg: f64,
+    pub mean_anomaly_deg: f64,
+    pub mean_motion_rev_per_day: f64,
+    pub revolution_number: u32,
+}
+
+/// Parse every element set in a file, with or without title lines
+pub fn parse_tles(text: &str) -> Result<Vec<Tle>> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut tles = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let titled = !lines[index].starts_with("1 ");
+        let count = if titled { 3 } else { 2 };
+        let set = lines.get(index..index + count).ok_or_else(|| {
+            OrbitalMechanicsError::invalid_elements(format!(
+                "TLE starting at line {} is truncated",
+                index + 1
+            ))
+        })?;
+        tles.push(Tle::parse(&set.join("\n"))?);
+        index += count;
+    }
+    Ok(tles)
+}
+
+impl Tle {
+    /// Parse one element set of two lines, or three with a title
+    pub fn parse(text: &str) -> Result<Self> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        match lines.as_slice() {
+            [line1, line2] => Self::parse_lines(line1, line2),
+            [title, line1, line2] => {
+                let name = title.strip_prefix("0 ").unwrap_or(title).trim();
+                let mut tle = Self::parse_lines(line1, line2)?;
+                tle.name = Some(name.to_string());
+                Ok(tle)
+            }
+            _ => Err(OrbitalMechanicsError::invalid_elements(format!(
+                "TLE needs two or three lines, got {}",
+                lines.len()
+            ))),
+        }
+    }
+
+    /// Parse the two data lines
+    pub fn parse_lines(line1: &str, line2: &str) -> Result<Self> {
+        let line1 = DataLine::new(line1, 1)?;
+        let line2 = DataLine::new(line2, 2)?;
+
+        let catalog_number = line1.integer(3, 7, "catalog number")?;
+        let line2_catalog_number = line2.integer(3, 7, "catalog number")?;
+        if catalog_number != line2_catalog_number {
+            return Err(OrbitalMechanicsError::invalid_elements(format!(
+                "TLE catalog numbers differ: {} on line 1, {} on line 2",
+                catalog_number, line2_catalog_number
+            )));
+        }
+
+        let tle = Self {
+            name: None,
+            catalog_number,
+            classification: line1.field(8, 8)?.chars().next().unwrap_or('U'),
+            international_designator: line1.field(10, 17)?.trim().to_string(),
+            epoch: line1.epoch()?,
+            mean_motion_dot: line1.decimal(34, 43, "mean motion derivative")?,
+            mean_motion_ddot: line1.exponent(45, 52, "mean motion second derivative")?,
+            bstar: line1.exponent(54, 61, "BSTAR")?,
+            element_set_number: line1.integer(65, 68, "element set number")?,
+            inclination_deg: line2.decimal(9, 16, "inclination")?,
+            raan_deg: line2.decimal(18, 25, "RAAN")?,
+            eccentricity: line2.assumed_decimal(27, 33, "eccentricity")?,
+            argument_of_perigee_deg: line2.decimal(35, 42, "argument of perigee")?,
+            mean_anomaly_deg: line2.decimal(44, 51, "mean anomaly")?,
+            mean_motion_rev_per_day: line2.decimal(53, 63, "mean motion")?,
+            revolution_number: line2.integer(64, 68, "revolution number")?,
+        };
+
+        if !(0.0..=180.0).contains(&tle.inclination_deg) {
+            return Err(OrbitalMechanicsError::invalid_elements(format!(
+                "TLE inclination {}° outside [0, 180]°",
+                tle.inclination_deg
+            )));
+        }
+        for (name, angle) in [
+            ("RAAN", tle.raan_deg),
+            ("argument of perigee", tle.argument_of_perigee_deg),
+            ("mean anomaly", tle.mean_anomaly_deg),
+        ] {
+            if !(0.0..=360.0).contains(&angle) {
+                return Err(OrbitalMechanicsError::invalid_elements(format!(
+                    "TLE {} {}° outside [0, 360]°",
+                    name, angle
+                )));
+            }
+        }
+        if tle.mean_motion_rev_per_day <= 0.0 {
+            return Err(OrbitalMechanicsError::invalid_elements(format!(
+                "TLE mean motion {} rev/day is not positive",
+                tle.mean_motion_rev_per_day
+            )));
+        }
+
+        Ok(tle)
+    }
+
+    /// Element set for an orbit, with zero drag terms
+    pub fn from_orbit(orbit: &SatelliteOrbit, catalog_number: u32) -> Self {
+        Self {
+            name: Some(orbit.name.clone()),
+            catalog_number,
+            classification: 'U',
+            international_designator: String::new(),
+            epoch: orbit.epoch,
+            mean_motion_dot: 0.0,
+            mean_motion_ddot: 0.0,
+            bstar: 0.0,
+            element_set_number: 1,
+            inclination_deg: orbit.elements.inclination_deg,
+            raan_deg: orbit.elements.raan_deg,
+            eccentricity: orbit.elements.eccentricity,
+            argument_of_perigee_deg: orbit.elements.argument_of_perigee_deg,
+            mean_anomaly_deg: orbit.elements.mean_anomaly_deg,
+            mean_motion_rev_per_day: orbit.mean_motion_rev_per_day,
+            revolution_number: 0,
+        }
+    }
+
+    /// Orbit with two-body elements taken from this set
+    pub fn to_orbit(&self, satellite_id: String) -> Result<SatelliteOrbit> {
+        let mean_motion_rad_per_sec = self.mean_motion_rev_per_day * TWO_PI / SOLAR_DAY_SECONDS;
+        let semi_major_axis_km =
+            (EARTH_MU / (mean_motion_rad_per_sec * mean_motion_rad_per_sec)).cbrt();
+        let elements = OrbitalElements::new(
+            semi_major_axis_km,
+            self.eccentricity,
+            self.inclination_deg,
+            self.raan_deg.rem_euclid(360.0),
+            self.argument_of_perigee_deg.rem_euclid(360.0),
+            self.mean_anomaly_deg.rem_euclid(360.0),
+        )?;
+        let name = self.name.clone().unwrap_or_else(|| satellite_id.clone());
+
+        Ok(SatelliteOrbit::new(
+            satellite_id,
+            name,
+            elements,
+            self.epoch,
+        ))
+    }
+
+    /// The two data lines, with checksums
+    pub fn to_lines(&self) -> Result<[String; 2]> {
+        let out_of_range = |name: &str, value: String| {
+            OrbitalMechanicsError::invalid_elements(format!(
+                "TLE {} {} does not fit its columns",
+                name, value
+            ))
+        };
+        if self.catalog_number > 99_999 {
+            return Err(out_of_range(
+                "catalog number",
+                self.catalog_number.to_string(),
+            ));
+        }
+        if self.element_set_number > 9_999 {
+            return Err(out_of_range(
+                "element set number",
+                self.element_set_number.to_string(),
+            ));
+        }
+        if self.revolution_number > 99_999 {
+            return Err(out_of_range(
+                "revolution number",
+                self.revolution_number.to_string(),
+            ));
+        }
+        if !self.classification.is_ascii_graphic() {
+            return Err(out_of_range(
+                "classification",
+                self.classification.to_string(),
+            ));
+        }
+        if self.international_designator.len() > 8 || !self.international_designator.is_ascii() {
+            return Err(out_of_range(
+                "international designator",
+                self.international_designator.clone(),
+            ));
+        }
+        for (name, angle, max) in [
+            ("inclination", self.inclination_deg, 180.0),
+            ("RAAN", self.raan_deg, 360.0),
+            ("argument of perigee", self.argument_of_perigee_deg, 360.0),
+            ("mean anomaly", self.mean_anomaly_deg, 360.0),
+        ] {
+            if !(0.0..=max).contains(&angle) {
+                return Err(out_of_range(name, angle.to_string()));
+            }
+        }
+        let eccentricity = (self.eccentricity * 1e7).round();
+        if !(0.0..1e7).contains(&eccentricity) {
+            return Err(out_of_range("eccentricity", self.eccentricity.to_string()));
+        }
+        if !(self.mean_motion_rev_per_day > 0.0 && self.mean_motion_rev_per_day < 100.0) {
+            return Err(out_of_range(
+                "mean motion",
+                self.mean_motion_rev_per_day.to_string(),
+            ));
+        }
+        let mean_motion_dot = format!("{:.8}", self.mean_motion_dot.abs());
+        if !mean_motion_dot.starts_with("0.") {
+            return Err(out_of_range(
+                "mean motion derivative",
+                self.mean_motion_dot.to_string(),
+            ));
+        }
+
+        let line1 = format!(
+            "1 {:05}{} {:<8} {} {}{} {} {} 0 {:>4}",
+            self.catalog_number,
+            self.classification,
+            self.international_designator,
+            format_epoch(self.epoch)?,
+            if self.mean_motion_dot < 0.0 { '-' } else { ' ' },
+            &mean_motion_dot[1..],
+            format_exponent(self.mean_motion_ddot).ok_or_else(|| out_of_range(
+                "mean motion second derivative",
+                self.mean_motion_ddot.to_string()
+            ))?,
+            format_exponent(self.bstar)
+                .ok_or_else(|| out_of_range("BSTAR", self.bstar.to_string()))?,
+            self.element_set_number,
+        );
+        let line2 = format!(
+            "2 {:05} {:8.4} {:8.4} {:07} {:8.4} {:8.4} {:11.8}{:5}",
+            self.catalog_number,
+            self.inclination_deg,
+            self.raan_deg,
+            eccentricity as u32,
+            self.argument_of_perigee_deg,
+            self.mean_anomaly_deg,
+            self.mean_motion_rev_per_day,
+            self.revolution_number,
+        );
+
+        // Rounding can still carry a value into an extra column
+        if line1.len() != LINE_LENGTH - 1 || line2.len() != LINE_LENGTH - 1 {
+            return Err(OrbitalMechanicsError::invalid_elements(format!(
+                "TLE elements overflow their columns:\n{}\n{}",
+                line1, line2
+            )));
+        }
+
+        Ok([line1, line2].map(|line| {
+            let checksum = checksum(&line);
+            format!("{}{}", line, checksum)
+        }))
+    }
+
+    /// Title line, if any, and both data lines, each ending in a newline
+    pub fn format(&self) -> Result<String> {
+        let [line1, line2] = self.to_lines()?;
+        Ok(match &self.name {
+            Some(name) => format!("{}\n{}\n{}\n", name, line1, line2),
+            None => format!("{}\n{}\n", line1, line2),
+        })
+    }
+}
+
+/// One data line, checked for length, line number and checksum
+struct DataLine<'a> {
+    text: &'a str,
+    number: u8,
+}
+
+impl<'a> DataLine<'a> {
+    fn new(text: &'a str, number: u8) -> Result<Self> {
+        let line = Self { text, number };
+        if !text.is_ascii() || text.len() != LINE_LENGTH {
+            return Err(line.error(format!(
+                "must be {} ASCII characters, got {:?}",
+                LINE_LENGTH, text
+            )));
+        }
+        if line.field(1, 1)? != number.to_string() {
+            return Err(line.error("has the wrong line number".to_string()));
+        }
+        let expected = checksum(&text[..LINE_LENGTH - 1]);
+        if line.field(LINE_LENGTH, LINE_LENGTH)? != expected.to_string() {
+            return Err(line.error(format!("checksum should be {}", expected)));
+        }
+        Ok(line)
+    }
+
+    fn error(&self, message: String) -> OrbitalMechanicsError {
+        OrbitalMechanicsError::invalid_elements(format!("TLE line {} {}", self.number, message))
+    }
+
+    /// Columns `first..=last`, counted from 1 as in the format definition
+    fn field(&self, first: usize, last: usize) -> Result<&'a str> {
+        self.text
+            .get(first - 1..last)
+            .ok_or_else(|| self.error(format!("is missing columns {}-{}", first, last)))
+    }
+
+    fn integer(&self, first: usize, last: usize, name: &str) -> Result<u32> {
+        let field = self.field(first, last)?.trim();
+        if field.is_empty() {
+            return Ok(0);
+        }
+        field
+            .parse()
+            .map_err(|_| self.error(format!("{} {:?} is not an integer", name, field)))
+    }
+
+    fn decimal(&self, first: usize, last: usize, name: &str) -> Result<f64> {
+        let field = self.field(first, last)?.trim();
+        let value: f64 = field
+            .parse()
+            .map_err(|_| self.error(format!("{} {:?} is not a number", name, field)))?;
+        // `f64::from_str` also accepts "NaN" and "inf"
+        if !value.is_finite() || field.bytes().any(|b| b.is_ascii_alphabetic()) {
+            return Err(self.error(format!("{} {:?} is not a number", name, field)));
+        }
+        Ok(value)
+    }
+
+    /// Digits after an implied leading decimal point
+    fn assumed_decimal(&self, first: usize, last: usize, name: &str) -> Result<f64> {
+        let field = self.field(first, last)?.trim();
+        if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(self.error(format!("{} {:?} is not a digit string", name, field)));
+        }
+        Ok(format!("0.{}", field).parse().unwrap_or(0.0))
+    }
+
+    /// Implied-decimal mantissa and power of ten, e.g. `-11606-4` for -0.11606e-4
+    fn exponent(&self, first: usize, last: usize, name: &str) -> Result<f64> {
+        let field = self.field(first, last)?.trim();
+        if field.is_empty() {
+            return Ok(0.0);
+        }
+        let invalid = || self.error(format!("{} {:?} is not in exponent notation", name, field));
+        let (mantissa, exponent) = field.split_at(field.len().checked_sub(2).ok_or_else(invalid)?);
+        let (sign, digits) = match mantissa.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", mantissa.strip_prefix('+').unwrap_or(mantissa)),
+        };
+        let exponent_valid =
+            matches!(exponent.as_bytes(), [b'+' | b'-', digit] if digit.is_ascii_digit());
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || !exponent_valid {
+            return Err(invalid());
+        }
+        format!("{}0.{}e{}", sign, digits, exponent)
+            .parse()
+            .map_err(|_| invalid())
+    }
+
+    /// Two-digit year and fractional day of year, columns 19-32
+    fn epoch(&self) -> Result<DateTime<Utc>> {
+        let year = self.integer(19, 20, "epoch year")? as i32;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let day = self.decimal(21, 32, "epoch day")?;
+        if !(1.0..days_in_year(year) + 1.0).contains(&day) {
+            return Err(self.error(format!("epoch day {} is not in {}", day, year)));
+        }
+        let start = Utc
+            .with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+            .single()
+            .ok_or_else(|| self.error(format!("epoch year {} is invalid", year)))?;
+        let microseconds = ((day - 1.0) * SOLAR_DAY_SECONDS * 1e6).round() as i64;
+        Ok(start + Duration::microseconds(microseconds))
+    }
+}
+
+/// Modulo-10 sum of the digits, with each minus sign counting as one
+fn checksum(line: &str) -> u32 {
+    line.chars()
+        .map(|c| match c {
+            '-' => 1,
+            c => c.to_digit(10).unwrap_or(0),
+        })
+        .sum::<u32>()
+        % 10
+}
+
+fn days_in_year(year: i32) -> f64 {
+    if NaiveDate::from_yo_opt(year, 366).is_some() {
+        366.0
+    } else {
+        365.0
+    }
+}
+
+/// `YYDDD.DDDDDDDD`, for epochs from 1957 through 2056
+fn format_epoch(epoch: DateTime<Utc>) -> Result<String> {
+    let seconds_of_day =
+        epoch.num_seconds_from_midnight() as f64 + epoch.timestamp_subsec_nanos() as f64 / 1e9;
+    let mut year = epoch.year();
+    let mut day = epoch.ordinal() as f64 + seconds_of_day / SOLAR_DAY_SECONDS;
+    // Rounding the last instant of a year carries into the next
+    if (day * 1e8).round() / 1e8 >= days_in_year(year) + 1.0 {
+        year += 1;
+        day = 1.0;
+    }
+    if !(1957..=2056).contains(&year) {
+        return Err(OrbitalMechanicsError::invalid_elements(format!(
+            "TLE epoch {} outside 1957-2056",
+            epoch
+        )));
+    }
+    Ok(format!("{:02}{:012.8}", year % 100, day))
+}
+
+/// `SMMMMMSE` implied-decimal exponent notation, or `None` if too large
+fn format_exponent(value: f64) -> Option<String> {
+    if !value.is_finite() {
+        return None;
+    }
+    let magnitude = value.abs();
+    if magnitude == 0.0 {
+        return Some(" 00000-0".to_string());
+    }
+    let mut exponent = magnitude.log10().floor() as i32 + 1;
+    let mut mantissa = (magnitude / 10f64.powi(exponent) * 1e5).round();
+    if mantissa >= 1e5 {
+        exponent += 1;
+        mantissa = (mantissa / 10.0).round();
+    }
+    if exponent < -9 {
+        // Below the smallest representable value
+        return Some(" 00000-0".to_string());
+    }
+    if exponent > 9 {
+        return None;
+    }
+    Some(format!(
+        "{}{:05}{}{}",
+        if value < 0.0 { '-' } else { ' ' },
+        mantissa as u32,
+        if exponent < 0 { '-' } else { '+' },
+        exponent.abs()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::wrap_angle_deg;
+    use proptest::prelude::*;
+
+    const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537
+";
+
+    // Round-trip tolerances from the TLE column precision
+    const ANGLE_TOLERANCE_DEG: f64 = 5.1e-5;
+    const ECCENTRICITY_TOLERANCE: f64 = 5.1e-8;
+    const MEAN_MOTION_TOLERANCE_REV_PER_DAY: f64 = 5.1e-9;
+    const EPOCH_TOLERANCE_MICROSECONDS: i64 = 500;
+
+    #[test]
+    fn test_parse_and_format_iss() {
+        let tle = Tle::parse(ISS).unwrap();
+        assert_eq!(tle.name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(tle.catalog_number, 25544);
+        assert_eq!(tle.international_designator, "98067A");
+        assert_eq!(
+            tle.epoch.format("%Y-%m-%d").to_string(),
+            "2008-09-20",
+            "day 264 of 2008"
+        );
+        assert!((tle.mean_motion_dot + 0.00002182).abs() < 1e-12);
+        assert!((tle.bstar + 0.11606e-4).abs() < 1e-12);
+        assert!((tle.eccentricity - 0.0006703).abs() < 1e-12);
+        assert_eq!(tle.revolution_number, 56353);
+
+        // Formatting reproduces the published lines, checksums included
+        assert_eq!(tle.format().unwrap(), ISS);
+        assert_eq!(parse_tles(&format!("{}{}", ISS, ISS)).unwrap().len(), 2);
+
+        let orbit = tle.to_orbit("ISS".to_string()).unwrap();
+        assert!((orbit.elements.semi_major_axis_km - 6730.0).abs() < 10.0);
+        assert!((orbit.mean_motion_rev_per_day - tle.mean_motion_rev_per_day).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_malformed_lines_are_rejected() {
+        let lines: Vec<&str> = ISS.lines().collect();
+        let (line1, line2) = (lines[1], lines[2]);
+
+        // Bad checksum, truncated line, swapped lines, NaN and non-ASCII fields
+        let with_checksum = |line: &str| format!("{}{}", &line[..68], checksum(&line[..68]));
+        let bad_checksum = format!("{}8", &line1[..68]);
+        let nan = with_checksum(&line2.replacen(" 51.6416", "     NaN", 1));
+        assert!(Tle::parse_lines(&bad_checksum, line2).is_err());
+        assert!(Tle::parse_lines(&line1[..40], line2).is_err());
+        assert!(Tle::parse_lines(line2, line1).is_err());
+        assert!(Tle::parse_lines(line1, &nan).is_err());
+        assert!(Tle::parse_lines(&line1.replacen('U', "é", 1), line2).is_err());
+        assert!(parse_tles(&ISS[..ISS.len() - 70]).is_err());
+    }
+
+    fn arbitrary_tle() -> impl Strategy<Value = Tle> {
+        (
+            (
+                0u32..=99_999,
+                0i64..3_155_000_000_000_000,
+                -0.5f64..0.5,
+                -1e-3f64..1e-3,
+            ),
+            (0.0f64..=180.0, 0.0f64..360.0, 0.0f64..0.99, 0.0f64..360.0),
+            (0.0f64..360.0, 0.05f64..20.0, 0u32..=99_999),
+        )
+            .prop_map(|(line1, angles, rest)| {
+                let (catalog_number, epoch_microseconds, mean_motion_dot, bstar) = line1;
+                let (inclination_deg, raan_deg, eccentricity, argument_of_perigee_deg) = angles;
+                let (mean_anomaly_deg, mean_motion_rev_per_day, revolution_number) = rest;
+                let first_epoch = Utc.with_ymd_and_hms(1957, 1, 1, 0, 0, 0).unwrap();
+                Tle {
+                    name: None,
+                    catalog_number,
+                    classification: 'U',
+                    international_designator: "24001A".to_string(),
+                    epoch: first_epoch + Duration::microseconds(epoch_microseconds),
+                    mean_motion_dot,
+                    mean_motion_ddot: 0.0,
+                    bstar,
+                    element_set_number: 999,
+                    inclination_deg,
+                    raan_deg,
+                    eccentricity,
+                    argument_of_perigee_deg,
+                    mean_anomaly_deg,
+                    mean_motion_rev_per_day,
+                    revolution_number,
+                }
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_text_round_trip(tle in arbitrary_tle()) {
+            let parsed = Tle::parse(&tle.format().unwrap()).unwrap();
+
+            prop_assert_eq!(parsed.catalog_number, tle.catalog_number);
+            prop_assert_eq!(parsed.revolution_number, tle.revolution_number);
+            let epoch_error = (parsed.epoch - tle.epoch).num_microseconds().unwrap().abs();
+            prop_assert!(
+                epoch_error <= EPOCH_TOLERANCE_MICROSECONDS,
+                "epoch off by {} us",
+                epoch_error
+            );
+            for (parsed, original) in [
+                (parsed.inclination_deg, tle.inclination_deg),
+                (parsed.raan_deg, tle.raan_deg),
+                (parsed.argument_of_perigee_deg, tle.argument_of_perigee_deg),
+                (parsed.mean_anomaly_deg, tle.mean_anomaly_deg),
+            ] {
+                prop_assert!(wrap_angle_deg(parsed - original).abs() <= ANGLE_TOLERANCE_DEG);
+            }
+            prop_assert!((parsed.eccentricity - tle.eccentricity).abs() <= ECCENTRICITY_TOLERANCE);
+            prop_assert!(
+                (parsed.mean_motion_rev_per_day - tle.mean_motion_rev_per_day).abs()
+                    <= MEAN_MOTION_TOLERANCE_REV_PER_DAY
+            );
+            prop_assert!((parsed.mean_motion_dot - tle.mean_motion_dot).abs() <= 5e-9);
+            prop_assert!((parsed.bstar - tle.bstar).abs() <= tle.bstar.abs() * 5e-5 + 1e-14);
+        }
+
+        #[test]
+        fn test_elements_round_trip(
+            semi_major_axis_km in 6700.0f64..45_000.0,
+            eccentricity in 0.0f64..0.5,
+            inclination_deg in 0.0f64..=180.0,
+            raan_deg in 0.0f64..360.0,
+            argument_of_perigee_deg in 0.0f64..360.0,
+            mean_anomaly_deg in 0.0f64..360.0,
+        ) {
+            let elements = OrbitalElements::new(
+                semi_major_axis_km,
+                eccentricity,
+                inclination_deg,
+                raan_deg,
+                argument_of_perigee_deg,
+                mean_anomaly_deg,
+            )
+            .unwrap();
+            let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 6, 30, 0).unwrap();
+            let orbit =
+                SatelliteOrbit::new("SAT-1".to_string(), "SAT 1".to_string(), elements, epoch);
+
+            let text = Tle::from_orbit(&orbit, 90001).format().unwrap();
+            let recovered = Tle::parse(&text).unwrap().to_orbit("SAT-1".to_string()).unwrap();
+
+            // 1e-8 rev/day in mean motion is under a metre in semi-major axis
+            let elements = &recovered.elements;
+            prop_assert!((elements.semi_major_axis_km - semi_major_axis_km).abs() < 1e-3);
+            prop_assert!((elements.eccentricity - eccentricity).abs() <= ECCENTRICITY_TOLERANCE);
+            for (actual, original) in [
+                (elements.inclination_deg, inclination_deg),
+                (elements.raan_deg, raan_deg),
+                (elements.argument_of_perigee_deg, argument_of_perigee_deg),
+                (elements.mean_anomaly_deg, mean_anomaly_deg),
+            ] {
+                prop_assert!(wrap_angle_deg(actual - original).abs() <= ANGLE_TOLERANCE_DEG);
+            }
+            let epoch_error = (recovered.epoch - epoch).num_microseconds().unwrap().abs();
+            prop_assert!(epoch_error <= EPOCH_TOLERANCE_MICROSECONDS);
+        }
+    }
+}
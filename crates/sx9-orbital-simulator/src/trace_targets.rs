@@ -0,0 +1,28 @@
+//! `tracing` target names used throughout the crate
+//!
+//! Every span and event is emitted under one of the targets below so operators
+//! can filter a single stage of the pipeline, e.g.
+//! `RUST_LOG=sx9_orbital::visibility=debug,sx9_orbital::propagator=trace`.
+//!
+//! | Target | Level | Content |
+//! |--------|-------|---------|
+//! | `sx9_orbital::engine` | info/debug | Engine construction and top-level analyses, with elapsed time |
+//! | `sx9_orbital::propagator` | trace/warn | Per-propagation timing, convergence failures, limit violations |
+//! | `sx9_orbital::visibility` | debug | Window searches: propagation count, windows found, elapsed time |
+//! | `sx9_orbital::simulator` | debug/info/warn | Simulation ticks and satellite lifecycle |
+//! | `sx9_orbital::fso` | trace | Links blocked by thermal keep-outs |
+
+/// Top-level `OrbitalMechanicsEngine` operations
+pub const ENGINE: &str = "sx9_orbital::engine";
+
+/// Orbital propagators
+pub const PROPAGATOR: &str = "sx9_orbital::propagator";
+
+/// Visibility window computation
+pub const VISIBILITY: &str = "sx9_orbital::visibility";
+
+/// Live satellite simulator
+pub const SIMULATOR: &str = "sx9_orbital::simulator";
+
+/// FSO link analysis
+pub const FSO: &str = "sx9_orbital::fso";
@@ -0,0 +1,735 @@
+//! Ground segment cost model and station trade studies
+//!
+//! A trade study takes a list of candidate sites, finds every site's passes
+//! with the constellation once, then scores each station subset within a
+//! size range by cost and availability. Availability is the fraction of
+//! sample times at which at least one chosen station both sees a satellite
+//! and has a clear sky. Sky conditions at different sites are taken as
+//! independent, so adding a second site in view at the same time still helps
+//! when clouds are likely: this is site diversity.
+//!
+//! Costs are totals over the network lifetime: station capex plus yearly
+//! opex, and one optical terminal per satellite. The report keeps every
+//! subset and marks the Pareto front, where no other subset is both cheaper
+//! and more available.
+//!
+//! Site selection answers the inverse question: the fewest sites, cheapest
+//! among equals, reaching a target availability. The exact search walks
+//! subsets smallest first; the greedy search adds the site with the largest
+//! availability gain at each step and suits long candidate lists.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::ground_station::GroundStation;
+use crate::orbit::SatelliteOrbit;
+use crate::pass_profile;
+use crate::progress::{NoProgress, Phase, ProgressSink, Span};
+use crate::propagator::OrbitalPropagator;
+use crate::units::Seconds;
+use crate::visibility::VisibilityCalculator;
+use crate::visibility_catalog::VisibilityCatalog;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Lifetime costs of a ground segment and its space terminals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostModel {
+    /// Build cost of a station, unless the site overrides it
+    pub station_capex: f64,
+    /// Yearly running cost of a station, unless the site overrides it
+    pub station_opex_per_year: f64,
+    /// Optical terminal cost carried by each satellite
+    pub terminal_cost_per_satellite: f64,
+    /// Years of opex counted in the total
+    pub lifetime_years: f64,
+}
+
+/// Station that may be built, with its weather and any site-specific costs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateSite {
+    pub station: GroundStation,
+    /// Fraction of time the sky is clear enough for an optical link
+    pub clear_sky_probability: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capex: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opex_per_year: Option<f64>,
+}
+
+/// Station subsets to compare and how finely to sample availability
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeStudy {
+    pub candidates: Vec<CandidateSite>,
+    pub cost_model: CostModel,
+    pub min_stations: usize,
+    pub max_stations: usize,
+    pub sample_seconds: f64,
+}
+
+/// One station subset with its cost and availability
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradePoint {
+    /// In candidate order
+    pub station_ids: Vec<String>,
+    pub station_cost: f64,
+    pub terminal_cost: f64,
+    pub total_cost: f64,
+    pub availability: f64,
+    /// No other subset is at least as cheap and as available, and better in one
+    pub pareto_optimal: bool,
+}
+
+/// Candidate sites in view of the constellation at each sample time
+#[derive(Debug, Clone)]
+pub struct SiteContacts {
+    pub satellite_count: usize,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Candidate indices in view, per sample
+    in_contact: Vec<Vec<usize>>,
+}
+
+/// Search used by `TradeStudy::select_sites`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SiteSelectionMode {
+    /// Add the site with the largest availability gain until the target is met
+    Greedy,
+    /// Try every subset, smallest first; limited to short candidate lists
+    Exact,
+}
+
+/// Sites chosen to meet an availability target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteSelection {
+    pub mode: SiteSelectionMode,
+    pub target_availability: f64,
+    /// In candidate order
+    pub station_ids: Vec<String>,
+    pub total_cost: f64,
+    pub availability: f64,
+    /// False when even the best subset found falls short of the target
+    pub meets_target: bool,
+}
+
+/// Every evaluated subset, cheapest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeStudyReport {
+    pub satellite_count: usize,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// By total cost, then highest availability
+    pub points: Vec<TradePoint>,
+}
+
+impl CostModel {
+    /// Lifetime cost of building and running `site`
+    pub fn station_cost(&self, site: &CandidateSite) -> f64 {
+        site.capex.unwrap_or(self.station_capex)
+            + site.opex_per_year.unwrap_or(self.station_opex_per_year) * self.lifetime_years
+    }
+
+    /// Terminal cost of a constellation of `satellite_count`
+    pub fn terminal_cost(&self, satellite_count: usize) -> f64 {
+        self.terminal_cost_per_satellite * satellite_count as f64
+    }
+}
+
+impl CandidateSite {
+    /// Always-clear site at the cost model's station prices
+    pub fn new(station: GroundStation) -> Self {
+        Self {
+            station,
+            clear_sky_probability: 1.0,
+            capex: None,
+            opex_per_year: None,
+        }
+    }
+
+    pub fn with_clear_sky_probability(mut self, clear_sky_probability: f64) -> Self {
+        self.clear_sky_probability = clear_sky_probability;
+        self
+    }
+
+    /// Override the cost model's station prices for this site
+    pub fn with_costs(mut self, capex: f64, opex_per_year: f64) -> Self {
+        self.capex = Some(capex);
+        self.opex_per_year = Some(opex_per_year);
+        self
+    }
+}
+
+impl TradeStudy {
+    /// Compare every non-empty subset, sampling availability each minute
+    pub fn new(candidates: Vec<CandidateSite>, cost_model: CostModel) -> Self {
+        Self {
+            min_stations: 1,
+            max_stations: candidates.len(),
+            candidates,
+            cost_model,
+            sample_seconds: 60.0,
+        }
+    }
+
+    /// Only compare subsets of `min` to `max` stations
+    pub fn with_station_count(mut self, min: usize, max: usize) -> Self {
+        self.min_stations = min;
+        self.max_stations = max;
+        self
+    }
+
+    pub fn with_sample_seconds(mut self, sample_seconds: f64) -> Self {
+        self.sample_seconds = sample_seconds;
+        self
+    }
+
+    /// Evaluate every subset over `duration_hours` from `start_time`
+    pub fn run(
+        &self,
+        satellites: &[SatelliteOrbit],
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        visibility: &VisibilityCalculator,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<TradeStudyReport> {
+        self.run_with_progress(
+            satellites,
+            start_time,
+            duration_hours,
+            visibility,
+            propagator,
+            &NoProgress,
+        )
+    }
+
+    /// Evaluate every subset like `run`, reporting progress to `progress`
+    ///
+    /// Finding contacts takes the first half, scoring subsets the rest.
+    pub fn run_with_progress(
+        &self,
+        satellites: &[SatelliteOrbit],
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        visibility: &VisibilityCalculator,
+        propagator: &dyn OrbitalPropagator,
+        progress: &dyn ProgressSink,
+    ) -> Result<TradeStudyReport> {
+        self.validate()?;
+        let contacts = self.site_contacts_with_progress(
+            satellites,
+            start_time,
+            duration_hours,
+            visibility,
+            propagator,
+            &Span::new(progress, 0.0, 50.0),
+        )?;
+
+        let subsets: f64 = (self.min_stations..=self.max_stations)
+            .map(|size| binomial(self.candidates.len(), size))
+            .sum();
+        let scoring_progress = Span::new(progress, 50.0, 100.0);
+        let scoring = Phase::start(&scoring_progress, "trade study", subsets as usize)?;
+        let mut points = Vec::new();
+        for size in self.min_stations..=self.max_stations {
+            for subset in combinations(self.candidates.len(), size) {
+                points.push(self.trade_point(&subset, &contacts));
+                scoring.step(points.len())?;
+            }
+        }
+        scoring.finish();
+
+        points.sort_by(|a, b| {
+            a.total_cost
+                .total_cmp(&b.total_cost)
+                .then(b.availability.total_cmp(&a.availability))
+        });
+        // Cheapest first, so a point is on the front when it beats every cheaper one
+        let mut best_availability = f64::NEG_INFINITY;
+        for point in &mut points {
+            if point.availability > best_availability {
+                point.pareto_optimal = true;
+                best_availability = point.availability;
+            }
+        }
+
+        Ok(TradeStudyReport {
+            satellite_count: contacts.satellite_count,
+            start_time: contacts.start_time,
+            end_time: contacts.end_time,
+            points,
+        })
+    }
+
+    /// Sample which candidates see the constellation, for `select_sites`
+    ///
+    /// The station count range is not used here.
+    pub fn site_contacts(
+        &self,
+        satellites: &[SatelliteOrbit],
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        visibility: &VisibilityCalculator,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<SiteContacts> {
+        self.site_contacts_with_progress(
+            satellites,
+            start_time,
+            duration_hours,
+            visibility,
+            propagator,
+            &NoProgress,
+        )
+    }
+
+    /// Sample contacts like `site_contacts`, reporting progress per candidate site
+    pub fn site_contacts_with_progress(
+        &self,
+        satellites: &[SatelliteOrbit],
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        visibility: &VisibilityCalculator,
+        propagator: &dyn OrbitalPropagator,
+        progress: &dyn ProgressSink,
+    ) -> Result<SiteContacts> {
+        self.validate_candidates()?;
+
+        let phase = Phase::start(progress, "visibility", self.candidates.len())?;
+        let mut windows = Vec::new();
+        for (done, site) in self.candidates.iter().enumerate() {
+            for satellite in satellites {
+                windows.extend(visibility.calculate_windows(
+                    satellite,
+                    &site.station,
+                    start_time,
+                    duration_hours,
+                    propagator,
+                )?);
+            }
+            phase.step(done + 1)?;
+        }
+        phase.finish();
+        let catalog = VisibilityCatalog::new(windows);
+
+        let end_time = start_time + Duration::seconds((duration_hours * 3600.0) as i64);
+        let times = pass_profile::sample_times(start_time, end_time, Seconds(self.sample_seconds))?;
+        let site_index: HashMap<&str, usize> = self
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(i, site)| (site.station.station_id.as_str(), i))
+            .collect();
+        // Sites with a satellite in view at each sample time
+        let in_contact: Vec<Vec<usize>> = times
+            .iter()
+            .map(|&time| {
+                let mut sites: Vec<usize> = catalog
+                    .active_at(time)
+                    .iter()
+                    .filter_map(|w| site_index.get(w.station_id.as_str()).copied())
+                    .collect();
+                sites.sort_unstable();
+                sites.dedup();
+                sites
+            })
+            .collect();
+
+        Ok(SiteContacts {
+            satellite_count: satellites.len(),
+            start_time,
+            end_time,
+            in_contact,
+        })
+    }
+
+    /// Fewest sites, cheapest among equals, reaching `target_availability`
+    ///
+    /// When no subset reaches the target, the exact search returns the most
+    /// available one and the greedy search stops once no site adds anything.
+    pub fn select_sites(
+        &self,
+        contacts: &SiteContacts,
+        target_availability: f64,
+        mode: SiteSelectionMode,
+    ) -> Result<SiteSelection> {
+        self.select_sites_with_progress(contacts, target_availability, mode, &NoProgress)
+    }
+
+    /// Select sites like `select_sites`, reporting progress per subset size
+    ///
+    /// Both searches try at most one size per candidate, so a search that
+    /// meets the target early jumps to 100 percent.
+    pub fn select_sites_with_progress(
+        &self,
+        contacts: &SiteContacts,
+        target_availability: f64,
+        mode: SiteSelectionMode,
+        progress: &dyn ProgressSink,
+    ) -> Result<SiteSelection> {
+        self.validate_candidates()?;
+        let phase = Phase::start(progress, "site selection", self.candidates.len())?;
+        let best = match mode {
+            SiteSelectionMode::Greedy => {
+                self.select_greedy(contacts, target_availability, &phase)?
+            }
+            SiteSelectionMode::Exact => self.select_exact(contacts, target_availability, &phase)?,
+        };
+        phase.finish();
+        Ok(SiteSelection {
+            mode,
+            target_availability,
+            meets_target: best.availability >= target_availability,
+            station_ids: best.station_ids,
+            total_cost: best.total_cost,
+            availability: best.availability,
+        })
+    }
+
+    fn select_greedy(
+        &self,
+        contacts: &SiteContacts,
+        target_availability: f64,
+        phase: &Phase,
+    ) -> Result<TradePoint> {
+        let mut chosen: Vec<usize> = Vec::new();
+        let mut best = self.trade_point(&chosen, contacts);
+        while best.availability < target_availability {
+            phase.step(chosen.len())?;
+            let next = (0..self.candidates.len())
+                .filter(|i| !chosen.contains(i))
+                .map(|i| {
+                    let mut subset = chosen.clone();
+                    subset.push(i);
+                    subset.sort_unstable();
+                    self.trade_point(&subset, contacts)
+                })
+                .min_by(|a, b| {
+                    b.availability
+                        .total_cmp(&a.availability)
+                        .then(a.total_cost.total_cmp(&b.total_cost))
+                });
+            match next {
+                Some(point) if point.availability > best.availability => {
+                    chosen = point
+                        .station_ids
+                        .iter()
+                        .filter_map(|id| self.candidate_index(id))
+                        .collect();
+                    best = point;
+                }
+                _ => break,
+            }
+        }
+        Ok(best)
+    }
+
+    fn select_exact(
+        &self,
+        contacts: &SiteContacts,
+        target_availability: f64,
+        phase: &Phase,
+    ) -> Result<TradePoint> {
+        let count = self.candidates.len();
+        let subsets: f64 = (1..=count).map(|size| binomial(count, size)).sum();
+        if subsets > MAX_TRADE_SUBSETS as f64 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Exact site selection over {} candidates would evaluate {} subsets, more than {}",
+                count, subsets, MAX_TRADE_SUBSETS
+            )));
+        }
+
+        let by_cost = |a: &TradePoint, b: &TradePoint| {
+            a.total_cost
+                .total_cmp(&b.total_cost)
+                .then(b.availability.total_cmp(&a.availability))
+        };
+        let mut most_available = self.trade_point(&[], contacts);
+        for size in 1..=count {
+            phase.step(size - 1)?;
+            let points: Vec<TradePoint> = combinations(count, size)
+                .iter()
+                .map(|subset| self.trade_point(subset, contacts))
+                .collect();
+            let meeting = points
+                .iter()
+                .filter(|p| p.availability >= target_availability)
+                .min_by(|a, b| by_cost(a, b));
+            if let Some(point) = meeting {
+                return Ok(point.clone());
+            }
+            for point in points {
+                if point.availability > most_available.availability {
+                    most_available = point;
+                }
+            }
+        }
+        Ok(most_available)
+    }
+
+    fn candidate_index(&self, station_id: &str) -> Option<usize> {
+        self.candidates
+            .iter()
+            .position(|site| site.station.station_id == station_id)
+    }
+
+    /// Cost and availability of the candidates at `subset`
+    fn trade_point(&self, subset: &[usize], contacts: &SiteContacts) -> TradePoint {
+        let station_cost: f64 = subset
+            .iter()
+            .map(|&i| self.cost_model.station_cost(&self.candidates[i]))
+            .sum();
+        let terminal_cost = self.cost_model.terminal_cost(contacts.satellite_count);
+        let chosen: HashSet<usize> = subset.iter().copied().collect();
+        TradePoint {
+            station_ids: subset
+                .iter()
+                .map(|&i| self.candidates[i].station.station_id.clone())
+                .collect(),
+            station_cost,
+            terminal_cost,
+            total_cost: station_cost + terminal_cost,
+            availability: self.availability(&chosen, &contacts.in_contact),
+            pareto_optimal: false,
+        }
+    }
+
+    /// Mean probability that some chosen station in contact has a clear sky
+    fn availability(&self, chosen: &HashSet<usize>, in_contact: &[Vec<usize>]) -> f64 {
+        if in_contact.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = in_contact
+            .iter()
+            .map(|sites| {
+                let all_clouded: f64 = sites
+                    .iter()
+                    .filter(|i| chosen.contains(i))
+                    .map(|&i| 1.0 - self.candidates[i].clear_sky_probability)
+                    .product();
+                1.0 - all_clouded
+            })
+            .sum();
+        total / in_contact.len() as f64
+    }
+
+    fn validate(&self) -> Result<()> {
+        let count = self.candidates.len();
+        if self.min_stations == 0
+            || self.min_stations > self.max_stations
+            || self.max_stations > count
+        {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Trade study needs 1 <= min <= max <= {} stations, got {} to {}",
+                count, self.min_stations, self.max_stations
+            )));
+        }
+        let subsets: f64 = (self.min_stations..=self.max_stations)
+            .map(|size| binomial(count, size))
+            .sum();
+        if subsets > MAX_TRADE_SUBSETS as f64 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Trade study would evaluate {} subsets, more than {}",
+                subsets, MAX_TRADE_SUBSETS
+            )));
+        }
+        self.validate_candidates()
+    }
+
+    fn validate_candidates(&self) -> Result<()> {
+        let mut ids = HashSet::new();
+        for site in &self.candidates {
+            if !ids.insert(site.station.station_id.as_str()) {
+                return Err(OrbitalMechanicsError::config_error(format!(
+                    "Duplicate candidate station {}",
+                    site.station.station_id
+                )));
+            }
+            if !(0.0..=1.0).contains(&site.clear_sky_probability) {
+                return Err(OrbitalMechanicsError::config_error(format!(
+                    "Clear-sky probability of {} must be within 0 to 1, got {}",
+                    site.station.station_id, site.clear_sky_probability
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TradeStudyReport {
+    /// Subsets on the Pareto front, cheapest first
+    pub fn pareto_front(&self) -> Vec<&TradePoint> {
+        self.points.iter().filter(|p| p.pareto_optimal).collect()
+    }
+
+    /// Cheapest subset reaching `availability`
+    pub fn cheapest_meeting(&self, availability: f64) -> Option<&TradePoint> {
+        self.points.iter().find(|p| p.availability >= availability)
+    }
+}
+
+/// All `size`-element index subsets of `0..count`, in lexicographic order
+fn combinations(count: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut subsets = Vec::new();
+    if size > count {
+        return subsets;
+    }
+    let mut indices: Vec<usize> = (0..size).collect();
+    loop {
+        subsets.push(indices.clone());
+        // Advance the rightmost index that still has room
+        let Some(position) = (0..size).rev().find(|&i| indices[i] < count - size + i) else {
+            return subsets;
+        };
+        indices[position] += 1;
+        for i in position + 1..size {
+            indices[i] = indices[i - 1] + 1;
+        }
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Largest number of station subsets a single trade study evaluates
+const MAX_TRADE_SUBSETS: usize = 100_000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orbit::OrbitalElements;
+    use crate::propagator::KeplerianPropagator;
+    use chrono::TimeZone;
+
+    fn site(station_id: &str, longitude_deg: f64) -> CandidateSite {
+        let station = GroundStation::builder()
+            .station_id(station_id)
+            .name(station_id)
+            .position(0.0, longitude_deg, 0.0)
+            .build()
+            .unwrap();
+        CandidateSite::new(station)
+    }
+
+    #[test]
+    fn test_trade_study_pareto_front() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite =
+            SatelliteOrbit::new("MEO-01".to_string(), "MEO 01".to_string(), elements, epoch);
+        let cost_model = CostModel {
+            station_capex: 10.0,
+            station_opex_per_year: 1.0,
+            terminal_cost_per_satellite: 2.0,
+            lifetime_years: 5.0,
+        };
+
+        // WEST duplicates EAST's geometry at a higher price
+        let candidates = vec![
+            site("EAST", 0.0).with_clear_sky_probability(0.7),
+            site("FAR", 180.0).with_costs(12.0, 1.0),
+            site("WEST", 0.0)
+                .with_clear_sky_probability(0.7)
+                .with_costs(20.0, 1.0),
+        ];
+        let study = TradeStudy::new(candidates, cost_model).with_sample_seconds(120.0);
+        let report = study
+            .run(
+                &[satellite],
+                epoch,
+                24.0,
+                &VisibilityCalculator::new(),
+                &KeplerianPropagator::new(),
+            )
+            .unwrap();
+        assert_eq!(report.points.len(), 7);
+
+        let point = |ids: &[&str]| report.points.iter().find(|p| p.station_ids == ids).unwrap();
+        let east = point(&["EAST"]);
+        assert_eq!(east.total_cost, 10.0 + 5.0 + 2.0);
+        assert!(east.availability > 0.0 && east.availability < 0.7);
+        // Site diversity: two co-visible sites beat either alone
+        let pair = point(&["EAST", "WEST"]);
+        assert!((pair.availability / east.availability - 0.91 / 0.7).abs() < 1e-9);
+        assert!(point(&["EAST", "FAR"]).availability > east.availability);
+
+        assert!(east.pareto_optimal);
+        assert!(!point(&["WEST"]).pareto_optimal);
+        let front = report.pareto_front();
+        assert!(front
+            .windows(2)
+            .all(|w| w[0].total_cost <= w[1].total_cost && w[0].availability < w[1].availability));
+        assert_eq!(report.cheapest_meeting(0.0).unwrap().station_ids, ["EAST"]);
+
+        let invalid = TradeStudy::new(vec![site("A", 0.0), site("A", 10.0)], study.cost_model);
+        assert!(invalid
+            .run(
+                &[],
+                epoch,
+                1.0,
+                &VisibilityCalculator::new(),
+                &KeplerianPropagator::new()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_site_selection() {
+        let epoch = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(14378.0, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite =
+            SatelliteOrbit::new("MEO-01".to_string(), "MEO 01".to_string(), elements, epoch);
+        let cost_model = CostModel {
+            station_capex: 10.0,
+            station_opex_per_year: 1.0,
+            terminal_cost_per_satellite: 0.0,
+            lifetime_years: 5.0,
+        };
+        // Cloudy EAST and WEST share a view; FAR sees the other side of the orbit
+        let candidates = vec![
+            site("WEST", 0.0)
+                .with_clear_sky_probability(0.7)
+                .with_costs(20.0, 1.0),
+            site("FAR", 180.0),
+            site("EAST", 0.0).with_clear_sky_probability(0.7),
+        ];
+        let study = TradeStudy::new(candidates, cost_model).with_sample_seconds(120.0);
+        let contacts = study
+            .site_contacts(
+                &[satellite],
+                epoch,
+                24.0,
+                &VisibilityCalculator::new(),
+                &KeplerianPropagator::new(),
+            )
+            .unwrap();
+
+        let far = study.trade_point(&[1], &contacts).availability;
+        let east = study.trade_point(&[2], &contacts).availability;
+        let target = far + east - 1e-6;
+        for mode in [SiteSelectionMode::Greedy, SiteSelectionMode::Exact] {
+            let selection = study.select_sites(&contacts, target, mode).unwrap();
+            assert_eq!(selection.station_ids, ["FAR", "EAST"]);
+            assert!(selection.meets_target);
+            assert_eq!(selection.total_cost, 30.0);
+        }
+
+        // Only all three sites reach this; beyond that the target is out of reach
+        let all = study.trade_point(&[0, 1, 2], &contacts).availability;
+        let exact = study
+            .select_sites(&contacts, all - 1e-6, SiteSelectionMode::Exact)
+            .unwrap();
+        assert_eq!(exact.station_ids.len(), 3);
+        let unreachable = study
+            .select_sites(&contacts, all + 0.01, SiteSelectionMode::Greedy)
+            .unwrap();
+        assert!(!unreachable.meets_target);
+        assert!((unreachable.availability - all).abs() < 1e-12);
+
+        // A cancelled search stops before trying any subset
+        let token = crate::progress::CancellationToken::new();
+        token.cancel();
+        let cancelled = study
+            .select_sites_with_progress(&contacts, target, SiteSelectionMode::Exact, &token)
+            .unwrap_err();
+        assert!(matches!(cancelled, OrbitalMechanicsError::Cancelled));
+    }
+}
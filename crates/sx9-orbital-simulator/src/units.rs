@@ -0,0 +1,272 @@
+//! Unit-safe quantities
+//!
+//! Thin newtypes over `f64` for the units that keep getting mixed up:
+//! degrees and radians, kilometres and metres, and seconds. A function taking
+//! `impl Into<Degrees>` accepts `Radians` and converts them, and rejects a
+//! bare `f64` or `Kilometers` at compile time.
+//!
+//! The existing `_deg`/`_km`/`_m` fields and `f64` APIs are unchanged; typed
+//! accessors and builder setters sit alongside them, and `.value()` or the
+//! public field gets the plain number back for older call sites.
+//!
+//! Each quantity adds and subtracts with its own kind, scales by `f64`, and
+//! divides by its own kind to give a plain ratio.
+
+use crate::constants::{DEG_TO_RAD, KM_TO_M, RAD_TO_DEG};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Angle in degrees
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Degrees(pub f64);
+
+/// Angle in radians
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Radians(pub f64);
+
+/// Length in kilometres
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Kilometers(pub f64);
+
+/// Length in metres
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Meters(pub f64);
+
+/// Duration in seconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Seconds(pub f64);
+
+macro_rules! quantity {
+    ($name:ident, $suffix:literal) => {
+        impl $name {
+            pub const fn new(value: f64) -> Self {
+                Self(value)
+            }
+
+            /// The plain number in this type's unit
+            pub const fn value(self) -> f64 {
+                self.0
+            }
+
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            pub fn is_finite(self) -> bool {
+                self.0.is_finite()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)?;
+                f.write_str($suffix)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                $name(self * rhs.0)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|q| q.0).sum())
+            }
+        }
+    };
+}
+
+quantity!(Degrees, "°");
+quantity!(Radians, " rad");
+quantity!(Kilometers, " km");
+quantity!(Meters, " m");
+quantity!(Seconds, " s");
+
+impl Degrees {
+    pub fn to_radians(self) -> Radians {
+        Radians(self.0 * DEG_TO_RAD)
+    }
+
+    /// Wrapped into [-180, 180)
+    pub fn wrapped(self) -> Self {
+        Self(crate::coordinates::wrap_angle_deg(self.0))
+    }
+
+    pub fn sin(self) -> f64 {
+        self.to_radians().sin()
+    }
+
+    pub fn cos(self) -> f64 {
+        self.to_radians().cos()
+    }
+}
+
+impl Radians {
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(self.0 * RAD_TO_DEG)
+    }
+
+    pub fn sin(self) -> f64 {
+        self.0.sin()
+    }
+
+    pub fn cos(self) -> f64 {
+        self.0.cos()
+    }
+}
+
+impl Kilometers {
+    pub fn to_meters(self) -> Meters {
+        Meters(self.0 * KM_TO_M)
+    }
+}
+
+impl Meters {
+    pub fn to_kilometers(self) -> Kilometers {
+        Kilometers(self.0 / KM_TO_M)
+    }
+}
+
+impl Seconds {
+    pub fn minutes(self) -> f64 {
+        self.0 / 60.0
+    }
+
+    pub fn hours(self) -> f64 {
+        self.0 / 3600.0
+    }
+
+    /// Nearest whole-millisecond `chrono::Duration`
+    pub fn to_duration(self) -> chrono::Duration {
+        chrono::Duration::milliseconds((self.0 * 1000.0).round() as i64)
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(angle: Radians) -> Self {
+        angle.to_degrees()
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(angle: Degrees) -> Self {
+        angle.to_radians()
+    }
+}
+
+impl From<Meters> for Kilometers {
+    fn from(length: Meters) -> Self {
+        length.to_kilometers()
+    }
+}
+
+impl From<Kilometers> for Meters {
+    fn from(length: Kilometers) -> Self {
+        length.to_meters()
+    }
+}
+
+impl From<chrono::Duration> for Seconds {
+    fn from(duration: chrono::Duration) -> Self {
+        Self(duration.num_milliseconds() as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions_and_arithmetic() {
+        assert!((Degrees(180.0).to_radians().value() - std::f64::consts::PI).abs() < 1e-12);
+        assert!((Degrees::from(Radians(std::f64::consts::FRAC_PI_2)).value() - 90.0).abs() < 1e-12);
+        assert_eq!(Meters::from(Kilometers(1.5)), Meters(1500.0));
+        assert_eq!(Kilometers::from(Meters(250.0)), Kilometers(0.25));
+        assert_eq!(
+            Seconds::from(chrono::Duration::milliseconds(90_500)),
+            Seconds(90.5)
+        );
+        assert_eq!(
+            Seconds(90.5).to_duration(),
+            chrono::Duration::milliseconds(90_500)
+        );
+
+        assert_eq!(Kilometers(7000.0) - Kilometers(6378.0), Kilometers(622.0));
+        assert_eq!(2.0 * Degrees(30.0), Degrees(60.0));
+        assert_eq!(Seconds(600.0) / Seconds(60.0), 10.0);
+        assert_eq!(Degrees(350.0).wrapped(), Degrees(-10.0));
+        assert!((Degrees(30.0).sin() - 0.5).abs() < 1e-12);
+        let total: Meters = [Meters(1.0), Meters(2.5)].into_iter().sum();
+        assert_eq!(total, Meters(3.5));
+
+        assert_eq!(Degrees(55.0).to_string(), "55°");
+        assert_eq!(
+            serde_json::to_string(&Kilometers(8062.0)).unwrap(),
+            "8062.0"
+        );
+    }
+}
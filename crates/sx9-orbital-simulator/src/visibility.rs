@@ -1,15 +1,25 @@
 //! Visibility calculations between satellites and ground stations
 
 use crate::constants::*;
-use crate::error::{OrbitalMechanicsError, Result};
-use crate::ground_station::GroundStation;
+use crate::coordinates::{EarthModel, Position3D, TopocentricFrame};
+use crate::covariance::StateCovariance;
+use crate::ephemeris;
+use crate::error::{Result, ResultExt};
+use crate::ground_station::{GroundStation, StationChange, StationScoped};
 use crate::orbit::{LookAngles, SatelliteOrbit, SatelliteState};
-use crate::propagator::OrbitalPropagator;
+use crate::pass_profile::{self, PassProfile, PassProfileSample};
+use crate::propagator::{NumericalPropagator, OrbitalPropagator};
+use crate::trace_targets;
+use crate::units::Seconds;
+use crate::visibility_catalog::VisibilityCatalog;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 
 /// Visibility window between satellite and ground station
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VisibilityWindow {
     pub satellite_id: String,
     pub station_id: String,
@@ -19,11 +29,58 @@ pub struct VisibilityWindow {
     pub max_elevation_time: DateTime<Utc>,
     pub max_elevation_deg: f64,
     pub min_range_km: f64,
+    /// Mean slant range over the scan samples inside the pass
+    #[serde(default)]
+    pub mean_range_km: f64,
+    /// Azimuth swept by the sky track, in degrees
+    #[serde(default)]
+    pub azimuth_span_deg: f64,
     pub pass_type: PassType,
+    /// Portions of the pass where the optical link must not be used
+    #[serde(default)]
+    pub unusable_intervals: Vec<UnusableInterval>,
+    /// One-sigma AOS timing uncertainty, when orbit covariance is available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aos_uncertainty_seconds: Option<f64>,
+    /// One-sigma LOS timing uncertainty, when orbit covariance is available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub los_uncertainty_seconds: Option<f64>,
+    /// Portions an Az/El mount cannot track, when checked against mount limits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mount_conflicts: Vec<MountConflict>,
+}
+
+/// Part of a visibility window that cannot be used, with the reason
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UnusableInterval {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Mount limit a pass runs into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum MountConflictKind {
+    /// Azimuth travel exceeds the cable wrap; tracking stops while the mount unwinds
+    CableWrap,
+    /// Transit near zenith, where the azimuth axis cannot keep up
+    Keyhole,
+}
+
+/// Part of a pass a mount cannot track
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MountConflict {
+    pub kind: MountConflictKind,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
 }
 
 /// Type of satellite pass
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum PassType {
     /// Regular pass with acquisition, tracking, and loss of signal
     Normal,
@@ -33,10 +90,189 @@ pub enum PassType {
     Partial,
 }
 
+/// Lighting requirements a pass must satisfy to count as visible
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LightingConstraint {
+    /// Maximum Sun elevation at the station in degrees (e.g. -6.0 for civil twilight)
+    pub station_in_darkness: Option<f64>,
+    /// Require the satellite to be sunlit (`Some(true)`) or eclipsed (`Some(false)`)
+    pub satellite_sunlit: Option<bool>,
+}
+
 /// Visibility calculator
 pub struct VisibilityCalculator {
     pub min_elevation_deg: f64,
     pub time_step_seconds: f64,
+    pub lighting: LightingConstraint,
+    /// Bisect rise/set edges to this accuracy; `None` keeps scan-step edges
+    pub edge_accuracy_seconds: Option<f64>,
+    /// Shape the station coordinates are placed on
+    pub earth_model: EarthModel,
+}
+
+/// Satellite and station a window search is scanning
+struct SearchPair<'a> {
+    satellite: &'a SatelliteOrbit,
+    station: &'a GroundStation,
+    propagator: &'a dyn OrbitalPropagator,
+}
+
+/// Visibility windows cached per satellite/station pair over a fixed span
+///
+/// Each satellite and station is fingerprinted when its windows are computed,
+/// so a refresh only recomputes pairs whose satellite or station was added or
+/// edited and drops pairs whose satellite or station was removed.
+#[derive(Debug, Clone)]
+pub struct VisibilityCache {
+    start_time: DateTime<Utc>,
+    duration_hours: f64,
+    settings: Option<u128>,
+    satellites: HashMap<String, u128>,
+    stations: HashMap<String, u128>,
+    windows: HashMap<(String, String), Vec<VisibilityWindow>>,
+}
+
+/// Work done by one cache refresh
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VisibilityRefresh {
+    pub recomputed_pairs: usize,
+    pub reused_pairs: usize,
+    pub removed_pairs: usize,
+}
+
+impl StationScoped for VisibilityWindow {
+    fn station_id(&self) -> &str {
+        &self.station_id
+    }
+}
+
+impl VisibilityWindow {
+    /// Pass time remaining after removing unusable intervals, in seconds
+    pub fn usable_seconds(&self) -> f64 {
+        let unusable: f64 = self
+            .unusable_intervals
+            .iter()
+            .map(|i| (i.end_time - i.start_time).num_milliseconds() as f64 / 1000.0)
+            .sum();
+        (self.duration_seconds - unusable).max(0.0)
+    }
+
+    /// Window widened by `sigmas` times the AOS/LOS uncertainty
+    ///
+    /// Edges without an uncertainty are left unchanged.
+    pub fn padded_interval(&self, sigmas: f64) -> (DateTime<Utc>, DateTime<Utc>) {
+        let pad = |uncertainty: Option<f64>| {
+            Duration::milliseconds((uncertainty.unwrap_or(0.0) * sigmas * 1000.0) as i64)
+        };
+        (
+            self.start_time - pad(self.aos_uncertainty_seconds),
+            self.end_time + pad(self.los_uncertainty_seconds),
+        )
+    }
+
+    /// Pieces of the pass between its mount conflicts
+    ///
+    /// For schedulers that split affected contacts instead of skipping them.
+    /// Shortened pieces become `Partial` and keep only the unusable intervals
+    /// and edge uncertainties that still apply; peak elevation and range
+    /// figures still describe the whole pass.
+    pub fn split_at_mount_conflicts(&self) -> Vec<VisibilityWindow> {
+        if self.mount_conflicts.is_empty() {
+            return vec![self.clone()];
+        }
+        let mut conflicts: Vec<&MountConflict> = self.mount_conflicts.iter().collect();
+        conflicts.sort_by_key(|conflict| conflict.start_time);
+
+        let mut bounds = Vec::new();
+        let mut cursor = self.start_time;
+        for conflict in conflicts {
+            if conflict.start_time.min(self.end_time) > cursor {
+                bounds.push((cursor, conflict.start_time.min(self.end_time)));
+            }
+            cursor = cursor.max(conflict.end_time);
+        }
+        if cursor < self.end_time {
+            bounds.push((cursor, self.end_time));
+        }
+
+        bounds
+            .into_iter()
+            .map(|(start, end)| {
+                let mut piece = self.clone();
+                piece.start_time = start;
+                piece.end_time = end;
+                piece.duration_seconds = (end - start).num_milliseconds() as f64 / 1000.0;
+                piece.mount_conflicts.clear();
+                piece
+                    .unusable_intervals
+                    .retain(|i| i.start_time < end && i.end_time > start);
+                for interval in &mut piece.unusable_intervals {
+                    interval.start_time = interval.start_time.max(start);
+                    interval.end_time = interval.end_time.min(end);
+                }
+                if start != self.start_time {
+                    piece.aos_uncertainty_seconds = None;
+                }
+                if end != self.end_time {
+                    piece.los_uncertainty_seconds = None;
+                }
+                if (start, end) != (self.start_time, self.end_time) {
+                    piece.pass_type = PassType::Partial;
+                }
+                piece
+            })
+            .collect()
+    }
+}
+
+impl LightingConstraint {
+    /// No lighting requirement
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Require the Sun at the station to be below `max_sun_elevation_deg`
+    pub fn station_in_darkness(max_sun_elevation_deg: f64) -> Self {
+        Self {
+            station_in_darkness: Some(max_sun_elevation_deg),
+            satellite_sunlit: None,
+        }
+    }
+
+    /// Require the satellite to be outside Earth's shadow
+    pub fn satellite_sunlit() -> Self {
+        Self {
+            station_in_darkness: None,
+            satellite_sunlit: Some(true),
+        }
+    }
+
+    /// Dark station and sunlit satellite (optical tracking condition)
+    pub fn optical_tracking(max_sun_elevation_deg: f64) -> Self {
+        Self {
+            station_in_darkness: Some(max_sun_elevation_deg),
+            satellite_sunlit: Some(true),
+        }
+    }
+
+    /// Check the constraint for a satellite state seen from a station
+    pub fn is_satisfied(&self, station: &GroundStation, state: &SatelliteState) -> bool {
+        if let Some(max_sun_elevation_deg) = self.station_in_darkness {
+            let sun_elevation = ephemeris::sun_elevation_deg(
+                station.position.latitude_deg,
+                station.position.longitude_deg,
+                state.timestamp,
+            );
+            if sun_elevation > max_sun_elevation_deg {
+                return false;
+            }
+        }
+
+        match self.satellite_sunlit {
+            Some(required) => ephemeris::is_sunlit(state.position_eci, state.timestamp) == required,
+            None => true,
+        }
+    }
 }
 
 impl VisibilityCalculator {
@@ -45,6 +281,9 @@ impl VisibilityCalculator {
         Self {
             min_elevation_deg: defaults::MIN_ELEVATION_DEG,
             time_step_seconds: 60.0, // 1 minute
+            lighting: LightingConstraint::none(),
+            edge_accuracy_seconds: Some(defaults::VISIBILITY_EDGE_ACCURACY_SECONDS),
+            earth_model: EarthModel::Sphere,
         }
     }
 
@@ -53,9 +292,30 @@ impl VisibilityCalculator {
         Self {
             min_elevation_deg,
             time_step_seconds,
+            lighting: LightingConstraint::none(),
+            edge_accuracy_seconds: Some(defaults::VISIBILITY_EDGE_ACCURACY_SECONDS),
+            earth_model: EarthModel::Sphere,
         }
     }
 
+    /// Apply a lighting constraint to the pass search
+    pub fn with_lighting(mut self, lighting: LightingConstraint) -> Self {
+        self.lighting = lighting;
+        self
+    }
+
+    /// Set rise/set edge accuracy (`None` disables refinement)
+    pub fn with_edge_accuracy(mut self, edge_accuracy_seconds: Option<f64>) -> Self {
+        self.edge_accuracy_seconds = edge_accuracy_seconds;
+        self
+    }
+
+    /// Place stations on `earth_model` (the spherical model by default)
+    pub fn with_earth_model(mut self, earth_model: EarthModel) -> Self {
+        self.earth_model = earth_model;
+        self
+    }
+
     /// Calculate visibility windows
     pub fn calculate_windows(
         &self,
@@ -65,33 +325,55 @@ impl VisibilityCalculator {
         duration_hours: f64,
         propagator: &dyn OrbitalPropagator,
     ) -> Result<Vec<VisibilityWindow>> {
+        let _span = tracing::debug_span!(
+            target: trace_targets::VISIBILITY,
+            "calculate_windows",
+            satellite_id = %satellite.satellite_id,
+            station_id = %station.station_id,
+            propagator = propagator.name()
+        )
+        .entered();
+        let started = Instant::now();
+        let mut propagations = 0usize;
+
         let mut windows = Vec::new();
         let end_time = start_time + Duration::seconds((duration_hours * 3600.0) as i64);
 
+        let step =
+            Duration::milliseconds((self.time_step_seconds * 1000.0).round().max(1.0) as i64);
+
         let mut current_time = start_time;
+        let mut previous_time = start_time;
         let mut in_pass = false;
         let mut pass_start = None;
         let mut max_elevation = 0.0;
         let mut max_elevation_time = start_time;
         let mut min_range = f64::INFINITY;
+        let mut track = SkyTrack::default();
+        let pair = SearchPair {
+            satellite,
+            station,
+            propagator,
+        };
 
         while current_time <= end_time {
-            let state = propagator.propagate(satellite, current_time)?;
-            let look_angles = state.look_angles_from_station(
-                station.position.latitude_deg,
-                station.position.longitude_deg,
-                station.position.elevation_m,
-            );
-
-            let visible = look_angles.elevation_deg >= self.min_elevation_deg;
+            let (visible, look_angles) =
+                self.visibility_at(satellite, station, current_time, propagator)?;
+            propagations += 1;
 
             if visible && !in_pass {
                 // Start of pass
                 in_pass = true;
-                pass_start = Some(current_time);
+                pass_start = Some(if current_time > start_time {
+                    self.refine_edge(&pair, previous_time, current_time, true, &mut propagations)?
+                } else {
+                    current_time
+                });
                 max_elevation = look_angles.elevation_deg;
                 max_elevation_time = current_time;
                 min_range = look_angles.range_km;
+                track = SkyTrack::default();
+                track.add(&look_angles);
             } else if visible && in_pass {
                 // Continue pass - check for maximum elevation
                 if look_angles.elevation_deg > max_elevation {
@@ -101,21 +383,35 @@ impl VisibilityCalculator {
                 if look_angles.range_km < min_range {
                     min_range = look_angles.range_km;
                 }
+                track.add(&look_angles);
             } else if !visible && in_pass {
                 // End of pass
                 if let Some(start) = pass_start {
-                    let duration = (current_time - start).num_seconds() as f64;
+                    let end = self.refine_edge(
+                        &pair,
+                        previous_time,
+                        current_time,
+                        false,
+                        &mut propagations,
+                    )?;
+                    let duration = (end - start).num_milliseconds() as f64 / 1000.0;
 
                     windows.push(VisibilityWindow {
                         satellite_id: satellite.satellite_id.clone(),
                         station_id: station.station_id.clone(),
                         start_time: start,
-                        end_time: current_time,
+                        end_time: end,
                         duration_seconds: duration,
                         max_elevation_time,
                         max_elevation_deg: max_elevation,
                         min_range_km: min_range,
+                        mean_range_km: track.mean_range_km(),
+                        azimuth_span_deg: track.azimuth_span_deg,
                         pass_type: PassType::Normal,
+                        unusable_intervals: Vec::new(),
+                        aos_uncertainty_seconds: None,
+                        los_uncertainty_seconds: None,
+                        mount_conflicts: Vec::new(),
                     });
                 }
 
@@ -124,13 +420,14 @@ impl VisibilityCalculator {
                 min_range = f64::INFINITY;
             }
 
-            current_time += Duration::seconds(self.time_step_seconds as i64);
+            previous_time = current_time;
+            current_time += step;
         }
 
         // Handle pass still in progress at end of observation period
         if in_pass {
             if let Some(start) = pass_start {
-                let duration = (end_time - start).num_seconds() as f64;
+                let duration = (end_time - start).num_milliseconds() as f64 / 1000.0;
 
                 windows.push(VisibilityWindow {
                     satellite_id: satellite.satellite_id.clone(),
@@ -141,11 +438,179 @@ impl VisibilityCalculator {
                     max_elevation_time,
                     max_elevation_deg: max_elevation,
                     min_range_km: min_range,
+                    mean_range_km: track.mean_range_km(),
+                    azimuth_span_deg: track.azimuth_span_deg,
                     pass_type: PassType::Partial,
+                    unusable_intervals: Vec::new(),
+                    aos_uncertainty_seconds: None,
+                    los_uncertainty_seconds: None,
+                    mount_conflicts: Vec::new(),
                 });
             }
         }
 
+        tracing::debug!(
+            target: trace_targets::VISIBILITY,
+            propagations,
+            windows_found = windows.len(),
+            elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "Visibility search complete"
+        );
+
+        Ok(windows)
+    }
+
+    /// Sample look angles, range and range rate through a visibility window
+    ///
+    /// Samples run every `step` from the window start and always include the
+    /// window end. Use `PassProfile::at` or `resample` for other instants.
+    pub fn pass_profile(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+        step: Seconds,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<PassProfile> {
+        let times = pass_profile::sample_times(window.start_time, window.end_time, step)?;
+        let mut samples = Vec::with_capacity(times.len());
+
+        for time in times {
+            let (_, look_angles) = self.visibility_at(satellite, station, time, propagator)?;
+            samples.push(PassProfileSample {
+                timestamp: time,
+                azimuth: look_angles.azimuth(),
+                elevation: look_angles.elevation(),
+                range: look_angles.range(),
+                range_rate_km_per_s: look_angles.range_rate_km_per_s,
+            });
+        }
+
+        Ok(PassProfile {
+            satellite_id: satellite.satellite_id.clone(),
+            station_id: station.station_id.clone(),
+            start_time: window.start_time,
+            end_time: window.end_time,
+            step,
+            samples,
+        })
+    }
+
+    /// Evaluate visibility (elevation mask, lighting and station availability)
+    /// at a single instant
+    fn visibility_at(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        time: DateTime<Utc>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<(bool, LookAngles)> {
+        let state = propagator
+            .propagate(satellite, time)
+            .for_satellite(&satellite.satellite_id)
+            .for_station(&station.station_id)
+            .at_epoch(time)?;
+        let look_angles = state.look_angles_from_station_with_model(
+            &self.earth_model,
+            station.position.latitude_deg,
+            station.position.longitude_deg,
+            station.position.elevation_m,
+        );
+
+        let min_elevation_deg = station.min_elevation_deg.unwrap_or(self.min_elevation_deg);
+        let visible = look_angles.elevation_deg >= min_elevation_deg
+            && self.lighting.is_satisfied(station, &state)
+            && station.is_available(time);
+        Ok((visible, look_angles))
+    }
+
+    /// Bisect a visibility transition between two scan samples
+    ///
+    /// Returns the first visible instant of a rise or the last visible
+    /// instant of a set, within `edge_accuracy_seconds`. Without refinement
+    /// both edges stay on the scan sample after the transition.
+    fn refine_edge(
+        &self,
+        pair: &SearchPair,
+        before: DateTime<Utc>,
+        after: DateTime<Utc>,
+        rising: bool,
+        propagations: &mut usize,
+    ) -> Result<DateTime<Utc>> {
+        let Some(accuracy_seconds) = self.edge_accuracy_seconds else {
+            return Ok(after);
+        };
+        let tolerance = Duration::milliseconds((accuracy_seconds * 1000.0).max(1.0) as i64);
+
+        // Invariant: visibility at `lo` is !rising, visibility at `hi` is rising
+        let (mut lo, mut hi) = (before, after);
+        while hi - lo > tolerance {
+            let mid = lo + (hi - lo) / 2;
+            let (visible, _) =
+                self.visibility_at(pair.satellite, pair.station, mid, pair.propagator)?;
+            *propagations += 1;
+
+            if visible == rising {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        Ok(if rising { hi } else { lo })
+    }
+
+    /// Calculate visibility windows with AOS/LOS timing uncertainty
+    ///
+    /// `epoch_covariance` is the state covariance at the satellite's element
+    /// epoch; it is carried to each edge through the propagator's STM. Edges
+    /// clipped by the search period get no uncertainty.
+    pub fn calculate_windows_with_covariance(
+        &self,
+        satellite: &SatelliteOrbit,
+        station: &GroundStation,
+        start_time: DateTime<Utc>,
+        duration_hours: f64,
+        propagator: &NumericalPropagator,
+        epoch_covariance: &StateCovariance,
+    ) -> Result<Vec<VisibilityWindow>> {
+        let mut windows =
+            self.calculate_windows(satellite, station, start_time, duration_hours, propagator)?;
+
+        for window in &mut windows {
+            let mut edges = Vec::new();
+            if window.start_time > start_time {
+                edges.push((window.start_time, true));
+            }
+            if matches!(window.pass_type, PassType::Normal) {
+                edges.push((window.end_time, false));
+            }
+
+            for (time, rising) in edges {
+                let (state, covariance) = propagator
+                    .propagate_covariance(satellite, time, epoch_covariance)
+                    .for_satellite(&satellite.satellite_id)
+                    .for_station(&station.station_id)
+                    .at_epoch(time)?;
+                let uncertainty =
+                    edge_uncertainty_seconds(&self.earth_model, station, &state, &covariance);
+                if rising {
+                    window.aos_uncertainty_seconds = uncertainty;
+                } else {
+                    window.los_uncertainty_seconds = uncertainty;
+                }
+            }
+        }
+
+        tracing::debug!(
+            target: trace_targets::VISIBILITY,
+            satellite_id = %satellite.satellite_id,
+            station_id = %station.station_id,
+            position_sigma_km = epoch_covariance.position_sigma_km(),
+            windows = windows.len(),
+            "Visibility edge uncertainty computed"
+        );
+
         Ok(windows)
     }
 
@@ -164,8 +629,314 @@ impl VisibilityCalculator {
 
         Ok(windows.into_iter().next())
     }
+
+    /// Bring a cache up to date, recomputing only pairs that changed
+    ///
+    /// Changing any calculator setting or the propagator invalidates the
+    /// whole cache.
+    pub fn update_cache<'a, 'b>(
+        &self,
+        cache: &mut VisibilityCache,
+        satellites: impl IntoIterator<Item = &'a SatelliteOrbit>,
+        stations: impl IntoIterator<Item = &'b GroundStation>,
+        propagator: &dyn OrbitalPropagator,
+    ) -> Result<VisibilityRefresh> {
+        cache.reset_for_settings(self.settings_fingerprint(propagator, &())?);
+        let (start_time, duration_hours) = (cache.start_time, cache.duration_hours);
+        cache.refresh(satellites, stations, |satellite, station| {
+            self.calculate_windows(satellite, station, start_time, duration_hours, propagator)
+        })
+    }
+
+    /// Fingerprint of every setting that affects the windows found
+    ///
+    /// `keep_outs` covers whatever annotates the windows after the search,
+    /// such as the engine's FSO keep-out configuration.
+    pub(crate) fn settings_fingerprint<K: Serialize + ?Sized>(
+        &self,
+        propagator: &dyn OrbitalPropagator,
+        keep_outs: &K,
+    ) -> Result<u128> {
+        fingerprint(&(
+            self.min_elevation_deg,
+            self.time_step_seconds,
+            &self.lighting,
+            self.edge_accuracy_seconds,
+            &self.earth_model,
+            propagator.name(),
+            keep_outs,
+        ))
+    }
 }
 
+impl VisibilityCache {
+    /// Empty cache for windows starting at `start_time`
+    pub fn new(start_time: DateTime<Utc>, duration_hours: f64) -> Self {
+        Self {
+            start_time,
+            duration_hours,
+            settings: None,
+            satellites: HashMap::new(),
+            stations: HashMap::new(),
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Start of the cached span
+    pub fn start_time(&self) -> DateTime<Utc> {
+        self.start_time
+    }
+
+    /// Length of the cached span
+    pub fn duration_hours(&self) -> f64 {
+        self.duration_hours
+    }
+
+    /// Move the cached span, dropping every pair if it changed
+    pub fn set_span(&mut self, start_time: DateTime<Utc>, duration_hours: f64) {
+        if (start_time, duration_hours) != (self.start_time, self.duration_hours) {
+            self.clear();
+            self.start_time = start_time;
+            self.duration_hours = duration_hours;
+        }
+    }
+
+    /// Recompute pairs involving added or edited satellites and stations
+    ///
+    /// `compute` produces the windows of one pair; pairs whose satellite and
+    /// station are both unchanged keep their cached windows.
+    pub fn refresh<'a, 'b, F>(
+        &mut self,
+        satellites: impl IntoIterator<Item = &'a SatelliteOrbit>,
+        stations: impl IntoIterator<Item = &'b GroundStation>,
+        mut compute: F,
+    ) -> Result<VisibilityRefresh>
+    where
+        F: FnMut(&SatelliteOrbit, &GroundStation) -> Result<Vec<VisibilityWindow>>,
+    {
+        let satellites: Vec<(&SatelliteOrbit, u128)> = satellites
+            .into_iter()
+            .map(|s| Ok((s, fingerprint(s).for_satellite(&s.satellite_id)?)))
+            .collect::<Result<_>>()?;
+        let stations: Vec<(&GroundStation, u128)> = stations
+            .into_iter()
+            .map(|s| Ok((s, fingerprint(s).for_station(&s.station_id)?)))
+            .collect::<Result<_>>()?;
+
+        let changed_satellites: HashSet<&str> = satellites
+            .iter()
+            .filter(|(s, hash)| self.satellites.get(&s.satellite_id) != Some(hash))
+            .map(|(s, _)| s.satellite_id.as_str())
+            .collect();
+        let changed_stations: HashSet<&str> = stations
+            .iter()
+            .filter(|(s, hash)| self.stations.get(&s.station_id) != Some(hash))
+            .map(|(s, _)| s.station_id.as_str())
+            .collect();
+
+        let mut refresh = VisibilityRefresh::default();
+        let satellite_ids: HashSet<&str> = satellites
+            .iter()
+            .map(|(s, _)| s.satellite_id.as_str())
+            .collect();
+        let station_ids: HashSet<&str> = stations
+            .iter()
+            .map(|(s, _)| s.station_id.as_str())
+            .collect();
+        self.windows.retain(|(satellite_id, station_id), _| {
+            let keep = satellite_ids.contains(satellite_id.as_str())
+                && station_ids.contains(station_id.as_str());
+            if !keep {
+                refresh.removed_pairs += 1;
+            }
+            keep
+        });
+
+        for (satellite, _) in &satellites {
+            for (station, _) in &stations {
+                let key = (satellite.satellite_id.clone(), station.station_id.clone());
+                let stale = changed_satellites.contains(satellite.satellite_id.as_str())
+                    || changed_stations.contains(station.station_id.as_str())
+                    || !self.windows.contains_key(&key);
+                if stale {
+                    self.windows.insert(key, compute(satellite, station)?);
+                    refresh.recomputed_pairs += 1;
+                } else {
+                    refresh.reused_pairs += 1;
+                }
+            }
+        }
+
+        self.satellites = satellites
+            .iter()
+            .map(|(s, hash)| (s.satellite_id.clone(), *hash))
+            .collect();
+        self.stations = stations
+            .iter()
+            .map(|(s, hash)| (s.station_id.clone(), *hash))
+            .collect();
+
+        tracing::debug!(
+            target: trace_targets::VISIBILITY,
+            recomputed_pairs = refresh.recomputed_pairs,
+            reused_pairs = refresh.reused_pairs,
+            removed_pairs = refresh.removed_pairs,
+            "Visibility cache refreshed"
+        );
+
+        Ok(refresh)
+    }
+
+    /// Drop every cached pair
+    pub fn clear(&mut self) {
+        self.satellites.clear();
+        self.stations.clear();
+        self.windows.clear();
+    }
+
+    /// Force the satellite's pairs to be recomputed on the next refresh
+    pub fn invalidate_satellite(&mut self, satellite_id: &str) {
+        self.satellites.remove(satellite_id);
+    }
+
+    /// Force the station's pairs to be recomputed on the next refresh
+    pub fn invalidate_station(&mut self, station_id: &str) {
+        self.stations.remove(station_id);
+    }
+
+    /// Invalidate after a station change; a removed station's windows are
+    /// dropped at once rather than on the next refresh
+    pub fn apply_station_change(&mut self, change: &StationChange) {
+        self.invalidate_station(change.station_id());
+        if let StationChange::Removed(station_id) = change {
+            self.windows.retain(|(_, station), _| station != station_id);
+        }
+    }
+
+    /// All cached windows ordered by start time, satellite and station
+    pub fn windows(&self) -> Vec<VisibilityWindow> {
+        let mut windows: Vec<VisibilityWindow> = self.windows.values().flatten().cloned().collect();
+        windows.sort_by(|a, b| {
+            a.start_time
+                .cmp(&b.start_time)
+                .then_with(|| a.satellite_id.cmp(&b.satellite_id))
+                .then_with(|| a.station_id.cmp(&b.station_id))
+        });
+        windows
+    }
+
+    /// Cached windows indexed for satellite, station and time queries
+    pub fn catalog(&self) -> VisibilityCatalog {
+        self.windows.values().flatten().cloned().collect()
+    }
+
+    /// Cached windows of one pair
+    pub fn pair_windows(
+        &self,
+        satellite_id: &str,
+        station_id: &str,
+    ) -> Option<&[VisibilityWindow]> {
+        self.windows
+            .get(&(satellite_id.to_string(), station_id.to_string()))
+            .map(Vec::as_slice)
+    }
+
+    /// Clear the cache when the settings producing its windows change
+    pub(crate) fn reset_for_settings(&mut self, settings: u128) {
+        if self.settings != Some(settings) {
+            self.clear();
+            self.settings = Some(settings);
+        }
+    }
+}
+
+/// Stable hash of a value's serialized form
+/// Running range and azimuth totals over the samples of one pass
+#[derive(Default)]
+struct SkyTrack {
+    samples: usize,
+    range_sum_km: f64,
+    azimuth_span_deg: f64,
+    last_azimuth_deg: Option<f64>,
+}
+
+impl SkyTrack {
+    fn add(&mut self, look_angles: &LookAngles) {
+        self.samples += 1;
+        self.range_sum_km += look_angles.range_km;
+        if let Some(last) = self.last_azimuth_deg {
+            // Shortest way round, so tracks crossing north don't count 360°
+            let step = (look_angles.azimuth_deg - last + 540.0).rem_euclid(360.0) - 180.0;
+            self.azimuth_span_deg += step.abs();
+        }
+        self.last_azimuth_deg = Some(look_angles.azimuth_deg);
+    }
+
+    fn mean_range_km(&self) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.range_sum_km / self.samples as f64
+    }
+}
+
+fn fingerprint<T: Serialize + ?Sized>(value: &T) -> Result<u128> {
+    let json = serde_json::to_string(value)?;
+    Ok(murmur3::murmur3_x64_128(&mut json.as_bytes(), 0)?)
+}
+
+/// One-sigma timing uncertainty of an elevation-mask crossing
+///
+/// Position uncertainty along the elevation gradient divided by the elevation
+/// rate; `None` when the pass grazes the mask.
+fn edge_uncertainty_seconds(
+    earth_model: &EarthModel,
+    station: &GroundStation,
+    state: &SatelliteState,
+    covariance: &StateCovariance,
+) -> Option<f64> {
+    let frame = TopocentricFrame::with_model(
+        earth_model,
+        station.position.latitude_deg,
+        station.position.longitude_deg,
+        station.position.elevation_m,
+    );
+    let elevation_deg =
+        |position: [f64; 3]| frame.transform(&Position3D::from(position)).elevation_deg();
+    let offset = |direction: [f64; 3], scale: f64| {
+        [0, 1, 2].map(|i| state.position_eci[i] + direction[i] * scale)
+    };
+
+    let rate_deg_per_s = (elevation_deg(offset(state.velocity_eci, 1.0))
+        - elevation_deg(offset(state.velocity_eci, -1.0)))
+        / 2.0;
+    if rate_deg_per_s.abs() < MIN_EDGE_ELEVATION_RATE_DEG_PER_S {
+        return None;
+    }
+
+    // Central-difference gradient of elevation with respect to position
+    let gradient = [0, 1, 2].map(|axis| {
+        let mut unit = [0.0; 3];
+        unit[axis] = 1.0;
+        (elevation_deg(offset(unit, EDGE_GRADIENT_STEP_KM))
+            - elevation_deg(offset(unit, -EDGE_GRADIENT_STEP_KM)))
+            / (2.0 * EDGE_GRADIENT_STEP_KM)
+    });
+    let position = covariance.position_covariance();
+    let variance_deg2: f64 = (0..3)
+        .flat_map(|i| (0..3).map(move |j| (i, j)))
+        .map(|(i, j)| gradient[i] * position[i][j] * gradient[j])
+        .sum();
+
+    Some(variance_deg2.max(0.0).sqrt() / rate_deg_per_s.abs())
+}
+
+/// Elevation rate below which a mask crossing is treated as grazing
+const MIN_EDGE_ELEVATION_RATE_DEG_PER_S: f64 = 1e-4;
+
+/// Position step for the elevation gradient
+const EDGE_GRADIENT_STEP_KM: f64 = 0.01;
+
 impl Default for VisibilityCalculator {
     fn default() -> Self {
         Self::new()
@@ -175,10 +946,10 @@ impl Default for VisibilityCalculator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ground_station::{GroundStation, StationPosition};
+    use crate::ground_station::{GroundStation, StationOutage, StationPosition};
     use crate::orbit::{OrbitalElements, SatelliteOrbit};
-    use crate::propagator::KeplerianPropagator;
-    use chrono::Utc;
+    use crate::propagator::{KeplerianPropagator, Sgp4Propagator};
+    use chrono::{TimeZone, Utc};
 
     #[test]
     fn test_visibility_calculation() {
@@ -201,6 +972,10 @@ mod tests {
                 longitude_deg: -105.0,
                 elevation_m: 1600.0,
             },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
         };
 
         let windows =
@@ -208,4 +983,536 @@ mod tests {
 
         assert!(windows.is_ok());
     }
+
+    #[test]
+    fn test_lighting_constrained_windows() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+
+        let elements = OrbitalElements::new(14378.0, 0.0, 55.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            start,
+        );
+
+        let station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+
+        let total = |windows: &[VisibilityWindow]| -> f64 {
+            windows.iter().map(|w| w.duration_seconds).sum()
+        };
+
+        let unconstrained = VisibilityCalculator::new()
+            .calculate_windows(&satellite, &station, start, 24.0, &propagator)
+            .unwrap();
+        let lighting = LightingConstraint::optical_tracking(-6.0);
+        let optical = VisibilityCalculator::new()
+            .with_lighting(lighting.clone())
+            .calculate_windows(&satellite, &station, start, 24.0, &propagator)
+            .unwrap();
+
+        assert!(!unconstrained.is_empty());
+        assert!(!optical.is_empty());
+        assert!(total(&optical) < total(&unconstrained));
+        for window in &optical {
+            let state = propagator
+                .propagate(&satellite, window.max_elevation_time)
+                .unwrap();
+            assert!(lighting.is_satisfied(&station, &state));
+        }
+    }
+
+    #[test]
+    fn test_lighting_splits_window_at_terminator() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+
+        // Equatorial LEO seen from a station under the edge of Earth's shadow
+        let elements = OrbitalElements::new(7000.0, 0.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            start,
+        );
+        let station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 114.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+        let windows = |lighting: LightingConstraint| {
+            VisibilityCalculator::new()
+                .with_lighting(lighting)
+                .calculate_windows(&satellite, &station, start, 12.0, &propagator)
+                .unwrap()
+        };
+        let geometric = windows(LightingConstraint::none());
+        let sunlit = windows(LightingConstraint::satellite_sunlit());
+        let eclipsed = windows(LightingConstraint {
+            station_in_darkness: None,
+            satellite_sunlit: Some(false),
+        });
+
+        let within = |parts: &[VisibilityWindow], window: &VisibilityWindow| {
+            parts
+                .iter()
+                .find(|p| p.start_time >= window.start_time && p.end_time <= window.end_time)
+                .cloned()
+        };
+        let (window, lit, dark) = geometric
+            .iter()
+            .find_map(|w| Some((w.clone(), within(&sunlit, w)?, within(&eclipsed, w)?)))
+            .expect("a pass crosses the terminator");
+
+        // The lit and unlit parts tile the pass, meeting at the shadow edge
+        let (first, second) = if lit.start_time < dark.start_time {
+            (&lit, &dark)
+        } else {
+            (&dark, &lit)
+        };
+        let seconds = |a: DateTime<Utc>, b: DateTime<Utc>| (b - a).num_milliseconds().abs();
+        assert!(seconds(first.start_time, window.start_time) <= 1000);
+        assert!(seconds(second.end_time, window.end_time) <= 1000);
+        assert!(seconds(first.end_time, second.start_time) <= 2000);
+        assert!(first.duration_seconds > 60.0 && second.duration_seconds > 60.0);
+
+        let sunlit_at = |part: &VisibilityWindow| {
+            let state = propagator
+                .propagate(
+                    &satellite,
+                    part.start_time + (part.end_time - part.start_time) / 2,
+                )
+                .unwrap();
+            ephemeris::is_sunlit(state.position_eci, state.timestamp)
+        };
+        assert!(sunlit_at(&lit));
+        assert!(!sunlit_at(&dark));
+    }
+
+    #[test]
+    fn test_edge_refinement_accuracy() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+
+        let elements = OrbitalElements::new(7000.0, 0.0, 55.0, 0.0, 0.0, 180.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            start,
+        );
+
+        let station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+
+        // 1 s brute-force scan as the reference
+        let reference = VisibilityCalculator::with_params(10.0, 1.0)
+            .with_edge_accuracy(None)
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap();
+        let refined = VisibilityCalculator::with_params(10.0, 60.0)
+            .with_edge_accuracy(Some(0.1))
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap();
+
+        assert!(!reference.is_empty());
+        assert_eq!(reference.len(), refined.len());
+        for (r, w) in reference.iter().zip(&refined) {
+            assert!((w.start_time - r.start_time).num_milliseconds().abs() <= 1000);
+            assert!((w.end_time - r.end_time).num_milliseconds().abs() <= 1000);
+        }
+
+        // Without refinement edges stay on scan samples, a set on the first
+        // sample below the mask
+        let coarse = VisibilityCalculator::with_params(10.0, 60.0).with_edge_accuracy(None);
+        let unrefined = coarse
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap();
+        assert_eq!(unrefined.len(), refined.len());
+        for window in &unrefined {
+            assert_eq!((window.end_time - start).num_milliseconds() % 60_000, 0);
+            let (visible, _) = coarse
+                .visibility_at(&satellite, &station, window.end_time, &propagator)
+                .unwrap();
+            assert!(!visible || matches!(window.pass_type, PassType::Partial));
+        }
+
+        // A station mask overrides the calculator's, shortening every pass
+        let masked_station = GroundStation {
+            min_elevation_deg: Some(30.0),
+            ..station
+        };
+        let masked = VisibilityCalculator::with_params(10.0, 60.0)
+            .with_edge_accuracy(Some(0.1))
+            .calculate_windows(&satellite, &masked_station, start, 6.0, &propagator)
+            .unwrap();
+        assert!(masked.len() <= refined.len());
+        let total =
+            |windows: &[VisibilityWindow]| windows.iter().map(|w| w.duration_seconds).sum::<f64>();
+        assert!(total(&masked) < total(&refined));
+        assert!(masked.iter().all(|w| w.max_elevation_deg >= 30.0));
+    }
+
+    #[test]
+    fn test_pass_geometry_metrics() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(7000.0, 0.0, 55.0, 0.0, 0.0, 180.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            start,
+        );
+        let station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+
+        let windows = VisibilityCalculator::with_params(10.0, 10.0)
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap();
+
+        assert!(!windows.is_empty());
+        for window in &windows {
+            assert!(window.max_elevation_time >= window.start_time);
+            assert!(window.max_elevation_time <= window.end_time);
+            assert!(window.min_range_km <= window.mean_range_km);
+
+            // Sky track from the sampled look angles, rising to setting
+            let first = propagator
+                .propagate(&satellite, window.start_time)
+                .unwrap()
+                .look_angles_from_station(0.0, 0.0, 0.0);
+            let last = propagator
+                .propagate(&satellite, window.end_time)
+                .unwrap()
+                .look_angles_from_station(0.0, 0.0, 0.0);
+            let chord = (last.azimuth_deg - first.azimuth_deg + 540.0).rem_euclid(360.0) - 180.0;
+            assert!(window.azimuth_span_deg + 1.0 >= chord.abs());
+            assert!(window.azimuth_span_deg < 360.0);
+        }
+    }
+
+    #[test]
+    fn test_station_outage_splits_windows() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(7000.0, 0.0, 55.0, 0.0, 0.0, 180.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            start,
+        );
+        let mut station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+        let calculator =
+            VisibilityCalculator::with_params(10.0, 10.0).with_edge_accuracy(Some(0.1));
+
+        let open = calculator
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap();
+        let pass = open
+            .iter()
+            .find(|w| w.start_time > start && w.duration_seconds > 120.0)
+            .expect("no complete pass");
+
+        // Outage over the middle minute of the pass
+        let middle = pass.start_time + (pass.end_time - pass.start_time) / 2;
+        station.availability.outages.push(StationOutage {
+            start_time: middle - Duration::seconds(30),
+            end_time: middle + Duration::seconds(30),
+            reason: "Maintenance".to_string(),
+        });
+        let constrained = calculator
+            .calculate_windows(&satellite, &station, start, 6.0, &propagator)
+            .unwrap();
+
+        assert_eq!(constrained.len(), open.len() + 1);
+        let before = constrained
+            .iter()
+            .find(|w| w.start_time == pass.start_time)
+            .unwrap();
+        assert!(
+            (before.end_time - (middle - Duration::seconds(30)))
+                .num_milliseconds()
+                .abs()
+                <= 200
+        );
+        for window in &constrained {
+            assert!(
+                window.end_time < middle - Duration::seconds(30)
+                    || window.start_time > middle + Duration::seconds(30)
+            );
+        }
+    }
+
+    #[test]
+    fn test_edge_uncertainty_from_covariance() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let elements = OrbitalElements::new(7000.0, 0.0, 55.0, 0.0, 0.0, 180.0).unwrap();
+        let satellite = SatelliteOrbit::new(
+            "TEST-01".to_string(),
+            "Test Satellite".to_string(),
+            elements,
+            start,
+        );
+        let station = GroundStation {
+            station_id: "GS-001".to_string(),
+            name: "Test Station".to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg: 0.0,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+        let calculator =
+            VisibilityCalculator::with_params(10.0, 60.0).with_edge_accuracy(Some(0.1));
+        let propagator = NumericalPropagator::new(30.0);
+
+        let windows_for = |sigma_km: f64| {
+            calculator
+                .calculate_windows_with_covariance(
+                    &satellite,
+                    &station,
+                    start,
+                    6.0,
+                    &propagator,
+                    &StateCovariance::from_sigmas(sigma_km, 0.0),
+                )
+                .unwrap()
+        };
+        let tight = windows_for(0.1);
+        let loose = windows_for(1.0);
+        let window = tight
+            .iter()
+            .position(|w| w.start_time > start && matches!(w.pass_type, PassType::Normal))
+            .expect("no complete pass");
+
+        // LEO at 7 km/s: a kilometer of position error is a fraction of a second
+        let aos = tight[window].aos_uncertainty_seconds.unwrap();
+        let los = tight[window].los_uncertainty_seconds.unwrap();
+        assert!(aos > 0.0 && aos < 1.0, "AOS uncertainty {} s", aos);
+        assert!(los > 0.0 && los < 1.0, "LOS uncertainty {} s", los);
+
+        // Linear in the position sigma
+        let loose_aos = loose[window].aos_uncertainty_seconds.unwrap();
+        assert!((loose_aos / aos - 10.0).abs() < 0.1);
+
+        let (padded_start, padded_end) = loose[window].padded_interval(3.0);
+        assert!(padded_start < loose[window].start_time);
+        assert!(padded_end > loose[window].end_time);
+    }
+
+    #[test]
+    fn test_incremental_refresh_matches_full_recompute() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let satellite = |id: &str, mean_anomaly_deg: f64| {
+            let elements =
+                OrbitalElements::new(7000.0, 0.0, 55.0, 0.0, 0.0, mean_anomaly_deg).unwrap();
+            SatelliteOrbit::new(id.to_string(), id.to_string(), elements, start)
+        };
+        let station = |id: &str, longitude_deg: f64| GroundStation {
+            station_id: id.to_string(),
+            name: id.to_string(),
+            position: StationPosition {
+                latitude_deg: 0.0,
+                longitude_deg,
+                elevation_m: 0.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+        let calculator = VisibilityCalculator::with_params(10.0, 30.0);
+        let full = |satellites: &[SatelliteOrbit], stations: &[GroundStation]| {
+            let mut fresh = VisibilityCache::new(start, 6.0);
+            calculator
+                .update_cache(&mut fresh, satellites, stations, &propagator)
+                .unwrap();
+            fresh.windows()
+        };
+
+        let mut satellites = vec![satellite("SAT-A", 0.0), satellite("SAT-B", 120.0)];
+        let mut stations = vec![station("GS-1", 0.0), station("GS-2", 180.0)];
+        let mut cache = VisibilityCache::new(start, 6.0);
+        let refresh = calculator
+            .update_cache(&mut cache, &satellites, &stations, &propagator)
+            .unwrap();
+        assert_eq!(refresh.recomputed_pairs, 4);
+
+        // Edit one satellite, drop another, add one, and add a station
+        satellites[0].elements.mean_anomaly_deg = 45.0;
+        satellites.remove(1);
+        satellites.push(satellite("SAT-C", 240.0));
+        stations.push(station("GS-3", -90.0));
+        let refresh = calculator
+            .update_cache(&mut cache, &satellites, &stations, &propagator)
+            .unwrap();
+        assert_eq!(
+            refresh,
+            VisibilityRefresh {
+                recomputed_pairs: 6,
+                reused_pairs: 0,
+                removed_pairs: 2,
+            }
+        );
+
+        // Only the new station's pairs change
+        stations[2].position.longitude_deg = 0.0;
+        let refresh = calculator
+            .update_cache(&mut cache, &satellites, &stations, &propagator)
+            .unwrap();
+        assert_eq!(refresh.recomputed_pairs, 2);
+        assert_eq!(refresh.reused_pairs, 4);
+
+        let expected = full(&satellites, &stations);
+        let incremental = cache.windows();
+        assert!(!expected.is_empty());
+        assert_eq!(incremental.len(), expected.len());
+        for (a, b) in incremental.iter().zip(&expected) {
+            assert_eq!(a.satellite_id, b.satellite_id);
+            assert_eq!(a.station_id, b.station_id);
+            assert_eq!(a.start_time, b.start_time);
+            assert_eq!(a.end_time, b.end_time);
+        }
+
+        // Changing a calculator setting invalidates everything
+        let stricter = VisibilityCalculator::with_params(20.0, 30.0);
+        let refresh = stricter
+            .update_cache(&mut cache, &satellites, &stations, &propagator)
+            .unwrap();
+        assert_eq!(refresh.recomputed_pairs, 6);
+
+        // So does switching propagator
+        let refresh = stricter
+            .update_cache(&mut cache, &satellites, &stations, &Sgp4Propagator::new())
+            .unwrap();
+        assert_eq!(refresh.recomputed_pairs, 6);
+        assert_eq!(refresh.reused_pairs, 0);
+
+        // And moving the span
+        cache.set_span(start + Duration::hours(6), 6.0);
+        assert!(cache.windows().is_empty());
+        let refresh = stricter
+            .update_cache(&mut cache, &satellites, &stations, &Sgp4Propagator::new())
+            .unwrap();
+        assert_eq!(refresh.recomputed_pairs, 6);
+        assert!(cache
+            .windows()
+            .iter()
+            .all(|w| w.end_time > start + Duration::hours(6)));
+    }
+
+    #[test]
+    fn test_windows_follow_selected_earth_model() {
+        let propagator = KeplerianPropagator::new();
+        let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        // Apex of the ground track passes over the station
+        let elements = OrbitalElements::new(7000.0, 0.0, 60.0, 0.0, 0.0, 0.0).unwrap();
+        let satellite =
+            SatelliteOrbit::new("HI-01".to_string(), "High".to_string(), elements, start);
+        let station = GroundStation {
+            station_id: "GS-N".to_string(),
+            name: "Northern".to_string(),
+            position: StationPosition {
+                latitude_deg: 60.0,
+                longitude_deg: 90.0,
+                elevation_m: 300.0,
+            },
+            availability: Default::default(),
+            mount: None,
+            min_elevation_deg: None,
+            timezone: None,
+        };
+
+        let windows_on = |model: EarthModel| {
+            VisibilityCalculator::with_params(10.0, 10.0)
+                .with_earth_model(model)
+                .calculate_windows(&satellite, &station, start, 2.0, &propagator)
+                .unwrap()
+        };
+        let ellipsoid = windows_on(EarthModel::Wgs84);
+        let sphere = windows_on(EarthModel::Sphere);
+        assert!(!ellipsoid.is_empty());
+        assert_eq!(ellipsoid.len(), sphere.len());
+
+        for (window, spherical) in ellipsoid.iter().zip(&sphere) {
+            // Window geometry matches the orbit module on the same model
+            let look = propagator
+                .propagate(&satellite, window.max_elevation_time)
+                .unwrap()
+                .look_angles_from_station_with_model(&EarthModel::Wgs84, 60.0, 90.0, 300.0);
+            assert!((window.max_elevation_deg - look.elevation_deg).abs() < 1e-9);
+            // The ellipsoid puts the station ~23 km closer to the equatorial plane
+            assert!((window.max_elevation_deg - spherical.max_elevation_deg).abs() > 0.1);
+        }
+
+        let settings = |model| {
+            VisibilityCalculator::new()
+                .with_earth_model(model)
+                .settings_fingerprint(&propagator, &())
+                .unwrap()
+        };
+        assert_ne!(settings(EarthModel::Wgs84), settings(EarthModel::Sphere));
+    }
 }
@@ -0,0 +1,364 @@
+//! Indexed visibility window catalog
+//!
+//! Answers "which passes overlap this span" and "next pass after T" without
+//! scanning every window. Windows are indexed three ways (all windows, per
+//! satellite, per station) plus per satellite/station pair, each as a static
+//! interval tree: entries sorted by start time, laid out as an implicit
+//! balanced tree with the latest end time of every subtree, so a range query
+//! visits only subtrees that can overlap.
+//!
+//! The catalog is immutable once built; build it from any window source
+//! (`VisibilityCalculator`, `VisibilityCache::catalog`, a loaded scenario)
+//! and rebuild after the windows change. Intervals are closed: a window that
+//! ends exactly at a query start still overlaps it.
+
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Visibility windows indexed by satellite, station and time
+#[derive(Debug, Clone, Default)]
+pub struct VisibilityCatalog {
+    /// Ordered by start time, satellite and station
+    windows: Vec<VisibilityWindow>,
+    all: IntervalTree,
+    by_satellite: HashMap<String, IntervalTree>,
+    by_station: HashMap<String, IntervalTree>,
+    by_pair: HashMap<(String, String), IntervalTree>,
+}
+
+/// Static interval tree over window indices
+#[derive(Debug, Clone, Default)]
+struct IntervalTree {
+    /// (start, end, window index), sorted by start
+    entries: Vec<(DateTime<Utc>, DateTime<Utc>, usize)>,
+    /// Latest end in the subtree rooted at each entry
+    max_end: Vec<DateTime<Utc>>,
+}
+
+impl VisibilityCatalog {
+    /// Index `windows`
+    pub fn new(mut windows: Vec<VisibilityWindow>) -> Self {
+        windows.sort_by(|a, b| {
+            a.start_time
+                .cmp(&b.start_time)
+                .then_with(|| a.satellite_id.cmp(&b.satellite_id))
+                .then_with(|| a.station_id.cmp(&b.station_id))
+        });
+
+        let mut by_satellite: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut by_station: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut by_pair: HashMap<(String, String), Vec<usize>> = HashMap::new();
+        for (index, window) in windows.iter().enumerate() {
+            by_satellite
+                .entry(window.satellite_id.clone())
+                .or_default()
+                .push(index);
+            by_station
+                .entry(window.station_id.clone())
+                .or_default()
+                .push(index);
+            by_pair
+                .entry((window.satellite_id.clone(), window.station_id.clone()))
+                .or_default()
+                .push(index);
+        }
+
+        let build = |indices: Vec<usize>| IntervalTree::build(&windows, indices);
+        Self {
+            all: build((0..windows.len()).collect()),
+            by_satellite: by_satellite
+                .into_iter()
+                .map(|(k, v)| (k, build(v)))
+                .collect(),
+            by_station: by_station.into_iter().map(|(k, v)| (k, build(v))).collect(),
+            by_pair: by_pair.into_iter().map(|(k, v)| (k, build(v))).collect(),
+            windows,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Every window, ordered by start time, satellite and station
+    pub fn windows(&self) -> &[VisibilityWindow] {
+        &self.windows
+    }
+
+    /// Windows overlapping `[from, to]`, ordered by start time
+    pub fn overlapping(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<&VisibilityWindow> {
+        self.collect(Some(&self.all), from, to)
+    }
+
+    /// Windows in progress at `time`
+    pub fn active_at(&self, time: DateTime<Utc>) -> Vec<&VisibilityWindow> {
+        self.overlapping(time, time)
+    }
+
+    /// One satellite's windows overlapping `[from, to]`
+    pub fn satellite_windows(
+        &self,
+        satellite_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<&VisibilityWindow> {
+        self.collect(self.by_satellite.get(satellite_id), from, to)
+    }
+
+    /// One station's windows overlapping `[from, to]`
+    pub fn station_windows(
+        &self,
+        station_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<&VisibilityWindow> {
+        self.collect(self.by_station.get(station_id), from, to)
+    }
+
+    /// One pair's windows overlapping `[from, to]`
+    pub fn pair_windows(
+        &self,
+        satellite_id: &str,
+        station_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<&VisibilityWindow> {
+        let key = (satellite_id.to_string(), station_id.to_string());
+        self.collect(self.by_pair.get(&key), from, to)
+    }
+
+    /// First pass of the satellite over the station starting at or after `after`
+    pub fn next_pass(
+        &self,
+        satellite_id: &str,
+        station_id: &str,
+        after: DateTime<Utc>,
+    ) -> Option<&VisibilityWindow> {
+        let key = (satellite_id.to_string(), station_id.to_string());
+        self.first_starting(self.by_pair.get(&key)?, after)
+    }
+
+    /// First pass of the satellite over any station starting at or after `after`
+    pub fn next_satellite_pass(
+        &self,
+        satellite_id: &str,
+        after: DateTime<Utc>,
+    ) -> Option<&VisibilityWindow> {
+        self.first_starting(self.by_satellite.get(satellite_id)?, after)
+    }
+
+    /// First pass of any satellite over the station starting at or after `after`
+    pub fn next_station_pass(
+        &self,
+        station_id: &str,
+        after: DateTime<Utc>,
+    ) -> Option<&VisibilityWindow> {
+        self.first_starting(self.by_station.get(station_id)?, after)
+    }
+
+    fn collect(
+        &self,
+        tree: Option<&IntervalTree>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<&VisibilityWindow> {
+        let mut found = Vec::new();
+        if let Some(tree) = tree {
+            tree.query(0, tree.entries.len(), from, to, &mut found);
+        }
+        found.sort_unstable();
+        found
+            .into_iter()
+            .map(|index| &self.windows[index])
+            .collect()
+    }
+
+    fn first_starting(
+        &self,
+        tree: &IntervalTree,
+        after: DateTime<Utc>,
+    ) -> Option<&VisibilityWindow> {
+        let position = tree.entries.partition_point(|(start, _, _)| *start < after);
+        tree.entries
+            .get(position)
+            .map(|(_, _, index)| &self.windows[*index])
+    }
+}
+
+impl FromIterator<VisibilityWindow> for VisibilityCatalog {
+    fn from_iter<I: IntoIterator<Item = VisibilityWindow>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl IntervalTree {
+    /// `indices` must already be ordered by window start
+    fn build(windows: &[VisibilityWindow], indices: Vec<usize>) -> Self {
+        let entries: Vec<_> = indices
+            .into_iter()
+            .map(|i| (windows[i].start_time, windows[i].end_time, i))
+            .collect();
+        let mut max_end: Vec<DateTime<Utc>> = entries.iter().map(|(_, end, _)| *end).collect();
+        Self::fill_max_end(&entries, &mut max_end, 0, entries.len());
+        Self { entries, max_end }
+    }
+
+    /// Subtree `[lo, hi)` is rooted at its midpoint
+    fn fill_max_end(
+        entries: &[(DateTime<Utc>, DateTime<Utc>, usize)],
+        max_end: &mut [DateTime<Utc>],
+        lo: usize,
+        hi: usize,
+    ) -> Option<DateTime<Utc>> {
+        if lo >= hi {
+            return None;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let left = Self::fill_max_end(entries, max_end, lo, mid);
+        let right = Self::fill_max_end(entries, max_end, mid + 1, hi);
+        let subtree_max = [left, right]
+            .into_iter()
+            .flatten()
+            .fold(entries[mid].1, DateTime::max);
+        max_end[mid] = subtree_max;
+        Some(subtree_max)
+    }
+
+    fn query(
+        &self,
+        lo: usize,
+        hi: usize,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        found: &mut Vec<usize>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] < from {
+            return;
+        }
+
+        self.query(lo, mid, from, to, found);
+        let (start, end, index) = self.entries[mid];
+        if start > to {
+            // Everything to the right starts even later
+            return;
+        }
+        if end >= from {
+            found.push(index);
+        }
+        self.query(mid + 1, hi, from, to, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visibility::PassType;
+    use chrono::{Duration, TimeZone};
+
+    fn window(
+        satellite: usize,
+        station: usize,
+        start_min: i64,
+        length_min: i64,
+    ) -> VisibilityWindow {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        VisibilityWindow {
+            satellite_id: format!("SAT-{}", satellite),
+            station_id: format!("GS-{}", station),
+            start_time: start + Duration::minutes(start_min),
+            end_time: start + Duration::minutes(start_min + length_min),
+            duration_seconds: length_min as f64 * 60.0,
+            max_elevation_time: start + Duration::minutes(start_min + length_min / 2),
+            max_elevation_deg: 45.0,
+            min_range_km: 800.0,
+            mean_range_km: 1200.0,
+            azimuth_span_deg: 120.0,
+            pass_type: PassType::Normal,
+            unusable_intervals: Vec::new(),
+            aos_uncertainty_seconds: None,
+            los_uncertainty_seconds: None,
+            mount_conflicts: Vec::new(),
+        }
+    }
+
+    fn windows() -> Vec<VisibilityWindow> {
+        // Deterministic scatter of short passes and a few long ones
+        (0..600)
+            .map(|i| {
+                let start = (i * 37 % 1440) as i64;
+                let length = if i % 50 == 0 {
+                    300
+                } else {
+                    4 + (i % 11) as i64
+                };
+                window(i % 13, i % 7, start, length)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_queries_match_linear_scan() {
+        let all = windows();
+        let catalog: VisibilityCatalog = all.iter().cloned().collect();
+        assert_eq!(catalog.len(), all.len());
+
+        let origin = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        for (from_min, to_min) in [(0, 0), (100, 160), (700, 701), (1430, 2000), (-60, -1)] {
+            let from = origin + Duration::minutes(from_min);
+            let to = origin + Duration::minutes(to_min);
+            let overlaps = |w: &VisibilityWindow| w.start_time <= to && w.end_time >= from;
+
+            let expected = all.iter().filter(|w| overlaps(w)).count();
+            let found = catalog.overlapping(from, to);
+            assert_eq!(found.len(), expected);
+            assert!(found.iter().all(|w| overlaps(w)));
+            assert!(found.windows(2).all(|p| p[0].start_time <= p[1].start_time));
+
+            let expected = all
+                .iter()
+                .filter(|w| w.satellite_id == "SAT-3" && overlaps(w))
+                .count();
+            assert_eq!(catalog.satellite_windows("SAT-3", from, to).len(), expected);
+            let expected = all
+                .iter()
+                .filter(|w| w.station_id == "GS-2" && overlaps(w))
+                .count();
+            assert_eq!(catalog.station_windows("GS-2", from, to).len(), expected);
+        }
+        assert!(catalog
+            .satellite_windows("SAT-99", origin, origin + Duration::days(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_next_pass() {
+        let catalog = VisibilityCatalog::new(windows());
+        let origin = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let after = origin + Duration::minutes(500);
+
+        let next = catalog.next_pass("SAT-4", "GS-4", after).unwrap();
+        let expected = windows()
+            .into_iter()
+            .filter(|w| w.satellite_id == "SAT-4" && w.station_id == "GS-4")
+            .filter(|w| w.start_time >= after)
+            .min_by_key(|w| w.start_time)
+            .unwrap();
+        assert_eq!(next.start_time, expected.start_time);
+
+        let next = catalog.next_station_pass("GS-0", after).unwrap();
+        assert!(next.start_time >= after && next.station_id == "GS-0");
+        assert!(catalog
+            .next_satellite_pass("SAT-1", origin + Duration::days(2))
+            .is_none());
+        assert!(catalog.next_pass("SAT-99", "GS-0", origin).is_none());
+    }
+}
@@ -0,0 +1,314 @@
+//! Weather go/no-go decisions for optical contacts
+//!
+//! Clouds block an optical link outright and haze eats its margin, so the
+//! station chosen for a downlink depends on the sky over it when the choice
+//! is made. A `WeatherProvider` answers for one station at one time; the
+//! simulator consults it each tick when it picks a downlink station, and
+//! `WeatherLimits::decide_pass` answers for a whole pass. Operators plug in
+//! a nowcasting service by implementing the trait.
+//!
+//! Two providers ship with the crate: `ConstantWeather`, the same sky
+//! everywhere, and `FileWeatherProvider`, which replays station
+//! observations from a file with one JSON `WeatherObservation` per line.
+
+use crate::error::{OrbitalMechanicsError, Result, ResultExt};
+use crate::ground_station::GroundStation;
+use crate::visibility::VisibilityWindow;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Source of the weather over ground stations
+pub trait WeatherProvider: Send + Sync {
+    /// Fraction of the sky covered by cloud, 0-1
+    fn cloud_cover(&self, station: &GroundStation, time: DateTime<Utc>) -> f64;
+
+    /// Horizontal visibility
+    fn visibility_km(&self, station: &GroundStation, time: DateTime<Utc>) -> f64;
+}
+
+/// The same sky over every station at every time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConstantWeather {
+    pub cloud_cover: f64,
+    pub visibility_km: f64,
+}
+
+/// Weather reported at a station
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeatherObservation {
+    pub station_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// 0-1
+    pub cloud_cover: f64,
+    pub visibility_km: f64,
+}
+
+/// Recorded observations, each holding until the next or until it goes stale
+#[derive(Debug, Clone)]
+pub struct FileWeatherProvider {
+    observations: HashMap<String, Vec<WeatherObservation>>,
+    /// Observations older than this are not used
+    pub max_observation_age_seconds: f64,
+    /// Used for stations without a recent observation
+    pub fallback: ConstantWeather,
+}
+
+/// Worst weather a contact may be taken in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherLimits {
+    pub max_cloud_cover: f64,
+    pub min_visibility_km: f64,
+}
+
+/// Outcome of a weather check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WeatherDecision {
+    Go,
+    NoGo { reason: String },
+}
+
+impl ConstantWeather {
+    pub fn new(cloud_cover: f64, visibility_km: f64) -> Self {
+        Self {
+            cloud_cover,
+            visibility_km,
+        }
+    }
+
+    /// Cloudless with 50 km visibility
+    pub fn clear() -> Self {
+        Self::new(0.0, CLEAR_VISIBILITY_KM)
+    }
+}
+
+impl WeatherProvider for ConstantWeather {
+    fn cloud_cover(&self, _station: &GroundStation, _time: DateTime<Utc>) -> f64 {
+        self.cloud_cover
+    }
+
+    fn visibility_km(&self, _station: &GroundStation, _time: DateTime<Utc>) -> f64 {
+        self.visibility_km
+    }
+}
+
+impl FileWeatherProvider {
+    /// Provider with observations held up to an hour, clear sky otherwise
+    pub fn new(observations: impl IntoIterator<Item = WeatherObservation>) -> Result<Self> {
+        let mut provider = Self {
+            observations: HashMap::new(),
+            max_observation_age_seconds: 3600.0,
+            fallback: ConstantWeather::clear(),
+        };
+        for observation in observations {
+            provider.ingest(observation)?;
+        }
+        Ok(provider)
+    }
+
+    /// Read an observation file: one JSON `WeatherObservation` per line
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).for_path(path)?;
+        let observations = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).for_path(path))
+            .collect::<Result<Vec<WeatherObservation>>>()?;
+        Self::new(observations).for_path(path)
+    }
+
+    pub fn with_fallback(mut self, fallback: ConstantWeather) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    pub fn with_max_observation_age(mut self, seconds: f64) -> Self {
+        self.max_observation_age_seconds = seconds;
+        self
+    }
+
+    /// Add an observation; one at an existing timestamp replaces it
+    pub fn ingest(&mut self, observation: WeatherObservation) -> Result<()> {
+        if !(0.0..=1.0).contains(&observation.cloud_cover) || observation.visibility_km < 0.0 {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Invalid weather at {} {}: cloud cover {}, visibility {} km",
+                observation.station_id,
+                observation.timestamp,
+                observation.cloud_cover,
+                observation.visibility_km
+            )));
+        }
+        let history = self
+            .observations
+            .entry(observation.station_id.clone())
+            .or_default();
+        match history.binary_search_by_key(&observation.timestamp, |o| o.timestamp) {
+            Ok(index) => history[index] = observation,
+            Err(index) => history.insert(index, observation),
+        }
+        Ok(())
+    }
+
+    /// Latest observation at the station up to `time`, if still fresh
+    pub fn observation_at(
+        &self,
+        station_id: &str,
+        time: DateTime<Utc>,
+    ) -> Option<&WeatherObservation> {
+        let max_age = Duration::milliseconds((self.max_observation_age_seconds * 1000.0) as i64);
+        let history = self.observations.get(station_id)?;
+        let latest = history.partition_point(|o| o.timestamp <= time);
+        history[..latest]
+            .last()
+            .filter(|observation| time - observation.timestamp <= max_age)
+    }
+}
+
+impl WeatherProvider for FileWeatherProvider {
+    fn cloud_cover(&self, station: &GroundStation, time: DateTime<Utc>) -> f64 {
+        self.observation_at(&station.station_id, time)
+            .map_or(self.fallback.cloud_cover, |o| o.cloud_cover)
+    }
+
+    fn visibility_km(&self, station: &GroundStation, time: DateTime<Utc>) -> f64 {
+        self.observation_at(&station.station_id, time)
+            .map_or(self.fallback.visibility_km, |o| o.visibility_km)
+    }
+}
+
+impl Default for WeatherLimits {
+    fn default() -> Self {
+        Self {
+            max_cloud_cover: 0.5,
+            min_visibility_km: 10.0,
+        }
+    }
+}
+
+impl WeatherLimits {
+    pub fn new(max_cloud_cover: f64, min_visibility_km: f64) -> Self {
+        Self {
+            max_cloud_cover,
+            min_visibility_km,
+        }
+    }
+
+    /// Whether a contact may be taken at the station at `time`
+    pub fn decide(
+        &self,
+        provider: &dyn WeatherProvider,
+        station: &GroundStation,
+        time: DateTime<Utc>,
+    ) -> WeatherDecision {
+        let cloud_cover = provider.cloud_cover(station, time);
+        if cloud_cover > self.max_cloud_cover {
+            return WeatherDecision::NoGo {
+                reason: format!(
+                    "Cloud cover {:.0}% over {} exceeds {:.0}%",
+                    cloud_cover * 100.0,
+                    station.station_id,
+                    self.max_cloud_cover * 100.0
+                ),
+            };
+        }
+        let visibility_km = provider.visibility_km(station, time);
+        if visibility_km < self.min_visibility_km {
+            return WeatherDecision::NoGo {
+                reason: format!(
+                    "Visibility {:.1} km at {} is below {:.1} km",
+                    visibility_km, station.station_id, self.min_visibility_km
+                ),
+            };
+        }
+        WeatherDecision::Go
+    }
+
+    /// Weather decision for a pass, taken at its start
+    ///
+    /// The provider is asked when the call is made, so a nowcast decides
+    /// with the latest data it has for the start of the pass.
+    pub fn decide_pass(
+        &self,
+        provider: &dyn WeatherProvider,
+        station: &GroundStation,
+        window: &VisibilityWindow,
+    ) -> Result<WeatherDecision> {
+        if station.station_id != window.station_id {
+            return Err(OrbitalMechanicsError::config_error(format!(
+                "Pass is over {}, not {}",
+                window.station_id, station.station_id
+            )));
+        }
+        Ok(self.decide(provider, station, window.start_time))
+    }
+}
+
+impl WeatherDecision {
+    pub fn is_go(&self) -> bool {
+        matches!(self, WeatherDecision::Go)
+    }
+}
+
+const CLEAR_VISIBILITY_KM: f64 = 50.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_weather_decisions() {
+        let t0 = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let station = |id: &str| {
+            GroundStation::builder()
+                .station_id(id)
+                .latitude_deg(20.0)
+                .longitude_deg(-155.0)
+                .build()
+                .unwrap()
+        };
+        let (hawaii, spain) = (station("GS-HAWAII"), station("GS-SPAIN"));
+        let limits = WeatherLimits::default();
+
+        let overcast = ConstantWeather::new(0.9, 20.0);
+        assert!(!limits.decide(&overcast, &hawaii, t0).is_go());
+        assert!(limits
+            .decide(&ConstantWeather::clear(), &hawaii, t0)
+            .is_go());
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("observations.jsonl");
+        fs::write(
+            &path,
+            r#"{"station_id": "GS-HAWAII", "timestamp": "2024-03-20T00:00:00Z", "cloud_cover": 0.8, "visibility_km": 30.0}
+
+{"station_id": "GS-HAWAII", "timestamp": "2024-03-20T01:00:00Z", "cloud_cover": 0.1, "visibility_km": 4.0}"#,
+        )
+        .unwrap();
+        let provider = FileWeatherProvider::load(&path).unwrap();
+
+        assert_eq!(
+            provider.cloud_cover(&hawaii, t0 + Duration::minutes(30)),
+            0.8
+        );
+        let hazy = limits.decide(&provider, &hawaii, t0 + Duration::minutes(90));
+        assert!(matches!(hazy, WeatherDecision::NoGo { reason } if reason.contains("Visibility")));
+        // Stale or missing observations fall back to a clear sky
+        assert!(limits
+            .decide(&provider, &hawaii, t0 + Duration::hours(3))
+            .is_go());
+        assert!(limits.decide(&provider, &spain, t0).is_go());
+
+        let invalid = WeatherObservation {
+            station_id: "GS-SPAIN".to_string(),
+            timestamp: t0,
+            cloud_cover: 1.5,
+            visibility_km: 10.0,
+        };
+        assert!(FileWeatherProvider::new([invalid]).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+//! Live simulation event stream over WebSocket
+//!
+//! The dashboard renders constellation state from JSON frames pushed over a
+//! WebSocket at `/events`. Every frame names its topic:
+//!
+//! - `positions`: every live satellite's state and obstruction warnings,
+//!   sent every `position_interval_seconds`
+//! - `passes`: `ContactStarted` and `ContactEnded` simulation events
+//! - `warnings`: every other simulation event
+//!
+//! A client picks topics with `/events?topics=positions,passes` (all topics
+//! by default) and changes them later by sending
+//! `{"subscribe": ["warnings"], "unsubscribe": ["positions"]}`. A client
+//! too slow for the event stream skips the events it missed.
+
+use crate::error::{OrbitalMechanicsError, Result};
+use crate::orbit::GeodeticPosition;
+use crate::satellite_simulator::{
+    LiveSatellite, ObstructionWarning, SatelliteOperationalStatus, SatelliteSimulator,
+    SimulationEvent,
+};
+use crate::trace_targets;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+/// Stream settings shared by every connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsServerConfig {
+    pub position_interval_seconds: f64,
+}
+
+/// Kind of frame a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardTopic {
+    Positions,
+    Passes,
+    Warnings,
+}
+
+/// One satellite in a `positions` frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatellitePosition {
+    pub id: Uuid,
+    pub satellite_id: String,
+    pub name: String,
+    pub timestamp: DateTime<Utc>,
+    pub position_eci: [f64; 3],
+    pub velocity_eci: [f64; 3],
+    pub geodetic: GeodeticPosition,
+    pub operational_status: SatelliteOperationalStatus,
+    pub obstruction_warnings: Vec<ObstructionWarning>,
+}
+
+/// JSON frame sent to dashboard clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "topic", rename_all = "snake_case")]
+pub enum DashboardFrame {
+    Positions {
+        timestamp: DateTime<Utc>,
+        satellites: Vec<SatellitePosition>,
+    },
+    Passes {
+        event: SimulationEvent,
+    },
+    Warnings {
+        event: SimulationEvent,
+    },
+}
+
+/// Topic changes sent by a client
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubscriptionRequest {
+    #[serde(default)]
+    pub subscribe: Vec<DashboardTopic>,
+    #[serde(default)]
+    pub unsubscribe: Vec<DashboardTopic>,
+}
+
+#[derive(Clone)]
+struct DashboardState {
+    simulator: Arc<SatelliteSimulator>,
+    config: WsServerConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamQuery {
+    topics: Option<String>,
+}
+
+impl Default for WsServerConfig {
+    fn default() -> Self {
+        Self {
+            position_interval_seconds: 1.0,
+        }
+    }
+}
+
+impl WsServerConfig {
+    /// Reject a position interval tokio cannot tick at
+    pub fn validate(&self) -> Result<()> {
+        let interval = self.position_interval_seconds;
+        match std::time::Duration::try_from_secs_f64(interval) {
+            Ok(duration) if !duration.is_zero() => Ok(()),
+            _ => Err(OrbitalMechanicsError::config_error(format!(
+                "Dashboard position interval must be positive and finite, got {} s",
+                interval
+            ))),
+        }
+    }
+}
+
+impl DashboardTopic {
+    pub const ALL: [DashboardTopic; 3] = [
+        DashboardTopic::Positions,
+        DashboardTopic::Passes,
+        DashboardTopic::Warnings,
+    ];
+
+    /// Topics named in a comma-separated list
+    pub fn parse_list(list: &str) -> Result<HashSet<DashboardTopic>> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(DashboardTopic::from_str)
+            .collect()
+    }
+}
+
+impl FromStr for DashboardTopic {
+    type Err = OrbitalMechanicsError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "positions" => Ok(DashboardTopic::Positions),
+            "passes" => Ok(DashboardTopic::Passes),
+            "warnings" => Ok(DashboardTopic::Warnings),
+            _ => Err(OrbitalMechanicsError::config_error(format!(
+                "Unknown dashboard topic '{}'",
+                name
+            ))),
+        }
+    }
+}
+
+impl From<&LiveSatellite> for SatellitePosition {
+    fn from(satellite: &LiveSatellite) -> Self {
+        let state = &satellite.current_state;
+        Self {
+            id: satellite.id,
+            satellite_id: satellite.orbit.satellite_id.clone(),
+            name: satellite.name.clone(),
+            timestamp: state.timestamp,
+            position_eci: state.position_eci,
+            velocity_eci: state.velocity_eci,
+            geodetic: state.geodetic.clone(),
+            operational_status: satellite.operational_status.clone(),
+            obstruction_warnings: satellite.obstruction_warnings.clone(),
+        }
+    }
+}
+
+impl DashboardFrame {
+    /// Positions of `satellites`, ordered by satellite ID
+    pub fn positions(timestamp: DateTime<Utc>, satellites: &[LiveSatellite]) -> Self {
+        let mut satellites: Vec<SatellitePosition> =
+            satellites.iter().map(SatellitePosition::from).collect();
+        satellites.sort_by(|a, b| a.satellite_id.cmp(&b.satellite_id));
+        DashboardFrame::Positions {
+            timestamp,
+            satellites,
+        }
+    }
+
+    pub fn topic(&self) -> DashboardTopic {
+        match self {
+            DashboardFrame::Positions { .. } => DashboardTopic::Positions,
+            DashboardFrame::Passes { .. } => DashboardTopic::Passes,
+            DashboardFrame::Warnings { .. } => DashboardTopic::Warnings,
+        }
+    }
+}
+
+impl From<SimulationEvent> for DashboardFrame {
+    fn from(event: SimulationEvent) -> Self {
+        match event {
+            SimulationEvent::ContactStarted { .. } | SimulationEvent::ContactEnded { .. } => {
+                DashboardFrame::Passes { event }
+            }
+            _ => DashboardFrame::Warnings { event },
+        }
+    }
+}
+
+impl SubscriptionRequest {
+    pub fn apply(&self, topics: &mut HashSet<DashboardTopic>) {
+        topics.extend(self.subscribe.iter().copied());
+        for topic in &self.unsubscribe {
+            topics.remove(topic);
+        }
+    }
+}
+
+/// Routes serving the event stream of `simulator`
+pub fn router(simulator: Arc<SatelliteSimulator>, config: WsServerConfig) -> Result<Router> {
+    config.validate()?;
+    Ok(Router::new()
+        .route("/events", get(event_stream))
+        .with_state(DashboardState { simulator, config }))
+}
+
+/// Serve the event stream on `address` until the server fails
+pub async fn serve(
+    simulator: Arc<SatelliteSimulator>,
+    address: SocketAddr,
+    config: WsServerConfig,
+) -> Result<()> {
+    let router = router(simulator, config)?;
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    tracing::info!(
+        target: trace_targets::SIMULATOR,
+        %address,
+        "Dashboard event stream listening"
+    );
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn event_stream(
+    ws: WebSocketUpgrade,
+    Query(query): Query<StreamQuery>,
+    State(state): State<DashboardState>,
+) -> Response {
+    let topics = match query.topics.as_deref() {
+        Some(list) => match DashboardTopic::parse_list(list) {
+            Ok(topics) => topics,
+            Err(error) => return (StatusCode::BAD_REQUEST, error.to_string()).into_response(),
+        },
+        None => DashboardTopic::ALL.into_iter().collect(),
+    };
+    ws.on_upgrade(move |socket| stream_frames(socket, state, topics))
+}
+
+/// Push subscribed frames to one client until it disconnects
+async fn stream_frames(
+    mut socket: WebSocket,
+    state: DashboardState,
+    mut topics: HashSet<DashboardTopic>,
+) {
+    let mut events = state.simulator.subscribe_events();
+    let mut positions = tokio::time::interval(std::time::Duration::from_secs_f64(
+        state.config.position_interval_seconds,
+    ));
+
+    loop {
+        let frame = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<SubscriptionRequest>(&text) {
+                        Ok(request) => request.apply(&mut topics),
+                        Err(error) => tracing::debug!(
+                            target: trace_targets::SIMULATOR,
+                            %error,
+                            "Ignoring malformed dashboard subscription"
+                        ),
+                    }
+                    continue;
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) => DashboardFrame::from(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        target: trace_targets::SIMULATOR,
+                        skipped,
+                        "Dashboard client lagging; events skipped"
+                    );
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            },
+            _ = positions.tick(), if topics.contains(&DashboardTopic::Positions) => {
+                let satellites = state.simulator.get_all_satellites().await;
+                DashboardFrame::positions(state.simulator.simulation_time(), &satellites)
+            }
+        };
+        if !topics.contains(&frame.topic()) {
+            continue;
+        }
+        let json = match serde_json::to_string(&frame) {
+            Ok(json) => json,
+            Err(error) => {
+                tracing::warn!(
+                    target: trace_targets::SIMULATOR,
+                    %error,
+                    "Dropping unserializable dashboard frame"
+                );
+                continue;
+            }
+        };
+        if socket.send(Message::Text(json)).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagator::{create_propagator, PropagatorType};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_dashboard_frames_and_subscriptions() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let frame = DashboardFrame::from(SimulationEvent::ContactStarted {
+            satellite_id: Uuid::nil(),
+            timestamp,
+            station_id: "GS-001".to_string(),
+        });
+        assert_eq!(frame.topic(), DashboardTopic::Passes);
+        let json: serde_json::Value = serde_json::to_value(&frame).unwrap();
+        assert_eq!(json["topic"], "passes");
+        assert_eq!(json["event"]["ContactStarted"]["station_id"], "GS-001");
+        let warning = DashboardFrame::from(SimulationEvent::PlaybackFinished { timestamp });
+        assert_eq!(warning.topic(), DashboardTopic::Warnings);
+
+        let mut topics = DashboardTopic::parse_list("positions, passes").unwrap();
+        assert_eq!(topics.len(), 2);
+        assert!(DashboardTopic::parse_list("positions,weather").is_err());
+        let request: SubscriptionRequest =
+            serde_json::from_str(r#"{"subscribe": ["warnings"], "unsubscribe": ["positions"]}"#)
+                .unwrap();
+        request.apply(&mut topics);
+        assert_eq!(
+            topics,
+            HashSet::from([DashboardTopic::Passes, DashboardTopic::Warnings])
+        );
+    }
+
+    #[test]
+    fn test_router_rejects_bad_position_interval() {
+        let propagator = create_propagator(PropagatorType::Keplerian).unwrap();
+        let simulator = Arc::new(SatelliteSimulator::new(propagator));
+        for interval in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = WsServerConfig {
+                position_interval_seconds: interval,
+            };
+            assert!(router(simulator.clone(), config).is_err());
+        }
+        assert!(router(simulator, WsServerConfig::default()).is_ok());
+    }
+}
@@ -0,0 +1,50 @@
+//! `sx9-orbital` command-line tool as built with default features
+//!
+//! Only `update-data` needs the `online` feature; the rest of the tool works
+//! offline.
+
+use ctas7_orbital_mechanics::config::CustomGroundStation;
+use ctas7_orbital_mechanics::{save_constellation_config, Config};
+use std::process::Command;
+
+#[test]
+fn test_passes_without_online_feature() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("constellation.toml");
+    let mut config = Config::laserlight_fso_meo();
+    config.ground_station_config.use_predefined_stations = false;
+    config
+        .ground_station_config
+        .custom_stations
+        .push(CustomGroundStation {
+            station_id: "GS-HAWAII".to_string(),
+            name: "Mauna Kea".to_string(),
+            latitude_deg: 19.82,
+            longitude_deg: -155.47,
+            elevation_m: 4205.0,
+            capabilities: None,
+            availability: Default::default(),
+            mount: None,
+            timezone: Some(chrono_tz::Pacific::Honolulu),
+        });
+    save_constellation_config(&config, &path).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sx9-orbital"))
+        .arg("passes")
+        .arg(&path)
+        .args(["--start", "2024-03-20T00:00:00Z", "--hours", "12"])
+        .args(["--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let mut lines = csv.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("satellite_id,station_id,aos_utc"));
+    assert!(lines.any(|line| line.contains("GS-HAWAII")));
+}
@@ -0,0 +1,156 @@
+//! Golden-file regression tests for end-to-end scenarios
+//!
+//! Each scenario in `tests/scenarios` is run through `Scenario::run` and its
+//! key outputs are compared with `tests/golden/<scenario>.json`. Counts must
+//! match exactly; coverage and link margins within the tolerances below.
+//!
+//! After an intended change in results, regenerate the golden files with
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test --test golden_scenarios
+//! ```
+//!
+//! and review the diff before committing it.
+
+use ctas7_orbital_mechanics::{
+    load_scenario, CoverageGrid, DefaultPassScorer, ScoredPass, VisibilityWindow,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const COVERAGE_TOLERANCE_PERCENT: f64 = 0.5;
+const LINK_MARGIN_TOLERANCE_DB: f64 = 0.05;
+const CONTACT_TIME_TOLERANCE_MINUTES: f64 = 1.0;
+
+/// Outputs tracked for regressions
+#[derive(Debug, Serialize, Deserialize)]
+struct GoldenSummary {
+    pass_count: usize,
+    contact_count: usize,
+    contact_minutes: f64,
+    coverage_percent: f64,
+    min_link_margin_db: f64,
+    mean_link_margin_db: f64,
+}
+
+fn manifest_path(relative: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(relative)
+}
+
+fn run_scenario_summary(name: &str) -> GoldenSummary {
+    let scenario_path = manifest_path(&format!("tests/scenarios/{}.toml", name));
+    let mut scenario = load_scenario(&scenario_path).unwrap();
+    let output_directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    scenario.output_directory = output_directory.clone();
+    scenario.run(scenario_path.parent().unwrap()).unwrap();
+
+    let read = |file: &str| fs::read_to_string(output_directory.join(file)).unwrap();
+    let windows: Vec<VisibilityWindow> =
+        serde_json::from_str(&read("visibility_windows.json")).unwrap();
+    let contacts: Vec<ScoredPass> = serde_json::from_str(&read("contacts.json")).unwrap();
+    let grid: CoverageGrid = serde_json::from_str(&read("coverage_grid.json")).unwrap();
+
+    let scorer = DefaultPassScorer::new();
+    let margins: Vec<f64> = contacts
+        .iter()
+        .map(|contact| scorer.predicted_link_margin_db(&contact.window))
+        .collect();
+
+    GoldenSummary {
+        pass_count: windows.len(),
+        contact_count: contacts.len(),
+        contact_minutes: contacts
+            .iter()
+            .map(|c| c.window.duration_seconds)
+            .sum::<f64>()
+            / 60.0,
+        coverage_percent: grid.coverage_percent(1),
+        min_link_margin_db: margins.iter().copied().fold(f64::INFINITY, f64::min),
+        mean_link_margin_db: margins.iter().sum::<f64>() / margins.len().max(1) as f64,
+    }
+}
+
+fn check_golden(name: &str) {
+    let actual = run_scenario_summary(name);
+    let golden_path = manifest_path(&format!("tests/golden/{}.json", name));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let json = serde_json::to_string_pretty(&actual).unwrap();
+        fs::write(&golden_path, json + "\n").unwrap();
+        return;
+    }
+
+    let expected: GoldenSummary =
+        serde_json::from_str(&fs::read_to_string(&golden_path).unwrap()).unwrap();
+    let close = |actual: f64, expected: f64, tolerance: f64| (actual - expected).abs() <= tolerance;
+
+    assert_eq!(
+        actual.pass_count, expected.pass_count,
+        "{}: pass count",
+        name
+    );
+    assert_eq!(
+        actual.contact_count, expected.contact_count,
+        "{}: contact count",
+        name
+    );
+    assert!(
+        close(
+            actual.contact_minutes,
+            expected.contact_minutes,
+            CONTACT_TIME_TOLERANCE_MINUTES
+        ),
+        "{}: contact time {:.2} min, golden {:.2} min",
+        name,
+        actual.contact_minutes,
+        expected.contact_minutes
+    );
+    assert!(
+        close(
+            actual.coverage_percent,
+            expected.coverage_percent,
+            COVERAGE_TOLERANCE_PERCENT
+        ),
+        "{}: coverage {:.2}%, golden {:.2}%",
+        name,
+        actual.coverage_percent,
+        expected.coverage_percent
+    );
+    for (label, actual, expected) in [
+        (
+            "minimum",
+            actual.min_link_margin_db,
+            expected.min_link_margin_db,
+        ),
+        (
+            "mean",
+            actual.mean_link_margin_db,
+            expected.mean_link_margin_db,
+        ),
+    ] {
+        assert!(
+            close(actual, expected, LINK_MARGIN_TOLERANCE_DB),
+            "{}: {} link margin {:.3} dB, golden {:.3} dB",
+            name,
+            label,
+            actual,
+            expected
+        );
+    }
+}
+
+#[test]
+fn golden_meo_fso_ring() {
+    check_golden("meo_fso_ring");
+}
+
+#[test]
+fn golden_gps_like_constellation() {
+    check_golden("gps_like");
+}
+
+#[test]
+fn golden_single_leo_satellite() {
+    check_golden("single_leo");
+}